@@ -0,0 +1,270 @@
+//! Directory compression for `als batch`.
+//!
+//! Walks the input directory, compresses every file matching the pattern to
+//! an `.als` file at the same relative path under the output directory, and
+//! prints an aggregate summary. Files are spread over worker threads, and a
+//! failing file is reported at the end instead of stopping the batch.
+
+use crate::{compress_content, detect_format, format_bytes, map_als_error, Format};
+use als_compression::{AlsCompressor, CompressorConfig};
+use anyhow::{Context, Result};
+use log::{debug, info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// Extensions picked up when neither a pattern nor a format is given.
+const AUTO_EXTENSIONS: [&str; 5] = ["csv", "tsv", "json", "ndjson", "jsonl"];
+
+/// One file to compress.
+struct Job {
+    input: PathBuf,
+    output: PathBuf,
+    relative: String,
+}
+
+/// Sizes of one compressed file.
+struct Compressed {
+    input_size: usize,
+    output_size: usize,
+}
+
+/// Execute the batch command
+pub fn batch_command(
+    input_dir: &Path,
+    output_dir: &Path,
+    format: Format,
+    pattern: Option<&str>,
+    config: CompressorConfig,
+    quiet: bool,
+) -> Result<()> {
+    if !input_dir.is_dir() {
+        anyhow::bail!("Input directory not found: {}", input_dir.display());
+    }
+
+    let mut files = Vec::new();
+    collect_files(input_dir, output_dir, &mut files)
+        .with_context(|| format!("Failed to read input directory: {}", input_dir.display()))?;
+    files.sort();
+
+    let mut failures = Vec::new();
+    let mut jobs: Vec<Job> = Vec::new();
+    for input in files {
+        let relative = relative_path(input_dir, &input);
+        if !matches_pattern(pattern, format, &relative) {
+            continue;
+        }
+        let output = output_dir.join(&relative).with_extension("als");
+        // Inputs differing only by extension would share one output
+        if let Some(other) = jobs.iter().find(|job| job.output == output) {
+            let message = format!("output {} is already written for {}", output.display(), other.relative);
+            failures.push((relative, message));
+            continue;
+        }
+        jobs.push(Job { input, output, relative });
+    }
+
+    if jobs.is_empty() && failures.is_empty() {
+        warn!("No files in {} match the pattern", input_dir.display());
+    }
+
+    let workers = match config.parallelism {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+    .clamp(1, jobs.len().max(1));
+    info!("Compressing {} files on {} worker threads", jobs.len(), workers);
+
+    // Each worker compresses whole files, so a file gets a single thread
+    let config = if workers > 1 { config.with_parallelism(1) } else { config };
+    let compressor = AlsCompressor::with_config(config);
+
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::with_capacity(jobs.len()));
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else { break };
+                let result = compress_file(&compressor, job, format);
+                results.lock().unwrap_or_else(|e| e.into_inner()).push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut compressed = Vec::new();
+    for (index, result) in results {
+        match result {
+            Ok(sizes) => compressed.push(sizes),
+            Err(e) => failures.push((jobs[index].relative.clone(), format!("{:#}", e))),
+        }
+    }
+
+    if !quiet {
+        print_summary(&compressed, failures.len());
+    }
+
+    if !failures.is_empty() {
+        for (file, message) in &failures {
+            eprintln!("✗ {}: {}", file, message);
+        }
+        anyhow::bail!("{} of {} files failed to compress", failures.len(), compressed.len() + failures.len());
+    }
+    Ok(())
+}
+
+/// Recursively collect the files under `dir`, skipping `output_dir`.
+fn collect_files(dir: &Path, output_dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            // The output directory may live inside the input directory
+            if !same_path(&path, output_dir) {
+                collect_files(&path, output_dir, files)?;
+            }
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Whether two paths name the same existing directory.
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// The path of `file` relative to `dir`, with `/` separators.
+fn relative_path(dir: &Path, file: &Path) -> String {
+    let relative = file.strip_prefix(dir).unwrap_or(file);
+    relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Check a relative path against the pattern, or the format's extensions.
+///
+/// A pattern without `/` is matched against the file name only.
+fn matches_pattern(pattern: Option<&str>, format: Format, relative: &str) -> bool {
+    let name = relative.rsplit('/').next().unwrap_or(relative);
+    match pattern {
+        Some(pattern) if pattern.contains('/') => glob_match(pattern.as_bytes(), relative.as_bytes()),
+        Some(pattern) => glob_match(pattern.as_bytes(), name.as_bytes()),
+        None => {
+            let extension = name.rsplit_once('.').map(|(_, extension)| extension);
+            match format {
+                Format::Auto => extension.is_some_and(|extension| AUTO_EXTENSIONS.contains(&extension)),
+                Format::Ndjson => matches!(extension, Some("ndjson" | "jsonl")),
+                _ => extension == Some(format.as_str()),
+            }
+        }
+    }
+}
+
+/// Match `text` against a glob supporting `*`, `**`, and `?`.
+///
+/// `*` and `?` never match `/`, while `**` matches across directories.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern {
+        [] => text.is_empty(),
+        [b'*', b'*', b'/', rest @ ..] => {
+            // Zero or more whole directories
+            glob_match(rest, text)
+                || text.iter().enumerate().any(|(i, &c)| c == b'/' && glob_match(rest, &text[i + 1..]))
+        }
+        [b'*', b'*', rest @ ..] => (0..=text.len()).any(|i| glob_match(rest, &text[i..])),
+        [b'*', rest @ ..] => {
+            let segment = text.iter().position(|&c| c == b'/').unwrap_or(text.len());
+            (0..=segment).any(|i| glob_match(rest, &text[i..]))
+        }
+        [b'?', rest @ ..] => matches!(text, [c, tail @ ..] if *c != b'/' && glob_match(rest, tail)),
+        [c, rest @ ..] => matches!(text, [t, tail @ ..] if t == c && glob_match(rest, tail)),
+    }
+}
+
+/// Compress one file and write its `.als` output.
+fn compress_file(compressor: &AlsCompressor, job: &Job, format: Format) -> Result<Compressed> {
+    let content = fs::read_to_string(&job.input)
+        .with_context(|| format!("Failed to read input file: {}", job.input.display()))?;
+
+    let format = match format {
+        Format::Auto => detect_format(&job.relative, &content),
+        _ => format,
+    };
+    debug!("Compressing {} as {}", job.relative, format.as_str());
+
+    let compressed = if content.is_empty() {
+        Vec::new()
+    } else {
+        let text = compress_content(compressor, format, &content)?;
+        compressor
+            .apply_post_codec(&text)
+            .map_err(|e| map_als_error(e, "Output encoding"))?
+    };
+
+    if let Some(parent) = job.output.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create output directory: {}", parent.display()))?;
+    }
+    fs::write(&job.output, &compressed)
+        .with_context(|| format!("Failed to write output file: {}", job.output.display()))?;
+
+    Ok(Compressed {
+        input_size: content.len(),
+        output_size: compressed.len(),
+    })
+}
+
+/// Print the totals over all compressed files.
+fn print_summary(compressed: &[Compressed], failed: usize) {
+    let input_size: usize = compressed.iter().map(|file| file.input_size).sum();
+    let output_size: usize = compressed.iter().map(|file| file.output_size).sum();
+    let ratios: Vec<f64> = compressed
+        .iter()
+        .filter(|file| file.output_size > 0)
+        .map(|file| file.input_size as f64 / file.output_size as f64)
+        .collect();
+    let average = if ratios.is_empty() { 0.0 } else { ratios.iter().sum::<f64>() / ratios.len() as f64 };
+
+    eprintln!("✓ Batch compression complete");
+    eprintln!("  Files:       {} compressed, {} failed", compressed.len(), failed);
+    eprintln!("  Input:       {}", format_bytes(input_size));
+    eprintln!("  Output:      {}", format_bytes(output_size));
+    eprintln!("  Avg ratio:   {:.2}x", average);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match(b"*.csv", b"a.csv"));
+        assert!(!glob_match(b"*.csv", b"a.tsv"));
+        assert!(!glob_match(b"*.csv", b"sub/a.csv"));
+        assert!(glob_match(b"**/*.csv", b"sub/deep/a.csv"));
+        assert!(glob_match(b"**/*.csv", b"a.csv"));
+        assert!(!glob_match(b"**/x.csv", b"sub/ax.csv"));
+        assert!(glob_match(b"sub/?.csv", b"sub/a.csv"));
+        assert!(!glob_match(b"sub/?.csv", b"sub/ab.csv"));
+    }
+
+    #[test]
+    fn test_matches_pattern_defaults_to_format_extensions() {
+        assert!(matches_pattern(None, Format::Auto, "sub/a.jsonl"));
+        assert!(!matches_pattern(None, Format::Auto, "README.md"));
+        assert!(matches_pattern(None, Format::Csv, "a.csv"));
+        assert!(!matches_pattern(None, Format::Csv, "a.json"));
+        assert!(matches_pattern(Some("*.txt"), Format::Csv, "sub/a.txt"));
+    }
+}
@@ -0,0 +1,152 @@
+//! Throughput benchmark for `als bench`.
+//!
+//! Runs compression and decompression of the input once per SIMD level the
+//! CPU supports, forcing each level through `SimdConfig::with_forced_level`.
+
+use crate::{detect_format, format_bytes, map_als_error, parse_input, read_input, Format};
+use als_compression::{
+    AlsCompressor, AlsParser, AlsSerializer, CompressorConfig, CpuFeatures, ParserConfig,
+    SimdLevel,
+};
+use anyhow::Result;
+use log::{info, warn};
+use std::time::{Duration, Instant};
+
+/// Timings and output of one SIMD level.
+struct LevelResult {
+    level: SimdLevel,
+    compress: Duration,
+    decompress: Duration,
+    compressed: String,
+}
+
+/// Execute the bench command
+pub fn bench_command(
+    input: &str,
+    format: Format,
+    iterations: u32,
+    config: CompressorConfig,
+) -> Result<()> {
+    info!("Benchmarking {} with {} iterations per SIMD level", input, iterations);
+
+    let input_data = read_input(input)?;
+    if input_data.is_empty() {
+        warn!("Input is empty");
+        return Ok(());
+    }
+
+    let detected_format = match format {
+        Format::Auto => {
+            let detected = detect_format(input, &input_data);
+            info!("Auto-detected format: {}", detected.as_str());
+            detected
+        }
+        _ => format,
+    };
+
+    let features = CpuFeatures::detect();
+    let mut results = Vec::new();
+    let mut skipped = Vec::new();
+
+    for level in SimdLevel::ALL {
+        if !features.supports(level) {
+            skipped.push(level);
+            continue;
+        }
+        results.push(bench_level(level, &input_data, detected_format, iterations, &config)?);
+    }
+
+    // Every level must produce the same document
+    if let Some(reference) = results.iter().find(|r| r.level == SimdLevel::Scalar) {
+        for result in &results {
+            if result.compressed != reference.compressed {
+                warn!("{} output differs from the scalar output", result.level);
+            }
+        }
+    }
+
+    print_results(&results, &skipped, input_data.len(), iterations);
+    Ok(())
+}
+
+/// Compress and decompress the input `iterations` times at one SIMD level.
+fn bench_level(
+    level: SimdLevel,
+    input_data: &str,
+    format: Format,
+    iterations: u32,
+    config: &CompressorConfig,
+) -> Result<LevelResult> {
+    let simd_config = config.simd_config.with_forced_level(level);
+    let compressor = AlsCompressor::with_config(config.clone().with_simd_config(simd_config));
+    let parser = AlsParser::with_config(
+        ParserConfig::new()
+            .with_simd_config(simd_config)
+            .with_csv_config(config.csv_config),
+    );
+    let serializer = AlsSerializer::new();
+
+    let mut compressed = String::new();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let data = parse_input(&compressor, format, input_data)?;
+        let doc = compressor
+            .compress(&data)
+            .map_err(|e| map_als_error(e, "Compression"))?;
+        compressed = serializer.serialize(&doc);
+    }
+    let compress = start.elapsed();
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        let doc = parser
+            .parse(&compressed)
+            .map_err(|e| map_als_error(e, "ALS parsing"))?;
+        parser
+            .expand(&doc)
+            .map_err(|e| map_als_error(e, "ALS expansion"))?;
+    }
+    let decompress = start.elapsed();
+
+    Ok(LevelResult {
+        level,
+        compress,
+        decompress,
+        compressed,
+    })
+}
+
+/// Print one row per SIMD level with throughput and compression ratio.
+fn print_results(results: &[LevelResult], skipped: &[SimdLevel], input_size: usize, iterations: u32) {
+    let total_mb = (input_size as f64 * iterations as f64) / 1_048_576.0;
+    let throughput = |duration: Duration| {
+        let seconds = duration.as_secs_f64();
+        if seconds > 0.0 {
+            format!("{:.2} MB/s", total_mb / seconds)
+        } else {
+            "n/a".to_string()
+        }
+    };
+
+    println!("=== Benchmark ===\n");
+    println!("Input: {} ({} iterations per level)\n", format_bytes(input_size), iterations);
+    println!(
+        "{:<8}  {:>14}  {:>14}  {:>8}",
+        "Level", "Compress", "Decompress", "Ratio"
+    );
+
+    for result in results {
+        let ratio = input_size as f64 / result.compressed.len().max(1) as f64;
+        println!(
+            "{:<8}  {:>14}  {:>14}  {:>8}",
+            result.level.to_string(),
+            throughput(result.compress),
+            throughput(result.decompress),
+            format!("{:.2}x", ratio)
+        );
+    }
+
+    for level in skipped {
+        println!("{:<8}  skipped (not supported by this CPU)", level.to_string());
+    }
+}
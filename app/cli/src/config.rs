@@ -0,0 +1,306 @@
+//! Config file loading for the CLI.
+//!
+//! Config files may be written in TOML or JSON. Every key is optional; keys
+//! that are not present keep their `CompressorConfig::default()` value.
+//!
+//! ```toml
+//! ctx_fallback_threshold = 1.5
+//! min_pattern_length = 4
+//! max_dictionary_entries = 1024
+//! parallelism = 2
+//!
+//! [simd]
+//! enable_avx512 = false
+//! ```
+
+use als_compression::{CompressorConfig, JsonColumnOrder, SimdConfig};
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+/// Config file syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+/// Load a `CompressorConfig` from a TOML or JSON file.
+pub fn load_config(path: &Path) -> Result<CompressorConfig> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+
+    parse_config(&content, detect_config_format(path, &content))
+        .with_context(|| format!("Invalid config file: {}", path.display()))
+}
+
+/// Detect the config syntax from the file extension, falling back to content sniffing.
+fn detect_config_format(path: &Path, content: &str) -> ConfigFormat {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("toml") => ConfigFormat::Toml,
+        Some("json") => ConfigFormat::Json,
+        _ => {
+            if content.trim_start().starts_with('{') {
+                ConfigFormat::Json
+            } else {
+                ConfigFormat::Toml
+            }
+        }
+    }
+}
+
+/// Parse config text in the given syntax.
+fn parse_config(content: &str, format: ConfigFormat) -> Result<CompressorConfig> {
+    let value: Value = match format {
+        ConfigFormat::Toml => toml::from_str(content).context("Failed to parse TOML config")?,
+        ConfigFormat::Json => {
+            serde_json::from_str(content).context("Failed to parse JSON config")?
+        }
+    };
+
+    match value {
+        Value::Object(map) => apply_config(CompressorConfig::default(), &map),
+        _ => bail!("Config must be a table of key/value pairs"),
+    }
+}
+
+/// Apply each key of a config table onto `config` via its builder method.
+fn apply_config(mut config: CompressorConfig, map: &Map<String, Value>) -> Result<CompressorConfig> {
+    for (key, value) in map {
+        config = match key.as_str() {
+            "ctx_fallback_threshold" => {
+                let threshold = value
+                    .as_f64()
+                    .ok_or_else(|| anyhow!("'{}' must be a number", key))?;
+                if !threshold.is_finite() || threshold < 1.0 {
+                    bail!("'{}' must be >= 1.0, got {}", key, threshold);
+                }
+                config.with_ctx_fallback_threshold(threshold)
+            }
+            "hashmap_threshold" => config.with_hashmap_threshold(expect_usize(key, value, 0)?),
+            "min_pattern_length" => config.with_min_pattern_length(expect_usize(key, value, 1)?),
+            "max_toggle_cycle_length" => {
+                config.with_max_toggle_cycle_length(expect_usize(key, value, 2)?)
+            }
+            "parallelism" => config.with_parallelism(expect_usize(key, value, 0)?),
+            "max_range_expansion" => {
+                config.with_max_range_expansion(expect_usize(key, value, 1)?)
+            }
+            "max_dictionary_entries" => {
+                config.with_max_dictionary_entries(expect_usize(key, value, 1)?)
+            }
+            "max_input_size" => config.with_max_input_size(expect_usize(key, value, 1)?),
+            "max_input_bytes" => config.with_max_input_bytes(expect_usize(key, value, 1)?),
+            "max_cells" => config.with_max_cells(expect_usize(key, value, 1)?),
+            "case_insensitive_keys" => {
+                let enabled = value
+                    .as_bool()
+                    .ok_or_else(|| anyhow!("'{}' must be a boolean", key))?;
+                config.with_case_insensitive_keys(enabled)
+            }
+            "json_column_order" => {
+                let order = match value.as_str() {
+                    Some("first_seen") => JsonColumnOrder::FirstSeen,
+                    Some("sorted") => JsonColumnOrder::Sorted,
+                    _ => bail!("'{}' must be \"first_seen\" or \"sorted\"", key),
+                };
+                config.with_json_column_order(order)
+            }
+            "simd" => {
+                let table = value
+                    .as_object()
+                    .ok_or_else(|| anyhow!("'{}' must be a table", key))?;
+                let simd = apply_simd_config(config.simd_config, table)?;
+                config.with_simd_config(simd)
+            }
+            _ => bail!("Unknown config key '{}'", key),
+        };
+    }
+
+    Ok(config)
+}
+
+/// Apply the keys of the `[simd]` table onto `simd`.
+fn apply_simd_config(mut simd: SimdConfig, map: &Map<String, Value>) -> Result<SimdConfig> {
+    for (key, value) in map {
+        let enable = value
+            .as_bool()
+            .ok_or_else(|| anyhow!("'simd.{}' must be a boolean", key))?;
+        simd = match key.as_str() {
+            "enable_avx512" => simd.with_avx512(enable),
+            "enable_avx2" => simd.with_avx2(enable),
+            "enable_neon" => simd.with_neon(enable),
+            _ => bail!("Unknown config key 'simd.{}'", key),
+        };
+    }
+
+    Ok(simd)
+}
+
+/// Read a non-negative integer of at least `min` from a config value.
+fn expect_usize(key: &str, value: &Value, min: usize) -> Result<usize> {
+    let n = value
+        .as_u64()
+        .ok_or_else(|| anyhow!("'{}' must be a non-negative integer", key))?;
+    let n = usize::try_from(n).map_err(|_| anyhow!("'{}' is out of range: {}", key, n))?;
+    if n < min {
+        bail!("'{}' must be >= {}, got {}", key, min, n);
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use als_compression::AlsCompressor;
+    use std::io::Write;
+
+    fn write_temp(suffix: &str, content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    fn assert_custom_config(config: &CompressorConfig) {
+        assert_eq!(config.ctx_fallback_threshold, 1.5);
+        assert_eq!(config.hashmap_threshold, 500);
+        assert_eq!(config.min_pattern_length, 4);
+        assert_eq!(config.max_toggle_cycle_length, 12);
+        assert_eq!(config.parallelism, 2);
+        assert_eq!(config.max_range_expansion, 1000);
+        assert_eq!(config.max_dictionary_entries, 128);
+        assert_eq!(config.max_input_size, 4096);
+        assert_eq!(config.max_input_bytes, Some(8192));
+        assert_eq!(config.max_cells, Some(500));
+        assert!(config.case_insensitive_keys);
+        assert_eq!(config.json_column_order, JsonColumnOrder::Sorted);
+        assert!(!config.simd_config.enable_avx512);
+        assert!(config.simd_config.enable_avx2);
+        assert!(!config.simd_config.enable_neon);
+    }
+
+    #[test]
+    fn test_load_toml_config() {
+        let file = write_temp(
+            ".toml",
+            r#"
+ctx_fallback_threshold = 1.5
+hashmap_threshold = 500
+min_pattern_length = 4
+max_toggle_cycle_length = 12
+parallelism = 2
+max_range_expansion = 1000
+max_dictionary_entries = 128
+max_input_size = 4096
+max_input_bytes = 8192
+max_cells = 500
+case_insensitive_keys = true
+json_column_order = "sorted"
+
+[simd]
+enable_avx512 = false
+enable_avx2 = true
+enable_neon = false
+"#,
+        );
+
+        let config = load_config(file.path()).unwrap();
+        assert_custom_config(&config);
+
+        // Every field reaches the compressor
+        let compressor = AlsCompressor::with_config(config);
+        assert_custom_config(compressor.config());
+    }
+
+    #[test]
+    fn test_load_json_config() {
+        let file = write_temp(
+            ".json",
+            r#"{
+                "ctx_fallback_threshold": 1.5,
+                "hashmap_threshold": 500,
+                "min_pattern_length": 4,
+                "max_toggle_cycle_length": 12,
+                "parallelism": 2,
+                "max_range_expansion": 1000,
+                "max_dictionary_entries": 128,
+                "max_input_size": 4096,
+                "max_input_bytes": 8192,
+                "max_cells": 500,
+                "case_insensitive_keys": true,
+                "json_column_order": "sorted",
+                "simd": {"enable_avx512": false, "enable_avx2": true, "enable_neon": false}
+            }"#,
+        );
+
+        let config = load_config(file.path()).unwrap();
+        let compressor = AlsCompressor::with_config(config);
+        assert_custom_config(compressor.config());
+    }
+
+    #[test]
+    fn test_missing_keys_keep_defaults() {
+        let file = write_temp(".toml", "min_pattern_length = 5\n");
+
+        let config = load_config(file.path()).unwrap();
+        let defaults = CompressorConfig::default();
+        assert_eq!(config.min_pattern_length, 5);
+        assert_eq!(config.ctx_fallback_threshold, defaults.ctx_fallback_threshold);
+        assert_eq!(config.max_dictionary_entries, defaults.max_dictionary_entries);
+        assert_eq!(config.max_input_size, defaults.max_input_size);
+        assert_eq!(config.max_cells, None);
+    }
+
+    #[test]
+    fn test_format_sniffing_without_extension() {
+        let json = write_temp(".conf", r#"{"parallelism": 3}"#);
+        assert_eq!(load_config(json.path()).unwrap().parallelism, 3);
+
+        let toml = write_temp(".conf", "parallelism = 4\n");
+        assert_eq!(load_config(toml.path()).unwrap().parallelism, 4);
+    }
+
+    #[test]
+    fn test_unknown_key_is_reported() {
+        let file = write_temp(".toml", "max_dict_entries = 10\n");
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("max_dict_entries"), "{}", err);
+
+        let file = write_temp(".json", r#"{"simd": {"enable_sse": true}}"#);
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("simd.enable_sse"), "{}", err);
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_rejected() {
+        let file = write_temp(".toml", "ctx_fallback_threshold = 0.5\n");
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("ctx_fallback_threshold"), "{}", err);
+
+        let file = write_temp(".json", r#"{"max_dictionary_entries": 0}"#);
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("max_dictionary_entries"), "{}", err);
+
+        let file = write_temp(".toml", "max_toggle_cycle_length = 1\n");
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("max_toggle_cycle_length"), "{}", err);
+
+        let file = write_temp(".json", r#"{"parallelism": -1}"#);
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("parallelism"), "{}", err);
+
+        let file = write_temp(".toml", "case_insensitive_keys = \"yes\"\n");
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("case_insensitive_keys"), "{}", err);
+
+        let file = write_temp(".toml", "json_column_order = \"random\"\n");
+        let err = format!("{:#}", load_config(file.path()).unwrap_err());
+        assert!(err.contains("json_column_order"), "{}", err);
+    }
+}
@@ -0,0 +1,285 @@
+//! Semantic comparison for `als diff`.
+//!
+//! Both files are decoded into `TabularData`, so an ALS document and the
+//! source it was compressed from compare equal even though their text
+//! differs. Columns are matched by name and cells are compared row by row.
+
+use crate::{
+    detect_format, map_als_error, parse_input, read_input, values_equivalent, Format, Mismatch,
+};
+use als_compression::{AlsCompressor, AlsParser, CompressorConfig, CsvConfig, ParserConfig, TabularData, Value};
+use anyhow::Result;
+use log::info;
+
+/// Differences found between two tables.
+#[derive(Debug, Default)]
+struct TableDiff {
+    /// Columns present only in the left table
+    left_only: Vec<String>,
+    /// Columns present only in the right table
+    right_only: Vec<String>,
+    /// Both tables have the same columns in a different order
+    reordered: bool,
+    /// Row counts of the left and right table, if they differ
+    row_counts: Option<(usize, usize)>,
+    /// First differing cells, in row-major order
+    cells: Vec<Mismatch>,
+    /// Total number of differing cells
+    differing_cells: usize,
+}
+
+impl TableDiff {
+    fn is_empty(&self) -> bool {
+        self.left_only.is_empty()
+            && self.right_only.is_empty()
+            && !self.reordered
+            && self.row_counts.is_none()
+            && self.differing_cells == 0
+    }
+}
+
+/// Execute the diff command
+pub fn diff_command(
+    left: &str,
+    right: &str,
+    limit: usize,
+    strict: bool,
+    csv_config: CsvConfig,
+    quiet: bool,
+) -> Result<()> {
+    info!("Comparing {} with {}", left, right);
+
+    let left_data = load_table(left, &csv_config)?;
+    let right_data = load_table(right, &csv_config)?;
+    let diff = diff_tables(&left_data, &right_data, limit, strict);
+
+    if diff.is_empty() {
+        if !quiet {
+            println!(
+                "Files are equal: {} rows, {} columns",
+                left_data.row_count,
+                left_data.column_count()
+            );
+        }
+        return Ok(());
+    }
+
+    if !quiet {
+        print_diff(&diff);
+    }
+    anyhow::bail!("{} and {} differ", left, right);
+}
+
+/// Read a file and decode it into tabular data, auto-detecting its format
+fn load_table(input: &str, csv_config: &CsvConfig) -> Result<TabularData<'static>> {
+    let content = read_input(input)?;
+    let format = detect_format(input, &content);
+    info!("Auto-detected format of {}: {}", input, format.as_str());
+
+    match format {
+        Format::Als => {
+            let parser = AlsParser::with_config(ParserConfig::new().with_csv_config(*csv_config));
+            let doc = parser
+                .parse(&content)
+                .map_err(|e| map_als_error(e, "ALS parsing"))?;
+            parser
+                .expand_typed(&doc)
+                .map_err(|e| map_als_error(e, "ALS expansion"))
+        }
+        _ => {
+            let compressor = AlsCompressor::with_config(CompressorConfig::new().with_csv_config(*csv_config));
+            parse_input(&compressor, format, &content)
+        }
+    }
+}
+
+/// Compare two tables, recording at most `limit` differing cells
+fn diff_tables(left: &TabularData, right: &TabularData, limit: usize, strict: bool) -> TableDiff {
+    let left_names = left.column_names();
+    let right_names = right.column_names();
+
+    let mut diff = TableDiff {
+        left_only: left_names
+            .iter()
+            .filter(|name| !right_names.contains(name))
+            .map(|name| name.to_string())
+            .collect(),
+        right_only: right_names
+            .iter()
+            .filter(|name| !left_names.contains(name))
+            .map(|name| name.to_string())
+            .collect(),
+        ..TableDiff::default()
+    };
+    diff.reordered =
+        diff.left_only.is_empty() && diff.right_only.is_empty() && left_names != right_names;
+
+    if left.row_count != right.row_count {
+        diff.row_counts = Some((left.row_count, right.row_count));
+    }
+
+    // Compare the columns both tables share, over the rows both tables have
+    let shared: Vec<_> = left
+        .columns
+        .iter()
+        .filter_map(|column| {
+            right
+                .get_column_by_name(&column.name)
+                .map(|other| (column, other))
+        })
+        .collect();
+
+    for row in 0..left.row_count.min(right.row_count) {
+        for (left_col, right_col) in &shared {
+            let (Some(expected), Some(found)) =
+                (left_col.values.get(row), right_col.values.get(row))
+            else {
+                continue;
+            };
+            if cells_equal(expected, found, strict) {
+                continue;
+            }
+            diff.differing_cells += 1;
+            if diff.cells.len() < limit {
+                diff.cells.push(Mismatch {
+                    row,
+                    column: left_col.name.to_string(),
+                    expected: render(expected),
+                    found: render(found),
+                });
+            }
+        }
+    }
+
+    diff
+}
+
+/// Compare two cells, treating `3` and `3.0` as equal unless `strict`
+fn cells_equal(a: &Value, b: &Value, strict: bool) -> bool {
+    if strict {
+        a == b
+    } else {
+        values_equivalent(a, b)
+    }
+}
+
+/// Render a cell so that `--strict` differences stay visible (`3` vs `3.0`)
+fn render(value: &Value) -> String {
+    match value {
+        Value::Float(f) if f.is_finite() && f.fract() == 0.0 => format!("{:.1}", f),
+        _ => value.to_string_repr().into_owned(),
+    }
+}
+
+/// Print a concise summary of the differences
+fn print_diff(diff: &TableDiff) {
+    println!("Files differ");
+    if !diff.left_only.is_empty() {
+        println!("  Columns only in left:  {}", diff.left_only.join(", "));
+    }
+    if !diff.right_only.is_empty() {
+        println!("  Columns only in right: {}", diff.right_only.join(", "));
+    }
+    if diff.reordered {
+        println!("  Column order differs");
+    }
+    if let Some((left, right)) = diff.row_counts {
+        println!("  Rows:        {} (left), {} (right)", left, right);
+    }
+    if diff.differing_cells > 0 {
+        println!("  Differing cells: {}", diff.differing_cells);
+        for cell in &diff.cells {
+            println!(
+                "    row {}, column '{}': '{}' != '{}'",
+                cell.row + 1,
+                cell.column,
+                cell.expected,
+                cell.found
+            );
+        }
+        if diff.differing_cells > diff.cells.len() {
+            println!("    ... and {} more", diff.differing_cells - diff.cells.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use als_compression::Column;
+    use std::borrow::Cow;
+
+    fn table(columns: Vec<(&str, Vec<Value<'static>>)>) -> TabularData<'static> {
+        let mut data = TabularData::new();
+        for (name, values) in columns {
+            data.add_column(Column::new(Cow::Owned(name.to_string()), values));
+        }
+        data
+    }
+
+    #[test]
+    fn test_equal_tables() {
+        let left = table(vec![("id", vec![Value::Integer(1), Value::Integer(2)])]);
+        let diff = diff_tables(&left, &left.clone(), 10, false);
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    fn test_integer_and_float_equal_unless_strict() {
+        let left = table(vec![("n", vec![Value::Integer(3)])]);
+        let right = table(vec![("n", vec![Value::Float(3.0)])]);
+
+        assert!(diff_tables(&left, &right, 10, false).is_empty());
+
+        let diff = diff_tables(&left, &right, 10, true);
+        assert_eq!(diff.differing_cells, 1);
+        assert_eq!(diff.cells[0].expected, "3");
+        assert_eq!(diff.cells[0].found, "3.0");
+    }
+
+    #[test]
+    fn test_schema_and_row_count_differences() {
+        let left = table(vec![
+            ("id", vec![Value::Integer(1), Value::Integer(2)]),
+            ("a", vec![Value::Null, Value::Null]),
+        ]);
+        let right = table(vec![
+            ("id", vec![Value::Integer(1)]),
+            ("b", vec![Value::Null]),
+        ]);
+
+        let diff = diff_tables(&left, &right, 10, false);
+        assert_eq!(diff.left_only, vec!["a"]);
+        assert_eq!(diff.right_only, vec!["b"]);
+        assert!(!diff.reordered);
+        assert_eq!(diff.row_counts, Some((2, 1)));
+        assert_eq!(diff.differing_cells, 0);
+    }
+
+    #[test]
+    fn test_reordered_columns_compare_by_name() {
+        let left = table(vec![
+            ("a", vec![Value::Integer(1)]),
+            ("b", vec![Value::Integer(2)]),
+        ]);
+        let right = table(vec![
+            ("b", vec![Value::Integer(2)]),
+            ("a", vec![Value::Integer(1)]),
+        ]);
+
+        let diff = diff_tables(&left, &right, 10, false);
+        assert!(diff.reordered);
+        assert_eq!(diff.differing_cells, 0);
+    }
+
+    #[test]
+    fn test_limit_caps_listed_cells() {
+        let left = table(vec![("n", (0..5).map(Value::Integer).collect())]);
+        let right = table(vec![("n", (10..15).map(Value::Integer).collect())]);
+
+        let diff = diff_tables(&left, &right, 2, false);
+        assert_eq!(diff.differing_cells, 5);
+        assert_eq!(diff.cells.len(), 2);
+        assert_eq!(diff.cells[1].row, 1);
+    }
+}
@@ -1,13 +1,28 @@
-use als_compression::{AlsCompressor, AlsError, AlsParser, CompressorConfig};
+use als_compression::{
+    AlsCompressor, AlsError, AlsParser, AlsSerializer, Codec, ColumnType, CompressorConfig, CsvConfig,
+    ParserConfig, PatternType, TabularData, Value, ValueStats,
+};
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use indicatif::{ProgressBar, ProgressStyle};
 use log::{debug, error, info, warn};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::PathBuf;
 use std::time::Instant;
 
+mod batch;
+mod bench;
+mod config;
+mod diff;
+mod progress;
+mod report;
+
+use config::load_config;
+use report::print_compression_report;
+
 /// ALS (Adaptive Logic Stream) compression tool for structured data
 #[derive(Parser)]
 #[command(name = "als")]
@@ -26,6 +41,14 @@ struct Cli {
     #[arg(short, long, global = true, value_name = "FILE")]
     config: Option<PathBuf>,
 
+    /// Field delimiter for CSV input and output (a single character, e.g. ';')
+    #[arg(long, global = true, value_name = "CHAR", value_parser = parse_csv_delimiter)]
+    csv_delimiter: Option<u8>,
+
+    /// Worker threads for compression and parsing (0 = auto-detect)
+    #[arg(long, global = true, value_name = "N", value_parser = parse_threads)]
+    threads: Option<usize>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -35,10 +58,20 @@ struct Cli {
 enum Format {
     /// CSV (Comma-Separated Values)
     Csv,
+    /// TSV (Tab-Separated Values)
+    Tsv,
     /// JSON (JavaScript Object Notation)
     Json,
+    /// NDJSON (newline-delimited JSON, one object per line)
+    Ndjson,
     /// ALS (Adaptive Logic Stream)
     Als,
+    /// Apache Parquet (decompress output only)
+    Parquet,
+    /// Apache Avro object container file (decompress output only)
+    Avro,
+    /// XML document with one element per row (decompress output only)
+    Xml,
     /// Auto-detect format from file extension or content
     Auto,
 }
@@ -47,16 +80,42 @@ impl Format {
     fn as_str(&self) -> &'static str {
         match self {
             Format::Csv => "csv",
+            Format::Tsv => "tsv",
             Format::Json => "json",
+            Format::Ndjson => "ndjson",
             Format::Als => "als",
+            Format::Parquet => "parquet",
+            Format::Avro => "avro",
+            Format::Xml => "xml",
             Format::Auto => "auto",
         }
     }
 }
 
+/// Codecs that can wrap compressed output
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum OutputCodec {
+    /// Plain ALS text
+    None,
+    /// Gzip
+    Gzip,
+    /// Zstandard
+    Zstd,
+}
+
+impl From<OutputCodec> for Codec {
+    fn from(codec: OutputCodec) -> Self {
+        match codec {
+            OutputCodec::None => Codec::None,
+            OutputCodec::Gzip => Codec::Gzip,
+            OutputCodec::Zstd => Codec::Zstd,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
-    /// Compress CSV or JSON data to ALS format
+    /// Compress CSV, TSV, JSON, or NDJSON data to ALS format
     Compress {
         /// Input file (use '-' for stdin)
         #[arg(short, long, value_name = "FILE", default_value = "-")]
@@ -66,12 +125,60 @@ enum Commands {
         #[arg(short, long, value_name = "FILE", default_value = "-")]
         output: String,
 
-        /// Input format: csv, json, or auto-detect
+        /// Input format: csv, tsv, json, ndjson, or auto-detect
         #[arg(short, long, value_enum, default_value = "auto")]
         format: Format,
+
+        /// Print a per-column compression report (to stderr when writing ALS to stdout)
+        #[arg(long)]
+        stats: bool,
+
+        /// Report the chosen format and estimated ratio without writing output
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Estimate the ratio and format from the first ROWS rows without writing output
+        #[arg(long, value_name = "ROWS", conflicts_with = "dry_run")]
+        sample: Option<usize>,
+
+        /// Compression effort from 0 (fastest) to 9 (smallest output) [default: 6]
+        #[arg(short, long, value_name = "LEVEL", value_parser = clap::value_parser!(u8).range(0..=9))]
+        level: Option<u8>,
+
+        /// Wrap the ALS output with a general-purpose codec (detected automatically on decompress)
+        #[arg(long, value_enum, default_value = "none")]
+        codec: OutputCodec,
+
+        /// Show a progress bar of the bytes consumed from the input file (stdin keeps the spinner)
+        #[arg(long)]
+        progress: bool,
+
+        /// Always write ALS, even when the ratio is below the CTX fallback threshold
+        #[arg(long)]
+        no_ctx_fallback: bool,
     },
 
-    /// Decompress ALS data to CSV or JSON format
+    /// Compress every matching file in a directory to ALS files in another directory
+    Batch {
+        /// Directory to search for input files, including subdirectories
+        #[arg(short, long, value_name = "DIR")]
+        input_dir: PathBuf,
+
+        /// Directory to write the .als files to, mirroring the input layout
+        #[arg(short, long, value_name = "DIR")]
+        output_dir: PathBuf,
+
+        /// Input format: csv, tsv, json, ndjson, or auto-detect per file
+        #[arg(short, long, value_enum, default_value = "auto")]
+        format: Format,
+
+        /// Glob for the files to compress, e.g. '*.csv' or 'logs/**/*.json'
+        /// [default: files with the format's extension]
+        #[arg(short, long, value_name = "GLOB")]
+        pattern: Option<String>,
+    },
+
+    /// Decompress ALS data to CSV, TSV, JSON, or NDJSON format
     Decompress {
         /// Input file (use '-' for stdin)
         #[arg(short, long, value_name = "FILE", default_value = "-")]
@@ -81,9 +188,24 @@ enum Commands {
         #[arg(short, long, value_name = "FILE", default_value = "-")]
         output: String,
 
-        /// Output format: csv or json
+        /// Output format: csv, tsv, json, ndjson, parquet, avro, or xml
         #[arg(short, long, value_enum, default_value = "csv")]
         format: Format,
+
+        /// Only expand these columns, given as names or zero-based indices (e.g. 'id,2')
+        #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+        select: Option<Vec<String>>,
+    },
+
+    /// Verify that compressing and decompressing reproduces the input
+    Verify {
+        /// Input file (use '-' for stdin)
+        #[arg(short, long, value_name = "FILE", default_value = "-")]
+        input: String,
+
+        /// Input format: csv, tsv, json, ndjson, or auto-detect
+        #[arg(short, long, value_enum, default_value = "auto")]
+        format: Format,
     },
 
     /// Display information about ALS compressed data
@@ -91,6 +213,49 @@ enum Commands {
         /// Input file (use '-' for stdin)
         #[arg(short, long, value_name = "FILE", default_value = "-")]
         input: String,
+
+        /// Emit the document information as a JSON object instead of text
+        #[arg(long)]
+        json: bool,
+
+        /// Read only the version, dictionaries, and schema, skipping the
+        /// column streams (row count and pattern statistics are not reported)
+        #[arg(long)]
+        header_only: bool,
+    },
+
+    /// Measure compression and decompression throughput at each SIMD level
+    Bench {
+        /// Input file (use '-' for stdin)
+        #[arg(short, long, value_name = "FILE", default_value = "-")]
+        input: String,
+
+        /// Input format: csv, tsv, json, ndjson, or auto-detect
+        #[arg(short, long, value_enum, default_value = "auto")]
+        format: Format,
+
+        /// Number of compression and decompression runs per SIMD level
+        #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+    },
+
+    /// Compare the data of two ALS, CSV, TSV, JSON, or NDJSON files
+    Diff {
+        /// First file to compare
+        #[arg(value_name = "LEFT")]
+        left: String,
+
+        /// Second file to compare
+        #[arg(value_name = "RIGHT")]
+        right: String,
+
+        /// Maximum number of differing cells to list
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+
+        /// Compare values exactly instead of treating `3` and `3.0` as equal
+        #[arg(long)]
+        strict: bool,
     },
 }
 
@@ -101,11 +266,22 @@ fn main() -> Result<()> {
     setup_logging(cli.verbose, cli.quiet);
 
     // Load configuration if specified
-    let config = if let Some(config_path) = &cli.config {
+    let mut config = if let Some(config_path) = &cli.config {
         load_config(config_path)?
     } else {
         CompressorConfig::default()
     };
+    if let Some(delimiter) = cli.csv_delimiter {
+        let csv_config = config.csv_config.with_delimiter(delimiter);
+        config = config.with_csv_config(csv_config);
+    }
+    if let Some(threads) = cli.threads {
+        config = config.with_parallelism(threads);
+    }
+    let csv_config = config.csv_config;
+    let parser_config = ParserConfig::new()
+        .with_csv_config(csv_config)
+        .with_parallelism(config.parallelism);
 
     // Execute the appropriate command
     match cli.command {
@@ -113,24 +289,116 @@ fn main() -> Result<()> {
             input,
             output,
             format,
+            stats,
+            dry_run,
+            sample,
+            level,
+            codec,
+            progress,
+            no_ctx_fallback,
         } => {
-            compress_command(&input, &output, format, config, cli.verbose, cli.quiet)?;
+            let config = match level {
+                Some(level) => config.with_effort(level),
+                None => config,
+            };
+            let ctx_fallback = config.ctx_fallback && !no_ctx_fallback;
+            let config = config
+                .with_post_codec(codec.into())
+                .with_ctx_fallback(ctx_fallback);
+            if let Some(rows) = sample {
+                sample_command(&input, format, config, rows, cli.quiet)?;
+            } else if dry_run {
+                dry_run_command(&input, format, config, stats, cli.quiet)?;
+            } else {
+                compress_command(&input, &output, format, config, stats, progress, cli.quiet)?;
+            }
+        }
+        Commands::Batch {
+            input_dir,
+            output_dir,
+            format,
+            pattern,
+        } => {
+            batch::batch_command(&input_dir, &output_dir, format, pattern.as_deref(), config, cli.quiet)?;
         }
         Commands::Decompress {
             input,
             output,
             format,
+            select,
         } => {
-            decompress_command(&input, &output, format, cli.verbose, cli.quiet)?;
+            decompress_command(
+                &input,
+                &output,
+                format,
+                select.as_deref(),
+                parser_config,
+                cli.verbose,
+                cli.quiet,
+            )?;
+        }
+        Commands::Verify { input, format } => {
+            verify_command(&input, format, config, cli.quiet)?;
         }
-        Commands::Info { input } => {
-            info_command(&input, cli.verbose, cli.quiet)?;
+        Commands::Info { input, json, header_only } => {
+            info_command(&input, json, header_only, cli.verbose, cli.quiet)?;
+        }
+        Commands::Bench {
+            input,
+            format,
+            iterations,
+        } => {
+            bench::bench_command(&input, format, iterations, config)?;
+        }
+        Commands::Diff {
+            left,
+            right,
+            limit,
+            strict,
+        } => {
+            diff::diff_command(&left, &right, limit, strict, csv_config, cli.quiet)?;
         }
     }
 
     Ok(())
 }
 
+/// Parse the `--csv-delimiter` value into a single delimiter byte
+fn parse_csv_delimiter(value: &str) -> std::result::Result<u8, String> {
+    let delimiter = match value {
+        "\\t" => '\t',
+        _ => {
+            let mut chars = value.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(format!("expected a single character, got '{}'", value)),
+            }
+        }
+    };
+
+    if !delimiter.is_ascii() {
+        return Err(format!("delimiter must be an ASCII character, got '{}'", delimiter));
+    }
+    if matches!(delimiter, '"' | '\n' | '\r') {
+        return Err("delimiter cannot be a quote or line break".to_string());
+    }
+    Ok(delimiter as u8)
+}
+
+/// Largest accepted `--threads` value
+const MAX_THREADS: usize = 1024;
+
+/// Parse the `--threads` value, rejecting absurd thread counts
+fn parse_threads(value: &str) -> std::result::Result<usize, String> {
+    let threads: usize = value
+        .parse()
+        .map_err(|_| format!("expected a non-negative integer, got '{}'", value))?;
+    if threads > MAX_THREADS {
+        return Err(format!("at most {} threads are supported, got {}", MAX_THREADS, threads));
+    }
+    Ok(threads)
+}
+
 /// Set up logging based on verbosity flags
 fn setup_logging(verbose: bool, quiet: bool) {
     let log_level = if quiet {
@@ -150,13 +418,6 @@ fn setup_logging(verbose: bool, quiet: bool) {
     debug!("Logging initialized at {} level", log_level);
 }
 
-/// Load configuration from a file
-fn load_config(_path: &PathBuf) -> Result<CompressorConfig> {
-    // For now, return default config
-    // TODO: Implement actual config file loading in task 35.6
-    Ok(CompressorConfig::default())
-}
-
 /// Read input from file or stdin
 fn read_input(input: &str) -> Result<String> {
     if input == "-" {
@@ -172,12 +433,34 @@ fn read_input(input: &str) -> Result<String> {
     }
 }
 
+/// Read ALS input from file or stdin, decoding a post-compression codec if present
+fn read_als_input(input: &str) -> Result<String> {
+    let bytes = if input == "-" {
+        let mut buffer = Vec::new();
+        io::stdin()
+            .read_to_end(&mut buffer)
+            .context("Failed to read from stdin")?;
+        buffer
+    } else {
+        fs::read(input).with_context(|| format!("Failed to read input file: {}", input))?
+    };
+
+    let codec = Codec::detect(&bytes);
+    if codec != Codec::None {
+        debug!("Detected {} codec", codec.name());
+    }
+    als_compression::als::codec::decode(&bytes)
+        .map(|text| text.into_owned())
+        .map_err(|e| map_als_error(e, "Decoding ALS input"))
+}
+
 /// Write output to file or stdout
-fn write_output(output: &str, content: &str) -> Result<()> {
+fn write_output(output: &str, content: impl AsRef<[u8]>) -> Result<()> {
+    let content = content.as_ref();
     if output == "-" {
         // Write to stdout
         io::stdout()
-            .write_all(content.as_bytes())
+            .write_all(content)
             .context("Failed to write to stdout")?;
         io::stdout().flush().context("Failed to flush stdout")?;
     } else {
@@ -191,19 +474,21 @@ fn write_output(output: &str, content: &str) -> Result<()> {
 /// Detect input format from content or file extension
 fn detect_format(input: &str, content: &str) -> Format {
     // First try to detect from file extension
-    if input != "-" {
-        if input.ends_with(".csv") {
-            return Format::Csv;
-        } else if input.ends_with(".json") {
-            return Format::Json;
-        } else if input.ends_with(".als") {
-            return Format::Als;
-        }
+    if let Some(format) = format_from_extension(input) {
+        return format;
     }
 
     // Try to detect from content
     let trimmed = content.trim_start();
 
+    // NDJSON has a complete object on the first line, followed by more lines
+    if let Some((first_line, rest)) = trimmed.split_once('\n') {
+        let first_line = first_line.trim_end();
+        if first_line.starts_with('{') && first_line.ends_with('}') && !rest.trim().is_empty() {
+            return Format::Ndjson;
+        }
+    }
+
     // JSON typically starts with [ or {
     if trimmed.starts_with('[') || trimmed.starts_with('{') {
         return Format::Json;
@@ -214,81 +499,128 @@ fn detect_format(input: &str, content: &str) -> Format {
         return Format::Als;
     }
 
+    // Tabs but no commas in the header line suggest TSV
+    let first_line = trimmed.lines().next().unwrap_or("");
+    if first_line.contains('\t') && !first_line.contains(',') {
+        return Format::Tsv;
+    }
+
     // Default to CSV
     Format::Csv
 }
 
+/// Detect the format of an input file from its extension
+fn format_from_extension(input: &str) -> Option<Format> {
+    if input == "-" {
+        None
+    } else if input.ends_with(".csv") {
+        Some(Format::Csv)
+    } else if input.ends_with(".tsv") {
+        Some(Format::Tsv)
+    } else if input.ends_with(".json") {
+        Some(Format::Json)
+    } else if input.ends_with(".ndjson") || input.ends_with(".jsonl") {
+        Some(Format::Ndjson)
+    } else if input.ends_with(".als") {
+        Some(Format::Als)
+    } else {
+        None
+    }
+}
+
 /// Execute the compress command
 fn compress_command(
     input: &str,
     output: &str,
     format: Format,
     config: CompressorConfig,
-    _verbose: bool,
+    stats: bool,
+    show_progress: bool,
     quiet: bool,
 ) -> Result<()> {
     let start_time = Instant::now();
 
     info!("Starting compression: {} -> {}", input, output);
 
-    // Read input with progress bar for large files
-    let progress = create_progress_bar(quiet, "Reading input");
-    let input_data = read_input(input)?;
-    progress.finish_and_clear();
-
-    if input_data.is_empty() {
-        warn!("Input is empty");
-        write_output(output, "")?;
-        return Ok(());
-    }
-
-    let input_size = input_data.len();
-    debug!("Read {} bytes from input", input_size);
+    // Create compressor
+    let ctx_fallback = config.ctx_fallback;
+    let compressor = AlsCompressor::with_config(config);
 
-    // Detect format if auto
-    let detected_format = match format {
-        Format::Auto => {
-            let detected = detect_format(input, &input_data);
-            info!("Auto-detected format: {}", detected.as_str());
-            detected
+    // CSV and JSON files are compressed straight from the file, so the
+    // progress bar follows the bytes the parser consumes
+    let file_format = match format {
+        _ if input == "-" => None,
+        Format::Auto => format_from_extension(input),
+        format => Some(format),
+    };
+    let streamed = match file_format {
+        Some(format @ (Format::Csv | Format::Json)) if show_progress && !stats => {
+            let compress_start = Instant::now();
+            progress::compress_file_with_progress(&compressor, input, format, quiet)?
+                .map(|(input_size, compressed)| (input_size, compressed, compress_start))
         }
-        _ => format,
+        _ => None,
     };
 
-    debug!("Input format: {}", detected_format.as_str());
-
-    // Create compressor
-    let compressor = AlsCompressor::with_config(config);
+    let mut report = None;
+    let (input_size, compressed, compress_start) = match streamed {
+        Some((input_size, compressed, compress_start)) => (input_size, compressed, compress_start),
+        None => {
+            // Read input with progress bar for large files
+            let input_data = if show_progress {
+                progress::read_input_with_progress(input, quiet)?
+            } else {
+                let progress = create_progress_bar(quiet, "Reading input");
+                let input_data = read_input(input)?;
+                progress.finish_and_clear();
+                input_data
+            };
+
+            if input_data.is_empty() {
+                warn!("Input is empty");
+                write_output(output, "")?;
+                return Ok(());
+            }
 
-    // Compress based on format with progress indication
-    let progress = create_progress_bar(quiet, "Compressing");
-    let compress_start = Instant::now();
+            debug!("Read {} bytes from input", input_data.len());
 
-    let compressed = match detected_format {
-        Format::Csv => {
-            debug!("Compressing CSV data");
-            compressor
-                .compress_csv(&input_data)
-                .map_err(|e| map_als_error(e, "CSV compression"))?
-        }
-        Format::Json => {
-            debug!("Compressing JSON data");
-            compressor
-                .compress_json(&input_data)
-                .map_err(|e| map_als_error(e, "JSON compression"))?
-        }
-        Format::Als => {
-            error!("Input is already in ALS format");
-            anyhow::bail!("Input is already in ALS format. Use 'decompress' command instead.");
-        }
-        Format::Auto => {
-            error!("Failed to detect input format");
-            anyhow::bail!("Failed to detect input format");
+            // Detect format if auto
+            let detected_format = match format {
+                Format::Auto => {
+                    let detected = detect_format(input, &input_data);
+                    info!("Auto-detected format: {}", detected.as_str());
+                    detected
+                }
+                _ => format,
+            };
+
+            debug!("Input format: {}", detected_format.as_str());
+
+            // Compress based on format with progress indication
+            let progress = create_progress_bar(quiet, "Compressing");
+            let compress_start = Instant::now();
+
+            let compressed = if stats {
+                let data = parse_input(&compressor, detected_format, &input_data)?;
+                debug!("Compressing {} data with statistics", detected_format.as_str());
+                let (doc, compression_report) = compressor
+                    .compress_with_stats(&data)
+                    .map_err(|e| map_als_error(e, "Compression"))?;
+                report = Some(compression_report);
+                compressor.serialize(&doc)
+            } else {
+                compress_content(&compressor, detected_format, &input_data)?
+            };
+            progress.finish_and_clear();
+            (input_data.len(), compressed, compress_start)
         }
     };
 
+    let compressed = compressor
+        .apply_post_codec(&compressed)
+        .map_err(|e| map_als_error(e, "Output encoding"))?;
+
     let compress_duration = compress_start.elapsed();
-    progress.finish_and_clear();
 
     let output_size = compressed.len();
     let ratio = input_size as f64 / output_size as f64;
@@ -316,21 +648,376 @@ fn compress_command(
         eprintln!("  Savings:     {:.1}%", savings);
         eprintln!("  Time:        {:.3}s", total_duration.as_secs_f64());
         eprintln!("  Throughput:  {:.2} MB/s", throughput);
+        if !ctx_fallback {
+            eprintln!("  CTX:         disabled (ALS output forced)");
+        }
+
+        if let Some(report) = &report {
+            // Keep the report out of the ALS stream when writing to stdout
+            if output == "-" {
+                print_compression_report(report, &mut io::stderr().lock())?;
+            } else {
+                print_compression_report(report, &mut io::stdout().lock())?;
+            }
+        }
+    }
+
+    info!(
+        "Compression completed in {:.3}s",
+        total_duration.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+/// Compress CSV, TSV, JSON, or NDJSON content to ALS text
+fn compress_content(compressor: &AlsCompressor, format: Format, content: &str) -> Result<String> {
+    match format {
+        Format::Csv => {
+            debug!("Compressing CSV data");
+            compressor
+                .compress_csv(content)
+                .map_err(|e| map_als_error(e, "CSV compression"))
+        }
+        Format::Tsv => {
+            debug!("Compressing TSV data");
+            compressor
+                .compress_tsv(content)
+                .map_err(|e| map_als_error(e, "TSV compression"))
+        }
+        Format::Json => {
+            debug!("Compressing JSON data");
+            compressor
+                .compress_json(content)
+                .map_err(|e| map_als_error(e, "JSON compression"))
+        }
+        Format::Ndjson => {
+            debug!("Compressing NDJSON data");
+            compressor
+                .compress_ndjson(content)
+                .map_err(|e| map_als_error(e, "NDJSON compression"))
+        }
+        Format::Als => {
+            error!("Input is already in ALS format");
+            anyhow::bail!("Input is already in ALS format. Use 'decompress' command instead.");
+        }
+        Format::Parquet => {
+            error!("Parquet input is not supported");
+            anyhow::bail!("Parquet input is not supported. Parquet is only available as a decompress output format.");
+        }
+        Format::Avro => {
+            error!("Avro input is not supported");
+            anyhow::bail!("Avro input is not supported. Avro is only available as a decompress output format.");
+        }
+        Format::Xml => {
+            error!("XML input is not supported");
+            anyhow::bail!("XML input is not supported. XML is only available as a decompress output format.");
+        }
+        Format::Auto => {
+            error!("Failed to detect input format");
+            anyhow::bail!("Failed to detect input format");
+        }
+    }
+}
+
+/// Execute the compress command in dry-run mode
+///
+/// Compresses in memory and reports whether the output would be ALS or fall
+/// back to CTX, the estimated ratio, and the columns stored as raw values.
+/// Nothing is written. In quiet mode only the chosen format is printed.
+fn dry_run_command(
+    input: &str,
+    format: Format,
+    config: CompressorConfig,
+    stats: bool,
+    quiet: bool,
+) -> Result<()> {
+    info!("Starting dry-run compression of {}", input);
+
+    let progress = create_progress_bar(quiet, "Reading input");
+    let input_data = read_input(input)?;
+    progress.finish_and_clear();
+
+    if input_data.is_empty() {
+        warn!("Input is empty");
+        return Ok(());
+    }
+
+    let detected_format = match format {
+        Format::Auto => {
+            let detected = detect_format(input, &input_data);
+            info!("Auto-detected format: {}", detected.as_str());
+            detected
+        }
+        _ => format,
+    };
+
+    let ctx_fallback = config.ctx_fallback;
+    let compressor = AlsCompressor::with_config(config);
+
+    let progress = create_progress_bar(quiet, "Compressing");
+    let data = parse_input(&compressor, detected_format, &input_data)?;
+    let (doc, report) = compressor
+        .compress_with_stats(&data)
+        .map_err(|e| map_als_error(e, "Compression"))?;
+    // Serialize and encode in memory so the size matches what would be written
+    let output_size = compressor
+        .apply_post_codec(&compressor.serialize(&doc))
+        .map_err(|e| map_als_error(e, "Output encoding"))?
+        .len();
+    progress.finish_and_clear();
+
+    let input_size = input_data.len();
+    let ratio = input_size as f64 / output_size.max(1) as f64;
+    let chosen = if report.used_ctx_fallback { "ctx" } else { "als" };
+    debug!("Dry run chose {} at {:.2}x", chosen, ratio);
+
+    if quiet {
+        println!("{}", chosen);
+        return Ok(());
+    }
+
+    let raw_columns: Vec<&str> = report
+        .columns
+        .iter()
+        .filter(|column| column.pattern_type == PatternType::Raw)
+        .map(|column| column.name.as_str())
+        .collect();
+    let raw_summary = if report.used_ctx_fallback {
+        "all (CTX fallback)".to_string()
+    } else if raw_columns.is_empty() {
+        "none".to_string()
+    } else {
+        raw_columns.join(", ")
+    };
+
+    println!("Dry run: no output written");
+    if report.used_ctx_fallback {
+        println!("  Format:      CTX (ratio below fallback threshold)");
+    } else if !ctx_fallback {
+        println!("  Format:      ALS (CTX fallback disabled)");
+    } else {
+        println!("  Format:      ALS");
+    }
+    println!("  Input:       {}", format_bytes(input_size));
+    println!("  Output:      {}", format_bytes(output_size));
+    println!("  Ratio:       {:.2}x", ratio);
+    println!("  Raw columns: {}", raw_summary);
+
+    if stats {
+        print_compression_report(&report, &mut io::stdout().lock())?;
+    }
+
+    Ok(())
+}
+
+/// Execute the compress command in sample mode
+///
+/// Compresses only the first `rows` rows and extrapolates the ratio and the
+/// ALS/CTX decision to the whole input. Nothing is written. In quiet mode
+/// only the likely format is printed.
+fn sample_command(
+    input: &str,
+    format: Format,
+    config: CompressorConfig,
+    rows: usize,
+    quiet: bool,
+) -> Result<()> {
+    use als_compression::convert::csv::parse_csv_sample;
+    use als_compression::convert::json::{parse_json_sample, parse_ndjson_sample};
+    use als_compression::convert::tsv::parse_tsv_sample;
+
+    info!("Estimating compression of {} from the first {} rows", input, rows);
+
+    let progress = create_progress_bar(quiet, "Reading input");
+    let input_data = read_input(input)?;
+    progress.finish_and_clear();
+
+    if input_data.is_empty() {
+        warn!("Input is empty");
+        return Ok(());
+    }
+
+    let detected_format = match format {
+        Format::Auto => {
+            let detected = detect_format(input, &input_data);
+            info!("Auto-detected format: {}", detected.as_str());
+            detected
+        }
+        _ => format,
+    };
+
+    let progress = create_progress_bar(quiet, "Compressing sample");
+    let (data, sample_size) = match detected_format {
+        Format::Csv => parse_csv_sample(&input_data, &config.csv_config, rows)
+            .map_err(|e| map_als_error(e, "CSV parsing"))?,
+        Format::Tsv => parse_tsv_sample(&input_data, rows).map_err(|e| map_als_error(e, "TSV parsing"))?,
+        Format::Json => parse_json_sample(&input_data, rows).map_err(|e| map_als_error(e, "JSON parsing"))?,
+        Format::Ndjson => {
+            parse_ndjson_sample(&input_data, rows).map_err(|e| map_als_error(e, "NDJSON parsing"))?
+        }
+        Format::Als => {
+            error!("Input is already in ALS format");
+            anyhow::bail!("Input is already in ALS format. Use 'decompress' command instead.");
+        }
+        Format::Parquet => {
+            error!("Parquet input is not supported");
+            anyhow::bail!("Parquet input is not supported. Parquet is only available as a decompress output format.");
+        }
+        Format::Avro => {
+            error!("Avro input is not supported");
+            anyhow::bail!("Avro input is not supported. Avro is only available as a decompress output format.");
+        }
+        Format::Xml => {
+            error!("XML input is not supported");
+            anyhow::bail!("XML input is not supported. XML is only available as a decompress output format.");
+        }
+        Format::Auto => {
+            error!("Failed to detect input format");
+            anyhow::bail!("Failed to detect input format");
+        }
+    };
+    let checksum = config.checksum;
+    let compressor = AlsCompressor::with_config(config);
+    let (doc, report) = compressor
+        .compress_with_stats(&data)
+        .map_err(|e| map_als_error(e, "Compression"))?;
+    let sample_output = AlsSerializer::new().with_checksum(checksum).serialize(&doc).len();
+    progress.finish_and_clear();
+
+    let ratio = sample_size as f64 / sample_output.max(1) as f64;
+    let input_size = input_data.len();
+    let estimated_output = (input_size as f64 / ratio).round() as usize;
+    let chosen = if report.used_ctx_fallback { "ctx" } else { "als" };
+    debug!(
+        "Sample of {} rows ({} bytes) chose {} at {:.2}x",
+        data.row_count, sample_size, chosen, ratio
+    );
+
+    if quiet {
+        println!("{}", chosen);
+        return Ok(());
     }
 
-    info!(
-        "Compression completed in {:.3}s",
-        total_duration.as_secs_f64()
+    println!("Estimate from a sample of {} rows: no output written", data.row_count);
+    if report.used_ctx_fallback {
+        println!("  Likely format:     CTX (ratio below fallback threshold)");
+    } else {
+        println!("  Likely format:     ALS");
+    }
+    println!(
+        "  Sampled:           {} of {} input",
+        format_bytes(sample_size),
+        format_bytes(input_size)
     );
+    println!("  Estimated ratio:   {:.2}x", ratio);
+    println!("  Estimated output:  {}", format_bytes(estimated_output));
+    if sample_size < input_size {
+        eprintln!(
+            "Note: the estimate may differ from full compression, especially when \
+             dictionaries or patterns span more rows than the sample"
+        );
+    }
 
     Ok(())
 }
 
+/// Parse input text of the given format into tabular data
+fn parse_input(compressor: &AlsCompressor, format: Format, content: &str) -> Result<TabularData<'static>> {
+    match format {
+        Format::Csv => compressor.parse_csv(content).map_err(|e| map_als_error(e, "CSV parsing")),
+        Format::Tsv => compressor.parse_tsv(content).map_err(|e| map_als_error(e, "TSV parsing")),
+        Format::Json => compressor.parse_json(content).map_err(|e| map_als_error(e, "JSON parsing")),
+        Format::Ndjson => compressor.parse_ndjson(content).map_err(|e| map_als_error(e, "NDJSON parsing")),
+        Format::Als => {
+            error!("Input is already in ALS format");
+            anyhow::bail!("Input is already in ALS format. Use 'decompress' command instead.");
+        }
+        Format::Parquet => {
+            error!("Parquet input is not supported");
+            anyhow::bail!("Parquet input is not supported. Parquet is only available as a decompress output format.");
+        }
+        Format::Avro => {
+            error!("Avro input is not supported");
+            anyhow::bail!("Avro input is not supported. Avro is only available as a decompress output format.");
+        }
+        Format::Xml => {
+            error!("XML input is not supported");
+            anyhow::bail!("XML input is not supported. XML is only available as a decompress output format.");
+        }
+        Format::Auto => {
+            error!("Failed to detect input format");
+            anyhow::bail!("Failed to detect input format");
+        }
+    }
+}
+
+/// Decompress only the selected columns of an ALS document.
+///
+/// Each selector is matched against the schema by name first and then parsed
+/// as a zero-based column index. Streams of unselected columns are never
+/// expanded.
+fn decompress_selected(
+    parser: &AlsParser,
+    als_data: &str,
+    selectors: &[String],
+    output_format: Format,
+    csv_config: &CsvConfig,
+) -> Result<Vec<u8>> {
+    let doc = parser
+        .parse(als_data)
+        .map_err(|e| map_als_source_error(e, "ALS parsing", als_data))?;
+
+    let indices = selectors
+        .iter()
+        .map(|selector| {
+            let selector = selector.trim();
+            doc.column_index(selector)
+                .or_else(|| selector.parse::<usize>().ok().filter(|&index| index < doc.column_count()))
+                .ok_or_else(|| {
+                    map_als_error(
+                        AlsError::ColumnNotFound {
+                            column: selector.to_string(),
+                        },
+                        "Column selection",
+                    )
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    debug!("Selected column indices: {:?}", indices);
+
+    let data = parser
+        .expand_typed_columns(&doc, &indices)
+        .map_err(|e| map_als_error(e, "ALS expansion"))?;
+
+    let formatted = match output_format {
+        Format::Csv => {
+            als_compression::convert::csv::to_csv_with_config(&data, csv_config).map(String::into_bytes)
+        }
+        Format::Tsv => als_compression::convert::tsv::to_tsv(&data).map(String::into_bytes),
+        Format::Json => als_compression::convert::json::to_json(&data).map(String::into_bytes),
+        Format::Ndjson => als_compression::convert::json::to_ndjson(&data).map(String::into_bytes),
+        Format::Parquet => {
+            let mut parquet = Vec::new();
+            als_compression::convert::parquet::to_parquet(&data, &mut parquet).map(|()| parquet)
+        }
+        Format::Avro => {
+            let mut avro = Vec::new();
+            als_compression::convert::avro::to_avro(&data, &mut avro).map(|()| avro)
+        }
+        Format::Xml => als_compression::convert::xml::to_xml(&data).map(String::into_bytes),
+        _ => unreachable!("Output format should be CSV, TSV, JSON, NDJSON, Parquet, Avro, or XML at this point"),
+    };
+    formatted.map_err(|e| map_als_error(e, &format!("Conversion to {}", output_format.as_str())))
+}
+
 /// Execute the decompress command
 fn decompress_command(
     input: &str,
     output: &str,
     format: Format,
+    select: Option<&[String]>,
+    parser_config: ParserConfig,
     _verbose: bool,
     quiet: bool,
 ) -> Result<()> {
@@ -341,7 +1028,7 @@ fn decompress_command(
 
     // Read ALS input with progress bar
     let progress = create_progress_bar(quiet, "Reading input");
-    let als_data = read_input(input)?;
+    let als_data = read_als_input(input)?;
     progress.finish_and_clear();
 
     if als_data.is_empty() {
@@ -353,13 +1040,18 @@ fn decompress_command(
     let input_size = als_data.len();
     debug!("Read {} bytes from input", input_size);
 
-    // Validate that format is CSV or JSON (not ALS or Auto)
+    // Validate that format is CSV, TSV, JSON, NDJSON, Parquet, Avro, or XML (not ALS or Auto)
     let output_format = match format {
         Format::Csv => Format::Csv,
+        Format::Tsv => Format::Tsv,
         Format::Json => Format::Json,
+        Format::Ndjson => Format::Ndjson,
+        Format::Parquet => Format::Parquet,
+        Format::Avro => Format::Avro,
+        Format::Xml => Format::Xml,
         Format::Als => {
             error!("Cannot decompress to ALS format");
-            anyhow::bail!("Cannot decompress to ALS format. Use 'csv' or 'json' as output format.");
+            anyhow::bail!("Cannot decompress to ALS format. Use 'csv', 'tsv', 'json', 'ndjson', 'parquet', 'avro', or 'xml' as output format.");
         }
         Format::Auto => {
             // Default to CSV for auto-detection
@@ -369,26 +1061,71 @@ fn decompress_command(
     };
 
     // Create parser
-    let parser = AlsParser::new();
+    let csv_config = parser_config.csv_config;
+    let parser = AlsParser::with_config(parser_config);
 
     // Decompress based on output format with progress indication
     let progress = create_progress_bar(quiet, "Decompressing");
     let decompress_start = Instant::now();
 
-    let decompressed = match output_format {
-        Format::Csv => {
-            debug!("Decompressing to CSV");
-            parser
-                .to_csv(&als_data)
-                .map_err(|e| map_als_error(e, "ALS decompression to CSV"))?
-        }
-        Format::Json => {
-            debug!("Decompressing to JSON");
-            parser
-                .to_json(&als_data)
-                .map_err(|e| map_als_error(e, "ALS decompression to JSON"))?
+    let decompressed = match select {
+        Some(selectors) => {
+            decompress_selected(&parser, &als_data, selectors, output_format, &csv_config)?
         }
-        _ => unreachable!("Output format should be CSV or JSON at this point"),
+        None => match output_format {
+            Format::Csv => {
+                debug!("Decompressing to CSV");
+                parser
+                    .to_csv(&als_data)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to CSV", &als_data))?
+                    .into_bytes()
+            }
+            Format::Tsv => {
+                debug!("Decompressing to TSV");
+                parser
+                    .to_tsv(&als_data)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to TSV", &als_data))?
+                    .into_bytes()
+            }
+            Format::Json => {
+                debug!("Decompressing to JSON");
+                parser
+                    .to_json(&als_data)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to JSON", &als_data))?
+                    .into_bytes()
+            }
+            Format::Ndjson => {
+                debug!("Decompressing to NDJSON");
+                parser
+                    .to_ndjson(&als_data)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to NDJSON", &als_data))?
+                    .into_bytes()
+            }
+            Format::Parquet => {
+                debug!("Decompressing to Parquet");
+                let mut parquet = Vec::new();
+                parser
+                    .to_parquet(&als_data, &mut parquet)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to Parquet", &als_data))?;
+                parquet
+            }
+            Format::Avro => {
+                debug!("Decompressing to Avro");
+                let mut avro = Vec::new();
+                parser
+                    .to_avro(&als_data, &mut avro)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to Avro", &als_data))?;
+                avro
+            }
+            Format::Xml => {
+                debug!("Decompressing to XML");
+                parser
+                    .to_xml(&als_data)
+                    .map_err(|e| map_als_source_error(e, "ALS decompression to XML", &als_data))?
+                    .into_bytes()
+            }
+            _ => unreachable!("Output format should be CSV, TSV, JSON, NDJSON, Parquet, Avro, or XML at this point"),
+        },
     };
 
     let decompress_duration = decompress_start.elapsed();
@@ -431,15 +1168,241 @@ fn decompress_command(
     Ok(())
 }
 
+/// Location and values of the first cell that differs after a round-trip
+#[derive(Debug)]
+struct Mismatch {
+    row: usize,
+    column: String,
+    expected: String,
+    found: String,
+}
+
+/// Execute the verify command
+fn verify_command(input: &str, format: Format, config: CompressorConfig, quiet: bool) -> Result<()> {
+    use als_compression::convert::csv::parse_csv_text_with_config;
+    use als_compression::convert::json::{parse_json, parse_ndjson};
+
+    let start_time = Instant::now();
+
+    info!("Verifying round-trip fidelity of {}", input);
+
+    // Read input with progress bar
+    let progress = create_progress_bar(quiet, "Reading input");
+    let input_data = read_input(input)?;
+    progress.finish_and_clear();
+
+    if input_data.is_empty() {
+        warn!("Input is empty");
+        return Ok(());
+    }
+
+    // Detect format if auto
+    let detected_format = match format {
+        Format::Auto => {
+            let detected = detect_format(input, &input_data);
+            info!("Auto-detected format: {}", detected.as_str());
+            detected
+        }
+        _ => format,
+    };
+
+    let csv_config = config.csv_config;
+    let parser = AlsParser::with_config(
+        ParserConfig::new()
+            .with_csv_config(csv_config)
+            .with_parallelism(config.parallelism),
+    );
+    let compressor = AlsCompressor::with_config(config);
+
+    // Compress, decompress, and re-parse both sides into tabular data.
+    // CSV and TSV cells are compared as written, so a round trip that
+    // turns `007` into `7` fails; JSON carries its own types.
+    let tsv_config = CsvConfig::new().with_delimiter(b'\t');
+    let progress = create_progress_bar(quiet, "Verifying");
+    let (original, compressed, round_trip) = match detected_format {
+        Format::Csv => {
+            let original = parse_csv_text_with_config(&input_data, &csv_config)
+                .map_err(|e| map_als_error(e, "CSV parsing"))?;
+            let compressed = compressor
+                .compress_csv(&input_data)
+                .map_err(|e| map_als_error(e, "CSV compression"))?;
+            let decompressed = parser
+                .to_csv(&compressed)
+                .map_err(|e| map_als_error(e, "ALS decompression to CSV"))?;
+            let round_trip = parse_csv_text_with_config(&decompressed, &csv_config)
+                .map_err(|e| map_als_error(e, "Decompressed CSV parsing"))?;
+            (original, compressed, round_trip)
+        }
+        Format::Tsv => {
+            let original = parse_csv_text_with_config(&input_data, &tsv_config)
+                .map_err(|e| map_als_error(e, "TSV parsing"))?;
+            let compressed = compressor
+                .compress_tsv(&input_data)
+                .map_err(|e| map_als_error(e, "TSV compression"))?;
+            let decompressed = parser
+                .to_tsv(&compressed)
+                .map_err(|e| map_als_error(e, "ALS decompression to TSV"))?;
+            let round_trip = parse_csv_text_with_config(&decompressed, &tsv_config)
+                .map_err(|e| map_als_error(e, "Decompressed TSV parsing"))?;
+            (original, compressed, round_trip)
+        }
+        Format::Json => {
+            let original =
+                parse_json(&input_data).map_err(|e| map_als_error(e, "JSON parsing"))?;
+            let compressed = compressor
+                .compress_json(&input_data)
+                .map_err(|e| map_als_error(e, "JSON compression"))?;
+            let decompressed = parser
+                .to_json(&compressed)
+                .map_err(|e| map_als_error(e, "ALS decompression to JSON"))?;
+            let round_trip = parse_json(&decompressed)
+                .map_err(|e| map_als_error(e, "Decompressed JSON parsing"))?;
+            (original, compressed, round_trip)
+        }
+        Format::Ndjson => {
+            let original =
+                parse_ndjson(&input_data).map_err(|e| map_als_error(e, "NDJSON parsing"))?;
+            let compressed = compressor
+                .compress_ndjson(&input_data)
+                .map_err(|e| map_als_error(e, "NDJSON compression"))?;
+            let decompressed = parser
+                .to_ndjson(&compressed)
+                .map_err(|e| map_als_error(e, "ALS decompression to NDJSON"))?;
+            let round_trip = parse_ndjson(&decompressed)
+                .map_err(|e| map_als_error(e, "Decompressed NDJSON parsing"))?;
+            (original, compressed, round_trip)
+        }
+        Format::Als => {
+            error!("Input is already in ALS format");
+            anyhow::bail!("Input is already in ALS format. Verify expects CSV, TSV, JSON, or NDJSON input.");
+        }
+        Format::Parquet => {
+            error!("Parquet input is not supported");
+            anyhow::bail!("Parquet input is not supported. Parquet is only available as a decompress output format.");
+        }
+        Format::Avro => {
+            error!("Avro input is not supported");
+            anyhow::bail!("Avro input is not supported. Avro is only available as a decompress output format.");
+        }
+        Format::Xml => {
+            error!("XML input is not supported");
+            anyhow::bail!("XML input is not supported. XML is only available as a decompress output format.");
+        }
+        Format::Auto => {
+            error!("Failed to detect input format");
+            anyhow::bail!("Failed to detect input format");
+        }
+    };
+    progress.finish_and_clear();
+
+    // Compare schema, row count, then cell-by-cell
+    if original.column_names() != round_trip.column_names() {
+        error!("Column names differ after round-trip");
+        anyhow::bail!(
+            "Verification failed: columns differ\n  Expected: {}\n  Found:    {}",
+            original.column_names().join(", "),
+            round_trip.column_names().join(", ")
+        );
+    }
+
+    if original.row_count != round_trip.row_count {
+        error!("Row count differs after round-trip");
+        anyhow::bail!(
+            "Verification failed: expected {} rows, found {}",
+            original.row_count,
+            round_trip.row_count
+        );
+    }
+
+    if let Some(mismatch) = find_first_mismatch(&original, &round_trip) {
+        error!("Round-trip mismatch at row {}", mismatch.row + 1);
+        let context = |data: &TabularData| {
+            data.get_row(mismatch.row)
+                .map(|row| {
+                    row.iter()
+                        .map(|v| v.to_string_repr().into_owned())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .unwrap_or_default()
+        };
+        anyhow::bail!(
+            "Verification failed at row {}, column '{}': expected '{}', found '{}'\n  Original row:   {}\n  Round-trip row: {}",
+            mismatch.row + 1,
+            mismatch.column,
+            mismatch.expected,
+            mismatch.found,
+            context(&original),
+            context(&round_trip)
+        );
+    }
+
+    let total_duration = start_time.elapsed();
+
+    // Display summary
+    if !quiet {
+        let ratio = input_data.len() as f64 / compressed.len() as f64;
+        eprintln!("✓ Verification passed");
+        eprintln!("  Rows:        {}", original.row_count);
+        eprintln!("  Columns:     {}", original.column_count());
+        eprintln!("  Input:       {}", format_bytes(input_data.len()));
+        eprintln!("  Compressed:  {}", format_bytes(compressed.len()));
+        eprintln!("  Ratio:       {:.2}x", ratio);
+        eprintln!("  Time:        {:.3}s", total_duration.as_secs_f64());
+    }
+
+    info!(
+        "Verification completed in {:.3}s",
+        total_duration.as_secs_f64()
+    );
+
+    Ok(())
+}
+
+/// Find the first cell that differs between two tables with the same shape
+fn find_first_mismatch(original: &TabularData, round_trip: &TabularData) -> Option<Mismatch> {
+    for row in 0..original.row_count {
+        for (expected_col, found_col) in original.columns.iter().zip(&round_trip.columns) {
+            let expected = expected_col.values.get(row)?;
+            let found = found_col.values.get(row)?;
+            if !values_equivalent(expected, found) {
+                return Some(Mismatch {
+                    row,
+                    column: expected_col.name.to_string(),
+                    expected: expected.to_string_repr().into_owned(),
+                    found: found.to_string_repr().into_owned(),
+                });
+            }
+        }
+    }
+    None
+}
+
+/// Compare two values after normalizing numeric representations (`3.0` == `3`)
+///
+/// Strings, including every CSV and TSV cell, must match exactly.
+fn values_equivalent(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Integer(i), Value::Float(f)) | (Value::Float(f), Value::Integer(i)) => {
+            *i as f64 == *f
+        }
+        (Value::Float(x), Value::Float(y)) => x == y || (x.is_nan() && y.is_nan()),
+        (Value::Decimal(_), Value::Integer(_) | Value::Float(_))
+        | (Value::Integer(_) | Value::Float(_), Value::Decimal(_)) => a.as_float() == b.as_float(),
+        (Value::Timestamp(t), Value::Integer(i)) | (Value::Integer(i), Value::Timestamp(t)) => t == i,
+        _ => a == b,
+    }
+}
+
 /// Execute the info command
-fn info_command(input: &str, verbose: bool, quiet: bool) -> Result<()> {
+fn info_command(input: &str, json: bool, header_only: bool, verbose: bool, quiet: bool) -> Result<()> {
     let start_time = Instant::now();
 
     info!("Reading ALS document info from {}", input);
 
     // Read ALS input with progress bar
     let progress = create_progress_bar(quiet, "Reading input");
-    let als_data = read_input(input)?;
+    let als_data = read_als_input(input)?;
     progress.finish_and_clear();
 
     if als_data.is_empty() {
@@ -454,9 +1417,12 @@ fn info_command(input: &str, verbose: bool, quiet: bool) -> Result<()> {
     let parser = AlsParser::new();
     let parse_start = Instant::now();
 
-    let doc = parser
-        .parse(&als_data)
-        .map_err(|e| map_als_error(e, "ALS parsing"))?;
+    let doc = if header_only {
+        parser.parse_header(&als_data)
+    } else {
+        parser.parse(&als_data)
+    }
+    .map_err(|e| map_als_source_error(e, "ALS parsing", &als_data))?;
 
     let parse_duration = parse_start.elapsed();
     progress.finish_and_clear();
@@ -466,9 +1432,30 @@ fn info_command(input: &str, verbose: bool, quiet: bool) -> Result<()> {
         parse_duration.as_secs_f64()
     );
 
+    // Infer column types and value metrics from the expanded values
+    let (column_types, value_stats): (Vec<ColumnType>, Vec<ValueStats>) = if header_only {
+        (Vec::new(), Vec::new())
+    } else {
+        match parser.expand_typed(&doc) {
+            Ok(data) => (
+                data.schema_types().into_iter().map(|(_, ty)| ty).collect(),
+                data.columns.iter().map(|column| ValueStats::from_values(&column.values)).collect(),
+            ),
+            Err(e) => {
+                warn!("Could not infer column types: {}", e);
+                (Vec::new(), Vec::new())
+            }
+        }
+    };
+
     // Display document information
-    if !quiet {
-        display_document_info(&doc, &als_data, verbose);
+    if json {
+        let report = build_info_report(&doc, &als_data, &column_types, &value_stats);
+        let output = serde_json::to_string_pretty(&report)
+            .context("Failed to serialize document information")?;
+        println!("{}", output);
+    } else if !quiet {
+        display_document_info(&doc, &als_data, &column_types, verbose);
     }
 
     let total_duration = start_time.elapsed();
@@ -481,7 +1468,12 @@ fn info_command(input: &str, verbose: bool, quiet: bool) -> Result<()> {
 }
 
 /// Display information about an ALS document
-fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, verbose: bool) {
+fn display_document_info(
+    doc: &als_compression::AlsDocument,
+    als_data: &str,
+    column_types: &[ColumnType],
+    verbose: bool,
+) {
     use als_compression::FormatIndicator;
 
     println!("=== ALS Document Information ===\n");
@@ -496,7 +1488,10 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
     );
     println!("Version: {}", doc.version);
     println!("Columns: {}", doc.column_count());
-    println!("Rows: {}", doc.row_count());
+    match doc.known_row_count() {
+        Some(rows) => println!("Rows: {}", rows),
+        None => println!("Rows: unknown (header only)"),
+    }
     println!("Compressed size: {} bytes", als_data.len());
 
     // Calculate estimated uncompressed size
@@ -517,7 +1512,10 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
     if !doc.schema.is_empty() {
         println!("\n--- Schema ---");
         for (i, col_name) in doc.schema.iter().enumerate() {
-            println!("  {}: {}", i + 1, col_name);
+            match column_types.get(i) {
+                Some(ty) => println!("  {}: {} ({})", i + 1, col_name, ty.name()),
+                None => println!("  {}: {}", i + 1, col_name),
+            }
         }
     }
 
@@ -540,6 +1538,10 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
     }
 
     // Pattern statistics
+    if doc.known_row_count().is_none() {
+        println!();
+        return;
+    }
     println!("\n--- Compression Patterns ---");
     let pattern_stats = analyze_patterns(doc);
 
@@ -561,6 +1563,12 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
             pattern_stats.toggles
         );
     }
+    if pattern_stats.deltas > 0 {
+        println!(
+            "  Deltas: {} (nearly-arithmetic sequences)",
+            pattern_stats.deltas
+        );
+    }
     if pattern_stats.dict_refs > 0 {
         println!("  Dictionary references: {}", pattern_stats.dict_refs);
     }
@@ -574,12 +1582,14 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
     let total_operators = pattern_stats.ranges
         + pattern_stats.multipliers
         + pattern_stats.toggles
+        + pattern_stats.deltas
         + pattern_stats.dict_refs
         + pattern_stats.raw_values;
     if total_operators > 0 {
         let compressed_ops = pattern_stats.ranges
             + pattern_stats.multipliers
             + pattern_stats.toggles
+            + pattern_stats.deltas
             + pattern_stats.dict_refs;
         let compression_effectiveness = (compressed_ops as f64 / total_operators as f64) * 100.0;
         println!(
@@ -605,6 +1615,9 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
             if col_stats.toggles > 0 {
                 println!("    - Toggles: {}", col_stats.toggles);
             }
+            if col_stats.deltas > 0 {
+                println!("    - Deltas: {}", col_stats.deltas);
+            }
             if col_stats.dict_refs > 0 {
                 println!("    - Dictionary refs: {}", col_stats.dict_refs);
             }
@@ -617,12 +1630,114 @@ fn display_document_info(doc: &als_compression::AlsDocument, als_data: &str, ver
     println!();
 }
 
+/// Machine-readable document information emitted by `info --json`
+#[derive(Debug, Serialize)]
+struct InfoReport {
+    format: &'static str,
+    version: u8,
+    column_count: usize,
+    /// `None` when only the header was read
+    row_count: Option<usize>,
+    compressed_size: usize,
+    columns: Vec<ColumnInfo>,
+    dictionaries: BTreeMap<String, usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    patterns: Option<PatternStats>,
+}
+
+/// Per-column entry of an [`InfoReport`]
+#[derive(Debug, Serialize)]
+struct ColumnInfo {
+    name: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    inferred_type: Option<&'static str>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    stream: Option<StreamInfo>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    values: Option<ValueInfo>,
+}
+
+/// Operator statistics of a [`ColumnInfo`], absent when only the header was read
+#[derive(Debug, Serialize)]
+struct StreamInfo {
+    operators: usize,
+    expanded_values: usize,
+    patterns: PatternStats,
+}
+
+/// Value length and cardinality metrics of a [`ColumnInfo`]
+#[derive(Debug, Serialize)]
+struct ValueInfo {
+    distinct_count: usize,
+    min_value_length: usize,
+    max_value_length: usize,
+    mean_value_length: f64,
+    median_value_length: usize,
+}
+
+impl From<&ValueStats> for ValueInfo {
+    fn from(stats: &ValueStats) -> Self {
+        Self {
+            distinct_count: stats.distinct_count,
+            min_value_length: stats.min_value_length,
+            max_value_length: stats.max_value_length,
+            mean_value_length: stats.mean_value_length,
+            median_value_length: stats.median_value_length,
+        }
+    }
+}
+
+/// Collect the information shown by `info` into a serializable report
+fn build_info_report(
+    doc: &als_compression::AlsDocument,
+    als_data: &str,
+    column_types: &[ColumnType],
+    value_stats: &[ValueStats],
+) -> InfoReport {
+    use als_compression::FormatIndicator;
+
+    let columns = doc
+        .schema
+        .iter()
+        .enumerate()
+        .map(|(i, name)| ColumnInfo {
+            name: name.clone(),
+            inferred_type: column_types.get(i).map(|ty| ty.name()),
+            stream: doc.streams.get(i).map(|stream| StreamInfo {
+                operators: stream.operator_count(),
+                expanded_values: stream.expanded_count(),
+                patterns: analyze_column_stream(stream),
+            }),
+            values: value_stats.get(i).map(ValueInfo::from),
+        })
+        .collect();
+
+    InfoReport {
+        format: match doc.format_indicator {
+            FormatIndicator::Als => "als",
+            FormatIndicator::Ctx => "ctx",
+        },
+        version: doc.version,
+        column_count: doc.column_count(),
+        row_count: doc.known_row_count(),
+        compressed_size: als_data.len(),
+        columns,
+        dictionaries: doc
+            .dictionaries
+            .iter()
+            .map(|(name, entries)| (name.clone(), entries.len()))
+            .collect(),
+        patterns: doc.known_row_count().map(|_| analyze_patterns(doc)),
+    }
+}
+
 /// Pattern statistics for a document or column
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize)]
 struct PatternStats {
     ranges: usize,
     multipliers: usize,
     toggles: usize,
+    deltas: usize,
     dict_refs: usize,
     raw_values: usize,
 }
@@ -656,14 +1771,33 @@ fn count_operator_patterns(op: &als_compression::AlsOperator, stats: &mut Patter
     use als_compression::AlsOperator;
 
     match op {
-        AlsOperator::Range { .. } => stats.ranges += 1,
+        AlsOperator::Range { .. }
+        | AlsOperator::SparseRange { .. }
+        | AlsOperator::PatchedRange { .. }
+        | AlsOperator::FloatRange { .. } => stats.ranges += 1,
         AlsOperator::Multiply { value, .. } => {
             stats.multipliers += 1;
             // Count nested operator
             count_operator_patterns(value, stats);
         }
         AlsOperator::Toggle { .. } => stats.toggles += 1,
+        AlsOperator::RunList { .. } => stats.multipliers += 1,
+        // Count the encodings of every lane
+        AlsOperator::Interleave { lanes } => {
+            lanes.iter().for_each(|lane| count_operator_patterns(lane, stats))
+        }
+        // Count the encodings of the middles inside the affix wrapper
+        AlsOperator::Affix { values, .. } => {
+            values.iter().for_each(|value| count_operator_patterns(value, stats))
+        }
+        AlsOperator::Delta { .. } => stats.deltas += 1,
+        AlsOperator::Geometric { .. } => stats.ranges += 1,
+        // Count the epoch encoding inside the timestamp wrapper
+        AlsOperator::Timestamp { value, .. } => count_operator_patterns(value, stats),
+        // Count the address encoding inside the IPv4 wrapper
+        AlsOperator::Ipv4 { value } => count_operator_patterns(value, stats),
         AlsOperator::DictRef(_) => stats.dict_refs += 1,
+        AlsOperator::IntDict { indices, .. } => stats.dict_refs += indices.len(),
         AlsOperator::Raw(_) => stats.raw_values += 1,
     }
 }
@@ -728,6 +1862,19 @@ fn format_bytes(bytes: usize) -> String {
     }
 }
 
+/// Map an error from parsing ALS text to an anyhow error.
+///
+/// Syntax errors show the offending line of `source` with a caret under the
+/// column; every other error is mapped by `map_als_error`.
+fn map_als_source_error(error: AlsError, context: &str, source: &str) -> anyhow::Error {
+    match error {
+        AlsError::AlsSyntaxError { .. } | AlsError::InvalidEscape { .. } => {
+            anyhow::anyhow!("{}: {}", context, error.with_source(source))
+        }
+        other => map_als_error(other, context),
+    }
+}
+
 /// Map AlsError to anyhow::Error with context
 fn map_als_error(error: AlsError, context: &str) -> anyhow::Error {
     match error {
@@ -775,6 +1922,80 @@ fn map_als_error(error: AlsError, context: &str) -> anyhow::Error {
                 step
             )
         }
+        AlsError::InvalidFloatRange {
+            start,
+            end,
+            step,
+            message,
+        } => {
+            anyhow::anyhow!(
+                "{}: Invalid float range {} to {} with step {}: {}",
+                context,
+                start,
+                end,
+                step,
+                message
+            )
+        }
+        AlsError::DeltaOverflow { base, index } => {
+            anyhow::anyhow!(
+                "{}: Delta overflow: value {} of delta sequence starting at {} exceeds the i64 range",
+                context,
+                index,
+                base
+            )
+        }
+        AlsError::InvalidGeometric { start, ratio, message } => {
+            anyhow::anyhow!(
+                "{}: Invalid geometric sequence {}^*{}: {}",
+                context,
+                start,
+                ratio,
+                message
+            )
+        }
+        AlsError::InvalidSparseRange { start, end, message } => {
+            anyhow::anyhow!(
+                "{}: Invalid sparse range {}>{}: {}",
+                context,
+                start,
+                end,
+                message
+            )
+        }
+        AlsError::InvalidPatchedRange { start, end, message } => {
+            anyhow::anyhow!(
+                "{}: Invalid patched range {}>{}: {}",
+                context,
+                start,
+                end,
+                message
+            )
+        }
+        AlsError::InvalidInterleave { lanes, message } => {
+            anyhow::anyhow!(
+                "{}: Invalid interleave of {} lanes: {}",
+                context,
+                lanes,
+                message
+            )
+        }
+        AlsError::InvalidTimestamp { value, message } => {
+            anyhow::anyhow!(
+                "{}: Invalid timestamp value '{}': {}",
+                context,
+                value,
+                message
+            )
+        }
+        AlsError::InvalidIpv4 { value, message } => {
+            anyhow::anyhow!(
+                "{}: Invalid IPv4 address value '{}': {}",
+                context,
+                value,
+                message
+            )
+        }
         AlsError::VersionMismatch { expected, found } => {
             anyhow::anyhow!(
                 "{}: Version mismatch: expected <= {}, found {}",
@@ -783,6 +2004,37 @@ fn map_als_error(error: AlsError, context: &str) -> anyhow::Error {
                 found
             )
         }
+        AlsError::InvalidEscape { position, sequence } => {
+            anyhow::anyhow!(
+                "{}: Invalid escape sequence {} at position {}",
+                context,
+                sequence,
+                position
+            )
+        }
+        AlsError::ChecksumMismatch { expected, found } => {
+            anyhow::anyhow!(
+                "{}: Checksum mismatch: expected {:08x}, found {:08x}",
+                context,
+                expected,
+                found
+            )
+        }
+        AlsError::InputLimitExceeded { limit, unit } => {
+            anyhow::anyhow!(
+                "{}: Input exceeds the configured limit of {} {}",
+                context,
+                limit,
+                unit
+            )
+        }
+        AlsError::OutputTooLarge { limit } => {
+            anyhow::anyhow!(
+                "{}: Expanded output exceeds the configured limit of {} values",
+                context,
+                limit
+            )
+        }
         AlsError::ColumnMismatch { schema, data } => {
             anyhow::anyhow!(
                 "{}: Column count mismatch: schema has {} columns, data has {} columns",
@@ -791,8 +2043,108 @@ fn map_als_error(error: AlsError, context: &str) -> anyhow::Error {
                 data
             )
         }
+        AlsError::SchemaMismatch { expected, found } => {
+            anyhow::anyhow!(
+                "{}: Schema mismatch: expected {:?}, found {:?}",
+                context,
+                expected,
+                found
+            )
+        }
+        AlsError::SentinelMismatch { .. } => anyhow::anyhow!("{}: {}", context, error),
+        AlsError::ColumnNotFound { column } => {
+            anyhow::anyhow!("{}: Column not found: {}", context, column)
+        }
+        AlsError::UnsupportedSimdLevel { level } => {
+            anyhow::anyhow!(
+                "{}: Unsupported SIMD level: {} is not available on this CPU",
+                context,
+                level
+            )
+        }
         AlsError::IoError(e) => {
             anyhow::anyhow!("{}: IO error: {}", context, e)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use als_compression::Column;
+
+    #[test]
+    fn test_values_equivalent_normalizes_numbers() {
+        assert!(values_equivalent(&Value::Integer(3), &Value::Float(3.0)));
+        assert!(values_equivalent(&Value::Float(3.0), &Value::Integer(3)));
+        assert!(values_equivalent(&Value::Float(f64::NAN), &Value::Float(f64::NAN)));
+        assert!(!values_equivalent(&Value::Integer(3), &Value::Float(3.5)));
+        assert!(!values_equivalent(&Value::Integer(1), &Value::Boolean(true)));
+    }
+
+    #[test]
+    fn test_find_first_mismatch_reports_location() {
+        let mut original = TabularData::new();
+        original.add_column(Column::new("id", vec![Value::Integer(1), Value::Integer(2)]));
+        original.add_column(Column::new(
+            "name",
+            vec![Value::String("a".into()), Value::String("b".into())],
+        ));
+
+        let mut round_trip = TabularData::new();
+        round_trip.add_column(Column::new("id", vec![Value::Float(1.0), Value::Integer(2)]));
+        round_trip.add_column(Column::new(
+            "name",
+            vec![Value::String("a".into()), Value::String("c".into())],
+        ));
+
+        let mismatch = find_first_mismatch(&original, &round_trip).unwrap();
+        assert_eq!(mismatch.row, 1);
+        assert_eq!(mismatch.column, "name");
+        assert_eq!(mismatch.expected, "b");
+        assert_eq!(mismatch.found, "c");
+
+        assert!(find_first_mismatch(&original, &original).is_none());
+    }
+
+    #[test]
+    fn test_parse_csv_delimiter() {
+        assert_eq!(parse_csv_delimiter(";"), Ok(b';'));
+        assert_eq!(parse_csv_delimiter("|"), Ok(b'|'));
+        assert_eq!(parse_csv_delimiter("\\t"), Ok(b'\t'));
+        assert!(parse_csv_delimiter("").is_err());
+        assert!(parse_csv_delimiter(";;").is_err());
+        assert!(parse_csv_delimiter("\"").is_err());
+        assert!(parse_csv_delimiter("é").is_err());
+    }
+
+    #[test]
+    fn test_parse_threads() {
+        assert_eq!(parse_threads("0"), Ok(0));
+        assert_eq!(parse_threads("8"), Ok(8));
+        assert_eq!(parse_threads("1024"), Ok(1024));
+        assert!(parse_threads("1025").is_err());
+        assert!(parse_threads("-1").is_err());
+        assert!(parse_threads("many").is_err());
+    }
+
+    #[test]
+    fn test_detect_format_tsv() {
+        assert!(matches!(detect_format("data.tsv", "a,b\n1,2"), Format::Tsv));
+        assert!(matches!(detect_format("-", "a\tb\n1\t2"), Format::Tsv));
+        assert!(matches!(detect_format("-", "a,b\tc\n1,2"), Format::Csv));
+    }
+
+    #[test]
+    fn test_detect_format_ndjson() {
+        assert!(matches!(detect_format("logs.ndjson", ""), Format::Ndjson));
+        assert!(matches!(detect_format("logs.jsonl", ""), Format::Ndjson));
+        assert!(matches!(
+            detect_format("-", "{\"a\": 1}\n{\"a\": 2}\n"),
+            Format::Ndjson
+        ));
+        assert!(matches!(detect_format("-", "{\"a\": 1}\n"), Format::Json));
+        assert!(matches!(detect_format("-", "{\n  \"a\": 1\n}"), Format::Json));
+        assert!(matches!(detect_format("-", "[{\"a\": 1}]"), Format::Json));
+    }
+}
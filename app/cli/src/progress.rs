@@ -0,0 +1,167 @@
+//! Byte-based progress for `als compress --progress`.
+//!
+//! When the input is a file its length is known up front, so a determinate
+//! bar shows bytes read out of the file size. CSV and JSON files are
+//! compressed straight from the file, so the bar follows parsing; other
+//! files drive it while being read into memory. Stdin has no known length
+//! and keeps the indeterminate spinner.
+
+use crate::{create_progress_bar, map_als_error, read_input, Format};
+use als_compression::AlsCompressor;
+use anyhow::{Context, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fs::File;
+use std::io::Read;
+
+/// Bytes read from the input between progress updates
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Read input from file or stdin, showing a byte progress bar for files
+pub fn read_input_with_progress(input: &str, quiet: bool) -> Result<String> {
+    if input == "-" {
+        let progress = create_progress_bar(quiet, "Reading input");
+        let content = read_input(input);
+        progress.finish_and_clear();
+        return content;
+    }
+
+    let (file, len) = open_input(input)?;
+    let progress = create_byte_progress_bar(quiet, len, "Reading input");
+    let content = read_with_progress(file, &progress)
+        .with_context(|| format!("Failed to read input file: {}", input));
+    progress.finish_and_clear();
+    content
+}
+
+/// Compress a CSV or JSON file as it is read, showing a byte progress bar
+///
+/// Returns the input size and the ALS text, or `None` for an empty file.
+pub fn compress_file_with_progress(
+    compressor: &AlsCompressor,
+    input: &str,
+    format: Format,
+    quiet: bool,
+) -> Result<Option<(usize, String)>> {
+    let (file, len) = open_input(input)?;
+    if len == 0 {
+        return Ok(None);
+    }
+
+    let progress = create_byte_progress_bar(quiet, len, "Compressing");
+    let compressed = compress_with_progress(compressor, file, format, &progress);
+    progress.finish_and_clear();
+    Ok(Some((len as usize, compressed?)))
+}
+
+/// Compress CSV or JSON from `reader`, advancing `progress` by each read
+fn compress_with_progress(
+    compressor: &AlsCompressor,
+    reader: impl Read,
+    format: Format,
+    progress: &ProgressBar,
+) -> Result<String> {
+    let reader = progress.wrap_read(reader);
+    if matches!(format, Format::Json) {
+        compressor
+            .compress_json_reader(reader)
+            .map_err(|e| map_als_error(e, "JSON compression"))
+    } else {
+        compressor
+            .compress_csv_reader(reader)
+            .map_err(|e| map_als_error(e, "CSV compression"))
+    }
+}
+
+/// Open an input file and get its length
+fn open_input(input: &str) -> Result<(File, u64)> {
+    let file = File::open(input).with_context(|| format!("Failed to read input file: {}", input))?;
+    let len = file
+        .metadata()
+        .with_context(|| format!("Failed to read input file: {}", input))?
+        .len();
+    Ok((file, len))
+}
+
+/// Create a progress bar counting `len` bytes
+fn create_byte_progress_bar(quiet: bool, len: u64, message: &str) -> ProgressBar {
+    if quiet {
+        return ProgressBar::hidden();
+    }
+
+    let pb = ProgressBar::new(len);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{msg} [{bar:40.green/white}] {bytes}/{total_bytes} ({eta})")
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    pb.set_message(message.to_string());
+    pb
+}
+
+/// Read UTF-8 text from `reader` in chunks, advancing `progress` by each chunk
+fn read_with_progress(reader: impl Read, progress: &ProgressBar) -> Result<String> {
+    let mut reader = progress.wrap_read(reader);
+    let mut bytes = Vec::with_capacity(progress.length().unwrap_or(0) as usize);
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(n) => bytes.extend_from_slice(&chunk[..n]),
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    String::from_utf8(bytes).context("Input is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indicatif::ProgressDrawTarget;
+    use std::io::Write;
+
+    #[test]
+    fn test_read_with_progress_reaches_file_length() {
+        let content = "id,name\n".to_string() + &"1,alpha\n".repeat(20_000);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let len = file.as_file().metadata().unwrap().len();
+        let progress = ProgressBar::with_draw_target(Some(len), ProgressDrawTarget::hidden());
+        let read = read_with_progress(File::open(file.path()).unwrap(), &progress).unwrap();
+
+        assert_eq!(read, content);
+        assert!(len as usize > CHUNK_SIZE);
+        assert_eq!(progress.position(), len);
+        assert_eq!(progress.length(), Some(len));
+    }
+
+    #[test]
+    fn test_compress_with_progress_reaches_input_length() {
+        let compressor = AlsCompressor::new();
+        let csv = "id,name\n".to_string() + &"1,alpha\n".repeat(20_000);
+        let json = format!("[{}]", vec![r#"{"id":1,"name":"alpha"}"#; 5_000].join(","));
+
+        for (format, content) in [(Format::Csv, &csv), (Format::Json, &json)] {
+            let len = content.len() as u64;
+            let progress = ProgressBar::with_draw_target(Some(len), ProgressDrawTarget::hidden());
+            let als = compress_with_progress(&compressor, content.as_bytes(), format, &progress).unwrap();
+
+            let expected = match format {
+                Format::Json => compressor.compress_json(content).unwrap(),
+                _ => compressor.compress_csv(content).unwrap(),
+            };
+            assert_eq!(als, expected);
+            assert_eq!(progress.position(), len);
+        }
+    }
+
+    #[test]
+    fn test_read_with_progress_rejects_invalid_utf8() {
+        let progress = ProgressBar::hidden();
+        let err = read_with_progress(&[0x66, 0xff, 0x6f][..], &progress).unwrap_err();
+        assert!(err.to_string().contains("UTF-8"), "{}", err);
+        assert_eq!(progress.position(), 3);
+    }
+}
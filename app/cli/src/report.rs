@@ -0,0 +1,221 @@
+//! Compression report formatting for `compress --stats`.
+
+use crate::format_bytes;
+use als_compression::{ColumnStats, CompressionReport, PatternType};
+use std::io::{self, Write};
+
+/// Write a `CompressionReport` as an aligned per-column table followed by a summary.
+pub fn print_compression_report(report: &CompressionReport, out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out)?;
+    writeln!(out, "=== Compression Report ===")?;
+    writeln!(out)?;
+
+    if report.columns.is_empty() {
+        writeln!(out, "No columns to report")?;
+        return Ok(());
+    }
+
+    let storage = if report.used_ctx_fallback {
+        "ctx-fallback"
+    } else {
+        "als"
+    };
+
+    let rows: Vec<[String; 10]> = report
+        .columns
+        .iter()
+        .map(|column| {
+            [
+                column.name.clone(),
+                pattern_label(column.pattern_type).to_string(),
+                storage.to_string(),
+                format_bytes(column.input_bytes),
+                format_bytes(column.output_bytes),
+                format_signed_bytes(column.bytes_saved()),
+                format!("{:.2}x", column.compression_ratio()),
+                column.values.distinct_count.to_string(),
+                format!(
+                    "{}/{}/{}",
+                    column.values.min_value_length,
+                    column.values.median_value_length,
+                    column.values.max_value_length
+                ),
+                format!("{:.1}", column.values.mean_value_length),
+            ]
+        })
+        .collect();
+
+    let headers = ["Column", "Pattern", "Storage", "Input", "Output", "Saved", "Ratio", "Distinct", "Len min/med/max", "Len mean"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    // Text columns are left-aligned, numeric columns right-aligned
+    let write_row = |out: &mut dyn Write, cells: &[&str]| -> io::Result<()> {
+        let mut line = String::new();
+        for (i, (cell, width)) in cells.iter().zip(widths).enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+            if i < 3 {
+                line.push_str(&format!("{:<width$}", cell, width = width));
+            } else {
+                line.push_str(&format!("{:>width$}", cell, width = width));
+            }
+        }
+        writeln!(out, "  {}", line.trim_end())
+    };
+
+    write_row(out, &headers)?;
+    let total_width = widths.iter().sum::<usize>() + 2 * (widths.len() - 1);
+    writeln!(out, "  {}", "-".repeat(total_width))?;
+    for row in &rows {
+        let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+        write_row(out, &cells)?;
+    }
+
+    writeln!(out)?;
+    if report.used_ctx_fallback {
+        writeln!(
+            out,
+            "  CTX fallback:        yes (ALS ratio below threshold; patterns shown were not used)"
+        )?;
+    }
+    if let Some(column) = report.most_effective_column() {
+        writeln!(out, "  Most effective:      {}", describe_column(column))?;
+    }
+    if let Some(column) = report.least_effective_column() {
+        writeln!(out, "  Least effective:     {}", describe_column(column))?;
+    }
+    writeln!(
+        out,
+        "  Columns compressed:  {}/{}",
+        report.compressed_column_count(),
+        report.columns.len()
+    )?;
+    writeln!(
+        out,
+        "  Total saved:         {}",
+        format_signed_bytes(report.total_bytes_saved())
+    )?;
+    writeln!(
+        out,
+        "  Dictionary use:      {:.1}%",
+        report.dictionary_utilization * 100.0
+    )?;
+
+    Ok(())
+}
+
+/// Short label for a detected pattern type.
+fn pattern_label(pattern_type: PatternType) -> &'static str {
+    match pattern_type {
+        PatternType::Sequential => "sequential",
+        PatternType::Arithmetic => "arithmetic",
+        PatternType::SparseRange => "sparse-range",
+        PatternType::PatchedRange => "patched-range",
+        PatternType::Repeat => "repeat",
+        PatternType::Toggle => "toggle",
+        PatternType::RunList => "run-list",
+        PatternType::RepeatedRange => "repeated-range",
+        PatternType::RepeatedToggle => "repeated-toggle",
+        PatternType::Interleave => "interleave",
+        PatternType::Delta => "delta",
+        PatternType::Geometric => "geometric",
+        PatternType::Timestamp => "timestamp",
+        PatternType::IpRange => "ip-range",
+        PatternType::Affix => "affix",
+        PatternType::IntDictionary => "int-dict",
+        PatternType::Raw => "raw",
+    }
+}
+
+/// Describe a column by name, pattern, and ratio.
+fn describe_column(column: &ColumnStats) -> String {
+    format!(
+        "{} ({}, {:.2}x)",
+        column.name,
+        pattern_label(column.pattern_type),
+        column.compression_ratio()
+    )
+}
+
+/// Format a possibly negative byte count.
+fn format_signed_bytes(bytes: i64) -> String {
+    if bytes < 0 {
+        format!("-{}", format_bytes(bytes.unsigned_abs() as usize))
+    } else {
+        format_bytes(bytes as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use als_compression::{CompressionStats, Value, ValueStats};
+
+    fn render(report: &CompressionReport) -> String {
+        let mut out = Vec::new();
+        print_compression_report(report, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn sample_columns() -> Vec<ColumnStats> {
+        vec![
+            ColumnStats::new("id".to_string(), 0, 400, 6, PatternType::Sequential, 100),
+            ColumnStats::new("customer_name".to_string(), 1, 300, 320, PatternType::Raw, 100),
+        ]
+    }
+
+    #[test]
+    fn test_report_table_is_aligned() {
+        let report = CompressionReport::new(CompressionStats::new().snapshot(), sample_columns(), false, 0.5);
+        let text = render(&report);
+
+        let lines: Vec<&str> = text.lines().filter(|l| l.contains(" als ")).collect();
+        assert_eq!(lines.len(), 2);
+        // Storage column starts at the same offset in every row
+        let offsets: Vec<usize> = lines.iter().map(|l| l.find(" als ").unwrap()).collect();
+        assert_eq!(offsets[0], offsets[1]);
+
+        assert!(text.contains("Most effective:      id (sequential, 66.67x)"));
+        assert!(text.contains("Least effective:     customer_name (raw, 0.94x)"));
+        assert!(text.contains("Columns compressed:  1/2"));
+        assert!(text.contains("Dictionary use:      50.0%"));
+        assert!(!text.contains("CTX fallback"));
+    }
+
+    #[test]
+    fn test_report_marks_ctx_fallback() {
+        let report = CompressionReport::new(CompressionStats::new().snapshot(), sample_columns(), true, 0.0);
+        let text = render(&report);
+
+        assert!(text.contains("ctx-fallback"));
+        assert!(text.contains("CTX fallback:        yes"));
+        assert!(!text.contains(" als "));
+    }
+
+    #[test]
+    fn test_report_shows_value_metrics() {
+        let values = [Value::string("abc"), Value::string("abcde"), Value::string("abc")];
+        let columns = vec![
+            ColumnStats::new("code".to_string(), 0, 11, 8, PatternType::Raw, 3).with_values(ValueStats::from_values(&values)),
+        ];
+        let report = CompressionReport::new(CompressionStats::new().snapshot(), columns, false, 0.0);
+        let text = render(&report);
+
+        assert!(text.contains("Distinct  Len min/med/max  Len mean"));
+        let row = text.lines().find(|l| l.contains("code")).unwrap();
+        let cells: Vec<&str> = row.split_whitespace().collect();
+        assert_eq!(&cells[cells.len() - 3..], ["2", "3/3/5", "3.7"]);
+    }
+
+    #[test]
+    fn test_format_signed_bytes() {
+        assert_eq!(format_signed_bytes(-20), format!("-{}", format_bytes(20)));
+        assert_eq!(format_signed_bytes(20), format_bytes(20));
+    }
+}
@@ -0,0 +1,93 @@
+//! Integration tests for `als batch`.
+
+use assert_cmd::Command;
+use std::fs;
+
+fn numbered_csv(rows: usize) -> String {
+    let mut csv = String::from("id,status\n");
+    for i in 1..=rows {
+        csv.push_str(&format!("{},active\n", i));
+    }
+    csv
+}
+
+#[test]
+fn test_batch_compresses_directory_preserving_layout() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    fs::create_dir(input.path().join("nested")).unwrap();
+    fs::write(input.path().join("first.csv"), numbered_csv(50)).unwrap();
+    fs::write(input.path().join("nested/second.csv"), numbered_csv(80)).unwrap();
+    fs::write(input.path().join("notes.txt"), "not a data file").unwrap();
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["batch", "--threads", "2", "--input-dir"])
+        .arg(input.path())
+        .arg("--output-dir")
+        .arg(output.path())
+        .assert()
+        .success();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("2 compressed, 0 failed"), "{}", stderr);
+    assert!(stderr.contains("Avg ratio:"), "{}", stderr);
+
+    let first = fs::read_to_string(output.path().join("first.als")).unwrap();
+    let second = fs::read_to_string(output.path().join("nested/second.als")).unwrap();
+    assert!(first.contains("1>50"), "{}", first);
+    assert!(second.contains("1>80"), "{}", second);
+    assert!(!output.path().join("notes.als").exists());
+
+    // The outputs decompress back to the inputs
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["decompress", "-i"])
+        .arg(output.path().join("nested/second.als"))
+        .assert()
+        .success()
+        .stdout(numbered_csv(80));
+}
+
+#[test]
+fn test_batch_continues_past_failing_files() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    fs::write(input.path().join("bad.json"), "{ not json").unwrap();
+    fs::write(input.path().join("good.csv"), numbered_csv(20)).unwrap();
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["batch", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(output.path())
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("1 compressed, 1 failed"), "{}", stderr);
+    assert!(stderr.contains("✗ bad.json"), "{}", stderr);
+    assert!(stderr.contains("1 of 2 files failed to compress"), "{}", stderr);
+
+    assert!(output.path().join("good.als").exists());
+    assert!(!output.path().join("bad.als").exists());
+}
+
+#[test]
+fn test_batch_pattern_selects_files() {
+    let input = tempfile::tempdir().unwrap();
+    let output = tempfile::tempdir().unwrap();
+    fs::write(input.path().join("keep.csv"), numbered_csv(20)).unwrap();
+    fs::write(input.path().join("skip.csv"), numbered_csv(20)).unwrap();
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["batch", "-q", "-p", "k*.csv", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(output.path())
+        .assert()
+        .success();
+
+    assert!(output.path().join("keep.als").exists());
+    assert!(!output.path().join("skip.als").exists());
+}
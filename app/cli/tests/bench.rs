@@ -0,0 +1,58 @@
+//! Integration tests for `als bench`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_csv;
+
+fn sample_csv() -> String {
+    let mut csv = String::from("id,status\n");
+    for i in 1..=100 {
+        csv.push_str(&format!("{},active\n", i));
+    }
+    csv
+}
+
+#[test]
+fn test_bench_reports_every_simd_level() {
+    let input = write_csv(&sample_csv());
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["bench", "-n", "2", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+
+    assert!(stdout.contains("=== Benchmark ==="), "{}", stdout);
+    assert!(stdout.contains("2 iterations per level"), "{}", stdout);
+
+    // The scalar level always runs; the others either run or are skipped
+    let scalar = stdout
+        .lines()
+        .find(|line| line.starts_with("Scalar"))
+        .unwrap_or_else(|| panic!("missing scalar row:\n{}", stdout));
+    assert!(scalar.contains("MB/s"), "{}", scalar);
+    assert!(scalar.trim_end().ends_with('x'), "{}", scalar);
+    for level in ["AVX-512", "AVX2", "NEON"] {
+        assert!(
+            stdout.lines().any(|line| line.starts_with(level)),
+            "missing {} row:\n{}",
+            level,
+            stdout
+        );
+    }
+}
+
+#[test]
+fn test_bench_rejects_zero_iterations() {
+    let input = write_csv(&sample_csv());
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["bench", "-n", "0", "-i"])
+        .arg(input.path())
+        .assert()
+        .failure();
+}
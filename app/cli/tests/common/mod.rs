@@ -0,0 +1,44 @@
+//! Helpers shared by the CLI integration tests.
+//!
+//! Each test file compiles this module on its own and uses only part of it.
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use std::io::Write;
+
+/// ALS document with an integer, a string and a float column, with a null
+pub const ALS: &str = "#id #name #score\n1>3|Alice Bob _|1.5 2.5 3.5";
+
+/// Write `content` to a temporary file whose name ends with `suffix`
+pub fn write_temp(suffix: &str, content: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+/// Write `content` to a temporary `.csv` file
+pub fn write_csv(content: &str) -> tempfile::NamedTempFile {
+    write_temp(".csv", content)
+}
+
+/// Write `content` to a temporary `.als` file
+pub fn write_als(content: &str) -> tempfile::NamedTempFile {
+    write_temp(".als", content)
+}
+
+/// Run `als -q decompress` with `args` on `input` from stdin and return stdout
+pub fn decompress(input: impl Into<Vec<u8>>, args: &[&str]) -> Vec<u8> {
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "decompress"])
+        .args(args)
+        .write_stdin(input)
+        .assert()
+        .success();
+    assert.get_output().stdout.clone()
+}
+
+/// Like [`decompress`], for text output
+pub fn decompress_text(input: impl Into<Vec<u8>>, args: &[&str]) -> String {
+    String::from_utf8(decompress(input, args)).unwrap()
+}
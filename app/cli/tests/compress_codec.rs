@@ -0,0 +1,70 @@
+//! Integration tests for `als compress --codec` and codec detection on decompress.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{decompress_text, write_csv};
+use std::io::Write;
+
+const CSV: &str = "id,status\n1,active\n2,inactive\n3,active\n4,inactive\n";
+
+fn compress(codec: &str) -> Vec<u8> {
+    let input = write_csv(CSV);
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "--codec", codec, "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    assert.get_output().stdout.clone()
+}
+
+#[test]
+fn test_codec_none_writes_plain_als() {
+    let als = compress("none");
+    assert!(std::str::from_utf8(&als).unwrap().contains("#id #status"));
+    assert_eq!(decompress_text(als, &[]), CSV);
+}
+
+#[test]
+fn test_gzip_roundtrip() {
+    let als = compress("gzip");
+    assert_eq!(als[0], 0x81);
+    assert_eq!(decompress_text(als, &[]), CSV);
+}
+
+#[test]
+fn test_zstd_roundtrip() {
+    let als = compress("zstd");
+    assert_eq!(als[0], 0x82);
+    assert_eq!(decompress_text(als, &[]), CSV);
+}
+
+#[test]
+fn test_info_reads_encoded_input() {
+    let als = compress("zstd");
+    let mut file = tempfile::Builder::new().suffix(".als").tempfile().unwrap();
+    file.write_all(&als).unwrap();
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["info", "--json", "-i"])
+        .arg(file.path())
+        .assert()
+        .success();
+    let report: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(report["row_count"], 4);
+}
+
+#[test]
+fn test_corrupt_codec_stream_fails() {
+    let mut als = compress("gzip");
+    als.truncate(als.len() / 2);
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "decompress"])
+        .write_stdin(als)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Decoding ALS input"));
+}
@@ -0,0 +1,150 @@
+//! Integration tests for `als compress --dry-run`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{write_csv, write_temp};
+
+fn patterned_csv() -> String {
+    let mut csv = String::from("id,status,flag\n");
+    for i in 1..=200 {
+        let flag = if i % 2 == 0 { "on" } else { "off" };
+        csv.push_str(&format!("{},active,{}\n", i, flag));
+    }
+    csv
+}
+
+fn random_csv() -> String {
+    // Small LCG so the data is noisy but the test stays deterministic
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        state >> 33
+    };
+
+    let mut csv = String::from("token,amount\n");
+    for _ in 0..200 {
+        csv.push_str(&format!("{:x},{}\n", next(), next() % 1_000_003));
+    }
+    csv
+}
+
+fn dry_run(input: &tempfile::NamedTempFile, extra: &[&str]) -> (String, std::path::PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out.als");
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(extra)
+        .args(["compress", "--dry-run", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .success();
+
+    assert!(!output.exists(), "dry run wrote {}", output.display());
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    (stdout, output)
+}
+
+#[test]
+fn test_dry_run_reports_als_for_patterned_data() {
+    let input = write_csv(&patterned_csv());
+    let (stdout, _) = dry_run(&input, &[]);
+
+    assert!(stdout.contains("Dry run: no output written"), "{}", stdout);
+    assert!(stdout.contains("Format:      ALS"), "{}", stdout);
+    assert!(stdout.contains("Raw columns: none"), "{}", stdout);
+
+    let ratio: f64 = stdout
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Ratio:"))
+        .and_then(|value| value.trim().trim_end_matches('x').parse().ok())
+        .unwrap_or_else(|| panic!("missing ratio:\n{}", stdout));
+    assert!(ratio > 5.0, "{}", stdout);
+}
+
+#[test]
+fn test_dry_run_reports_ctx_for_random_data() {
+    let input = write_csv(&random_csv());
+    let (stdout, _) = dry_run(&input, &[]);
+
+    assert!(stdout.contains("Format:      CTX"), "{}", stdout);
+    assert!(stdout.contains("Raw columns: all (CTX fallback)"), "{}", stdout);
+}
+
+#[test]
+fn test_dry_run_quiet_prints_only_format() {
+    let input = write_csv(&patterned_csv());
+    let (stdout, _) = dry_run(&input, &["--quiet"]);
+    assert_eq!(stdout, "als\n");
+
+    let input = write_csv(&random_csv());
+    let (stdout, _) = dry_run(&input, &["--quiet"]);
+    assert_eq!(stdout, "ctx\n");
+}
+
+#[test]
+fn test_dry_run_with_stats_includes_report() {
+    let input = write_csv(&patterned_csv());
+    let (stdout, _) = dry_run(&input, &[]);
+    assert!(!stdout.contains("=== Compression Report ==="));
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out.als");
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--dry-run", "--stats", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+
+    assert!(stdout.contains("=== Compression Report ==="), "{}", stdout);
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_dry_run_applies_config_limits() {
+    let input = write_csv(&patterned_csv());
+    let config = write_temp(".toml", "max_input_bytes = 10\n");
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .arg("-c")
+        .arg(config.path())
+        .args(["-q", "compress", "--dry-run", "-i"])
+        .arg(input.path())
+        .assert()
+        .failure()
+        .stdout("");
+}
+
+#[test]
+fn test_dry_run_matches_compressed_size() {
+    let input = write_temp(".json", r#"[{"ID": 1, "id": 2}, {"ID": 3, "id": 4}]"#);
+    let config = write_temp(".toml", "case_insensitive_keys = true\n");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("out.als");
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .arg("-c")
+        .arg(config.path())
+        .args(["-q", "compress", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .success();
+    let written = std::fs::metadata(&output).unwrap().len();
+
+    let (stdout, _) = dry_run(&input, &["-c", config.path().to_str().unwrap()]);
+    let expected = format!("Output:      {} B", written);
+    assert!(stdout.contains(&expected), "expected {:?} in:\n{}", expected, stdout);
+}
@@ -0,0 +1,63 @@
+//! Integration tests for `als compress --level`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{decompress_text, write_csv};
+
+fn patterned_csv() -> String {
+    let mut csv = String::from("id,cycle,status\n");
+    for i in 0..300 {
+        let status = ["accepted", "rejected", "pending"][(i * 7 % 11) % 3];
+        csv.push_str(&format!("{},{},{}\n", i, i % 5 + 1, status));
+    }
+    csv
+}
+
+fn compress(input: &tempfile::NamedTempFile, extra: &[&str]) -> String {
+    let output = Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "compress", "-i"])
+        .arg(input.path())
+        .args(extra)
+        .assert()
+        .success();
+    String::from_utf8(output.get_output().stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_default_level_matches_no_flag() {
+    let input = write_csv(&patterned_csv());
+    assert_eq!(compress(&input, &["--level", "6"]), compress(&input, &[]));
+}
+
+#[test]
+fn test_every_level_round_trips() {
+    let csv = patterned_csv();
+    let input = write_csv(&csv);
+    for level in 0..=9 {
+        let level = level.to_string();
+        let als = compress(&input, &["--level", &level]);
+        assert_eq!(decompress_text(als.as_str(), &[]), csv, "level {}", level);
+    }
+}
+
+#[test]
+fn test_high_level_not_larger_than_low_level() {
+    let input = write_csv(&patterned_csv());
+    let fastest = compress(&input, &["--level", "0"]);
+    let smallest = compress(&input, &["-l", "9"]);
+    assert!(smallest.len() <= fastest.len(), "{} > {}", smallest.len(), fastest.len());
+    assert!(!fastest.contains('$'));
+}
+
+#[test]
+fn test_level_out_of_range_rejected() {
+    let input = write_csv(&patterned_csv());
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--level", "10", "-i"])
+        .arg(input.path())
+        .assert()
+        .failure();
+}
@@ -0,0 +1,73 @@
+//! Integration tests for `als compress --no-ctx-fallback`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_csv;
+
+fn random_csv() -> String {
+    // Small LCG so the data is noisy but the test stays deterministic
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        state >> 33
+    };
+
+    let mut csv = String::from("token,amount\n");
+    for _ in 0..200 {
+        let token: String = (0..6).map(|_| (b'a' + (next() % 26) as u8) as char).collect();
+        csv.push_str(&format!("{},{}\n", token, next() % 1_000_003));
+    }
+    csv
+}
+
+fn compress(input: &tempfile::NamedTempFile, extra: &[&str]) -> (String, String) {
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "-i"])
+        .arg(input.path())
+        .args(extra)
+        .assert()
+        .success();
+    let output = assert.get_output();
+    (
+        String::from_utf8(output.stdout.clone()).unwrap(),
+        String::from_utf8(output.stderr.clone()).unwrap(),
+    )
+}
+
+#[test]
+fn test_no_ctx_fallback_emits_als_for_noisy_data() {
+    let csv = random_csv();
+    let input = write_csv(&csv);
+
+    let (default, _) = compress(&input, &[]);
+    assert!(default.starts_with("!ctx"), "{}", default);
+
+    let (forced, stderr) = compress(&input, &["--no-ctx-fallback"]);
+    assert!(forced.starts_with("!v1"), "{}", forced);
+    assert!(stderr.contains("CTX:         disabled"), "{}", stderr);
+    assert!(stderr.contains("Ratio:"), "{}", stderr);
+
+    // The forced ALS output still decompresses to the input
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "decompress"])
+        .write_stdin(forced)
+        .assert()
+        .success();
+    assert_eq!(String::from_utf8(assert.get_output().stdout.clone()).unwrap(), csv);
+}
+
+#[test]
+fn test_no_ctx_fallback_dry_run_reports_als() {
+    let input = write_csv(&random_csv());
+
+    let (stdout, _) = compress(&input, &["--dry-run"]);
+    assert!(stdout.contains("Format:      CTX"), "{}", stdout);
+
+    let (stdout, _) = compress(&input, &["--dry-run", "--no-ctx-fallback"]);
+    assert!(stdout.contains("Format:      ALS (CTX fallback disabled)"), "{}", stdout);
+}
@@ -0,0 +1,84 @@
+//! Integration tests for `als compress --progress`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{write_csv, write_temp};
+
+fn large_csv() -> String {
+    let mut csv = String::from("id,status,region\n");
+    for i in 1..=20_000 {
+        let region = ["eu", "us", "ap"][i % 3];
+        csv.push_str(&format!("{},active,{}\n", i, region));
+    }
+    csv
+}
+
+fn compress_file(input: &tempfile::NamedTempFile, extra: &[&str]) -> Vec<u8> {
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "-i"])
+        .arg(input.path())
+        .args(extra)
+        .assert()
+        .success();
+    assert.get_output().stdout.clone()
+}
+
+#[test]
+fn test_progress_output_matches_plain_compression() {
+    let input = write_csv(&large_csv());
+
+    let plain = compress_file(&input, &[]);
+    let with_progress = compress_file(&input, &["--progress"]);
+    assert!(!plain.is_empty());
+    assert_eq!(with_progress, plain);
+
+    let quiet = compress_file(&input, &["--progress", "-q"]);
+    assert_eq!(quiet, plain);
+}
+
+#[test]
+fn test_progress_compresses_json_file() {
+    let mut json = String::from("[");
+    for i in 1..=5_000 {
+        json.push_str(&format!(r#"{{"id":{},"status":"active"}},"#, i));
+    }
+    json.pop();
+    json.push(']');
+    let input = write_temp(".json", &json);
+
+    let plain = compress_file(&input, &["-q"]);
+    assert!(!plain.is_empty());
+    assert_eq!(compress_file(&input, &["--progress", "-q"]), plain);
+}
+
+#[test]
+fn test_progress_handles_empty_file() {
+    let input = write_csv("");
+    assert!(compress_file(&input, &["--progress", "-q"]).is_empty());
+}
+
+#[test]
+fn test_progress_falls_back_to_spinner_for_stdin() {
+    let csv = large_csv();
+    let input = write_csv(&csv);
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--progress", "-f", "csv"])
+        .write_stdin(csv)
+        .assert()
+        .success();
+    assert_eq!(assert.get_output().stdout, compress_file(&input, &[]));
+}
+
+#[test]
+fn test_progress_reports_missing_file() {
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--progress", "-i", "does-not-exist.csv"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("does-not-exist.csv"));
+}
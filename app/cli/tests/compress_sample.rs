@@ -0,0 +1,112 @@
+//! Integration tests for `als compress --sample`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_temp;
+
+/// Rows drawn from the same distribution throughout the file.
+fn uniform_csv(rows: usize) -> String {
+    // Small LCG so the data is noisy but the test stays deterministic
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        state >> 33
+    };
+
+    let regions = ["north", "south", "east", "west"];
+    let mut csv = String::from("region,amount,code\n");
+    for _ in 0..rows {
+        let region = regions[(next() % 4) as usize];
+        csv.push_str(&format!("{},{},{:x}\n", region, next() % 10_000, next()));
+    }
+    csv
+}
+
+fn sample(input: &tempfile::NamedTempFile, rows: &str) -> (String, String) {
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--sample", rows, "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    let output = assert.get_output();
+    (
+        String::from_utf8(output.stdout.clone()).unwrap(),
+        String::from_utf8(output.stderr.clone()).unwrap(),
+    )
+}
+
+fn estimated_ratio(stdout: &str) -> f64 {
+    let line = stdout
+        .lines()
+        .find(|line| line.contains("Estimated ratio:"))
+        .unwrap_or_else(|| panic!("missing ratio:\n{}", stdout));
+    line.split_whitespace().last().unwrap().trim_end_matches('x').parse().unwrap()
+}
+
+#[test]
+fn test_sample_ratio_close_to_full_compression() {
+    let csv = uniform_csv(4000);
+    let input = write_temp(".csv", &csv);
+
+    let (stdout, stderr) = sample(&input, "400");
+    assert!(stdout.contains("Estimate from a sample of 400 rows"), "{}", stdout);
+    assert!(stderr.contains("may differ from full compression"), "{}", stderr);
+
+    let full = Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    let full_ratio = csv.len() as f64 / full.get_output().stdout.len() as f64;
+
+    let estimate = estimated_ratio(&stdout);
+    assert!(
+        (estimate - full_ratio).abs() / full_ratio < 0.15,
+        "estimate {} vs full {}",
+        estimate,
+        full_ratio
+    );
+}
+
+#[test]
+fn test_sample_larger_than_input_is_exact() {
+    let csv = uniform_csv(50);
+    let input = write_temp(".csv", &csv);
+
+    let (stdout, stderr) = sample(&input, "1000");
+    assert!(stdout.contains("Estimate from a sample of 50 rows"), "{}", stdout);
+    assert!(!stderr.contains("may differ"), "{}", stderr);
+}
+
+#[test]
+fn test_sample_ndjson_quiet_prints_format() {
+    let mut ndjson = String::new();
+    for i in 0..100 {
+        ndjson.push_str(&format!("{{\"id\": {}, \"level\": \"info\"}}\n", i));
+    }
+    let input = write_temp(".ndjson", &ndjson);
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "--sample", "10", "-f", "ndjson", "-i"])
+        .arg(input.path())
+        .assert()
+        .success()
+        .stdout("als\n");
+}
+
+#[test]
+fn test_sample_conflicts_with_dry_run() {
+    let input = write_temp(".csv", &uniform_csv(10));
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--sample", "5", "--dry-run", "-i"])
+        .arg(input.path())
+        .assert()
+        .failure();
+}
@@ -0,0 +1,144 @@
+//! Integration tests for `als compress --stats`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{write_csv, write_temp};
+
+fn sample_csv() -> String {
+    let mut csv = String::from("id,status,flag\n");
+    for i in 1..=50 {
+        let flag = if i % 2 == 0 { "on" } else { "off" };
+        csv.push_str(&format!("{},active,{}\n", i, flag));
+    }
+    csv
+}
+
+#[test]
+fn test_compress_stats_prints_report_to_stdout() {
+    let input = write_csv(&sample_csv());
+    let output = tempfile::NamedTempFile::new().unwrap();
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--stats", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(output.path())
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+
+    assert!(stdout.contains("=== Compression Report ==="), "{}", stdout);
+
+    // Every column appears in the table with its pattern label
+    let row = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.split_whitespace().next() == Some(name))
+            .unwrap_or_else(|| panic!("missing row for {}:\n{}", name, stdout))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(row("id")[1], "sequential");
+    assert_eq!(row("status")[1], "repeat");
+    assert_eq!(row("flag")[1], "toggle");
+    assert_eq!(row("id")[2], "als");
+
+    assert!(stdout.contains("Most effective:"));
+    assert!(stdout.contains("Least effective:"));
+    assert!(stdout.contains("Dictionary use:"));
+
+    // The compressed output is unaffected by the report
+    let als = std::fs::read_to_string(output.path()).unwrap();
+    assert!(als.contains("#id"));
+    assert!(!als.contains("Compression Report"));
+}
+
+#[test]
+fn test_compress_stats_keeps_stdout_clean_for_als_output() {
+    let input = write_csv(&sample_csv());
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--stats", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    let output = assert.get_output();
+    let stdout = String::from_utf8(output.stdout.clone()).unwrap();
+    let stderr = String::from_utf8(output.stderr.clone()).unwrap();
+
+    assert!(stdout.contains("#id"));
+    assert!(!stdout.contains("Compression Report"));
+    assert!(stderr.contains("Compression Report"));
+}
+
+#[test]
+fn test_compress_stats_marks_ctx_fallback() {
+    let input = write_csv("a,b\nx1,y7\nq2,z3\n");
+    let output = tempfile::NamedTempFile::new().unwrap();
+
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--stats", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(output.path())
+        .assert()
+        .success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+
+    assert!(stdout.contains("ctx-fallback"), "{}", stdout);
+    assert!(stdout.contains("CTX fallback:"), "{}", stdout);
+}
+
+#[test]
+fn test_compress_stats_silent_in_quiet_mode() {
+    let input = write_csv(&sample_csv());
+    let output = tempfile::NamedTempFile::new().unwrap();
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "compress", "--stats", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(output.path())
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("");
+}
+
+/// Run `als -q compress` with `config` on `input`, optionally with `--stats`
+fn compress_with_config(config: &std::path::Path, input: &std::path::Path, stats: bool) -> assert_cmd::assert::Assert {
+    let mut cmd = Command::cargo_bin("als").unwrap();
+    cmd.arg("-c").arg(config).args(["-q", "compress", "-i"]).arg(input);
+    if stats {
+        cmd.arg("--stats");
+    }
+    cmd.assert()
+}
+
+#[test]
+fn test_compress_stats_output_matches_plain_compress() {
+    let input = write_temp(".json", r#"[{"b": 1, "a": "x"}, {"b": 2, "a": "y"}, {"b": 3, "a": "z"}]"#);
+    let config = write_temp(".toml", "json_column_order = \"sorted\"\n");
+
+    let plain = compress_with_config(config.path(), input.path(), false).success();
+    let with_stats = compress_with_config(config.path(), input.path(), true).success();
+
+    let plain = String::from_utf8(plain.get_output().stdout.clone()).unwrap();
+    let with_stats = String::from_utf8(with_stats.get_output().stdout.clone()).unwrap();
+    assert!(plain.contains("#a #b"), "{}", plain);
+    assert_eq!(with_stats, plain);
+}
+
+#[test]
+fn test_compress_stats_enforces_input_limits() {
+    let input = write_csv(&sample_csv());
+    let config = write_temp(".toml", "max_input_bytes = 10\n");
+
+    compress_with_config(config.path(), input.path(), false).failure();
+    compress_with_config(config.path(), input.path(), true).failure();
+}
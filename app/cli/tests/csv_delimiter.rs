@@ -0,0 +1,66 @@
+//! Integration tests for `--csv-delimiter`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_temp;
+
+fn sample_csv() -> String {
+    let mut csv = String::from("id;name;city\n");
+    for i in 1..=30 {
+        csv.push_str(&format!("{};\"Smith, Jane\";\"Paris; France\"\n", i));
+    }
+    csv
+}
+
+#[test]
+fn test_compress_decompress_with_semicolon_delimiter() {
+    let input = write_temp(".csv", &sample_csv());
+    let compressed = tempfile::Builder::new().suffix(".als").tempfile().unwrap();
+    let restored = tempfile::Builder::new().suffix(".csv").tempfile().unwrap();
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "--csv-delimiter", ";", "compress", "-i"])
+        .arg(input.path())
+        .arg("-o")
+        .arg(compressed.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress", "--csv-delimiter", ";", "-i"])
+        .arg(compressed.path())
+        .arg("-o")
+        .arg(restored.path())
+        .assert()
+        .success();
+
+    let output = std::fs::read_to_string(restored.path()).unwrap();
+    assert!(output.starts_with("id;name;city\n"), "{}", output);
+    // Only the field containing the delimiter needs quoting
+    assert!(output.contains("1;Smith, Jane;\"Paris; France\"\n"), "{}", output);
+}
+
+#[test]
+fn test_verify_with_semicolon_delimiter() {
+    let input = write_temp(".csv", &sample_csv());
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "verify", "--csv-delimiter", ";", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_csv_delimiter_rejects_multiple_characters() {
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["compress", "--csv-delimiter", ";;"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("expected a single character"));
+}
@@ -0,0 +1,37 @@
+//! Integration tests for `als decompress --format avro`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{decompress, write_temp, ALS};
+
+#[test]
+fn test_decompress_to_avro_writes_avro_file() {
+    let avro = decompress(ALS, &["-f", "avro"]);
+    assert!(avro.starts_with(b"Obj\x01"));
+    let text = String::from_utf8_lossy(&avro);
+    assert!(text.contains(r#"{"name":"id","type":"long"}"#));
+    assert!(text.contains(r#""type":"double""#));
+    assert!(text.contains("Alice") && text.contains("Bob"));
+}
+
+#[test]
+fn test_decompress_selected_columns_to_avro() {
+    let avro = decompress(ALS, &["-f", "avro", "--select", "name"]);
+    assert!(avro.starts_with(b"Obj\x01"));
+    let text = String::from_utf8_lossy(&avro);
+    assert!(text.contains(r#""name":"name""#));
+    assert!(!text.contains("score"));
+}
+
+#[test]
+fn test_compress_rejects_avro_input() {
+    let file = write_temp(".avro", "Obj\u{1}");
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "-f", "avro", "-i"])
+        .arg(file.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Avro input is not supported"));
+}
@@ -0,0 +1,34 @@
+//! Integration tests for `als decompress --format parquet`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{decompress, write_temp, ALS};
+
+#[test]
+fn test_decompress_to_parquet_writes_parquet_file() {
+    let parquet = decompress(ALS, &["-f", "parquet"]);
+    assert!(parquet.starts_with(b"PAR1"));
+    assert!(parquet.ends_with(b"PAR1"));
+    let text = String::from_utf8_lossy(&parquet);
+    assert!(text.contains("name") && text.contains("score"));
+}
+
+#[test]
+fn test_decompress_selected_columns_to_parquet() {
+    let parquet = decompress(ALS, &["-f", "parquet", "--select", "name"]);
+    assert!(parquet.ends_with(b"PAR1"));
+    assert!(!String::from_utf8_lossy(&parquet).contains("score"));
+}
+
+#[test]
+fn test_compress_rejects_parquet_input() {
+    let file = write_temp(".parquet", "PAR1");
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "-f", "parquet", "-i"])
+        .arg(file.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Parquet input is not supported"));
+}
@@ -0,0 +1,48 @@
+//! Integration tests for `decompress --select`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_temp;
+use predicates::prelude::*;
+
+const ALS: &str = "#id #name #score\n1>3|alice bob carol|7*3";
+
+#[test]
+fn test_decompress_select_by_name() {
+    let input = write_temp(".als", ALS);
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress", "--select", "name,id", "-i"])
+        .arg(input.path())
+        .assert()
+        .success()
+        .stdout("name,id\nalice,1\nbob,2\ncarol,3\n");
+}
+
+#[test]
+fn test_decompress_select_by_index() {
+    let input = write_temp(".als", ALS);
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress", "-f", "tsv", "--select", "2", "-i"])
+        .arg(input.path())
+        .assert()
+        .success()
+        .stdout("score\n7\n7\n7\n");
+}
+
+#[test]
+fn test_decompress_select_unknown_column() {
+    let input = write_temp(".als", ALS);
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress", "--select", "id,city", "-i"])
+        .arg(input.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Column not found: city"));
+}
@@ -0,0 +1,38 @@
+//! Integration tests for `als decompress --format xml`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{decompress_text, write_temp, ALS};
+
+/// Values that need escaping in XML, and a column name that is not an element name
+const SPECIAL_ALS: &str = "#id #first\\ name #note\n1>3|Alice \\\\0 Bob|a<b Tom\\&Jerry \"q\"";
+
+#[test]
+fn test_decompress_to_xml_writes_escaped_rows() {
+    let xml = decompress_text(SPECIAL_ALS, &["-f", "xml"]);
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rows>\n"), "{}", xml);
+    assert!(xml.contains("<row><id>1</id><first_name>Alice</first_name><note>a&lt;b</note></row>"), "{}", xml);
+    assert!(xml.contains("<row><id>2</id><first_name/><note>Tom&amp;Jerry</note></row>"), "{}", xml);
+    assert!(xml.contains("<note>&quot;q&quot;</note>"), "{}", xml);
+    assert!(xml.ends_with("</rows>\n"));
+}
+
+#[test]
+fn test_decompress_selected_columns_to_xml() {
+    let xml = decompress_text(ALS, &["-f", "xml", "--select", "id"]);
+    assert!(xml.contains("<row><id>3</id></row>"), "{}", xml);
+    assert!(!xml.contains("name"));
+}
+
+#[test]
+fn test_compress_rejects_xml_input() {
+    let file = write_temp(".xml", "<rows></rows>");
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "-f", "xml", "-i"])
+        .arg(file.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("XML input is not supported"));
+}
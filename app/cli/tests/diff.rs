@@ -0,0 +1,94 @@
+//! Integration tests for `als diff`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_temp;
+
+const SOURCE_CSV: &str = "id,name,score\n1,Alice,90\n2,Bob,85\n3,Carol,85\n4,Dave,70\n";
+
+fn compress(csv: &tempfile::NamedTempFile) -> tempfile::NamedTempFile {
+    let als = tempfile::Builder::new().suffix(".als").tempfile().unwrap();
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "compress", "-i"])
+        .arg(csv.path())
+        .arg("-o")
+        .arg(als.path())
+        .assert()
+        .success();
+    als
+}
+
+fn diff(
+    left: &tempfile::NamedTempFile,
+    right: &tempfile::NamedTempFile,
+    extra: &[&str],
+) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("als")
+        .unwrap()
+        .arg("diff")
+        .arg(left.path())
+        .arg(right.path())
+        .args(extra)
+        .assert()
+}
+
+#[test]
+fn test_diff_als_equals_its_csv_source() {
+    let csv = write_temp(".csv", SOURCE_CSV);
+    let als = compress(&csv);
+
+    let assert = diff(&als, &csv, &[]).success();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Files are equal: 4 rows, 3 columns"), "{}", stdout);
+}
+
+#[test]
+fn test_diff_reports_modified_cell() {
+    let csv = write_temp(".csv", SOURCE_CSV);
+    let als = compress(&csv);
+    let modified = write_temp(".csv", &SOURCE_CSV.replace("Carol", "Caroline"));
+
+    let assert = diff(&als, &modified, &[]).failure();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Files differ"), "{}", stdout);
+    assert!(stdout.contains("Differing cells: 1"), "{}", stdout);
+    assert!(
+        stdout.contains("row 3, column 'name': 'Carol' != 'Caroline'"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_diff_reports_schema_and_row_count() {
+    let left = write_temp(".csv", SOURCE_CSV);
+    let right = write_temp(".csv", "id,name,grade\n1,Alice,A\n2,Bob,B\n");
+
+    let assert = diff(&left, &right, &[]).failure();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("Columns only in left:  score"), "{}", stdout);
+    assert!(stdout.contains("Columns only in right: grade"), "{}", stdout);
+    assert!(stdout.contains("Rows:        4 (left), 2 (right)"), "{}", stdout);
+}
+
+#[test]
+fn test_diff_numeric_representations_unless_strict() {
+    let left = write_temp(".csv", "amount\n3\n4.5\n");
+    let right = write_temp(".csv", "amount\n3.0\n4.5\n");
+
+    diff(&left, &right, &[]).success();
+
+    let assert = diff(&left, &right, &["--strict"]).failure();
+    let stdout = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+    assert!(stdout.contains("row 1, column 'amount': '3' != '3.0'"), "{}", stdout);
+}
+
+#[test]
+fn test_diff_quiet_prints_nothing() {
+    let left = write_temp(".csv", SOURCE_CSV);
+    let right = write_temp(".csv", &SOURCE_CSV.replace("90", "91"));
+
+    diff(&left, &right, &["-q"]).failure().stdout("");
+}
@@ -0,0 +1,56 @@
+//! Integration tests for `als info --header-only`.
+
+mod common;
+
+use assert_cmd::Command;
+use assert_cmd::assert::Assert;
+use common::write_als;
+use predicates::prelude::*;
+
+/// A document whose stream section does not parse.
+const BROKEN_STREAMS: &str = "!v1\n$status:active|inactive\n#id #status\n1>4|5>x\n";
+
+fn info(content: &str, args: &[&str]) -> Assert {
+    let input = write_als(content);
+    Command::cargo_bin("als")
+        .unwrap()
+        .arg("info")
+        .args(args)
+        .arg("-i")
+        .arg(input.path())
+        .assert()
+}
+
+#[test]
+fn test_header_only_skips_streams() {
+    info(BROKEN_STREAMS, &[]).failure();
+
+    let assert = info(BROKEN_STREAMS, &["--json", "--header-only"]).success();
+    let report: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+
+    assert_eq!(report["column_count"], 2);
+    assert_eq!(report["row_count"], serde_json::Value::Null);
+    assert_eq!(report["dictionaries"]["status"], 2);
+    assert_eq!(report["columns"][0]["name"], "id");
+    assert_eq!(report["columns"][1]["name"], "status");
+    assert!(report["columns"][0].get("operators").is_none());
+    assert!(report.get("patterns").is_none());
+}
+
+#[test]
+fn test_header_only_human_output() {
+    info(BROKEN_STREAMS, &["--header-only"])
+        .success()
+        .stdout(predicates::str::contains("Rows: unknown (header only)"))
+        .stdout(predicates::str::contains("1: id"))
+        .stdout(predicates::str::contains("status: 2 entries"))
+        .stdout(predicates::str::contains("Compression Patterns").not());
+}
+
+#[test]
+fn test_full_info_still_reports_rows() {
+    let assert = info("!v1\n#id\n1>3\n", &["--json"]).success();
+    let report: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(report["row_count"], 3);
+    assert_eq!(report["columns"][0]["operators"], 1);
+}
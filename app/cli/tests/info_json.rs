@@ -0,0 +1,100 @@
+//! Integration tests for `als info --json`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_als;
+
+fn info_json(content: &str) -> serde_json::Value {
+    let input = write_als(content);
+    let assert = Command::cargo_bin("als")
+        .unwrap()
+        .args(["info", "--json", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    serde_json::from_slice(&assert.get_output().stdout).unwrap()
+}
+
+#[test]
+fn test_info_json_reports_document_metadata() {
+    let report = info_json("!v1\n$status:active|inactive\n#id #status #flag\n1>4|(_0)*2 (_1)*2|on~off*4\n");
+
+    assert_eq!(report["format"], "als");
+    assert_eq!(report["version"], 1);
+    assert_eq!(report["column_count"], 3);
+    assert_eq!(report["row_count"], 4);
+    assert_eq!(report["dictionaries"]["status"], 2);
+}
+
+#[test]
+fn test_info_json_reports_pattern_counts() {
+    let report = info_json("!v1\n$status:active|inactive\n#id #status #flag\n1>4|(_0)*2 (_1)*2|on~off*4\n");
+
+    let patterns = &report["patterns"];
+    assert_eq!(patterns["ranges"], 1);
+    assert_eq!(patterns["multipliers"], 2);
+    assert_eq!(patterns["toggles"], 1);
+    assert_eq!(patterns["deltas"], 0);
+    assert_eq!(patterns["dict_refs"], 2);
+    assert_eq!(patterns["raw_values"], 0);
+
+    let columns = report["columns"].as_array().unwrap();
+    assert_eq!(columns.len(), 3);
+    assert_eq!(columns[1]["name"], "status");
+    assert_eq!(columns[1]["operators"], 2);
+    assert_eq!(columns[1]["expanded_values"], 4);
+    assert_eq!(columns[1]["patterns"]["dict_refs"], 2);
+    assert_eq!(columns[2]["patterns"]["toggles"], 1);
+}
+
+#[test]
+fn test_info_json_reports_inferred_types() {
+    let report = info_json("!v1\n#id #score #name #flag\n1>3|1 2.5 3|a b c|true false true\n");
+
+    let columns = report["columns"].as_array().unwrap();
+    assert_eq!(columns[0]["type"], "integer");
+    assert_eq!(columns[1]["type"], "float");
+    assert_eq!(columns[2]["type"], "string");
+    assert_eq!(columns[3]["type"], "boolean");
+}
+
+#[test]
+fn test_info_json_reports_value_metrics() {
+    let report = info_json("!v1\n#code #name\nab12*3|a abc abcdef\n");
+
+    let columns = report["columns"].as_array().unwrap();
+    assert_eq!(columns[0]["distinct_count"], 1);
+    assert_eq!(columns[0]["min_value_length"], 4);
+    assert_eq!(columns[0]["max_value_length"], 4);
+    assert_eq!(columns[1]["distinct_count"], 3);
+    assert_eq!(columns[1]["min_value_length"], 1);
+    assert_eq!(columns[1]["median_value_length"], 3);
+    assert_eq!(columns[1]["max_value_length"], 6);
+    assert_eq!(columns[1]["mean_value_length"], 10.0 / 3.0);
+}
+
+#[test]
+fn test_info_without_json_keeps_human_output() {
+    let input = write_als("!v1\n#id\n1>3\n");
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["info", "-i"])
+        .arg(input.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("=== ALS Document Information ==="));
+}
+
+#[test]
+fn test_info_human_output_lists_inferred_types() {
+    let input = write_als("!v1\n#id #name\n1>3|a b c\n");
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["info", "-i"])
+        .arg(input.path())
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("1: id (integer)"))
+        .stdout(predicates::str::contains("2: name (string)"));
+}
@@ -0,0 +1,28 @@
+//! Integration tests for how ALS syntax errors are reported.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+#[test]
+fn test_decompress_syntax_error_shows_source_line() {
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress"])
+        .write_stdin("#id #name\n1>x|alice")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ALS syntax error at line 2, column 3"))
+        .stderr(predicate::str::contains("2 | 1>x|alice\n  |   ^"))
+        .stderr(predicate::str::contains("hint: expected integer after `>`"));
+}
+
+#[test]
+fn test_decompress_select_syntax_error_shows_source_line() {
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress", "--select", "id"])
+        .write_stdin("#id\n10>20:")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ALS parsing: ALS syntax error at line 2, column 7"));
+}
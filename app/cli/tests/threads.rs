@@ -0,0 +1,83 @@
+//! Integration tests for `--threads`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::write_temp;
+
+/// Enough rows and columns to take the parallel path when it is allowed
+fn sample_csv() -> String {
+    let mut csv = String::from("id,status,region,score\n");
+    for i in 0..2000 {
+        let status = ["ok", "error", "pending"][i % 3];
+        let region = ["us-east", "eu-west"][(i / 7) % 2];
+        csv.push_str(&format!("{},{},{},{}\n", i, status, region, (i * 37) % 101));
+    }
+    csv
+}
+
+fn compress(input: &tempfile::NamedTempFile, extra: &[&str]) -> String {
+    let output = Command::cargo_bin("als")
+        .unwrap()
+        .arg("--quiet")
+        .args(extra)
+        .args(["compress", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+    String::from_utf8(output.get_output().stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_single_thread_matches_default_output() {
+    let input = write_temp(".csv", &sample_csv());
+
+    let sequential = compress(&input, &["--threads", "1"]);
+    let auto = compress(&input, &["--threads", "0"]);
+    assert_eq!(sequential, auto);
+    assert_eq!(sequential, compress(&input, &[]));
+}
+
+#[test]
+fn test_single_thread_round_trip() {
+    let csv = sample_csv();
+    let input = write_temp(".csv", &csv);
+    let compressed = write_temp(".als", &compress(&input, &["--threads", "1"]));
+
+    let output = Command::cargo_bin("als")
+        .unwrap()
+        .args(["--quiet", "decompress", "--threads", "1", "-i"])
+        .arg(compressed.path())
+        .assert()
+        .success();
+    let restored = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert_eq!(restored, csv);
+}
+
+#[test]
+fn test_threads_flag_with_config_file() {
+    let input = write_temp(".csv", &sample_csv());
+    let config = write_temp(".toml", "parallelism = 4\n");
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .arg("-c")
+        .arg(config.path())
+        .args(["--quiet", "--threads", "1", "verify", "-i"])
+        .arg(input.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_absurd_thread_count_is_rejected() {
+    let input = write_temp(".csv", "id\n1\n");
+
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["--threads", "100000", "compress", "-i"])
+        .arg(input.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("at most 1024 threads"));
+}
@@ -0,0 +1,33 @@
+//! Integration tests for `als verify`.
+
+mod common;
+
+use assert_cmd::Command;
+use common::{write_csv, write_temp};
+
+fn verify(input: &tempfile::NamedTempFile) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("als")
+        .unwrap()
+        .args(["-q", "verify", "-i"])
+        .arg(input.path())
+        .assert()
+}
+
+#[test]
+fn test_verify_passes_lossless_round_trip() {
+    verify(&write_csv("id,name,price\n1,alice,1.50\n2,bob,2.25\n3,carol,\n")).success();
+}
+
+#[test]
+fn test_verify_fails_when_padding_is_lost() {
+    verify(&write_csv("id,code\n1,007\n2,008\n3,009\n"))
+        .failure()
+        .stderr(predicates::str::contains("column 'code': expected '007', found '7'"));
+}
+
+#[test]
+fn test_verify_fails_when_boolean_text_is_rewritten() {
+    verify(&write_temp(".tsv", "id\tflag\n1\tf\n2\tt\n3\tf\n"))
+        .failure()
+        .stderr(predicates::str::contains("column 'flag': expected 'f', found 'false'"));
+}
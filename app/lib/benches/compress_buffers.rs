@@ -0,0 +1,87 @@
+//! Benchmarks for compressing wide datasets with and without reused buffers.
+//!
+//! A counting allocator reports the allocations of one compression per
+//! variant before timing, since reused buffers mainly save allocations.
+
+use als_compression::{AlsCompressor, Column, CompressionBuffers, CompressorConfig, TabularData, Value};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// System allocator that counts allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Build `columns` columns of `rows` scattered integers and labels.
+fn wide_data(columns: usize, rows: usize) -> TabularData<'static> {
+    let mut data = TabularData::new();
+    for c in 0..columns {
+        let values: Vec<Value> = (0..rows)
+            .map(|r| {
+                let n = (r * 7919 + c * 104_729) % 1000;
+                if c % 2 == 0 {
+                    Value::Integer(n as i64)
+                } else {
+                    Value::string_owned(format!("label-{}", n % 37))
+                }
+            })
+            .collect();
+        data.add_column(Column::new(Cow::Owned(format!("col{}", c)), values));
+    }
+    data
+}
+
+/// Count the allocations made by `f`.
+fn count_allocations(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+fn bench_compress_buffers(c: &mut Criterion) {
+    let compressor = AlsCompressor::with_config(CompressorConfig::new().with_parallelism(1));
+    let data = wide_data(200, 64);
+    let mut buffers = CompressionBuffers::new();
+    compressor.compress_with_buffers(&data, &mut buffers).unwrap();
+
+    let fresh = count_allocations(|| {
+        compressor.compress(&data).unwrap();
+    });
+    let reused = count_allocations(|| {
+        compressor.compress_with_buffers(&data, &mut buffers).unwrap();
+    });
+    eprintln!("allocations per compression: {} fresh, {} with reused buffers", fresh, reused);
+
+    let mut group = c.benchmark_group("compress_buffers");
+    group.throughput(Throughput::Elements((data.column_count() * data.row_count) as u64));
+    group.bench_function("compress", |b| b.iter(|| compressor.compress(black_box(&data)).unwrap()));
+    group.bench_function("compress_with_buffers", |b| {
+        b.iter(|| compressor.compress_with_buffers(black_box(&data), &mut buffers).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_compress_buffers);
+criterion_main!(benches);
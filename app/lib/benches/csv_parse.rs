@@ -0,0 +1,53 @@
+//! Benchmarks for CSV compression with and without SIMD field scanning.
+
+use als_compression::{AlsCompressor, CompressorConfig, SimdConfig};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+/// Build a quote-free CSV document with `rows` data rows.
+fn unquoted_csv(rows: usize) -> String {
+    let mut csv = String::from("id,name,city,score,active\n");
+    for i in 0..rows {
+        csv.push_str(&format!(
+            "{},user_{},city_{},{}.5,{}\n",
+            i,
+            i % 97,
+            i % 13,
+            i % 100,
+            i % 2 == 0
+        ));
+    }
+    csv
+}
+
+/// Build a CSV document with `rows` data rows and a quoted column.
+fn quoted_csv(rows: usize) -> String {
+    let mut csv = String::from("id,note\n");
+    for i in 0..rows {
+        csv.push_str(&format!("{},\"item {}, batch {}\"\n", i, i % 50, i % 7));
+    }
+    csv
+}
+
+fn bench_csv_parse(c: &mut Criterion) {
+    let simd = AlsCompressor::new();
+    let scalar =
+        AlsCompressor::with_config(CompressorConfig::new().with_simd_config(SimdConfig::disabled()));
+
+    let inputs = [("unquoted", unquoted_csv(10_000)), ("quoted", quoted_csv(10_000))];
+
+    let mut group = c.benchmark_group("csv_parse");
+    for (name, input) in &inputs {
+        group.throughput(Throughput::Bytes(input.len() as u64));
+        group.bench_function(format!("{name}/simd"), |b| {
+            b.iter(|| simd.compress_csv(black_box(input)).unwrap())
+        });
+        group.bench_function(format!("{name}/scalar"), |b| {
+            b.iter(|| scalar.compress_csv(black_box(input)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_csv_parse);
+criterion_main!(benches);
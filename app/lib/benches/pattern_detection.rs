@@ -0,0 +1,44 @@
+//! Benchmarks for pattern detection on integer columns that are not ranges.
+
+use als_compression::{PatternDetector, PatternEngine, RangeDetector};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::hint::black_box;
+
+/// Build `rows` pseudo-random integers, as in a measurement column.
+fn scattered_integers(rows: usize) -> Vec<String> {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    (0..rows)
+        .map(|_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+            ((state >> 33) % 100_000).to_string()
+        })
+        .collect()
+}
+
+/// Build `rows` increasing integers with uneven gaps, as in a timestamp column.
+fn jittered_integers(rows: usize) -> Vec<String> {
+    (0..rows).map(|i| (1_700_000_000 + i * 60 + i % 7).to_string()).collect()
+}
+
+fn bench_pattern_detection(c: &mut Criterion) {
+    let range = RangeDetector::new(3);
+    let engine = PatternEngine::new();
+
+    let inputs = [("scattered", scattered_integers(10_000)), ("jittered", jittered_integers(10_000))];
+
+    let mut group = c.benchmark_group("pattern_detection");
+    for (name, column) in &inputs {
+        let values: Vec<&str> = column.iter().map(String::as_str).collect();
+        group.throughput(Throughput::Elements(values.len() as u64));
+        group.bench_function(format!("{name}/range"), |b| {
+            b.iter(|| range.detect(black_box(&values)))
+        });
+        group.bench_function(format!("{name}/engine"), |b| {
+            b.iter(|| engine.detect(black_box(&values)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_pattern_detection);
+criterion_main!(benches);
@@ -8,12 +8,13 @@
  * # Memory Management
  *
  * All strings returned by this API are allocated on the heap and must be freed
- * by calling als_string_free(). Failure to free strings will result in memory leaks.
+ * by calling als_string_free() or als_free(). Failure to free strings will result
+ * in memory leaks.
  *
  * # Error Handling
  *
- * Functions return NULL pointers on error. Use als_get_last_error() to retrieve
- * the error message.
+ * Functions returning pointers return NULL on error; the streaming functions return
+ * an ALS_ERROR_* code instead. Use als_get_last_error() to retrieve the error message.
  *
  * # Thread Safety
  *
@@ -75,6 +76,22 @@ extern "C" {
 
 #include <stddef.h>
 
+/** @brief Success status code */
+#define ALS_OK 0
+/** @brief A required pointer argument was NULL */
+#define ALS_ERROR_NULL_POINTER (-1)
+/** @brief The input could not be compressed */
+#define ALS_ERROR_COMPRESSION (-2)
+/** @brief A chunk callback returned a nonzero value */
+#define ALS_ERROR_CALLBACK (-3)
+/** @brief A panic was caught at the FFI boundary */
+#define ALS_ERROR_PANIC (-4)
+
+/** @brief CSV input format for streaming compression */
+#define ALS_FORMAT_CSV 0
+/** @brief JSON array input format for streaming compression */
+#define ALS_FORMAT_JSON 1
+
 /**
  * @brief Opaque handle to an ALS compressor
  *
@@ -89,6 +106,25 @@ typedef struct AlsCompressorHandle AlsCompressor;
  */
 typedef struct AlsParserHandle AlsParser;
 
+/**
+ * @brief Opaque handle to a streaming compression session
+ *
+ * Created by als_stream_compress_begin() and released by als_stream_compress_end().
+ */
+typedef struct AlsStreamCompressorHandle AlsStreamCompressor;
+
+/**
+ * @brief Callback receiving one compressed chunk of a stream
+ *
+ * The chunk is owned by the callback and must be freed with als_free().
+ *
+ * @param chunk Null-terminated ALS fragment
+ * @param len Length of the chunk in bytes (excluding null terminator)
+ * @param user_data Pointer passed to als_stream_compress_begin()
+ * @return 0 to continue, or nonzero to abort the stream with ALS_ERROR_CALLBACK
+ */
+typedef int (*AlsChunkCallback)(char* chunk, size_t len, void* user_data);
+
 /**
  * @brief Create a new ALS compressor with default configuration
  *
@@ -205,6 +241,57 @@ char* als_to_json(
     size_t len
 );
 
+/**
+ * @brief Begin a streaming compression session
+ *
+ * Compressed chunks are passed to the callback as they become ready, always on the
+ * thread calling als_stream_compress_feed() or als_stream_compress_end(). Concatenated
+ * in order, they form one ALS stream. The session must be released with
+ * als_stream_compress_end(); the compressor may be freed once this function returns.
+ *
+ * @param compressor Pointer to an ALS compressor whose configuration is used (must not be NULL)
+ * @param format ALS_FORMAT_CSV or ALS_FORMAT_JSON (a JSON array)
+ * @param chunk_rows Rows (or JSON objects) per chunk (0 = library default)
+ * @param callback Function receiving each compressed chunk (must not be NULL)
+ * @param user_data Opaque pointer passed to the callback, valid until the stream ends
+ * @return Pointer to the session, or NULL on failure
+ */
+AlsStreamCompressor* als_stream_compress_begin(
+    const AlsCompressor* compressor,
+    int format,
+    size_t chunk_rows,
+    AlsChunkCallback callback,
+    void* user_data
+);
+
+/**
+ * @brief Feed the next piece of input to a streaming session
+ *
+ * Pieces may split rows or UTF-8 sequences anywhere. Blocks while the compression
+ * worker is too far behind the fed input.
+ *
+ * @param stream Pointer to a streaming session (must not be NULL)
+ * @param bytes Pointer to the input piece (must not be NULL)
+ * @param len Length of the piece in bytes
+ * @return ALS_OK, or the error code of the first failure of the stream
+ */
+int als_stream_compress_feed(
+    AlsStreamCompressor* stream,
+    const char* bytes,
+    size_t len
+);
+
+/**
+ * @brief Finish a streaming session and free it
+ *
+ * Delivers the remaining chunks to the callback. The session is freed whatever the
+ * outcome and must not be used again.
+ *
+ * @param stream Pointer to a streaming session (must not be NULL)
+ * @return ALS_OK if the whole stream compressed, or the error code of the first failure
+ */
+int als_stream_compress_end(AlsStreamCompressor* stream);
+
 /**
  * @brief Free a string returned by the ALS library
  *
@@ -214,6 +301,16 @@ char* als_to_json(
  */
 void als_string_free(char* s);
 
+/**
+ * @brief Free memory allocated by the ALS library
+ *
+ * Releases stream chunks passed to an AlsChunkCallback as well as any other string
+ * returned by the library. After calling this function, the pointer must not be used again.
+ *
+ * @param ptr Pointer to free (may be NULL)
+ */
+void als_free(char* ptr);
+
 /**
  * @brief Get the last error message
  *
@@ -0,0 +1,186 @@
+//! Integrity checksums for ALS documents.
+//!
+//! A document may carry a `!crc<hex>` directive on the line after its
+//! version header. The value is the CRC-32 (IEEE) of every byte that follows
+//! the directive line, so corruption is reported before parsing starts
+//! instead of surfacing as a syntax error deep inside a column stream.
+//!
+//! ```text
+//! !v1
+//! !crc3b4f0a9e
+//! #id #name
+//! 1>3|alice bob charlie
+//! ```
+
+use std::borrow::Cow;
+
+use crate::error::{AlsError, Result};
+
+/// Prefix of the checksum directive line.
+pub const CHECKSUM_PREFIX: &str = "!crc";
+
+/// Lookup table for the reflected CRC-32 polynomial `0xEDB88320`.
+const CRC32_TABLE: [u32; 256] = build_crc32_table();
+
+const fn build_crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Compute the CRC-32 (IEEE) checksum of `bytes`.
+///
+/// # Example
+///
+/// ```
+/// use als_compression::als::checksum::crc32;
+/// assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+/// ```
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Insert a checksum directive after the version line of a serialized document.
+///
+/// Documents without a version line get the directive on the first line.
+pub fn add_checksum(serialized: &str) -> String {
+    let split = version_line_end(serialized);
+    let (version, body) = serialized.split_at(split);
+    let mut output = String::with_capacity(serialized.len() + 16);
+    output.push_str(version);
+    output.push_str(&format!("{}{:08x}\n", CHECKSUM_PREFIX, crc32(body.as_bytes())));
+    output.push_str(body);
+    output
+}
+
+/// Verify and strip the checksum directive of a serialized document.
+///
+/// Documents without a directive are returned unchanged. A directive whose
+/// checksum does not match the remaining bytes returns
+/// [`AlsError::ChecksumMismatch`].
+pub fn verify_checksum(input: &str) -> Result<Cow<'_, str>> {
+    let start = version_line_end(input);
+    let rest = &input[start..];
+    let Some(directive) = rest.strip_prefix(CHECKSUM_PREFIX) else {
+        return Ok(Cow::Borrowed(input));
+    };
+
+    let line_len = directive.find('\n').map(|i| i + 1).unwrap_or(directive.len());
+    let hex = directive[..line_len].trim_end();
+    let expected = u32::from_str_radix(hex, 16).map_err(|_| AlsError::AlsSyntaxError {
+        position: start,
+        message: format!("Invalid checksum directive '{}{}'", CHECKSUM_PREFIX, hex),
+    })?;
+
+    let body = &directive[line_len..];
+    let found = crc32(body.as_bytes());
+    if expected != found {
+        return Err(AlsError::ChecksumMismatch { expected, found });
+    }
+
+    let mut stripped = String::with_capacity(start + body.len());
+    stripped.push_str(&input[..start]);
+    stripped.push_str(body);
+    Ok(Cow::Owned(stripped))
+}
+
+/// Strip the checksum directive of a serialized document without verifying it.
+///
+/// For readers that never look at the bytes the checksum covers, such as
+/// [`AlsParser::parse_header`](crate::AlsParser::parse_header). Documents
+/// without a directive are returned unchanged.
+pub fn strip_checksum(input: &str) -> Cow<'_, str> {
+    let start = version_line_end(input);
+    let Some(directive) = input[start..].strip_prefix(CHECKSUM_PREFIX) else {
+        return Cow::Borrowed(input);
+    };
+
+    let line_len = directive.find('\n').map(|i| i + 1).unwrap_or(directive.len());
+    let mut stripped = String::with_capacity(input.len());
+    stripped.push_str(&input[..start]);
+    stripped.push_str(&directive[line_len..]);
+    Cow::Owned(stripped)
+}
+
+/// Byte offset just past the version line, or 0 if there is none.
+fn version_line_end(input: &str) -> usize {
+    let is_version = input.starts_with('!') && !input.starts_with(CHECKSUM_PREFIX);
+    if !is_version {
+        return 0;
+    }
+    input.find('\n').map(|i| i + 1).unwrap_or(input.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_known_values() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"The quick brown fox jumps over the lazy dog"), 0x414F_A339);
+    }
+
+    #[test]
+    fn test_add_checksum_after_version() {
+        let doc = "!v1\n#id\n1>3";
+        let with_checksum = add_checksum(doc);
+        let mut lines = with_checksum.lines();
+        assert_eq!(lines.next(), Some("!v1"));
+        assert_eq!(
+            lines.next(),
+            Some(format!("!crc{:08x}", crc32(b"#id\n1>3")).as_str())
+        );
+        assert_eq!(lines.next(), Some("#id"));
+    }
+
+    #[test]
+    fn test_verify_round_trip() {
+        let doc = "!v1\n$default:a|b\n#x\n_0 _1";
+        let with_checksum = add_checksum(doc);
+        assert_eq!(verify_checksum(&with_checksum).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_verify_without_version_line() {
+        let doc = "#x\n1>3";
+        let with_checksum = add_checksum(doc);
+        assert!(with_checksum.starts_with(CHECKSUM_PREFIX));
+        assert_eq!(verify_checksum(&with_checksum).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_verify_without_directive_is_unchanged() {
+        let doc = "!v1\n#x\n1>3";
+        assert!(matches!(verify_checksum(doc).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_verify_detects_mismatch() {
+        let with_checksum = add_checksum("!v1\n#x\n1>3");
+        let corrupted = with_checksum.replace("1>3", "1>4");
+        let err = verify_checksum(&corrupted).unwrap_err();
+        assert!(matches!(err, AlsError::ChecksumMismatch { .. }));
+    }
+
+    #[test]
+    fn test_verify_rejects_invalid_directive() {
+        let err = verify_checksum("!v1\n!crcxyz\n#x\n1").unwrap_err();
+        assert!(matches!(err, AlsError::AlsSyntaxError { .. }));
+    }
+}
@@ -0,0 +1,243 @@
+//! Optional entropy coding of serialized ALS documents.
+//!
+//! ALS output is already compact text, but a general-purpose codec on top
+//! can shrink it further for storage. An encoded document is the codec's
+//! output prefixed with a single magic byte naming the codec:
+//!
+//! | Codec  | Magic  |
+//! |--------|--------|
+//! | gzip   | `0x81` |
+//! | zstd   | `0x82` |
+//!
+//! Both magic bytes are UTF-8 continuation bytes, which can never start
+//! valid UTF-8 text, so [`decode`] tells encoded documents apart from plain
+//! ALS text by the first byte alone. Gzip and zstd support are enabled by
+//! the `gzip` and `zstd` features.
+
+use std::borrow::Cow;
+use std::io;
+
+use crate::error::Result;
+
+/// Magic byte prefixing gzip-encoded documents.
+pub const GZIP_MAGIC: u8 = 0x81;
+
+/// Magic byte prefixing zstd-encoded documents.
+pub const ZSTD_MAGIC: u8 = 0x82;
+
+/// Codec applied to a serialized ALS document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Codec {
+    /// Plain ALS text.
+    #[default]
+    None,
+    /// Gzip (DEFLATE) coding, requires the `gzip` feature.
+    Gzip,
+    /// Zstandard coding, requires the `zstd` feature.
+    Zstd,
+}
+
+impl Codec {
+    /// Short lowercase name of the codec.
+    pub fn name(self) -> &'static str {
+        match self {
+            Codec::None => "none",
+            Codec::Gzip => "gzip",
+            Codec::Zstd => "zstd",
+        }
+    }
+
+    /// Whether support for the codec was compiled in.
+    pub fn is_available(self) -> bool {
+        match self {
+            Codec::None => true,
+            Codec::Gzip => cfg!(feature = "gzip"),
+            Codec::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    /// Detect the codec of an encoded document from its magic byte.
+    ///
+    /// Input without a magic byte is plain text and detects as
+    /// [`Codec::None`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use als_compression::als::codec::{Codec, GZIP_MAGIC};
+    /// assert_eq!(Codec::detect(&[GZIP_MAGIC, 0x1f, 0x8b]), Codec::Gzip);
+    /// assert_eq!(Codec::detect(b"#id\n1>3"), Codec::None);
+    /// ```
+    pub fn detect(bytes: &[u8]) -> Codec {
+        match bytes.first() {
+            Some(&GZIP_MAGIC) => Codec::Gzip,
+            Some(&ZSTD_MAGIC) => Codec::Zstd,
+            _ => Codec::None,
+        }
+    }
+
+    /// Encode a serialized document, prefixing the codec's magic byte.
+    ///
+    /// [`Codec::None`] returns the text bytes unchanged. Encoding with a
+    /// codec whose feature is disabled returns an `Unsupported` I/O error.
+    pub fn encode(self, als: &str) -> Result<Vec<u8>> {
+        match self {
+            Codec::None => Ok(als.as_bytes().to_vec()),
+            Codec::Gzip => gzip_encode(als.as_bytes()),
+            Codec::Zstd => zstd_encode(als.as_bytes()),
+        }
+    }
+}
+
+/// Decode a document written by [`Codec::encode`].
+///
+/// The codec is detected from the magic byte; plain ALS text is borrowed
+/// unchanged. Corrupt codec streams and non-UTF-8 output are reported as
+/// I/O errors.
+///
+/// # Example
+///
+/// ```
+/// use als_compression::als::codec::decode;
+/// assert_eq!(decode(b"#id\n1>3").unwrap(), "#id\n1>3");
+/// ```
+pub fn decode(bytes: &[u8]) -> Result<Cow<'_, str>> {
+    match Codec::detect(bytes) {
+        Codec::None => std::str::from_utf8(bytes)
+            .map(Cow::Borrowed)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into()),
+        Codec::Gzip => into_text(gzip_decode(&bytes[1..])?),
+        Codec::Zstd => into_text(zstd_decode(&bytes[1..])?),
+    }
+}
+
+/// Convert decoded bytes to text.
+fn into_text(bytes: Vec<u8>) -> Result<Cow<'static, str>> {
+    String::from_utf8(bytes)
+        .map(Cow::Owned)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e).into())
+}
+
+/// Error for a codec whose feature is disabled.
+#[cfg(not(all(feature = "gzip", feature = "zstd")))]
+fn unsupported(codec: Codec) -> crate::error::AlsError {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{} support requires the `{}` feature", codec.name(), codec.name()),
+    )
+    .into()
+}
+
+#[cfg(feature = "gzip")]
+fn gzip_encode(bytes: &[u8]) -> Result<Vec<u8>> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    let mut encoder = GzEncoder::new(vec![GZIP_MAGIC], Compression::default());
+    encoder.write_all(bytes)?;
+    Ok(encoder.finish()?)
+}
+
+#[cfg(feature = "gzip")]
+fn gzip_decode(bytes: &[u8]) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let mut output = Vec::new();
+    flate2::read::GzDecoder::new(bytes).read_to_end(&mut output)?;
+    Ok(output)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gzip_encode(_bytes: &[u8]) -> Result<Vec<u8>> {
+    Err(unsupported(Codec::Gzip))
+}
+
+#[cfg(not(feature = "gzip"))]
+fn gzip_decode(_bytes: &[u8]) -> Result<Vec<u8>> {
+    Err(unsupported(Codec::Gzip))
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_encode(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut output = vec![ZSTD_MAGIC];
+    zstd::stream::copy_encode(bytes, &mut output, 0)?;
+    Ok(output)
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_decode(bytes: &[u8]) -> Result<Vec<u8>> {
+    Ok(zstd::stream::decode_all(bytes)?)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_encode(_bytes: &[u8]) -> Result<Vec<u8>> {
+    Err(unsupported(Codec::Zstd))
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_decode(_bytes: &[u8]) -> Result<Vec<u8>> {
+    Err(unsupported(Codec::Zstd))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = "!v1\n#id #status\n1>100|active~inactive*100";
+
+    #[test]
+    fn test_plain_text_is_borrowed() {
+        let encoded = Codec::None.encode(DOCUMENT).unwrap();
+        assert_eq!(encoded, DOCUMENT.as_bytes());
+        assert!(matches!(decode(&encoded).unwrap(), Cow::Borrowed(DOCUMENT)));
+    }
+
+    #[test]
+    fn test_detect_magic_bytes() {
+        assert_eq!(Codec::detect(&[GZIP_MAGIC]), Codec::Gzip);
+        assert_eq!(Codec::detect(&[ZSTD_MAGIC]), Codec::Zstd);
+        assert_eq!(Codec::detect(b"!v1"), Codec::None);
+        assert_eq!(Codec::detect(&[]), Codec::None);
+        // Multi-byte text never starts with a continuation byte
+        assert_eq!(Codec::detect("日本".as_bytes()), Codec::None);
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_rejected() {
+        assert!(decode(&[b'#', 0xFF]).is_err());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_roundtrip() {
+        let encoded = Codec::Gzip.encode(DOCUMENT).unwrap();
+        assert_eq!(encoded[0], GZIP_MAGIC);
+        assert_eq!(&encoded[1..3], &[0x1f, 0x8b]);
+        assert_eq!(decode(&encoded).unwrap(), DOCUMENT);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_roundtrip() {
+        let encoded = Codec::Zstd.encode(DOCUMENT).unwrap();
+        assert_eq!(encoded[0], ZSTD_MAGIC);
+        assert_eq!(decode(&encoded).unwrap(), DOCUMENT);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_corrupt_stream_is_rejected() {
+        let mut encoded = Codec::Gzip.encode(DOCUMENT).unwrap();
+        encoded.truncate(encoded.len() / 2);
+        assert!(decode(&encoded).is_err());
+    }
+
+    #[cfg(not(feature = "zstd"))]
+    #[test]
+    fn test_disabled_codec_is_unsupported() {
+        assert!(!Codec::Zstd.is_available());
+        assert!(Codec::Zstd.encode(DOCUMENT).is_err());
+        assert!(decode(&[ZSTD_MAGIC, 0]).is_err());
+    }
+}
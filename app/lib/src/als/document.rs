@@ -5,14 +5,17 @@
 
 use std::collections::HashMap;
 
-use super::AlsOperator;
+use super::operator::OperatorCursor;
+use super::{AlsOperator, AlsParser, SentinelTokens};
+use crate::convert::TabularData;
+use crate::error::{AlsError, Result};
 
 /// Represents a complete ALS document.
 ///
 /// An ALS document consists of:
 /// - A version indicator
 /// - Optional dictionaries for string deduplication
-/// - A schema defining column names
+/// - A schema defining column names, optionally annotated with encoding hints
 /// - Column streams containing compressed data
 /// - A format indicator (ALS or CTX fallback)
 ///
@@ -35,11 +38,28 @@ pub struct AlsDocument {
     /// Dictionary references in operators use indices into these vectors.
     pub dictionaries: HashMap<String, Vec<String>>,
 
+    /// Dictionaries assigned to individual columns, by schema position.
+    ///
+    /// `DictRef`s in a listed column resolve against the named dictionary;
+    /// every other column uses the `default` dictionary. Written as a
+    /// `!dicts:` directive.
+    pub column_dictionaries: HashMap<usize, String>,
+
     /// Column schema defining the names of each column.
     ///
     /// The order of names corresponds to the order of streams.
     pub schema: Vec<String>,
 
+    /// Encoding hints attached to schema columns (e.g. `#id:range`).
+    ///
+    /// Keys are column names from the schema.
+    pub encoding_hints: HashMap<String, ColumnEncoding>,
+
+    /// Numeric types attached to schema columns (e.g. `#price:float`).
+    ///
+    /// Keys are column names from the schema.
+    pub number_types: HashMap<String, NumberType>,
+
     /// Column streams containing compressed data.
     ///
     /// Each stream corresponds to a column in the schema.
@@ -47,6 +67,32 @@ pub struct AlsDocument {
 
     /// Format indicator distinguishing ALS from CTX fallback.
     pub format_indicator: FormatIndicator,
+
+    /// Tokens that stand for null values and empty strings in the streams.
+    pub sentinel_tokens: SentinelTokens,
+
+    /// Order in which the columns are written, as positions in `schema`.
+    ///
+    /// `schema` and `streams` are always in the original column order; when
+    /// set, the serializer writes the columns in this order and records it
+    /// in an `!order:` directive, which the parser uses to restore the
+    /// original order. `None` writes the columns in schema order.
+    pub column_order: Option<Vec<usize>>,
+
+    /// Stored rows that stand for runs of identical rows, as `(row, count)`
+    /// pairs sorted by row.
+    ///
+    /// The streams hold each run once, at stored row `row`, and expansion
+    /// repeats it `count` times across every column. Written as a `!rows:`
+    /// directive. Empty when every stored row is a single row.
+    pub row_repeats: Vec<(usize, usize)>,
+
+    /// Text of the document's `;;` comment lines, without the marker.
+    ///
+    /// Filled by the parser only when `ParserConfig::preserve_comments` is
+    /// set. The serializer writes each entry back as a comment line after
+    /// the version header.
+    pub comments: Vec<String>,
 }
 
 impl AlsDocument {
@@ -58,9 +104,16 @@ impl AlsDocument {
         Self {
             version: Self::CURRENT_VERSION,
             dictionaries: HashMap::new(),
+            column_dictionaries: HashMap::new(),
             schema: Vec::new(),
+            encoding_hints: HashMap::new(),
+            number_types: HashMap::new(),
             streams: Vec::new(),
             format_indicator: FormatIndicator::Als,
+            sentinel_tokens: SentinelTokens::DEFAULT,
+            column_order: None,
+            row_repeats: Vec::new(),
+            comments: Vec::new(),
         }
     }
 
@@ -73,12 +126,24 @@ impl AlsDocument {
         Self {
             version: Self::CURRENT_VERSION,
             dictionaries: HashMap::new(),
+            column_dictionaries: HashMap::new(),
             schema: schema.into_iter().map(|s| s.into()).collect(),
+            encoding_hints: HashMap::new(),
+            number_types: HashMap::new(),
             streams: Vec::new(),
             format_indicator: FormatIndicator::Als,
+            sentinel_tokens: SentinelTokens::DEFAULT,
+            column_order: None,
+            row_repeats: Vec::new(),
+            comments: Vec::new(),
         }
     }
 
+    /// Start building a document, validated when it is built.
+    pub fn builder() -> AlsDocumentBuilder {
+        AlsDocumentBuilder::new()
+    }
+
     /// Add a dictionary to the document.
     ///
     /// # Arguments
@@ -89,6 +154,56 @@ impl AlsDocument {
         self.dictionaries.insert(name.into(), entries);
     }
 
+    /// Resolve the `DictRef`s of the column at schema position `column`
+    /// against the dictionary called `name`.
+    ///
+    /// Assigning `default` removes any earlier assignment.
+    pub fn set_column_dictionary<S: Into<String>>(&mut self, column: usize, name: S) {
+        let name = name.into();
+        if name == "default" {
+            self.column_dictionaries.remove(&column);
+        } else {
+            self.column_dictionaries.insert(column, name);
+        }
+    }
+
+    /// Get the name of the dictionary used by the column at schema position
+    /// `column`.
+    pub fn dictionary_name(&self, column: usize) -> &str {
+        self.column_dictionaries.get(&column).map_or("default", String::as_str)
+    }
+
+    /// Get the entries of the dictionary used by the column at schema
+    /// position `column` (if any).
+    pub fn column_dictionary(&self, column: usize) -> Option<&[String]> {
+        self.dictionaries.get(self.dictionary_name(column)).map(Vec::as_slice)
+    }
+
+    /// Attach an encoding hint to a schema column.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Name of the column
+    /// * `encoding` - Encoding to record in the schema
+    pub fn set_encoding_hint<S: Into<String>>(&mut self, column: S, encoding: ColumnEncoding) {
+        self.encoding_hints.insert(column.into(), encoding);
+    }
+
+    /// Get the encoding hint attached to a schema column, if any.
+    pub fn encoding_hint(&self, column: &str) -> Option<ColumnEncoding> {
+        self.encoding_hints.get(column).copied()
+    }
+
+    /// Record the numeric type of a column, written as a schema annotation.
+    pub fn set_number_type<S: Into<String>>(&mut self, column: S, number_type: NumberType) {
+        self.number_types.insert(column.into(), number_type);
+    }
+
+    /// Get the numeric type recorded for a column, if any.
+    pub fn number_type(&self, column: &str) -> Option<NumberType> {
+        self.number_types.get(column).copied()
+    }
+
     /// Add a column stream to the document.
     ///
     /// # Arguments
@@ -98,6 +213,13 @@ impl AlsDocument {
         self.streams.push(stream);
     }
 
+    /// Get the position of the named column in the schema.
+    ///
+    /// Returns the first match if the schema repeats the name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.schema.iter().position(|column| column == name)
+    }
+
     /// Get the number of columns in the document.
     pub fn column_count(&self) -> usize {
         self.schema.len()
@@ -105,15 +227,77 @@ impl AlsDocument {
 
     /// Get the number of rows in the document.
     ///
-    /// This is calculated by expanding the first column stream.
+    /// This is calculated by expanding the first column stream and adding
+    /// the rows of [`row_repeats`](Self::row_repeats).
     /// Returns 0 if there are no streams.
     pub fn row_count(&self) -> usize {
         self.streams
             .first()
-            .map(|s| s.expanded_count())
+            .map(|s| s.expanded_count() + self.repeated_rows())
             .unwrap_or(0)
     }
 
+    /// Number of rows [`row_repeats`](Self::row_repeats) adds beyond the
+    /// stored rows.
+    pub(crate) fn repeated_rows(&self) -> usize {
+        self.row_repeats
+            .iter()
+            .map(|&(_, count)| count.saturating_sub(1))
+            .fold(0, usize::saturating_add)
+    }
+
+    /// Get how many rows the stored row at `row` stands for.
+    pub fn row_repeat_count(&self, row: usize) -> usize {
+        self.row_repeats
+            .binary_search_by_key(&row, |&(repeated, _)| repeated)
+            .map_or(1, |index| self.row_repeats[index].1)
+    }
+
+    /// Repeat the stored values of one column as listed in
+    /// [`row_repeats`](Self::row_repeats).
+    ///
+    /// Expanding a stream gives one value per stored row; this turns them
+    /// into one value per row.
+    pub fn expand_row_repeats<T: Clone>(&self, values: Vec<T>) -> Vec<T> {
+        if self.row_repeats.is_empty() {
+            return values;
+        }
+        let mut expanded = Vec::with_capacity(values.len() + self.repeated_rows());
+        let mut repeats = self.row_repeats.iter().peekable();
+        for (row, value) in values.into_iter().enumerate() {
+            match repeats.next_if(|&&(repeated, _)| repeated == row) {
+                Some(&(_, count)) => {
+                    expanded.extend(std::iter::repeat_n(value, count));
+                }
+                None => expanded.push(value),
+            }
+        }
+        expanded
+    }
+
+    /// Get the number of rows, if the column streams were decoded.
+    ///
+    /// Returns `None` for a document with a schema but no streams, such as
+    /// one read by [`AlsParser::parse_header`](crate::AlsParser::parse_header).
+    pub fn known_row_count(&self) -> Option<usize> {
+        if self.streams.is_empty() && !self.schema.is_empty() {
+            None
+        } else {
+            Some(self.row_count())
+        }
+    }
+
+    /// Schema positions of the columns in the order they are written.
+    ///
+    /// This is [`column_order`](Self::column_order) when it is a permutation
+    /// of the schema positions, and schema order otherwise.
+    pub fn written_column_order(&self) -> Vec<usize> {
+        match &self.column_order {
+            Some(order) if is_permutation(order, self.schema.len()) => order.clone(),
+            _ => (0..self.schema.len()).collect(),
+        }
+    }
+
     /// Check if the document uses CTX fallback format.
     pub fn is_ctx(&self) -> bool {
         self.format_indicator == FormatIndicator::Ctx
@@ -142,6 +326,95 @@ impl AlsDocument {
         self.dictionaries.get("default")
     }
 
+    /// Expand the document to typed tabular data.
+    ///
+    /// Shorthand for [`AlsParser::expand_typed`](super::AlsParser::expand_typed)
+    /// with the default parser configuration.
+    pub fn to_tabular(&self) -> crate::error::Result<TabularData<'static>> {
+        AlsParser::new().expand_typed(self)
+    }
+
+    /// Append the rows of another document with the same schema.
+    ///
+    /// Row repeats of `other` are kept, moved past the rows stored in
+    /// `self`. Dictionaries are unioned by name: entries of `other` that are not yet
+    /// present are appended, and the `DictRef` indices in `other`'s streams
+    /// are renumbered against the merged dictionary of their column. Columns
+    /// keep the dictionary they use in `self`. Encoding hints and number
+    /// types are kept only where both documents agree, and the result uses
+    /// CTX format if either document does.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::SchemaMismatch` if the schemas differ,
+    /// `AlsError::SentinelMismatch` if the documents use different null or
+    /// empty tokens, or `AlsError::ColumnMismatch` if either document has a
+    /// different number of streams than schema columns. `self` is unchanged
+    /// on error.
+    pub fn merge(&mut self, other: &AlsDocument) -> Result<()> {
+        if self.schema != other.schema {
+            return Err(AlsError::SchemaMismatch {
+                expected: self.schema.clone(),
+                found: other.schema.clone(),
+            });
+        }
+        if self.sentinel_tokens != other.sentinel_tokens {
+            return Err(AlsError::SentinelMismatch {
+                expected: self.sentinel_tokens.clone(),
+                found: other.sentinel_tokens.clone(),
+            });
+        }
+        for doc in [&*self, other] {
+            if doc.streams.len() != doc.schema.len() {
+                return Err(AlsError::ColumnMismatch {
+                    schema: doc.schema.len(),
+                    data: doc.streams.len(),
+                });
+            }
+        }
+
+        // Rows of `other` come after the rows stored in `self`
+        let stored_rows = self.streams.first().map_or(0, ColumnStream::expanded_count);
+
+        // Union the dictionaries, remembering where each entry lands
+        let mut remaps: HashMap<&str, Vec<Option<usize>>> = HashMap::with_capacity(other.dictionaries.len());
+        for (name, entries) in &other.dictionaries {
+            let mapping = append_dictionary_entries(self.dictionaries.entry(name.clone()).or_default(), entries);
+            remaps.insert(name.as_str(), mapping);
+        }
+
+        for (column, (stream, other_stream)) in self.streams.iter_mut().zip(&other.streams).enumerate() {
+            let source = other.dictionary_name(column);
+            let target = self.column_dictionaries.get(&column).map_or("default", String::as_str);
+            // A column assigned to different dictionaries reads `other`'s
+            // entries into the dictionary it already uses here
+            let remap = if source == target {
+                remaps.get(source).cloned().unwrap_or_default()
+            } else {
+                let entries = other.dictionaries.get(source).map_or(&[][..], Vec::as_slice);
+                append_dictionary_entries(self.dictionaries.entry(target.to_string()).or_default(), entries)
+            };
+            stream.operators.extend(other_stream.operators.iter().map(|op| {
+                let mut op = op.clone();
+                op.remap_dict_refs(&remap);
+                op
+            }));
+        }
+
+        self.row_repeats
+            .extend(other.row_repeats.iter().map(|&(row, count)| (stored_rows + row, count)));
+        self.encoding_hints
+            .retain(|column, encoding| other.encoding_hints.get(column) == Some(encoding));
+        self.number_types
+            .retain(|column, number_type| other.number_types.get(column) == Some(number_type));
+        self.version = self.version.max(other.version);
+        if other.is_ctx() {
+            self.set_ctx_format();
+        }
+
+        Ok(())
+    }
+
     /// Validate the document structure.
     ///
     /// Checks that:
@@ -169,6 +442,33 @@ impl AlsDocument {
 
         true
     }
+
+    /// Check that the document can be expanded, without expanding it.
+    ///
+    /// Verifies that there is one stream per schema column and that every
+    /// operator is well formed, with `DictRef` indices in range of the
+    /// column's dictionary. Useful to fail fast on documents built
+    /// programmatically rather than parsed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::ColumnMismatch` if the stream and schema counts
+    /// differ, `AlsError::InvalidDictRef` for an out-of-range dictionary
+    /// reference, or the error of the first malformed operator (see
+    /// [`AlsOperator::validate`]).
+    pub fn validate(&self) -> Result<()> {
+        if self.streams.len() != self.schema.len() {
+            return Err(AlsError::ColumnMismatch {
+                schema: self.schema.len(),
+                data: self.streams.len(),
+            });
+        }
+
+        for (column, stream) in self.streams.iter().enumerate() {
+            stream.validate(self.column_dictionary(column).map_or(0, <[String]>::len))?;
+        }
+        Ok(())
+    }
 }
 
 impl Default for AlsDocument {
@@ -177,11 +477,97 @@ impl Default for AlsDocument {
     }
 }
 
+/// Builder for constructing an `AlsDocument` programmatically.
+///
+/// Collects the schema, dictionaries, and streams, then checks the result
+/// with [`AlsDocument::validate`] in `build`, so mistakes surface where the
+/// document is made rather than when it is expanded.
+#[derive(Debug, Clone, Default)]
+pub struct AlsDocumentBuilder {
+    document: AlsDocument,
+}
+
+impl AlsDocumentBuilder {
+    /// Create a builder for an empty document.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the column names of the document.
+    pub fn with_schema<S: Into<String>>(mut self, schema: Vec<S>) -> Self {
+        self.document.schema = schema.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    /// Add a dictionary, replacing any earlier one with the same name.
+    pub fn with_dictionary<S: Into<String>>(mut self, name: S, entries: Vec<String>) -> Self {
+        self.document.add_dictionary(name, entries);
+        self
+    }
+
+    /// Resolve the `DictRef`s of the column at schema position `column`
+    /// against the dictionary called `name`.
+    pub fn with_column_dictionary<S: Into<String>>(mut self, column: usize, name: S) -> Self {
+        self.document.set_column_dictionary(column, name);
+        self
+    }
+
+    /// Add the stream of the next schema column.
+    pub fn with_stream(mut self, stream: ColumnStream) -> Self {
+        self.document.add_stream(stream);
+        self
+    }
+
+    /// Finish the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::ColumnMismatch` if the stream and schema counts
+    /// differ, `AlsError::InvalidDictRef` for an out-of-range dictionary
+    /// reference, or the error of the first malformed operator.
+    pub fn build(self) -> Result<AlsDocument> {
+        self.document.validate()?;
+        Ok(self.document)
+    }
+}
+
+/// Append the entries missing from `merged`, returning the position in
+/// `merged` of each entry of `entries`.
+fn append_dictionary_entries(merged: &mut Vec<String>, entries: &[String]) -> Vec<Option<usize>> {
+    let mut positions: HashMap<&str, usize> = HashMap::with_capacity(merged.len());
+    for (index, entry) in merged.iter().enumerate() {
+        positions.entry(entry.as_str()).or_insert(index);
+    }
+
+    let mut appended = Vec::new();
+    let mapping = entries
+        .iter()
+        .map(|entry| {
+            let index = *positions.entry(entry.as_str()).or_insert_with(|| {
+                appended.push(entry.clone());
+                merged.len() + appended.len() - 1
+            });
+            Some(index)
+        })
+        .collect();
+    merged.extend(appended);
+    mapping
+}
+
+/// Check whether `order` lists each of the positions `0..len` exactly once.
+pub(crate) fn is_permutation(order: &[usize], len: usize) -> bool {
+    let mut seen = vec![false; len];
+    order.len() == len
+        && order
+            .iter()
+            .all(|&index| index < len && !std::mem::replace(&mut seen[index], true))
+}
+
 /// A single column's compressed representation.
 ///
 /// Contains a sequence of operators that, when expanded, produce
 /// the column's values.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ColumnStream {
     /// Operators that produce this column's values when expanded.
     pub operators: Vec<AlsOperator>,
@@ -220,6 +606,18 @@ impl ColumnStream {
         self.operators.is_empty()
     }
 
+    /// Check that every operator is well formed and that every `DictRef`,
+    /// including those nested in other operators, indexes one of the
+    /// `dict_len` dictionary entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first malformed operator, see
+    /// [`AlsOperator::validate`].
+    pub fn validate(&self, dict_len: usize) -> Result<()> {
+        self.operators.iter().try_for_each(|op| op.validate(dict_len))
+    }
+
     /// Expand all operators and return the values.
     ///
     /// # Arguments
@@ -230,12 +628,155 @@ impl ColumnStream {
     ///
     /// Returns an error if any DictRef references an invalid index.
     pub fn expand(&self, dictionary: Option<&[String]>) -> crate::error::Result<Vec<String>> {
-        let mut result = Vec::with_capacity(self.expanded_count());
+        let len = self.operators.iter().map(AlsOperator::expanded_len).sum();
+        let mut result = Vec::with_capacity(len);
         for op in &self.operators {
-            result.extend(op.expand(dictionary)?);
+            op.expand_into(dictionary, &mut |value| result.push(value.into_owned()))?;
+        }
+        Ok(result)
+    }
+
+    /// Expand all operators, pairing each value with the index of the
+    /// operator that produced it.
+    ///
+    /// Indices refer to the top-level operators of this stream, so every
+    /// value produced inside a multiply or toggle is attributed to the
+    /// outer operator. Useful for seeing where compression boundaries fall.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any DictRef references an invalid index.
+    pub fn expand_with_provenance(
+        &self,
+        dictionary: Option<&[String]>,
+    ) -> crate::error::Result<Vec<(String, usize)>> {
+        let len = self.operators.iter().map(AlsOperator::expanded_len).sum();
+        let mut result = Vec::with_capacity(len);
+        for (index, op) in self.operators.iter().enumerate() {
+            op.expand_into(dictionary, &mut |value| result.push((value.into_owned(), index)))?;
         }
         Ok(result)
     }
+
+    /// Merge adjacent operators without changing the expanded values.
+    ///
+    /// Runs of identical operators become a single [`AlsOperator::Multiply`],
+    /// and consecutive integer raws are folded into a range. Unlike
+    /// [`AlsCompressor::canonicalize`](crate::AlsCompressor::canonicalize),
+    /// this only looks at neighbouring operators, which makes it cheap enough
+    /// to run while a stream is being built.
+    pub fn compact(&mut self) {
+        let mut compacted: Vec<AlsOperator> = Vec::with_capacity(self.operators.len());
+        for op in self.operators.drain(..) {
+            let merged = match compacted.last_mut() {
+                Some(last) => Self::merge_adjacent(last, &op),
+                None => false,
+            };
+            if !merged {
+                compacted.push(op);
+            }
+        }
+        self.operators = compacted;
+    }
+
+    /// Fold `next` into `last` if the pair can be written as one operator.
+    ///
+    /// Returns `false` and leaves `last` untouched otherwise.
+    fn merge_adjacent(last: &mut AlsOperator, next: &AlsOperator) -> bool {
+        let (last_value, last_count) = Self::repeated(last);
+        let (next_value, next_count) = Self::repeated(next);
+        if last_value == next_value {
+            *last = AlsOperator::multiply(last_value.clone(), last_count + next_count);
+            return true;
+        }
+
+        let Some(value) = Self::integer_raw(next) else {
+            return false;
+        };
+        match last {
+            AlsOperator::Raw(_) => {
+                let Some(previous) = Self::integer_raw(last) else {
+                    return false;
+                };
+                let step = value.wrapping_sub(previous);
+                if previous.checked_add(step) != Some(value) || step.abs() != 1 {
+                    return false;
+                }
+                *last = AlsOperator::range_with_step(previous, value, step);
+                true
+            }
+            AlsOperator::Range { start, end, step } => {
+                // Only extend ranges whose last value is exactly `end`
+                let ends_on_end = *step != 0
+                    && (*end >= *start) == (*step > 0)
+                    && (*end as i128 - *start as i128) % *step as i128 == 0;
+                if !ends_on_end || end.checked_add(*step) != Some(value) {
+                    return false;
+                }
+                *end = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Split an operator into the value it repeats and the repeat count.
+    fn repeated(op: &AlsOperator) -> (&AlsOperator, usize) {
+        match op {
+            AlsOperator::Multiply { value, count } => (value, *count),
+            _ => (op, 1),
+        }
+    }
+
+    /// The integer held by a raw operator, if it is written in canonical form.
+    ///
+    /// Raws like `007` or `+1` are excluded, as a range would expand them
+    /// to different text.
+    fn integer_raw(op: &AlsOperator) -> Option<i64> {
+        match op {
+            AlsOperator::Raw(text) => text
+                .parse::<i64>()
+                .ok()
+                .filter(|value| value.to_string() == *text),
+            _ => None,
+        }
+    }
+}
+
+/// Cursor that yields the expanded values of a column stream one at a time.
+///
+/// Like [`OperatorCursor`], the cursor does not borrow the stream; the same
+/// stream must be passed to every call of [`StreamCursor::next_value`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StreamCursor {
+    /// Index of the operator currently being expanded
+    operator: usize,
+    /// Cursor into the current operator, created on first use
+    current: Option<OperatorCursor>,
+}
+
+impl StreamCursor {
+    /// Create a cursor positioned at the first value of a stream.
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Yield the next value of `stream`, or `None` once it is exhausted.
+    pub(crate) fn next_value(
+        &mut self,
+        stream: &ColumnStream,
+        dictionary: Option<&[String]>,
+    ) -> Option<crate::error::Result<String>> {
+        loop {
+            let op = stream.operators.get(self.operator)?;
+            let cursor = self.current.get_or_insert_with(|| OperatorCursor::new(op));
+            if let Some(value) = cursor.next_value(op, dictionary) {
+                return Some(value);
+            }
+            self.operator += 1;
+            self.current = None;
+        }
+    }
 }
 
 impl Default for ColumnStream {
@@ -274,8 +815,101 @@ impl FormatIndicator {
     }
 }
 
+/// Encoding requested for a column through a schema annotation.
+///
+/// In ALS text the hint follows the column name after a colon, e.g.
+/// `#id:range #status:dict`. When enabled in the compressor configuration,
+/// a hint skips pattern detection and forces the given encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnEncoding {
+    /// Integer range or arithmetic sequence (`range`).
+    Range,
+    /// A single repeated value (`repeat`).
+    Repeat,
+    /// Alternating values (`toggle`).
+    Toggle,
+    /// Integers stored as consecutive differences (`delta`).
+    Delta,
+    /// Dictionary references for every string value (`dict`).
+    Dict,
+    /// Raw values without compression (`raw`).
+    Raw,
+}
+
+impl ColumnEncoding {
+    /// Get the annotation name used in ALS text.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColumnEncoding::Range => "range",
+            ColumnEncoding::Repeat => "repeat",
+            ColumnEncoding::Toggle => "toggle",
+            ColumnEncoding::Delta => "delta",
+            ColumnEncoding::Dict => "dict",
+            ColumnEncoding::Raw => "raw",
+        }
+    }
+
+    /// Look up an encoding by its annotation name.
+    ///
+    /// Returns `None` if the name is not a known encoding.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "range" => Some(ColumnEncoding::Range),
+            "repeat" => Some(ColumnEncoding::Repeat),
+            "toggle" => Some(ColumnEncoding::Toggle),
+            "delta" => Some(ColumnEncoding::Delta),
+            "dict" => Some(ColumnEncoding::Dict),
+            "raw" => Some(ColumnEncoding::Raw),
+            _ => None,
+        }
+    }
+}
 
 
+
+/// Numeric type recorded for a column through a schema annotation.
+///
+/// In ALS text the type follows the column name and any encoding hint after
+/// a colon, e.g. `#price:float` or `#id:range:int`. Parsers configured to
+/// preserve number types use it to keep `1.0` a float, `19.90` an exact
+/// decimal, or `1672531200` a timestamp, when converting back to typed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumberType {
+    /// Integer values (`int`).
+    Integer,
+    /// Floating point values, including whole numbers (`float`).
+    Float,
+    /// Fixed-point decimals kept as exact text (`decimal`).
+    Decimal,
+    /// Unix epoch timestamps in seconds (`epoch`).
+    Timestamp,
+}
+
+impl NumberType {
+    /// Get the annotation name used in ALS text.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NumberType::Integer => "int",
+            NumberType::Float => "float",
+            NumberType::Decimal => "decimal",
+            NumberType::Timestamp => "epoch",
+        }
+    }
+
+    /// Look up a number type by its annotation name.
+    ///
+    /// Returns `None` if the name is not a known number type.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "int" => Some(NumberType::Integer),
+            "float" => Some(NumberType::Float),
+            "decimal" => Some(NumberType::Decimal),
+            "epoch" => Some(NumberType::Timestamp),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,6 +940,31 @@ mod tests {
         assert_eq!(doc.dictionaries["colors"].len(), 3);
     }
 
+    #[test]
+    fn test_als_document_encoding_hints() {
+        let mut doc = AlsDocument::with_schema(vec!["id", "status"]);
+        assert!(doc.encoding_hints.is_empty());
+
+        doc.set_encoding_hint("id", ColumnEncoding::Range);
+        assert_eq!(doc.encoding_hint("id"), Some(ColumnEncoding::Range));
+        assert_eq!(doc.encoding_hint("status"), None);
+    }
+
+    #[test]
+    fn test_column_encoding_names_round_trip() {
+        for encoding in [
+            ColumnEncoding::Range,
+            ColumnEncoding::Repeat,
+            ColumnEncoding::Toggle,
+            ColumnEncoding::Delta,
+            ColumnEncoding::Dict,
+            ColumnEncoding::Raw,
+        ] {
+            assert_eq!(ColumnEncoding::from_name(encoding.name()), Some(encoding));
+        }
+        assert_eq!(ColumnEncoding::from_name("utc"), None);
+    }
+
     #[test]
     fn test_als_document_add_stream() {
         let mut doc = AlsDocument::with_schema(vec!["col1"]);
@@ -354,6 +1013,63 @@ mod tests {
         assert!(!doc.is_valid());
     }
 
+    #[test]
+    fn test_als_document_validate() {
+        let mut doc = AlsDocument::with_schema(vec!["status", "id"]);
+        doc.add_dictionary("default", vec!["active".to_string(), "closed".to_string()]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::multiply(AlsOperator::dict_ref(0), 2),
+            AlsOperator::dict_ref(1),
+        ]));
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 3)]));
+        assert!(doc.validate().is_ok());
+        assert_eq!(doc.streams[0].expand(doc.default_dictionary().map(Vec::as_slice)).unwrap().len(), 3);
+
+        // A reference nested in a multiply past the end of the dictionary
+        doc.streams[0].push(AlsOperator::multiply(
+            AlsOperator::multiply(AlsOperator::dict_ref(2), 2),
+            3,
+        ));
+        assert!(matches!(
+            doc.validate(),
+            Err(AlsError::InvalidDictRef { index: 2, size: 2 })
+        ));
+
+        // Without a dictionary every reference is out of range
+        doc.dictionaries.clear();
+        assert!(matches!(
+            doc.validate(),
+            Err(AlsError::InvalidDictRef { index: 0, size: 0 })
+        ));
+
+        doc.streams.pop();
+        assert!(matches!(
+            doc.validate(),
+            Err(AlsError::ColumnMismatch { schema: 2, data: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_column_stream_validate_operators() {
+        let valid = ColumnStream::from_operators(vec![
+            AlsOperator::range_with_step(10, 50, 10),
+            AlsOperator::int_dict(vec![200, 404], vec![0, 1, 1]),
+            AlsOperator::geometric(1, 2, 5),
+        ]);
+        assert!(valid.validate(0).is_ok());
+
+        let invalid = [
+            (AlsOperator::Range { start: 1, end: 5, step: 0 }, "RangeOverflow"),
+            (AlsOperator::int_dict(vec![200], vec![0, 1]), "InvalidDictRef"),
+            (AlsOperator::geometric(1, 1, 3), "InvalidGeometric"),
+            (AlsOperator::delta(i64::MAX, vec![1]), "DeltaOverflow"),
+        ];
+        for (op, expected) in invalid {
+            let err = ColumnStream::from_operators(vec![op]).validate(0).unwrap_err();
+            assert!(format!("{:?}", err).starts_with(expected), "{:?}", err);
+        }
+    }
+
     #[test]
     fn test_als_document_default_dictionary() {
         let mut doc = AlsDocument::new();
@@ -415,6 +1131,42 @@ mod tests {
         assert_eq!(values, vec!["apple", "banana"]);
     }
 
+    #[test]
+    fn test_column_stream_expand_with_provenance() {
+        let dict = vec!["apple".to_string()];
+        let stream = ColumnStream::from_operators(vec![
+            AlsOperator::range(1, 3),
+            AlsOperator::multiply(AlsOperator::raw("x"), 2),
+            AlsOperator::multiply(
+                AlsOperator::multiply(AlsOperator::dict_ref(0), 2),
+                2,
+            ),
+            AlsOperator::raw("y"),
+        ]);
+
+        let values = stream.expand_with_provenance(Some(&dict)).unwrap();
+        let expected: Vec<(String, usize)> = [
+            ("1", 0), ("2", 0), ("3", 0),
+            ("x", 1), ("x", 1),
+            ("apple", 2), ("apple", 2), ("apple", 2), ("apple", 2),
+            ("y", 3),
+        ]
+        .iter()
+        .map(|&(value, index)| (value.to_string(), index))
+        .collect();
+        assert_eq!(values, expected);
+        assert_eq!(
+            values.into_iter().map(|(value, _)| value).collect::<Vec<_>>(),
+            stream.expand(Some(&dict)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_column_stream_expand_with_provenance_invalid_dict_ref() {
+        let stream = ColumnStream::from_operators(vec![AlsOperator::dict_ref(3)]);
+        assert!(stream.expand_with_provenance(Some(&[])).is_err());
+    }
+
     #[test]
     fn test_column_stream_from_iter() {
         let ops = vec![AlsOperator::raw("a"), AlsOperator::raw("b")];
@@ -440,6 +1192,20 @@ mod tests {
         assert_eq!(doc.row_count(), 0);
     }
 
+    #[test]
+    fn test_document_to_tabular() {
+        use crate::convert::Value;
+
+        let mut doc = AlsDocument::with_schema(vec!["id", "flag"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 2)]));
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("yes"), AlsOperator::raw("no")]));
+
+        let data = doc.to_tabular().unwrap();
+        assert_eq!(data.column_names(), vec!["id", "flag"]);
+        assert_eq!(data.columns[0].values, vec![Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(data.columns[1].values, vec![Value::Boolean(true), Value::Boolean(false)]);
+    }
+
     #[test]
     fn test_types_are_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
@@ -447,4 +1213,249 @@ mod tests {
         assert_send_sync::<ColumnStream>();
         assert_send_sync::<FormatIndicator>();
     }
+
+    #[test]
+    fn test_column_stream_compact_identical_raws() {
+        let mut stream: ColumnStream = (0..10).map(|_| AlsOperator::raw("x")).collect();
+        stream.compact();
+
+        assert_eq!(stream.operators, vec![AlsOperator::multiply(AlsOperator::raw("x"), 10)]);
+        assert_eq!(stream.expand(None).unwrap(), vec!["x"; 10]);
+    }
+
+    #[test]
+    fn test_column_stream_compact_mixed() {
+        let operators = vec![
+            AlsOperator::raw("1"),
+            AlsOperator::raw("2"),
+            AlsOperator::raw("3"),
+            AlsOperator::raw("a"),
+            AlsOperator::multiply(AlsOperator::raw("a"), 2),
+            AlsOperator::raw("b"),
+            AlsOperator::raw("007"),
+            AlsOperator::raw("8"),
+            AlsOperator::range(10, 12),
+            AlsOperator::raw("13"),
+            AlsOperator::raw("20"),
+        ];
+        let mut stream = ColumnStream::from_operators(operators.clone());
+        let expected = stream.expand(None).unwrap();
+        stream.compact();
+
+        assert_eq!(
+            stream.operators,
+            vec![
+                AlsOperator::range(1, 3),
+                AlsOperator::multiply(AlsOperator::raw("a"), 3),
+                AlsOperator::raw("b"),
+                AlsOperator::raw("007"),
+                AlsOperator::raw("8"),
+                AlsOperator::range(10, 13),
+                AlsOperator::raw("20"),
+            ]
+        );
+        assert_eq!(stream.expand(None).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_column_stream_compact_keeps_uneven_range() {
+        // 1>10:2 ends at 9, so a following 11 cannot extend it
+        let mut stream = ColumnStream::from_operators(vec![
+            AlsOperator::range_with_step(1, 10, 2),
+            AlsOperator::raw("11"),
+            AlsOperator::raw("5"),
+            AlsOperator::raw("4"),
+        ]);
+        let expected = stream.expand(None).unwrap();
+        stream.compact();
+
+        assert_eq!(stream.operator_count(), 3);
+        assert_eq!(stream.operators[2], AlsOperator::range_with_step(5, 4, -1));
+        assert_eq!(stream.expand(None).unwrap(), expected);
+    }
+
+    fn merge_rows(first: &str, second: &str) -> (AlsDocument, Vec<Vec<String>>) {
+        let parser = AlsParser::new();
+        let mut doc = parser.parse(first).unwrap();
+        doc.merge(&parser.parse(second).unwrap()).unwrap();
+        let rows = parser.expand(&doc).unwrap();
+        (doc, rows)
+    }
+
+    #[test]
+    fn test_merge_overlapping_dictionaries() {
+        let (doc, rows) = merge_rows(
+            "$default:red|green\n#id #color\n1>3|_0 _1 _0",
+            "$default:green|blue|red\n#id #color\n4>6|_0 _1 _2",
+        );
+
+        assert_eq!(doc.dictionaries["default"], vec!["red", "green", "blue"]);
+        let colors: Vec<&str> = rows.iter().map(|row| row[1].as_str()).collect();
+        assert_eq!(colors, vec!["red", "green", "red", "green", "blue", "red"]);
+        let ids: Vec<&str> = rows.iter().map(|row| row[0].as_str()).collect();
+        assert_eq!(ids, vec!["1", "2", "3", "4", "5", "6"]);
+    }
+
+    #[test]
+    fn test_merge_disjoint_dictionaries() {
+        let (doc, rows) = merge_rows(
+            "$default:apple|banana\n#id #fruit\n1>2|_1 _0",
+            "$default:cherry|date\n#id #fruit\n3>4|(_1)*2",
+        );
+
+        assert_eq!(doc.dictionaries["default"], vec!["apple", "banana", "cherry", "date"]);
+        assert_eq!(
+            rows,
+            vec![
+                vec!["1", "banana"],
+                vec!["2", "apple"],
+                vec!["3", "date"],
+                vec!["4", "date"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_column_dictionaries() {
+        let (doc, rows) = merge_rows(
+            "!dicts:1=status\n$default:eu|us\n$status:ok|fail\n#region #status\n_0 _1|_1 _0",
+            "$default:us|ok|ap\n#region #status\n_2 _0|_1 _1",
+        );
+
+        assert_eq!(doc.dictionary_name(1), "status");
+        assert_eq!(doc.dictionaries["default"], vec!["eu", "us", "ok", "ap"]);
+        assert_eq!(doc.dictionaries["status"], vec!["ok", "fail", "us", "ap"]);
+        assert_eq!(
+            rows,
+            vec![vec!["eu", "fail"], vec!["us", "ok"], vec!["ap", "ok"], vec!["us", "ok"]]
+        );
+    }
+
+    #[test]
+    fn test_validate_column_dictionaries() {
+        let parser = AlsParser::new();
+        let doc = parser
+            .parse("!dicts:1=status\n$default:eu|us|ap\n$status:ok\n#region #status\n_2|_0")
+            .unwrap();
+        assert!(doc.validate().is_ok());
+
+        let doc = parser
+            .parse("!dicts:1=status\n$default:eu|us|ap\n$status:ok\n#region #status\n_0|_2")
+            .unwrap();
+        assert!(matches!(doc.validate(), Err(AlsError::InvalidDictRef { index: 2, size: 1 })));
+    }
+
+    #[test]
+    fn test_builder_builds_valid_document() {
+        let doc = AlsDocument::builder()
+            .with_schema(vec!["id", "status"])
+            .with_dictionary("default", vec!["ok".to_string(), "fail".to_string()])
+            .with_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 3)]))
+            .with_stream(ColumnStream::from_operators(vec![
+                AlsOperator::dict_ref(0),
+                AlsOperator::dict_ref(1),
+                AlsOperator::dict_ref(0),
+            ]))
+            .build()
+            .unwrap();
+
+        assert_eq!(doc.schema, vec!["id", "status"]);
+        assert_eq!(doc.row_count(), 3);
+        let rows = AlsParser::new().expand(&doc).unwrap();
+        assert_eq!(rows[1], vec!["2", "fail"]);
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_document() {
+        let result = AlsDocument::builder()
+            .with_schema(vec!["id", "name"])
+            .with_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 3)]))
+            .build();
+        assert!(matches!(result, Err(AlsError::ColumnMismatch { schema: 2, data: 1 })));
+
+        let result = AlsDocument::builder()
+            .with_schema(vec!["status"])
+            .with_dictionary("default", vec!["ok".to_string()])
+            .with_stream(ColumnStream::from_operators(vec![AlsOperator::dict_ref(1)]))
+            .build();
+        assert!(matches!(result, Err(AlsError::InvalidDictRef { index: 1, size: 1 })));
+    }
+
+    #[test]
+    fn test_merge_without_dictionary() {
+        let (doc, rows) = merge_rows("#id\n1>2", "$default:x\n#id\n_0");
+
+        assert_eq!(doc.dictionaries["default"], vec!["x"]);
+        assert_eq!(rows, vec![vec!["1"], vec!["2"], vec!["x"]]);
+    }
+
+    #[test]
+    fn test_merge_keeps_row_repeats() {
+        let (doc, rows) = merge_rows("!rows:1*2\n#a #b\n1>3|x y z", "!rows:0*3\n#a #b\n7 8|p q");
+
+        assert_eq!(doc.row_repeats, vec![(1, 2), (3, 3)]);
+        assert_eq!(doc.row_count(), 8);
+        let values: Vec<&str> = rows.iter().map(|row| row[1].as_str()).collect();
+        assert_eq!(values, vec!["x", "y", "y", "z", "p", "p", "p", "q"]);
+    }
+
+    #[test]
+    fn test_merge_downgrades_to_ctx() {
+        let mut doc = AlsDocument::with_schema(vec!["id"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 2)]));
+        let mut other = doc.clone();
+        other.set_ctx_format();
+
+        doc.merge(&other).unwrap();
+        assert!(doc.is_ctx());
+        assert_eq!(doc.row_count(), 4);
+    }
+
+    #[test]
+    fn test_merge_rejects_different_sentinel_tokens() {
+        let mut doc = AlsDocument::with_schema(vec!["id"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("NULL")]));
+        let mut other = doc.clone();
+        other.sentinel_tokens = SentinelTokens::new("NULL", "EMPTY");
+
+        assert!(matches!(doc.merge(&other), Err(AlsError::SentinelMismatch { .. })));
+        assert_eq!(doc.row_count(), 1);
+    }
+
+    #[test]
+    fn test_merge_keeps_agreeing_annotations() {
+        let mut doc = AlsDocument::with_schema(vec!["id", "price"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 2)]));
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("1.5"), AlsOperator::raw("2")]));
+        doc.set_encoding_hint("id", ColumnEncoding::Range);
+        doc.set_number_type("price", NumberType::Float);
+        let mut other = doc.clone();
+        other.set_encoding_hint("id", ColumnEncoding::Delta);
+
+        doc.merge(&other).unwrap();
+        assert_eq!(doc.encoding_hint("id"), None);
+        assert_eq!(doc.number_type("price"), Some(NumberType::Float));
+    }
+
+    #[test]
+    fn test_merge_schema_mismatch() {
+        let mut doc = AlsDocument::with_schema(vec!["id"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 2)]));
+        let mut other = AlsDocument::with_schema(vec!["key"]);
+        other.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(3, 4)]));
+
+        let result = doc.merge(&other);
+        assert!(matches!(result, Err(AlsError::SchemaMismatch { .. })));
+        assert_eq!(doc.row_count(), 2);
+    }
+
+    #[test]
+    fn test_merge_missing_streams() {
+        let mut doc = AlsDocument::with_schema(vec!["id"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 2)]));
+        let other = AlsDocument::with_schema(vec!["id"]);
+
+        let result = doc.merge(&other);
+        assert!(matches!(result, Err(AlsError::ColumnMismatch { schema: 1, data: 0 })));
+    }
 }
@@ -10,6 +10,8 @@
 //! - `\0` - Represents a null value
 //! - `\e` - Represents an empty string
 //!
+//! Documents can declare other tokens; see [`SentinelTokens`].
+//!
 //! # Escape Sequences
 //!
 //! | Character | Escape | Description |
@@ -22,11 +24,27 @@
 //! | `#` | `\#` | Schema prefix |
 //! | `$` | `\$` | Dictionary header prefix |
 //! | `:` | `\:` | Step separator in ranges |
+//! | `^` | `\^` | Delta operator |
+//! | `@` | `\@` | Timestamp operator prefix |
+//! | `,` | `\,` | Run list separator |
+//! | `(` | `\(` | Group open |
+//! | `)` | `\)` | Group close |
+//! | `%` | `\%` | Integer dictionary prefix |
+//! | `!` | `\!` | Sparse range exclusions |
+//! | `&` | `\&` | Interleave lane separator |
+//! | `{` | `\{` | Affix open |
+//! | `}` | `\}` | Affix close |
 //! | `\` | `\\` | Escape character itself |
 //! | newline | `\n` | Line break |
 //! | tab | `\t` | Tab character |
 //! | carriage return | `\r` | Carriage return |
 //! | space | `\ ` | Preserved space (in delimiter contexts) |
+//! | other ASCII control | `\xHH` | NUL, DEL, etc. as two hex digits |
+//!
+//! Every escaped character is ASCII, so multi-byte UTF-8 sequences (emoji,
+//! CJK text, combining marks) always pass through unchanged.
+
+use std::borrow::Cow;
 
 use crate::error::{AlsError, Result};
 
@@ -56,6 +74,49 @@ pub const NULL_TOKEN: &str = "\\0";
 /// ```
 pub const EMPTY_TOKEN: &str = "\\e";
 
+/// Marker that starts a comment line in ALS format.
+///
+/// A line whose first non-blank characters are `;;` is a comment, which the
+/// parser skips. Values that start with `;;` are escaped as `\;;`.
+///
+/// # Example
+///
+/// ```
+/// use als_compression::als::escape::COMMENT_MARKER;
+/// assert_eq!(COMMENT_MARKER, ";;");
+/// ```
+pub const COMMENT_MARKER: &str = ";;";
+
+/// Characters other than controls that `escape_als_string` prefixes with `\`.
+const OPERATOR_BYTES: &[u8] = b">*~|_#$:^@,()%!&{}\\ ";
+
+/// Bytes that `escape_als_string` rewrites, indexed by byte value.
+///
+/// Only ASCII bytes are set, so the bytes of a multi-byte UTF-8 sequence
+/// never match.
+const ESCAPED_BYTES: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut byte = 0;
+    while byte < 0x80 {
+        table[byte] = (byte as u8).is_ascii_control();
+        byte += 1;
+    }
+    let mut i = 0;
+    while i < OPERATOR_BYTES.len() {
+        table[OPERATOR_BYTES[i] as usize] = true;
+        i += 1;
+    }
+    table
+};
+
+/// Decode the two hex digits of a `\xHH` escape.
+///
+/// Returns `None` unless the digits name an ASCII character.
+pub(crate) fn decode_hex_escape(high: char, low: char) -> Option<char> {
+    let value = high.to_digit(16)? * 16 + low.to_digit(16)?;
+    char::from_u32(value).filter(char::is_ascii)
+}
+
 /// Escape a string for use in ALS format.
 ///
 /// This function escapes all characters that have special meaning in ALS
@@ -83,30 +144,40 @@ pub const EMPTY_TOKEN: &str = "\\e";
 ///
 /// // Escape newlines and tabs
 /// assert_eq!(escape_als_string("line1\nline2"), "line1\\nline2");
+///
+/// // Escape other control characters as hex
+/// assert_eq!(escape_als_string("a\0b"), "a\\x00b");
 /// ```
 pub fn escape_als_string(s: &str) -> String {
     // Pre-allocate with some extra capacity for escape sequences
     let mut result = String::with_capacity(s.len() + s.len() / 4);
-    
-    for c in s.chars() {
-        match c {
-            '>' => result.push_str("\\>"),
-            '*' => result.push_str("\\*"),
-            '~' => result.push_str("\\~"),
-            '|' => result.push_str("\\|"),
-            '_' => result.push_str("\\_"),
-            '#' => result.push_str("\\#"),
-            '$' => result.push_str("\\$"),
-            ':' => result.push_str("\\:"),
-            '\\' => result.push_str("\\\\"),
-            '\n' => result.push_str("\\n"),
-            '\t' => result.push_str("\\t"),
-            '\r' => result.push_str("\\r"),
-            ' ' => result.push_str("\\ "),
-            _ => result.push(c),
+
+    // A value starting a line must not read as a comment
+    if s.starts_with(COMMENT_MARKER) {
+        result.push('\\');
+    }
+
+    let mut copied = 0;
+    for (i, byte) in s.bytes().enumerate() {
+        if !ESCAPED_BYTES[byte as usize] {
+            continue;
+        }
+        // Escaped bytes are ASCII, so `i` is always a char boundary
+        result.push_str(&s[copied..i]);
+        match byte {
+            b'\n' => result.push_str("\\n"),
+            b'\t' => result.push_str("\\t"),
+            b'\r' => result.push_str("\\r"),
+            _ if byte.is_ascii_control() => result.push_str(&format!("\\x{:02X}", byte)),
+            _ => {
+                result.push('\\');
+                result.push(byte as char);
+            }
         }
+        copied = i + 1;
     }
-    
+    result.push_str(&s[copied..]);
+
     result
 }
 
@@ -129,6 +200,7 @@ pub fn escape_als_string(s: &str) -> String {
 /// Returns `AlsError::AlsSyntaxError` if:
 /// - An escape sequence is incomplete (trailing backslash)
 /// - An unknown escape sequence is encountered
+/// - A `\xHH` escape does not name an ASCII character
 ///
 /// # Example
 ///
@@ -143,6 +215,9 @@ pub fn escape_als_string(s: &str) -> String {
 ///
 /// // Unescape newlines and tabs
 /// assert_eq!(unescape_als_string("line1\\nline2").unwrap(), "line1\nline2");
+///
+/// // Unescape hex control characters
+/// assert_eq!(unescape_als_string("a\\x00b").unwrap(), "a\0b");
 /// ```
 pub fn unescape_als_string(s: &str) -> Result<String> {
     let mut result = String::with_capacity(s.len());
@@ -160,11 +235,39 @@ pub fn unescape_als_string(s: &str) -> Result<String> {
                 Some('#') => result.push('#'),
                 Some('$') => result.push('$'),
                 Some(':') => result.push(':'),
+                Some('^') => result.push('^'),
+                Some('@') => result.push('@'),
+                Some(',') => result.push(','),
+                Some('(') => result.push('('),
+                Some(')') => result.push(')'),
+                Some('%') => result.push('%'),
+                Some('!') => result.push('!'),
+                Some('&') => result.push('&'),
+                Some('{') => result.push('{'),
+                Some('}') => result.push('}'),
+                Some(';') => result.push(';'),
                 Some('\\') => result.push('\\'),
                 Some('n') => result.push('\n'),
                 Some('t') => result.push('\t'),
                 Some('r') => result.push('\r'),
                 Some(' ') => result.push(' '),
+                Some('x') => {
+                    let (high, low) = (chars.next(), chars.next());
+                    match high.zip(low).and_then(|(high, low)| decode_hex_escape(high, low)) {
+                        Some(c) => result.push(c),
+                        None => {
+                            let digits: String = high.into_iter().chain(low).collect();
+                            return Err(AlsError::AlsSyntaxError {
+                                position,
+                                message: format!(
+                                    "Unknown escape sequence: \\x{}, expected two hex digits of an ASCII character",
+                                    digits
+                                ),
+                            });
+                        }
+                    }
+                    position += 2;
+                }
                 Some('0') => {
                     // This is the NULL_TOKEN - return special marker
                     // The caller should handle this case specially
@@ -319,6 +422,137 @@ pub fn decode_als_value(s: &str) -> Result<Option<String>> {
     }
 }
 
+/// The pair of tokens that stand for null and empty values in a document.
+///
+/// Documents use [`NULL_TOKEN`] and [`EMPTY_TOKEN`] unless they declare
+/// other tokens with `!null:` and `!empty:` directives after the version
+/// line. Data strings that would read back as one of the active tokens are
+/// prefixed with a backslash when encoded, so a cell holding the literal
+/// text of a sentinel still round-trips as a string.
+///
+/// # Example
+///
+/// ```
+/// use als_compression::als::SentinelTokens;
+///
+/// let tokens = SentinelTokens::new("NULL", "EMPTY");
+/// assert_eq!(tokens.encode_str(""), "EMPTY");
+/// assert_eq!(tokens.encode_str("NULL"), "\\NULL");
+/// assert_eq!(tokens.decode_str("NULL".to_string()), None);
+/// assert_eq!(tokens.decode_str("\\NULL".to_string()), Some("NULL".to_string()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SentinelTokens {
+    null: Cow<'static, str>,
+    empty: Cow<'static, str>,
+}
+
+impl SentinelTokens {
+    /// The default tokens, [`NULL_TOKEN`] and [`EMPTY_TOKEN`].
+    pub const DEFAULT: SentinelTokens = SentinelTokens {
+        null: Cow::Borrowed(NULL_TOKEN),
+        empty: Cow::Borrowed(EMPTY_TOKEN),
+    };
+
+    /// Create a pair of null and empty tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either token is empty or if both tokens are the same.
+    pub fn new(null: impl Into<String>, empty: impl Into<String>) -> Self {
+        let (null, empty) = (null.into(), empty.into());
+        if let Err(message) = Self::validate(&null, &empty) {
+            panic!("{}", message);
+        }
+        Self {
+            null: Cow::Owned(null),
+            empty: Cow::Owned(empty),
+        }
+    }
+
+    /// Check that `null` and `empty` can be told apart.
+    pub(crate) fn validate(null: &str, empty: &str) -> std::result::Result<(), &'static str> {
+        if null.is_empty() || empty.is_empty() {
+            Err("sentinel tokens must not be empty")
+        } else if null == empty {
+            Err("null and empty tokens must differ")
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The token that stands for a null value.
+    pub fn null(&self) -> &str {
+        &self.null
+    }
+
+    /// The token that stands for an empty string.
+    pub fn empty(&self) -> &str {
+        &self.empty
+    }
+
+    /// Whether these are the default tokens.
+    pub fn is_default(&self) -> bool {
+        *self == Self::DEFAULT
+    }
+
+    /// Check if an expanded value is the null token.
+    #[inline]
+    pub fn is_null_token(&self, s: &str) -> bool {
+        s == self.null
+    }
+
+    /// Check if an expanded value is the empty token.
+    #[inline]
+    pub fn is_empty_token(&self, s: &str) -> bool {
+        s == self.empty
+    }
+
+    /// Encode a data string as it is stored in column streams.
+    ///
+    /// Empty strings become the empty token. Strings that read back as a
+    /// token, that is a token preceded by zero or more backslashes, get one
+    /// more leading backslash.
+    pub fn encode_str<'a>(&'a self, s: &'a str) -> Cow<'a, str> {
+        if s.is_empty() {
+            Cow::Borrowed(&self.empty)
+        } else if self.collides(s) {
+            Cow::Owned(format!("\\{}", s))
+        } else {
+            Cow::Borrowed(s)
+        }
+    }
+
+    /// Decode an expanded value, returning `None` for the null token.
+    ///
+    /// Reverses [`encode_str`](Self::encode_str).
+    pub fn decode_str(&self, s: String) -> Option<String> {
+        if self.is_null_token(&s) {
+            None
+        } else if self.is_empty_token(&s) {
+            Some(String::new())
+        } else if s.starts_with('\\') && self.collides(&s[1..]) {
+            Some(s[1..].to_string())
+        } else {
+            Some(s)
+        }
+    }
+
+    /// Check if `s` is a token preceded by zero or more backslashes.
+    pub(crate) fn collides(&self, s: &str) -> bool {
+        [self.null.as_ref(), self.empty.as_ref()].iter().any(|token| {
+            s.strip_suffix(token)
+                .is_some_and(|prefix| prefix.bytes().all(|b| b == b'\\'))
+        })
+    }
+}
+
+impl Default for SentinelTokens {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
 /// Check if a string needs escaping for ALS format.
 ///
 /// This is useful for optimization - if a string doesn't contain any
@@ -332,6 +566,8 @@ pub fn decode_als_value(s: &str) -> Result<Option<String>> {
 ///
 /// `true` if the string contains characters that need escaping.
 ///
+/// Scans bytes rather than characters, since only ASCII is ever escaped.
+///
 /// # Example
 ///
 /// ```
@@ -340,11 +576,10 @@ pub fn decode_als_value(s: &str) -> Result<Option<String>> {
 /// assert!(!needs_escaping("hello"));
 /// assert!(needs_escaping("a>b"));
 /// assert!(needs_escaping("line1\nline2"));
+/// assert!(needs_escaping("nul\0"));
 /// ```
 pub fn needs_escaping(s: &str) -> bool {
-    s.chars().any(|c| matches!(c, 
-        '>' | '*' | '~' | '|' | '_' | '#' | '$' | ':' | '\\' | '\n' | '\t' | '\r' | ' '
-    ))
+    s.starts_with(COMMENT_MARKER) || s.bytes().any(|byte| ESCAPED_BYTES[byte as usize])
 }
 
 #[cfg(test)]
@@ -366,6 +601,62 @@ mod tests {
         assert_eq!(escape_als_string("*"), "\\*");
     }
 
+    #[test]
+    fn test_escape_delta_operator() {
+        assert_eq!(escape_als_string("2^10"), "2\\^10");
+        assert_eq!(unescape_als_string("2\\^10").unwrap(), "2^10");
+        assert!(needs_escaping("^"));
+    }
+
+    #[test]
+    fn test_escape_run_list_separator() {
+        assert_eq!(escape_als_string("Smith,John"), "Smith\\,John");
+        assert_eq!(unescape_als_string("Smith\\,John").unwrap(), "Smith,John");
+        assert!(needs_escaping("a,b"));
+    }
+
+    #[test]
+    fn test_escape_sparse_range_exclusion() {
+        assert_eq!(escape_als_string("7!4"), "7\\!4");
+        assert_eq!(unescape_als_string("7\\!4").unwrap(), "7!4");
+        assert!(needs_escaping("hi!"));
+    }
+
+    #[test]
+    fn test_escape_group_parentheses() {
+        assert_eq!(escape_als_string("sshd(pam)"), "sshd\\(pam\\)");
+        assert_eq!(unescape_als_string("sshd\\(pam\\)").unwrap(), "sshd(pam)");
+        assert!(needs_escaping("(a)"));
+    }
+
+    #[test]
+    fn test_escape_int_dict_prefix() {
+        assert_eq!(escape_als_string("%200"), "\\%200");
+        assert_eq!(unescape_als_string("50\\%").unwrap(), "50%");
+        assert!(needs_escaping("50%"));
+    }
+
+    #[test]
+    fn test_escape_interleave_separator() {
+        assert_eq!(escape_als_string("AT&T"), "AT\\&T");
+        assert_eq!(unescape_als_string("AT\\&T").unwrap(), "AT&T");
+        assert!(needs_escaping("R&D"));
+    }
+
+    #[test]
+    fn test_escape_affix_braces() {
+        assert_eq!(escape_als_string("{a:b}"), "\\{a\\:b\\}");
+        assert_eq!(unescape_als_string("\\{x\\}").unwrap(), "{x}");
+        assert!(needs_escaping("{\"json\"}"));
+    }
+
+    #[test]
+    fn test_escape_timestamp_prefix() {
+        assert_eq!(escape_als_string("@T0Z"), "\\@T0Z");
+        assert_eq!(unescape_als_string("a\\@b").unwrap(), "a@b");
+        assert!(needs_escaping("user@example.com"));
+    }
+
     #[test]
     fn test_escape_toggle_operator() {
         assert_eq!(escape_als_string("a~b"), "a\\~b");
@@ -613,6 +904,48 @@ mod tests {
         assert_eq!(original, unescaped);
     }
 
+    #[test]
+    fn test_roundtrip_multibyte_text() {
+        // Emoji with modifiers and joiners, CJK, and combining characters
+        for original in ["👍🏽", "👨\u{200D}👩\u{200D}👧", "漢字 かな 한글", "e\u{301}", "a\u{0308}\u{0323}", "\u{3000}x"] {
+            let escaped = escape_als_string(original);
+            assert_eq!(unescape_als_string(&escaped).unwrap(), original);
+        }
+        // Multi-byte sequences are never rewritten, only the ASCII space
+        assert_eq!(escape_als_string("漢字 かな"), "漢字\\ かな");
+        assert_eq!(escape_als_string("e\u{301}"), "e\u{301}");
+    }
+
+    #[test]
+    fn test_escape_control_characters() {
+        assert_eq!(escape_als_string("a\0b"), "a\\x00b");
+        assert_eq!(escape_als_string("\u{1}\u{1F}\u{7F}"), "\\x01\\x1F\\x7F");
+        assert_eq!(escape_als_string("\u{b}\u{c}"), "\\x0B\\x0C");
+        assert_eq!(unescape_als_string("\\x1b[0m").unwrap(), "\u{1b}[0m");
+        assert_eq!(unescape_als_string("\\x7f").unwrap(), "\u{7f}");
+    }
+
+    #[test]
+    fn test_roundtrip_every_control_character() {
+        for byte in (0u8..0x20).chain([0x7F]) {
+            let original = format!("a{}b{}", byte as char, byte as char);
+            let escaped = escape_als_string(&original);
+            assert!(!escaped.bytes().any(|b| b.is_ascii_control()), "{:?}", escaped);
+            assert_eq!(unescape_als_string(&escaped).unwrap(), original);
+            assert!(needs_escaping(&original));
+        }
+    }
+
+    #[test]
+    fn test_unescape_invalid_hex_escape() {
+        for input in ["\\x0", "\\xZZ", "\\xC3", "\\x"] {
+            assert!(matches!(
+                unescape_als_string(input),
+                Err(AlsError::AlsSyntaxError { .. })
+            ), "{}", input);
+        }
+    }
+
     // ==================== Token tests ====================
 
     #[test]
@@ -631,6 +964,43 @@ mod tests {
         assert!(!is_empty_token("hello"));
     }
 
+    #[test]
+    fn test_sentinel_tokens_default() {
+        let tokens = SentinelTokens::default();
+        assert!(tokens.is_default());
+        assert_eq!(tokens.null(), NULL_TOKEN);
+        assert_eq!(tokens.empty(), EMPTY_TOKEN);
+        assert!(!SentinelTokens::new("NULL", EMPTY_TOKEN).is_default());
+    }
+
+    #[test]
+    fn test_sentinel_tokens_escape_collisions() {
+        let tokens = SentinelTokens::new("NULL", "-");
+        for data in ["NULL", "\\NULL", "\\\\NULL", "-", "\\-", "", "NULLS", "a\\NULL", "\\0"] {
+            let encoded = tokens.encode_str(data);
+            assert!(!tokens.is_null_token(&encoded), "{:?} encoded as null", data);
+            assert_eq!(tokens.decode_str(encoded.into_owned()), Some(data.to_string()));
+        }
+        assert_eq!(tokens.encode_str("NULL"), "\\NULL");
+        assert_eq!(tokens.encode_str("NULLS"), "NULLS");
+        assert_eq!(tokens.decode_str("NULL".to_string()), None);
+        assert_eq!(tokens.decode_str("-".to_string()), Some(String::new()));
+    }
+
+    #[test]
+    fn test_sentinel_tokens_default_escape_collisions() {
+        let tokens = SentinelTokens::DEFAULT;
+        assert_eq!(tokens.encode_str(NULL_TOKEN), "\\\\0");
+        assert_eq!(tokens.decode_str("\\\\0".to_string()), Some(NULL_TOKEN.to_string()));
+        assert_eq!(tokens.decode_str("\\\\e".to_string()), Some(EMPTY_TOKEN.to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "null and empty tokens must differ")]
+    fn test_sentinel_tokens_must_differ() {
+        SentinelTokens::new("x", "x");
+    }
+
     // ==================== encode/decode tests ====================
 
     #[test]
@@ -691,6 +1061,15 @@ mod tests {
         assert!(needs_escaping("a b"));
     }
 
+    #[test]
+    fn test_escape_comment_marker_prefix() {
+        assert_eq!(escape_als_string(";;note"), "\\;;note");
+        assert_eq!(escape_als_string("a;;b"), "a;;b");
+        assert_eq!(unescape_als_string("\\;;note").unwrap(), ";;note");
+        assert!(needs_escaping(";;"));
+        assert!(!needs_escaping("a;;b"));
+    }
+
     #[test]
     fn test_needs_escaping_false() {
         assert!(!needs_escaping("hello"));
@@ -698,5 +1077,14 @@ mod tests {
         assert!(!needs_escaping(""));
         assert!(!needs_escaping("日本語"));
         assert!(!needs_escaping("🎉"));
+        assert!(!needs_escaping("e\u{301}漢字"));
+    }
+
+    #[test]
+    fn test_needs_escaping_matches_escape() {
+        let samples = ["plain", "a b", "x\0", "日本>語", "🎉", "\u{7f}", "tab\t", "a;b", "a\u{85}b"];
+        for s in samples {
+            assert_eq!(needs_escaping(s), escape_als_string(s) != s, "{:?}", s);
+        }
     }
 }
@@ -3,19 +3,25 @@
 //! This module contains the core data structures for representing ALS documents,
 //! including operators, column streams, and document structures.
 
+pub mod checksum;
+pub mod codec;
 mod document;
 pub mod escape;
 mod operator;
 mod parser;
 mod serializer;
+mod timestamp;
 mod tokenizer;
 
-pub use document::{AlsDocument, ColumnStream, FormatIndicator};
+pub use codec::Codec;
+pub use document::{AlsDocument, AlsDocumentBuilder, ColumnEncoding, ColumnStream, FormatIndicator, NumberType};
 pub use escape::{
     decode_als_value, encode_als_value, escape_als_string, is_empty_token, is_null_token,
-    needs_escaping, unescape_als_string, EMPTY_TOKEN, NULL_TOKEN,
+    needs_escaping, unescape_als_string, SentinelTokens, COMMENT_MARKER, EMPTY_TOKEN, NULL_TOKEN,
 };
 pub use operator::AlsOperator;
 pub use parser::AlsParser;
 pub use serializer::{AlsPrettyPrinter, AlsSerializer};
-pub use tokenizer::{Token, Tokenizer, VersionType};
+pub(crate) use serializer::escape_dict_value;
+pub use timestamp::TimestampFormat;
+pub use tokenizer::{SpannedToken, Token, Tokenizer, Tokens, VersionType};
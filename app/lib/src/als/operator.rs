@@ -3,27 +3,59 @@
 //! This module defines the `AlsOperator` enum which represents the various
 //! compression operators used in the ALS format.
 
+use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
+
+use super::timestamp::TimestampFormat;
 use crate::config::CompressorConfig;
 use crate::error::{AlsError, Result};
 
 /// Default maximum range expansion limit.
 const DEFAULT_MAX_RANGE_EXPANSION: usize = 10_000_000;
 
+/// Largest magnitude a scaled float range value may have while remaining
+/// exactly representable as an `f64` (2^53).
+const MAX_EXACT_FLOAT_INTEGER: i64 = 1 << 53;
+
+/// Tag of the IPv4 operator, written in place of a timestamp format tag.
+pub(crate) const IPV4_TAG: &str = "ip4";
+
+/// Characters used to pack integer dictionary indices, six bits each.
+const PACKED_INDEX_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
 /// Represents a single ALS compression operator.
 ///
 /// ALS uses several operators to compress data:
 /// - `Raw`: Uncompressed literal values
 /// - `Range`: Sequential or arithmetic sequences (`start>end` or `start>end:step`)
+/// - `SparseRange`: Ranges with a few values left out (`start>end!e1,e2`)
+/// - `PatchedRange`: Ranges with a few values replaced (`start>end!=i1:v1,i2:v2`)
+/// - `FloatRange`: Decimal arithmetic sequences (`0.0>2.0:0.5`)
 /// - `Multiply`: Repeated values (`val*n`)
 /// - `Toggle`: Alternating patterns (`val1~val2*n`)
+/// - `RunList`: Consecutive runs of repeated values (`(a*3,b*2)`)
+/// - `Affix`: Values sharing a prefix and suffix (`{pre:suf}(v1,v2)`)
 /// - `DictRef`: Dictionary references (`_i`)
+/// - `Delta`: Integers encoded as consecutive differences (`base^dd1,d2,...`)
+/// - `Geometric`: Integers multiplied by a constant ratio (`start^*ratio#count`)
+/// - `Timestamp`: ISO-8601 timestamps encoded as epoch integers (`@tag(...)`)
+/// - `Ipv4`: IPv4 addresses encoded as integers (`@ip4(...)`)
+/// - `IntDict`: Low-cardinality integers as packed indices (`%v1,v2#count:packed`)
 ///
 /// # Serialization
 ///
 /// This type supports zero-copy serialization via rkyv for the non-recursive
 /// variants. The `Multiply` variant uses `Box<AlsOperator>` which requires
 /// special handling during serialization.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// # Equality
+///
+/// Operators compare structurally and implement `Eq` and `Hash`, so they can
+/// be deduplicated in a `HashSet` or used as map keys. The floats of a
+/// `FloatRange` compare by value with two normalizations: `0.0` equals
+/// `-0.0`, and NaN equals NaN whatever its payload.
+#[derive(Debug, Clone)]
 pub enum AlsOperator {
     /// Raw value: uncompressed literal string.
     ///
@@ -51,6 +83,74 @@ pub enum AlsOperator {
         step: i64,
     },
 
+    /// Sparse range operator: `start>end!e1,e2` or `start>end:step!e1,e2`.
+    ///
+    /// Represents a range with some of its values left out, such as an ID
+    /// column with gaps from deleted rows. The excluded values must belong
+    /// to the base range and are listed in the order the range produces them.
+    ///
+    /// # Examples
+    ///
+    /// - `1>7!4` expands to `1, 2, 3, 5, 6, 7`
+    /// - `10>1!8,3` expands to `10, 9, 7, 6, 5, 4, 2, 1`
+    SparseRange {
+        /// Starting value of the base range (inclusive)
+        start: i64,
+        /// Ending value of the base range (inclusive)
+        end: i64,
+        /// Step between consecutive values of the base range
+        step: i64,
+        /// Values of the base range that are skipped
+        excluded: Vec<i64>,
+    },
+
+    /// Patched range operator: `start>end!=i1:v1,i2:v2` or
+    /// `start>end:step!=i1:v1,i2:v2`.
+    ///
+    /// Represents a range in which a few positions hold other values, such
+    /// as an ID column with a handful of out-of-sequence IDs. Each override
+    /// replaces the value at a zero-based position of the base range, so the
+    /// operator expands to as many values as its base range. Overrides are
+    /// listed in increasing position order.
+    ///
+    /// # Examples
+    ///
+    /// - `1>6!=2:90` expands to `1, 2, 90, 4, 5, 6`
+    /// - `10>50:10!=0:7,4:-1` expands to `7, 20, 30, 40, -1`
+    PatchedRange {
+        /// Starting value of the base range (inclusive)
+        start: i64,
+        /// Ending value of the base range (inclusive)
+        end: i64,
+        /// Step between consecutive values of the base range
+        step: i64,
+        /// Positions in the base range paired with the value found there
+        overrides: Vec<(usize, i64)>,
+    },
+
+    /// Float range operator: `start>end:step` with decimal bounds.
+    ///
+    /// Represents a decimal arithmetic sequence from `start` to `end`
+    /// (inclusive). Values are reconstructed using fixed-point arithmetic
+    /// at `precision` decimal places, so `0.1>0.3:0.1` expands to
+    /// `0.1, 0.2, 0.3` rather than accumulating floating point error.
+    /// Every value is written with `precision` fraction digits.
+    ///
+    /// # Examples
+    ///
+    /// - `0.0>2.0:0.5` expands to `0.0, 0.5, 1.0, 1.5, 2.0`
+    /// - `1.25>0.75:-0.25` expands to `1.25, 1.00, 0.75`
+    FloatRange {
+        /// Starting value of the range (inclusive)
+        start: f64,
+        /// Ending value of the range (inclusive)
+        end: f64,
+        /// Step between consecutive values (can be negative for descending)
+        step: f64,
+        /// Number of decimal places used for fixed-point reconstruction
+        precision: u8,
+    },
+
     /// Multiplier operator: `val*n`.
     ///
     /// Represents a value repeated `count` times. The inner value can be
@@ -84,6 +184,60 @@ pub enum AlsOperator {
         count: usize,
     },
 
+    /// Run list operator: `(val1*n1,val2*n2,...)`.
+    ///
+    /// Represents consecutive runs of repeated values, such as a sorted
+    /// categorical column. Each value is repeated by its count in order.
+    ///
+    /// # Examples
+    ///
+    /// - `(A*3,B*2)` expands to `A, A, A, B, B`
+    /// - `(red*1,blue*2)` expands to `red, blue, blue`
+    RunList {
+        /// Values paired with the length of their run
+        runs: Vec<(String, usize)>,
+    },
+
+    /// Interleave operator: `(lane1&lane2&...)`.
+    ///
+    /// Merges the values of two or more lanes round-robin: the first value
+    /// of every lane in order, then the second, and so on. This suits
+    /// columns that alternate between independent sequences, such as a
+    /// counter interleaved with a constant. All lanes have the same length,
+    /// except that trailing lanes may be one value shorter so that columns
+    /// of any length can be represented.
+    ///
+    /// # Examples
+    ///
+    /// - `(1>3&A*3)` expands to `1, A, 2, A, 3, A`
+    /// - `(1>3&A*2)` expands to `1, A, 2, A, 3`
+    Interleave {
+        /// Operators whose values are merged, in lane order
+        lanes: Vec<AlsOperator>,
+    },
+
+    /// Affix operator: `{prefix:suffix}(value1,value2,...)`.
+    ///
+    /// Stores a prefix and suffix shared by every value once, followed by
+    /// the operators producing the varying middles. Each expanded value is
+    /// the prefix, a middle and the suffix concatenated. The `:suffix` part
+    /// is omitted when the suffix is empty. This suits URL, path and file
+    /// name columns.
+    ///
+    /// # Examples
+    ///
+    /// - `{https\://example.com/}(a,b)` expands to `https://example.com/a`,
+    ///   `https://example.com/b`
+    /// - `{img:.png}(1>3)` expands to `img1.png`, `img2.png`, `img3.png`
+    Affix {
+        /// Text shared at the start of every value
+        prefix: String,
+        /// Text shared at the end of every value
+        suffix: String,
+        /// Operators producing the middle of each value, in order
+        values: Vec<AlsOperator>,
+    },
+
     /// Dictionary reference: `_i`.
     ///
     /// References a value from the document's dictionary by index.
@@ -95,6 +249,100 @@ pub enum AlsOperator {
     /// - `_0` references the first dictionary entry
     /// - `_5` references the sixth dictionary entry
     DictRef(usize),
+
+    /// Delta operator: `base^dd1,d2,...`.
+    ///
+    /// Represents an integer sequence that starts at `base`, where each
+    /// following value is the previous value plus the next delta. This
+    /// suits nearly-arithmetic columns such as counters or timestamps
+    /// that mostly advance by a fixed amount but occasionally jump.
+    ///
+    /// # Examples
+    ///
+    /// - `100^d0,0,5,0` expands to `100, 100, 100, 105, 105`
+    /// - `10^d1,1,-3` expands to `10, 11, 12, 9`
+    Delta {
+        /// First value of the sequence
+        base: i64,
+        /// Differences between consecutive values
+        deltas: Vec<i64>,
+    },
+
+    /// Geometric operator: `start^*ratio#count`.
+    ///
+    /// Represents `count` integers starting at `start`, where each following
+    /// value is the previous value multiplied by `ratio`. Ratios of 0 and 1
+    /// are rejected on expansion, as those sequences are better encoded as
+    /// raw values or a multiplier.
+    ///
+    /// # Examples
+    ///
+    /// - `1^*2#5` expands to `1, 2, 4, 8, 16`
+    /// - `3^*10#3` expands to `3, 30, 300`
+    Geometric {
+        /// First value of the sequence
+        start: i64,
+        /// Ratio between consecutive values
+        ratio: i64,
+        /// Number of values in the sequence
+        count: usize,
+    },
+
+    /// Timestamp operator: `@tag(inner)`.
+    ///
+    /// Wraps an integer-producing operator whose values count units of
+    /// `10^-fraction_digits` seconds since `1970-01-01T00:00:00`. Each value
+    /// is formatted back to ISO-8601 text using the format described by the
+    /// tag, including fractional seconds and the timezone suffix.
+    ///
+    /// # Examples
+    ///
+    /// - `@T0Z(1672531200>1672531202)` expands to `2023-01-01T00:00:00Z`,
+    ///   `2023-01-01T00:00:01Z`, `2023-01-01T00:00:02Z`
+    /// - `@S3(1250^d500)` expands to `1970-01-01 00:00:01.250`,
+    ///   `1970-01-01 00:00:01.750`
+    Timestamp {
+        /// Textual layout used to format each value
+        format: TimestampFormat,
+        /// Operator producing the scaled epoch integers
+        value: Box<AlsOperator>,
+    },
+
+    /// IPv4 address operator: `@ip4(inner)`.
+    ///
+    /// Wraps an integer-producing operator whose values are IPv4 addresses
+    /// as unsigned 32-bit integers. Each value is formatted back to
+    /// dotted-quad text, so a range carries over octet boundaries.
+    ///
+    /// # Examples
+    ///
+    /// - `@ip4(167772161>167772163)` expands to `10.0.0.1`, `10.0.0.2`,
+    ///   `10.0.0.3`
+    /// - `@ip4(167772415>167772417)` expands to `10.0.0.255`, `10.0.1.0`,
+    ///   `10.0.1.1`
+    Ipv4 {
+        /// Operator producing the addresses as integers
+        value: Box<AlsOperator>,
+    },
+
+    /// Integer dictionary operator: `%v1,v2,...#count:packed`.
+    ///
+    /// Stores the distinct integers of a low-cardinality column once and
+    /// packs one index per value into base64 characters, using just enough
+    /// bits per index to address every distinct value (see
+    /// [`AlsOperator::int_dict_width`]). Indices are packed least significant
+    /// bit first. Values expand to integers rather than dictionary strings.
+    ///
+    /// # Examples
+    ///
+    /// - `%200,404#4:G` expands to `200, 404, 404, 200`
+    /// - `%100,200,404#3:k` expands to `100, 200, 404`
+    IntDict {
+        /// Distinct integer values
+        values: Vec<i64>,
+        /// Index into `values` of each expanded value
+        indices: Vec<usize>,
+    },
 }
 
 impl AlsOperator {
@@ -187,24 +435,18 @@ impl AlsOperator {
     /// # Errors
     ///
     /// Returns `AlsError::RangeOverflow` if the range would produce more
-    /// values than `max_expansion`.
+    /// values than `max_expansion`, or if `step` is 0 and the range would
+    /// never reach its end.
     pub fn range_safe_with_limit(
         start: i64,
         end: i64,
         step: i64,
         max_expansion: usize,
     ) -> Result<Self> {
-        if step == 0 {
-            return Err(AlsError::RangeOverflow { start, end, step });
-        }
-
-        let count = Self::calculate_range_count(start, end, step);
-        
-        if count > max_expansion as u64 {
-            return Err(AlsError::RangeOverflow { start, end, step });
+        match Self::checked_range_count(start, end, step) {
+            Some(count) if count <= max_expansion as u64 => Ok(AlsOperator::Range { start, end, step }),
+            _ => Err(AlsError::RangeOverflow { start, end, step }),
         }
-
-        Ok(AlsOperator::Range { start, end, step })
     }
 
     /// Create a Range operator using configuration limits.
@@ -224,10 +466,172 @@ impl AlsOperator {
         Self::range_safe_with_limit(start, end, step, config.max_range_expansion)
     }
 
+    /// Create a new SparseRange operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Starting value of the base range (inclusive)
+    /// * `end` - Ending value of the base range (inclusive)
+    /// * `step` - Step between consecutive values of the base range
+    /// * `excluded` - Values to skip, in the order the range produces them
+    ///
+    /// # Panics
+    ///
+    /// Panics if step is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// let op = AlsOperator::sparse_range(1, 7, 1, vec![4]);
+    /// assert_eq!(op.expand(None).unwrap(), vec!["1", "2", "3", "5", "6", "7"]);
+    /// ```
+    pub fn sparse_range(start: i64, end: i64, step: i64, excluded: Vec<i64>) -> Self {
+        assert!(step != 0, "Step cannot be zero");
+        AlsOperator::SparseRange { start, end, step, excluded }
+    }
+
+    /// Create a new SparseRange operator with validation and a custom
+    /// expansion limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::RangeOverflow` if the base range would produce
+    /// more values than `max_expansion`, or `AlsError::InvalidSparseRange`
+    /// if the step points away from the end, an excluded value is not part
+    /// of the base range, or the exclusions are not in range order.
+    pub fn sparse_range_safe_with_limit(
+        start: i64,
+        end: i64,
+        step: i64,
+        excluded: Vec<i64>,
+        max_expansion: usize,
+    ) -> Result<Self> {
+        Self::range_safe_with_limit(start, end, step, max_expansion)?;
+        Self::validate_sparse_range(start, end, step, &excluded)?;
+        Ok(AlsOperator::SparseRange { start, end, step, excluded })
+    }
+
+    /// Check that a sparse range only excludes values of its base range, in
+    /// range order, and return the number of values in the base range.
+    fn validate_sparse_range(start: i64, end: i64, step: i64, excluded: &[i64]) -> Result<u64> {
+        let invalid = |message: String| AlsError::InvalidSparseRange { start, end, message };
+
+        if step == 0 || (end >= start) != (step > 0) {
+            return Err(invalid(format!("step {} does not lead from start to end", step)));
+        }
+
+        let count = Self::calculate_range_count(start, end, step);
+        let mut previous = None;
+        for &value in excluded {
+            let position = Self::range_position(start, step, count, value)
+                .ok_or_else(|| invalid(format!("excluded value {} is not in the range", value)))?;
+            if previous.is_some_and(|previous| position <= previous) {
+                return Err(invalid(format!("excluded value {} is out of order", value)));
+            }
+            previous = Some(position);
+        }
+
+        Ok(count)
+    }
+
+    /// Create a new PatchedRange operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Starting value of the base range (inclusive)
+    /// * `end` - Ending value of the base range (inclusive)
+    /// * `step` - Step between consecutive values of the base range
+    /// * `overrides` - `(position, value)` pairs in increasing position order
+    ///
+    /// # Panics
+    ///
+    /// Panics if step is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// let op = AlsOperator::patched_range(1, 6, 1, vec![(2, 90)]);
+    /// assert_eq!(op.expand(None).unwrap(), vec!["1", "2", "90", "4", "5", "6"]);
+    /// ```
+    pub fn patched_range(start: i64, end: i64, step: i64, overrides: Vec<(usize, i64)>) -> Self {
+        assert!(step != 0, "Step cannot be zero");
+        AlsOperator::PatchedRange { start, end, step, overrides }
+    }
+
+    /// Create a new PatchedRange operator with validation and a custom
+    /// expansion limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::RangeOverflow` if the base range would produce
+    /// more values than `max_expansion`, or `AlsError::InvalidPatchedRange`
+    /// if the step points away from the end, an override position is past
+    /// the end of the base range, or the positions are not increasing.
+    pub fn patched_range_safe_with_limit(
+        start: i64,
+        end: i64,
+        step: i64,
+        overrides: Vec<(usize, i64)>,
+        max_expansion: usize,
+    ) -> Result<Self> {
+        Self::range_safe_with_limit(start, end, step, max_expansion)?;
+        Self::validate_patched_range(start, end, step, &overrides)?;
+        Ok(AlsOperator::PatchedRange { start, end, step, overrides })
+    }
+
+    /// Check that a patched range only overrides positions of its base
+    /// range, in increasing order, and return the number of values in the
+    /// base range.
+    fn validate_patched_range(start: i64, end: i64, step: i64, overrides: &[(usize, i64)]) -> Result<u64> {
+        let invalid = |message: String| AlsError::InvalidPatchedRange { start, end, message };
+
+        if step == 0 || (end >= start) != (step > 0) {
+            return Err(invalid(format!("step {} does not lead from start to end", step)));
+        }
+
+        let count = Self::calculate_range_count(start, end, step);
+        let mut previous = None;
+        for &(position, _) in overrides {
+            if position as u64 >= count {
+                return Err(invalid(format!("override position {} is past the end of the range", position)));
+            }
+            if previous.is_some_and(|previous| position <= previous) {
+                return Err(invalid(format!("override position {} is out of order", position)));
+            }
+            previous = Some(position);
+        }
+
+        Ok(count)
+    }
+
+    /// Index of `value` within the `count` values of a range, if it is one.
+    fn range_position(start: i64, step: i64, count: u64, value: i64) -> Option<u64> {
+        let offset = value as i128 - start as i128;
+        let step = step as i128;
+        if offset % step != 0 {
+            return None;
+        }
+        u64::try_from(offset / step).ok().filter(|&position| position < count)
+    }
+
     /// Calculate the number of values a range would produce.
+    ///
+    /// Saturates at `u64::MAX` where [`AlsOperator::checked_range_count`]
+    /// has no count.
     fn calculate_range_count(start: i64, end: i64, step: i64) -> u64 {
+        Self::checked_range_count(start, end, step).unwrap_or(u64::MAX)
+    }
+
+    /// Calculate the number of values a range would produce, or `None` if
+    /// `step` is 0 or the count does not fit in a `u64` (only
+    /// `i64::MIN>i64::MAX` with a step of 1 or -1).
+    fn checked_range_count(start: i64, end: i64, step: i64) -> Option<u64> {
         if step == 0 {
-            return u64::MAX; // Invalid, will trigger overflow error
+            return None;
         }
 
         // Check if the range is valid (step direction matches range direction)
@@ -237,20 +641,123 @@ impl AlsOperator {
         if range_ascending != step_positive {
             // Invalid range direction - would produce 0 or infinite values
             // Return 1 to just include the start value
-            return 1;
+            return Some(1);
+        }
+
+        // The span of two i64 values always fits in an i128
+        let diff = (end as i128 - start as i128).abs();
+        let abs_step = (step as i128).abs();
+        u64::try_from(diff / abs_step + 1).ok()
+    }
+
+    /// Create a new FloatRange operator.
+    ///
+    /// The decimal precision is derived from the shortest representation
+    /// of `start`, `end`, and `step`, and every value expands with that
+    /// many fraction digits.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Starting value (inclusive)
+    /// * `end` - Ending value (inclusive)
+    /// * `step` - Step between consecutive values
+    ///
+    /// # Panics
+    ///
+    /// Panics if step is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// let op = AlsOperator::float_range(0.0, 1.0, 0.25);
+    /// assert_eq!(op.expand(None).unwrap(), vec!["0.00", "0.25", "0.50", "0.75", "1.00"]);
+    /// ```
+    pub fn float_range(start: f64, end: f64, step: f64) -> Self {
+        assert!(step != 0.0, "Step cannot be zero");
+        let precision = Self::decimal_places(start)
+            .max(Self::decimal_places(end))
+            .max(Self::decimal_places(step));
+        AlsOperator::FloatRange { start, end, step, precision }
+    }
+
+    /// Create a new FloatRange operator with validation.
+    ///
+    /// Use this method when creating float ranges from untrusted input.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::InvalidFloatRange` if any bound is not finite,
+    /// the step is zero, the values cannot be represented exactly at the
+    /// detected precision, or the range would produce more values than
+    /// `max_expansion`.
+    pub fn float_range_safe_with_limit(
+        start: f64,
+        end: f64,
+        step: f64,
+        max_expansion: usize,
+    ) -> Result<Self> {
+        let invalid = |message: &str| AlsError::InvalidFloatRange {
+            start,
+            end,
+            step,
+            message: message.to_string(),
+        };
+
+        if !start.is_finite() || !end.is_finite() || !step.is_finite() {
+            return Err(invalid("bounds must be finite"));
+        }
+        if step == 0.0 {
+            return Err(invalid("step cannot be zero"));
+        }
+
+        let op = Self::float_range(start, end, step);
+        let (scaled_start, scaled_end, scaled_step) = op
+            .scaled_float_range()
+            .ok_or_else(|| invalid("values cannot be represented exactly"))?;
+
+        let count = Self::calculate_range_count(scaled_start, scaled_end, scaled_step);
+        if count > max_expansion as u64 {
+            return Err(invalid("range would produce too many values"));
         }
 
-        // Calculate count safely to avoid overflow
-        let diff = if range_ascending {
-            (end as i128) - (start as i128)
-        } else {
-            (start as i128) - (end as i128)
+        Ok(op)
+    }
+
+    /// Count the digits after the decimal point in the shortest
+    /// representation of `value`.
+    fn decimal_places(value: f64) -> u8 {
+        let repr = value.to_string();
+        repr.split_once('.')
+            .map(|(_, fraction)| fraction.len().min(u8::MAX as usize) as u8)
+            .unwrap_or(0)
+    }
+
+    /// Scale a FloatRange to fixed-point integers at its precision.
+    ///
+    /// Returns `None` if this is not a FloatRange or the scaled values
+    /// are not exactly representable.
+    fn scaled_float_range(&self) -> Option<(i64, i64, i64)> {
+        let AlsOperator::FloatRange { start, end, step, precision } = self else {
+            return None;
         };
 
-        let abs_step = (step as i128).abs();
-        let count = (diff / abs_step) + 1;
+        let factor = 10f64.powi(i32::from(*precision));
+        let scale = |value: f64| {
+            let scaled = (value * factor).round();
+            if scaled.abs() > MAX_EXACT_FLOAT_INTEGER as f64 || scaled / factor != value {
+                None
+            } else {
+                Some(scaled as i64)
+            }
+        };
 
-        count as u64
+        let scaled_step = scale(*step)?;
+        if scaled_step == 0 {
+            return None;
+        }
+        Some((scale(*start)?, scale(*end)?, scaled_step))
     }
 
     /// Create a new Multiply operator.
@@ -293,77 +800,560 @@ impl AlsOperator {
         }
     }
 
-    /// Create a new DictRef operator.
+    /// Create a new RunList operator.
     ///
     /// # Arguments
     ///
-    /// * `index` - Index into the dictionary
-    pub fn dict_ref(index: usize) -> Self {
-        AlsOperator::DictRef(index)
+    /// * `runs` - Values paired with the length of their run
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// let op = AlsOperator::run_list(vec![("A", 3), ("B", 2)]);
+    /// assert_eq!(op.expand(None).unwrap(), vec!["A", "A", "A", "B", "B"]);
+    /// ```
+    pub fn run_list<S: Into<String>>(runs: Vec<(S, usize)>) -> Self {
+        AlsOperator::RunList {
+            runs: runs.into_iter().map(|(value, count)| (value.into(), count)).collect(),
+        }
     }
 
-    /// Expand this operator into a vector of string values.
-    ///
-    /// This method recursively expands all operators to produce the
-    /// final sequence of values.
+    /// Create a new Interleave operator.
     ///
     /// # Arguments
     ///
-    /// * `dictionary` - Optional dictionary for resolving DictRef operators
+    /// * `lanes` - Operators whose values are merged round-robin
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// let op = AlsOperator::interleave(vec![
+    ///     AlsOperator::range(1, 3),
+    ///     AlsOperator::multiply(AlsOperator::raw("A"), 3),
+    /// ]);
+    /// assert_eq!(op.expand(None).unwrap(), vec!["1", "A", "2", "A", "3", "A"]);
+    /// ```
+    pub fn interleave(lanes: Vec<AlsOperator>) -> Self {
+        AlsOperator::Interleave { lanes }
+    }
+
+    /// Create a new Interleave operator with validation.
     ///
     /// # Errors
     ///
-    /// Returns `AlsError::InvalidDictRef` if a DictRef references an
-    /// index that doesn't exist in the dictionary.
-    pub fn expand(&self, dictionary: Option<&[String]>) -> Result<Vec<String>> {
-        match self {
-            AlsOperator::Raw(value) => Ok(vec![value.clone()]),
+    /// Returns `AlsError::InvalidInterleave` if there are fewer than two
+    /// lanes, or if a lane is longer than the lane before it or more than
+    /// one value shorter than the first lane.
+    pub fn interleave_safe(lanes: Vec<AlsOperator>) -> Result<Self> {
+        Self::validate_interleave(&lanes)?;
+        Ok(AlsOperator::Interleave { lanes })
+    }
 
-            AlsOperator::Range { start, end, step } => {
-                let mut values = Vec::new();
-                let mut current = *start;
-
-                if *step > 0 {
-                    while current <= *end {
-                        values.push(current.to_string());
-                        current = current.saturating_add(*step);
-                        if current < *start {
-                            // Overflow occurred
-                            break;
-                        }
-                    }
-                } else {
-                    while current >= *end {
-                        values.push(current.to_string());
-                        current = current.saturating_add(*step);
-                        if current > *start {
-                            // Underflow occurred
-                            break;
-                        }
-                    }
-                }
+    /// Check that an interleave has at least two lanes whose lengths only
+    /// drop by one after the first lane, and return its number of values.
+    fn validate_interleave(lanes: &[AlsOperator]) -> Result<usize> {
+        let invalid = |message: String| AlsError::InvalidInterleave {
+            lanes: lanes.len(),
+            message,
+        };
 
-                Ok(values)
+        if lanes.len() < 2 {
+            return Err(invalid("at least two lanes are required".to_string()));
+        }
+
+        let first = lanes[0].expanded_len();
+        let mut previous = first;
+        let mut total = 0usize;
+        for (i, lane) in lanes.iter().enumerate() {
+            let len = lane.expanded_len();
+            if len > previous || len + 1 < first {
+                return Err(invalid(format!(
+                    "lane {} has {} values but lane 1 has {}",
+                    i + 1,
+                    len,
+                    first
+                )));
+            }
+            previous = len;
+            total += len;
+        }
+
+        Ok(total)
+    }
+
+    /// Create a new Affix operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Text shared at the start of every value
+    /// * `suffix` - Text shared at the end of every value
+    /// * `values` - Operators producing the middle of each value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// let op = AlsOperator::affix("img", ".png", vec![AlsOperator::range(1, 2)]);
+    /// assert_eq!(op.expand(None).unwrap(), vec!["img1.png", "img2.png"]);
+    /// ```
+    pub fn affix<P: Into<String>, S: Into<String>>(prefix: P, suffix: S, values: Vec<AlsOperator>) -> Self {
+        AlsOperator::Affix {
+            prefix: prefix.into(),
+            suffix: suffix.into(),
+            values,
+        }
+    }
+
+    /// Create a new DictRef operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index into the dictionary
+    pub fn dict_ref(index: usize) -> Self {
+        AlsOperator::DictRef(index)
+    }
+
+    /// Create a new IntDict operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Distinct integer values
+    /// * `indices` - Index into `values` of each expanded value
+    pub fn int_dict(values: Vec<i64>, indices: Vec<usize>) -> Self {
+        AlsOperator::IntDict { values, indices }
+    }
+
+    /// Number of bits used to pack each index of an IntDict with
+    /// `cardinality` distinct values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    ///
+    /// assert_eq!(AlsOperator::int_dict_width(2), 1);
+    /// assert_eq!(AlsOperator::int_dict_width(3), 2);
+    /// assert_eq!(AlsOperator::int_dict_width(256), 8);
+    /// ```
+    pub fn int_dict_width(cardinality: usize) -> u32 {
+        (usize::BITS - cardinality.saturating_sub(1).leading_zeros()).max(1)
+    }
+
+    /// Pack indices into base64 characters, `width` bits per index.
+    pub(crate) fn pack_indices(indices: &[usize], width: u32) -> String {
+        let mut packed = String::with_capacity((indices.len() * width as usize).div_ceil(6));
+        let mut buffer: u64 = 0;
+        let mut bits = 0;
+        for &index in indices {
+            buffer |= (index as u64) << bits;
+            bits += width;
+            while bits >= 6 {
+                packed.push(PACKED_INDEX_ALPHABET[(buffer & 0x3F) as usize] as char);
+                buffer >>= 6;
+                bits -= 6;
+            }
+        }
+        if bits > 0 {
+            packed.push(PACKED_INDEX_ALPHABET[(buffer & 0x3F) as usize] as char);
+        }
+        packed
+    }
+
+    /// Unpack `count` indices of `width` bits from base64 characters.
+    ///
+    /// Returns `None` if the packed text has an invalid character or holds
+    /// fewer than `count` indices.
+    pub(crate) fn unpack_indices(packed: &str, width: u32, count: usize) -> Option<Vec<usize>> {
+        if width == 0 || width > 32 || packed.len() * 6 < count.checked_mul(width as usize)? {
+            return None;
+        }
+
+        let mask = (1u64 << width) - 1;
+        let mut indices = Vec::with_capacity(count);
+        let mut buffer: u64 = 0;
+        let mut bits = 0;
+        for byte in packed.bytes() {
+            let digit = PACKED_INDEX_ALPHABET.iter().position(|&c| c == byte)?;
+            buffer |= (digit as u64) << bits;
+            bits += 6;
+            while bits >= width && indices.len() < count {
+                indices.push((buffer & mask) as usize);
+                buffer >>= width;
+                bits -= width;
+            }
+        }
+        Some(indices)
+    }
+
+    /// Look up the value of an IntDict index.
+    fn int_dict_value(values: &[i64], index: usize) -> Result<String> {
+        values
+            .get(index)
+            .map(|value| value.to_string())
+            .ok_or(AlsError::InvalidDictRef {
+                index,
+                size: values.len(),
+            })
+    }
+
+    /// Create a new Delta operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `base` - First value of the sequence
+    /// * `deltas` - Differences between consecutive values
+    pub fn delta(base: i64, deltas: Vec<i64>) -> Self {
+        AlsOperator::Delta { base, deltas }
+    }
+
+    /// Create a new Geometric operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - First value of the sequence
+    /// * `ratio` - Ratio between consecutive values
+    /// * `count` - Number of values in the sequence
+    pub fn geometric(start: i64, ratio: i64, count: usize) -> Self {
+        AlsOperator::Geometric { start, ratio, count }
+    }
+
+    /// Create a Geometric operator with validation and a custom expansion limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::InvalidGeometric` if the ratio is 0 or 1, if
+    /// `count` exceeds `max_expansion`, or if the last value overflows `i64`.
+    pub fn geometric_safe_with_limit(
+        start: i64,
+        ratio: i64,
+        count: usize,
+        max_expansion: usize,
+    ) -> Result<Self> {
+        if count > max_expansion {
+            return Err(Self::invalid_geometric(start, ratio, "sequence would produce too many values"));
+        }
+        if count > 0 {
+            Self::geometric_value(start, ratio, count - 1)?;
+        }
+        Ok(AlsOperator::Geometric { start, ratio, count })
+    }
+
+    /// Build an `InvalidGeometric` error.
+    fn invalid_geometric(start: i64, ratio: i64, message: &str) -> AlsError {
+        AlsError::InvalidGeometric {
+            start,
+            ratio,
+            message: message.to_string(),
+        }
+    }
+
+    /// Compute `start * ratio^index`, rejecting ratios of 0 or 1 and overflow.
+    fn geometric_value(start: i64, ratio: i64, index: usize) -> Result<i64> {
+        if ratio == 0 || ratio == 1 {
+            return Err(Self::invalid_geometric(start, ratio, "ratio must not be 0 or 1"));
+        }
+        u32::try_from(index)
+            .ok()
+            .and_then(|exp| ratio.checked_pow(exp))
+            .and_then(|factor| start.checked_mul(factor))
+            .ok_or_else(|| Self::invalid_geometric(start, ratio, "value exceeds the i64 range"))
+    }
+
+    /// Create a new Timestamp operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - Textual layout used to format each value
+    /// * `value` - Operator producing the scaled epoch integers
+    pub fn timestamp(format: TimestampFormat, value: AlsOperator) -> Self {
+        AlsOperator::Timestamp {
+            format,
+            value: Box::new(value),
+        }
+    }
+
+    /// Create a new IPv4 operator.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - Operator producing the addresses as integers
+    pub fn ipv4(value: AlsOperator) -> Self {
+        AlsOperator::Ipv4 {
+            value: Box::new(value),
+        }
+    }
+
+    /// Format an integer string as a dotted-quad IPv4 address.
+    fn format_ipv4(value: &str) -> Result<String> {
+        let address = value.parse::<u32>().map_err(|_| AlsError::InvalidIpv4 {
+            value: value.to_string(),
+            message: "not an integer between 0 and 4294967295".to_string(),
+        })?;
+        Ok(std::net::Ipv4Addr::from(address).to_string())
+    }
+
+    /// Format a scaled epoch integer string as a timestamp.
+    fn format_timestamp(format: &TimestampFormat, value: &str) -> Result<String> {
+        let invalid = |message: &str| AlsError::InvalidTimestamp {
+            value: value.to_string(),
+            message: message.to_string(),
+        };
+        let epoch = value
+            .parse::<i64>()
+            .map_err(|_| invalid("not an integer"))?;
+        format
+            .format(epoch)
+            .ok_or_else(|| invalid("outside the years 0000-9999"))
+    }
+
+    /// Check that this operator is well formed without expanding it.
+    ///
+    /// `dict_len` is the number of entries in the dictionary that `DictRef`
+    /// operators resolve against (0 when there is none). Nested operators
+    /// are checked recursively.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::InvalidDictRef` if a DictRef or IntDict index is
+    /// out of range, and otherwise the error expansion would report for a
+    /// malformed Range, SparseRange, PatchedRange, FloatRange, Interleave,
+    /// Delta or Geometric. Checks that depend on the expanded values, such
+    /// as whether a Timestamp value can be formatted, are left to expansion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::als::AlsOperator;
+    /// use als_compression::AlsError;
+    ///
+    /// let op = AlsOperator::multiply(AlsOperator::dict_ref(2), 3);
+    /// assert!(op.validate(3).is_ok());
+    /// assert!(matches!(op.validate(2), Err(AlsError::InvalidDictRef { index: 2, size: 2 })));
+    /// ```
+    pub fn validate(&self, dict_len: usize) -> Result<()> {
+        match self {
+            AlsOperator::Raw(_) | AlsOperator::Toggle { .. } | AlsOperator::RunList { .. } => {}
+            AlsOperator::Range { start, end, step } => {
+                if Self::checked_range_count(*start, *end, *step).is_none() {
+                    return Err(AlsError::RangeOverflow { start: *start, end: *end, step: *step });
+                }
+            }
+            AlsOperator::SparseRange { start, end, step, excluded } => {
+                Self::validate_sparse_range(*start, *end, *step, excluded)?;
+            }
+            AlsOperator::PatchedRange { start, end, step, overrides } => {
+                Self::validate_patched_range(*start, *end, *step, overrides)?;
+            }
+            AlsOperator::FloatRange { start, end, step, .. } => {
+                if self.scaled_float_range().is_none() {
+                    return Err(AlsError::InvalidFloatRange {
+                        start: *start,
+                        end: *end,
+                        step: *step,
+                        message: "values cannot be represented exactly".to_string(),
+                    });
+                }
+            }
+            AlsOperator::Multiply { value, .. }
+            | AlsOperator::Timestamp { value, .. }
+            | AlsOperator::Ipv4 { value } => value.validate(dict_len)?,
+            AlsOperator::Interleave { lanes } => {
+                Self::validate_interleave(lanes)?;
+                for lane in lanes {
+                    lane.validate(dict_len)?;
+                }
+            }
+            AlsOperator::Affix { values, .. } => {
+                for value in values {
+                    value.validate(dict_len)?;
+                }
+            }
+            AlsOperator::DictRef(index) => {
+                if *index >= dict_len {
+                    return Err(AlsError::InvalidDictRef { index: *index, size: dict_len });
+                }
+            }
+            AlsOperator::Delta { base, deltas } => {
+                let mut current = *base;
+                for (i, delta) in deltas.iter().enumerate() {
+                    current = current.checked_add(*delta).ok_or(AlsError::DeltaOverflow {
+                        base: *base,
+                        index: i + 1,
+                    })?;
+                }
+            }
+            AlsOperator::Geometric { start, ratio, count } => {
+                if *count > 0 {
+                    Self::geometric_value(*start, *ratio, count - 1)?;
+                }
+            }
+            AlsOperator::IntDict { values, indices } => {
+                if let Some(&index) = indices.iter().find(|&&index| index >= values.len()) {
+                    return Err(AlsError::InvalidDictRef { index, size: values.len() });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Expand this operator into a vector of string values.
+    ///
+    /// This method recursively expands all operators to produce the
+    /// final sequence of values.
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary` - Optional dictionary for resolving DictRef operators
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::InvalidDictRef` if a DictRef references an
+    /// index that doesn't exist in the dictionary,
+    /// `AlsError::DeltaOverflow` if a Delta reconstructs a value outside
+    /// the `i64` range, `AlsError::RangeOverflow` if a Range has a step of 0
+    /// or more values than a `u64` can count, `AlsError::InvalidGeometric`
+    /// if a Geometric has a ratio of 0 or 1 or overflows, or
+    /// `AlsError::InvalidTimestamp` if a Timestamp wraps a value that cannot
+    /// be formatted, or `AlsError::InvalidIpv4` if an Ipv4 wraps a value that
+    /// is not an address.
+    pub fn expand(&self, dictionary: Option<&[String]>) -> Result<Vec<String>> {
+        // Bound the up-front allocation; a range built without the safe
+        // constructors may hold more values than fit in memory
+        let capacity = self.expanded_len().min(DEFAULT_MAX_RANGE_EXPANSION);
+        let mut values = Vec::with_capacity(capacity);
+        self.expand_into(dictionary, &mut |value| values.push(value.into_owned()))?;
+        Ok(values)
+    }
+
+    /// Expand this operator, passing each value to `sink` in order.
+    ///
+    /// Unlike [`AlsOperator::expand`], no intermediate `Vec` is allocated:
+    /// literal, toggle, run list and dictionary values are borrowed, and
+    /// computed values are produced one at a time. Values already passed
+    /// to `sink` stay delivered if a later value fails to expand.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`AlsOperator::expand`].
+    pub fn expand_into(
+        &self,
+        dictionary: Option<&[String]>,
+        sink: &mut dyn FnMut(Cow<'_, str>),
+    ) -> Result<()> {
+        match self {
+            AlsOperator::Raw(value) => sink(Cow::Borrowed(value)),
+
+            AlsOperator::Range { start, end, step } => {
+                let (start, end, step) = (*start, *end, *step);
+                let count = Self::checked_range_count(start, end, step)
+                    .ok_or(AlsError::RangeOverflow { start, end, step })?;
+
+                // A range whose step points away from its end expands to nothing
+                if (end >= start) == (step > 0) {
+                    // Stepping by index cannot overshoot `end` and wrap
+                    for i in 0..count {
+                        let value = (start as i128 + i as i128 * step as i128) as i64;
+                        sink(Cow::Owned(value.to_string()));
+                    }
+                }
+            }
+
+            AlsOperator::SparseRange { start, end, step, excluded } => {
+                let count = Self::validate_sparse_range(*start, *end, *step, excluded)?;
+                let mut excluded = excluded.iter().peekable();
+                for i in 0..count {
+                    let value = (*start as i128 + i as i128 * *step as i128) as i64;
+                    if excluded.next_if_eq(&&value).is_none() {
+                        sink(Cow::Owned(value.to_string()));
+                    }
+                }
+            }
+
+            AlsOperator::PatchedRange { start, end, step, overrides } => {
+                let count = Self::validate_patched_range(*start, *end, *step, overrides)?;
+                let mut overrides = overrides.iter().peekable();
+                for i in 0..count {
+                    let value = match overrides.next_if(|&&(position, _)| position as u64 == i) {
+                        Some(&(_, value)) => value,
+                        None => (*start as i128 + i as i128 * *step as i128) as i64,
+                    };
+                    sink(Cow::Owned(value.to_string()));
+                }
+            }
+
+            AlsOperator::FloatRange { start, end, step, precision } => {
+                let (scaled_start, scaled_end, scaled_step) =
+                    self.scaled_float_range().ok_or_else(|| AlsError::InvalidFloatRange {
+                        start: *start,
+                        end: *end,
+                        step: *step,
+                        message: "values cannot be represented exactly".to_string(),
+                    })?;
+
+                let count = Self::calculate_range_count(scaled_start, scaled_end, scaled_step);
+                let factor = 10f64.powi(i32::from(*precision));
+                for i in 0..count as i64 {
+                    let scaled = scaled_start + i * scaled_step;
+                    sink(Cow::Owned(format!("{:.*}", *precision as usize, scaled as f64 / factor)));
+                }
             }
 
             AlsOperator::Multiply { value, count } => {
-                let expanded = value.expand(dictionary)?;
-                let mut result = Vec::with_capacity(expanded.len() * count);
+                // A zero count still validates the repeated operator
+                if *count == 0 {
+                    value.expand_into(dictionary, &mut |_| {})?;
+                }
                 for _ in 0..*count {
-                    result.extend(expanded.iter().cloned());
+                    value.expand_into(dictionary, sink)?;
                 }
-                Ok(result)
             }
 
             AlsOperator::Toggle { values, count } => {
-                if values.is_empty() {
-                    return Ok(Vec::new());
+                if !values.is_empty() {
+                    for i in 0..*count {
+                        sink(Cow::Borrowed(&values[i % values.len()]));
+                    }
                 }
-                let mut result = Vec::with_capacity(*count);
-                for i in 0..*count {
-                    result.push(values[i % values.len()].clone());
+            }
+
+            AlsOperator::RunList { runs } => {
+                for (value, count) in runs {
+                    for _ in 0..*count {
+                        sink(Cow::Borrowed(value));
+                    }
+                }
+            }
+
+            AlsOperator::Interleave { lanes } => {
+                Self::validate_interleave(lanes)?;
+                // Shorter lanes come last, so a round ends early only once
+                // every lane is exhausted
+                let mut cursors: Vec<OperatorCursor> = lanes.iter().map(OperatorCursor::new).collect();
+                loop {
+                    let mut produced = false;
+                    for (lane, cursor) in lanes.iter().zip(&mut cursors) {
+                        if let Some(value) = cursor.next_value(lane, dictionary) {
+                            sink(Cow::Owned(value?));
+                            produced = true;
+                        }
+                    }
+                    if !produced {
+                        break;
+                    }
+                }
+            }
+
+            AlsOperator::Affix { prefix, suffix, values } => {
+                for value in values {
+                    value.expand_into(dictionary, &mut |middle| {
+                        sink(Cow::Owned(format!("{}{}{}", prefix, middle, suffix)))
+                    })?;
                 }
-                Ok(result)
             }
 
             AlsOperator::DictRef(index) => {
@@ -372,14 +1362,106 @@ impl AlsOperator {
                     size: 0,
                 })?;
 
-                dict.get(*index)
-                    .map(|s| vec![s.clone()])
-                    .ok_or(AlsError::InvalidDictRef {
-                        index: *index,
-                        size: dict.len(),
-                    })
+                let value = dict.get(*index).ok_or(AlsError::InvalidDictRef {
+                    index: *index,
+                    size: dict.len(),
+                })?;
+                sink(Cow::Borrowed(value));
+            }
+
+            AlsOperator::Delta { base, deltas } => {
+                let mut current = *base;
+                sink(Cow::Owned(current.to_string()));
+                for (i, delta) in deltas.iter().enumerate() {
+                    current = current.checked_add(*delta).ok_or(AlsError::DeltaOverflow {
+                        base: *base,
+                        index: i + 1,
+                    })?;
+                    sink(Cow::Owned(current.to_string()));
+                }
+            }
+
+            AlsOperator::Geometric { start, ratio, count } => {
+                if *count == 0 {
+                    return Ok(());
+                }
+                let mut current = Self::geometric_value(*start, *ratio, 0)?;
+                sink(Cow::Owned(current.to_string()));
+                for _ in 1..*count {
+                    current = current.checked_mul(*ratio).ok_or_else(|| {
+                        Self::invalid_geometric(*start, *ratio, "value exceeds the i64 range")
+                    })?;
+                    sink(Cow::Owned(current.to_string()));
+                }
+            }
+
+            AlsOperator::Timestamp { format, value } => {
+                // The inner sink cannot fail, so keep the first formatting error
+                let mut error = None;
+                value.expand_into(dictionary, &mut |epoch| {
+                    if error.is_none() {
+                        match Self::format_timestamp(format, &epoch) {
+                            Ok(formatted) => sink(Cow::Owned(formatted)),
+                            Err(e) => error = Some(e),
+                        }
+                    }
+                })?;
+                if let Some(e) = error {
+                    return Err(e);
+                }
+            }
+
+            AlsOperator::Ipv4 { value } => {
+                let mut error = None;
+                value.expand_into(dictionary, &mut |address| {
+                    if error.is_none() {
+                        match Self::format_ipv4(&address) {
+                            Ok(formatted) => sink(Cow::Owned(formatted)),
+                            Err(e) => error = Some(e),
+                        }
+                    }
+                })?;
+                if let Some(e) = error {
+                    return Err(e);
+                }
+            }
+
+            AlsOperator::IntDict { values, indices } => {
+                for &index in indices {
+                    sink(Cow::Owned(Self::int_dict_value(values, index)?));
+                }
             }
         }
+
+        Ok(())
+    }
+
+    /// Returns the exact number of values [`AlsOperator::expand`] produces,
+    /// without materializing them.
+    ///
+    /// Unlike [`AlsOperator::expanded_count`], a range whose step points away
+    /// from its end and a toggle without values both count as empty.
+    pub fn expanded_len(&self) -> usize {
+        match self {
+            // A range whose step points away from its end expands to nothing
+            AlsOperator::Range { start, end, step }
+            | AlsOperator::SparseRange { start, end, step, .. }
+            | AlsOperator::PatchedRange { start, end, step, .. }
+                if *step == 0 || (end >= start) != (*step > 0) =>
+            {
+                0
+            }
+            AlsOperator::Multiply { value, count } => value.expanded_len().saturating_mul(*count),
+            AlsOperator::Timestamp { value, .. } | AlsOperator::Ipv4 { value } => value.expanded_len(),
+            AlsOperator::Interleave { lanes } => {
+                lanes.iter().map(AlsOperator::expanded_len).fold(0, usize::saturating_add)
+            }
+            AlsOperator::Affix { values, .. } => {
+                values.iter().map(AlsOperator::expanded_len).fold(0, usize::saturating_add)
+            }
+            AlsOperator::Toggle { values, .. } if values.is_empty() => 0,
+            _ => self.expanded_count(),
+        }
     }
 
     /// Returns the number of values this operator will produce when expanded.
@@ -392,9 +1474,54 @@ impl AlsOperator {
             AlsOperator::Range { start, end, step } => {
                 Self::calculate_range_count(*start, *end, *step) as usize
             }
-            AlsOperator::Multiply { value, count } => value.expanded_count() * count,
+            AlsOperator::SparseRange { start, end, step, excluded } => {
+                Self::calculate_range_count(*start, *end, *step)
+                    .saturating_sub(excluded.len() as u64) as usize
+            }
+            AlsOperator::PatchedRange { start, end, step, .. } => {
+                Self::calculate_range_count(*start, *end, *step) as usize
+            }
+            AlsOperator::FloatRange { .. } => self
+                .scaled_float_range()
+                .map(|(start, end, step)| Self::calculate_range_count(start, end, step) as usize)
+                .unwrap_or(0),
+            AlsOperator::Multiply { value, count } => value.expanded_count().saturating_mul(*count),
             AlsOperator::Toggle { count, .. } => *count,
+            AlsOperator::RunList { runs } => {
+                runs.iter().map(|&(_, count)| count).fold(0, usize::saturating_add)
+            }
+            AlsOperator::Interleave { lanes } => {
+                lanes.iter().map(AlsOperator::expanded_count).fold(0, usize::saturating_add)
+            }
+            AlsOperator::Affix { values, .. } => {
+                values.iter().map(AlsOperator::expanded_count).fold(0, usize::saturating_add)
+            }
             AlsOperator::DictRef(_) => 1,
+            AlsOperator::Delta { deltas, .. } => deltas.len() + 1,
+            AlsOperator::Geometric { count, .. } => *count,
+            AlsOperator::Timestamp { value, .. } | AlsOperator::Ipv4 { value } => value.expanded_count(),
+            AlsOperator::IntDict { indices, .. } => indices.len(),
+        }
+    }
+
+    /// Renumber the dictionary references of this operator, recursively.
+    ///
+    /// Each `DictRef(i)` becomes `DictRef(j)` where `remap[i]` is `Some(j)`;
+    /// references without a mapping are left untouched.
+    pub(crate) fn remap_dict_refs(&mut self, remap: &[Option<usize>]) {
+        match self {
+            AlsOperator::DictRef(index) => {
+                if let Some(Some(new_index)) = remap.get(*index) {
+                    *index = *new_index;
+                }
+            }
+            AlsOperator::Multiply { value, .. }
+            | AlsOperator::Timestamp { value, .. }
+            | AlsOperator::Ipv4 { value } => value.remap_dict_refs(remap),
+            AlsOperator::Interleave { lanes } | AlsOperator::Affix { values: lanes, .. } => {
+                lanes.iter_mut().for_each(|lane| lane.remap_dict_refs(remap))
+            }
+            _ => {}
         }
     }
 
@@ -408,6 +1535,21 @@ impl AlsOperator {
         matches!(self, AlsOperator::Range { .. })
     }
 
+    /// Returns true if this operator is a SparseRange.
+    pub fn is_sparse_range(&self) -> bool {
+        matches!(self, AlsOperator::SparseRange { .. })
+    }
+
+    /// Returns true if this operator is a PatchedRange.
+    pub fn is_patched_range(&self) -> bool {
+        matches!(self, AlsOperator::PatchedRange { .. })
+    }
+
+    /// Returns true if this operator is a FloatRange.
+    pub fn is_float_range(&self) -> bool {
+        matches!(self, AlsOperator::FloatRange { .. })
+    }
+
     /// Returns true if this operator is a Multiply.
     pub fn is_multiply(&self) -> bool {
         matches!(self, AlsOperator::Multiply { .. })
@@ -418,10 +1560,360 @@ impl AlsOperator {
         matches!(self, AlsOperator::Toggle { .. })
     }
 
+    /// Returns true if this operator is a RunList.
+    pub fn is_run_list(&self) -> bool {
+        matches!(self, AlsOperator::RunList { .. })
+    }
+
+    /// Returns true if this operator is an Interleave.
+    pub fn is_interleave(&self) -> bool {
+        matches!(self, AlsOperator::Interleave { .. })
+    }
+
+    /// Returns true if this operator is an Affix.
+    pub fn is_affix(&self) -> bool {
+        matches!(self, AlsOperator::Affix { .. })
+    }
+
     /// Returns true if this operator is a DictRef.
     pub fn is_dict_ref(&self) -> bool {
         matches!(self, AlsOperator::DictRef(_))
     }
+
+    /// Returns true if this operator is a Delta.
+    pub fn is_delta(&self) -> bool {
+        matches!(self, AlsOperator::Delta { .. })
+    }
+
+    /// Returns true if this operator is a Geometric.
+    pub fn is_geometric(&self) -> bool {
+        matches!(self, AlsOperator::Geometric { .. })
+    }
+
+    /// Returns true if this operator is a Timestamp.
+    pub fn is_timestamp(&self) -> bool {
+        matches!(self, AlsOperator::Timestamp { .. })
+    }
+
+    /// Returns true if this operator is an Ipv4.
+    pub fn is_ipv4(&self) -> bool {
+        matches!(self, AlsOperator::Ipv4 { .. })
+    }
+
+    /// Returns true if this operator is an IntDict.
+    pub fn is_int_dict(&self) -> bool {
+        matches!(self, AlsOperator::IntDict { .. })
+    }
+}
+
+/// Bit pattern a `FloatRange` bound compares and hashes by.
+///
+/// Both zeros map to the pattern of `0.0` and every NaN to that of
+/// `f64::NAN`, which keeps equality reflexive and consistent with hashing.
+fn float_bits(value: f64) -> u64 {
+    if value == 0.0 {
+        0
+    } else if value.is_nan() {
+        f64::NAN.to_bits()
+    } else {
+        value.to_bits()
+    }
+}
+
+impl PartialEq for AlsOperator {
+    fn eq(&self, other: &Self) -> bool {
+        use AlsOperator::*;
+        match (self, other) {
+            (Raw(a), Raw(b)) => a == b,
+            (
+                Range { start: s1, end: e1, step: t1 },
+                Range { start: s2, end: e2, step: t2 },
+            ) => (s1, e1, t1) == (s2, e2, t2),
+            (
+                SparseRange { start: s1, end: e1, step: t1, excluded: x1 },
+                SparseRange { start: s2, end: e2, step: t2, excluded: x2 },
+            ) => (s1, e1, t1, x1) == (s2, e2, t2, x2),
+            (
+                PatchedRange { start: s1, end: e1, step: t1, overrides: o1 },
+                PatchedRange { start: s2, end: e2, step: t2, overrides: o2 },
+            ) => (s1, e1, t1, o1) == (s2, e2, t2, o2),
+            (
+                FloatRange { start: s1, end: e1, step: t1, precision: p1 },
+                FloatRange { start: s2, end: e2, step: t2, precision: p2 },
+            ) => {
+                float_bits(*s1) == float_bits(*s2)
+                    && float_bits(*e1) == float_bits(*e2)
+                    && float_bits(*t1) == float_bits(*t2)
+                    && p1 == p2
+            }
+            (Multiply { value: v1, count: c1 }, Multiply { value: v2, count: c2 }) => {
+                c1 == c2 && v1 == v2
+            }
+            (Toggle { values: v1, count: c1 }, Toggle { values: v2, count: c2 }) => {
+                c1 == c2 && v1 == v2
+            }
+            (RunList { runs: a }, RunList { runs: b }) => a == b,
+            (Interleave { lanes: a }, Interleave { lanes: b }) => a == b,
+            (
+                Affix { prefix: p1, suffix: s1, values: v1 },
+                Affix { prefix: p2, suffix: s2, values: v2 },
+            ) => p1 == p2 && s1 == s2 && v1 == v2,
+            (DictRef(a), DictRef(b)) => a == b,
+            (Delta { base: b1, deltas: d1 }, Delta { base: b2, deltas: d2 }) => {
+                b1 == b2 && d1 == d2
+            }
+            (
+                Geometric { start: s1, ratio: r1, count: c1 },
+                Geometric { start: s2, ratio: r2, count: c2 },
+            ) => (s1, r1, c1) == (s2, r2, c2),
+            (Timestamp { format: f1, value: v1 }, Timestamp { format: f2, value: v2 }) => {
+                f1 == f2 && v1 == v2
+            }
+            (Ipv4 { value: a }, Ipv4 { value: b }) => a == b,
+            (IntDict { values: v1, indices: i1 }, IntDict { values: v2, indices: i2 }) => {
+                v1 == v2 && i1 == i2
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for AlsOperator {}
+
+impl Hash for AlsOperator {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            AlsOperator::Raw(value) => value.hash(state),
+            AlsOperator::Range { start, end, step } => (start, end, step).hash(state),
+            AlsOperator::SparseRange { start, end, step, excluded } => {
+                (start, end, step, excluded).hash(state)
+            }
+            AlsOperator::PatchedRange { start, end, step, overrides } => {
+                (start, end, step, overrides).hash(state)
+            }
+            AlsOperator::FloatRange { start, end, step, precision } => {
+                (float_bits(*start), float_bits(*end), float_bits(*step), precision).hash(state)
+            }
+            AlsOperator::Multiply { value, count } => (value, count).hash(state),
+            AlsOperator::Toggle { values, count } => (values, count).hash(state),
+            AlsOperator::RunList { runs } => runs.hash(state),
+            AlsOperator::Interleave { lanes } => lanes.hash(state),
+            AlsOperator::Affix { prefix, suffix, values } => (prefix, suffix, values).hash(state),
+            AlsOperator::DictRef(index) => index.hash(state),
+            AlsOperator::Delta { base, deltas } => (base, deltas).hash(state),
+            AlsOperator::Geometric { start, ratio, count } => (start, ratio, count).hash(state),
+            AlsOperator::Timestamp { format, value } => (format, value).hash(state),
+            AlsOperator::Ipv4 { value } => value.hash(state),
+            AlsOperator::IntDict { values, indices } => (values, indices).hash(state),
+        }
+    }
+}
+
+/// Cursor that yields the expanded values of a single operator one at a time.
+///
+/// The cursor does not borrow the operator; the same operator must be passed
+/// to every call of [`OperatorCursor::next_value`]. Values are computed from
+/// their index rather than materialized, except for `Delta`, whose running
+/// sum is carried between consecutive indices, and `RunList`, whose current
+/// run is remembered so that runs are not rescanned for every value. The
+/// lanes of an `Interleave` and the middles of an `Affix` each get a cursor
+/// of their own.
+#[derive(Debug, Clone)]
+pub(crate) struct OperatorCursor {
+    /// Index of the next value to yield
+    position: usize,
+    /// Total number of values the operator expands to
+    len: usize,
+    /// Last reconstructed Delta value and its index
+    delta: Option<(usize, i64)>,
+    /// Last visited RunList run or Affix middle and the index of its first value
+    run: (usize, usize),
+    /// Cursors of the lanes of an Interleave or the middles of an Affix,
+    /// created on first use
+    lanes: Vec<OperatorCursor>,
+}
+
+impl OperatorCursor {
+    /// Create a cursor positioned at the first value of `op`.
+    pub(crate) fn new(op: &AlsOperator) -> Self {
+        Self {
+            position: 0,
+            len: op.expanded_len(),
+            delta: None,
+            run: (0, 0),
+            lanes: Vec::new(),
+        }
+    }
+
+    /// Yield the next value of `op`, or `None` once it is exhausted.
+    pub(crate) fn next_value(
+        &mut self,
+        op: &AlsOperator,
+        dictionary: Option<&[String]>,
+    ) -> Option<Result<String>> {
+        if self.position >= self.len {
+            return None;
+        }
+        let index = self.position;
+        self.position += 1;
+        Some(self.value_at(op, index, dictionary))
+    }
+
+    /// Compute the value at `index` within the expansion of `op`.
+    fn value_at(
+        &mut self,
+        op: &AlsOperator,
+        index: usize,
+        dictionary: Option<&[String]>,
+    ) -> Result<String> {
+        match op {
+            AlsOperator::Raw(value) => Ok(value.clone()),
+
+            AlsOperator::Range { start, step, .. } => {
+                Ok((*start as i128 + index as i128 * *step as i128).to_string())
+            }
+
+            AlsOperator::SparseRange { start, end, step, excluded } => {
+                let count = AlsOperator::validate_sparse_range(*start, *end, *step, excluded)?;
+                // Shift past every excluded position at or before the value
+                let mut position = index as u64;
+                for &value in excluded {
+                    match AlsOperator::range_position(*start, *step, count, value) {
+                        Some(skipped) if skipped <= position => position += 1,
+                        _ => break,
+                    }
+                }
+                Ok((*start as i128 + position as i128 * *step as i128).to_string())
+            }
+
+            AlsOperator::PatchedRange { start, end, step, overrides } => {
+                AlsOperator::validate_patched_range(*start, *end, *step, overrides)?;
+                match overrides.binary_search_by_key(&index, |&(position, _)| position) {
+                    Ok(found) => Ok(overrides[found].1.to_string()),
+                    Err(_) => Ok((*start as i128 + index as i128 * *step as i128).to_string()),
+                }
+            }
+
+            AlsOperator::FloatRange { start, end, step, precision } => {
+                let (scaled_start, _, scaled_step) =
+                    op.scaled_float_range().ok_or_else(|| AlsError::InvalidFloatRange {
+                        start: *start,
+                        end: *end,
+                        step: *step,
+                        message: "values cannot be represented exactly".to_string(),
+                    })?;
+                let factor = 10f64.powi(i32::from(*precision));
+                let scaled = scaled_start + index as i64 * scaled_step;
+                Ok(format!("{:.*}", *precision as usize, scaled as f64 / factor))
+            }
+
+            AlsOperator::Multiply { value, .. } => {
+                let inner_len = value.expanded_len();
+                self.value_at(value, index % inner_len, dictionary)
+            }
+
+            AlsOperator::Toggle { values, .. } => Ok(values[index % values.len()].clone()),
+
+            AlsOperator::RunList { runs } => {
+                // Restarted inside a Multiply: scan again from the first run
+                let (mut run, mut run_start) = self.run;
+                if index < run_start {
+                    (run, run_start) = (0, 0);
+                }
+                while index >= run_start + runs[run].1 {
+                    run_start += runs[run].1;
+                    run += 1;
+                }
+                self.run = (run, run_start);
+                Ok(runs[run].0.clone())
+            }
+
+            AlsOperator::Interleave { lanes } => {
+                AlsOperator::validate_interleave(lanes)?;
+                if self.lanes.is_empty() {
+                    self.lanes = lanes.iter().map(OperatorCursor::new).collect();
+                }
+                // The last lane is the shortest; past its end only the
+                // longer leading lanes still have values
+                let shortest = lanes[lanes.len() - 1].expanded_len();
+                let full_rounds = shortest * lanes.len();
+                let (lane, lane_index) = if index < full_rounds {
+                    (index % lanes.len(), index / lanes.len())
+                } else {
+                    (index - full_rounds, shortest)
+                };
+                self.lanes[lane].value_at(&lanes[lane], lane_index, dictionary)
+            }
+
+            AlsOperator::Affix { prefix, suffix, values } => {
+                if self.lanes.is_empty() {
+                    self.lanes = values.iter().map(OperatorCursor::new).collect();
+                }
+                // Restarted inside a Multiply: scan again from the first middle
+                let (mut part, mut part_start) = self.run;
+                if index < part_start {
+                    (part, part_start) = (0, 0);
+                }
+                while index >= part_start + values[part].expanded_len() {
+                    part_start += values[part].expanded_len();
+                    part += 1;
+                }
+                self.run = (part, part_start);
+                let middle = self.lanes[part].value_at(&values[part], index - part_start, dictionary)?;
+                Ok(format!("{}{}{}", prefix, middle, suffix))
+            }
+
+            AlsOperator::DictRef(index) => {
+                let dict = dictionary.ok_or(AlsError::InvalidDictRef {
+                    index: *index,
+                    size: 0,
+                })?;
+
+                dict.get(*index).cloned().ok_or(AlsError::InvalidDictRef {
+                    index: *index,
+                    size: dict.len(),
+                })
+            }
+
+            AlsOperator::Delta { base, deltas } => {
+                let value = match self.delta {
+                    _ if index == 0 => Some(*base),
+                    Some((last, current)) if index == last + 1 => {
+                        current.checked_add(deltas[index - 1])
+                    }
+                    // Restarted inside a Multiply or skipped ahead: recompute
+                    _ => deltas[..index]
+                        .iter()
+                        .try_fold(*base, |acc, delta| acc.checked_add(*delta)),
+                }
+                .ok_or(AlsError::DeltaOverflow {
+                    base: *base,
+                    index,
+                })?;
+                self.delta = Some((index, value));
+                Ok(value.to_string())
+            }
+
+            AlsOperator::Geometric { start, ratio, .. } => {
+                AlsOperator::geometric_value(*start, *ratio, index).map(|value| value.to_string())
+            }
+
+            AlsOperator::Timestamp { format, value } => {
+                let epoch = self.value_at(value, index, dictionary)?;
+                AlsOperator::format_timestamp(format, &epoch)
+            }
+
+            AlsOperator::Ipv4 { value } => {
+                let address = self.value_at(value, index, dictionary)?;
+                AlsOperator::format_ipv4(&address)
+            }
+
+            AlsOperator::IntDict { values, indices } => {
+                AlsOperator::int_dict_value(values, indices[index])
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -458,23 +1950,97 @@ mod tests {
     }
 
     #[test]
-    fn test_range_with_step() {
-        let op = AlsOperator::range_with_step(10, 50, 10);
-        assert_eq!(
-            op.expand(None).unwrap(),
-            vec!["10", "20", "30", "40", "50"]
-        );
-        assert_eq!(op.expanded_count(), 5);
+    fn test_range_with_step() {
+        let op = AlsOperator::range_with_step(10, 50, 10);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["10", "20", "30", "40", "50"]
+        );
+        assert_eq!(op.expanded_count(), 5);
+    }
+
+    #[test]
+    fn test_range_with_negative_step() {
+        let op = AlsOperator::range_with_step(50, 10, -10);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["50", "40", "30", "20", "10"]
+        );
+        assert_eq!(op.expanded_count(), 5);
+    }
+
+    #[test]
+    fn test_sparse_range_expand() {
+        let op = AlsOperator::sparse_range(1, 7, 1, vec![4]);
+        assert!(op.is_sparse_range());
+        assert_eq!(op.expand(None).unwrap(), vec!["1", "2", "3", "5", "6", "7"]);
+        assert_eq!(op.expanded_count(), 6);
+        assert_eq!(op.expanded_len(), 6);
+
+        let op = AlsOperator::sparse_range(10, 1, -1, vec![8, 3]);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["10", "9", "7", "6", "5", "4", "2", "1"]
+        );
+
+        let op = AlsOperator::sparse_range(0, 50, 10, vec![10, 40]);
+        assert_eq!(op.expand(None).unwrap(), vec!["0", "20", "30", "50"]);
+    }
+
+    #[test]
+    fn test_patched_range_expand() {
+        let op = AlsOperator::patched_range(1, 6, 1, vec![(0, 900), (2, -5)]);
+        assert!(op.is_patched_range());
+        assert_eq!(op.expand(None).unwrap(), vec!["900", "2", "-5", "4", "5", "6"]);
+        assert_eq!(op.expanded_count(), 6);
+        assert_eq!(op.expanded_len(), 6);
+
+        let op = AlsOperator::patched_range(20, 0, -10, vec![(2, 7)]);
+        assert_eq!(op.expand(None).unwrap(), vec!["20", "10", "7"]);
+    }
+
+    #[test]
+    fn test_patched_range_safe_rejects_invalid_overrides() {
+        for overrides in [vec![(6, 1)], vec![(3, 1), (1, 2)], vec![(2, 1), (2, 5)]] {
+            assert!(matches!(
+                AlsOperator::patched_range_safe_with_limit(1, 6, 1, overrides, 100),
+                Err(AlsError::InvalidPatchedRange { .. })
+            ));
+        }
+        assert!(matches!(
+            AlsOperator::patched_range_safe_with_limit(1, 6, -1, vec![(0, 5)], 100),
+            Err(AlsError::InvalidPatchedRange { .. })
+        ));
+        assert!(matches!(
+            AlsOperator::patched_range(1, 6, 1, vec![(8, 0)]).expand(None),
+            Err(AlsError::InvalidPatchedRange { .. })
+        ));
     }
 
     #[test]
-    fn test_range_with_negative_step() {
-        let op = AlsOperator::range_with_step(50, 10, -10);
-        assert_eq!(
-            op.expand(None).unwrap(),
-            vec!["50", "40", "30", "20", "10"]
-        );
-        assert_eq!(op.expanded_count(), 5);
+    fn test_sparse_range_safe_rejects_invalid_exclusions() {
+        for excluded in [vec![9], vec![5, 3], vec![4, 4], vec![0]] {
+            assert!(matches!(
+                AlsOperator::sparse_range_safe_with_limit(1, 7, 1, excluded, 100),
+                Err(AlsError::InvalidSparseRange { .. })
+            ));
+        }
+        assert!(matches!(
+            AlsOperator::sparse_range_safe_with_limit(0, 10, 2, vec![3], 100),
+            Err(AlsError::InvalidSparseRange { .. })
+        ));
+        assert!(matches!(
+            AlsOperator::sparse_range_safe_with_limit(1, 7, -1, vec![4], 100),
+            Err(AlsError::InvalidSparseRange { .. })
+        ));
+        assert!(matches!(
+            AlsOperator::sparse_range_safe_with_limit(1, 1000, 1, vec![4], 100),
+            Err(AlsError::RangeOverflow { .. })
+        ));
+        assert!(matches!(
+            AlsOperator::sparse_range(1, 7, 1, vec![9]).expand(None),
+            Err(AlsError::InvalidSparseRange { .. })
+        ));
     }
 
     #[test]
@@ -504,6 +2070,60 @@ mod tests {
         assert!(matches!(result, Err(AlsError::RangeOverflow { .. })));
     }
 
+    #[test]
+    fn test_range_safe_extreme_bounds() {
+        // 2^64 values, which must not wrap around to a small count
+        for (start, end, step) in [(i64::MIN, i64::MAX, 1), (i64::MAX, i64::MIN, -1)] {
+            assert!(matches!(
+                AlsOperator::range_safe_with_limit(start, end, step, usize::MAX),
+                Err(AlsError::RangeOverflow { .. })
+            ));
+        }
+
+        let op = AlsOperator::range_safe(i64::MIN, i64::MAX, i64::MAX).unwrap();
+        assert_eq!(op.expanded_count(), 3);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec![i64::MIN.to_string(), "-1".to_string(), (i64::MAX - 1).to_string()]
+        );
+
+        let op = AlsOperator::range_safe(i64::MAX, i64::MIN, i64::MIN).unwrap();
+        assert_eq!(op.expand(None).unwrap(), vec![i64::MAX.to_string(), "-1".to_string()]);
+    }
+
+    #[test]
+    fn test_range_expands_up_to_i64_limits() {
+        let op = AlsOperator::range(i64::MAX - 2, i64::MAX);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec![(i64::MAX - 2).to_string(), (i64::MAX - 1).to_string(), i64::MAX.to_string()]
+        );
+
+        let op = AlsOperator::range(i64::MIN + 1, i64::MIN);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec![(i64::MIN + 1).to_string(), i64::MIN.to_string()]
+        );
+    }
+
+    #[test]
+    fn test_range_uneven_step() {
+        let op = AlsOperator::range_safe(1, 10, 4).unwrap();
+        assert_eq!(op.expanded_count(), 3);
+        assert_eq!(op.expand(None).unwrap(), vec!["1", "5", "9"]);
+
+        let op = AlsOperator::range_safe(10, -5, -7).unwrap();
+        assert_eq!(op.expand(None).unwrap(), vec!["10", "3", "-4"]);
+    }
+
+    #[test]
+    fn test_range_expand_rejects_invalid_counts() {
+        for (start, end, step) in [(1, 10, 0), (10, 1, 0), (i64::MIN, i64::MAX, 1)] {
+            let op = AlsOperator::Range { start, end, step };
+            assert!(matches!(op.expand(None), Err(AlsError::RangeOverflow { .. })));
+        }
+    }
+
     #[test]
     fn test_multiply_operator() {
         let op = AlsOperator::multiply(AlsOperator::raw("hello"), 3);
@@ -596,6 +2216,49 @@ mod tests {
         assert_eq!(op1, op2);
     }
 
+    #[test]
+    fn test_float_range_ascending() {
+        let op = AlsOperator::float_range(0.0, 2.0, 0.5);
+        assert!(op.is_float_range());
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["0.0", "0.5", "1.0", "1.5", "2.0"]
+        );
+        assert_eq!(op.expanded_count(), 5);
+    }
+
+    #[test]
+    fn test_float_range_descending() {
+        let op = AlsOperator::float_range(1.25, 0.75, -0.25);
+        assert_eq!(op.expand(None).unwrap(), vec!["1.25", "1.00", "0.75"]);
+        assert_eq!(op.expanded_count(), 3);
+    }
+
+    #[test]
+    fn test_float_range_precision_sensitive() {
+        // Naive accumulation would produce 0.30000000000000004
+        let op = AlsOperator::float_range(0.1, 0.5, 0.1);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["0.1", "0.2", "0.3", "0.4", "0.5"]
+        );
+
+        let op = AlsOperator::float_range(-0.03, 0.03, 0.01);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["-0.03", "-0.02", "-0.01", "0.00", "0.01", "0.02", "0.03"]
+        );
+    }
+
+    #[test]
+    fn test_float_range_safe_rejects_invalid() {
+        assert!(AlsOperator::float_range_safe_with_limit(0.0, 1.0, 0.0, 100).is_err());
+        assert!(AlsOperator::float_range_safe_with_limit(0.0, f64::INFINITY, 0.5, 100).is_err());
+        assert!(AlsOperator::float_range_safe_with_limit(f64::NAN, 1.0, 0.5, 100).is_err());
+        assert!(AlsOperator::float_range_safe_with_limit(0.0, 1e12, 0.001, 1_000_000).is_err());
+        assert!(AlsOperator::float_range_safe_with_limit(0.0, 1.0, 0.5, 100).is_ok());
+    }
+
     #[test]
     fn test_operator_clone() {
         let op = AlsOperator::multiply(AlsOperator::range(1, 3), 2);
@@ -638,5 +2301,469 @@ mod tests {
         assert!(AlsOperator::multiply(AlsOperator::raw("x"), 2).is_multiply());
         assert!(AlsOperator::toggle("a", "b", 4).is_toggle());
         assert!(AlsOperator::dict_ref(0).is_dict_ref());
+        assert!(AlsOperator::delta(1, vec![1, 1]).is_delta());
+    }
+
+    #[test]
+    fn test_delta_expand() {
+        let op = AlsOperator::delta(100, vec![0, 0, 5, 0]);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["100", "100", "100", "105", "105"]
+        );
+        assert_eq!(op.expanded_count(), 5);
+    }
+
+    #[test]
+    fn test_delta_negative_deltas() {
+        let op = AlsOperator::delta(-2, vec![-3, 10, -1]);
+        assert_eq!(op.expand(None).unwrap(), vec!["-2", "-5", "5", "4"]);
+    }
+
+    #[test]
+    fn test_delta_empty_deltas() {
+        let op = AlsOperator::delta(7, vec![]);
+        assert_eq!(op.expand(None).unwrap(), vec!["7"]);
+        assert_eq!(op.expanded_count(), 1);
+    }
+
+    #[test]
+    fn test_delta_overflow() {
+        let op = AlsOperator::delta(i64::MAX - 1, vec![1, 1]);
+        let result = op.expand(None);
+        assert!(matches!(
+            result,
+            Err(AlsError::DeltaOverflow { index: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_geometric_powers_of_two() {
+        let op = AlsOperator::geometric(1, 2, 5);
+        assert!(op.is_geometric());
+        assert_eq!(op.expanded_count(), 5);
+        assert_eq!(op.expand(None).unwrap(), vec!["1", "2", "4", "8", "16"]);
+    }
+
+    #[test]
+    fn test_geometric_negative_values() {
+        let op = AlsOperator::geometric(-3, -2, 4);
+        assert_eq!(op.expand(None).unwrap(), vec!["-3", "6", "-12", "24"]);
+    }
+
+    #[test]
+    fn test_geometric_rejects_ratio_zero_or_one() {
+        for ratio in [0, 1] {
+            let op = AlsOperator::geometric(5, ratio, 3);
+            assert!(matches!(
+                op.expand(None),
+                Err(AlsError::InvalidGeometric { .. })
+            ));
+            assert!(matches!(
+                AlsOperator::geometric_safe_with_limit(5, ratio, 3, 100),
+                Err(AlsError::InvalidGeometric { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_geometric_overflow() {
+        // 2^62 fits, 2^63 does not
+        let op = AlsOperator::geometric(1, 2, 64);
+        assert!(matches!(
+            op.expand(None),
+            Err(AlsError::InvalidGeometric { .. })
+        ));
+        assert!(AlsOperator::geometric_safe_with_limit(1, 2, 63, 100).is_ok());
+        assert!(AlsOperator::geometric_safe_with_limit(1, 2, 64, 100).is_err());
+        assert!(AlsOperator::geometric_safe_with_limit(1, 10, 1_000_000, 100).is_err());
+        assert!(matches!(drain_cursor(&op), Err(AlsError::InvalidGeometric { .. })));
+    }
+
+    fn drain_cursor(op: &AlsOperator) -> Result<Vec<String>> {
+        let mut cursor = OperatorCursor::new(op);
+        std::iter::from_fn(|| cursor.next_value(op, None)).collect()
+    }
+
+    #[test]
+    fn test_operator_cursor_matches_expand() {
+        let ops = vec![
+            AlsOperator::range_with_step(10, 50, 10),
+            AlsOperator::range_with_step(1, 5, -1),
+            AlsOperator::float_range(1.25, 0.75, -0.25),
+            AlsOperator::multiply(AlsOperator::delta(100, vec![1, -3, 7]), 3),
+            AlsOperator::multiply(AlsOperator::geometric(3, 10, 4), 2),
+            AlsOperator::multiply(AlsOperator::toggle("a", "b", 3), 2),
+            AlsOperator::multiply(AlsOperator::run_list(vec![("x", 2), ("y", 0), ("z", 1)]), 3),
+            AlsOperator::toggle_multi(Vec::<String>::new(), 4),
+            AlsOperator::timestamp(
+                TimestampFormat::from_tag("T0Z").unwrap(),
+                AlsOperator::delta(1_672_531_200, vec![60, 60, 3600]),
+            ),
+            AlsOperator::multiply(AlsOperator::int_dict(vec![200, 404], vec![1, 0, 0]), 2),
+            AlsOperator::multiply(AlsOperator::sparse_range(1, 9, 1, vec![2, 3, 7]), 2),
+            AlsOperator::sparse_range(20, 0, -5, vec![20, 0]),
+            AlsOperator::multiply(
+                AlsOperator::interleave(vec![
+                    AlsOperator::run_list(vec![("x", 2), ("y", 1)]),
+                    AlsOperator::delta(5, vec![1, 1]),
+                    AlsOperator::run_list(vec![("z", 2)]),
+                ]),
+                2,
+            ),
+            AlsOperator::multiply(
+                AlsOperator::affix(
+                    "id-",
+                    "",
+                    vec![AlsOperator::delta(7, vec![2, 2]), AlsOperator::raw("x"), AlsOperator::range(1, 0)],
+                ),
+                2,
+            ),
+        ];
+        for op in &ops {
+            assert_eq!(drain_cursor(op).unwrap(), op.expand(None).unwrap(), "{:?}", op);
+        }
+    }
+
+    #[test]
+    fn test_operator_cursor_delta_overflow() {
+        let op = AlsOperator::delta(i64::MAX - 1, vec![1, 1]);
+        assert!(matches!(
+            drain_cursor(&op),
+            Err(AlsError::DeltaOverflow { index: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_int_dict_expand() {
+        let op = AlsOperator::int_dict(vec![100, 200, 404], vec![1, 1, 2, 0, 1]);
+        assert!(op.is_int_dict());
+        assert_eq!(op.expanded_count(), 5);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["200", "200", "404", "100", "200"]
+        );
+    }
+
+    #[test]
+    fn test_int_dict_invalid_index() {
+        let op = AlsOperator::int_dict(vec![100, 200, 404], vec![0, 3]);
+        assert!(matches!(
+            op.expand(None),
+            Err(AlsError::InvalidDictRef { index: 3, size: 3 })
+        ));
+        assert!(matches!(
+            drain_cursor(&op),
+            Err(AlsError::InvalidDictRef { index: 3, size: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_int_dict_width() {
+        assert_eq!(AlsOperator::int_dict_width(0), 1);
+        assert_eq!(AlsOperator::int_dict_width(1), 1);
+        assert_eq!(AlsOperator::int_dict_width(2), 1);
+        assert_eq!(AlsOperator::int_dict_width(3), 2);
+        assert_eq!(AlsOperator::int_dict_width(4), 2);
+        assert_eq!(AlsOperator::int_dict_width(5), 3);
+        assert_eq!(AlsOperator::int_dict_width(1000), 10);
+    }
+
+    #[test]
+    fn test_pack_indices_round_trip() {
+        for width in [1, 2, 3, 5, 7, 12] {
+            let indices: Vec<usize> = (0..97).map(|i| (i * 7) % (1 << width)).collect();
+            let packed = AlsOperator::pack_indices(&indices, width);
+            assert_eq!(packed.len(), (indices.len() * width as usize).div_ceil(6));
+            assert_eq!(
+                AlsOperator::unpack_indices(&packed, width, indices.len()),
+                Some(indices)
+            );
+        }
+        assert_eq!(AlsOperator::pack_indices(&[0, 1, 2], 2), "k");
+    }
+
+    #[test]
+    fn test_unpack_indices_rejects_bad_input() {
+        // Too few characters for the count
+        assert_eq!(AlsOperator::unpack_indices("A", 2, 4), None);
+        // Character outside the alphabet
+        assert_eq!(AlsOperator::unpack_indices("A-", 2, 4), None);
+        assert_eq!(AlsOperator::unpack_indices("", 0, 0), None);
+    }
+
+    #[test]
+    fn test_run_list_expand() {
+        let op = AlsOperator::run_list(vec![("A", 3), ("B", 1), ("C", 2)]);
+        assert!(op.is_run_list());
+        assert_eq!(op.expanded_count(), 6);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["A", "A", "A", "B", "C", "C"]
+        );
+    }
+
+    #[test]
+    fn test_run_list_empty() {
+        let op = AlsOperator::run_list(Vec::<(String, usize)>::new());
+        assert_eq!(op.expanded_count(), 0);
+        assert!(op.expand(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_interleave_expand() {
+        let op = AlsOperator::interleave(vec![
+            AlsOperator::range(1, 3),
+            AlsOperator::multiply(AlsOperator::raw("A"), 3),
+        ]);
+        assert!(op.is_interleave());
+        assert_eq!(op.expanded_count(), 6);
+        assert_eq!(op.expand(None).unwrap(), vec!["1", "A", "2", "A", "3", "A"]);
+
+        // Trailing lanes may be one value shorter
+        let op = AlsOperator::interleave(vec![
+            AlsOperator::range(1, 3),
+            AlsOperator::range(10, 12),
+            AlsOperator::multiply(AlsOperator::raw("x"), 2),
+        ]);
+        assert_eq!(op.expanded_len(), 8);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["1", "10", "x", "2", "11", "x", "3", "12"]
+        );
+        assert_eq!(drain_cursor(&op).unwrap(), op.expand(None).unwrap());
+    }
+
+    #[test]
+    fn test_affix_expand() {
+        let op = AlsOperator::affix(
+            "https://example.com/",
+            "",
+            vec![AlsOperator::raw("a"), AlsOperator::range(1, 2)],
+        );
+        assert!(op.is_affix());
+        assert_eq!(op.expanded_count(), 3);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["https://example.com/a", "https://example.com/1", "https://example.com/2"]
+        );
+
+        let dictionary = vec!["report".to_string()];
+        let op = AlsOperator::affix("", ".csv", vec![AlsOperator::multiply(AlsOperator::dict_ref(0), 2)]);
+        assert_eq!(op.expand(Some(&dictionary)).unwrap(), vec!["report.csv", "report.csv"]);
+        assert!(matches!(op.expand(None), Err(AlsError::InvalidDictRef { index: 0, .. })));
+    }
+
+    #[test]
+    fn test_interleave_invalid_lanes() {
+        let single = AlsOperator::interleave(vec![AlsOperator::range(1, 3)]);
+        assert!(matches!(single.expand(None), Err(AlsError::InvalidInterleave { lanes: 1, .. })));
+
+        // A later lane may not be longer, nor more than one value shorter
+        for lanes in [
+            vec![AlsOperator::range(1, 2), AlsOperator::range(1, 3)],
+            vec![AlsOperator::range(1, 4), AlsOperator::range(1, 2)],
+        ] {
+            let op = AlsOperator::interleave(lanes);
+            assert!(matches!(op.expand(None), Err(AlsError::InvalidInterleave { lanes: 2, .. })));
+            assert!(matches!(drain_cursor(&op), Err(AlsError::InvalidInterleave { .. })));
+        }
+    }
+
+    #[test]
+    fn test_timestamp_expand() {
+        let op = AlsOperator::timestamp(
+            TimestampFormat::from_tag("T0Z").unwrap(),
+            AlsOperator::range(1_672_531_200, 1_672_531_202),
+        );
+        assert!(op.is_timestamp());
+        assert_eq!(op.expanded_count(), 3);
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec![
+                "2023-01-01T00:00:00Z",
+                "2023-01-01T00:00:01Z",
+                "2023-01-01T00:00:02Z"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_timestamp_expand_fractional_with_offset() {
+        let op = AlsOperator::timestamp(
+            TimestampFormat::from_tag("S3+05:30").unwrap(),
+            AlsOperator::delta(1250, vec![500]),
+        );
+        assert_eq!(
+            op.expand(None).unwrap(),
+            vec!["1970-01-01 00:00:01.250+05:30", "1970-01-01 00:00:01.750+05:30"]
+        );
+    }
+
+    #[test]
+    fn test_timestamp_invalid_inner_value() {
+        let format = TimestampFormat::from_tag("T0").unwrap();
+        let op = AlsOperator::timestamp(format.clone(), AlsOperator::raw("abc"));
+        assert!(matches!(
+            op.expand(None),
+            Err(AlsError::InvalidTimestamp { .. })
+        ));
+
+        let op = AlsOperator::timestamp(format, AlsOperator::raw(i64::MAX.to_string()));
+        assert!(matches!(
+            op.expand(None),
+            Err(AlsError::InvalidTimestamp { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ipv4_expand_crosses_octet_boundary() {
+        let op = AlsOperator::ipv4(AlsOperator::range(167_772_415, 167_772_417));
+        assert_eq!(op.expanded_count(), 3);
+        assert_eq!(op.expand(None).unwrap(), vec!["10.0.0.255", "10.0.1.0", "10.0.1.1"]);
+    }
+
+    #[test]
+    fn test_ipv4_invalid_inner_value() {
+        for value in ["-1", "4294967296", "abc"] {
+            let op = AlsOperator::ipv4(AlsOperator::raw(value));
+            assert!(matches!(op.expand(None), Err(AlsError::InvalidIpv4 { .. })), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_expanded_len_matches_expand() {
+        let ops = vec![
+            AlsOperator::range(1, 10),
+            AlsOperator::range(10, 1),
+            AlsOperator::range_with_step(0, 100, 7),
+            AlsOperator::Range { start: 1, end: 10, step: -1 },
+            AlsOperator::multiply(AlsOperator::raw("x"), 4),
+            AlsOperator::multiply(AlsOperator::range(1, 3), 5),
+            AlsOperator::multiply(AlsOperator::multiply(AlsOperator::range(1, 3), 2), 3),
+            AlsOperator::multiply(AlsOperator::Range { start: 5, end: 1, step: 1 }, 3),
+            AlsOperator::toggle("T", "F", 7),
+            AlsOperator::toggle_multi(vec!["a", "b", "c"], 8),
+            AlsOperator::toggle_multi(Vec::<String>::new(), 5),
+        ];
+
+        for op in ops {
+            assert_eq!(op.expanded_len(), op.expand(None).unwrap().len(), "{:?}", op);
+        }
+    }
+
+    #[test]
+    fn test_expanded_len_large_range_without_expanding() {
+        let op = AlsOperator::multiply(AlsOperator::range(1, 1_000_000), 1_000);
+        assert_eq!(op.expanded_len(), 1_000_000_000);
+    }
+
+    #[test]
+    fn test_expand_into_matches_expand() {
+        let dict = vec!["apple".to_string(), "banana".to_string()];
+        let ops = vec![
+            AlsOperator::raw("x"),
+            AlsOperator::range(3, 1),
+            AlsOperator::multiply(AlsOperator::toggle("a", "b", 3), 2),
+            AlsOperator::run_list(vec![("p", 2), ("q", 1)]),
+            AlsOperator::dict_ref(1),
+            AlsOperator::delta(10, vec![1, -2]),
+            AlsOperator::geometric(1, 3, 4),
+            AlsOperator::int_dict(vec![200, 404], vec![1, 0, 1]),
+        ];
+
+        for op in ops {
+            let mut streamed = Vec::new();
+            op.expand_into(Some(&dict), &mut |value| streamed.push(value.into_owned()))
+                .unwrap();
+            assert_eq!(streamed, op.expand(Some(&dict)).unwrap(), "{:?}", op);
+        }
+    }
+
+    #[test]
+    fn test_expand_into_borrows_literal_values() {
+        let dict = vec!["apple".to_string()];
+        let mut borrowed = 0;
+        AlsOperator::multiply(AlsOperator::dict_ref(0), 3)
+            .expand_into(Some(&dict), &mut |value| {
+                if matches!(value, Cow::Borrowed(_)) {
+                    borrowed += 1;
+                }
+            })
+            .unwrap();
+        assert_eq!(borrowed, 3);
+    }
+
+    #[test]
+    fn test_expand_into_reports_errors() {
+        let op = AlsOperator::multiply(AlsOperator::dict_ref(5), 0);
+        assert!(matches!(
+            op.expand_into(None, &mut |_| {}),
+            Err(AlsError::InvalidDictRef { index: 5, size: 0 })
+        ));
+
+        let op = AlsOperator::timestamp(
+            TimestampFormat::from_tag("T0").unwrap(),
+            AlsOperator::raw("abc"),
+        );
+        let mut count = 0;
+        assert!(matches!(
+            op.expand_into(None, &mut |_| count += 1),
+            Err(AlsError::InvalidTimestamp { .. })
+        ));
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_operator_equality_and_hash() {
+        use std::collections::HashSet;
+
+        assert_eq!(AlsOperator::range(1, 10), AlsOperator::range_with_step(1, 10, 1));
+        assert_ne!(AlsOperator::range(1, 10), AlsOperator::range_with_step(1, 10, 3));
+        assert_eq!(
+            AlsOperator::toggle("T", "F", 4),
+            AlsOperator::toggle("T", "F", 4)
+        );
+        assert_ne!(
+            AlsOperator::toggle("T", "F", 4),
+            AlsOperator::toggle_multi(vec!["T", "F", "T"], 4)
+        );
+
+        let operators = vec![
+            AlsOperator::range(1, 10),
+            AlsOperator::toggle("T", "F", 4),
+            AlsOperator::range(1, 10),
+            AlsOperator::multiply(AlsOperator::range(1, 3), 2),
+            AlsOperator::toggle("T", "F", 4),
+            AlsOperator::multiply(AlsOperator::range(1, 3), 2),
+        ];
+        let unique: HashSet<&AlsOperator> = operators.iter().collect();
+        assert_eq!(unique.len(), 3);
+        assert!(unique.contains(&AlsOperator::range(1, 10)));
+    }
+
+    #[test]
+    fn test_float_range_equality_normalizes_zero_and_nan() {
+        use std::collections::HashSet;
+
+        let float_range = |start: f64| AlsOperator::FloatRange {
+            start,
+            end: 1.0,
+            step: 0.5,
+            precision: 1,
+        };
+
+        assert_eq!(float_range(0.0), float_range(-0.0));
+        assert_ne!(float_range(0.0), float_range(0.5));
+
+        // NaN equals itself, whatever the payload, so `Eq` stays reflexive
+        let nan = float_range(f64::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_eq!(nan, float_range(f64::from_bits(f64::NAN.to_bits() | 1)));
+        assert_ne!(nan, float_range(0.0));
+
+        let unique: HashSet<AlsOperator> =
+            [float_range(0.0), float_range(-0.0), nan.clone(), nan].into_iter().collect();
+        assert_eq!(unique.len(), 2);
     }
 }
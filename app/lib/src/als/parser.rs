@@ -4,13 +4,23 @@
 //! `AlsDocument` structures and expanding them to tabular data.
 
 use crate::config::ParserConfig;
-use crate::error::{AlsError, Result};
+use crate::convert::{
+    decimal_scale, format_epoch_iso8601, is_epoch_column, is_fixed_decimal_column,
+    is_verbatim_number, Column, TabularData, Value,
+};
+use crate::error::{AlsError, AlsSyntaxError, Result};
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use super::document::{AlsDocument, ColumnStream, FormatIndicator};
-use super::operator::AlsOperator;
+use super::checksum::{strip_checksum, verify_checksum};
+use super::codec::decode;
+use super::document::{is_permutation, AlsDocument, ColumnStream, FormatIndicator, NumberType, StreamCursor};
+use super::escape::{SentinelTokens, COMMENT_MARKER};
+use super::operator::{AlsOperator, IPV4_TAG};
+use super::timestamp::TimestampFormat;
 use super::tokenizer::{Token, Tokenizer, VersionType};
 
 /// Default threshold for parallel decompression (number of columns * estimated rows).
@@ -49,13 +59,130 @@ impl AlsParser {
     }
 
     /// Parse ALS format text into an `AlsDocument`.
+    ///
+    /// If [`ParserConfig::lenient`] is set, syntax errors in column streams
+    /// are recovered from as in [`AlsParser::parse_lenient`] and the
+    /// diagnostics are discarded.
+    ///
+    /// A `!crc<hex>` checksum directive after the version header is verified
+    /// before parsing and returns [`AlsError::ChecksumMismatch`] on mismatch.
+    /// Documents without a directive parse as before.
     pub fn parse(&self, input: &str) -> Result<AlsDocument> {
-        let mut tokenizer = Tokenizer::new(input);
-        self.parse_document(&mut tokenizer)
+        let input = verify_checksum(input)?;
+        let mut tokenizer = Tokenizer::new(&input);
+        let mut diagnostics = Vec::new();
+        let lenient = self.config.lenient.then_some(&mut diagnostics);
+        self.parse_document(&mut tokenizer, lenient)
+    }
+
+    /// Parse only the header of an ALS document.
+    ///
+    /// Reads the version, sentinel directives, dictionaries and schema, then
+    /// stops before the column streams, so the cost does not grow with the
+    /// amount of data. The returned document has empty `streams` and
+    /// [`AlsDocument::known_row_count`] reports the row count as unknown.
+    /// A `!crc<hex>` checksum directive is skipped without being verified,
+    /// since the bytes it covers are not read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let doc = AlsParser::new()
+    ///     .parse_header("$default:red|blue\n#id #color\n1>3|_0 _1 _0")
+    ///     .unwrap();
+    /// assert_eq!(doc.schema, vec!["id", "color"]);
+    /// assert!(doc.dictionaries.contains_key("default"));
+    /// assert_eq!(doc.known_row_count(), None);
+    /// ```
+    pub fn parse_header(&self, input: &str) -> Result<AlsDocument> {
+        let input = strip_checksum(input);
+        let mut tokenizer = Tokenizer::new(&input);
+        self.parse_header_section(&mut tokenizer)
+    }
+
+    /// Parse an ALS document that may be wrapped with a post-compression codec.
+    ///
+    /// The codec is detected from the leading magic byte (see
+    /// [`codec`](super::codec)); plain ALS text parses as in
+    /// [`AlsParser::parse`].
+    pub fn parse_bytes(&self, input: &[u8]) -> Result<AlsDocument> {
+        self.parse(&decode(input)?)
+    }
+
+    /// Parse ALS format text, recovering from syntax errors in column streams.
+    ///
+    /// Each operator that fails to parse is replaced with a `Raw` placeholder
+    /// holding its unparsed source text, and parsing resumes at the next
+    /// whitespace or column separator. Missing column streams are filled with
+    /// empty streams and surplus streams are dropped. Every recovery is
+    /// reported as an [`AlsSyntaxError`] alongside the document.
+    ///
+    /// Errors in the document header (version, dictionaries, schema) are not
+    /// recoverable and are still returned as `Err`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let parser = AlsParser::new();
+    /// let (doc, errors) = parser.parse_lenient("#id #name\n1>3|Alice Bob*").unwrap();
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1", "2", "3"]);
+    /// ```
+    pub fn parse_lenient(&self, input: &str) -> Result<(AlsDocument, Vec<AlsSyntaxError>)> {
+        let input = verify_checksum(input)?;
+        let mut tokenizer = Tokenizer::new(&input);
+        let mut diagnostics = Vec::new();
+        let doc = self.parse_document(&mut tokenizer, Some(&mut diagnostics))?;
+        Ok((doc, diagnostics))
     }
 
     /// Parse a complete ALS document from the tokenizer.
-    fn parse_document(&self, tokenizer: &mut Tokenizer) -> Result<AlsDocument> {
+    ///
+    /// When `diagnostics` is provided, stream syntax errors are recorded
+    /// there instead of aborting the parse.
+    fn parse_document(
+        &self,
+        tokenizer: &mut Tokenizer,
+        diagnostics: Option<&mut Vec<AlsSyntaxError>>,
+    ) -> Result<AlsDocument> {
+        let mut doc = self.parse_header_section(tokenizer)?;
+        self.skip_whitespace_tokens(tokenizer)?;
+        if self.config.preserve_comments {
+            doc.comments = collect_comments(tokenizer.input());
+        }
+
+        // Parse streams
+        if !doc.schema.is_empty() {
+            let streams = self.parse_streams(tokenizer, doc.schema.len(), diagnostics)?;
+            doc.streams = match &doc.column_order {
+                Some(order) if order.len() == streams.len() => restore_column_order(streams, order),
+                _ => streams,
+            };
+        }
+
+        // Every repeated row must be one of the stored rows
+        let stored_rows = doc.streams.first().map_or(0, |stream| stream.expanded_count());
+        if let Some(&(row, _)) = doc.row_repeats.last().filter(|&&(row, _)| row >= stored_rows) {
+            return Err(AlsError::AlsSyntaxError {
+                position: tokenizer.position(),
+                message: format!(
+                    "Row repeat directive refers to row {}, but the streams hold {} rows",
+                    row, stored_rows
+                ),
+            });
+        }
+
+        Ok(doc)
+    }
+
+    /// Parse the document header up to the end of the schema.
+    ///
+    /// Leaves the tokenizer at the first token after the last schema column.
+    fn parse_header_section(&self, tokenizer: &mut Tokenizer) -> Result<AlsDocument> {
         let mut doc = AlsDocument::new();
 
         // Parse optional version
@@ -80,6 +207,47 @@ impl AlsParser {
             self.skip_whitespace_tokens(tokenizer)?;
         }
 
+        // Parse optional sentinel token directives
+        doc.sentinel_tokens = self.parse_sentinel_tokens(tokenizer)?;
+
+        // Parse optional column order directive
+        let order_position = tokenizer.position();
+        let column_order = match tokenizer.peek_token()? {
+            Token::OrderDirective(order) => {
+                tokenizer.next_token()?; // consume order directive
+                self.skip_whitespace_tokens(tokenizer)?;
+                Some(order)
+            }
+            _ => None,
+        };
+
+        // Parse optional row repeat directive
+        if let Token::RowsDirective(repeats) = tokenizer.peek_token()? {
+            let position = tokenizer.position();
+            let sorted = repeats.windows(2).all(|pair| pair[0].0 < pair[1].0);
+            if !sorted || repeats.iter().any(|&(_, count)| count < 2) {
+                return Err(AlsError::AlsSyntaxError {
+                    position,
+                    message: "Row repeat directive must list increasing rows, each repeated at least twice"
+                        .to_string(),
+                });
+            }
+            tokenizer.next_token()?; // consume rows directive
+            self.skip_whitespace_tokens(tokenizer)?;
+            doc.row_repeats = repeats;
+        }
+
+        // Parse optional column dictionary directive
+        let dicts_position = tokenizer.position();
+        let column_dictionaries = match tokenizer.peek_token()? {
+            Token::DictsDirective(assignments) => {
+                tokenizer.next_token()?; // consume dicts directive
+                self.skip_whitespace_tokens(tokenizer)?;
+                assignments
+            }
+            _ => Vec::new(),
+        };
+
         // Parse optional dictionaries
         while let Token::DictionaryHeader { name, values } = tokenizer.peek_token()? {
             tokenizer.next_token()?; // consume dictionary header
@@ -88,26 +256,82 @@ impl AlsParser {
         }
 
         // Parse schema
-        while let Token::SchemaColumn(name) = tokenizer.peek_token()? {
+        while let Token::SchemaColumn(name, hint, number_type) = tokenizer.peek_token()? {
             tokenizer.next_token()?; // consume schema column
+            if let Some(hint) = hint {
+                doc.set_encoding_hint(name.clone(), hint);
+            }
+            if let Some(number_type) = number_type {
+                doc.set_number_type(name.clone(), number_type);
+            }
             doc.schema.push(name);
         }
-        self.skip_whitespace_tokens(tokenizer)?;
 
-        // Parse streams
-        if !doc.schema.is_empty() {
-            let streams = self.parse_streams(tokenizer, doc.schema.len())?;
-            doc.streams = streams;
+        for (column, name) in column_dictionaries {
+            if column >= doc.schema.len() {
+                return Err(AlsError::AlsSyntaxError {
+                    position: dicts_position,
+                    message: format!(
+                        "Column dictionary directive refers to column {}, but the schema has {} columns",
+                        column,
+                        doc.schema.len()
+                    ),
+                });
+            }
+            doc.set_column_dictionary(column, name);
+        }
+
+        // Put the columns back in their original order
+        if let Some(order) = column_order {
+            if !is_permutation(&order, doc.schema.len()) {
+                return Err(AlsError::AlsSyntaxError {
+                    position: order_position,
+                    message: format!(
+                        "Column order directive must list each of the {} schema columns once",
+                        doc.schema.len()
+                    ),
+                });
+            }
+            doc.schema = restore_column_order(std::mem::take(&mut doc.schema), &order);
+            doc.column_order = Some(order);
         }
 
         Ok(doc)
     }
 
+    /// Parse the `!null:` and `!empty:` directives, falling back to the
+    /// configured tokens for those that are missing.
+    fn parse_sentinel_tokens(&self, tokenizer: &mut Tokenizer) -> Result<SentinelTokens> {
+        let position = tokenizer.position();
+        let (mut null, mut empty) = (None, None);
+        loop {
+            match tokenizer.peek_token()? {
+                Token::NullDirective(token) => null = Some(token),
+                Token::EmptyDirective(token) => empty = Some(token),
+                _ => break,
+            }
+            tokenizer.next_token()?;
+            self.skip_whitespace_tokens(tokenizer)?;
+        }
+
+        let configured = &self.config.sentinel_tokens;
+        if null.is_none() && empty.is_none() {
+            return Ok(configured.clone());
+        }
+        let null = null.unwrap_or_else(|| configured.null().to_string());
+        let empty = empty.unwrap_or_else(|| configured.empty().to_string());
+        SentinelTokens::validate(&null, &empty).map_err(|message| AlsError::AlsSyntaxError {
+            position,
+            message: message.to_string(),
+        })?;
+        Ok(SentinelTokens::new(null, empty))
+    }
+
     /// Skip newline tokens.
     fn skip_whitespace_tokens(&self, tokenizer: &mut Tokenizer) -> Result<()> {
         loop {
             match tokenizer.peek_token()? {
-                Token::Newline => {
+                Token::Newline | Token::Comment(_) => {
                     tokenizer.next_token()?;
                 }
                 _ => break,
@@ -117,19 +341,33 @@ impl AlsParser {
     }
 
     /// Parse column streams separated by |.
-    fn parse_streams(&self, tokenizer: &mut Tokenizer, expected_columns: usize) -> Result<Vec<ColumnStream>> {
+    fn parse_streams(
+        &self,
+        tokenizer: &mut Tokenizer,
+        expected_columns: usize,
+        mut diagnostics: Option<&mut Vec<AlsSyntaxError>>,
+    ) -> Result<Vec<ColumnStream>> {
         let mut streams = Vec::with_capacity(expected_columns);
         let mut current_stream = ColumnStream::new();
 
         loop {
-            let token = tokenizer.next_token()?;
-            
+            let start = tokenizer.position();
+            let token = match tokenizer.next_token() {
+                Ok(token) => token,
+                Err(e) => match diagnostics.as_deref_mut() {
+                    Some(diagnostics) => {
+                        current_stream.push(Self::recover(tokenizer, start, e, diagnostics));
+                        continue;
+                    }
+                    None => return Err(e),
+                },
+            };
+
             match token {
                 Token::Eof => {
-                    // End of input - save current stream if not empty
-                    if !current_stream.is_empty() || streams.is_empty() {
-                        streams.push(current_stream);
-                    }
+                    // End of input closes the last stream, which is empty
+                    // after a trailing `|` (e.g. a column with no rows)
+                    streams.push(current_stream);
                     break;
                 }
                 Token::ColumnSeparator => {
@@ -137,13 +375,19 @@ impl AlsParser {
                     streams.push(current_stream);
                     current_stream = ColumnStream::new();
                 }
-                Token::Newline => {
-                    // Skip newlines in stream section
+                Token::Newline | Token::Comment(_) => {
+                    // Skip newlines and comment lines in stream section
                     continue;
                 }
                 _ => {
                     // Parse an element and add to current stream
-                    let operator = self.parse_element(tokenizer, token)?;
+                    let operator = match self.parse_element(tokenizer, token, 0) {
+                        Ok(operator) => operator,
+                        Err(e) => match diagnostics.as_deref_mut() {
+                            Some(diagnostics) => Self::recover(tokenizer, start, e, diagnostics),
+                            None => return Err(e),
+                        },
+                    };
                     current_stream.push(operator);
                 }
             }
@@ -151,23 +395,67 @@ impl AlsParser {
 
         // Validate column count
         if streams.len() != expected_columns && expected_columns > 0 {
-            return Err(AlsError::ColumnMismatch {
-                schema: expected_columns,
-                data: streams.len(),
+            let Some(diagnostics) = diagnostics else {
+                return Err(AlsError::ColumnMismatch {
+                    schema: expected_columns,
+                    data: streams.len(),
+                });
+            };
+            diagnostics.push(AlsSyntaxError {
+                position: tokenizer.position(),
+                message: format!(
+                    "Expected {} column streams but found {}",
+                    expected_columns,
+                    streams.len()
+                ),
             });
+            streams.resize_with(expected_columns, ColumnStream::new);
         }
 
         Ok(streams)
     }
 
+    /// Record a stream syntax error and replace the malformed element with a
+    /// raw placeholder holding its source text.
+    fn recover(
+        tokenizer: &mut Tokenizer,
+        start: usize,
+        error: AlsError,
+        diagnostics: &mut Vec<AlsSyntaxError>,
+    ) -> AlsOperator {
+        let (position, message) = match error {
+            AlsError::AlsSyntaxError { position, message } => (position, message),
+            other => (start, other.to_string()),
+        };
+        diagnostics.push(AlsSyntaxError { position, message });
+        AlsOperator::raw(tokenizer.skip_element(start))
+    }
+
     /// Parse a single element (operator or value).
-    fn parse_element(&self, tokenizer: &mut Tokenizer, first_token: Token) -> Result<AlsOperator> {
+    ///
+    /// `depth` is the number of enclosing groups, bounded by
+    /// [`ParserConfig::max_nesting_depth`] so that deeply nested input fails
+    /// with a syntax error instead of overflowing the stack.
+    fn parse_element(
+        &self,
+        tokenizer: &mut Tokenizer,
+        first_token: Token,
+        depth: usize,
+    ) -> Result<AlsOperator> {
         match first_token {
             Token::Integer(n) => self.parse_integer_element(tokenizer, n),
-            Token::Float(f) => self.parse_float_element(tokenizer, f),
+            Token::Float(f) => self.parse_float_element(tokenizer, f, f.to_string()),
+            Token::Decimal(f, text) => self.parse_float_element(tokenizer, f, text),
             Token::RawValue(s) => self.parse_raw_element(tokenizer, s),
             Token::DictRef(idx) => Ok(AlsOperator::dict_ref(idx)),
-            Token::OpenParen => self.parse_grouped_element(tokenizer),
+            Token::OpenParen => self.parse_grouped_element(tokenizer, depth),
+            Token::TimestampTag(tag) => self.parse_timestamp_element(tokenizer, &tag, depth),
+            Token::AffixTag { prefix, suffix } => {
+                self.parse_affix_element(tokenizer, prefix, suffix, depth)
+            }
+            Token::IntDict { values, count, packed } => {
+                self.parse_int_dict_element(tokenizer, values, count, &packed)
+            }
             _ => Err(AlsError::AlsSyntaxError {
                 position: tokenizer.position(),
                 message: format!("Unexpected token: {:?}", first_token),
@@ -175,13 +463,55 @@ impl AlsParser {
         }
     }
 
-    /// Parse an element starting with an integer (could be range, multiply, or raw).
+    /// Unpack the indices of an integer dictionary and validate them.
+    fn parse_int_dict_element(
+        &self,
+        tokenizer: &Tokenizer,
+        values: Vec<i64>,
+        count: usize,
+        packed: &str,
+    ) -> Result<AlsOperator> {
+        let width = AlsOperator::int_dict_width(values.len());
+        let indices = AlsOperator::unpack_indices(packed, width, count).ok_or_else(|| {
+            AlsError::AlsSyntaxError {
+                position: tokenizer.position(),
+                message: format!(
+                    "Integer dictionary packs fewer than {} indices of {} bits",
+                    count, width
+                ),
+            }
+        })?;
+
+        if let Some(&index) = indices.iter().find(|&&index| index >= values.len()) {
+            return Err(AlsError::InvalidDictRef {
+                index,
+                size: values.len(),
+            });
+        }
+
+        Ok(AlsOperator::int_dict(values, indices))
+    }
+
+    /// Parse an element starting with an integer (could be range, delta, geometric, multiply, or raw).
     fn parse_integer_element(&self, tokenizer: &mut Tokenizer, start: i64) -> Result<AlsOperator> {
         match tokenizer.peek_token()? {
             Token::RangeOp => {
                 tokenizer.next_token()?; // consume >
                 self.parse_range(tokenizer, start)
             }
+            Token::DeltaOp(deltas) => {
+                tokenizer.next_token()?; // consume ^d...
+                Ok(AlsOperator::delta(start, deltas))
+            }
+            Token::GeometricOp { ratio, count } => {
+                tokenizer.next_token()?; // consume ^*ratio#count
+                AlsOperator::geometric_safe_with_limit(
+                    start,
+                    ratio,
+                    count,
+                    self.config.max_range_expansion,
+                )
+            }
             Token::MultiplyOp => {
                 tokenizer.next_token()?; // consume *
                 let count = self.expect_integer(tokenizer)?;
@@ -195,19 +525,24 @@ impl AlsParser {
         }
     }
 
-    /// Parse an element starting with a float.
-    fn parse_float_element(&self, tokenizer: &mut Tokenizer, value: f64) -> Result<AlsOperator> {
+    /// Parse an element starting with a float (could be float range, multiply, or raw).
+    fn parse_float_element(&self, tokenizer: &mut Tokenizer, value: f64, text: String) -> Result<AlsOperator> {
         match tokenizer.peek_token()? {
+            Token::RangeOp => {
+                tokenizer.next_token()?; // consume >
+                let end = self.expect_number(tokenizer)?;
+                self.parse_float_range(tokenizer, value, end)
+            }
             Token::MultiplyOp => {
                 tokenizer.next_token()?; // consume *
                 let count = self.expect_integer(tokenizer)?;
-                Ok(AlsOperator::multiply(AlsOperator::raw(value.to_string()), count as usize))
+                Ok(AlsOperator::multiply(AlsOperator::raw(text), count as usize))
             }
             Token::ToggleOp => {
                 tokenizer.next_token()?; // consume ~
-                self.parse_toggle(tokenizer, value.to_string())
+                self.parse_toggle(tokenizer, text)
             }
-            _ => Ok(AlsOperator::raw(value.to_string())),
+            _ => Ok(AlsOperator::raw(text)),
         }
     }
 
@@ -228,24 +563,103 @@ impl AlsParser {
     }
 
     /// Parse a range expression: start>end or start>end:step
+    ///
+    /// Falls through to a float range if the end or step is a float.
     fn parse_range(&self, tokenizer: &mut Tokenizer, start: i64) -> Result<AlsOperator> {
-        let end = self.expect_integer(tokenizer)?;
+        // Report a malformed bound at its start rather than its end
+        let position = tokenizer.position();
+        let end = match tokenizer.next_token()? {
+            Token::Integer(n) => n,
+            Token::Float(f) | Token::Decimal(f, _) => {
+                return self.parse_float_range(tokenizer, start as f64, f)
+            }
+            other => {
+                return Err(AlsError::AlsSyntaxError {
+                    position,
+                    message: format!("Expected integer but found {:?}", other),
+                });
+            }
+        };
         
         let step = if let Token::StepSeparator = tokenizer.peek_token()? {
             tokenizer.next_token()?; // consume :
-            self.expect_integer(tokenizer)?
+            let position = tokenizer.position();
+            match tokenizer.next_token()? {
+                Token::Integer(n) => n,
+                Token::Float(f) | Token::Decimal(f, _) => {
+                    let range_op = AlsOperator::float_range_safe_with_limit(
+                        start as f64,
+                        end as f64,
+                        f,
+                        self.config.max_range_expansion,
+                    )?;
+                    return self.parse_range_multiply(tokenizer, range_op);
+                }
+                other => {
+                    return Err(AlsError::AlsSyntaxError {
+                        position,
+                        message: format!("Expected integer but found {:?}", other),
+                    });
+                }
+            }
         } else {
             if end >= start { 1 } else { -1 }
         };
 
-        // Check for multiply after range
-        let range_op = AlsOperator::range_safe_with_limit(
+        // Check for exclusions or overrides, then multiply after range
+        let range_op = if let Token::ExcludeOp(excluded) = tokenizer.peek_token()? {
+            tokenizer.next_token()?; // consume !e1,e2
+            AlsOperator::sparse_range_safe_with_limit(
+                start,
+                end,
+                step,
+                excluded,
+                self.config.max_range_expansion,
+            )?
+        } else if let Token::OverrideOp(overrides) = tokenizer.peek_token()? {
+            tokenizer.next_token()?; // consume !=i1:v1,i2:v2
+            AlsOperator::patched_range_safe_with_limit(
+                start,
+                end,
+                step,
+                overrides,
+                self.config.max_range_expansion,
+            )?
+        } else {
+            AlsOperator::range_safe_with_limit(
+                start,
+                end,
+                step,
+                self.config.max_range_expansion,
+            )?
+        };
+
+        self.parse_range_multiply(tokenizer, range_op)
+    }
+
+    /// Parse a float range after its end value: [:step]
+    fn parse_float_range(&self, tokenizer: &mut Tokenizer, start: f64, end: f64) -> Result<AlsOperator> {
+        let step = if let Token::StepSeparator = tokenizer.peek_token()? {
+            tokenizer.next_token()?; // consume :
+            self.expect_number(tokenizer)?
+        } else if end >= start {
+            1.0
+        } else {
+            -1.0
+        };
+
+        let range_op = AlsOperator::float_range_safe_with_limit(
             start,
             end,
             step,
             self.config.max_range_expansion,
         )?;
 
+        self.parse_range_multiply(tokenizer, range_op)
+    }
+
+    /// Parse an optional multiply suffix after a range: *count
+    fn parse_range_multiply(&self, tokenizer: &mut Tokenizer, range_op: AlsOperator) -> Result<AlsOperator> {
         if let Token::MultiplyOp = tokenizer.peek_token()? {
             tokenizer.next_token()?; // consume *
             let count = self.expect_integer(tokenizer)?;
@@ -265,6 +679,15 @@ impl AlsParser {
 
         // Parse additional toggle values
         while let Token::ToggleOp = tokenizer.peek_token()? {
+            if values.len() >= self.config.max_toggle_values {
+                return Err(AlsError::AlsSyntaxError {
+                    position: tokenizer.position(),
+                    message: format!(
+                        "Toggle has more than {} values",
+                        self.config.max_toggle_values
+                    ),
+                });
+            }
             tokenizer.next_token()?; // consume ~
             let next_value = self.expect_value(tokenizer)?;
             values.push(next_value);
@@ -281,10 +704,19 @@ impl AlsParser {
         Ok(AlsOperator::toggle_multi(values, count))
     }
 
-    /// Parse a grouped element: (element)
-    fn parse_grouped_element(&self, tokenizer: &mut Tokenizer) -> Result<AlsOperator> {
+    /// Parse a grouped element: (element), a run list (val*n,val*n,...), or
+    /// an interleave (lane&lane&...)
+    fn parse_grouped_element(&self, tokenizer: &mut Tokenizer, depth: usize) -> Result<AlsOperator> {
+        self.check_nesting_depth(tokenizer, depth)?;
+
         let inner_token = tokenizer.next_token()?;
-        let inner = self.parse_element(tokenizer, inner_token)?;
+        let mut inner = self.parse_element(tokenizer, inner_token, depth + 1)?;
+
+        match tokenizer.peek_token()? {
+            Token::RunSeparator => inner = self.parse_run_list(tokenizer, inner)?,
+            Token::InterleaveSeparator => inner = self.parse_interleave(tokenizer, inner, depth)?,
+            _ => {}
+        }
         
         // Expect closing paren
         match tokenizer.next_token()? {
@@ -307,6 +739,143 @@ impl AlsParser {
         }
     }
 
+    /// Parse the remaining lanes of an interleave after its first lane: &lane...
+    fn parse_interleave(&self, tokenizer: &mut Tokenizer, first: AlsOperator, depth: usize) -> Result<AlsOperator> {
+        let mut lanes = vec![first];
+        while let Token::InterleaveSeparator = tokenizer.peek_token()? {
+            tokenizer.next_token()?; // consume &
+            let token = tokenizer.next_token()?;
+            lanes.push(self.parse_element(tokenizer, token, depth + 1)?);
+        }
+
+        AlsOperator::interleave_safe(lanes)
+    }
+
+    /// Parse the remaining runs of a run list after its first run: ,val*n...
+    fn parse_run_list(&self, tokenizer: &mut Tokenizer, first: AlsOperator) -> Result<AlsOperator> {
+        let first_run = match first {
+            AlsOperator::Multiply { value, count } => match *value {
+                AlsOperator::Raw(value) => (value, count),
+                other => {
+                    return Err(AlsError::AlsSyntaxError {
+                        position: tokenizer.position(),
+                        message: format!("Expected value*count in run list but found {:?}", other),
+                    });
+                }
+            },
+            other => {
+                return Err(AlsError::AlsSyntaxError {
+                    position: tokenizer.position(),
+                    message: format!("Expected value*count in run list but found {:?}", other),
+                });
+            }
+        };
+
+        let mut runs = vec![first_run];
+        while let Token::RunSeparator = tokenizer.peek_token()? {
+            tokenizer.next_token()?; // consume ,
+            let value = self.expect_value(tokenizer)?;
+            match tokenizer.next_token()? {
+                Token::MultiplyOp => {}
+                other => {
+                    return Err(AlsError::AlsSyntaxError {
+                        position: tokenizer.position(),
+                        message: format!("Expected '*' in run list but found {:?}", other),
+                    });
+                }
+            }
+            let count = self.expect_integer(tokenizer)?;
+            runs.push((value, count as usize));
+        }
+
+        Ok(AlsOperator::RunList { runs })
+    }
+
+    /// Parse a timestamp element: @tag(element), or an IPv4 element: @ip4(element)
+    fn parse_timestamp_element(
+        &self,
+        tokenizer: &mut Tokenizer,
+        tag: &str,
+        depth: usize,
+    ) -> Result<AlsOperator> {
+        let format = if tag == IPV4_TAG {
+            None
+        } else {
+            let format = TimestampFormat::from_tag(tag).ok_or_else(|| AlsError::AlsSyntaxError {
+                position: tokenizer.position(),
+                message: format!("Invalid timestamp format tag: {}", tag),
+            })?;
+            Some(format)
+        };
+
+        match tokenizer.next_token()? {
+            Token::OpenParen => {}
+            other => {
+                return Err(AlsError::AlsSyntaxError {
+                    position: tokenizer.position(),
+                    message: format!("Expected '(' but found {:?}", other),
+                });
+            }
+        }
+
+        let inner = self.parse_grouped_element(tokenizer, depth)?;
+        Ok(match format {
+            Some(format) => AlsOperator::timestamp(format, inner),
+            None => AlsOperator::ipv4(inner),
+        })
+    }
+
+    /// Parse an affix element: {prefix:suffix}(element,element...)
+    fn parse_affix_element(
+        &self,
+        tokenizer: &mut Tokenizer,
+        prefix: String,
+        suffix: String,
+        depth: usize,
+    ) -> Result<AlsOperator> {
+        self.check_nesting_depth(tokenizer, depth)?;
+        tokenizer.next_token()?; // consume (, checked by the tokenizer
+
+        let mut values = Vec::new();
+        loop {
+            let token = tokenizer.next_token()?;
+            values.push(self.parse_element(tokenizer, token, depth + 1)?);
+            match tokenizer.next_token()? {
+                Token::RunSeparator => {}
+                Token::CloseParen => break,
+                other => {
+                    return Err(AlsError::AlsSyntaxError {
+                        position: tokenizer.position(),
+                        message: format!("Expected ',' or ')' in affix but found {:?}", other),
+                    });
+                }
+            }
+        }
+
+        let affix = AlsOperator::affix(prefix, suffix, values);
+        if let Token::MultiplyOp = tokenizer.peek_token()? {
+            tokenizer.next_token()?; // consume *
+            let count = self.expect_integer(tokenizer)?;
+            Ok(AlsOperator::multiply(affix, count as usize))
+        } else {
+            Ok(affix)
+        }
+    }
+
+    /// Reject groups nested deeper than the configured limit.
+    fn check_nesting_depth(&self, tokenizer: &Tokenizer, depth: usize) -> Result<()> {
+        if depth >= self.config.max_nesting_depth {
+            return Err(AlsError::AlsSyntaxError {
+                position: tokenizer.position(),
+                message: format!(
+                    "Groups nested more than {} levels deep",
+                    self.config.max_nesting_depth
+                ),
+            });
+        }
+        Ok(())
+    }
+
     /// Expect and consume an integer token.
     fn expect_integer(&self, tokenizer: &mut Tokenizer) -> Result<i64> {
         match tokenizer.next_token()? {
@@ -318,12 +887,24 @@ impl AlsParser {
         }
     }
 
+    /// Expect and consume a numeric token (integer or float) as a float.
+    fn expect_number(&self, tokenizer: &mut Tokenizer) -> Result<f64> {
+        match tokenizer.next_token()? {
+            Token::Integer(n) => Ok(n as f64),
+            Token::Float(f) | Token::Decimal(f, _) => Ok(f),
+            other => Err(AlsError::AlsSyntaxError {
+                position: tokenizer.position(),
+                message: format!("Expected number but found {:?}", other),
+            }),
+        }
+    }
+
     /// Expect and consume a value token (integer, float, or raw).
     fn expect_value(&self, tokenizer: &mut Tokenizer) -> Result<String> {
         match tokenizer.next_token()? {
             Token::Integer(n) => Ok(n.to_string()),
             Token::Float(f) => Ok(f.to_string()),
-            Token::RawValue(s) => Ok(s),
+            Token::Decimal(_, s) | Token::RawValue(s) => Ok(s),
             other => Err(AlsError::AlsSyntaxError {
                 position: tokenizer.position(),
                 message: format!("Expected value but found {:?}", other),
@@ -342,24 +923,9 @@ impl AlsParser {
             return Ok(Vec::new());
         }
 
-        // Get the default dictionary for resolving references
-        let default_dict = doc.default_dictionary();
-
         // Expand all columns (parallel or sequential based on size)
-        let expanded_columns = self.expand_columns_internal(doc, default_dict)?;
-
-        // Validate all columns have the same length
-        if let Some(first) = expanded_columns.first() {
-            let expected_len = first.len();
-            for col in expanded_columns.iter() {
-                if col.len() != expected_len {
-                    return Err(AlsError::ColumnMismatch {
-                        schema: expected_len,
-                        data: col.len(),
-                    });
-                }
-            }
-        }
+        let expanded_columns = self.expand_columns_internal(doc)?;
+        check_column_lengths(&expanded_columns)?;
 
         // Transpose columns to rows
         let row_count = expanded_columns.first().map(|c| c.len()).unwrap_or(0);
@@ -376,66 +942,227 @@ impl AlsParser {
         Ok(rows)
     }
 
-    /// Determine if parallel processing should be used for expansion.
-    fn should_use_parallel_expand(&self, doc: &AlsDocument) -> bool {
-        // Check if parallelism is explicitly disabled (parallelism = 1)
-        if self.config.parallelism == 1 {
-            return false;
-        }
+    /// Expand an ALS document to typed tabular data.
+    ///
+    /// Columns appear in schema order with their names kept as-is, so
+    /// dot-notation names such as `user.name` are not reconstructed into
+    /// nested structures. Each expanded value is typed as follows:
+    ///
+    /// - The null token (`\0`) becomes [`Value::Null`]
+    /// - The empty token (`\e`) becomes an empty [`Value::String`]
+    /// - Integers become [`Value::Integer`], other numbers [`Value::Float`]
+    /// - Numbers in a column of fixed-point decimals that a float would not
+    ///   reproduce exactly, such as prices `19.90`, become [`Value::Decimal`]
+    /// - Integers in a column named `timestamp`, `ts` or `time` that are all
+    ///   plausible epochs become [`Value::Timestamp`]
+    /// - `true`/`false`, `yes`/`no`, `y`/`n`, `t`/`f` (any case) become
+    ///   [`Value::Boolean`]
+    /// - Anything else becomes [`Value::String`]
+    ///
+    /// With `ParserConfig::preserve_number_types` enabled, numbers in a column
+    /// annotated `:int`, `:float`, `:decimal` or `:epoch` take that type
+    /// instead, so `1` in a `:float` column becomes `Value::Float(1.0)`.
+    ///
+    /// This is the conversion used by [`AlsParser::to_csv`] and
+    /// [`AlsParser::to_json`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{AlsParser, Value};
+    ///
+    /// let parser = AlsParser::new();
+    /// let doc = parser.parse("#id #active\n1>2|true false").unwrap();
+    /// let data = parser.expand_typed(&doc).unwrap();
+    /// assert_eq!(data.columns[0].values, vec![Value::Integer(1), Value::Integer(2)]);
+    /// assert_eq!(data.columns[1].values[1], Value::Boolean(false));
+    /// ```
+    pub fn expand_typed(&self, doc: &AlsDocument) -> Result<TabularData<'static>> {
+        let expanded_columns = if doc.streams.is_empty() {
+            Vec::new()
+        } else {
+            self.expand_columns_internal(doc)?
+        };
+        check_column_lengths(&expanded_columns)?;
 
-        // Need at least 2 columns for parallel benefit
-        if doc.streams.len() < 2 {
-            return false;
+        let mut data = TabularData::with_capacity(doc.schema.len());
+        let mut expanded_columns = expanded_columns.into_iter();
+        for name in &doc.schema {
+            let values = expanded_columns.next().unwrap_or_default();
+            data.add_column(self.typed_column(doc, name, values));
         }
 
-        // Estimate the data size based on stream complexity
-        let estimated_size: usize = doc.streams.iter()
-            .map(|s| s.expanded_count())
-            .sum::<usize>() * doc.streams.len();
-
-        estimated_size >= PARALLEL_EXPAND_THRESHOLD
+        Ok(data)
     }
 
-    /// Expand columns using either parallel or sequential processing.
-    fn expand_columns_internal(
-        &self,
-        doc: &AlsDocument,
-        default_dict: Option<&Vec<String>>,
-    ) -> Result<Vec<Vec<String>>> {
-        #[cfg(feature = "parallel")]
-        {
-            if self.should_use_parallel_expand(doc) {
-                return self.expand_columns_parallel(doc, default_dict);
-            }
+    /// Expand only the columns at the given schema indices.
+    ///
+    /// Streams of other columns are not expanded, which makes this much
+    /// cheaper than [`AlsParser::expand`] when only a few columns of a wide
+    /// document are needed. Returns one vector of values per index, in the
+    /// order given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::ColumnNotFound` if an index is outside the schema,
+    /// and the same errors as [`AlsParser::expand`] otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let parser = AlsParser::new();
+    /// let doc = parser.parse("#id #name #score\n1>3|a b c|7*3").unwrap();
+    /// let columns = parser.expand_columns(&doc, &[2, 0]).unwrap();
+    /// assert_eq!(columns, vec![vec!["7", "7", "7"], vec!["1", "2", "3"]]);
+    /// ```
+    pub fn expand_columns(&self, doc: &AlsDocument, indices: &[usize]) -> Result<Vec<Vec<String>>> {
+        let budget = OutputBudget::new(self.config.max_output_values);
+        let columns = indices
+            .iter()
+            .map(|&index| {
+                if index >= doc.schema.len() {
+                    return Err(AlsError::ColumnNotFound {
+                        column: index.to_string(),
+                    });
+                }
+                match doc.streams.get(index) {
+                    Some(stream) => self.expand_stream(doc, stream, doc.column_dictionary(index), &budget),
+                    None => Ok(Vec::new()),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        check_column_lengths(&columns)?;
+        Ok(columns)
+    }
+
+    /// Expand only the columns at the given schema indices to typed tabular
+    /// data.
+    ///
+    /// Combines [`AlsParser::expand_columns`] with the value typing of
+    /// [`AlsParser::expand_typed`].
+    pub fn expand_typed_columns(&self, doc: &AlsDocument, indices: &[usize]) -> Result<TabularData<'static>> {
+        let expanded_columns = self.expand_columns(doc, indices)?;
+
+        let mut data = TabularData::with_capacity(indices.len());
+        for (&index, values) in indices.iter().zip(expanded_columns) {
+            data.add_column(self.typed_column(doc, &doc.schema[index], values));
+        }
+
+        Ok(data)
+    }
+
+    /// Type the expanded values of the named column.
+    ///
+    /// Columns without a number type annotation that look like epoch
+    /// timestamps become `Value::Timestamp`, and those that look like
+    /// fixed-point decimals keep their exact text as `Value::Decimal`.
+    fn typed_column(&self, doc: &AlsDocument, name: &str, values: Vec<String>) -> Column<'static> {
+        let number_type = if self.config.preserve_number_types {
+            doc.number_type(name)
+        } else {
+            None
+        };
+        let tokens = &doc.sentinel_tokens;
+        let number_type = number_type.or_else(|| {
+            let non_null = || {
+                values
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|value| !tokens.is_null_token(value) && !tokens.is_empty_token(value))
+            };
+            if is_epoch_column(name, non_null()) {
+                Some(NumberType::Timestamp)
+            } else {
+                is_fixed_decimal_column(non_null()).then_some(NumberType::Decimal)
+            }
+        });
+        let values = values
+            .into_iter()
+            .map(|value| decode_value(value, tokens, number_type))
+            .collect();
+        Column::new(Cow::Owned(name.to_string()), values)
+    }
+
+    /// Determine if parallel processing should be used for expansion.
+    fn should_use_parallel_expand(&self, doc: &AlsDocument) -> bool {
+        // Check if parallelism is explicitly disabled (parallelism = 1)
+        if self.config.parallelism == 1 {
+            return false;
+        }
+
+        // Need at least 2 columns for parallel benefit
+        if doc.streams.len() < 2 {
+            return false;
+        }
+
+        // Estimate the data size based on stream complexity
+        let estimated_size: usize = doc.streams.iter()
+            .map(|s| s.expanded_count())
+            .sum::<usize>() * doc.streams.len();
+
+        estimated_size >= PARALLEL_EXPAND_THRESHOLD
+    }
+
+    /// Expand columns using either parallel or sequential processing.
+    ///
+    /// `DictRef`s in each column resolve against that column's dictionary.
+    fn expand_columns_internal(&self, doc: &AlsDocument) -> Result<Vec<Vec<String>>> {
+        let budget = OutputBudget::new(self.config.max_output_values);
+
+        #[cfg(feature = "parallel")]
+        {
+            if self.should_use_parallel_expand(doc) {
+                return self.expand_columns_parallel(doc, &budget);
+            }
         }
 
         // Sequential expansion
-        self.expand_columns_sequential(doc, default_dict)
+        self.expand_columns_sequential(doc, &budget)
     }
 
     /// Expand columns sequentially.
-    fn expand_columns_sequential(
-        &self,
-        doc: &AlsDocument,
-        default_dict: Option<&Vec<String>>,
-    ) -> Result<Vec<Vec<String>>> {
+    fn expand_columns_sequential(&self, doc: &AlsDocument, budget: &OutputBudget) -> Result<Vec<Vec<String>>> {
         let mut expanded_columns: Vec<Vec<String>> = Vec::with_capacity(doc.streams.len());
-        for stream in &doc.streams {
-            let column_values = stream.expand(default_dict.map(|v| v.as_slice()))?;
+        for (column, stream) in doc.streams.iter().enumerate() {
+            let column_values = self.expand_stream(doc, stream, doc.column_dictionary(column), budget)?;
             expanded_columns.push(column_values);
         }
         Ok(expanded_columns)
     }
 
-    /// Expand columns in parallel using Rayon.
-    #[cfg(feature = "parallel")]
-    fn expand_columns_parallel(
+    /// Expand one column stream, including the document's row repeats.
+    ///
+    /// With [`ParserConfig::max_output_values`] set, each operator's values
+    /// are counted against `budget` before the operator is expanded, so a
+    /// decompression bomb fails before its values are allocated.
+    fn expand_stream(
         &self,
         doc: &AlsDocument,
-        default_dict: Option<&Vec<String>>,
-    ) -> Result<Vec<Vec<String>>> {
-        let dict_slice = default_dict.map(|v| v.as_slice());
+        stream: &ColumnStream,
+        dictionary: Option<&[String]>,
+        budget: &OutputBudget,
+    ) -> Result<Vec<String>> {
+        if budget.limit.is_none() {
+            return stream.expand(dictionary).map(|values| doc.expand_row_repeats(values));
+        }
 
+        let mut values = Vec::new();
+        for op in &stream.operators {
+            let len = op.expanded_len();
+            budget.spend(len)?;
+            values.reserve(len);
+            op.expand_into(dictionary, &mut |value| values.push(value.into_owned()))?;
+        }
+        // Row repeats copy stored rows into every column
+        budget.spend(doc.repeated_rows())?;
+        Ok(doc.expand_row_repeats(values))
+    }
+
+    /// Expand columns in parallel using Rayon.
+    #[cfg(feature = "parallel")]
+    fn expand_columns_parallel(&self, doc: &AlsDocument, budget: &OutputBudget) -> Result<Vec<Vec<String>>> {
         // Configure thread pool if parallelism is specified
         let result: Result<Vec<Vec<String>>> = if self.config.parallelism > 1 {
             // Use a custom thread pool with specified parallelism
@@ -450,14 +1177,16 @@ impl AlsParser {
             pool.install(|| {
                 doc.streams
                     .par_iter()
-                    .map(|stream| stream.expand(dict_slice))
+                    .enumerate()
+                    .map(|(column, stream)| self.expand_stream(doc, stream, doc.column_dictionary(column), budget))
                     .collect()
             })
         } else {
             // Use default Rayon thread pool (auto-detect cores)
             doc.streams
                 .par_iter()
-                .map(|stream| stream.expand(dict_slice))
+                .enumerate()
+                .map(|(column, stream)| self.expand_stream(doc, stream, doc.column_dictionary(column), budget))
                 .collect()
         };
 
@@ -496,8 +1225,8 @@ impl AlsParser {
             return Ok(Vec::new());
         }
 
-        let default_dict = doc.default_dictionary();
-        let expanded_columns = self.expand_columns_parallel(doc, default_dict)?;
+        let budget = OutputBudget::new(self.config.max_output_values);
+        let expanded_columns = self.expand_columns_parallel(doc, &budget)?;
 
         // Validate all columns have the same length
         if let Some(first) = expanded_columns.first() {
@@ -534,10 +1263,39 @@ impl AlsParser {
         Ok((doc.schema.clone(), rows))
     }
 
+    /// Parse ALS and iterate over the expanded rows one at a time.
+    ///
+    /// Unlike [`AlsParser::expand`], columns are never fully materialized:
+    /// each column stream is advanced by one value per row, so memory use is
+    /// bounded by the compressed document rather than the expanded data.
+    ///
+    /// A column length mismatch is reported as an `AlsError::ColumnMismatch`
+    /// item once the shortest column is exhausted; the rows before it are
+    /// yielded normally. Likewise, with [`ParserConfig::max_output_values`]
+    /// set, an `AlsError::OutputTooLarge` item replaces the row that would
+    /// pass the limit. The iterator stops after the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let parser = AlsParser::new();
+    /// let mut rows = parser.parse_rows_lazy("#id #name\n1>3|Alice Bob Charlie").unwrap();
+    /// assert_eq!(rows.next().unwrap().unwrap(), vec!["1", "Alice"]);
+    /// assert_eq!(rows.count(), 2);
+    /// ```
+    pub fn parse_rows_lazy(&self, input: &str) -> Result<impl Iterator<Item = Result<Vec<String>>>> {
+        let doc = self.parse(input)?;
+        Ok(LazyRows::new(doc, self.config.max_output_values))
+    }
+
     /// Parse ALS format and convert to CSV.
     ///
     /// This is a convenience method that parses ALS input, expands it to tabular data,
     /// and serializes the result to CSV format.
+    /// The output uses the delimiter and quote character from
+    /// [`ParserConfig::csv_config`].
     ///
     /// # Arguments
     ///
@@ -558,57 +1316,36 @@ impl AlsParser {
     /// assert!(csv.contains("id,name"));
     /// ```
     pub fn to_csv(&self, input: &str) -> Result<String> {
-        use crate::convert::csv::to_csv;
-        use crate::convert::{Column, TabularData, Value};
-        use std::borrow::Cow;
-
-        // Parse ALS document
-        let doc = self.parse(input)?;
-
-        // Expand to rows
-        let rows = self.expand(&doc)?;
+        crate::convert::csv::to_csv_with_config(&self.to_tabular_data(input)?, &self.config.csv_config)
+    }
 
-        // Convert to TabularData
-        let mut data = TabularData::with_capacity(doc.schema.len());
+    /// Parse ALS format and convert to TSV.
+    ///
+    /// Identical to [`AlsParser::to_csv`] except that fields are separated by
+    /// tabs. Values containing tabs or line breaks are quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let parser = AlsParser::new();
+    /// let als = "#id #name\n1>3|Alice Bob Charlie";
+    /// let tsv = parser.to_tsv(als).unwrap();
+    /// assert!(tsv.contains("id\tname"));
+    /// ```
+    pub fn to_tsv(&self, input: &str) -> Result<String> {
+        crate::convert::tsv::to_tsv(&self.to_tabular_data(input)?)
+    }
 
-        if !rows.is_empty() {
-            // Transpose rows to columns
-            for (col_idx, col_name) in doc.schema.iter().enumerate() {
-                let col_values: Vec<Value> = rows
-                    .iter()
-                    .map(|row| {
-                        let value_str = &row[col_idx];
-                        // Check for special tokens first
-                        if value_str == crate::als::NULL_TOKEN {
-                            Value::Null
-                        } else if value_str == crate::als::EMPTY_TOKEN {
-                            Value::String(Cow::Owned(String::new()))
-                        } else if value_str.is_empty() {
-                            // Empty string without token (shouldn't happen but handle it)
-                            Value::Null
-                        } else if let Ok(i) = value_str.parse::<i64>() {
-                            Value::Integer(i)
-                        } else if let Ok(f) = value_str.parse::<f64>() {
-                            Value::Float(f)
-                        } else if let Some(b) = parse_boolean_value(value_str) {
-                            Value::Boolean(b)
-                        } else {
-                            Value::String(Cow::Owned(value_str.clone()))
-                        }
-                    })
-                    .collect();
-
-                data.add_column(Column::new(Cow::Owned(col_name.clone()), col_values));
-            }
-        } else {
-            // Empty data - just add columns with no values
-            for col_name in &doc.schema {
-                data.add_column(Column::new(Cow::Owned(col_name.clone()), Vec::new()));
-            }
+    /// Parse and expand ALS into typed tabular data for output conversion.
+    fn to_tabular_data(&self, input: &str) -> Result<TabularData<'static>> {
+        let doc = self.parse(input)?;
+        let mut data = self.expand_typed(&doc)?;
+        if self.config.iso8601_timestamps {
+            render_iso8601_timestamps(&mut data);
         }
-
-        // Convert to CSV
-        to_csv(&data)
+        Ok(data)
     }
 
     /// Parse ALS format and convert directly to JSON.
@@ -635,57 +1372,82 @@ impl AlsParser {
     /// assert!(json.contains("\"id\""));
     /// ```
     pub fn to_json(&self, input: &str) -> Result<String> {
-        use crate::convert::json::to_json;
-        use crate::convert::{Column, TabularData, Value};
-        use std::borrow::Cow;
-
-        // Parse ALS document
-        let doc = self.parse(input)?;
+        crate::convert::json::to_json(&self.to_tabular_data(input)?)
+    }
 
-        // Expand to rows
-        let rows = self.expand(&doc)?;
+    /// Parse ALS format and write it to `writer` as a Parquet file.
+    ///
+    /// Column types map to Parquet types as described on
+    /// [`crate::convert::parquet::to_parquet`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let mut parquet = Vec::new();
+    /// AlsParser::new().to_parquet("#id\n1>3", &mut parquet).unwrap();
+    /// assert!(parquet.starts_with(b"PAR1"));
+    /// ```
+    #[cfg(feature = "parquet")]
+    pub fn to_parquet<W: std::io::Write + Send>(&self, input: &str, writer: W) -> Result<()> {
+        crate::convert::parquet::to_parquet(&self.to_tabular_data(input)?, writer)
+    }
 
-        // Convert to TabularData
-        let mut data = TabularData::with_capacity(doc.schema.len());
+    /// Parse ALS format and write it to `writer` as an Avro object container
+    /// file.
+    ///
+    /// The record schema is inferred as described on
+    /// [`crate::convert::avro::to_avro`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let mut avro = Vec::new();
+    /// AlsParser::new().to_avro("#id\n1>3", &mut avro).unwrap();
+    /// assert!(avro.starts_with(b"Obj\x01"));
+    /// ```
+    #[cfg(feature = "avro")]
+    pub fn to_avro<W: std::io::Write>(&self, input: &str, writer: W) -> Result<()> {
+        crate::convert::avro::to_avro(&self.to_tabular_data(input)?, writer)
+    }
 
-        if !rows.is_empty() {
-            // Transpose rows to columns
-            for (col_idx, col_name) in doc.schema.iter().enumerate() {
-                let col_values: Vec<Value> = rows
-                    .iter()
-                    .map(|row| {
-                        let value_str = &row[col_idx];
-                        // Check for special tokens first
-                        if value_str == crate::als::NULL_TOKEN {
-                            Value::Null
-                        } else if value_str == crate::als::EMPTY_TOKEN {
-                            Value::String(Cow::Owned(String::new()))
-                        } else if value_str.is_empty() {
-                            // Empty string without token (shouldn't happen but handle it)
-                            Value::Null
-                        } else if let Ok(i) = value_str.parse::<i64>() {
-                            Value::Integer(i)
-                        } else if let Ok(f) = value_str.parse::<f64>() {
-                            Value::Float(f)
-                        } else if let Some(b) = parse_boolean_value(value_str) {
-                            Value::Boolean(b)
-                        } else {
-                            Value::String(Cow::Owned(value_str.clone()))
-                        }
-                    })
-                    .collect();
-
-                data.add_column(Column::new(Cow::Owned(col_name.clone()), col_values));
-            }
-        } else {
-            // Empty data - just add columns with no values
-            for col_name in &doc.schema {
-                data.add_column(Column::new(Cow::Owned(col_name.clone()), Vec::new()));
-            }
-        }
+    /// Parse ALS format and convert to an XML document.
+    ///
+    /// Rows become `<row>` elements under a `<rows>` root, as described on
+    /// [`crate::convert::xml::to_xml`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let xml = AlsParser::new().to_xml("#id\n1>2").unwrap();
+    /// assert!(xml.contains("<row><id>2</id></row>"));
+    /// ```
+    #[cfg(feature = "xml")]
+    pub fn to_xml(&self, input: &str) -> Result<String> {
+        crate::convert::xml::to_xml(&self.to_tabular_data(input)?)
+    }
 
-        // Convert to JSON
-        to_json(&data)
+    /// Parse ALS format and convert to newline-delimited JSON (NDJSON).
+    ///
+    /// Each row is written as one JSON object per line, with dot-notation
+    /// columns reconstructed into nested objects as in [`AlsParser::to_json`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let parser = AlsParser::new();
+    /// let ndjson = parser.to_ndjson("#id\n1>2").unwrap();
+    /// assert_eq!(ndjson, "{\"id\":1}\n{\"id\":2}\n");
+    /// ```
+    pub fn to_ndjson(&self, input: &str) -> Result<String> {
+        crate::convert::json::to_ndjson(&self.to_tabular_data(input)?)
     }
 
     /// Parse ALS format text into an `AlsDocument` asynchronously.
@@ -863,7 +1625,257 @@ impl AlsParser {
     }
 }
 
-/// Parse a string as a boolean value (helper for to_csv).
+/// Running total of the values expanded from one document, checked
+/// against [`ParserConfig::max_output_values`].
+///
+/// Columns expanded in parallel share one budget, so the total is atomic.
+struct OutputBudget {
+    limit: Option<usize>,
+    used: AtomicUsize,
+}
+
+impl OutputBudget {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// Count `values` more expanded values, failing once the total passes
+    /// the limit.
+    fn spend(&self, values: usize) -> Result<()> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+        let previous = self
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| Some(used.saturating_add(values)))
+            .unwrap_or_else(|used| used);
+        if previous.saturating_add(values) > limit {
+            Err(AlsError::OutputTooLarge { limit })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Row iterator returned by [`AlsParser::parse_rows_lazy`].
+struct LazyRows {
+    doc: AlsDocument,
+    cursors: Vec<StreamCursor>,
+    budget: OutputBudget,
+    rows: usize,
+    /// Copies of the last stored row still to be returned, per the
+    /// document's row repeats
+    repeated: Option<(Vec<String>, usize)>,
+    finished: bool,
+}
+
+impl LazyRows {
+    fn new(doc: AlsDocument, max_output_values: Option<usize>) -> Self {
+        let cursors = vec![StreamCursor::new(); doc.streams.len()];
+        let finished = doc.streams.is_empty();
+        Self {
+            doc,
+            cursors,
+            budget: OutputBudget::new(max_output_values),
+            rows: 0,
+            repeated: None,
+            finished,
+        }
+    }
+}
+
+impl Iterator for LazyRows {
+    type Item = Result<Vec<String>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if let Err(e) = self.budget.spend(self.cursors.len()) {
+            self.finished = true;
+            self.repeated = None;
+            return Some(Err(e));
+        }
+
+        if let Some((row, remaining)) = self.repeated.as_mut() {
+            *remaining -= 1;
+            let row = if *remaining == 0 {
+                self.repeated.take().map(|(row, _)| row)?
+            } else {
+                row.clone()
+            };
+            return Some(Ok(row));
+        }
+
+        if self.finished {
+            return None;
+        }
+
+        let mut row = Vec::with_capacity(self.cursors.len());
+        let mut exhausted = None;
+        let mut live = None;
+
+        for (col, (cursor, stream)) in self.cursors.iter_mut().zip(&self.doc.streams).enumerate() {
+            match cursor.next_value(stream, self.doc.column_dictionary(col)) {
+                Some(Ok(value)) => {
+                    live.get_or_insert(col);
+                    row.push(value);
+                }
+                Some(Err(e)) => {
+                    self.finished = true;
+                    return Some(Err(e));
+                }
+                None => {
+                    exhausted.get_or_insert(col);
+                }
+            }
+        }
+
+        match exhausted {
+            None => {
+                let copies = self.doc.row_repeat_count(self.rows);
+                if copies > 1 {
+                    self.repeated = Some((row.clone(), copies - 1));
+                }
+                self.rows += 1;
+                Some(Ok(row))
+            }
+            Some(_) if live.is_none() => {
+                self.finished = true;
+                None
+            }
+            Some(col) => {
+                // Some columns ran out while others still have values;
+                // report lengths relative to the first column, as `expand` does
+                self.finished = true;
+                let (schema, data) = match live {
+                    Some(longer) if col == 0 => {
+                        (self.rows, self.doc.streams[longer].expanded_count())
+                    }
+                    _ => (self.doc.streams[0].expanded_count(), self.rows),
+                };
+                Some(Err(AlsError::ColumnMismatch { schema, data }))
+            }
+        }
+    }
+}
+
+/// Check that every expanded column has the same number of values.
+fn check_column_lengths(columns: &[Vec<String>]) -> Result<()> {
+    if let Some(first) = columns.first() {
+        let expected_len = first.len();
+        for col in columns {
+            if col.len() != expected_len {
+                return Err(AlsError::ColumnMismatch {
+                    schema: expected_len,
+                    data: col.len(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Text of every `;;` comment line in a document.
+///
+/// A literal line break always ends a line in ALS text (values escape theirs
+/// as `\n`), so comment lines can be found without tokenizing.
+fn collect_comments(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix(COMMENT_MARKER))
+        .map(|text| text.trim().to_string())
+        .collect()
+}
+
+/// Move columns written in `order` back to their schema positions.
+///
+/// `order` must be a permutation of the positions of `written`.
+fn restore_column_order<T>(written: Vec<T>, order: &[usize]) -> Vec<T> {
+    let mut columns: Vec<Option<T>> = written.iter().map(|_| None).collect();
+    for (column, &index) in written.into_iter().zip(order) {
+        columns[index] = Some(column);
+    }
+    columns.into_iter().flatten().collect()
+}
+
+/// Convert an expanded value to a typed `Value`, resolving the sentinel
+/// tokens first and then typing the text by `number_type` if given.
+fn decode_value(value: String, tokens: &SentinelTokens, number_type: Option<NumberType>) -> Value<'static> {
+    if value.is_empty() {
+        // Empty string without token (shouldn't happen but handle it)
+        return Value::Null;
+    }
+    match tokens.decode_str(value) {
+        None => Value::Null,
+        Some(value) if value.is_empty() => Value::String(Cow::Owned(value)),
+        Some(value) => match number_type {
+            Some(number_type) => coerce_number(value, number_type),
+            None => coerce_value(value),
+        },
+    }
+}
+
+/// Convert the text of a non-null, non-empty value to a typed `Value`.
+fn coerce_value(value: String) -> Value<'static> {
+    if is_verbatim_number(&value) {
+        Value::Decimal(value)
+    } else if let Ok(i) = value.parse::<i64>() {
+        Value::Integer(i)
+    } else if let Ok(f) = value.parse::<f64>() {
+        Value::Float(f)
+    } else if let Some(b) = parse_boolean_value(&value) {
+        Value::Boolean(b)
+    } else {
+        Value::String(Cow::Owned(value))
+    }
+}
+
+/// Convert an expanded value of a column annotated with a number type.
+///
+/// Values that do not parse as that type are converted by `coerce_value`,
+/// as are numbers kept verbatim (see `is_verbatim_number`).
+fn coerce_number(value: String, number_type: NumberType) -> Value<'static> {
+    if is_verbatim_number(&value) {
+        return Value::Decimal(value);
+    }
+    match number_type {
+        NumberType::Integer => match value.parse::<i64>() {
+            Ok(i) => Value::Integer(i),
+            Err(_) => coerce_value(value),
+        },
+        NumberType::Float => match value.parse::<f64>() {
+            Ok(f) => Value::Float(f),
+            Err(_) => coerce_value(value),
+        },
+        NumberType::Decimal if decimal_scale(&value).is_some() => Value::Decimal(value),
+        NumberType::Decimal => coerce_value(value),
+        NumberType::Timestamp => match value.parse::<i64>() {
+            Ok(t) if t.to_string() == value => Value::Timestamp(t),
+            _ => coerce_value(value),
+        },
+    }
+}
+
+/// Replace timestamp values with their ISO-8601 UTC text.
+///
+/// Timestamps outside years 0000-9999 keep their epoch value.
+fn render_iso8601_timestamps(data: &mut TabularData<'static>) {
+    for column in &mut data.columns {
+        for value in &mut column.values {
+            if let Value::Timestamp(t) = value {
+                if let Some(text) = format_epoch_iso8601(*t) {
+                    *value = Value::String(Cow::Owned(text));
+                }
+            }
+        }
+    }
+}
+
+/// Parse a string as a boolean value (helper for `coerce_value`).
 fn parse_boolean_value(s: &str) -> Option<bool> {
     match s.to_lowercase().as_str() {
         "true" | "yes" | "y" | "t" => Some(true),
@@ -872,108 +1884,721 @@ fn parse_boolean_value(s: &str) -> Option<bool> {
     }
 }
 
-impl Default for AlsParser {
-    fn default() -> Self {
-        Self::new()
+impl Default for AlsParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::als::{AlsSerializer, ColumnEncoding};
+    use crate::als::checksum::add_checksum;
+
+    #[test]
+    fn test_parse_empty_document() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("").unwrap();
+        assert!(doc.schema.is_empty());
+        assert!(doc.streams.is_empty());
+    }
+
+    #[test]
+    fn test_parse_version_als() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("!v1\n#col\n1").unwrap();
+        assert_eq!(doc.version, 1);
+        assert_eq!(doc.format_indicator, FormatIndicator::Als);
+    }
+
+    #[test]
+    fn test_parse_version_ctx() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("!ctx\n#col\n1").unwrap();
+        assert_eq!(doc.format_indicator, FormatIndicator::Ctx);
+    }
+
+    #[test]
+    fn test_parse_unsupported_version() {
+        let parser = AlsParser::new();
+        let result = parser.parse("!v99\n#col\n1");
+        assert!(matches!(result, Err(AlsError::VersionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_parse_dictionary() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("$default:apple|banana|cherry\n#col\n_0").unwrap();
+        assert!(doc.dictionaries.contains_key("default"));
+        assert_eq!(doc.dictionaries["default"], vec!["apple", "banana", "cherry"]);
+    }
+
+    #[test]
+    fn test_parse_schema() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#name #age #city\n1|2|3").unwrap();
+        assert_eq!(doc.schema, vec!["name", "age", "city"]);
+    }
+
+    #[test]
+    fn test_parse_schema_encoding_hints() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#id:range #status:dict #note\n1>2|a b|x y").unwrap();
+        assert_eq!(doc.schema, vec!["id", "status", "note"]);
+        assert_eq!(doc.encoding_hint("id"), Some(ColumnEncoding::Range));
+        assert_eq!(doc.encoding_hint("status"), Some(ColumnEncoding::Dict));
+        assert_eq!(doc.encoding_hint("note"), None);
+    }
+
+    #[test]
+    fn test_encoding_hints_round_trip() {
+        let parser = AlsParser::new();
+        let input = "!v1\n#id:range #status:dict #a\\:b\n1>2|x y|p q";
+        let doc = parser.parse(input).unwrap();
+        assert_eq!(doc.schema[2], "a:b");
+
+        let serialized = AlsSerializer::new().serialize(&doc);
+        assert_eq!(serialized, input);
+        assert_eq!(parser.parse(&serialized).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_number_types_round_trip() {
+        let parser = AlsParser::new();
+        let input = "!v1\n#id:range:int #price:float #note\n1>2|1 2.5|x y";
+        let doc = parser.parse(input).unwrap();
+        assert_eq!(doc.number_type("id"), Some(NumberType::Integer));
+        assert_eq!(doc.number_type("price"), Some(NumberType::Float));
+        assert_eq!(doc.number_type("note"), None);
+        assert_eq!(doc.encoding_hint("id"), Some(ColumnEncoding::Range));
+
+        let serialized = AlsSerializer::new().serialize(&doc);
+        assert_eq!(serialized, input);
+    }
+
+    #[test]
+    fn test_expand_typed_preserve_number_types() {
+        let input = "#price:float #count:int\n1 2.5|3 x";
+        let doc = AlsParser::new().parse(input).unwrap();
+
+        let data = AlsParser::new().expand_typed(&doc).unwrap();
+        assert_eq!(data.columns[0].values[0], Value::Integer(1));
+
+        let parser = AlsParser::with_config(ParserConfig::new().with_preserve_number_types(true));
+        let data = parser.expand_typed(&doc).unwrap();
+        assert_eq!(data.columns[0].values, vec![Value::Float(1.0), Value::Float(2.5)]);
+        // Values that are not numbers are typed as usual
+        assert_eq!(data.columns[1].values, vec![Value::Integer(3), Value::string("x")]);
+    }
+
+    #[test]
+    fn test_expand_typed_keeps_decimal_text() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#price #score\n19.90 5.00 \\\\0|1.50 2.5 3").unwrap();
+        assert_eq!(parser.expand(&doc).unwrap()[0], vec!["19.90", "1.50"]);
+
+        let data = parser.expand_typed(&doc).unwrap();
+        assert_eq!(
+            data.columns[0].values,
+            vec![Value::Decimal("19.90".to_string()), Value::Decimal("5.00".to_string()), Value::Null]
+        );
+        // Mixed scales are not a decimal column
+        assert_eq!(data.columns[1].values[0], Value::Float(1.5));
+    }
+
+    #[test]
+    fn test_expand_typed_keeps_exponent_text() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#a:float #b\n1e-9 2.5|+7 -3e+2").unwrap();
+        assert_eq!(parser.expand(&doc).unwrap(), vec![vec!["1e-9", "+7"], vec!["2.5", "-3e+2"]]);
+
+        for parser in [parser, AlsParser::with_config(ParserConfig::new().with_preserve_number_types(true))] {
+            let data = parser.expand_typed(&doc).unwrap();
+            assert_eq!(data.columns[0].values, vec![Value::Decimal("1e-9".to_string()), Value::Float(2.5)]);
+            assert_eq!(
+                data.columns[1].values,
+                vec![Value::Decimal("+7".to_string()), Value::Decimal("-3e+2".to_string())]
+            );
+        }
+        assert_eq!(AlsParser::new().to_ndjson("#x\n1e-9 +7").unwrap(), "{\"x\":1e-9}\n{\"x\":7}\n");
+    }
+
+    #[test]
+    fn test_expand_typed_decimal_annotation() {
+        let doc = AlsParser::new().parse("#price:decimal\n1.5 2.25 x").unwrap();
+        assert_eq!(doc.number_type("price"), Some(NumberType::Decimal));
+
+        let parser = AlsParser::with_config(ParserConfig::new().with_preserve_number_types(true));
+        let data = parser.expand_typed(&doc).unwrap();
+        assert_eq!(
+            data.columns[0].values,
+            vec![Value::Decimal("1.5".to_string()), Value::Decimal("2.25".to_string()), Value::string("x")]
+        );
+    }
+
+    #[test]
+    fn test_expand_typed_epoch_column() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#ts #id\n1672531200^d60,60,5|1>4").unwrap();
+        let data = parser.expand_typed(&doc).unwrap();
+        assert_eq!(data.columns[0].values[1], Value::Timestamp(1_672_531_260));
+        assert_eq!(data.columns[1].values[1], Value::Integer(2));
+
+        // Annotated columns need no recognizable name
+        let doc = parser.parse("#created:epoch\n5 x").unwrap();
+        assert_eq!(doc.number_type("created"), Some(NumberType::Timestamp));
+        let parser = AlsParser::with_config(ParserConfig::new().with_preserve_number_types(true));
+        let data = parser.expand_typed(&doc).unwrap();
+        assert_eq!(data.columns[0].values, vec![Value::Timestamp(5), Value::string("x")]);
+    }
+
+    #[test]
+    fn test_to_csv_iso8601_timestamps() {
+        let als = "#ts #n\n1672531200 1672617600|1672531200 1";
+
+        assert_eq!(
+            AlsParser::new().to_csv(als).unwrap(),
+            "ts,n\n1672531200,1672531200\n1672617600,1\n"
+        );
+        let parser = AlsParser::with_config(ParserConfig::new().with_iso8601_timestamps(true));
+        assert_eq!(
+            parser.to_csv(als).unwrap(),
+            "ts,n\n2023-01-01T00:00:00Z,1672531200\n2023-01-02T00:00:00Z,1\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_escaped_pipes_in_streams() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#a #b\na\\|b \\|*2|x 1\\|2~y\\|*2").unwrap();
+        assert_eq!(doc.streams.len(), 2);
+
+        let rows = parser.expand(&doc).unwrap();
+        assert_eq!(rows, vec![vec!["a|b", "x"], vec!["|", "1|2"], vec!["|", "y|"]]);
+    }
+
+    #[test]
+    fn test_parse_raw_values() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\nhello world foo").unwrap();
+        assert_eq!(doc.streams.len(), 1);
+        assert_eq!(doc.streams[0].expanded_count(), 3);
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n1>5").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["1", "2", "3", "4", "5"]);
+    }
+
+    #[test]
+    fn test_parse_range_with_step() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n10>50:10").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["10", "20", "30", "40", "50"]);
+    }
+
+    #[test]
+    fn test_parse_descending_range() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n5>1:-1").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["5", "4", "3", "2", "1"]);
+    }
+
+    #[test]
+    fn test_parse_multiply() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\nhello*3").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["hello", "hello", "hello"]);
+    }
+
+    #[test]
+    fn test_parse_toggle() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\nT~F*4").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["T", "F", "T", "F"]);
+    }
+
+    #[test]
+    fn test_parse_float_range() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n0.0>2.0:0.5").unwrap();
+        assert!(doc.streams[0].operators[0].is_float_range());
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["0.0", "0.5", "1.0", "1.5", "2.0"]);
+    }
+
+    #[test]
+    fn test_parse_float_range_descending() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n1.50>0.75:-0.25").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["1.50", "1.25", "1.00", "0.75"]);
+    }
+
+    #[test]
+    fn test_parse_float_range_with_integer_start() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n1>2:0.5*2").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["1.0", "1.5", "2.0", "1.0", "1.5", "2.0"]);
+    }
+
+    #[test]
+    fn test_parse_float_range_respects_expansion_limit() {
+        let config = ParserConfig::new().with_max_range_expansion(100);
+        let parser = AlsParser::with_config(config);
+        let result = parser.parse("#col\n0.0>100.0:0.5");
+        assert!(matches!(result, Err(AlsError::InvalidFloatRange { .. })));
+    }
+
+    #[test]
+    fn test_parse_delta() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n100^d0,0,5,-2 7").unwrap();
+        assert_eq!(doc.streams[0].operators[0], AlsOperator::delta(100, vec![0, 0, 5, -2]));
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["100", "100", "100", "105", "103", "7"]);
+    }
+
+    #[test]
+    fn test_parse_geometric() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n1^*2#5 (10^*10#3)*2").unwrap();
+        assert_eq!(doc.streams[0].operators[0], AlsOperator::geometric(1, 2, 5));
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(
+            expanded,
+            vec!["1", "2", "4", "8", "16", "10", "100", "1000", "10", "100", "1000"]
+        );
+    }
+
+    #[test]
+    fn test_parse_geometric_invalid() {
+        let parser = AlsParser::new();
+        for input in ["#col\n5^*1#3", "#col\n5^*0#3", "#col\n2^*2#64"] {
+            assert!(
+                matches!(parser.parse(input), Err(AlsError::InvalidGeometric { .. })),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_grouped_delta_multiply() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n(1^d2,-1)*2").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["1", "3", "2", "1", "3", "2"]);
+    }
+
+    #[test]
+    fn test_parse_run_list() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n(A*3,B\\,C*2,7*1) (x*1,y*2)*2").unwrap();
+        assert_eq!(
+            doc.streams[0].operators[0],
+            AlsOperator::run_list(vec![("A", 3), ("B,C", 2), ("7", 1)])
+        );
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(
+            expanded,
+            vec!["A", "A", "A", "B,C", "B,C", "7", "x", "y", "y", "x", "y", "y"]
+        );
+    }
+
+    #[test]
+    fn test_parse_run_list_invalid_run() {
+        let parser = AlsParser::new();
+        assert!(matches!(
+            parser.parse("#col\n(A*3,B)"),
+            Err(AlsError::AlsSyntaxError { .. })
+        ));
+        assert!(matches!(
+            parser.parse("#col\n(1>3,B*2)"),
+            Err(AlsError::AlsSyntaxError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_interleave() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n(1>3&A*3) (x~y*2&R\\&D*2&0>1)*2").unwrap();
+        assert_eq!(
+            doc.streams[0].operators[0],
+            AlsOperator::interleave(vec![
+                AlsOperator::range(1, 3),
+                AlsOperator::multiply(AlsOperator::raw("A"), 3),
+            ])
+        );
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(
+            expanded,
+            vec![
+                "1", "A", "2", "A", "3", "A",
+                "x", "R&D", "0", "y", "R&D", "1",
+                "x", "R&D", "0", "y", "R&D", "1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_interleave_invalid_lanes() {
+        let parser = AlsParser::new();
+        assert!(matches!(
+            parser.parse("#col\n(1>3&A*1)"),
+            Err(AlsError::InvalidInterleave { lanes: 2, .. })
+        ));
+        assert!(matches!(
+            parser.parse("#col\n(1>3&)"),
+            Err(AlsError::AlsSyntaxError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_affix() {
+        let parser = AlsParser::new();
+        let doc = parser
+            .parse("#url\n{https\\://example.com/}(a,b\\,c,1>2) {img:.png}(x*2)*2")
+            .unwrap();
+        assert_eq!(
+            doc.streams[0].operators[0],
+            AlsOperator::affix(
+                "https://example.com/",
+                "",
+                vec![AlsOperator::raw("a"), AlsOperator::raw("b,c"), AlsOperator::range(1, 2)],
+            )
+        );
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(
+            expanded,
+            vec![
+                "https://example.com/a",
+                "https://example.com/b,c",
+                "https://example.com/1",
+                "https://example.com/2",
+                "imgx.png",
+                "imgx.png",
+                "imgx.png",
+                "imgx.png",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_affix_invalid() {
+        let parser = AlsParser::new();
+        for input in ["#col\n{pre}()", "#col\n{pre}(a b)", "#col\n{pre}(a,", "#col\n{pre(a)"] {
+            assert!(
+                matches!(parser.parse(input), Err(AlsError::AlsSyntaxError { .. })),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#ts\n@T0Z(1672531200>1672531320:60) @S3(1250^d500)").unwrap();
+        assert!(doc.streams[0].operators[0].is_timestamp());
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(
+            expanded,
+            vec![
+                "2023-01-01T00:00:00Z",
+                "2023-01-01T00:01:00Z",
+                "2023-01-01T00:02:00Z",
+                "1970-01-01 00:00:01.250",
+                "1970-01-01 00:00:01.750",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_grouped_timestamp_multiply() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#ts\n(@T0(0>1))*2").unwrap();
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(
+            expanded,
+            vec![
+                "1970-01-01T00:00:00",
+                "1970-01-01T00:00:01",
+                "1970-01-01T00:00:00",
+                "1970-01-01T00:00:01",
+            ]
+        );
     }
-}
 
+    #[test]
+    fn test_parse_ipv4_round_trip() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#ip\n(@ip4(167772415>167772416))*2").unwrap();
+        assert!(doc.streams[0].operators[0].is_multiply());
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["10.0.0.255", "10.0.1.0", "10.0.0.255", "10.0.1.0"]);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        let serialized = AlsSerializer::new().serialize(&doc);
+        assert!(serialized.contains("(@ip4(167772415>167772416))*2"), "{}", serialized);
+        assert_eq!(parser.parse(&serialized).unwrap(), doc);
+    }
 
     #[test]
-    fn test_parse_empty_document() {
+    fn test_parse_timestamp_invalid_tag() {
         let parser = AlsParser::new();
-        let doc = parser.parse("").unwrap();
-        assert!(doc.schema.is_empty());
-        assert!(doc.streams.is_empty());
+        assert!(matches!(
+            parser.parse("#ts\n@X0(1>3)"),
+            Err(AlsError::AlsSyntaxError { .. })
+        ));
+        assert!(matches!(
+            parser.parse("#ts\n@T0 1>3"),
+            Err(AlsError::AlsSyntaxError { .. })
+        ));
     }
 
     #[test]
-    fn test_parse_version_als() {
+    fn test_parse_lenient_malformed_range() {
         let parser = AlsParser::new();
-        let doc = parser.parse("!v1\n#col\n1").unwrap();
-        assert_eq!(doc.version, 1);
-        assert_eq!(doc.format_indicator, FormatIndicator::Als);
+        let (doc, errors) = parser.parse_lenient("#a #b #c\n1>3|5>x 7|A*2 B").unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(doc.streams.len(), 3);
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(doc.streams[1].operators[0], AlsOperator::raw("5>x"));
+        assert_eq!(doc.streams[1].operators[1], AlsOperator::raw("7"));
+        assert_eq!(doc.streams[2].expand(None).unwrap(), vec!["A", "A", "B"]);
     }
 
     #[test]
-    fn test_parse_version_ctx() {
+    fn test_parse_lenient_dangling_multiplier() {
         let parser = AlsParser::new();
-        let doc = parser.parse("!ctx\n#col\n1").unwrap();
-        assert_eq!(doc.format_indicator, FormatIndicator::Ctx);
+        let input = "#a #b\n1>3|A*";
+        let (doc, errors) = parser.parse_lenient(input).unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].position <= input.len());
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1", "2", "3"]);
+        assert_eq!(doc.streams[1].expand(None).unwrap(), vec!["A*"]);
     }
 
     #[test]
-    fn test_parse_unsupported_version() {
+    fn test_parse_lenient_truncated_document() {
         let parser = AlsParser::new();
-        let result = parser.parse("!v99\n#col\n1");
-        assert!(matches!(result, Err(AlsError::VersionMismatch { .. })));
+        let (doc, errors) = parser.parse_lenient("#a #b #c\n1>4|x~y*").unwrap();
+
+        // The incomplete toggle and the missing third column are both reported
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].message.contains("Expected 3 column streams but found 2"));
+        assert_eq!(doc.streams.len(), 3);
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1", "2", "3", "4"]);
+        assert!(doc.streams[2].is_empty());
     }
 
     #[test]
-    fn test_parse_dictionary() {
+    fn test_parse_lenient_valid_document_has_no_errors() {
         let parser = AlsParser::new();
-        let doc = parser.parse("$default:apple|banana|cherry\n#col\n_0").unwrap();
-        assert!(doc.dictionaries.contains_key("default"));
-        assert_eq!(doc.dictionaries["default"], vec!["apple", "banana", "cherry"]);
+        let input = "$default:red|blue\n#id #color\n1>3|_0 _1 _0";
+        let (doc, errors) = parser.parse_lenient(input).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(doc, parser.parse(input).unwrap());
     }
 
     #[test]
-    fn test_parse_schema() {
+    fn test_parse_header_skips_streams() {
+        let mut input = String::from("!v1\n$default:info|warn|error\n$hosts:web|db\n#id #level #host:dict\n");
+        for i in 0..2_000 {
+            input.push_str(&format!("{} ", i));
+        }
+        input.push_str("|_0 _1 _2|_0 _1");
+        // A broken stream section proves the streams are never tokenized
+        let broken = format!("{} 5>x", input);
+
         let parser = AlsParser::new();
-        let doc = parser.parse("#name #age #city\n1|2|3").unwrap();
-        assert_eq!(doc.schema, vec!["name", "age", "city"]);
+        let start = std::time::Instant::now();
+        let header = parser.parse_header(&broken).unwrap();
+        let header_time = start.elapsed();
+
+        assert_eq!(header.schema, vec!["id", "level", "host"]);
+        let mut names: Vec<_> = header.dictionaries.keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, vec!["default", "hosts"]);
+        assert!(header.streams.is_empty());
+        assert_eq!(header.known_row_count(), None);
+        assert!(parser.parse(&broken).is_err());
+
+        let start = std::time::Instant::now();
+        let full = parser.parse(&input).unwrap();
+        assert!(header_time < start.elapsed());
+        assert_eq!(full.schema, header.schema);
+        assert_eq!(full.encoding_hints, header.encoding_hints);
+        assert_eq!(full.known_row_count(), Some(2_000));
     }
 
     #[test]
-    fn test_parse_raw_values() {
-        let parser = AlsParser::new();
-        let doc = parser.parse("#col\nhello world foo").unwrap();
-        assert_eq!(doc.streams.len(), 1);
-        assert_eq!(doc.streams[0].expanded_count(), 3);
+    fn test_parse_header_ignores_checksum() {
+        let input = add_checksum("!v1\n#a #b\n1>3|x y z");
+        // Corrupt a byte covered by the checksum
+        let corrupted = input.replace("x y z", "x y q");
+
+        assert!(AlsParser::new().parse(&corrupted).is_err());
+        let header = AlsParser::new().parse_header(&corrupted).unwrap();
+        assert_eq!(header.schema, vec!["a", "b"]);
+        assert_eq!(header.version, 1);
     }
 
     #[test]
-    fn test_parse_range() {
-        let parser = AlsParser::new();
-        let doc = parser.parse("#col\n1>5").unwrap();
-        let expanded = doc.streams[0].expand(None).unwrap();
-        assert_eq!(expanded, vec!["1", "2", "3", "4", "5"]);
+    fn test_parse_column_order_restores_schema_order() {
+        let doc = AlsParser::new().parse("!v1\n!order:1,0\n#b #a\nx y|1>2").unwrap();
+        assert_eq!(doc.schema, vec!["a", "b"]);
+        assert_eq!(doc.column_order, Some(vec![1, 0]));
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1", "2"]);
+        assert_eq!(doc.streams[1].expand(None).unwrap(), vec!["x", "y"]);
+
+        let reserialized = AlsSerializer::new().serialize(&doc);
+        assert_eq!(reserialized, "!v1\n!order:1,0\n#b #a\nx y|1>2");
     }
 
     #[test]
-    fn test_parse_range_with_step() {
-        let parser = AlsParser::new();
-        let doc = parser.parse("#col\n10>50:10").unwrap();
-        let expanded = doc.streams[0].expand(None).unwrap();
-        assert_eq!(expanded, vec!["10", "20", "30", "40", "50"]);
+    fn test_parse_column_order_rejects_invalid_permutation() {
+        for input in ["!order:0,0\n#a #b\n1|2", "!order:0\n#a #b\n1|2", "!order:0,x\n#a\n1"] {
+            assert!(
+                matches!(AlsParser::new().parse(input), Err(AlsError::AlsSyntaxError { .. })),
+                "{input:?} should be rejected"
+            );
+        }
     }
 
     #[test]
-    fn test_parse_descending_range() {
+    fn test_parse_column_dictionaries() {
+        let input = "!v1\n!dicts:1=status\n$default:eu|us\n$status:ok|fail\n#region #status\n_0 _1 _1|_1 _0 _0";
         let parser = AlsParser::new();
-        let doc = parser.parse("#col\n5>1:-1").unwrap();
-        let expanded = doc.streams[0].expand(None).unwrap();
-        assert_eq!(expanded, vec!["5", "4", "3", "2", "1"]);
+        let doc = parser.parse(input).unwrap();
+        assert_eq!(doc.dictionary_name(0), "default");
+        assert_eq!(doc.dictionary_name(1), "status");
+        assert_eq!(
+            parser.expand(&doc).unwrap(),
+            vec![vec!["eu", "fail"], vec!["us", "ok"], vec!["us", "ok"]]
+        );
+        assert_eq!(parser.expand_columns(&doc, &[1]).unwrap(), vec![vec!["fail", "ok", "ok"]]);
+        assert_eq!(AlsSerializer::new().serialize(&doc), input);
     }
 
     #[test]
-    fn test_parse_multiply() {
+    fn test_parse_column_dictionaries_rejects_invalid_directive() {
+        for input in ["!dicts:2=b\n#a #b\n1|2", "!dicts:1\n#a #b\n1|2", "!dicts:1=a-b\n#a #b\n1|2"] {
+            assert!(
+                matches!(AlsParser::new().parse(input), Err(AlsError::AlsSyntaxError { .. })),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_row_repeats_expand_every_column() {
+        let input = "!v1\n!rows:1*5\n#a #b #c\n1>3|x y z|p q r";
         let parser = AlsParser::new();
-        let doc = parser.parse("#col\nhello*3").unwrap();
-        let expanded = doc.streams[0].expand(None).unwrap();
-        assert_eq!(expanded, vec!["hello", "hello", "hello"]);
+        let doc = parser.parse(input).unwrap();
+        assert_eq!(doc.row_repeats, vec![(1, 5)]);
+        assert_eq!(doc.row_count(), 7);
+
+        let rows = parser.expand(&doc).unwrap();
+        assert_eq!(rows.len(), 7);
+        assert!(rows[1..6].iter().all(|row| row == &["2", "y", "q"]));
+        assert_eq!(rows[6], vec!["3", "z", "r"]);
+        assert_eq!(parser.expand_columns(&doc, &[2]).unwrap()[0].len(), 7);
+
+        assert_eq!(AlsSerializer::new().serialize(&doc), input);
     }
 
     #[test]
-    fn test_parse_toggle() {
+    fn test_parse_row_repeats_rejects_invalid_directive() {
+        for input in [
+            "!rows:1*1\n#a\n1>3",
+            "!rows:2*3,1*3\n#a\n1>3",
+            "!rows:1\n#a\n1>3",
+            "!rows:3*2\n#a\n1>3",
+        ] {
+            assert!(
+                matches!(AlsParser::new().parse(input), Err(AlsError::AlsSyntaxError { .. })),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_comments_do_not_affect_expansion() {
+        let plain = "!v1\n$default:red|blue\n#id #color\n1>4|_0 _1\n_0 _1";
+        let commented = ";; exported nightly\n!v1\n;; colors\n$default:red|blue\n  ;; columns\n#id #color\n;; ids\n1>4|_0 _1\n;; second half\n_0 _1\n;; end";
         let parser = AlsParser::new();
-        let doc = parser.parse("#col\nT~F*4").unwrap();
-        let expanded = doc.streams[0].expand(None).unwrap();
-        assert_eq!(expanded, vec!["T", "F", "T", "F"]);
+        assert_eq!(parser.to_csv(commented).unwrap(), parser.to_csv(plain).unwrap());
+
+        let doc = parser.parse(commented).unwrap();
+        assert_eq!(doc.version, 1);
+        assert!(doc.comments.is_empty());
+    }
+
+    #[test]
+    fn test_parse_preserve_comments() {
+        let input = ";; first\n!v1\n#id\n;;second ;; part\n1>3";
+        let parser = AlsParser::with_config(ParserConfig::new().with_preserve_comments(true));
+        let doc = parser.parse(input).unwrap();
+        assert_eq!(doc.comments, vec!["first", "second ;; part"]);
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_parse_comment_mid_operator_is_rejected() {
+        assert!(AlsParser::new().parse("#id\n1>\n;; no\n3").is_err());
+    }
+
+    #[test]
+    fn test_parse_comment_marker_value_roundtrip() {
+        let mut doc = AlsDocument::with_schema(vec!["note"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::raw(";;first"),
+            AlsOperator::raw("a;;b"),
+        ]));
+        let als = AlsSerializer::new().serialize(&doc);
+        let parsed = AlsParser::new().parse(&als).unwrap();
+        assert_eq!(parsed.streams[0].expand(None).unwrap(), vec![";;first", "a;;b"]);
+    }
+
+    #[test]
+    fn test_parse_control_and_multibyte_value_roundtrip() {
+        let values = vec!["nul\0byte", "\u{1b}[1mbold", "👨\u{200D}👩", "漢字 かな", "e\u{301}"];
+        let mut doc = AlsDocument::with_schema(vec!["text"]);
+        doc.add_stream(ColumnStream::from_operators(
+            values.iter().map(|&value| AlsOperator::raw(value)).collect(),
+        ));
+        let als = AlsSerializer::new().serialize(&doc);
+        assert!(!als.contains('\0') && !als.contains('\u{1b}'), "{:?}", als);
+
+        let parsed = AlsParser::new().parse(&als).unwrap();
+        assert_eq!(parsed.streams[0].expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_parse_strict_by_default() {
+        let input = "#a #b\n1>3|A*";
+        assert!(matches!(
+            AlsParser::new().parse(input),
+            Err(AlsError::AlsSyntaxError { .. })
+        ));
+
+        let lenient = AlsParser::with_config(ParserConfig::new().with_lenient(true));
+        let doc = lenient.parse(input).unwrap();
+        assert_eq!(doc.streams[1].expand(None).unwrap(), vec!["A*"]);
     }
 
     #[test]
@@ -1006,6 +2631,97 @@ mod tests {
         assert_eq!(expanded, vec!["1", "2", "3", "1", "2", "3"]);
     }
 
+    fn nested(depth: usize) -> String {
+        format!("#col\n{}1{}", "(".repeat(depth), ")".repeat(depth))
+    }
+
+    #[test]
+    fn test_parse_nesting_depth_limit() {
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_nesting_depth(3));
+        let doc = parser.parse(&nested(3)).unwrap();
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["1"]);
+
+        let result = parser.parse(&nested(4));
+        assert!(matches!(result, Err(AlsError::AlsSyntaxError { .. })));
+    }
+
+    #[test]
+    fn test_parse_default_nesting_depth_limit() {
+        let parser = AlsParser::new();
+        assert!(parser.parse(&nested(ParserConfig::DEFAULT_MAX_NESTING_DEPTH)).is_ok());
+        assert!(parser.parse(&nested(ParserConfig::DEFAULT_MAX_NESTING_DEPTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_max_output_values_rejects_bomb() {
+        let config = ParserConfig::new()
+            .with_max_range_expansion(usize::MAX)
+            .with_max_output_values(1_000_000);
+        let parser = AlsParser::with_config(config.clone());
+        let doc = parser.parse("#n\n1>1000000000*1000").unwrap();
+
+        let too_large = |result: Result<_>| matches!(result, Err(AlsError::OutputTooLarge { limit: 1_000_000 }));
+        assert!(too_large(parser.expand(&doc).map(drop)));
+        assert!(too_large(parser.expand_typed(&doc).map(drop)));
+        assert!(too_large(parser.expand_columns(&doc, &[0]).map(drop)));
+        assert!(too_large(parser.to_csv("#n\n1>1000000000*1000").map(drop)));
+
+        // The parallel path shares one budget across columns
+        let parser = AlsParser::with_config(config.with_parallelism(2));
+        let doc = parser.parse("#a #b\n1>600000|x*600000").unwrap();
+        assert!(too_large(parser.expand(&doc).map(drop)));
+    }
+
+    #[test]
+    fn test_max_output_values_allows_bounded_document() {
+        let input = "#a #b\n1>50000|x*50000";
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_output_values(100_000));
+        let rows = parser.expand(&parser.parse(input).unwrap()).unwrap();
+        assert_eq!(rows.len(), 50_000);
+        assert_eq!(rows[49_999], vec!["50000", "x"]);
+
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_output_values(99_999));
+        let result = parser.expand(&parser.parse(input).unwrap());
+        assert!(matches!(result, Err(AlsError::OutputTooLarge { limit: 99_999 })));
+    }
+
+    #[test]
+    fn test_max_output_values_counts_row_repeats() {
+        let input = "!v1\n!rows:0*1000\n#a\n1>2";
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_output_values(1_001));
+        assert_eq!(parser.expand(&parser.parse(input).unwrap()).unwrap().len(), 1_001);
+
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_output_values(500));
+        let result = parser.expand(&parser.parse(input).unwrap());
+        assert!(matches!(result, Err(AlsError::OutputTooLarge { limit: 500 })));
+    }
+
+    #[test]
+    fn test_max_output_values_stops_lazy_rows() {
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_output_values(6));
+        let rows: Vec<_> = parser.parse_rows_lazy("#a #b\n1>5|x*5").unwrap().collect();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[2].as_ref().unwrap(), &vec!["3", "x"]);
+        assert!(matches!(rows[3], Err(AlsError::OutputTooLarge { limit: 6 })));
+    }
+
+    #[test]
+    fn test_parse_deep_nesting_does_not_overflow_stack() {
+        let parser = AlsParser::new();
+        let result = parser.parse(&nested(100_000));
+        assert!(matches!(result, Err(AlsError::AlsSyntaxError { .. })));
+    }
+
+    #[test]
+    fn test_parse_toggle_value_limit() {
+        let parser = AlsParser::with_config(ParserConfig::new().with_max_toggle_values(3));
+        let doc = parser.parse("#col\na~b~c*3").unwrap();
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["a", "b", "c"]);
+
+        let result = parser.parse("#col\na~b~c~d*4");
+        assert!(matches!(result, Err(AlsError::AlsSyntaxError { .. })));
+    }
+
     #[test]
     fn test_parse_range_multiply() {
         let parser = AlsParser::new();
@@ -1014,6 +2730,51 @@ mod tests {
         assert_eq!(expanded, vec!["1", "2", "3", "1", "2", "3"]);
     }
 
+    #[test]
+    fn test_parse_sparse_range() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n1>7!4 (10>0:-5!5)*2").unwrap();
+        assert_eq!(doc.streams[0].operators[0], AlsOperator::sparse_range(1, 7, 1, vec![4]));
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["1", "2", "3", "5", "6", "7", "10", "0", "10", "0"]);
+    }
+
+    #[test]
+    fn test_parse_sparse_range_invalid_exclusion() {
+        let parser = AlsParser::new();
+        for input in ["#col\n1>7!9", "#col\n1>7!5,3"] {
+            assert!(
+                matches!(parser.parse(input), Err(AlsError::InvalidSparseRange { .. })),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_patched_range() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col\n1>6!=0:900,2:-5 (0>10:5!=1:3)*2").unwrap();
+        assert_eq!(
+            doc.streams[0].operators[0],
+            AlsOperator::patched_range(1, 6, 1, vec![(0, 900), (2, -5)])
+        );
+        let expanded = doc.streams[0].expand(None).unwrap();
+        assert_eq!(expanded, vec!["900", "2", "-5", "4", "5", "6", "0", "3", "10", "0", "3", "10"]);
+    }
+
+    #[test]
+    fn test_parse_patched_range_invalid_override() {
+        let parser = AlsParser::new();
+        for input in ["#col\n1>6!=6:1", "#col\n1>6!=3:1,1:2"] {
+            assert!(
+                matches!(parser.parse(input), Err(AlsError::InvalidPatchedRange { .. })),
+                "{}",
+                input
+            );
+        }
+    }
+
     #[test]
     fn test_expand_to_rows() {
         let parser = AlsParser::new();
@@ -1044,6 +2805,135 @@ mod tests {
         assert!(matches!(result, Err(AlsError::ColumnMismatch { .. })));
     }
 
+    #[test]
+    fn test_parse_trailing_empty_stream() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#col1 #col2\n|").unwrap();
+        assert_eq!(doc.streams.len(), 2);
+        assert!(doc.streams.iter().all(|stream| stream.is_empty()));
+    }
+
+    #[test]
+    fn test_parse_sentinel_directives() {
+        let parser = AlsParser::new();
+        let doc = parser
+            .parse("!v1\n!null:N/A\n!empty:no\\ value\n#a\nN/A no\\ value \\\\0 \\\\N/A")
+            .unwrap();
+        assert_eq!(doc.sentinel_tokens, SentinelTokens::new("N/A", "no value"));
+        let data = parser.expand_typed(&doc).unwrap();
+        assert_eq!(
+            data.columns[0].values,
+            vec![Value::Null, Value::string(""), Value::string("\\0"), Value::string("N/A")]
+        );
+    }
+
+    #[test]
+    fn test_parse_sentinel_tokens_from_config() {
+        let tokens = SentinelTokens::new("NULL", "EMPTY");
+        let parser = AlsParser::with_config(ParserConfig::new().with_sentinel_tokens(tokens.clone()));
+        let doc = parser.parse("#a\nNULL EMPTY").unwrap();
+        assert_eq!(doc.sentinel_tokens, tokens);
+        assert_eq!(parser.expand_typed(&doc).unwrap().columns[0].values, vec![Value::Null, Value::string("")]);
+
+        // A directive overrides the configured token it names
+        let doc = parser.parse("!v1\n!empty:-\n#a\nNULL -").unwrap();
+        assert_eq!(doc.sentinel_tokens, SentinelTokens::new("NULL", "-"));
+    }
+
+    #[test]
+    fn test_parse_invalid_sentinel_directives() {
+        let parser = AlsParser::new();
+        for input in ["!v1\n!null:x\n!empty:x\n#a\nx", "!v1\n!null:\\e\n#a\nx", "!null:\n#a\nx"] {
+            assert!(
+                matches!(parser.parse(input), Err(AlsError::AlsSyntaxError { .. })),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_lone_dash_value() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#status\nok - ok").unwrap();
+        assert_eq!(doc.streams[0].expand(None).unwrap(), vec!["ok", "-", "ok"]);
+    }
+
+    fn assert_lazy_matches_expand(input: &str) {
+        let parser = AlsParser::new();
+        let expected = parser.expand(&parser.parse(input).unwrap()).unwrap();
+        let lazy: Vec<Vec<String>> = parser
+            .parse_rows_lazy(input)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(lazy, expected, "input: {}", input);
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_ranges() {
+        assert_lazy_matches_expand("#a #b\n1>5|10>50:10");
+        assert_lazy_matches_expand("#a #b\n5>1|100>60:-10");
+        assert_lazy_matches_expand("#a\n0.0>2.0:0.5");
+        assert_lazy_matches_expand("#a #b\n1>3 7 8|x y z w v");
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_multipliers() {
+        assert_lazy_matches_expand("#a #b\nhello*4|(1>2)*2");
+        assert_lazy_matches_expand("#a #b\n(1>3)*2 9|a*7");
+        assert_lazy_matches_expand("#a\n(100^d1,1,5)*3");
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_toggles() {
+        assert_lazy_matches_expand("#a #b\nT~F*5|1>5");
+        assert_lazy_matches_expand("#a #b\n(a~b~c*3)*2|1>6");
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_dictionary_refs() {
+        assert_lazy_matches_expand("$default:red|green|blue\n#id #color\n1>6|_0 (_1)*2 _2 _2 _0");
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_yields_rows_before_mismatch() {
+        let parser = AlsParser::new();
+        let mut rows = parser.parse_rows_lazy("#a #b\n1>3|x y").unwrap();
+        assert_eq!(rows.next().unwrap().unwrap(), vec!["1", "x"]);
+        assert_eq!(rows.next().unwrap().unwrap(), vec!["2", "y"]);
+        assert!(matches!(
+            rows.next(),
+            Some(Err(AlsError::ColumnMismatch { schema: 3, data: 2 }))
+        ));
+        assert!(rows.next().is_none());
+
+        let mut rows = parser.parse_rows_lazy("#a #b\n1>2|x y z").unwrap();
+        assert_eq!(rows.by_ref().take(2).count(), 2);
+        assert!(matches!(
+            rows.next(),
+            Some(Err(AlsError::ColumnMismatch { schema: 2, data: 3 }))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_invalid_dict_ref() {
+        let parser = AlsParser::new();
+        let mut rows = parser.parse_rows_lazy("$default:red\n#a #b\n1>2|_0 _5").unwrap();
+        assert_eq!(rows.next().unwrap().unwrap(), vec!["1", "red"]);
+        assert!(matches!(
+            rows.next(),
+            Some(Err(AlsError::InvalidDictRef { index: 5, size: 1 }))
+        ));
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn test_parse_rows_lazy_empty_document() {
+        let parser = AlsParser::new();
+        assert_eq!(parser.parse_rows_lazy("").unwrap().count(), 0);
+    }
+
     #[test]
     fn test_parse_complex_document() {
         let input = r#"!v1
@@ -1189,6 +3079,67 @@ $default:active|inactive|pending
         assert_eq!(array[0]["user"]["age"], 30);
     }
 
+    #[test]
+    fn test_expand_typed_mixed_types() {
+        use crate::convert::Value;
+
+        let parser = AlsParser::new();
+        let als = "$default:Alice|Bob\n#id #score #active #name #user.city #note\n\
+                   1>3|1.5 2 2.5|true no T|_0 _1 _0|Paris*3|\\\\0 \\\\e x";
+        let doc = parser.parse(als).unwrap();
+        let data = parser.expand_typed(&doc).unwrap();
+
+        assert_eq!(
+            data.column_names(),
+            vec!["id", "score", "active", "name", "user.city", "note"]
+        );
+        assert_eq!(data.row_count, 3);
+        assert_eq!(
+            data.columns[0].values,
+            vec![Value::Integer(1), Value::Integer(2), Value::Integer(3)]
+        );
+        assert_eq!(
+            data.columns[1].values,
+            vec![Value::Float(1.5), Value::Integer(2), Value::Float(2.5)]
+        );
+        assert_eq!(
+            data.columns[2].values,
+            vec![Value::Boolean(true), Value::Boolean(false), Value::Boolean(true)]
+        );
+        assert_eq!(
+            data.columns[3].values,
+            vec![Value::string("Alice"), Value::string("Bob"), Value::string("Alice")]
+        );
+        assert_eq!(data.columns[4].values, vec![Value::string("Paris"); 3]);
+        assert_eq!(
+            data.columns[5].values,
+            vec![Value::Null, Value::string(""), Value::string("x")]
+        );
+    }
+
+    #[test]
+    fn test_expand_typed_schema_without_rows() {
+        let parser = AlsParser::new();
+        let doc = AlsDocument::with_schema(vec!["id", "name"]);
+        let data = parser.expand_typed(&doc).unwrap();
+
+        assert_eq!(data.column_names(), vec!["id", "name"]);
+        assert_eq!(data.row_count, 0);
+    }
+
+    #[test]
+    fn test_expand_typed_column_mismatch() {
+        let parser = AlsParser::new();
+        let mut doc = AlsDocument::with_schema(vec!["a", "b"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 3)]));
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 2)]));
+
+        assert!(matches!(
+            parser.expand_typed(&doc),
+            Err(AlsError::ColumnMismatch { schema: 3, data: 2 })
+        ));
+    }
+
     #[test]
     fn test_to_json_with_dictionary() {
         let parser = AlsParser::new();
@@ -1295,6 +3246,49 @@ $default:active|inactive|pending
         assert_eq!(result[20][0], "blue");
     }
 
+    #[test]
+    fn test_expand_columns_selects_subset_in_order() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#id #name #score\n1>3|a b c|7*3").unwrap();
+
+        let columns = parser.expand_columns(&doc, &[2, 0]).unwrap();
+        assert_eq!(columns, vec![vec!["7", "7", "7"], vec!["1", "2", "3"]]);
+    }
+
+    #[test]
+    fn test_expand_columns_skips_unselected_streams() {
+        let parser = AlsParser::new();
+        // The color stream references a missing dictionary entry and fails to expand
+        let doc = parser.parse("$default:red\n#id #color\n1>2|_0 _5").unwrap();
+        assert!(parser.expand(&doc).is_err());
+
+        let columns = parser.expand_columns(&doc, &[0]).unwrap();
+        assert_eq!(columns, vec![vec!["1", "2"]]);
+        assert!(matches!(
+            parser.expand_columns(&doc, &[1]),
+            Err(AlsError::InvalidDictRef { index: 5, size: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_expand_columns_out_of_range() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#id\n1>3").unwrap();
+
+        let result = parser.expand_columns(&doc, &[3]);
+        assert!(matches!(result, Err(AlsError::ColumnNotFound { column }) if column == "3"));
+    }
+
+    #[test]
+    fn test_expand_typed_columns() {
+        let parser = AlsParser::new();
+        let doc = parser.parse("#id #name\n1>2|alice bob").unwrap();
+
+        let data = parser.expand_typed_columns(&doc, &[1]).unwrap();
+        assert_eq!(data.column_names(), vec!["name"]);
+        assert_eq!(data.row_count, 2);
+    }
+
     #[cfg(feature = "parallel")]
     #[test]
     fn test_expand_parallel_with_custom_threads() {
@@ -4,9 +4,12 @@
 //! into ALS format text. It handles version headers, dictionaries, schema,
 //! and column streams with proper escaping.
 
+use super::checksum::add_checksum;
 use super::document::{AlsDocument, ColumnStream, FormatIndicator};
-use super::escape::escape_als_string;
-use super::operator::AlsOperator;
+use super::escape::{escape_als_string, COMMENT_MARKER, EMPTY_TOKEN, NULL_TOKEN};
+use super::operator::{AlsOperator, IPV4_TAG};
+use crate::convert::format_epoch_iso8601;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// ALS format serializer.
 ///
@@ -16,12 +19,37 @@ use super::operator::AlsOperator;
 /// - Dictionary headers (`$name:val1|val2`)
 /// - Schema definitions (`#col1 #col2`)
 /// - Column streams with operators separated by `|`
-pub struct AlsSerializer;
+/// - An optional `!crc<hex>` integrity checksum after the version header
+pub struct AlsSerializer {
+    checksum: bool,
+    generation_comment: bool,
+}
 
 impl AlsSerializer {
     /// Create a new serializer.
     pub fn new() -> Self {
-        Self
+        Self {
+            checksum: false,
+            generation_comment: false,
+        }
+    }
+
+    /// Enable or disable the `!crc<hex>` checksum directive.
+    ///
+    /// The checksum is the CRC-32 of every byte after the directive line
+    /// and is verified by `AlsParser` before parsing.
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Enable or disable the `;; Generated by ...` comment line.
+    ///
+    /// The comment names the library version and the current UTC time and
+    /// follows the version header. Parsers skip it.
+    pub fn with_generation_comment(mut self, comment: bool) -> Self {
+        self.generation_comment = comment;
+        self
     }
 
     /// Serialize an `AlsDocument` to ALS format string.
@@ -56,6 +84,21 @@ impl AlsSerializer {
         // Serialize version header
         self.serialize_version(&mut output, doc);
 
+        // Serialize comment lines
+        self.serialize_comments(&mut output, doc);
+
+        // Serialize sentinel token directives
+        self.serialize_sentinel_tokens(&mut output, doc);
+
+        // Serialize column order directive
+        self.serialize_column_order(&mut output, doc);
+
+        // Serialize row repeat directive
+        self.serialize_row_repeats(&mut output, doc);
+
+        // Serialize column dictionary directive
+        self.serialize_column_dictionaries(&mut output, doc);
+
         // Serialize dictionaries
         self.serialize_dictionaries(&mut output, doc);
 
@@ -65,9 +108,31 @@ impl AlsSerializer {
         // Serialize column streams
         self.serialize_streams(&mut output, doc);
 
+        if self.checksum {
+            return add_checksum(&output);
+        }
         output
     }
 
+    /// Serialize the generation comment and the document's comments.
+    fn serialize_comments(&self, output: &mut String, doc: &AlsDocument) {
+        if self.generation_comment {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs() as i64);
+            output.push_str(&format!(
+                "{} Generated by als-compression {}",
+                COMMENT_MARKER,
+                env!("CARGO_PKG_VERSION")
+            ));
+            if let Some(time) = format_epoch_iso8601(now) {
+                output.push_str(&format!(" at {}", time));
+            }
+            output.push('\n');
+        }
+        write_comments(output, &doc.comments);
+    }
+
     /// Serialize the version header.
     fn serialize_version(&self, output: &mut String, doc: &AlsDocument) {
         match doc.format_indicator {
@@ -80,6 +145,59 @@ impl AlsSerializer {
         }
     }
 
+    /// Serialize `!null:` and `!empty:` directives for non-default tokens.
+    fn serialize_sentinel_tokens(&self, output: &mut String, doc: &AlsDocument) {
+        let tokens = &doc.sentinel_tokens;
+        if tokens.null() != NULL_TOKEN {
+            output.push_str("!null:");
+            output.push_str(&escape_als_string(tokens.null()));
+            output.push('\n');
+        }
+        if tokens.empty() != EMPTY_TOKEN {
+            output.push_str("!empty:");
+            output.push_str(&escape_als_string(tokens.empty()));
+            output.push('\n');
+        }
+    }
+
+    /// Serialize the `!order:` directive for columns written out of schema order.
+    fn serialize_column_order(&self, output: &mut String, doc: &AlsDocument) {
+        let order = doc.written_column_order();
+        if order.iter().enumerate().all(|(position, &index)| position == index) {
+            return;
+        }
+        output.push_str("!order:");
+        for (i, index) in order.iter().enumerate() {
+            if i > 0 {
+                output.push(',');
+            }
+            output.push_str(&index.to_string());
+        }
+        output.push('\n');
+    }
+
+    /// Serialize the `!rows:` directive for stored rows standing for runs
+    /// of identical rows.
+    fn serialize_row_repeats(&self, output: &mut String, doc: &AlsDocument) {
+        if doc.row_repeats.is_empty() {
+            return;
+        }
+        output.push_str("!rows:");
+        output.push_str(&format_row_repeats(&doc.row_repeats));
+        output.push('\n');
+    }
+
+    /// Serialize the `!dicts:` directive for columns that use a dictionary
+    /// other than `default`.
+    fn serialize_column_dictionaries(&self, output: &mut String, doc: &AlsDocument) {
+        if doc.column_dictionaries.is_empty() {
+            return;
+        }
+        output.push_str("!dicts:");
+        output.push_str(&format_column_dictionaries(doc));
+        output.push('\n');
+    }
+
     /// Serialize dictionary headers.
     fn serialize_dictionaries(&self, output: &mut String, doc: &AlsDocument) {
         // Sort dictionary names for deterministic output
@@ -106,12 +224,21 @@ impl AlsSerializer {
 
     /// Serialize the schema.
     fn serialize_schema(&self, output: &mut String, doc: &AlsDocument) {
-        for (i, col_name) in doc.schema.iter().enumerate() {
+        let order = doc.written_column_order();
+        for (i, col_name) in order.iter().map(|&index| &doc.schema[index]).enumerate() {
             if i > 0 {
                 output.push(' ');
             }
             output.push('#');
             output.push_str(&escape_schema_name(col_name));
+            if let Some(hint) = doc.encoding_hint(col_name) {
+                output.push(':');
+                output.push_str(hint.name());
+            }
+            if let Some(number_type) = doc.number_type(col_name) {
+                output.push(':');
+                output.push_str(number_type.name());
+            }
         }
         if !doc.schema.is_empty() {
             output.push('\n');
@@ -119,8 +246,15 @@ impl AlsSerializer {
     }
 
     /// Serialize column streams.
+    ///
+    /// Streams are written in [`AlsDocument::written_column_order`] when
+    /// there is one stream per schema column.
     pub fn serialize_streams(&self, output: &mut String, doc: &AlsDocument) {
-        for (i, stream) in doc.streams.iter().enumerate() {
+        let mut order = doc.written_column_order();
+        if order.len() != doc.streams.len() {
+            order = (0..doc.streams.len()).collect();
+        }
+        for (i, stream) in order.iter().map(|&index| &doc.streams[index]).enumerate() {
             if i > 0 {
                 output.push('|');
             }
@@ -138,6 +272,18 @@ impl AlsSerializer {
         }
     }
 
+    /// Serialize a range as `start>end`, adding `:step` if it is not the default.
+    fn serialize_range(&self, output: &mut String, start: i64, end: i64, step: i64) {
+        output.push_str(&start.to_string());
+        output.push('>');
+        output.push_str(&end.to_string());
+        let default_step = if end >= start { 1 } else { -1 };
+        if step != default_step {
+            output.push(':');
+            output.push_str(&step.to_string());
+        }
+    }
+
     /// Serialize a single operator.
     pub fn serialize_operator(&self, output: &mut String, op: &AlsOperator) {
         match op {
@@ -145,22 +291,52 @@ impl AlsSerializer {
                 output.push_str(&escape_als_string(value));
             }
             AlsOperator::Range { start, end, step } => {
-                output.push_str(&start.to_string());
-                output.push('>');
-                output.push_str(&end.to_string());
-                // Only include step if it's not the default
-                let default_step = if *end >= *start { 1 } else { -1 };
-                if *step != default_step {
-                    output.push(':');
-                    output.push_str(&step.to_string());
+                self.serialize_range(output, *start, *end, *step);
+            }
+            AlsOperator::SparseRange { start, end, step, excluded } => {
+                self.serialize_range(output, *start, *end, *step);
+                output.push('!');
+                for (i, value) in excluded.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    output.push_str(&value.to_string());
                 }
             }
+            AlsOperator::PatchedRange { start, end, step, overrides } => {
+                self.serialize_range(output, *start, *end, *step);
+                output.push_str("!=");
+                for (i, (position, value)) in overrides.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    output.push_str(&format!("{}:{}", position, value));
+                }
+            }
+            AlsOperator::FloatRange { start, end, step, precision } => {
+                // Always include the step; precision keeps bounds exact
+                let precision = *precision as usize;
+                output.push_str(&format!("{:.*}", precision, start));
+                output.push('>');
+                output.push_str(&format!("{:.*}", precision, end));
+                output.push(':');
+                output.push_str(&format!("{:.*}", precision, step));
+            }
             AlsOperator::Multiply { value, count } => {
                 // Check if inner value needs parentheses
                 let needs_parens = matches!(value.as_ref(), 
                     AlsOperator::Range { .. } | 
+                    AlsOperator::SparseRange { .. } |
+                    AlsOperator::PatchedRange { .. } |
+                    AlsOperator::FloatRange { .. } |
                     AlsOperator::Toggle { .. } |
-                    AlsOperator::Multiply { .. }
+                    AlsOperator::Multiply { .. } |
+                    AlsOperator::Delta { .. } |
+                    AlsOperator::Geometric { .. } |
+                    AlsOperator::Timestamp { .. } |
+                    AlsOperator::Ipv4 { .. } |
+                    AlsOperator::DictRef(_) |
+                    AlsOperator::IntDict { .. }
                 );
                 
                 if needs_parens {
@@ -183,10 +359,93 @@ impl AlsSerializer {
                 output.push('*');
                 output.push_str(&count.to_string());
             }
+            AlsOperator::RunList { runs } => {
+                output.push('(');
+                for (i, (value, count)) in runs.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    output.push_str(&escape_als_string(value));
+                    output.push('*');
+                    output.push_str(&count.to_string());
+                }
+                output.push(')');
+            }
+            AlsOperator::Interleave { lanes } => {
+                output.push('(');
+                for (i, lane) in lanes.iter().enumerate() {
+                    if i > 0 {
+                        output.push('&');
+                    }
+                    self.serialize_operator(output, lane);
+                }
+                output.push(')');
+            }
+            AlsOperator::Affix { prefix, suffix, values } => {
+                output.push('{');
+                output.push_str(&escape_als_string(prefix));
+                if !suffix.is_empty() {
+                    output.push(':');
+                    output.push_str(&escape_als_string(suffix));
+                }
+                output.push_str("}(");
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    self.serialize_operator(output, value);
+                }
+                output.push(')');
+            }
             AlsOperator::DictRef(index) => {
                 output.push('_');
                 output.push_str(&index.to_string());
             }
+            AlsOperator::Delta { base, deltas } => {
+                output.push_str(&base.to_string());
+                output.push_str("^d");
+                for (i, delta) in deltas.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    output.push_str(&delta.to_string());
+                }
+            }
+            AlsOperator::Geometric { start, ratio, count } => {
+                output.push_str(&start.to_string());
+                output.push_str("^*");
+                output.push_str(&ratio.to_string());
+                output.push('#');
+                output.push_str(&count.to_string());
+            }
+            AlsOperator::Timestamp { format, value } => {
+                output.push('@');
+                output.push_str(&format.tag());
+                output.push('(');
+                self.serialize_operator(output, value);
+                output.push(')');
+            }
+            AlsOperator::Ipv4 { value } => {
+                output.push('@');
+                output.push_str(IPV4_TAG);
+                output.push('(');
+                self.serialize_operator(output, value);
+                output.push(')');
+            }
+            AlsOperator::IntDict { values, indices } => {
+                output.push('%');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    output.push_str(&value.to_string());
+                }
+                output.push('#');
+                output.push_str(&indices.len().to_string());
+                output.push(':');
+                let width = AlsOperator::int_dict_width(values.len());
+                output.push_str(&AlsOperator::pack_indices(indices, width));
+            }
         }
     }
 }
@@ -207,6 +466,8 @@ pub struct AlsPrettyPrinter {
     show_expanded: bool,
     /// Indentation string (default: 2 spaces)
     indent: String,
+    /// Whether to lay out streams as an aligned grid of columns
+    align_columns: bool,
 }
 
 impl AlsPrettyPrinter {
@@ -215,6 +476,7 @@ impl AlsPrettyPrinter {
         Self {
             show_expanded: false,
             indent: "  ".to_string(),
+            align_columns: false,
         }
     }
 
@@ -233,6 +495,17 @@ impl AlsPrettyPrinter {
         self
     }
 
+    /// Enable or disable the aligned column layout for data streams.
+    ///
+    /// When enabled, streams are printed as a grid with one column per
+    /// schema entry: the column names form a header row and each row below
+    /// holds the next operator of every stream, padded to the widest cell
+    /// in its column. The grid is for display only and is not valid ALS.
+    pub fn with_column_alignment(mut self, align: bool) -> Self {
+        self.align_columns = align;
+        self
+    }
+
     /// Pretty print an `AlsDocument` to a formatted string.
     ///
     /// # Arguments
@@ -273,7 +546,11 @@ impl AlsPrettyPrinter {
         if !doc.streams.is_empty() {
             output.push_str("# Data Streams\n");
             output.push_str("# ------------\n");
-            self.format_streams(&mut output, doc);
+            if self.align_columns {
+                self.format_streams_aligned(&mut output, doc);
+            } else {
+                self.format_streams(&mut output, doc);
+            }
         }
 
         output
@@ -289,6 +566,23 @@ impl AlsPrettyPrinter {
                 output.push_str("!ctx  # CTX fallback format\n");
             }
         }
+        let tokens = &doc.sentinel_tokens;
+        write_comments(output, &doc.comments);
+        if tokens.null() != NULL_TOKEN {
+            output.push_str(&format!("!null:{}  # null token\n", escape_als_string(tokens.null())));
+        }
+        if tokens.empty() != EMPTY_TOKEN {
+            output.push_str(&format!("!empty:{}  # empty string token\n", escape_als_string(tokens.empty())));
+        }
+        if !doc.row_repeats.is_empty() {
+            output.push_str(&format!("!rows:{}  # repeated rows\n", format_row_repeats(&doc.row_repeats)));
+        }
+        if !doc.column_dictionaries.is_empty() {
+            output.push_str(&format!(
+                "!dicts:{}  # column dictionaries\n",
+                format_column_dictionaries(doc)
+            ));
+        }
     }
 
     /// Format dictionary headers.
@@ -330,14 +624,20 @@ impl AlsPrettyPrinter {
             }
             output.push('#');
             output.push_str(&escape_schema_name(col_name));
+            if let Some(hint) = doc.encoding_hint(col_name) {
+                output.push(':');
+                output.push_str(hint.name());
+            }
+            if let Some(number_type) = doc.number_type(col_name) {
+                output.push(':');
+                output.push_str(number_type.name());
+            }
         }
         output.push_str(&format!("  # {} columns\n", doc.schema.len()));
     }
 
     /// Format column streams.
     fn format_streams(&self, output: &mut String, doc: &AlsDocument) {
-        let default_dict = doc.default_dictionary();
-
         for (col_idx, stream) in doc.streams.iter().enumerate() {
             if col_idx > 0 {
                 output.push_str("\n|  # column separator\n\n");
@@ -347,7 +647,69 @@ impl AlsPrettyPrinter {
             let col_name = doc.schema.get(col_idx).map(|s| s.as_str()).unwrap_or("?");
             output.push_str(&format!("# Column {}: {}\n", col_idx, col_name));
 
-            self.format_stream(output, stream, default_dict.map(|v| v.as_slice()));
+            self.format_stream(output, stream, doc.column_dictionary(col_idx));
+        }
+    }
+
+    /// Format column streams as an aligned grid under a header of column names.
+    fn format_streams_aligned(&self, output: &mut String, doc: &AlsDocument) {
+        let columns: Vec<(String, Vec<String>)> = doc
+            .streams
+            .iter()
+            .enumerate()
+            .map(|(col_idx, stream)| {
+                let name = doc.schema.get(col_idx).map(|s| s.as_str()).unwrap_or("?");
+                let cells = stream
+                    .operators
+                    .iter()
+                    .map(|op| {
+                        let mut cell = String::new();
+                        self.format_operator(&mut cell, op, doc.column_dictionary(col_idx));
+                        cell
+                    })
+                    .collect();
+                (name.to_string(), cells)
+            })
+            .collect();
+
+        let widths: Vec<usize> = columns
+            .iter()
+            .map(|(name, cells)| {
+                cells
+                    .iter()
+                    .map(|cell| cell.chars().count())
+                    .chain(std::iter::once(name.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let rows = columns.iter().map(|(_, cells)| cells.len()).max().unwrap_or(0);
+
+        let push_row = |output: &mut String, cells: &mut dyn Iterator<Item = &str>| {
+            let mut line = String::new();
+            for (col_idx, (cell, width)) in cells.zip(&widths).enumerate() {
+                if col_idx > 0 {
+                    line.push_str(" | ");
+                }
+                line.push_str(&format!("{:<1$}", cell, width));
+            }
+            output.push_str(line.trim_end());
+            output.push('\n');
+        };
+
+        push_row(output, &mut columns.iter().map(|(name, _)| name.as_str()));
+
+        let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+        output.push_str(&rule.join("-+-"));
+        output.push('\n');
+
+        for row in 0..rows {
+            push_row(
+                output,
+                &mut columns
+                    .iter()
+                    .map(|(_, cells)| cells.get(row).map(|s| s.as_str()).unwrap_or("")),
+            );
         }
     }
 
@@ -372,16 +734,22 @@ impl AlsPrettyPrinter {
 
         // Add expanded comment if enabled
         if self.show_expanded {
-            if let Ok(expanded) = op.expand(dictionary) {
-                let preview = if expanded.len() <= 5 {
-                    expanded.join(", ")
+            // Keep only the values the preview shows instead of the full expansion
+            let len = op.expanded_len();
+            let mut head = Vec::with_capacity(len.min(5));
+            let mut last = String::new();
+            let expanded = op.expand_into(dictionary, &mut |value| {
+                if head.len() < 5 {
+                    head.push(value.into_owned());
+                } else {
+                    last = value.into_owned();
+                }
+            });
+            if expanded.is_ok() {
+                let preview = if len <= 5 {
+                    head.join(", ")
                 } else {
-                    format!(
-                        "{}, ..., {} ({} values)",
-                        expanded[..2].join(", "),
-                        expanded.last().unwrap(),
-                        expanded.len()
-                    )
+                    format!("{}, ..., {} ({} values)", head[..2].join(", "), last, len)
                 };
                 output.push_str(&format!("  /* {} */", preview));
             }
@@ -397,9 +765,10 @@ impl Default for AlsPrettyPrinter {
 
 /// Escape a dictionary value for serialization.
 ///
-/// Dictionary values are separated by `|` and terminated by newline,
-/// so we need to escape those characters plus the standard ALS operators.
-fn escape_dict_value(s: &str) -> String {
+/// Dictionary values are separated by `|` and terminated by newline, so
+/// those characters and the backslash itself are escaped. The tokenizer's
+/// `read_dict_value` accepts exactly these escapes.
+pub(crate) fn escape_dict_value(s: &str) -> String {
     let mut result = String::with_capacity(s.len() + s.len() / 4);
     
     for c in s.chars() {
@@ -415,6 +784,34 @@ fn escape_dict_value(s: &str) -> String {
     result
 }
 
+/// Write each comment as a `;; text` line, one per line of its text.
+fn write_comments(output: &mut String, comments: &[String]) {
+    for line in comments.iter().flat_map(|comment| comment.lines()) {
+        output.push_str(&format!("{} {}\n", COMMENT_MARKER, line.trim()));
+    }
+}
+
+/// Format row repeats as the `row*count` list of a `!rows:` directive.
+fn format_row_repeats(repeats: &[(usize, usize)]) -> String {
+    repeats
+        .iter()
+        .map(|(row, count)| format!("{}*{}", row, count))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Format the non-default column dictionaries as the `column=name` list of
+/// a `!dicts:` directive, in schema order.
+fn format_column_dictionaries(doc: &AlsDocument) -> String {
+    let mut assignments: Vec<_> = doc.column_dictionaries.iter().collect();
+    assignments.sort();
+    assignments
+        .iter()
+        .map(|(column, name)| format!("{}={}", column, name))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 /// Escape a schema column name for serialization.
 ///
 /// Schema names are separated by spaces, so we need to escape spaces
@@ -430,6 +827,7 @@ fn escape_schema_name(s: &str) -> String {
             '\t' => result.push_str("\\t"),
             '\\' => result.push_str("\\\\"),
             '#' => result.push_str("\\#"),
+            ':' => result.push_str("\\:"),
             _ => result.push(c),
         }
     }
@@ -441,7 +839,7 @@ fn escape_schema_name(s: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::als::AlsDocument;
+    use crate::als::{AlsDocument, ColumnEncoding, SentinelTokens, TimestampFormat};
 
     // ==================== AlsSerializer tests ====================
 
@@ -461,6 +859,42 @@ mod tests {
         assert!(result.starts_with("!v1\n"));
     }
 
+    #[test]
+    fn test_serialize_sentinel_directives() {
+        let mut doc = AlsDocument::with_schema(vec!["a"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("x")]));
+        let serializer = AlsSerializer::new();
+        assert_eq!(serializer.serialize(&doc), "!v1\n#a\nx");
+
+        doc.sentinel_tokens = SentinelTokens::new("N/A", EMPTY_TOKEN);
+        assert_eq!(serializer.serialize(&doc), "!v1\n!null:N/A\n#a\nx");
+
+        doc.sentinel_tokens = SentinelTokens::new("<null>", "no value");
+        assert_eq!(
+            serializer.serialize(&doc),
+            "!v1\n!null:<null\\>\n!empty:no\\ value\n#a\nx"
+        );
+    }
+
+    #[test]
+    fn test_serialize_comments() {
+        let mut doc = AlsDocument::with_schema(vec!["a"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("x")]));
+        doc.comments = vec!["nightly export".to_string(), "two\nlines".to_string()];
+        assert_eq!(
+            AlsSerializer::new().serialize(&doc),
+            "!v1\n;; nightly export\n;; two\n;; lines\n#a\nx"
+        );
+
+        doc.comments.clear();
+        let output = AlsSerializer::new().with_generation_comment(true).serialize(&doc);
+        let comment = output.lines().nth(1).unwrap();
+        let prefix = format!(";; Generated by als-compression {} at ", env!("CARGO_PKG_VERSION"));
+        assert!(comment.starts_with(&prefix), "{comment}");
+        assert!(comment.ends_with('Z'), "{comment}");
+        assert!(output.ends_with("\n#a\nx"));
+    }
+
     #[test]
     fn test_serialize_version_ctx() {
         let mut doc = AlsDocument::new();
@@ -499,6 +933,16 @@ mod tests {
         assert!(result.contains("#id #name #age\n"));
     }
 
+    #[test]
+    fn test_serialize_schema_encoding_hints() {
+        let mut doc = AlsDocument::with_schema(vec!["id", "status", "note"]);
+        doc.set_encoding_hint("id", ColumnEncoding::Range);
+        doc.set_encoding_hint("status", ColumnEncoding::Dict);
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("#id:range #status:dict #note\n"), "{}", result);
+    }
+
     #[test]
     fn test_serialize_raw_values() {
         let mut doc = AlsDocument::with_schema(vec!["col"]);
@@ -579,6 +1023,17 @@ mod tests {
         assert!(result.contains("(1>3)*2"));
     }
 
+    #[test]
+    fn test_serialize_multiply_dict_ref() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::multiply(AlsOperator::dict_ref(0), 4),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("(_0)*4"));
+    }
+
     #[test]
     fn test_serialize_toggle() {
         let mut doc = AlsDocument::with_schema(vec!["col"]);
@@ -601,6 +1056,137 @@ mod tests {
         assert!(result.contains("A~B~C*6"));
     }
 
+    #[test]
+    fn test_serialize_float_range() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::float_range(0.0, 2.0, 0.5),
+            AlsOperator::float_range(1.25, 0.75, -0.25),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("0.0>2.0:0.5 1.25>0.75:-0.25"));
+    }
+
+    #[test]
+    fn test_serialize_delta() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::delta(100, vec![0, 0, 5, -2]),
+            AlsOperator::multiply(AlsOperator::delta(1, vec![1]), 2),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("100^d0,0,5,-2 (1^d1)*2"));
+    }
+
+    #[test]
+    fn test_serialize_geometric() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::geometric(1, 2, 5),
+            AlsOperator::multiply(AlsOperator::geometric(-3, -10, 3), 2),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("1^*2#5 (-3^*-10#3)*2"), "{}", result);
+    }
+
+    #[test]
+    fn test_serialize_run_list() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::run_list(vec![("A", 3), ("a,b", 2), ("x>y", 1)]),
+            AlsOperator::multiply(AlsOperator::run_list(vec![("B", 2), ("C", 1)]), 2),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("(A*3,a\\,b*2,x\\>y*1) (B*2,C*1)*2"), "{}", result);
+    }
+
+    #[test]
+    fn test_serialize_affix() {
+        let mut doc = AlsDocument::with_schema(vec!["url", "file"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::affix(
+            "https://a.io/",
+            "",
+            vec![AlsOperator::raw("x,y"), AlsOperator::range(1, 3)],
+        )]));
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::multiply(
+            AlsOperator::affix("", ".csv", vec![AlsOperator::raw("a"), AlsOperator::raw("b")]),
+            2,
+        )]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("{https\\://a.io/}(x\\,y,1>3)|{:.csv}(a,b)*2"), "{}", result);
+    }
+
+    #[test]
+    fn test_serialize_interleave() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::interleave(vec![
+                AlsOperator::range(1, 3),
+                AlsOperator::multiply(AlsOperator::raw("R&D"), 3),
+            ]),
+            AlsOperator::multiply(
+                AlsOperator::interleave(vec![AlsOperator::raw("a"), AlsOperator::raw("b")]),
+                2,
+            ),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("(1>3&R\\&D*3) (a&b)*2"), "{}", result);
+    }
+
+    #[test]
+    fn test_serialize_sparse_range() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::sparse_range(1, 7, 1, vec![4]),
+            AlsOperator::multiply(AlsOperator::sparse_range(0, 20, 5, vec![5, 15]), 2),
+            AlsOperator::raw("7!4"),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("1>7!4 (0>20:5!5,15)*2 7\\!4"), "{}", result);
+
+        let parsed = crate::als::AlsParser::new().parse(&result).unwrap();
+        assert_eq!(parsed.streams, doc.streams);
+    }
+
+    #[test]
+    fn test_serialize_patched_range() {
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::patched_range(1, 6, 1, vec![(0, 900), (2, -5)]),
+            AlsOperator::multiply(AlsOperator::patched_range(0, 10, 5, vec![(1, 3)]), 2),
+            AlsOperator::raw("7!=1:2"),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("1>6!=0:900,2:-5 (0>10:5!=1:3)*2 7\\!=1\\:2"), "{}", result);
+
+        let parsed = crate::als::AlsParser::new().parse(&result).unwrap();
+        assert_eq!(parsed.streams, doc.streams);
+    }
+
+    #[test]
+    fn test_serialize_timestamp() {
+        let format = TimestampFormat::from_tag("T3+05:30").unwrap();
+        let mut doc = AlsDocument::with_schema(vec!["ts"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::timestamp(format.clone(), AlsOperator::range(1000, 3000)),
+            AlsOperator::multiply(
+                AlsOperator::timestamp(format, AlsOperator::delta(5, vec![1])),
+                2,
+            ),
+        ]));
+        let serializer = AlsSerializer::new();
+        let result = serializer.serialize(&doc);
+        assert!(result.contains("@T3+05:30(1000>3000) (@T3+05:30(5^d1))*2"));
+    }
+
     #[test]
     fn test_serialize_dict_ref() {
         let mut doc = AlsDocument::with_schema(vec!["col"]);
@@ -680,12 +1266,36 @@ mod tests {
         assert_eq!(escape_dict_value("a\\b"), "a\\\\b");
     }
 
+    #[test]
+    fn test_dictionary_values_round_trip() {
+        use crate::als::AlsParser;
+
+        let values = vec![
+            "a|b".to_string(),
+            "back\\slash".to_string(),
+            "line\nbreak".to_string(),
+            "\\|\\".to_string(),
+            "trailing\\".to_string(),
+        ];
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_dictionary("default", values.clone());
+        doc.add_stream(ColumnStream::from_operators(
+            (0..values.len()).map(AlsOperator::dict_ref).collect(),
+        ));
+
+        let serialized = AlsSerializer::new().serialize(&doc);
+        let parsed = AlsParser::new().parse(&serialized).unwrap();
+        assert_eq!(parsed.dictionaries["default"], values);
+        assert_eq!(parsed.streams[0].expand(Some(&values)).unwrap(), values);
+    }
+
     #[test]
     fn test_escape_schema_name() {
         assert_eq!(escape_schema_name("column"), "column");
         assert_eq!(escape_schema_name("my column"), "my\\ column");
         assert_eq!(escape_schema_name("col\ttab"), "col\\ttab");
         assert_eq!(escape_schema_name("a#b"), "a\\#b");
+        assert_eq!(escape_schema_name("a:range"), "a\\:range");
     }
 
     #[test]
@@ -771,6 +1381,60 @@ mod tests {
         assert!(result.contains("|  # column separator"));
     }
 
+    #[test]
+    fn test_pretty_print_column_alignment() {
+        let mut doc = AlsDocument::with_schema(vec!["id", "name", "score"]);
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::range(1, 3),
+        ]));
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::raw("alice"),
+            AlsOperator::raw("bob"),
+            AlsOperator::raw("charlotte"),
+        ]));
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::multiply(AlsOperator::raw("10"), 2),
+            AlsOperator::raw("7"),
+        ]));
+
+        let printer = AlsPrettyPrinter::new().with_column_alignment(true);
+        let result = printer.format(&doc);
+        let grid: Vec<&str> = result
+            .lines()
+            .skip_while(|line| *line != "# ------------" || !result.contains("# Data Streams"))
+            .skip_while(|line| !line.starts_with("id"))
+            .collect();
+
+        assert_eq!(grid[0], "id  | name      | score");
+        assert_eq!(grid[1], "----+-----------+------");
+        assert_eq!(grid[2], "1>3 | alice     | 10*2");
+        assert_eq!(grid[3], "    | bob       | 7");
+        assert_eq!(grid[4], "    | charlotte |");
+        assert!(!result.contains("# column separator"));
+
+        // Every header name starts at the same offset as its column's cells
+        for name in ["name", "score"] {
+            let offset = grid[0].find(name).unwrap();
+            assert_eq!(&grid[1][offset - 2..offset - 1], "+");
+            assert_eq!(&grid[2][offset - 2..offset - 1], "|");
+        }
+    }
+
+    #[test]
+    fn test_pretty_print_column_alignment_with_expanded_comments() {
+        let mut doc = AlsDocument::with_schema(vec!["a", "b"]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::range(1, 3)]));
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("x")]));
+
+        let printer = AlsPrettyPrinter::new()
+            .with_column_alignment(true)
+            .with_expanded_comments(true);
+        let result = printer.format(&doc);
+
+        assert!(result.contains("a                  | b\n"));
+        assert!(result.contains("1>3  /* 1, 2, 3 */ | x  /* x */\n"));
+    }
+
     #[test]
     fn test_pretty_print_with_expanded_comments() {
         let mut doc = AlsDocument::with_schema(vec!["col"]);
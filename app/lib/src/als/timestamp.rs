@@ -0,0 +1,325 @@
+//! ISO-8601 timestamp formats for the timestamp operator.
+//!
+//! Timestamps are converted to integers counting units of
+//! `10^-fraction_digits` seconds since `1970-01-01T00:00:00` in the
+//! timestamp's own wall-clock time. The timezone suffix is carried verbatim
+//! in the [`TimestampFormat`], so reconstruction reproduces the original text
+//! exactly without any timezone arithmetic.
+
+/// Seconds in one day.
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Largest number of fractional-second digits supported (nanoseconds).
+const MAX_FRACTION_DIGITS: u8 = 9;
+
+/// Textual layout shared by every timestamp in a column.
+///
+/// Describes timestamps of the form `YYYY-MM-DD<sep>HH:MM:SS[.fff][offset]`,
+/// for example `2023-01-01T00:00:00Z` or `2023-01-01 12:30:00.250+05:30`.
+///
+/// In ALS text the format is written as a compact tag: the separator (`T`,
+/// or `S` for a space), the number of fractional digits, then the offset
+/// suffix, e.g. `T0Z`, `S3`, or `T0+05:30`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TimestampFormat {
+    /// Separator between date and time (`'T'` or `' '`).
+    pub separator: char,
+    /// Number of fractional-second digits (0-9).
+    pub fraction_digits: u8,
+    /// Timezone suffix copied verbatim (`Z`, `+05:30`, `-0800`, or empty).
+    pub offset: String,
+}
+
+impl TimestampFormat {
+    /// Parse a timestamp, returning its format and scaled integer value.
+    ///
+    /// Returns `None` if the string is not a well-formed ISO-8601 timestamp
+    /// in the supported layout, or if it would not be reproduced exactly by
+    /// [`TimestampFormat::format`] (e.g. leap seconds), or if the scaled value
+    /// overflows `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::TimestampFormat;
+    ///
+    /// let (format, value) = TimestampFormat::parse("2023-01-01T00:00:01Z").unwrap();
+    /// assert_eq!(format.tag(), "T0Z");
+    /// assert_eq!(value, 1_672_531_201);
+    /// assert_eq!(format.format(value).unwrap(), "2023-01-01T00:00:01Z");
+    /// ```
+    pub fn parse(s: &str) -> Option<(Self, i64)> {
+        let bytes = s.as_bytes();
+        if bytes.len() < 19
+            || bytes[4] != b'-'
+            || bytes[7] != b'-'
+            || bytes[13] != b':'
+            || bytes[16] != b':'
+        {
+            return None;
+        }
+
+        let separator = match bytes[10] {
+            b'T' => 'T',
+            b' ' => ' ',
+            _ => return None,
+        };
+
+        let year = parse_digits(&bytes[0..4])?;
+        let month = parse_digits(&bytes[5..7])?;
+        let day = parse_digits(&bytes[8..10])?;
+        let hour = parse_digits(&bytes[11..13])?;
+        let minute = parse_digits(&bytes[14..16])?;
+        let second = parse_digits(&bytes[17..19])?;
+
+        if !(1..=12).contains(&month)
+            || day < 1
+            || day > days_in_month(year, month)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return None;
+        }
+
+        // Optional fractional seconds
+        let mut rest = &s[19..];
+        let mut fraction = 0;
+        let mut fraction_digits = 0u8;
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let digits = after_dot.bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 || digits > MAX_FRACTION_DIGITS as usize {
+                return None;
+            }
+            fraction = parse_digits(&after_dot.as_bytes()[..digits])?;
+            fraction_digits = digits as u8;
+            rest = &after_dot[digits..];
+        }
+
+        if !is_valid_offset(rest) {
+            return None;
+        }
+
+        let seconds = days_from_civil(year, month, day) * SECONDS_PER_DAY
+            + hour * 3600
+            + minute * 60
+            + second;
+        // Nanosecond precision overflows i64 for dates after 2262
+        let value = seconds
+            .checked_mul(10i64.pow(u32::from(fraction_digits)))?
+            .checked_add(fraction)?;
+
+        let format = Self {
+            separator,
+            fraction_digits,
+            offset: rest.to_string(),
+        };
+        Some((format, value))
+    }
+
+    /// Format a scaled integer value as a timestamp in this format.
+    ///
+    /// Returns `None` if the value falls outside years 0000-9999.
+    pub fn format(&self, value: i64) -> Option<String> {
+        let scale = 10i64.pow(u32::from(self.fraction_digits));
+        let seconds = value.div_euclid(scale);
+        let fraction = value.rem_euclid(scale);
+
+        let days = seconds.div_euclid(SECONDS_PER_DAY);
+        let second_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        if !(0..=9999).contains(&year) {
+            return None;
+        }
+
+        let mut result = format!(
+            "{:04}-{:02}-{:02}{}{:02}:{:02}:{:02}",
+            year,
+            month,
+            day,
+            self.separator,
+            second_of_day / 3600,
+            second_of_day % 3600 / 60,
+            second_of_day % 60
+        );
+        if self.fraction_digits > 0 {
+            result.push_str(&format!(
+                ".{:0width$}",
+                fraction,
+                width = self.fraction_digits as usize
+            ));
+        }
+        result.push_str(&self.offset);
+        Some(result)
+    }
+
+    /// Compact tag used to write this format in ALS text (e.g. `T3Z`).
+    pub fn tag(&self) -> String {
+        let separator = if self.separator == ' ' { 'S' } else { 'T' };
+        format!("{}{}{}", separator, self.fraction_digits, self.offset)
+    }
+
+    /// Parse a tag produced by [`TimestampFormat::tag`].
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let mut chars = tag.chars();
+        let separator = match chars.next()? {
+            'T' => 'T',
+            'S' => ' ',
+            _ => return None,
+        };
+        let fraction_digits = chars.next()?.to_digit(10)? as u8;
+        let offset = chars.as_str();
+        if !is_valid_offset(offset) {
+            return None;
+        }
+
+        Some(Self {
+            separator,
+            fraction_digits,
+            offset: offset.to_string(),
+        })
+    }
+}
+
+/// Parse a run of ASCII digits.
+fn parse_digits(bytes: &[u8]) -> Option<i64> {
+    bytes.iter().try_fold(0i64, |acc, &b| {
+        b.is_ascii_digit().then(|| acc * 10 + i64::from(b - b'0'))
+    })
+}
+
+/// Check that a timezone suffix is empty, `Z`, `±HH`, `±HHMM`, or `±HH:MM`.
+fn is_valid_offset(offset: &str) -> bool {
+    let bytes = offset.as_bytes();
+    match bytes {
+        [] | [b'Z'] => true,
+        [b'+' | b'-', rest @ ..] => {
+            let (hours, minutes) = match rest {
+                [h1, h2] => ([*h1, *h2], None),
+                [h1, h2, m1, m2] => ([*h1, *h2], Some([*m1, *m2])),
+                [h1, h2, b':', m1, m2] => ([*h1, *h2], Some([*m1, *m2])),
+                _ => return false,
+            };
+            let hours_ok = parse_digits(&hours).is_some_and(|h| h <= 23);
+            let minutes_ok = minutes.is_none_or(|m| parse_digits(&m).is_some_and(|m| m <= 59));
+            hours_ok && minutes_ok
+        }
+        _ => false,
+    }
+}
+
+/// Number of days in the given month of the proleptic Gregorian calendar.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a civil date (Howard Hinnant's algorithm).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Civil date for a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(s: &str) -> String {
+        let (format, value) = TimestampFormat::parse(s).unwrap();
+        format.format(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_utc_seconds() {
+        let (format, value) = TimestampFormat::parse("2023-01-01T00:00:00Z").unwrap();
+        assert_eq!(format.separator, 'T');
+        assert_eq!(format.fraction_digits, 0);
+        assert_eq!(format.offset, "Z");
+        assert_eq!(value, 1_672_531_200);
+    }
+
+    #[test]
+    fn test_parse_fractional_seconds_scale_value() {
+        let (format, value) = TimestampFormat::parse("1970-01-01T00:00:01.250Z").unwrap();
+        assert_eq!(format.fraction_digits, 3);
+        assert_eq!(value, 1250);
+    }
+
+    #[test]
+    fn test_round_trip_preserves_text() {
+        for s in [
+            "2023-01-01T00:00:00Z",
+            "2023-06-15 12:30:45",
+            "2024-02-29T23:59:59.999999+05:30",
+            "1969-12-31T23:59:59.5-0800",
+            "0001-01-01T00:00:00+01",
+            "9999-12-31T23:59:59Z",
+            "2262-04-11T23:47:16.854775807Z",
+        ] {
+            assert_eq!(round_trip(s), s);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_timestamps() {
+        for s in [
+            "2023-01-01",
+            "2023-13-01T00:00:00Z",
+            "2023-02-29T00:00:00Z",
+            "2023-01-01T24:00:00Z",
+            "2023-01-01T00:00:60Z",
+            "2023-01-01X00:00:00Z",
+            "2023-01-01T00:00:00.Z",
+            "2023-01-01T00:00:00 UTC",
+            "2023-01-01T00:00:00+5:30",
+            "+2023-01-01T00:00:00Z",
+            "2262-04-11T23:47:16.854775808Z",
+        ] {
+            assert!(TimestampFormat::parse(s).is_none(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_format_out_of_range() {
+        let (format, _) = TimestampFormat::parse("9999-12-31T23:59:59Z").unwrap();
+        let (_, max) = TimestampFormat::parse("9999-12-31T23:59:59Z").unwrap();
+        assert!(format.format(max + 1).is_none());
+        assert!(format.format(i64::MIN).is_none());
+    }
+
+    #[test]
+    fn test_tag_round_trip() {
+        for s in ["2023-01-01T00:00:00Z", "2023-01-01 00:00:00.123", "2023-01-01T00:00:00-08:00"] {
+            let (format, _) = TimestampFormat::parse(s).unwrap();
+            assert_eq!(TimestampFormat::from_tag(&format.tag()), Some(format));
+        }
+        assert_eq!(TimestampFormat::from_tag("S3").unwrap().separator, ' ');
+        assert!(TimestampFormat::from_tag("X0Z").is_none());
+        assert!(TimestampFormat::from_tag("T").is_none());
+        assert!(TimestampFormat::from_tag("T0UTC").is_none());
+    }
+}
@@ -7,19 +7,43 @@
 //!
 //! - Version prefix: `!v1` or `!ctx`
 //! - Dictionary header: `$name:val1|val2`
-//! - Schema prefix: `#column_name`
-//! - Operators: `>`, `*`, `~`
+//! - Schema prefix: `#column_name`, optionally annotated as `#column_name:range`
+//! - Operators: `>`, `*`, `~`, `^d`, `^*`
+//! - Sparse range exclusions: `!4,9`
+//! - Run list separator: `,`
+//! - Timestamp format tag: `@T0Z`, `@S3+05:30`, etc., or the IPv4 tag `@ip4`
+//! - Affix tag: `{prefix:suffix}`
+//! - Integer dictionary: `%100,200,404#3:k`
 //! - Column separator: `|`
 //! - Dictionary reference: `_0`, `_1`, etc.
 //! - Numbers and raw values
 
+use super::document::{ColumnEncoding, NumberType};
+use super::escape::{decode_hex_escape, COMMENT_MARKER};
 use crate::error::{AlsError, Result};
 
+/// Characters that terminate an unescaped raw value.
+const RAW_VALUE_DELIMITERS: &[char] =
+    &[' ', '\t', '\n', '\r', '|', '>', '*', '~', ':', ',', '&', '(', ')'];
+
+/// Characters that terminate the prefix or suffix of an affix tag.
+const AFFIX_DELIMITERS: &[char] = &[':', '}', '|', '\n'];
+
 /// Token types produced by the ALS tokenizer.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     /// Version indicator: `!v1` (ALS) or `!ctx` (CTX fallback)
     Version(VersionType),
+    /// Null token directive: `!null:NULL`
+    NullDirective(String),
+    /// Empty string token directive: `!empty:EMPTY`
+    EmptyDirective(String),
+    /// Column order directive: `!order:2,0,1`
+    OrderDirective(Vec<usize>),
+    /// Row repeat directive: `!rows:3*5,8*2`
+    RowsDirective(Vec<(usize, usize)>),
+    /// Column dictionary directive: `!dicts:1=geo,3=status`
+    DictsDirective(Vec<(usize, String)>),
     /// Dictionary header: `$name:val1|val2|val3`
     DictionaryHeader {
         /// Dictionary name
@@ -27,12 +51,16 @@ pub enum Token {
         /// Dictionary values
         values: Vec<String>,
     },
-    /// Schema column: `#column_name`
-    SchemaColumn(String),
+    /// Schema column with an optional encoding hint and number type:
+    /// `#column_name`, `#id:range`, or `#id:range:int`
+    SchemaColumn(String, Option<ColumnEncoding>, Option<NumberType>),
     /// Integer literal
     Integer(i64),
     /// Float literal
     Float(f64),
+    /// Float literal that is not the shortest form of its value, with the
+    /// text as written: `19.90`, `1e-9`, `+2.5`
+    Decimal(f64, String),
     /// Raw string value (possibly escaped)
     RawValue(String),
     /// Range operator: `>`
@@ -41,22 +69,71 @@ pub enum Token {
     MultiplyOp,
     /// Toggle operator: `~`
     ToggleOp,
+    /// Delta operator with its comma-separated deltas: `^d1,0,-2`
+    DeltaOp(Vec<i64>),
+    /// Values excluded from a sparse range: `!4,9`
+    ExcludeOp(Vec<i64>),
+    /// Positions and values overriding a patched range: `!=2:90,7:-1`
+    OverrideOp(Vec<(usize, i64)>),
+    /// Geometric operator with its ratio and count: `^*2#5`
+    GeometricOp {
+        /// Ratio between consecutive values
+        ratio: i64,
+        /// Number of values in the sequence
+        count: usize,
+    },
+    /// Timestamp format tag preceding a grouped operator: `@T0Z`, or the
+    /// IPv4 tag `@ip4`
+    TimestampTag(String),
+    /// Affix tag preceding a list of middles: `{prefix:suffix}`
+    AffixTag {
+        /// Text shared at the start of every value
+        prefix: String,
+        /// Text shared at the end of every value
+        suffix: String,
+    },
+    /// Integer dictionary with its values, count and packed indices: `%200,404#4:G`
+    IntDict {
+        /// Distinct integer values
+        values: Vec<i64>,
+        /// Number of packed indices
+        count: usize,
+        /// Base64 characters holding the packed indices
+        packed: String,
+    },
     /// Column separator: `|`
     ColumnSeparator,
     /// Dictionary reference: `_0`, `_1`, etc.
     DictRef(usize),
     /// Step separator in ranges: `:`
     StepSeparator,
+    /// Separator between the runs of a run list: `,`
+    RunSeparator,
+    /// Separator between the lanes of an interleave: `&`
+    InterleaveSeparator,
     /// Open parenthesis for grouping: `(`
     OpenParen,
     /// Close parenthesis for grouping: `)`
     CloseParen,
+    /// Comment line: `;; text` (skipped by the parser)
+    Comment(String),
     /// Newline (significant in some contexts)
     Newline,
     /// End of input
     Eof,
 }
 
+/// A token together with the byte range of its source text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken {
+    /// The token
+    pub token: Token,
+    /// Byte offset of the first character of the token
+    pub start: usize,
+    /// Byte offset just past the last character of the token
+    pub end: usize,
+}
+
 /// Version type indicator.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VersionType {
@@ -91,6 +168,11 @@ impl<'a> Tokenizer<'a> {
         self.position
     }
 
+    /// Get the full input text.
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
     /// Peek at the next character without consuming it.
     fn peek_char(&mut self) -> Option<char> {
         self.chars.peek().map(|(_, c)| *c)
@@ -115,6 +197,23 @@ impl<'a> Tokenizer<'a> {
         }
     }
 
+    /// Whether the upcoming characters open a `;;` comment line.
+    ///
+    /// Comments must start a line (only blanks may precede them), so a `;;`
+    /// inside a value or operator is never mistaken for one.
+    fn at_comment(&mut self) -> bool {
+        let start = match self.chars.peek() {
+            Some(&(pos, _)) => pos,
+            None => return false,
+        };
+        self.input[start..].starts_with(COMMENT_MARKER)
+            && self.input[..start]
+                .trim_end_matches([' ', '\t', '\r'])
+                .chars()
+                .next_back()
+                .is_none_or(|c| c == '\n')
+    }
+
     /// Read an escaped string value until a delimiter is encountered.
     fn read_escaped_value(&mut self, delimiters: &[char]) -> Result<String> {
         let mut result = String::new();
@@ -138,11 +237,38 @@ impl<'a> Tokenizer<'a> {
                     Some('#') => result.push('#'),
                     Some('$') => result.push('$'),
                     Some(':') => result.push(':'),
+                    Some('^') => result.push('^'),
+                    Some('@') => result.push('@'),
+                    Some(',') => result.push(','),
+                    Some('(') => result.push('('),
+                    Some(')') => result.push(')'),
+                    Some('%') => result.push('%'),
+                    Some('!') => result.push('!'),
+                    Some('&') => result.push('&'),
+                    Some('{') => result.push('{'),
+                    Some('}') => result.push('}'),
+                    Some(';') => result.push(';'),
                     Some('\\') => result.push('\\'),
                     Some('n') => result.push('\n'),
                     Some('t') => result.push('\t'),
                     Some('r') => result.push('\r'),
                     Some(' ') => result.push(' '),
+                    Some('x') => {
+                        let (high, low) = (self.next_char(), self.next_char());
+                        match high.zip(low).and_then(|(high, low)| decode_hex_escape(high, low)) {
+                            Some(c) => result.push(c),
+                            None => {
+                                let digits: String = high.into_iter().chain(low).collect();
+                                return Err(AlsError::AlsSyntaxError {
+                                    position: self.position,
+                                    message: format!(
+                                        "Unknown escape sequence: \\x{}, expected two hex digits of an ASCII character",
+                                        digits
+                                    ),
+                                });
+                            }
+                        }
+                    }
                     Some('0') => {
                         // Null token - return special marker
                         return Ok("\0".to_string());
@@ -244,26 +370,56 @@ impl<'a> Tokenizer<'a> {
             }
         }
 
+        // A number running into other text (e.g. `2023-01-01`, `12abc`) is a
+        // raw value; `^` still introduces the deltas of a delta operator,
+        // `!` the exclusions of a sparse range and `!=` the overrides of a
+        // patched range
+        if let Some(c) = self.peek_char() {
+            if c != '^'
+                && !self.at_exclusion_list()
+                && !self.at_override_list()
+                && !RAW_VALUE_DELIMITERS.contains(&c)
+            {
+                num_str.push_str(&self.read_escaped_value(RAW_VALUE_DELIMITERS)?);
+                return Ok(Token::RawValue(num_str));
+            }
+        }
+
+        // A lone `-` is a raw value, such as a placeholder for a missing
+        // value, and so is an integer written with a `+` that would be lost
+        if num_str == "-" || (num_str.starts_with('+') && !has_dot && !has_exp) {
+            return Ok(Token::RawValue(num_str));
+        }
+
         if has_dot || has_exp {
-            num_str
-                .parse::<f64>()
-                .map(Token::Float)
-                .map_err(|_| AlsError::AlsSyntaxError {
-                    position: start_pos,
-                    message: format!("Invalid float: {}", num_str),
-                })
+            let value = num_str.parse::<f64>().map_err(|_| AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: format!("Invalid float: {}", num_str),
+            })?;
+            // Keep the text of values like `19.90` or `1e-9` that would not
+            // survive formatting the float
+            if value.to_string() != num_str {
+                Ok(Token::Decimal(value, num_str))
+            } else {
+                Ok(Token::Float(value))
+            }
         } else {
-            num_str
-                .parse::<i64>()
-                .map(Token::Integer)
-                .map_err(|_| AlsError::AlsSyntaxError {
-                    position: start_pos,
-                    message: format!("Invalid integer: {}", num_str),
-                })
+            let value = num_str.parse::<i64>().map_err(|_| AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: format!("Invalid integer: {}", num_str),
+            })?;
+            // Likewise keep zero-padded text like `007` or `-0` as written
+            if value.to_string() != num_str {
+                Ok(Token::RawValue(num_str))
+            } else {
+                Ok(Token::Integer(value))
+            }
         }
     }
 
-    /// Parse a version prefix (!v1 or !ctx).
+    /// Parse a version prefix (!v1 or !ctx), a sentinel token directive
+    /// (!null:token or !empty:token), a column order directive
+    /// (!order:2,0,1) or a row repeat directive (!rows:3*5,8*2).
     fn parse_version(&mut self) -> Result<Token> {
         let start_pos = self.position;
         
@@ -278,7 +434,68 @@ impl<'a> Tokenizer<'a> {
             }
         }
 
-        if version_str == "ctx" {
+        if matches!(version_str.as_str(), "null" | "empty") && self.peek_char() == Some(':') {
+            self.next_char(); // consume ':'
+            let token = self.read_escaped_value(&['\n', '\r'])?;
+            if version_str == "null" {
+                Ok(Token::NullDirective(token))
+            } else {
+                Ok(Token::EmptyDirective(token))
+            }
+        } else if version_str == "order" && self.peek_char() == Some(':') {
+            self.next_char(); // consume ':'
+            let mut list = String::new();
+            while let Some(c) = self.peek_char().filter(|c| !matches!(c, '\n' | '\r')) {
+                list.push(c);
+                self.next_char();
+            }
+            list.split(',')
+                .map(|index| index.trim().parse::<usize>())
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map(Token::OrderDirective)
+                .map_err(|_| AlsError::AlsSyntaxError {
+                    position: start_pos,
+                    message: format!("Invalid column order directive: !order:{}", list),
+                })
+        } else if version_str == "rows" && self.peek_char() == Some(':') {
+            self.next_char(); // consume ':'
+            let mut list = String::new();
+            while let Some(c) = self.peek_char().filter(|c| !matches!(c, '\n' | '\r')) {
+                list.push(c);
+                self.next_char();
+            }
+            list.split(',')
+                .map(|repeat| {
+                    let (row, count) = repeat.split_once('*')?;
+                    Some((row.trim().parse::<usize>().ok()?, count.trim().parse::<usize>().ok()?))
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(Token::RowsDirective)
+                .ok_or_else(|| AlsError::AlsSyntaxError {
+                    position: start_pos,
+                    message: format!("Invalid row repeat directive: !rows:{}", list),
+                })
+        } else if version_str == "dicts" && self.peek_char() == Some(':') {
+            self.next_char(); // consume ':'
+            let mut list = String::new();
+            while let Some(c) = self.peek_char().filter(|c| !matches!(c, '\n' | '\r')) {
+                list.push(c);
+                self.next_char();
+            }
+            list.split(',')
+                .map(|assignment| {
+                    let (column, name) = assignment.split_once('=')?;
+                    let name = name.trim();
+                    let valid = !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+                    Some((column.trim().parse::<usize>().ok()?, name.to_string())).filter(|_| valid)
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(Token::DictsDirective)
+                .ok_or_else(|| AlsError::AlsSyntaxError {
+                    position: start_pos,
+                    message: format!("Invalid column dictionary directive: !dicts:{}", list),
+                })
+        } else if version_str == "ctx" {
             Ok(Token::Version(VersionType::Ctx))
         } else if version_str.starts_with('v') {
             let version_num = version_str[1..]
@@ -312,7 +529,7 @@ impl<'a> Tokenizer<'a> {
         // Read values separated by |
         let mut values = Vec::new();
         loop {
-            let value = self.read_escaped_value(&['|', '\n', '\r'])?;
+            let value = self.read_dict_value()?;
             values.push(value);
 
             if self.peek_char() == Some('|') {
@@ -325,16 +542,83 @@ impl<'a> Tokenizer<'a> {
         Ok(Token::DictionaryHeader { name, values })
     }
 
+    /// Read a single dictionary value up to the next unescaped `|` or newline.
+    ///
+    /// Dictionary values only use the escapes written by the serializer
+    /// (`\|`, `\\`, `\n`, `\r`), so any other escape is reported as
+    /// [`AlsError::InvalidEscape`] instead of being guessed at.
+    fn read_dict_value(&mut self) -> Result<String> {
+        let mut result = String::new();
+
+        while let Some(c) = self.peek_char() {
+            if matches!(c, '|' | '\n' | '\r') {
+                break;
+            }
+
+            let escape_pos = self.position;
+            self.next_char();
+            if c != '\\' {
+                result.push(c);
+                continue;
+            }
+
+            match self.next_char() {
+                Some('|') => result.push('|'),
+                Some('\\') => result.push('\\'),
+                Some('n') => result.push('\n'),
+                Some('r') => result.push('\r'),
+                other => {
+                    return Err(AlsError::InvalidEscape {
+                        position: escape_pos,
+                        sequence: other.map_or_else(|| "\\".to_string(), |c| format!("\\{}", c)),
+                    });
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Parse a schema column (#column_name).
+    ///
+    /// A trailing `:name` naming a known [`NumberType`] becomes the column's
+    /// number type, and a `:name` naming a known [`ColumnEncoding`] before it
+    /// (or at the end) becomes the encoding hint. Any other `:suffix` stays
+    /// part of the column name, so documents written before annotations
+    /// existed still parse.
     fn parse_schema_column(&mut self) -> Result<Token> {
-        let name = self.read_identifier();
-        if name.is_empty() {
-            // Read as escaped value if not a simple identifier
-            let value = self.read_escaped_value(&[' ', '\t', '\n', '\r', '|'])?;
-            Ok(Token::SchemaColumn(value))
-        } else {
-            Ok(Token::SchemaColumn(name))
+        const DELIMITERS: [char; 6] = [' ', '\t', '\n', '\r', '|', ':'];
+
+        let mut name = self.read_identifier();
+        let mut suffixes = Vec::new();
+        loop {
+            match self.peek_char() {
+                Some(':') => {
+                    self.next_char();
+                    suffixes.push(self.read_escaped_value(&DELIMITERS)?);
+                }
+                // Continue as an escaped value if the identifier is followed by
+                // escapes or other characters (e.g. `#first\ name`)
+                Some(c) if !DELIMITERS.contains(&c) => {
+                    name.push_str(&self.read_escaped_value(&DELIMITERS)?);
+                }
+                _ => break,
+            }
+        }
+
+        let number_type = suffixes.last().and_then(|suffix| NumberType::from_name(suffix));
+        if number_type.is_some() {
+            suffixes.pop();
+        }
+        let hint = suffixes.last().and_then(|suffix| ColumnEncoding::from_name(suffix));
+        if hint.is_some() {
+            suffixes.pop();
         }
+        for suffix in suffixes {
+            name.push(':');
+            name.push_str(&suffix);
+        }
+        Ok(Token::SchemaColumn(name, hint, number_type))
     }
 
     /// Parse a dictionary reference (_0, _1, etc.).
@@ -365,6 +649,306 @@ impl<'a> Tokenizer<'a> {
             })
     }
 
+    /// Parse the deltas of a delta operator (d1,-2,3 after the `^`).
+    fn parse_delta_op(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+
+        if self.peek_char() != Some('d') {
+            return Err(AlsError::AlsSyntaxError {
+                position: self.position,
+                message: "Expected 'd' or '*' after '^'".to_string(),
+            });
+        }
+        self.next_char(); // consume 'd'
+
+        let mut deltas = Vec::new();
+        loop {
+            let mut num_str = String::new();
+            if self.peek_char() == Some('-') {
+                num_str.push('-');
+                self.next_char();
+            }
+            while let Some(c) = self.peek_char() {
+                if c.is_ascii_digit() {
+                    num_str.push(c);
+                    self.next_char();
+                } else {
+                    break;
+                }
+            }
+
+            if num_str.is_empty() && deltas.is_empty() {
+                // `^d` with no deltas
+                break;
+            }
+
+            let delta = num_str.parse::<i64>().map_err(|_| AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: format!("Invalid delta: {}", num_str),
+            })?;
+            deltas.push(delta);
+
+            if self.peek_char() == Some(',') {
+                self.next_char(); // consume ','
+            } else {
+                break;
+            }
+        }
+
+        Ok(Token::DeltaOp(deltas))
+    }
+
+    /// Check whether the input continues with a sparse range exclusion list:
+    /// a `!` followed by a digit or minus sign.
+    fn at_exclusion_list(&self) -> bool {
+        let mut rest = self.input[self.position..].chars();
+        rest.next() == Some('!') && matches!(rest.next(), Some('-' | '0'..='9'))
+    }
+
+    /// Check whether the input continues with a patched range override
+    /// list: `!=` followed by a digit.
+    fn at_override_list(&self) -> bool {
+        let mut rest = self.input[self.position..].chars();
+        rest.next() == Some('!') && rest.next() == Some('=') && rest.next().is_some_and(|c| c.is_ascii_digit())
+    }
+
+    /// Parse the overrides of a patched range (the `2:90,7:-1` after the `!=`).
+    fn parse_override_op(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+        let invalid = |text: String| AlsError::AlsSyntaxError {
+            position: start_pos,
+            message: format!("Invalid range override: {}", text),
+        };
+
+        let mut overrides = Vec::new();
+        loop {
+            let mut position_str = String::new();
+            while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+                position_str.push(c);
+                self.next_char();
+            }
+            if self.peek_char() != Some(':') {
+                return Err(invalid(position_str));
+            }
+            self.next_char(); // consume ':'
+
+            let mut value_str = String::new();
+            if self.peek_char() == Some('-') {
+                value_str.push('-');
+                self.next_char();
+            }
+            while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+                value_str.push(c);
+                self.next_char();
+            }
+            let text = format!("{}:{}", position_str, value_str);
+            let position = position_str.parse::<usize>().map_err(|_| invalid(text.clone()))?;
+            let value = value_str.parse::<i64>().map_err(|_| invalid(text))?;
+            overrides.push((position, value));
+
+            if self.peek_char() == Some(',') {
+                self.next_char(); // consume ','
+            } else {
+                break;
+            }
+        }
+
+        Ok(Token::OverrideOp(overrides))
+    }
+
+    /// Parse the values excluded from a sparse range (the `4,9` after the `!`).
+    fn parse_exclude_op(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+
+        let mut excluded = Vec::new();
+        loop {
+            let mut num_str = String::new();
+            if self.peek_char() == Some('-') {
+                num_str.push('-');
+                self.next_char();
+            }
+            while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+                num_str.push(c);
+                self.next_char();
+            }
+            let value = num_str.parse::<i64>().map_err(|_| AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: format!("Invalid excluded value: {}", num_str),
+            })?;
+            excluded.push(value);
+
+            if self.peek_char() == Some(',') {
+                self.next_char(); // consume ','
+            } else {
+                break;
+            }
+        }
+
+        Ok(Token::ExcludeOp(excluded))
+    }
+
+    /// Parse the ratio and count of a geometric operator (2#5 after the `^*`).
+    fn parse_geometric_op(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+
+        let mut ratio_str = String::new();
+        if self.peek_char() == Some('-') {
+            ratio_str.push('-');
+            self.next_char();
+        }
+        while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+            ratio_str.push(c);
+            self.next_char();
+        }
+        let ratio = ratio_str.parse::<i64>().map_err(|_| AlsError::AlsSyntaxError {
+            position: start_pos,
+            message: format!("Invalid geometric ratio: {}", ratio_str),
+        })?;
+
+        if self.peek_char() != Some('#') {
+            return Err(AlsError::AlsSyntaxError {
+                position: self.position,
+                message: "Expected '#' after geometric ratio".to_string(),
+            });
+        }
+        self.next_char(); // consume '#'
+
+        let mut count_str = String::new();
+        while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+            count_str.push(c);
+            self.next_char();
+        }
+        let count = count_str.parse::<usize>().map_err(|_| AlsError::AlsSyntaxError {
+            position: start_pos,
+            message: format!("Invalid geometric count: {}", count_str),
+        })?;
+
+        Ok(Token::GeometricOp { ratio, count })
+    }
+
+    /// Parse a timestamp format tag (the `T0Z` after the `@`).
+    ///
+    /// The tag runs up to the opening parenthesis of the wrapped operator.
+    fn parse_timestamp_tag(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+        let mut tag = String::new();
+
+        while let Some(c) = self.peek_char() {
+            if c == '(' || c == '|' || c.is_whitespace() {
+                break;
+            }
+            tag.push(c);
+            self.next_char();
+        }
+
+        if tag.is_empty() || self.peek_char() != Some('(') {
+            return Err(AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: format!("Expected '(' after timestamp tag '@{}'", tag),
+            });
+        }
+
+        Ok(Token::TimestampTag(tag))
+    }
+
+    /// Parse an affix tag (the `prefix:suffix}` after the `{`).
+    ///
+    /// The suffix and its colon are optional. The tag must be followed by
+    /// the opening parenthesis of the list of middles.
+    fn parse_affix_tag(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+        let prefix = self.read_escaped_value(AFFIX_DELIMITERS)?;
+        let suffix = if self.peek_char() == Some(':') {
+            self.next_char();
+            self.read_escaped_value(AFFIX_DELIMITERS)?
+        } else {
+            String::new()
+        };
+
+        if self.peek_char() != Some('}') {
+            return Err(AlsError::AlsSyntaxError {
+                position: self.position,
+                message: "Expected '}' to close affix".to_string(),
+            });
+        }
+        self.next_char();
+
+        if self.peek_char() != Some('(') {
+            return Err(AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: "Expected '(' after affix".to_string(),
+            });
+        }
+
+        Ok(Token::AffixTag { prefix, suffix })
+    }
+
+    /// Parse an integer dictionary (the `200,404#4:G` after the `%`).
+    fn parse_int_dict(&mut self) -> Result<Token> {
+        let start_pos = self.position;
+
+        let mut values = Vec::new();
+        loop {
+            let mut num_str = String::new();
+            if self.peek_char() == Some('-') {
+                num_str.push('-');
+                self.next_char();
+            }
+            while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+                num_str.push(c);
+                self.next_char();
+            }
+            let value = num_str.parse::<i64>().map_err(|_| AlsError::AlsSyntaxError {
+                position: start_pos,
+                message: format!("Invalid integer dictionary value: {}", num_str),
+            })?;
+            values.push(value);
+
+            if self.peek_char() == Some(',') {
+                self.next_char(); // consume ','
+            } else {
+                break;
+            }
+        }
+
+        if self.peek_char() != Some('#') {
+            return Err(AlsError::AlsSyntaxError {
+                position: self.position,
+                message: "Expected '#' after integer dictionary values".to_string(),
+            });
+        }
+        self.next_char(); // consume '#'
+
+        let mut count_str = String::new();
+        while let Some(c) = self.peek_char().filter(char::is_ascii_digit) {
+            count_str.push(c);
+            self.next_char();
+        }
+        let count = count_str.parse::<usize>().map_err(|_| AlsError::AlsSyntaxError {
+            position: start_pos,
+            message: format!("Invalid integer dictionary count: {}", count_str),
+        })?;
+
+        if self.peek_char() != Some(':') {
+            return Err(AlsError::AlsSyntaxError {
+                position: self.position,
+                message: "Expected ':' after integer dictionary count".to_string(),
+            });
+        }
+        self.next_char(); // consume ':'
+
+        let mut packed = String::new();
+        while let Some(c) = self
+            .peek_char()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '+' || *c == '/')
+        {
+            packed.push(c);
+            self.next_char();
+        }
+
+        Ok(Token::IntDict { values, count, packed })
+    }
+
     /// Get the next token from the input.
     pub fn next_token(&mut self) -> Result<Token> {
         self.skip_whitespace();
@@ -375,6 +959,26 @@ impl<'a> Tokenizer<'a> {
         };
 
         match c {
+            ';' if self.at_comment() => {
+                let start = self.position;
+                let end = self.input[start..]
+                    .find('\n')
+                    .map_or(self.input.len(), |offset| start + offset);
+                while self.peek_char().is_some_and(|c| c != '\n') {
+                    self.next_char();
+                }
+                let text = &self.input[start + COMMENT_MARKER.len()..end];
+                Ok(Token::Comment(text.trim().to_string()))
+            }
+            '!' if self.at_exclusion_list() => {
+                self.next_char();
+                self.parse_exclude_op()
+            }
+            '!' if self.at_override_list() => {
+                self.next_char();
+                self.next_char();
+                self.parse_override_op()
+            }
             '!' => {
                 self.next_char();
                 self.parse_version()
@@ -403,6 +1007,27 @@ impl<'a> Tokenizer<'a> {
                 self.next_char();
                 Ok(Token::ToggleOp)
             }
+            '^' => {
+                self.next_char();
+                if self.peek_char() == Some('*') {
+                    self.next_char();
+                    self.parse_geometric_op()
+                } else {
+                    self.parse_delta_op()
+                }
+            }
+            '@' => {
+                self.next_char();
+                self.parse_timestamp_tag()
+            }
+            '%' => {
+                self.next_char();
+                self.parse_int_dict()
+            }
+            '{' => {
+                self.next_char();
+                self.parse_affix_tag()
+            }
             '|' => {
                 self.next_char();
                 self.in_header = false; // After first |, we're in streams
@@ -412,6 +1037,14 @@ impl<'a> Tokenizer<'a> {
                 self.next_char();
                 Ok(Token::StepSeparator)
             }
+            ',' => {
+                self.next_char();
+                Ok(Token::RunSeparator)
+            }
+            '&' => {
+                self.next_char();
+                Ok(Token::InterleaveSeparator)
+            }
             '(' => {
                 self.next_char();
                 Ok(Token::OpenParen)
@@ -424,13 +1057,17 @@ impl<'a> Tokenizer<'a> {
                 self.next_char();
                 Ok(Token::Newline)
             }
-            '-' | '0'..='9' => {
+            '+' if !self.input[self.position + 1..].starts_with(|d: char| d.is_ascii_digit()) => {
+                let value = self.read_escaped_value(RAW_VALUE_DELIMITERS)?;
+                Ok(Token::RawValue(value))
+            }
+            '-' | '+' | '0'..='9' => {
                 self.next_char();
                 self.read_number(c)
             }
             _ => {
                 // Read as raw value
-                let value = self.read_escaped_value(&[' ', '\t', '\n', '\r', '|', '>', '*', '~', ':', '(', ')'])?;
+                let value = self.read_escaped_value(RAW_VALUE_DELIMITERS)?;
                 if value.is_empty() {
                     // Skip and try again
                     self.next_char();
@@ -462,6 +1099,56 @@ impl<'a> Tokenizer<'a> {
         Ok(token)
     }
 
+    /// Skip the remainder of a malformed stream element.
+    ///
+    /// Consumes characters up to, but not including, the next unescaped
+    /// whitespace, newline, or column separator, and returns the source text
+    /// from `start` to the new position with surrounding whitespace trimmed.
+    /// Used by lenient parsing to resynchronize after a syntax error.
+    pub fn skip_element(&mut self, start: usize) -> &'a str {
+        while let Some(c) = self.peek_char() {
+            match c {
+                ' ' | '\t' | '\r' | '\n' | '|' => break,
+                '\\' => {
+                    self.next_char();
+                    self.next_char();
+                }
+                _ => {
+                    self.next_char();
+                }
+            }
+        }
+
+        let start = start.min(self.position);
+        self.input[start..self.position].trim()
+    }
+
+    /// Iterate over the remaining tokens with their source spans.
+    ///
+    /// Intended for tooling such as syntax highlighters and linters. The
+    /// iterator ends at the end of input without yielding [`Token::Eof`],
+    /// and ends after yielding the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{Token, Tokenizer};
+    ///
+    /// let mut tokenizer = Tokenizer::new("#id\n1>3");
+    /// let spans: Vec<(usize, usize)> = tokenizer
+    ///     .tokens()
+    ///     .map(|token| token.map(|t| (t.start, t.end)))
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(spans, vec![(0, 3), (3, 4), (4, 5), (5, 6), (6, 7)]);
+    /// ```
+    pub fn tokens(&mut self) -> Tokens<'_, 'a> {
+        Tokens {
+            tokenizer: self,
+            done: false,
+        }
+    }
+
     /// Tokenize the entire input and return all tokens.
     pub fn tokenize_all(&mut self) -> Result<Vec<Token>> {
         let mut tokens = Vec::new();
@@ -477,6 +1164,46 @@ impl<'a> Tokenizer<'a> {
     }
 }
 
+/// Iterator over the tokens of a [`Tokenizer`] and their source spans.
+///
+/// Created by [`Tokenizer::tokens`].
+pub struct Tokens<'t, 'a> {
+    tokenizer: &'t mut Tokenizer<'a>,
+    /// Set once the end of input or an error has been reached
+    done: bool,
+}
+
+impl Iterator for Tokens<'_, '_> {
+    type Item = Result<SpannedToken>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        self.tokenizer.skip_whitespace();
+        let start = self.tokenizer.position;
+        match self.tokenizer.next_token() {
+            Ok(Token::Eof) => {
+                self.done = true;
+                None
+            }
+            Ok(token) => Some(Ok(SpannedToken {
+                token,
+                start,
+                end: self.tokenizer.position,
+            })),
+            Err(e) => {
+                // Errors do not always consume input, so stop here
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl std::iter::FusedIterator for Tokens<'_, '_> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -506,12 +1233,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tokenize_dictionary_escaped_values() {
+        let mut tokenizer = Tokenizer::new(r"$default:a\|b|back\\slash|line\nbreak|cr\r||end\\");
+        let token = tokenizer.next_token().unwrap();
+        assert_eq!(
+            token,
+            Token::DictionaryHeader {
+                name: "default".to_string(),
+                values: vec![
+                    "a|b".to_string(),
+                    "back\\slash".to_string(),
+                    "line\nbreak".to_string(),
+                    "cr\r".to_string(),
+                    String::new(),
+                    "end\\".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_tokenize_dictionary_invalid_escape() {
+        let mut tokenizer = Tokenizer::new(r"$default:ok|bad\x");
+        match tokenizer.next_token() {
+            Err(AlsError::InvalidEscape { position, sequence }) => {
+                assert_eq!(position, 15);
+                assert_eq!(sequence, r"\x");
+            }
+            other => panic!("Expected InvalidEscape, got {:?}", other),
+        }
+
+        // Operator escapes from streams are not valid in dictionary values
+        let mut tokenizer = Tokenizer::new(r"$default:a\*b");
+        assert!(matches!(
+            tokenizer.next_token(),
+            Err(AlsError::InvalidEscape { position: 10, .. })
+        ));
+    }
+
+    #[test]
+    fn test_tokenize_dictionary_trailing_backslash() {
+        let mut tokenizer = Tokenizer::new("$default:value\\");
+        match tokenizer.next_token() {
+            Err(AlsError::InvalidEscape { position, sequence }) => {
+                assert_eq!(position, 14);
+                assert_eq!(sequence, "\\");
+            }
+            other => panic!("Expected InvalidEscape, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_tokenize_schema_column() {
         let mut tokenizer = Tokenizer::new("#name #age #city");
-        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("name".to_string()));
-        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("age".to_string()));
-        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("city".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("name".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("age".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("city".to_string(), None, None));
+    }
+
+    #[test]
+    fn test_tokenize_schema_with_escaped_spaces() {
+        let mut tokenizer = Tokenizer::new("#first\\ name #\\#id #a\\tb");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("first name".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("#id".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("a\tb".to_string(), None, None));
+    }
+
+    #[test]
+    fn test_tokenize_schema_with_encoding_hints() {
+        let mut tokenizer = Tokenizer::new("#id:range #status:dict #name");
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::SchemaColumn("id".to_string(), Some(ColumnEncoding::Range), None)
+        );
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::SchemaColumn("status".to_string(), Some(ColumnEncoding::Dict), None)
+        );
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("name".to_string(), None, None));
+    }
+
+    #[test]
+    fn test_tokenize_schema_unknown_suffix_stays_in_name() {
+        let mut tokenizer = Tokenizer::new("#time:utc #a\\:range #b:range:x");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("time:utc".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("a:range".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("b:range:x".to_string(), None, None));
+    }
+
+    #[test]
+    fn test_tokenize_schema_with_number_types() {
+        let mut tokenizer = Tokenizer::new("#price:float #id:range:int #b:int:range #c:x:float");
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::SchemaColumn("price".to_string(), None, Some(NumberType::Float))
+        );
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::SchemaColumn("id".to_string(), Some(ColumnEncoding::Range), Some(NumberType::Integer))
+        );
+        // The number type must come last
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::SchemaColumn("b:int".to_string(), Some(ColumnEncoding::Range), None)
+        );
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::SchemaColumn("c:x".to_string(), None, Some(NumberType::Float))
+        );
     }
 
     #[test]
@@ -548,8 +1378,56 @@ mod tests {
         let mut tokenizer = Tokenizer::new("3.14 -2.5 1e10 2.5e-3");
         assert_eq!(tokenizer.next_token().unwrap(), Token::Float(3.14));
         assert_eq!(tokenizer.next_token().unwrap(), Token::Float(-2.5));
-        assert_eq!(tokenizer.next_token().unwrap(), Token::Float(1e10));
-        assert_eq!(tokenizer.next_token().unwrap(), Token::Float(2.5e-3));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(1e10, "1e10".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(2.5e-3, "2.5e-3".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_exponents_and_signs() {
+        let mut tokenizer = Tokenizer::new("1e-9 2.5E10 -3e+2 +1.5 +7 + +x 1e 1e5>2e5");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(1e-9, "1e-9".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(2.5e10, "2.5E10".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(-300.0, "-3e+2".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(1.5, "+1.5".to_string()));
+        // A `+` integer and a lone or non-numeric `+` stay raw text
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("+7".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("+".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("+x".to_string()));
+        // An `e` without exponent digits is not an exponent
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("1e".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(1e5, "1e5".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(2e5, "2e5".to_string()));
+    }
+
+    #[test]
+    fn test_decimal_literals_keep_text() {
+        let mut tokenizer = Tokenizer::new("19.90 0.10 19.99 1.50>2.50");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(19.9, "19.90".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(0.1, "0.10".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Float(19.99));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(1.5, "1.50".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Decimal(2.5, "2.50".to_string()));
+    }
+
+    #[test]
+    fn test_zero_padded_integers_keep_text() {
+        let mut tokenizer = Tokenizer::new("007 -0 0 10");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("007".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("-0".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(0));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(10));
+    }
+
+    #[test]
+    fn test_skip_element_stops_at_boundary() {
+        let mut tokenizer = Tokenizer::new("1>x\\ y*2 next|B");
+        tokenizer.next_token().unwrap(); // 1
+        tokenizer.next_token().unwrap(); // >
+        assert_eq!(tokenizer.skip_element(0), "1>x\\ y*2");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("next".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::ColumnSeparator);
     }
 
     #[test]
@@ -566,6 +1444,65 @@ mod tests {
         assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("a*b".to_string()));
     }
 
+    #[test]
+    fn test_tokenize_escaped_pipes_do_not_split_columns() {
+        let mut tokenizer = Tokenizer::new("a\\|b 12\\|34 \\||x\\|");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("a|b".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("12|34".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("|".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::ColumnSeparator);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("x|".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Eof);
+    }
+
+    #[test]
+    fn test_tokenize_hex_escapes() {
+        let mut tokenizer = Tokenizer::new("a\\x00b \\x7F 日本\\x09語");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("a\0b".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("\u{7f}".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("日本\t語".to_string()));
+
+        for input in ["a\\xZZ", "a\\x8", "a\\xFF"] {
+            assert!(Tokenizer::new(input).next_token().is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_tokenize_escaped_parentheses() {
+        let mut tokenizer = Tokenizer::new("sshd\\(pam\\) x");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("sshd(pam)".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("x".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_int_dict() {
+        let mut tokenizer = Tokenizer::new("%-1,200,404#3:k x");
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::IntDict {
+                values: vec![-1, 200, 404],
+                count: 3,
+                packed: "k".to_string(),
+            }
+        );
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("x".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_int_dict_missing_count() {
+        let mut tokenizer = Tokenizer::new("%200,404:G");
+        assert!(tokenizer.next_token().is_err());
+        let mut tokenizer = Tokenizer::new("%200#2G");
+        assert!(tokenizer.next_token().is_err());
+    }
+
+    #[test]
+    fn test_tokenize_escaped_percent() {
+        let mut tokenizer = Tokenizer::new("\\%50 50\\%");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("%50".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("50%".to_string()));
+    }
+
     #[test]
     fn test_tokenize_range_expression() {
         let mut tokenizer = Tokenizer::new("1>5");
@@ -602,6 +1539,188 @@ mod tests {
         assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(4));
     }
 
+    #[test]
+    fn test_tokenize_delta_expression() {
+        let mut tokenizer = Tokenizer::new("100^d0,-3,5 7");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(100));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::DeltaOp(vec![0, -3, 5]));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(7));
+    }
+
+    #[test]
+    fn test_tokenize_delta_missing_d() {
+        let mut tokenizer = Tokenizer::new("100^1,2");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(100));
+        assert!(tokenizer.next_token().is_err());
+    }
+
+    #[test]
+    fn test_tokenize_geometric_expression() {
+        let mut tokenizer = Tokenizer::new("1^*2#5 3^*-10#4");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::GeometricOp { ratio: 2, count: 5 });
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(3));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::GeometricOp { ratio: -10, count: 4 });
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Eof);
+    }
+
+    #[test]
+    fn test_tokenize_geometric_missing_count() {
+        let mut tokenizer = Tokenizer::new("1^*2 5");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert!(tokenizer.next_token().is_err());
+
+        let mut tokenizer = Tokenizer::new("1^*2#");
+        tokenizer.next_token().unwrap();
+        assert!(tokenizer.next_token().is_err());
+    }
+
+    #[test]
+    fn test_tokenize_number_prefixed_raw_values() {
+        let mut tokenizer = Tokenizer::new("2023-01-01T00\\:00\\:00Z 12abc 1.2.3 12\\ 3 7^d1 -4>2");
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::RawValue("2023-01-01T00:00:00Z".to_string())
+        );
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("12abc".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("1.2.3".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("12 3".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(7));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::DeltaOp(vec![1]));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(-4));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(2));
+    }
+
+    #[test]
+    fn test_tokenize_run_list() {
+        let mut tokenizer = Tokenizer::new("(A*3,5*2,a\\,b*1)");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::OpenParen);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("A".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::MultiplyOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(3));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RunSeparator);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(5));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::MultiplyOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(2));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RunSeparator);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("a,b".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::MultiplyOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::CloseParen);
+    }
+
+    #[test]
+    fn test_tokenize_interleave() {
+        let mut tokenizer = Tokenizer::new("(1>3&A*3&R\\&D)");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::OpenParen);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(3));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::InterleaveSeparator);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("A".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::MultiplyOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(3));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::InterleaveSeparator);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("R&D".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::CloseParen);
+    }
+
+    #[test]
+    fn test_tokenize_affix() {
+        let mut tokenizer = Tokenizer::new("{http\\://a/:.h\\}tml}(x,1>2) {pre}(y)");
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::AffixTag {
+                prefix: "http://a/".to_string(),
+                suffix: ".h}tml".to_string(),
+            }
+        );
+        assert_eq!(tokenizer.next_token().unwrap(), Token::OpenParen);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("x".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RunSeparator);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(2));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::CloseParen);
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::AffixTag {
+                prefix: "pre".to_string(),
+                suffix: String::new(),
+            }
+        );
+
+        assert!(Tokenizer::new("{pre").next_token().is_err());
+        assert!(Tokenizer::new("{pre} (x)").next_token().is_err());
+    }
+
+    #[test]
+    fn test_tokenize_sparse_range() {
+        let mut tokenizer = Tokenizer::new("1>9!4,-2 a!b \\!v1");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(9));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::ExcludeOp(vec![4, -2]));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("a!b".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("!v1".to_string()));
+
+        let mut tokenizer = Tokenizer::new("!v1");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Version(VersionType::Als(1)));
+    }
+
+    #[test]
+    fn test_tokenize_patched_range() {
+        let mut tokenizer = Tokenizer::new("1>9!=2:90,7:-1 a!=b");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(9));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::OverrideOp(vec![(2, 90), (7, -1)]));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("a!=b".to_string()));
+
+        for input in ["1>9!=2", "1>9!=2:x", "1>9!=2:90,"] {
+            let mut tokenizer = Tokenizer::new(input);
+            let tokens: Result<Vec<_>> = (0..4).map(|_| tokenizer.next_token()).collect();
+            assert!(tokens.is_err(), "{input:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_tokenize_timestamp_tag() {
+        let mut tokenizer = Tokenizer::new("@T3+05:30(1>3) \\@home");
+        assert_eq!(
+            tokenizer.next_token().unwrap(),
+            Token::TimestampTag("T3+05:30".to_string())
+        );
+        assert_eq!(tokenizer.next_token().unwrap(), Token::OpenParen);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(3));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::CloseParen);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("@home".to_string()));
+    }
+
+    #[test]
+    fn test_tokenize_timestamp_tag_missing_paren() {
+        let mut tokenizer = Tokenizer::new("@T0Z 5");
+        assert!(tokenizer.next_token().is_err());
+    }
+
+    #[test]
+    fn test_tokenize_comment_lines() {
+        let mut tokenizer = Tokenizer::new(";; generated\n#a\n  ;;  note \nx;;y \\;;z");
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Comment("generated".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Newline);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("a".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Newline);
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Comment("note".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Newline);
+        // Only a `;;` that starts a line opens a comment
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue("x;;y".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::RawValue(";;z".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::Eof);
+    }
+
     #[test]
     fn test_tokenize_complete_document() {
         let input = "!v1\n$d:a|b\n#col1 #col2\n1>3|_0 _1";
@@ -611,8 +1730,8 @@ mod tests {
         assert_eq!(tokenizer.next_token().unwrap(), Token::Newline);
         assert!(matches!(tokenizer.next_token().unwrap(), Token::DictionaryHeader { .. }));
         assert_eq!(tokenizer.next_token().unwrap(), Token::Newline);
-        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("col1".to_string()));
-        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("col2".to_string()));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("col1".to_string(), None, None));
+        assert_eq!(tokenizer.next_token().unwrap(), Token::SchemaColumn("col2".to_string(), None, None));
         assert_eq!(tokenizer.next_token().unwrap(), Token::Newline);
         assert_eq!(tokenizer.next_token().unwrap(), Token::Integer(1));
         assert_eq!(tokenizer.next_token().unwrap(), Token::RangeOp);
@@ -642,4 +1761,72 @@ mod tests {
         let result = tokenizer.next_token();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_tokens_with_spans() {
+        let input = "!v1\n$default:a|b\n#id #name:dict\n1>5:2 _0*3|x~y*2 (1>2)*2";
+        let mut tokenizer = Tokenizer::new(input);
+        let tokens: Vec<SpannedToken> = tokenizer.tokens().collect::<Result<_>>().unwrap();
+
+        let kinds: Vec<&Token> = tokens.iter().map(|t| &t.token).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                &Token::Version(VersionType::Als(1)),
+                &Token::Newline,
+                &Token::DictionaryHeader {
+                    name: "default".to_string(),
+                    values: vec!["a".to_string(), "b".to_string()],
+                },
+                &Token::Newline,
+                &Token::SchemaColumn("id".to_string(), None, None),
+                &Token::SchemaColumn("name".to_string(), Some(ColumnEncoding::Dict), None),
+                &Token::Newline,
+                &Token::Integer(1),
+                &Token::RangeOp,
+                &Token::Integer(5),
+                &Token::StepSeparator,
+                &Token::Integer(2),
+                &Token::DictRef(0),
+                &Token::MultiplyOp,
+                &Token::Integer(3),
+                &Token::ColumnSeparator,
+                &Token::RawValue("x".to_string()),
+                &Token::ToggleOp,
+                &Token::RawValue("y".to_string()),
+                &Token::MultiplyOp,
+                &Token::Integer(2),
+                &Token::OpenParen,
+                &Token::Integer(1),
+                &Token::RangeOp,
+                &Token::Integer(2),
+                &Token::CloseParen,
+                &Token::MultiplyOp,
+                &Token::Integer(2),
+            ]
+        );
+
+        let texts: Vec<&str> = tokens.iter().map(|t| &input[t.start..t.end]).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "!v1", "\n", "$default:a|b", "\n", "#id", "#name:dict", "\n", "1", ">", "5", ":",
+                "2", "_0", "*", "3", "|", "x", "~", "y", "*", "2", "(", "1", ">", "2", ")", "*",
+                "2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_empty_and_error() {
+        let mut tokenizer = Tokenizer::new("   ");
+        assert!(tokenizer.tokens().next().is_none());
+
+        // The iterator stops after the invalid escape
+        let mut tokenizer = Tokenizer::new("#a\n1 x\\q 2");
+        let items: Vec<Result<SpannedToken>> = tokenizer.tokens().collect();
+        assert_eq!(items.len(), 4);
+        assert!(items[..3].iter().all(|item| item.is_ok()));
+        assert!(matches!(items[3], Err(AlsError::AlsSyntaxError { .. })));
+    }
 }
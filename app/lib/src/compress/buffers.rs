@@ -0,0 +1,132 @@
+//! Reusable scratch space for column compression.
+//!
+//! Pattern detection works on the string form of a column's values. Instead
+//! of allocating a fresh `Vec<String>` and `Vec<&str>` for every column,
+//! the compressor writes the values into a [`CompressionBuffers`] whose
+//! strings and vectors are cleared and refilled, keeping their capacity.
+
+use crate::als::SentinelTokens;
+use crate::convert::Column;
+
+/// Reusable buffers holding the string form of one column at a time.
+///
+/// [`AlsCompressor::compress`](crate::AlsCompressor::compress) uses one set
+/// of buffers for all columns of a dataset. Pass the same buffers to
+/// [`AlsCompressor::compress_with_buffers`](crate::AlsCompressor::compress_with_buffers)
+/// to also reuse the allocations across calls, e.g. when compressing many
+/// small datasets in a loop. The buffers hold no data between calls, so
+/// they never change the output.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::{AlsCompressor, Column, CompressionBuffers, TabularData, Value};
+/// use std::borrow::Cow;
+///
+/// let compressor = AlsCompressor::new();
+/// let mut buffers = CompressionBuffers::new();
+/// for batch in 0..3 {
+///     let mut data = TabularData::new();
+///     let ids: Vec<Value> = (batch * 100..batch * 100 + 100).map(Value::Integer).collect();
+///     data.add_column(Column::new(Cow::Borrowed("id"), ids));
+///     let doc = compressor.compress_with_buffers(&data, &mut buffers).unwrap();
+///     assert_eq!(doc, compressor.compress(&data).unwrap());
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct CompressionBuffers {
+    /// One string per value; only the first `column.len()` are in use.
+    strings: Vec<String>,
+    /// Empty vector kept for its allocation, see [`recycle`].
+    refs: Vec<&'static str>,
+}
+
+impl CompressionBuffers {
+    /// Create empty buffers; they grow to fit the widest column seen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write the string form of each value of `column` into the buffers and
+    /// call `f` with them.
+    pub(crate) fn with_values<R>(
+        &mut self,
+        column: &Column,
+        tokens: &SentinelTokens,
+        f: impl FnOnce(&[&str]) -> R,
+    ) -> R {
+        let len = column.values.len();
+        if self.strings.len() < len {
+            self.strings.resize_with(len, String::new);
+        }
+        for (slot, value) in self.strings.iter_mut().zip(&column.values) {
+            slot.clear();
+            value.write_string_repr_with(tokens, slot);
+        }
+
+        let mut refs = recycle(std::mem::take(&mut self.refs));
+        refs.extend(self.strings[..len].iter().map(String::as_str));
+        let result = f(&refs);
+        self.refs = recycle(refs);
+        result
+    }
+}
+
+/// Empty `refs` and return its allocation with a new lifetime.
+///
+/// Collecting an emptied vector through a no-op `map` reuses the source
+/// allocation, since both element types have the same layout.
+fn recycle<'b>(mut refs: Vec<&str>) -> Vec<&'b str> {
+    refs.clear();
+    refs.into_iter().map(|_| unreachable!("the vector is empty")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::Value;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_with_values_matches_string_repr() {
+        let tokens = SentinelTokens::DEFAULT;
+        let column = Column::new(
+            Cow::Borrowed("mixed"),
+            vec![
+                Value::Integer(-42),
+                Value::Float(1.5),
+                Value::Null,
+                Value::string(""),
+                Value::Boolean(true),
+                Value::string("text"),
+            ],
+        );
+        let expected: Vec<String> = column
+            .values
+            .iter()
+            .map(|value| value.to_string_repr_with(&tokens).into_owned())
+            .collect();
+
+        let mut buffers = CompressionBuffers::new();
+        let values = buffers.with_values(&column, &tokens, |values| values.to_vec().join("|"));
+        assert_eq!(values, expected.join("|"));
+    }
+
+    #[test]
+    fn test_with_values_reuses_allocations() {
+        let tokens = SentinelTokens::DEFAULT;
+        let wide = Column::new(Cow::Borrowed("a"), (0..100).map(Value::Integer).collect());
+        let narrow = Column::new(Cow::Borrowed("b"), vec![Value::string("x"), Value::string("y")]);
+
+        let mut buffers = CompressionBuffers::new();
+        assert_eq!(buffers.with_values(&wide, &tokens, |values| values.len()), 100);
+        let capacity = buffers.refs.capacity();
+        assert!(capacity >= 100);
+
+        // A shorter column sees only its own values, in the same buffers
+        let values = buffers.with_values(&narrow, &tokens, |values| values.join(","));
+        assert_eq!(values, "x,y");
+        assert_eq!(buffers.refs.capacity(), capacity);
+        assert_eq!(buffers.strings.len(), 100);
+    }
+}
@@ -4,22 +4,22 @@
 //! of tabular data to ALS format, including CTX fallback when ALS compression
 //! ratio is insufficient.
 
-use crate::als::{AlsDocument, AlsOperator, ColumnStream};
+use std::borrow::Cow;
+
+use crate::als::{AlsDocument, AlsOperator, ColumnEncoding, ColumnStream, NumberType, SentinelTokens};
 use crate::als::AlsSerializer;
 use crate::config::CompressorConfig;
-use crate::convert::{TabularData, Value};
+use crate::convert::{Column, ColumnType, TabularData, Value};
 use crate::error::{AlsError, Result};
-use crate::pattern::{PatternEngine, PatternType};
+use crate::pattern::{DetectionResult, PatternDetector, PatternEngine, PatternType};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use super::dictionary::DictionaryBuilder;
-use super::stats::{ColumnStats, CompressionReport, CompressionStats};
-
-/// Default threshold for parallel processing (number of columns * rows).
-/// Below this threshold, sequential processing is used to avoid parallel overhead.
-const PARALLEL_THRESHOLD: usize = 1000;
+use super::buffers::CompressionBuffers;
+use super::dictionary::{DictionaryBuilder, IntDictionaryEncoder};
+use super::reorder::similarity_order;
+use super::stats::{ColumnStats, CompressionReport, CompressionStats, ValueStats};
 
 /// Main entry point for ALS compression.
 ///
@@ -57,9 +57,11 @@ const PARALLEL_THRESHOLD: usize = 1000;
 /// # Parallel Processing
 ///
 /// When the `parallel` feature is enabled and the dataset is large enough
-/// (determined by `PARALLEL_THRESHOLD`), columns are compressed in parallel
-/// using Rayon's work-stealing scheduler. This provides significant speedup
-/// for multi-column datasets on multi-core systems.
+/// (determined by [`CompressorConfig::parallel_threshold`]), columns are
+/// compressed in parallel using Rayon's work-stealing scheduler. This
+/// provides significant speedup for multi-column datasets on multi-core
+/// systems. [`AlsCompressor::compress_sequential`] always stays on the
+/// calling thread.
 #[derive(Debug, Clone)]
 pub struct AlsCompressor {
     /// Compression configuration.
@@ -85,15 +87,100 @@ impl AlsCompressor {
         }
     }
 
+    /// Register a custom pattern detector for every column this compressor
+    /// analyzes.
+    ///
+    /// See [`PatternEngine::register`].
+    pub fn register_detector(&mut self, detector: impl PatternDetector + 'static) {
+        self.pattern_engine.register(detector);
+    }
+
     /// Get the current configuration.
     pub fn config(&self) -> &CompressorConfig {
         &self.config
     }
 
+    /// Parse CSV text into tabular data the way [`compress_csv`](Self::compress_csv) does.
+    ///
+    /// Applies [`CompressorConfig::csv_config`], the input limits and the
+    /// SIMD settings, so callers that compress the result with
+    /// [`compress_with_stats`](Self::compress_with_stats) see the same data
+    /// as a plain compress.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsCompressor;
+    ///
+    /// let compressor = AlsCompressor::new();
+    /// let data = compressor.parse_csv("id,name\n1,Alice\n2,Bob").unwrap();
+    /// assert_eq!(data.row_count, 2);
+    /// ```
+    pub fn parse_csv(&self, input: &str) -> Result<TabularData<'static>> {
+        use crate::convert::csv::parse_delimited_with_limits;
+        use crate::simd::SimdDispatcher;
+
+        parse_delimited_with_limits(
+            input,
+            &self.config.csv_config,
+            &self.config.input_limits(),
+            &SimdDispatcher::with_config(self.config.simd_config),
+        )
+    }
+
+    /// Parse TSV text into tabular data the way [`compress_tsv`](Self::compress_tsv) does.
+    pub fn parse_tsv(&self, input: &str) -> Result<TabularData<'static>> {
+        use crate::convert::tsv::parse_tsv_with_limits;
+        use crate::simd::SimdDispatcher;
+
+        parse_tsv_with_limits(
+            input,
+            &self.config.input_limits(),
+            &SimdDispatcher::with_config(self.config.simd_config),
+        )
+    }
+
+    /// Parse JSON text into tabular data the way [`compress_json`](Self::compress_json) does.
+    ///
+    /// Honors the input limits, [`CompressorConfig::case_insensitive_keys`]
+    /// and [`CompressorConfig::json_column_order`].
+    pub fn parse_json(&self, input: &str) -> Result<TabularData<'static>> {
+        use crate::convert::json::parse_json_with_options;
+
+        parse_json_with_options(
+            input,
+            &self.config.input_limits(),
+            self.config.case_insensitive_keys,
+            self.config.json_column_order,
+        )
+    }
+
+    /// Parse NDJSON text into tabular data the way [`compress_ndjson`](Self::compress_ndjson) does.
+    pub fn parse_ndjson(&self, input: &str) -> Result<TabularData<'static>> {
+        use crate::convert::json::parse_ndjson_with_options;
+
+        parse_ndjson_with_options(
+            input,
+            &self.config.input_limits(),
+            self.config.case_insensitive_keys,
+            self.config.json_column_order,
+        )
+    }
+
+    /// Serialize a document to ALS text with the configured checksum and
+    /// generation comment settings, as the `compress_*` methods do.
+    pub fn serialize(&self, doc: &AlsDocument) -> String {
+        AlsSerializer::new()
+            .with_checksum(self.config.checksum)
+            .with_generation_comment(self.config.generation_comment)
+            .serialize(doc)
+    }
+
     /// Compress CSV text to ALS format.
     ///
     /// This is a convenience method that parses CSV input, compresses it to ALS,
-    /// and serializes the result to a string.
+    /// and serializes the result to a string. The input is read with the
+    /// delimiter and quote character from [`CompressorConfig::csv_config`].
     ///
     /// # Arguments
     ///
@@ -113,18 +200,90 @@ impl AlsCompressor {
     /// let als = compressor.compress_csv(csv).unwrap();
     /// ```
     pub fn compress_csv(&self, input: &str) -> Result<String> {
-        use crate::convert::csv::parse_csv;
-        use crate::als::AlsSerializer;
+        // Parse CSV to TabularData
+        let data = self.parse_csv(input)?;
+
+        // Compress to ALS document
+        let doc = self.compress(&data)?;
+
+        // Serialize to string
+        Ok(self.serialize(&doc))
+    }
+
+    /// Compress CSV read from `reader` to ALS format.
+    ///
+    /// Produces the same output as [`compress_csv`](Self::compress_csv) on
+    /// the whole text, but parses record by record, so the input is never
+    /// held in memory as one string. The byte limit from
+    /// [`CompressorConfig::max_input_bytes`] is enforced while reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the CSV text, such as an open file
+    ///
+    /// # Returns
+    ///
+    /// A string containing the compressed ALS representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsCompressor;
+    /// use std::io::Cursor;
+    ///
+    /// let compressor = AlsCompressor::new();
+    /// let csv = "id,name\n1,Alice\n2,Bob\n3,Charlie";
+    /// let als = compressor.compress_csv_reader(Cursor::new(csv)).unwrap();
+    /// assert_eq!(als, compressor.compress_csv(csv).unwrap());
+    /// ```
+    pub fn compress_csv_reader(&self, reader: impl std::io::Read) -> Result<String> {
+        use crate::convert::csv::parse_delimited_reader_with_limits;
 
         // Parse CSV to TabularData
-        let data = parse_csv(input)?;
+        let data = parse_delimited_reader_with_limits(
+            reader,
+            &self.config.csv_config,
+            &self.config.input_limits(),
+        )?;
 
         // Compress to ALS document
         let doc = self.compress(&data)?;
 
         // Serialize to string
-        let serializer = AlsSerializer::new();
-        Ok(serializer.serialize(&doc))
+        Ok(self.serialize(&doc))
+    }
+
+    /// Compress TSV text to ALS format.
+    ///
+    /// This is a convenience method that parses tab-separated input, compresses
+    /// it to ALS, and serializes the result to a string.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - TSV text to compress
+    ///
+    /// # Returns
+    ///
+    /// A string containing the compressed ALS representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsCompressor;
+    ///
+    /// let compressor = AlsCompressor::new();
+    /// let tsv = "id\tname\n1\tAlice\n2\tBob\n3\tCharlie";
+    /// let als = compressor.compress_tsv(tsv).unwrap();
+    /// ```
+    pub fn compress_tsv(&self, input: &str) -> Result<String> {
+        // Parse TSV to TabularData
+        let data = self.parse_tsv(input)?;
+
+        // Compress to ALS document
+        let doc = self.compress(&data)?;
+
+        // Serialize to string
+        Ok(self.serialize(&doc))
     }
 
     /// Compress JSON text to ALS format.
@@ -150,18 +309,112 @@ impl AlsCompressor {
     /// let als = compressor.compress_json(json).unwrap();
     /// ```
     pub fn compress_json(&self, input: &str) -> Result<String> {
-        use crate::convert::json::parse_json;
-        use crate::als::AlsSerializer;
+        // Parse JSON to TabularData
+        let data = self.parse_json(input)?;
+
+        // Compress to ALS document
+        let doc = self.compress(&data)?;
+
+        // Serialize to string
+        Ok(self.serialize(&doc))
+    }
+
+    /// Compress JSON read from `reader` to ALS format.
+    ///
+    /// Produces the same output as [`compress_json`](Self::compress_json) on
+    /// the whole text, without first reading the input into a string. The
+    /// byte limit from [`CompressorConfig::max_input_bytes`] is enforced
+    /// while reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the JSON text (must be an array of objects)
+    ///
+    /// # Returns
+    ///
+    /// A string containing the compressed ALS representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsCompressor;
+    /// use std::io::Cursor;
+    ///
+    /// let compressor = AlsCompressor::new();
+    /// let json = r#"[{"id": 1, "name": "Alice"}, {"id": 2, "name": "Bob"}]"#;
+    /// let als = compressor.compress_json_reader(Cursor::new(json)).unwrap();
+    /// assert_eq!(als, compressor.compress_json(json).unwrap());
+    /// ```
+    pub fn compress_json_reader(&self, reader: impl std::io::Read) -> Result<String> {
+        use crate::convert::json::parse_json_reader_with_options;
 
         // Parse JSON to TabularData
-        let data = parse_json(input)?;
+        let data = parse_json_reader_with_options(
+            reader,
+            &self.config.input_limits(),
+            self.config.case_insensitive_keys,
+            self.config.json_column_order,
+        )?;
 
         // Compress to ALS document
         let doc = self.compress(&data)?;
 
         // Serialize to string
-        let serializer = AlsSerializer::new();
-        Ok(serializer.serialize(&doc))
+        Ok(self.serialize(&doc))
+    }
+
+    /// Compress newline-delimited JSON (NDJSON) to ALS format.
+    ///
+    /// This is a convenience method for log pipelines that emit one JSON object
+    /// per line. Objects may have different keys; missing fields become nulls.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - NDJSON text to compress (one object per line)
+    ///
+    /// # Returns
+    ///
+    /// A string containing the compressed ALS representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsCompressor;
+    ///
+    /// let compressor = AlsCompressor::new();
+    /// let ndjson = "{\"id\": 1, \"level\": \"info\"}\n{\"id\": 2, \"level\": \"warn\"}\n";
+    /// let als = compressor.compress_ndjson(ndjson).unwrap();
+    /// ```
+    pub fn compress_ndjson(&self, input: &str) -> Result<String> {
+        // Parse NDJSON to TabularData
+        let data = self.parse_ndjson(input)?;
+
+        // Compress to ALS document
+        let doc = self.compress(&data)?;
+
+        // Serialize to string
+        Ok(self.serialize(&doc))
+    }
+
+    /// Wrap serialized ALS text with [`CompressorConfig::post_codec`].
+    ///
+    /// The result starts with the codec's magic byte, which
+    /// [`AlsParser::parse_bytes`](crate::AlsParser::parse_bytes) detects on
+    /// read. Without a codec the text bytes are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{AlsCompressor, AlsParser};
+    ///
+    /// let compressor = AlsCompressor::new();
+    /// let als = compressor.compress_csv("id\n1\n2\n3").unwrap();
+    /// let bytes = compressor.apply_post_codec(&als).unwrap();
+    /// let doc = AlsParser::new().parse_bytes(&bytes).unwrap();
+    /// assert_eq!(doc.row_count(), 3);
+    /// ```
+    pub fn apply_post_codec(&self, als: &str) -> Result<Vec<u8>> {
+        self.config.post_codec.encode(als)
     }
 
     /// Compress tabular data to an ALS document.
@@ -181,13 +434,26 @@ impl AlsCompressor {
     ///
     /// An `AlsDocument` containing the compressed data.
     pub fn compress(&self, data: &TabularData) -> Result<AlsDocument> {
+        self.compress_with_buffers(data, &mut CompressionBuffers::new())
+    }
+
+    /// Compress tabular data, reusing `buffers` for pattern detection.
+    ///
+    /// Produces the same document as [`AlsCompressor::compress`]. Passing
+    /// the same buffers to repeated calls, for example when compressing many
+    /// small datasets in a loop, avoids allocating the string form of each
+    /// column again. Columns compressed in parallel use per-thread buffers.
+    pub fn compress_with_buffers(&self, data: &TabularData, buffers: &mut CompressionBuffers) -> Result<AlsDocument> {
         // Handle edge cases
         if data.is_empty() || data.column_count() == 0 {
             return Ok(self.create_empty_document(data));
         }
 
         // First, try ALS compression
-        let als_doc = self.compress_als(data)?;
+        let mut als_doc = self.compress_als(data, buffers)?;
+        if self.config.collapse_duplicate_rows {
+            als_doc = self.collapse_duplicate_rows(data, als_doc, buffers)?;
+        }
         
         // Calculate compression ratio
         let original_size = self.calculate_original_size(data);
@@ -199,31 +465,152 @@ impl AlsCompressor {
         };
 
         // Check if we should fall back to CTX
-        if compression_ratio < self.config.ctx_fallback_threshold {
-            Ok(self.compress_ctx(data))
+        let mut doc = if self.falls_back_to_ctx(compression_ratio) {
+            self.compress_ctx(data)
         } else {
-            Ok(als_doc)
+            als_doc
+        };
+
+        if self.config.column_reordering {
+            doc.column_order = similarity_order(data);
+        }
+        Ok(doc)
+    }
+
+    /// Re-compress an ALS document to its minimal encoding.
+    ///
+    /// Documents written by hand, by older versions, or recovered by the
+    /// lenient parser may use suboptimal encodings, such as raw values that
+    /// could be a range. This expands every column and runs pattern
+    /// detection again, so compressing a document's own output leaves it
+    /// unchanged.
+    ///
+    /// Column names and order are preserved, and values are kept exactly as
+    /// expanded (no numeric reformatting), along with any number type
+    /// annotations. The dictionary is rebuilt and kept
+    /// only if it still shrinks the output. Like [`AlsCompressor::compress`],
+    /// the result falls back to CTX when ALS does not meet the configured
+    /// ratio threshold.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a dictionary reference is out of bounds or the
+    /// columns expand to different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{AlsCompressor, AlsParser, AlsSerializer};
+    ///
+    /// let doc = AlsParser::new().parse("#id\n1 2 3 4 5 6 7 8 9 10").unwrap();
+    /// let canonical = AlsCompressor::new().canonicalize(&doc).unwrap();
+    /// assert_eq!(AlsSerializer::new().serialize(&canonical), "!v1\n#id\n1>10");
+    /// ```
+    pub fn canonicalize(&self, doc: &AlsDocument) -> Result<AlsDocument> {
+        if doc.schema.len() != doc.streams.len() {
+            return Err(AlsError::ColumnMismatch {
+                schema: doc.schema.len(),
+                data: doc.streams.len(),
+            });
+        }
+
+        let mut data = TabularData::with_capacity(doc.schema.len());
+        for (column, (name, stream)) in doc.schema.iter().zip(&doc.streams).enumerate() {
+            let values: Vec<Value<'static>> = doc
+                .expand_row_repeats(stream.expand(doc.column_dictionary(column))?)
+                .into_iter()
+                .map(|value| match doc.sentinel_tokens.decode_str(value) {
+                    Some(value) => Value::String(Cow::Owned(value)),
+                    None => Value::Null,
+                })
+                .collect();
+
+            if let Some(first) = data.columns.first() {
+                if first.values.len() != values.len() {
+                    return Err(AlsError::ColumnMismatch {
+                        schema: first.values.len(),
+                        data: values.len(),
+                    });
+                }
+            }
+            data.add_column(Column::new(Cow::Owned(name.clone()), values));
         }
+
+        let mut canonical = self.compress(&data)?;
+        canonical.number_types = doc.number_types.clone();
+        Ok(canonical)
     }
 
     /// Compress data using ALS format with pattern detection.
-    fn compress_als(&self, data: &TabularData) -> Result<AlsDocument> {
-        let mut doc = AlsDocument::with_schema(data.column_names().into_iter().map(String::from).collect());
+    fn compress_als(&self, data: &TabularData, buffers: &mut CompressionBuffers) -> Result<AlsDocument> {
+        let mut doc = self.new_document(data);
         doc.set_als_format();
 
         // Build dictionary for string values
         let dictionary = self.build_dictionary(data);
-        if !dictionary.is_empty() {
-            doc.add_dictionary("default", dictionary.clone());
-        }
 
         // Compress columns (parallel or sequential based on size and config)
-        let streams = self.compress_columns_internal(data, &dictionary)?;
+        let streams = self.compress_columns_internal(data, &dictionary, buffers)?;
+        self.finish_document(&mut doc, data, streams, &dictionary);
+        self.annotate_number_types(&mut doc, data);
+
+        Ok(doc)
+    }
+
+    /// Compress `data` with each run of identical rows stored once, and
+    /// keep the result if it is smaller than `doc`, the document compressed
+    /// from every row.
+    fn collapse_duplicate_rows(
+        &self,
+        data: &TabularData,
+        doc: AlsDocument,
+        buffers: &mut CompressionBuffers,
+    ) -> Result<AlsDocument> {
+        let (kept_rows, repeats) = duplicate_row_runs(data);
+        if repeats.is_empty() {
+            return Ok(doc);
+        }
+
+        let mut collapsed = TabularData::with_capacity(data.column_count());
+        for column in &data.columns {
+            let values = kept_rows.iter().map(|&row| column.values[row].clone()).collect();
+            collapsed.add_column(Column::with_type(column.name.clone(), values, column.inferred_type));
+        }
+        let mut collapsed_doc = self.compress_als(&collapsed, buffers)?;
+        collapsed_doc.row_repeats = repeats;
+
+        if self.calculate_compressed_size(&collapsed_doc) < self.calculate_compressed_size(&doc) {
+            Ok(collapsed_doc)
+        } else {
+            Ok(doc)
+        }
+    }
+
+    /// Add compressed column streams to a document along with the shared
+    /// dictionary, pruned to the entries the streams reference.
+    fn finish_document(
+        &self,
+        doc: &mut AlsDocument,
+        data: &TabularData,
+        columns: Vec<(ColumnStream, Option<ColumnEncoding>)>,
+        dictionary: &[String],
+    ) {
+        let mut streams = Vec::with_capacity(columns.len());
+        for (column, (stream, hint)) in data.columns.iter().zip(columns) {
+            if let Some(hint) = hint {
+                doc.set_encoding_hint(column.name.as_ref(), hint);
+            }
+            streams.push(stream);
+        }
+
+        let dictionary = self.share_dictionary(&mut streams, dictionary);
+        if !dictionary.is_empty() {
+            doc.add_dictionary("default", dictionary);
+        }
         for stream in streams {
             doc.add_stream(stream);
         }
-
-        Ok(doc)
+        self.split_dictionary(doc);
     }
 
     /// Determine if parallel processing should be used based on data size and config.
@@ -235,15 +622,19 @@ impl AlsCompressor {
 
         // Use parallel processing if data size exceeds threshold
         let data_size = data.column_count() * data.row_count;
-        data_size >= PARALLEL_THRESHOLD && data.column_count() > 1
+        data_size >= self.config.parallel_threshold && data.column_count() > 1
     }
 
     /// Compress columns using either parallel or sequential processing.
+    ///
+    /// `buffers` is used on the sequential path; parallel workers each use
+    /// their own.
     fn compress_columns_internal(
         &self,
         data: &TabularData,
         dictionary: &[String],
-    ) -> Result<Vec<ColumnStream>> {
+        buffers: &mut CompressionBuffers,
+    ) -> Result<Vec<(ColumnStream, Option<ColumnEncoding>)>> {
         #[cfg(feature = "parallel")]
         {
             if self.should_use_parallel(data) {
@@ -252,7 +643,7 @@ impl AlsCompressor {
         }
 
         // Sequential compression
-        self.compress_columns_sequential(data, dictionary)
+        self.compress_columns_sequential(data, dictionary, buffers)
     }
 
     /// Compress columns sequentially.
@@ -260,10 +651,11 @@ impl AlsCompressor {
         &self,
         data: &TabularData,
         dictionary: &[String],
-    ) -> Result<Vec<ColumnStream>> {
+        buffers: &mut CompressionBuffers,
+    ) -> Result<Vec<(ColumnStream, Option<ColumnEncoding>)>> {
         let mut streams = Vec::with_capacity(data.column_count());
         for column in &data.columns {
-            let stream = self.compress_column(column, dictionary)?;
+            let stream = self.compress_column(column, dictionary, buffers)?;
             streams.push(stream);
         }
         Ok(streams)
@@ -275,9 +667,9 @@ impl AlsCompressor {
         &self,
         data: &TabularData,
         dictionary: &[String],
-    ) -> Result<Vec<ColumnStream>> {
+    ) -> Result<Vec<(ColumnStream, Option<ColumnEncoding>)>> {
         // Configure thread pool if parallelism is specified
-        let result: Result<Vec<ColumnStream>> = if self.config.parallelism > 1 {
+        let result: Result<Vec<(ColumnStream, Option<ColumnEncoding>)>> = if self.config.parallelism > 1 {
             // Use a custom thread pool with specified parallelism
             let pool = rayon::ThreadPoolBuilder::new()
                 .num_threads(self.config.parallelism)
@@ -290,14 +682,18 @@ impl AlsCompressor {
             pool.install(|| {
                 data.columns
                     .par_iter()
-                    .map(|column| self.compress_column(column, dictionary))
+                    .map_init(CompressionBuffers::new, |buffers, column| {
+                        self.compress_column(column, dictionary, buffers)
+                    })
                     .collect()
             })
         } else {
             // Use default Rayon thread pool (auto-detect cores)
             data.columns
                 .par_iter()
-                .map(|column| self.compress_column(column, dictionary))
+                .map_init(CompressionBuffers::new, |buffers, column| {
+                    self.compress_column(column, dictionary, buffers)
+                })
                 .collect()
         };
 
@@ -306,7 +702,7 @@ impl AlsCompressor {
 
     /// Compress data using CTX format (no pattern compression).
     fn compress_ctx(&self, data: &TabularData) -> AlsDocument {
-        let mut doc = AlsDocument::with_schema(data.column_names().into_iter().map(String::from).collect());
+        let mut doc = self.new_document(data);
         doc.set_ctx_format();
 
         // In CTX format, we just use raw values without pattern compression
@@ -314,17 +710,43 @@ impl AlsCompressor {
             let operators: Vec<AlsOperator> = column
                 .values
                 .iter()
-                .map(|v| AlsOperator::raw(v.to_string_repr().into_owned()))
+                .map(|v| AlsOperator::raw(v.to_string_repr_with(&self.config.sentinel_tokens).into_owned()))
                 .collect();
             doc.add_stream(ColumnStream::from_operators(operators));
         }
+        self.annotate_number_types(&mut doc, data);
+
+        doc
+    }
+
+    /// Record integer and float columns as schema annotations, if enabled.
+    fn annotate_number_types(&self, doc: &mut AlsDocument, data: &TabularData) {
+        if !self.config.annotate_number_types {
+            return;
+        }
+        for column in &data.columns {
+            let number_type = match column.infer_type() {
+                ColumnType::Integer => NumberType::Integer,
+                ColumnType::Float => NumberType::Float,
+                ColumnType::Decimal => NumberType::Decimal,
+                ColumnType::Timestamp => NumberType::Timestamp,
+                _ => continue,
+            };
+            doc.set_number_type(column.name.as_ref(), number_type);
+        }
+    }
 
+    /// Create a document with the schema of `data` and the configured
+    /// sentinel tokens.
+    fn new_document(&self, data: &TabularData) -> AlsDocument {
+        let mut doc = AlsDocument::with_schema(data.column_names().into_iter().map(String::from).collect());
+        doc.sentinel_tokens = self.config.sentinel_tokens.clone();
         doc
     }
 
     /// Create an empty document for empty input.
     fn create_empty_document(&self, data: &TabularData) -> AlsDocument {
-        let mut doc = AlsDocument::with_schema(data.column_names().into_iter().map(String::from).collect());
+        let mut doc = self.new_document(data);
         
         // Add empty streams for each column
         for _ in 0..data.column_count() {
@@ -335,55 +757,448 @@ impl AlsCompressor {
     }
 
     /// Build a dictionary from the tabular data.
-    fn build_dictionary(&self, data: &TabularData) -> Vec<String> {
-        let mut builder = DictionaryBuilder::with_config(&self.config);
+    pub(crate) fn build_dictionary(&self, data: &TabularData) -> Vec<String> {
+        // Low effort levels skip frequency counting, keeping only hinted columns
+        let mut dictionary = if self.config.builds_dictionary() {
+            self.count_dictionary_values(data).build()
+        } else {
+            Vec::new()
+        };
 
-        // Add all string values to the dictionary builder
+        // Columns forced to dictionary encoding need every string in the dictionary
+        let mut known: std::collections::HashSet<String> = dictionary.iter().cloned().collect();
         for column in &data.columns {
+            if self.config.encoding_hint(column.name.as_ref()) != Some(ColumnEncoding::Dict) {
+                continue;
+            }
             for value in &column.values {
                 if let Value::String(s) = value {
-                    builder.add(s.as_ref());
+                    if !s.is_empty()
+                        && !self.config.sentinel_tokens.collides(s)
+                        && dictionary.len() < self.config.max_dictionary_entries
+                        && known.insert(s.to_string())
+                    {
+                        dictionary.push(s.to_string());
+                    }
                 }
             }
         }
 
-        builder.build()
+        dictionary
     }
 
-    /// Compress a single column.
-    fn compress_column(
-        &self,
-        column: &crate::convert::Column,
-        dictionary: &[String],
-    ) -> Result<ColumnStream> {
-        // Convert values to strings for pattern detection
-        let string_values: Vec<String> = column
-            .values
-            .iter()
-            .map(|v| v.to_string_repr().into_owned())
-            .collect();
+    /// Count the string values of every column for dictionary building.
+    ///
+    /// Large inputs count each column in parallel and merge the per-column
+    /// frequencies; small inputs stay sequential so the merge never dominates.
+    fn count_dictionary_values(&self, data: &TabularData) -> DictionaryBuilder {
+        #[cfg(feature = "parallel")]
+        {
+            if self.should_use_parallel(data) {
+                return self.count_dictionary_values_parallel(data);
+            }
+        }
 
-        let str_refs: Vec<&str> = string_values.iter().map(|s| s.as_str()).collect();
+        let mut builder = DictionaryBuilder::with_config(&self.config);
+        for column in &data.columns {
+            Self::count_column_values(&mut builder, column, &self.config.sentinel_tokens);
+        }
+        builder
+    }
 
-        // Try pattern detection
-        let detection = self.pattern_engine.detect(&str_refs);
+    /// Count the string values of every column in parallel using Rayon.
+    #[cfg(feature = "parallel")]
+    fn count_dictionary_values_parallel(&self, data: &TabularData) -> DictionaryBuilder {
+        let count = || {
+            data.columns
+                .par_iter()
+                .map(|column| {
+                    let mut builder = DictionaryBuilder::with_config(&self.config);
+                    Self::count_column_values(&mut builder, column, &self.config.sentinel_tokens);
+                    builder
+                })
+                .reduce(
+                    || DictionaryBuilder::with_config(&self.config),
+                    |mut merged, builder| {
+                        merged.merge(builder);
+                        merged
+                    },
+                )
+        };
 
-        // If pattern detection found something useful, use it
-        if detection.pattern_type != PatternType::Raw && detection.compression_ratio > 1.0 {
-            return Ok(ColumnStream::from_operators(vec![detection.operator]));
+        if self.config.parallelism > 1 {
+            // Counting cannot fail, so fall back to the default pool if a
+            // dedicated pool cannot be created
+            match rayon::ThreadPoolBuilder::new()
+                .num_threads(self.config.parallelism)
+                .build()
+            {
+                Ok(pool) => pool.install(count),
+                Err(_) => count(),
+            }
+        } else {
+            count()
         }
+    }
 
-        // Otherwise, try dictionary references or raw values
-        let operators = self.encode_with_dictionary(&str_refs, dictionary);
-        Ok(ColumnStream::from_operators(operators))
+    /// Add the string values of a column to a dictionary builder.
+    ///
+    /// Values that spell out a sentinel token are stored escaped, so they
+    /// are left out of the dictionary.
+    fn count_column_values(builder: &mut DictionaryBuilder, column: &crate::convert::Column, tokens: &SentinelTokens) {
+        for value in &column.values {
+            if let Value::String(s) = value {
+                if !tokens.collides(s) {
+                    builder.add(s.as_ref());
+                }
+            }
+        }
     }
 
-    /// Encode values using dictionary references where beneficial.
-    fn encode_with_dictionary(&self, values: &[&str], dictionary: &[String]) -> Vec<AlsOperator> {
-        // Build a lookup map for dictionary indices
-        let dict_lookup: std::collections::HashMap<&str, usize> = dictionary
-            .iter()
-            .enumerate()
+    /// Compress a single column, writing its values into `buffers`.
+    ///
+    /// Returns the column stream and the encoding hint it honored, if any.
+    fn compress_column(
+        &self,
+        column: &crate::convert::Column,
+        dictionary: &[String],
+        buffers: &mut CompressionBuffers,
+    ) -> Result<(ColumnStream, Option<ColumnEncoding>)> {
+        // A constant column is always best as a single `value*n`, so skip the
+        // string conversion and the generic detectors entirely
+        if let Some(operator) = self.encode_constant(column) {
+            return Ok((ColumnStream::from_operators(vec![operator]), None));
+        }
+
+        // Convert values to strings for pattern detection
+        buffers.with_values(column, &self.config.sentinel_tokens, |str_refs| {
+            // A forced encoding skips pattern detection entirely
+            if let Some((hint, operators, _)) = self.encode_with_hint(column.name.as_ref(), str_refs, dictionary) {
+                return Ok((ColumnStream::from_operators(operators), Some(hint)));
+            }
+
+            // Try pattern detection
+            let detection = self.pattern_engine.detect(str_refs);
+
+            // If pattern detection found something useful, use it; otherwise,
+            // try dictionary references or raw values. Exhaustive detection
+            // keeps the pattern only if it is actually the smaller encoding.
+            let operators = if detection.pattern_type != PatternType::Raw && detection.compression_ratio > 1.0 {
+                let pattern = vec![detection.operator];
+                if self.config.exhaustive_detection() {
+                    let fallback = self.encode_with_dictionary(str_refs, dictionary);
+                    if self.operators_size(&fallback) < self.operators_size(&pattern) {
+                        fallback
+                    } else {
+                        pattern
+                    }
+                } else {
+                    pattern
+                }
+            } else {
+                self.encode_with_dictionary(str_refs, dictionary)
+            };
+
+            // Integer enums may pack smaller into an integer dictionary
+            if let Some(int_dict) = self.encode_with_int_dictionary(str_refs, &operators) {
+                return Ok((ColumnStream::from_operators(vec![int_dict]), None));
+            }
+
+            Ok((ColumnStream::from_operators(operators), None))
+        })
+    }
+
+    /// Encode a column whose values are all identical as a single `value*n`.
+    ///
+    /// Returns `None` if the column is not constant, is shorter than the
+    /// minimum pattern length, has an encoding hint, or if repeat detection
+    /// is disabled or would not pay off.
+    fn encode_constant(&self, column: &crate::convert::Column) -> Option<AlsOperator> {
+        if column.len() < self.config.min_pattern_length
+            || !self.config.detector_enabled(PatternType::Repeat)
+            || self.config.encoding_hint(column.name.as_ref()).is_some()
+            || !column.is_constant()
+        {
+            return None;
+        }
+
+        let value = column.values[0].to_string_repr_with(&self.config.sentinel_tokens);
+        let detection = DetectionResult::repeat(&value, column.len(), 0);
+        (detection.compression_ratio > 1.0).then_some(detection.operator)
+    }
+
+    /// Encode a column as an integer dictionary if that is smaller than
+    /// `operators` and integer dictionaries are enabled.
+    fn encode_with_int_dictionary(&self, values: &[&str], operators: &[AlsOperator]) -> Option<AlsOperator> {
+        if !self.config.detector_enabled(PatternType::IntDictionary) {
+            return None;
+        }
+        let int_dict = IntDictionaryEncoder::new().encode(values)?;
+        (self.operators_size(std::slice::from_ref(&int_dict)) < self.operators_size(operators))
+            .then_some(int_dict)
+    }
+
+    /// Encode a column with its configured encoding hint.
+    ///
+    /// Returns `None` if the column has no honored hint, or if the hinted
+    /// encoding cannot represent the values. The latter logs a warning and
+    /// leaves the column to normal pattern detection.
+    fn encode_with_hint(
+        &self,
+        name: &str,
+        values: &[&str],
+        dictionary: &[String],
+    ) -> Option<(ColumnEncoding, Vec<AlsOperator>, PatternType)> {
+        let hint = self.config.encoding_hint(name)?;
+
+        let encoded = match hint {
+            ColumnEncoding::Raw => Some((
+                values.iter().map(|&value| AlsOperator::raw(value)).collect(),
+                PatternType::Raw,
+            )),
+            ColumnEncoding::Dict => {
+                let operators = self.encode_with_dictionary(values, dictionary);
+                let complete = operators.iter().zip(values).all(|(op, &value)| {
+                    let tokens = &self.config.sentinel_tokens;
+                    matches!(op, AlsOperator::DictRef(_)) || tokens.is_null_token(value) || tokens.is_empty_token(value)
+                });
+                complete.then_some((operators, PatternType::Raw))
+            }
+            _ => self
+                .pattern_engine
+                .detect_encoding(values, hint)
+                .map(|detection| (vec![detection.operator], detection.pattern_type)),
+        };
+
+        match encoded {
+            Some((operators, pattern_type)) => Some((hint, operators, pattern_type)),
+            None => {
+                log::warn!(
+                    "Column '{}' cannot be encoded as '{}'; falling back to pattern detection",
+                    name,
+                    hint.name()
+                );
+                None
+            }
+        }
+    }
+
+    /// Share dictionary entries across columns and prune the unused ones.
+    ///
+    /// Columns are compressed independently, so detected patterns still hold
+    /// raw values and only columns that fell back to dictionary encoding
+    /// reference the dictionary. This second pass rewrites repeats and run
+    /// lists onto entries those columns already pay for, whenever that is
+    /// shorter, then drops every entry nothing references and renumbers the
+    /// references to match. Toggle values stay raw since the toggle syntax
+    /// cannot hold dictionary references.
+    ///
+    /// Returns the pruned dictionary.
+    fn share_dictionary(&self, streams: &mut [ColumnStream], dictionary: &[String]) -> Vec<String> {
+        let mut used = vec![false; dictionary.len()];
+        for stream in streams.iter() {
+            for op in &stream.operators {
+                mark_dict_refs(op, &mut used);
+            }
+        }
+
+        // Only entries that are already referenced are free to share
+        let shared: std::collections::HashMap<&str, usize> = dictionary
+            .iter()
+            .enumerate()
+            .filter(|&(index, _)| used[index])
+            .map(|(index, value)| (value.as_str(), index))
+            .collect();
+
+        self.rewrite_streams_with_dictionary(streams, &shared);
+
+        // Renumber the surviving entries in their original order
+        let mut remap = vec![None; dictionary.len()];
+        let mut pruned = Vec::new();
+        for (index, value) in dictionary.iter().enumerate() {
+            if used[index] {
+                remap[index] = Some(pruned.len());
+                pruned.push(value.clone());
+            }
+        }
+        for stream in streams.iter_mut() {
+            for op in &mut stream.operators {
+                op.remap_dict_refs(&remap);
+            }
+        }
+
+        pruned
+    }
+
+    /// Split the default dictionary of `doc` into one dictionary per group
+    /// of columns, if that serializes smaller.
+    ///
+    /// Columns that reference a common entry share a group, so the groups
+    /// hold disjoint entries and no entry is written twice. Each group
+    /// renumbers its references from zero, which shortens them whenever
+    /// another group would otherwise take the low indices. The group of the
+    /// first column keeps the `default` name; the others are named after
+    /// their first column where that is a valid dictionary name.
+    fn split_dictionary(&self, doc: &mut AlsDocument) {
+        if !self.config.column_dictionaries {
+            return;
+        }
+        let Some(dictionary) = doc.default_dictionary() else {
+            return;
+        };
+
+        // Group columns by the entries they reference
+        let mut groups: Vec<usize> = (0..doc.streams.len()).collect();
+        let mut owner: Vec<Option<usize>> = vec![None; dictionary.len()];
+        let mut used = vec![false; dictionary.len()];
+        for (column, stream) in doc.streams.iter().enumerate() {
+            used.iter_mut().for_each(|slot| *slot = false);
+            for op in &stream.operators {
+                mark_dict_refs(op, &mut used);
+            }
+            for (index, _) in used.iter().enumerate().filter(|&(_, &used)| used) {
+                match owner[index] {
+                    Some(other) => union_groups(&mut groups, other, column),
+                    None => owner[index] = Some(column),
+                }
+            }
+        }
+
+        let mut split = doc.clone();
+        let mut entries: Vec<(usize, Vec<String>, Vec<Option<usize>>)> = Vec::new();
+        for (index, value) in dictionary.iter().enumerate() {
+            let Some(column) = owner[index] else { continue };
+            let group = find_group(&mut groups, column);
+            let position = match entries.iter().position(|(root, _, _)| *root == group) {
+                Some(position) => position,
+                None => {
+                    entries.push((group, Vec::new(), vec![None; dictionary.len()]));
+                    entries.len() - 1
+                }
+            };
+            let (_, values, remap) = &mut entries[position];
+            remap[index] = Some(values.len());
+            values.push(value.clone());
+        }
+        if entries.len() < 2 {
+            return;
+        }
+
+        entries.sort_by_key(|(group, _, _)| *group);
+        split.dictionaries.remove("default");
+        for (position, (group, values, remap)) in entries.into_iter().enumerate() {
+            // Groups are represented by their first column
+            let name = if position == 0 {
+                "default".to_string()
+            } else {
+                column_dictionary_name(&doc.schema, group, &split.dictionaries)
+            };
+            for column in 0..split.streams.len() {
+                if find_group(&mut groups, column) == group {
+                    for op in &mut split.streams[column].operators {
+                        op.remap_dict_refs(&remap);
+                    }
+                    split.set_column_dictionary(column, name.clone());
+                }
+            }
+            split.add_dictionary(name, values);
+        }
+
+        if self.calculate_compressed_size(&split) < self.calculate_compressed_size(doc) {
+            *doc = split;
+        }
+    }
+
+    /// Compress one batch of rows against a fixed dictionary.
+    ///
+    /// Used by streaming compression, where the dictionary is sampled up
+    /// front and written once in the stream header. Every entry is already
+    /// paid for, so repeats and run lists may reference any of them, while
+    /// values outside the dictionary stay raw. No CTX fallback is applied.
+    pub(crate) fn compress_batch(
+        &self,
+        data: &TabularData,
+        dictionary: &[String],
+        buffers: &mut CompressionBuffers,
+    ) -> Result<Vec<ColumnStream>> {
+        let mut streams: Vec<ColumnStream> = self
+            .compress_columns_internal(data, dictionary, buffers)?
+            .into_iter()
+            .map(|(stream, _)| stream)
+            .collect();
+
+        let shared: std::collections::HashMap<&str, usize> = dictionary
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (value.as_str(), index))
+            .collect();
+        self.rewrite_streams_with_dictionary(&mut streams, &shared);
+
+        Ok(streams)
+    }
+
+    /// Rewrite the repeats and run lists of every stream onto shared entries.
+    fn rewrite_streams_with_dictionary(
+        &self,
+        streams: &mut [ColumnStream],
+        shared: &std::collections::HashMap<&str, usize>,
+    ) {
+        if shared.is_empty() {
+            return;
+        }
+
+        for stream in streams.iter_mut() {
+            let operators = std::mem::take(&mut stream.operators);
+            stream.operators = operators
+                .into_iter()
+                .flat_map(|op| self.rewrite_with_dictionary(op, shared))
+                .collect();
+        }
+    }
+
+    /// Rewrite a repeat or run list onto shared dictionary entries.
+    ///
+    /// Returns the original operator unless the rewrite serializes shorter.
+    fn rewrite_with_dictionary(
+        &self,
+        op: AlsOperator,
+        shared: &std::collections::HashMap<&str, usize>,
+    ) -> Vec<AlsOperator> {
+        let rewritten = match &op {
+            AlsOperator::Multiply { value, count } => match value.as_ref() {
+                AlsOperator::Raw(raw) => shared
+                    .get(raw.as_str())
+                    .map(|&index| vec![AlsOperator::multiply(AlsOperator::dict_ref(index), *count)]),
+                _ => None,
+            },
+            AlsOperator::RunList { runs } if runs.iter().any(|(value, _)| shared.contains_key(value.as_str())) => Some(
+                runs.iter()
+                    .map(|(value, count)| {
+                        let inner = match shared.get(value.as_str()) {
+                            Some(&index) => AlsOperator::dict_ref(index),
+                            None => AlsOperator::raw(value.clone()),
+                        };
+                        AlsOperator::multiply(inner, *count)
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        match rewritten {
+            Some(operators) if self.operators_size(&operators) < self.operators_size(std::slice::from_ref(&op)) => {
+                operators
+            }
+            _ => vec![op],
+        }
+    }
+
+    /// Encode values using dictionary references where beneficial.
+    fn encode_with_dictionary(&self, values: &[&str], dictionary: &[String]) -> Vec<AlsOperator> {
+        // Build a lookup map for dictionary indices
+        let dict_lookup: std::collections::HashMap<&str, usize> = dictionary
+            .iter()
+            .enumerate()
             .map(|(i, s)| (s.as_str(), i))
             .collect();
 
@@ -409,7 +1224,7 @@ impl AlsCompressor {
             
             // Values
             for value in &column.values {
-                size += value.to_string_repr().len();
+                size += value.to_string_repr_with(&self.config.sentinel_tokens).len();
                 size += 1; // Separator (comma or newline)
             }
         }
@@ -448,10 +1263,15 @@ impl AlsCompressor {
             return Ok(false);
         }
 
-        let als_doc = self.compress_als(data)?;
+        let als_doc = self.compress_als(data, &mut CompressionBuffers::new())?;
         let ratio = self.calculate_compression_ratio(data, &als_doc);
         
-        Ok(ratio < self.config.ctx_fallback_threshold)
+        Ok(self.falls_back_to_ctx(ratio))
+    }
+
+    /// Check whether an ALS compression ratio calls for the CTX fallback.
+    fn falls_back_to_ctx(&self, ratio: f64) -> bool {
+        self.config.ctx_fallback && ratio < self.config.ctx_fallback_threshold
     }
 
     /// Check if parallel processing would be used for the given data.
@@ -473,6 +1293,38 @@ impl AlsCompressor {
         }
     }
 
+    /// Compress tabular data on the calling thread only.
+    ///
+    /// Produces the same document as [`AlsCompressor::compress`] but never
+    /// hands work to a thread pool, whatever the data size,
+    /// [`CompressorConfig::parallelism`] or
+    /// [`CompressorConfig::parallel_threshold`]. Useful for reproducible
+    /// timings or when the caller already runs compressions in parallel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{AlsCompressor, AlsSerializer, Column, TabularData, Value};
+    /// use std::borrow::Cow;
+    ///
+    /// let mut data = TabularData::new();
+    /// let ids: Vec<Value> = (1..=100).map(Value::Integer).collect();
+    /// data.add_column(Column::new(Cow::Borrowed("id"), ids));
+    ///
+    /// let doc = AlsCompressor::new().compress_sequential(&data).unwrap();
+    /// assert_eq!(AlsSerializer::new().serialize(&doc), "!v1\n#id\n1>100");
+    /// ```
+    pub fn compress_sequential(&self, data: &TabularData) -> Result<AlsDocument> {
+        let sequential = Self {
+            config: CompressorConfig {
+                parallelism: 1,
+                ..self.config.clone()
+            },
+            pattern_engine: self.pattern_engine.clone(),
+        };
+        sequential.compress(data)
+    }
+
     /// Compress tabular data using parallel processing.
     ///
     /// This method forces parallel compression regardless of data size.
@@ -502,19 +1354,15 @@ impl AlsCompressor {
         let dictionary = self.build_dictionary(data);
 
         // Create document
-        let mut doc = AlsDocument::with_schema(
-            data.column_names().into_iter().map(String::from).collect(),
-        );
+        let mut doc = self.new_document(data);
         doc.set_als_format();
 
-        if !dictionary.is_empty() {
-            doc.add_dictionary("default", dictionary.clone());
-        }
-
         // Force parallel compression
         let streams = self.compress_columns_parallel(data, &dictionary)?;
-        for stream in streams {
-            doc.add_stream(stream);
+        self.finish_document(&mut doc, data, streams, &dictionary);
+        self.annotate_number_types(&mut doc, data);
+        if self.config.collapse_duplicate_rows {
+            doc = self.collapse_duplicate_rows(data, doc, &mut CompressionBuffers::new())?;
         }
 
         // Check for CTX fallback
@@ -526,11 +1374,16 @@ impl AlsCompressor {
             f64::INFINITY
         };
 
-        if compression_ratio < self.config.ctx_fallback_threshold {
-            Ok(self.compress_ctx(data))
+        let mut doc = if self.falls_back_to_ctx(compression_ratio) {
+            self.compress_ctx(data)
         } else {
-            Ok(doc)
+            doc
+        };
+
+        if self.config.column_reordering {
+            doc.column_order = similarity_order(data);
         }
+        Ok(doc)
     }
 
     /// Compress tabular data and return detailed statistics.
@@ -565,69 +1418,99 @@ impl AlsCompressor {
 
         // Build dictionary
         let dictionary = self.build_dictionary(data);
-        let dict_entries_used = dictionary.len();
 
         // Compress each column and collect stats
-        let mut doc = AlsDocument::with_schema(
-            data.column_names().into_iter().map(String::from).collect(),
-        );
+        let mut doc = self.new_document(data);
         doc.set_als_format();
 
-        if !dictionary.is_empty() {
-            doc.add_dictionary("default", dictionary.clone());
-        }
-
-        for (idx, column) in data.columns.iter().enumerate() {
-            let col_input_size = self.calculate_column_size(column);
-            
+        let mut streams = Vec::with_capacity(data.column_count());
+        let mut pattern_types = Vec::with_capacity(data.column_count());
+        let mut buffers = CompressionBuffers::new();
+        for column in &data.columns {
             // Convert values to strings for pattern detection
-            let string_values: Vec<String> = column
-                .values
-                .iter()
-                .map(|v| v.to_string_repr().into_owned())
-                .collect();
-            let str_refs: Vec<&str> = string_values.iter().map(|s| s.as_str()).collect();
+            let (operators, pattern_type) = buffers.with_values(column, &self.config.sentinel_tokens, |str_refs| {
+                // A forced encoding skips pattern detection entirely
+                if let Some((hint, operators, pattern_type)) =
+                    self.encode_with_hint(column.name.as_ref(), str_refs, &dictionary)
+                {
+                    doc.set_encoding_hint(column.name.as_ref(), hint);
+                    return (operators, pattern_type);
+                }
 
-            // Try pattern detection
-            let detection = self.pattern_engine.detect(&str_refs);
-            let pattern_type = detection.pattern_type;
+                // Try pattern detection
+                let detection = self.pattern_engine.detect(str_refs);
+                let pattern_type = detection.pattern_type;
+                let useful = pattern_type != PatternType::Raw && detection.compression_ratio > 1.0;
+                let operators = if useful {
+                    vec![detection.operator]
+                } else {
+                    self.encode_with_dictionary(str_refs, &dictionary)
+                };
+
+                // Integer enums may pack smaller into an integer dictionary
+                match self.encode_with_int_dictionary(str_refs, &operators) {
+                    Some(int_dict) => (vec![int_dict], PatternType::IntDictionary),
+                    None if useful => (operators, pattern_type),
+                    None => (operators, PatternType::Raw),
+                }
+            });
 
-            // Determine the stream and track stats
-            let stream = if pattern_type != PatternType::Raw && detection.compression_ratio > 1.0 {
+            // Track stats for patterns, or for the raw values of
+            // dictionary-encoded columns
+            if pattern_type != PatternType::Raw {
                 stats.record_pattern(pattern_type);
-                ColumnStream::from_operators(vec![detection.operator])
             } else {
-                // Use dictionary references or raw values
-                let operators = self.encode_with_dictionary(&str_refs, &dictionary);
-                
-                // Count dict refs and raw values
                 for op in &operators {
-                    match op {
-                        AlsOperator::DictRef(_) => stats.record_dict_ref(),
-                        AlsOperator::Raw(_) => stats.record_raw_value(),
-                        _ => {}
+                    if let AlsOperator::Raw(_) = op {
+                        stats.record_raw_value();
                     }
                 }
-                
-                ColumnStream::from_operators(operators)
-            };
+            }
+            let stream = ColumnStream::from_operators(operators);
 
-            // Calculate output size for this column
-            let col_output_size = self.estimate_stream_size(&stream);
-            let was_compressed = col_output_size < col_input_size;
-            
-            stats.record_column_processed(was_compressed);
+            streams.push(stream);
+            pattern_types.push(pattern_type);
+        }
+
+        // Share dictionary entries across columns and drop the unused ones
+        let shared_dictionary = self.share_dictionary(&mut streams, &dictionary);
+        if !shared_dictionary.is_empty() {
+            doc.add_dictionary("default", shared_dictionary.clone());
+        }
 
-            column_stats.push(ColumnStats::new(
+        for stream in streams {
+            // Count dict refs, including those wrapped by repeats
+            stats.record_dict_refs(stream.operators.iter().map(count_dict_refs).sum());
+            doc.add_stream(stream);
+        }
+        self.split_dictionary(&mut doc);
+        self.annotate_number_types(&mut doc, data);
+
+        // Size each column as written, charging it its share of the
+        // dictionary header, since splitting renumbers its references
+        let dictionary_shares = dictionary_shares(&doc);
+        for (idx, ((column, stream), pattern_type)) in data
+            .columns
+            .iter()
+            .zip(&doc.streams)
+            .zip(pattern_types)
+            .enumerate()
+        {
+            let col_input_size = self.calculate_column_size(column);
+            let col_output_size = self.estimate_stream_size(stream);
+            let column_stat = ColumnStats::new(
                 column.name.to_string(),
                 idx,
                 col_input_size,
                 col_output_size,
                 pattern_type,
                 column.values.len(),
-            ));
+            )
+            .with_values(ValueStats::from_values(&column.values))
+            .with_dictionary_bytes(dictionary_shares[idx]);
 
-            doc.add_stream(stream);
+            stats.record_column_processed(column_stat.was_compressed);
+            column_stats.push(column_stat);
         }
 
         // Calculate final compressed size
@@ -641,22 +1524,21 @@ impl AlsCompressor {
             f64::INFINITY
         };
 
-        let used_ctx_fallback = compression_ratio < self.config.ctx_fallback_threshold;
+        let used_ctx_fallback = self.falls_back_to_ctx(compression_ratio);
         
-        let final_doc = if used_ctx_fallback {
+        let mut final_doc = if used_ctx_fallback {
             self.compress_ctx(data)
         } else {
             doc
         };
+        if self.config.column_reordering {
+            final_doc.column_order = similarity_order(data);
+        }
 
-        // Calculate dictionary utilization
+        // Calculate dictionary utilization: the share of candidate entries
+        // that survived pruning because some column references them
         let dict_utilization = if !dictionary.is_empty() {
-            let dict_refs = stats.get_dict_refs_used();
-            if dict_refs > 0 {
-                dict_entries_used as f64 / dictionary.len() as f64
-            } else {
-                0.0
-            }
+            shared_dictionary.len() as f64 / dictionary.len() as f64
         } else {
             0.0
         };
@@ -671,7 +1553,7 @@ impl AlsCompressor {
     fn calculate_column_size(&self, column: &crate::convert::Column) -> usize {
         let mut size = column.name.len();
         for value in &column.values {
-            size += value.to_string_repr().len();
+            size += value.to_string_repr_with(&self.config.sentinel_tokens).len();
             size += 1; // Separator
         }
         size
@@ -679,9 +1561,14 @@ impl AlsCompressor {
 
     /// Estimate the serialized size of a column stream.
     fn estimate_stream_size(&self, stream: &ColumnStream) -> usize {
+        self.operators_size(&stream.operators)
+    }
+
+    /// Serialized size of a sequence of operators.
+    fn operators_size(&self, operators: &[AlsOperator]) -> usize {
         let serializer = AlsSerializer::new();
         let mut output = String::new();
-        for (i, op) in stream.operators.iter().enumerate() {
+        for (i, op) in operators.iter().enumerate() {
             if i > 0 {
                 output.push(' ');
             }
@@ -818,6 +1705,173 @@ impl AlsCompressor {
     }
 }
 
+/// Find runs of consecutive rows that are identical in every column.
+///
+/// Returns the index of the first row of each run, and the runs longer than
+/// one row as `(position, count)` pairs, where `position` is the run's index
+/// among the kept rows.
+fn duplicate_row_runs(data: &TabularData) -> (Vec<usize>, Vec<(usize, usize)>) {
+    let mut kept_rows: Vec<usize> = Vec::with_capacity(data.row_count);
+    let mut repeats: Vec<(usize, usize)> = Vec::new();
+    for row in 0..data.row_count {
+        let duplicate = kept_rows.last().is_some_and(|&kept| {
+            data.columns.iter().all(|column| column.values[kept] == column.values[row])
+        });
+        if !duplicate {
+            kept_rows.push(row);
+            continue;
+        }
+        let position = kept_rows.len() - 1;
+        match repeats.last_mut() {
+            Some((repeated, count)) if *repeated == position => *count += 1,
+            _ => repeats.push((position, 2)),
+        }
+    }
+    (kept_rows, repeats)
+}
+
+/// Find the representative column of the group holding `column`.
+fn find_group(groups: &mut [usize], column: usize) -> usize {
+    let mut root = column;
+    while groups[root] != root {
+        root = groups[root];
+    }
+    groups[column] = root;
+    root
+}
+
+/// Merge the groups holding columns `a` and `b`, keeping the lower
+/// representative.
+fn union_groups(groups: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find_group(groups, a), find_group(groups, b));
+    groups[a.max(b)] = a.min(b);
+}
+
+/// Name the dictionary of the group starting at `column`: the column name
+/// when it is a valid dictionary name, `d<column>` otherwise, with `_`
+/// appended until it is not `default` or one of the `taken` names.
+fn column_dictionary_name(
+    schema: &[String],
+    column: usize,
+    taken: &std::collections::HashMap<String, Vec<String>>,
+) -> String {
+    let mut name = schema
+        .get(column)
+        .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '.'))
+        .cloned()
+        .unwrap_or_else(|| format!("d{}", column));
+    while name == "default" || taken.contains_key(&name) {
+        name.push('_');
+    }
+    name
+}
+
+/// Mark every dictionary entry an operator references.
+fn mark_dict_refs(op: &AlsOperator, used: &mut [bool]) {
+    match op {
+        AlsOperator::DictRef(index) => {
+            if let Some(slot) = used.get_mut(*index) {
+                *slot = true;
+            }
+        }
+        AlsOperator::Multiply { value, .. }
+        | AlsOperator::Timestamp { value, .. }
+        | AlsOperator::Ipv4 { value } => {
+            mark_dict_refs(value, used)
+        }
+        AlsOperator::Interleave { lanes } | AlsOperator::Affix { values: lanes, .. } => {
+            lanes.iter().for_each(|lane| mark_dict_refs(lane, used))
+        }
+        _ => {}
+    }
+}
+
+/// Count the references an operator makes to each dictionary entry.
+fn tally_dict_refs(op: &AlsOperator, counts: &mut [usize]) {
+    match op {
+        AlsOperator::DictRef(index) => {
+            if let Some(count) = counts.get_mut(*index) {
+                *count += 1;
+            }
+        }
+        AlsOperator::Multiply { value, .. }
+        | AlsOperator::Timestamp { value, .. }
+        | AlsOperator::Ipv4 { value } => {
+            tally_dict_refs(value, counts)
+        }
+        AlsOperator::Interleave { lanes } | AlsOperator::Affix { values: lanes, .. } => {
+            lanes.iter().for_each(|lane| tally_dict_refs(lane, counts))
+        }
+        _ => {}
+    }
+}
+
+/// Apportion the serialized dictionary header of `doc` across its columns.
+///
+/// Each entry costs its escaped text plus the `:` or `|` before it, and is
+/// split across the columns that reference it in proportion to their
+/// number of references. The rest of a dictionary's line (`$`, its name,
+/// and the line break) is split in proportion to those entry shares.
+/// Returns one share per column, rounded to whole bytes.
+fn dictionary_shares(doc: &AlsDocument) -> Vec<usize> {
+    let mut shares = vec![0.0f64; doc.streams.len()];
+    for (name, entries) in &doc.dictionaries {
+        let columns: Vec<usize> =
+            (0..doc.streams.len()).filter(|&column| doc.dictionary_name(column) == name).collect();
+
+        let counts: Vec<Vec<usize>> = columns
+            .iter()
+            .map(|&column| {
+                let mut counts = vec![0; entries.len()];
+                doc.streams[column].operators.iter().for_each(|op| tally_dict_refs(op, &mut counts));
+                counts
+            })
+            .collect();
+
+        let mut entry_shares = vec![0.0f64; columns.len()];
+        for (entry_index, entry) in entries.iter().enumerate() {
+            let references: usize = counts.iter().map(|counts| counts[entry_index]).sum();
+            if references == 0 {
+                continue;
+            }
+            let cost = (crate::als::escape_dict_value(entry).len() + 1) as f64;
+            for (share, counts) in entry_shares.iter_mut().zip(&counts) {
+                *share += cost * counts[entry_index] as f64 / references as f64;
+            }
+        }
+
+        let assigned: f64 = entry_shares.iter().sum();
+        if assigned == 0.0 {
+            continue;
+        }
+        let line_len = 1
+            + name.len()
+            + entries.iter().map(|entry| crate::als::escape_dict_value(entry).len() + 1).sum::<usize>()
+            + 1;
+        for (&column, share) in columns.iter().zip(entry_shares) {
+            shares[column] += line_len as f64 * share / assigned;
+        }
+    }
+    shares.into_iter().map(|share| share.round() as usize).collect()
+}
+
+/// Count the dictionary references of an operator.
+fn count_dict_refs(op: &AlsOperator) -> usize {
+    match op {
+        AlsOperator::DictRef(_) => 1,
+        AlsOperator::IntDict { indices, .. } => indices.len(),
+        AlsOperator::Multiply { value, .. }
+        | AlsOperator::Timestamp { value, .. }
+        | AlsOperator::Ipv4 { value } => {
+            count_dict_refs(value)
+        }
+        AlsOperator::Interleave { lanes } | AlsOperator::Affix { values: lanes, .. } => {
+            lanes.iter().map(count_dict_refs).sum()
+        }
+        _ => 0,
+    }
+}
+
 impl Default for AlsCompressor {
     fn default() -> Self {
         Self::new()
@@ -828,7 +1882,7 @@ impl Default for AlsCompressor {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::als::FormatIndicator;
+    use crate::als::{AlsParser, FormatIndicator};
     use crate::convert::{Column, Value};
     use std::borrow::Cow;
 
@@ -948,6 +2002,21 @@ mod tests {
         assert_eq!(result.format_indicator, FormatIndicator::Ctx);
     }
 
+    #[test]
+    fn test_compress_without_ctx_fallback() {
+        let data = create_test_data_no_patterns();
+        let config = CompressorConfig::new().with_ctx_fallback_threshold(100.0);
+        assert!(AlsCompressor::with_config(config.clone()).compress(&data).unwrap().is_ctx());
+
+        let compressor = AlsCompressor::with_config(config.with_ctx_fallback(false));
+        assert!(!compressor.would_use_ctx_fallback(&data).unwrap());
+        assert!(compressor.compress(&data).unwrap().is_als());
+
+        let (doc, report) = compressor.compress_with_stats(&data).unwrap();
+        assert!(doc.is_als());
+        assert!(!report.used_ctx_fallback);
+    }
+
     #[test]
     fn test_compress_als_format() {
         let compressor = AlsCompressor::with_config(
@@ -1191,6 +2260,34 @@ mod tests {
         assert!(effectiveness >= 0.0 && effectiveness <= 100.0);
     }
 
+    #[test]
+    fn test_compress_with_stats_value_metrics() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "code",
+            ["AB123", "CD456", "AB123", "EF789"].iter().map(|s| Value::string(*s)).collect(),
+        ));
+        data.add_column(Column::new(
+            "note",
+            vec![Value::string("a"), Value::Null, Value::string("abc"), Value::string("abcdef")],
+        ));
+
+        let (_doc, report) = AlsCompressor::new().compress_with_stats(&data).unwrap();
+
+        let code = &report.columns[0];
+        assert_eq!(code.values.distinct_count, 3);
+        assert_eq!(code.values.min_value_length, 5);
+        assert_eq!(code.values.max_value_length, 5);
+        assert_eq!(code.values.mean_value_length, 5.0);
+        assert_eq!(code.values.median_value_length, 5);
+
+        let note = &report.columns[1];
+        assert_eq!(note.values.distinct_count, 4);
+        assert_eq!((note.values.min_value_length, note.values.max_value_length), (1, 6));
+        assert_eq!(note.values.median_value_length, 3);
+        assert_eq!(note.percentile_value_length(100.0), 6);
+    }
+
     #[test]
     fn test_compress_with_stats_patterns_detected() {
         let compressor = AlsCompressor::new();
@@ -1216,14 +2313,64 @@ mod tests {
     }
 
     #[test]
-    fn test_compress_json_empty() {
-        let compressor = AlsCompressor::new();
-        let json = "[]";
-        
+    fn test_compress_json_case_insensitive_keys() {
+        use crate::AlsParser;
+
+        let json = r#"[{"Name": "Alice"}, {"name": "Bob"}, {"NAME": "Carol"}]"#;
+
+        let als = AlsCompressor::new().compress_json(json).unwrap();
+        assert!(als.contains("#NAME") && als.contains("#Name") && als.contains("#name"));
+
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_case_insensitive_keys(true));
         let als = compressor.compress_json(json).unwrap();
-        
-        // Should handle empty JSON array
-        assert!(!als.is_empty());
+        assert!(als.contains("#Name"));
+        assert!(!als.contains("#name") && !als.contains("#NAME"));
+
+        // Decompression restores the first-seen spelling
+        let restored = AlsParser::new().to_json(&als).unwrap();
+        let restored: serde_json::Value = serde_json::from_str(&restored).unwrap();
+        assert_eq!(
+            restored,
+            serde_json::json!([{"Name": "Alice"}, {"Name": "Bob"}, {"Name": "Carol"}])
+        );
+    }
+
+    #[test]
+    fn test_compress_json_stable_column_order() {
+        let json = r#"[
+            {"status": "ok", "id": 1, "user": {"name": "Alice"}},
+            {"id": 2, "status": "ok", "region": "eu", "user": {"name": "Bob"}},
+            {"user": {"name": "Carol"}, "id": 3, "status": "failed"}
+        ]"#;
+
+        let compressor = AlsCompressor::new();
+        let als = compressor.compress_json(json).unwrap();
+        for _ in 0..10 {
+            assert_eq!(compressor.compress_json(json).unwrap(), als);
+        }
+
+        // Columns follow the first object, with later keys appended
+        let schema = als.lines().find(|line| line.starts_with('#')).unwrap();
+        assert_eq!(schema, "#status #id #user.name #region");
+
+        let sorted = AlsCompressor::with_config(
+            CompressorConfig::new().with_json_column_order(crate::JsonColumnOrder::Sorted),
+        );
+        let als = sorted.compress_json(json).unwrap();
+        let schema = als.lines().find(|line| line.starts_with('#')).unwrap();
+        assert_eq!(schema, "#id #region #status #user.name");
+    }
+
+    #[test]
+    fn test_compress_json_empty() {
+        let compressor = AlsCompressor::new();
+        let json = "[]";
+        
+        let als = compressor.compress_json(json).unwrap();
+        
+        // Should handle empty JSON array
+        assert!(!als.is_empty());
     }
 
     #[test]
@@ -1267,6 +2414,145 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // Input limit tests
+
+    fn limit_test_csv(rows: usize) -> String {
+        let mut csv = String::from("id,name\n");
+        for i in 0..rows {
+            csv.push_str(&format!("{},user{}\n", i, i));
+        }
+        csv
+    }
+
+    #[test]
+    fn test_max_cells_rejects_oversized_input() {
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_max_cells(10));
+        let csv = limit_test_csv(100);
+        let tsv = csv.replace(',', "\t");
+        let ndjson: String = (0..100)
+            .map(|i| format!("{{\"id\": {}, \"name\": \"user{}\"}}\n", i, i))
+            .collect();
+        let json = format!("[{}]", ndjson.trim_end().replace('\n', ","));
+
+        for result in [
+            compressor.compress_csv(&csv),
+            compressor.compress_tsv(&tsv),
+            compressor.compress_json(&json),
+            compressor.compress_ndjson(&ndjson),
+        ] {
+            match result {
+                Err(AlsError::InputLimitExceeded { limit, unit }) => {
+                    assert_eq!(limit, 10);
+                    assert_eq!(unit, "cells");
+                }
+                other => panic!("Expected InputLimitExceeded, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_cells_counts_null_padding() {
+        // Three objects with disjoint keys expand to a 3x3 table
+        let ndjson = "{\"a\": 1}\n{\"b\": 2}\n{\"c\": 3}\n";
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_max_cells(8));
+        assert!(matches!(
+            compressor.compress_ndjson(ndjson),
+            Err(AlsError::InputLimitExceeded { limit: 8, .. })
+        ));
+
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_max_cells(9));
+        assert!(compressor.compress_ndjson(ndjson).is_ok());
+    }
+
+    #[test]
+    fn test_max_input_bytes_rejects_before_parsing() {
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_max_input_bytes(64));
+
+        // Rejected on size alone, even though the input is not valid JSON
+        let result = compressor.compress_json(&"x".repeat(65));
+        match result {
+            Err(error @ AlsError::InputLimitExceeded { .. }) => {
+                assert_eq!(error.to_string(), "Input exceeds the configured limit of 64 bytes");
+            }
+            other => panic!("Expected InputLimitExceeded, got {:?}", other),
+        }
+        assert!(compressor.compress_csv(&limit_test_csv(100)).is_err());
+    }
+
+    #[test]
+    fn test_input_under_limits_is_unaffected() {
+        let csv = limit_test_csv(10);
+        let limited = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_max_input_bytes(csv.len())
+                .with_max_cells(20),
+        );
+
+        assert_eq!(
+            limited.compress_csv(&csv).unwrap(),
+            AlsCompressor::new().compress_csv(&csv).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compress_reader_matches_str() {
+        use std::io::Cursor;
+
+        let compressor = AlsCompressor::new();
+        for csv in [
+            "id,name,status\n1,Alice,ok\n2,Bob,ok\n3,Carol,failed\n4,Dan,ok\n".to_string(),
+            "id,note\r\n1,\"Smith, Jane\"\r\n2,\"said \"\"hi\"\"\"\r\n3,plain\r\n".to_string(),
+            limit_test_csv(50),
+            "  \n".to_string(),
+            String::new(),
+        ] {
+            assert_eq!(
+                compressor.compress_csv_reader(Cursor::new(csv.as_bytes())).unwrap(),
+                compressor.compress_csv(&csv).unwrap(),
+                "{csv:?}"
+            );
+        }
+
+        for json in [
+            r#"[{"id": 1, "user": {"name": "Alice"}}, {"id": 2, "tags": ["a", "b"]}]"#,
+            "[]",
+            " \n",
+        ] {
+            assert_eq!(
+                compressor.compress_json_reader(Cursor::new(json)).unwrap(),
+                compressor.compress_json(json).unwrap(),
+                "{json:?}"
+            );
+        }
+        assert!(compressor.compress_json_reader(Cursor::new("[] []")).is_err());
+        assert!(compressor.compress_json_reader(Cursor::new("[{\"id\": 1}")).is_err());
+    }
+
+    #[test]
+    fn test_compress_reader_enforces_max_input_bytes() {
+        use std::io::Cursor;
+
+        let csv = limit_test_csv(10);
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_max_input_bytes(csv.len()));
+        assert!(compressor.compress_csv_reader(Cursor::new(&csv)).is_ok());
+
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_max_input_bytes(csv.len() - 1));
+        assert!(matches!(
+            compressor.compress_csv_reader(Cursor::new(&csv)),
+            Err(AlsError::InputLimitExceeded { unit: "bytes", .. })
+        ));
+
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_max_input_bytes(64));
+        assert!(matches!(
+            compressor.compress_json_reader(Cursor::new("[".repeat(65))),
+            Err(AlsError::InputLimitExceeded { limit: 64, unit: "bytes" })
+        ));
+    }
+
     // Parallel compression tests
 
     #[test]
@@ -1282,7 +2568,7 @@ mod tests {
     fn test_would_use_parallel_large_data() {
         let compressor = AlsCompressor::new();
         
-        // Create large data that exceeds PARALLEL_THRESHOLD (1000)
+        // Create large data that exceeds the default parallel threshold (1000)
         let mut data = TabularData::new();
         let values: Vec<Value> = (0..600).map(|i| Value::Integer(i)).collect();
         data.add_column(Column::new(Cow::Owned("col1".to_string()), values.clone()));
@@ -1296,6 +2582,36 @@ mod tests {
         assert!(!compressor.would_use_parallel(&data));
     }
 
+    #[test]
+    fn test_would_use_parallel_low_threshold() {
+        let mut data = TabularData::new();
+        let values: Vec<Value> = (0..50).map(Value::Integer).collect();
+        data.add_column(Column::new(Cow::Owned("col1".to_string()), values.clone()));
+        data.add_column(Column::new(Cow::Owned("col2".to_string()), values));
+
+        // 50 rows * 2 columns = 100 elements, below the default threshold
+        assert!(!AlsCompressor::new().would_use_parallel(&data));
+
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_parallel_threshold(100));
+        #[cfg(feature = "parallel")]
+        assert!(compressor.would_use_parallel(&data));
+        #[cfg(not(feature = "parallel"))]
+        assert!(!compressor.would_use_parallel(&data));
+    }
+
+    #[test]
+    fn test_compress_sequential_matches_compress() {
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new().with_parallelism(4).with_parallel_threshold(0),
+        );
+        let data = create_test_data_with_patterns();
+        let serializer = AlsSerializer::new();
+        assert_eq!(
+            serializer.serialize(&compressor.compress_sequential(&data).unwrap()),
+            serializer.serialize(&compressor.compress(&data).unwrap())
+        );
+    }
+
     #[test]
     fn test_would_use_parallel_disabled_by_config() {
         // Explicitly disable parallelism
@@ -1393,6 +2709,167 @@ mod tests {
         assert_eq!(result.row_count(), 50);
     }
 
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_dictionary_matches_sequential() {
+        // Wide data with many equally frequent strings so ties must be ordered
+        let mut data = TabularData::new();
+        for c in 0..8 {
+            data.add_column(Column::new(
+                Cow::Owned(format!("col_{}", c)),
+                (0..400)
+                    .map(|i| Value::string_owned(format!("category_{}", (i * 7 + c) % 40)))
+                    .collect(),
+            ));
+        }
+
+        let sequential = AlsCompressor::with_config(CompressorConfig::new().with_parallelism(1));
+        let parallel = AlsCompressor::with_config(CompressorConfig::new().with_parallelism(4));
+        assert!(!sequential.would_use_parallel(&data));
+        assert!(parallel.would_use_parallel(&data));
+
+        let sequential_dictionary = sequential.build_dictionary(&data);
+        assert!(!sequential_dictionary.is_empty());
+        assert_eq!(parallel.build_dictionary(&data), sequential_dictionary);
+
+        let serializer = AlsSerializer::new();
+        assert_eq!(
+            serializer.serialize(&parallel.compress(&data).unwrap()),
+            serializer.serialize(&sequential.compress(&data).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_forced_scalar_matches_detected_output() {
+        use crate::config::SimdConfig;
+        use crate::simd::SimdLevel;
+
+        let mut csv = String::from("id,status,score\n");
+        for i in 0..200 {
+            let status = if i % 3 == 0 { "open" } else { "closed" };
+            csv.push_str(&format!("{},{},{}\n", i, status, i * i % 17));
+        }
+
+        let detected = AlsCompressor::new().compress_csv(&csv).unwrap();
+        let scalar = AlsCompressor::with_config(
+            CompressorConfig::new().with_simd_config(SimdConfig::new().with_forced_level(SimdLevel::Scalar)),
+        )
+        .compress_csv(&csv)
+        .unwrap();
+
+        assert_eq!(scalar, detected);
+    }
+
+    #[test]
+    fn test_forced_dictionary_encoding() {
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_honor_encoding_hints(true)
+                .with_encoding_hint("status", ColumnEncoding::Dict),
+        );
+        let data = create_test_data_with_patterns();
+
+        let doc = compressor.compress(&data).unwrap();
+
+        // Detection would pick `active*10`; the hint forces dictionary refs
+        assert!(doc.is_als());
+        assert_eq!(doc.default_dictionary().unwrap(), &vec!["active".to_string()]);
+        assert_eq!(doc.streams[1].operators, vec![AlsOperator::dict_ref(0); 10]);
+        assert_eq!(doc.encoding_hint("status"), Some(ColumnEncoding::Dict));
+        assert_eq!(doc.encoding_hint("id"), None);
+    }
+
+    #[test]
+    fn test_forced_dictionary_adds_unique_values() {
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_honor_encoding_hints(true)
+                .with_encoding_hint("name", ColumnEncoding::Dict),
+        );
+        let data = create_test_data_no_patterns();
+
+        let dictionary = compressor.build_dictionary(&data);
+        let mut buffers = CompressionBuffers::new();
+        let (stream, hint) = compressor.compress_column(&data.columns[0], &dictionary, &mut buffers).unwrap();
+
+        assert_eq!(dictionary, vec!["alice", "bob", "charlie", "david", "eve"]);
+        assert!(stream.operators.iter().all(|op| matches!(op, AlsOperator::DictRef(_))));
+        assert_eq!(hint, Some(ColumnEncoding::Dict));
+    }
+
+    #[test]
+    fn test_forced_range_falls_back_gracefully() {
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_honor_encoding_hints(true)
+                .with_encoding_hint("status", ColumnEncoding::Range),
+        );
+        let data = create_test_data_with_patterns();
+
+        // Strings cannot be a range, so normal detection takes over
+        let doc = compressor.compress(&data).unwrap();
+
+        assert_eq!(doc.streams[1].operators, vec![AlsOperator::multiply(AlsOperator::raw("active"), 10)]);
+        assert_eq!(doc.encoding_hint("status"), None);
+    }
+
+    #[test]
+    fn test_encoding_hints_ignored_when_disabled() {
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new().with_encoding_hint("status", ColumnEncoding::Dict),
+        );
+        let data = create_test_data_with_patterns();
+
+        let doc = compressor.compress(&data).unwrap();
+
+        assert_eq!(doc.streams[1].operators, vec![AlsOperator::multiply(AlsOperator::raw("active"), 10)]);
+        assert!(doc.encoding_hints.is_empty());
+    }
+
+    #[test]
+    fn test_compress_with_stats_honors_encoding_hints() {
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_honor_encoding_hints(true)
+                .with_encoding_hint("id", ColumnEncoding::Range)
+                .with_encoding_hint("status", ColumnEncoding::Dict),
+        );
+        let data = create_test_data_with_patterns();
+
+        let (doc, report) = compressor.compress_with_stats(&data).unwrap();
+
+        assert!(!report.used_ctx_fallback);
+        assert_eq!(doc.encoding_hint("id"), Some(ColumnEncoding::Range));
+        assert_eq!(doc.encoding_hint("status"), Some(ColumnEncoding::Dict));
+        assert_eq!(report.overall.ranges_used, 1);
+        assert_eq!(report.overall.multipliers_used, 0);
+        assert_eq!(report.overall.dict_refs_used, 10);
+    }
+
+    #[test]
+    fn test_compress_csv_round_trips_encoding_hints() {
+        use crate::als::AlsParser;
+
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_honor_encoding_hints(true)
+                .with_encoding_hint("id", ColumnEncoding::Range)
+                .with_encoding_hint("status", ColumnEncoding::Dict),
+        );
+        let mut csv = String::from("id,status\n");
+        for i in 1..=20 {
+            csv.push_str(&format!("{},active\n", i));
+        }
+
+        let als = compressor.compress_csv(&csv).unwrap();
+        assert!(als.contains("#id:range #status:dict\n"), "{}", als);
+
+        let parser = AlsParser::new();
+        let doc = parser.parse(&als).unwrap();
+        assert_eq!(doc.encoding_hint("status"), Some(ColumnEncoding::Dict));
+        assert_eq!(parser.to_csv(&als).unwrap().trim_end(), csv.trim_end());
+    }
+
     #[cfg(feature = "parallel")]
     #[test]
     fn test_compress_parallel_ctx_fallback() {
@@ -1414,4 +2891,769 @@ mod tests {
         // Should fall back to CTX due to high threshold
         assert!(result.is_ctx());
     }
+
+    fn string_column(name: &str, values: &[&str]) -> Column<'static> {
+        Column::new(
+            Cow::Owned(name.to_string()),
+            values.iter().map(|v| Value::string_owned(v.to_string())).collect(),
+        )
+    }
+
+    fn create_test_data_shared_values() -> TabularData<'static> {
+        let origin = [
+            "San Francisco", "Los Angeles", "San Francisco", "Sacramento", "Los Angeles",
+            "San Francisco", "Sacramento", "Sacramento", "San Francisco", "Los Angeles",
+            "Los Angeles", "San Francisco",
+        ];
+        let mut data = TabularData::new();
+        data.add_column(string_column("origin", &origin));
+        data.add_column(string_column("destination", &["San Francisco"; 12]));
+        data.add_column(string_column(
+            "hub",
+            &[&["Los Angeles"; 6][..], &["Sacramento"; 6][..]].concat(),
+        ));
+        data
+    }
+
+    #[test]
+    fn test_shared_dictionary_rewrites_patterns() {
+        let compressor = AlsCompressor::new();
+        let data = create_test_data_shared_values();
+
+        let doc = compressor.compress(&data).unwrap();
+
+        assert!(doc.is_als());
+        let dictionary = doc.default_dictionary().unwrap();
+        let index_of = |value: &str| dictionary.iter().position(|v| v == value).unwrap();
+
+        // The repeat and the run list reuse entries the origin column pays for
+        assert_eq!(
+            doc.streams[1].operators,
+            vec![AlsOperator::multiply(AlsOperator::dict_ref(index_of("San Francisco")), 12)]
+        );
+        assert_eq!(
+            doc.streams[2].operators,
+            vec![
+                AlsOperator::multiply(AlsOperator::dict_ref(index_of("Los Angeles")), 6),
+                AlsOperator::multiply(AlsOperator::dict_ref(index_of("Sacramento")), 6),
+            ]
+        );
+
+        // The document still round-trips
+        let serialized = AlsSerializer::new().serialize(&doc);
+        let parser = crate::als::AlsParser::new();
+        let rows = parser.expand(&parser.parse(&serialized).unwrap()).unwrap();
+        assert_eq!(rows.len(), 12);
+        assert_eq!(rows[3], vec!["Sacramento", "San Francisco", "Los Angeles"]);
+        assert_eq!(rows[11], vec!["San Francisco", "San Francisco", "Sacramento"]);
+    }
+
+    #[test]
+    fn test_shared_dictionary_beats_independent_compression() {
+        let compressor = AlsCompressor::new();
+        let serializer = AlsSerializer::new();
+        let data = create_test_data_shared_values();
+
+        let shared = serializer.serialize(&compressor.compress(&data).unwrap()).len();
+        let independent: usize = data
+            .columns
+            .iter()
+            .map(|column| {
+                let mut single = TabularData::new();
+                single.add_column(column.clone());
+                serializer.serialize(&compressor.compress(&single).unwrap()).len()
+            })
+            .sum();
+
+        assert!(
+            shared < independent,
+            "shared {} bytes, independent {} bytes",
+            shared,
+            independent
+        );
+    }
+
+    #[test]
+    fn test_unused_dictionary_entries_are_pruned() {
+        let compressor = AlsCompressor::new();
+        let mut data = TabularData::new();
+        data.add_column(string_column(
+            "direction",
+            &["northbound", "southbound"].repeat(10),
+        ));
+
+        // Both values are dictionary candidates, but the toggle keeps them raw
+        assert_eq!(compressor.build_dictionary(&data).len(), 2);
+
+        let doc = compressor.compress(&data).unwrap();
+        assert!(doc.is_als());
+        assert!(doc.default_dictionary().is_none());
+        assert_eq!(doc.streams[0].operators.iter().map(count_dict_refs).sum::<usize>(), 0);
+    }
+
+    #[test]
+    fn test_compress_with_stats_dictionary_utilization() {
+        let compressor = AlsCompressor::new();
+        let mut data = create_test_data_shared_values();
+        data.add_column(string_column(
+            "direction",
+            &["northbound", "southbound"].repeat(6),
+        ));
+
+        let (doc, report) = compressor.compress_with_stats(&data).unwrap();
+
+        // Three city entries are referenced, the two direction entries are not
+        assert_eq!(doc.default_dictionary().unwrap().len(), 3);
+        assert!((report.dictionary_utilization - 0.6).abs() < 1e-9);
+        assert_eq!(report.overall.dict_refs_used, 12 + 1 + 2);
+        assert_eq!(doc.streams, compressor.compress(&data).unwrap().streams);
+    }
+
+    /// A three-value status column in an irregular order, so no repeat,
+    /// toggle or run list pattern applies.
+    fn status_codes(count: usize) -> Vec<i64> {
+        let codes = [200, 404, 200, 500, 200, 200, 404];
+        (0..count).map(|i| codes[(i * 5 + i / 7) % codes.len()]).collect()
+    }
+
+    #[test]
+    fn test_int_dictionary_encodes_status_column() {
+        let compressor = AlsCompressor::new();
+        let statuses = status_codes(300);
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "status",
+            statuses.iter().map(|&s| Value::Integer(s)).collect(),
+        ));
+
+        let doc = compressor.compress(&data).unwrap();
+        assert!(doc.is_als());
+        assert_eq!(doc.streams[0].operators.len(), 1);
+        assert!(matches!(
+            &doc.streams[0].operators[0],
+            AlsOperator::IntDict { values, .. } if values == &vec![200, 404, 500]
+        ));
+
+        // Round trip reconstructs integers
+        let als = AlsSerializer::new().serialize(&doc);
+        let parser = crate::als::AlsParser::new();
+        let typed = parser.expand_typed(&parser.parse(&als).unwrap()).unwrap();
+        let expanded: Vec<i64> = typed.columns[0]
+            .values
+            .iter()
+            .map(|v| v.as_integer().unwrap())
+            .collect();
+        assert_eq!(expanded, statuses);
+    }
+
+    #[test]
+    fn test_int_dictionary_smaller_than_string_dict_refs() {
+        let compressor = AlsCompressor::new();
+        let statuses = status_codes(300);
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "status",
+            statuses.iter().map(|&s| Value::Integer(s)).collect(),
+        ));
+        let serializer = AlsSerializer::new();
+        let int_dict_size = serializer.serialize(&compressor.compress(&data).unwrap()).len();
+
+        // The same column as string dictionary references
+        let dictionary = vec!["200".to_string(), "404".to_string(), "500".to_string()];
+        let mut string_doc = AlsDocument::with_schema(vec!["status"]);
+        string_doc.add_dictionary("default", dictionary.clone());
+        string_doc.add_stream(ColumnStream::from_operators(
+            statuses
+                .iter()
+                .map(|s| {
+                    let index = dictionary.iter().position(|d| d == &s.to_string()).unwrap();
+                    AlsOperator::dict_ref(index)
+                })
+                .collect(),
+        ));
+        let string_dict_size = serializer.serialize(&string_doc).len();
+
+        // Two bits per value instead of a three-byte `_i ` reference
+        assert!(
+            int_dict_size * 4 < string_dict_size,
+            "int dict {} bytes, string dict {} bytes",
+            int_dict_size,
+            string_dict_size
+        );
+    }
+
+    #[test]
+    fn test_int_dictionary_in_compress_with_stats() {
+        let compressor = AlsCompressor::new();
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "status",
+            status_codes(300).into_iter().map(Value::Integer).collect(),
+        ));
+
+        let (doc, report) = compressor.compress_with_stats(&data).unwrap();
+
+        assert_eq!(report.columns[0].pattern_type, PatternType::IntDictionary);
+        assert_eq!(report.overall.dict_refs_used, 300);
+        assert_eq!(doc.streams, compressor.compress(&data).unwrap().streams);
+    }
+
+    #[test]
+    fn test_int_dictionary_not_used_for_sequences() {
+        let compressor = AlsCompressor::new();
+        let data = create_test_data_with_patterns();
+
+        let doc = compressor.compress(&data).unwrap();
+        assert!(doc
+            .streams
+            .iter()
+            .flat_map(|s| &s.operators)
+            .all(|op| !op.is_int_dict()));
+    }
+
+    #[test]
+    fn test_canonicalize_raw_values() {
+        use crate::als::{AlsParser, AlsSerializer};
+
+        let doc = AlsParser::new()
+            .parse("#id #status\n1 2 3 4 5 6|ok ok ok ok ok ok")
+            .unwrap();
+        let canonical = AlsCompressor::new().canonicalize(&doc).unwrap();
+
+        assert_eq!(canonical.schema, doc.schema);
+        assert!(matches!(canonical.streams[0].operators[..], [AlsOperator::Range { .. }]));
+        assert!(matches!(canonical.streams[1].operators[..], [AlsOperator::Multiply { .. }]));
+        assert_eq!(
+            AlsParser::new().expand(&canonical).unwrap(),
+            AlsParser::new().expand(&doc).unwrap()
+        );
+
+        let serializer = AlsSerializer::new();
+        assert!(serializer.serialize(&canonical).len() < serializer.serialize(&doc).len());
+    }
+
+    #[test]
+    fn test_canonicalize_optimal_document_unchanged() {
+        use crate::als::{AlsParser, AlsSerializer};
+
+        let compressor = AlsCompressor::new();
+        let als = compressor
+            .compress_csv("id,name,score\n1,alpha,1.50\n2,beta,\n3,alpha,2.50\n4,beta,1.50")
+            .unwrap();
+        let doc = AlsParser::new().parse(&als).unwrap();
+
+        let canonical = compressor.canonicalize(&doc).unwrap();
+        assert_eq!(AlsSerializer::new().serialize(&canonical), als);
+    }
+
+    #[test]
+    fn test_canonicalize_column_mismatch() {
+        let mut doc = AlsDocument::with_schema(vec!["a".to_string(), "b".to_string()]);
+        doc.add_stream(ColumnStream::from_operators(vec![AlsOperator::raw("1")]));
+        doc.add_stream(ColumnStream::from_operators(vec![
+            AlsOperator::raw("1"),
+            AlsOperator::raw("2"),
+        ]));
+
+        let result = AlsCompressor::new().canonicalize(&doc);
+        assert!(matches!(result, Err(AlsError::ColumnMismatch { .. })));
+    }
+
+    #[test]
+    fn test_disabled_toggle_detector_falls_back() {
+        let values: Vec<Value> = (0..12).map(|i| Value::string(if i % 2 == 0 { "on" } else { "off" })).collect();
+        let mut data = TabularData::new();
+        data.add_column(Column::new(Cow::Borrowed("state"), values));
+
+        let doc = AlsCompressor::new().compress(&data).unwrap();
+        assert!(doc.streams[0].operators[0].is_toggle());
+
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_disabled_detectors([PatternType::Toggle]),
+        );
+        let doc = compressor.compress(&data).unwrap();
+        assert!(doc.streams[0]
+            .operators
+            .iter()
+            .all(|op| op.is_raw() || op.is_dict_ref()), "{:?}", doc.streams[0]);
+        assert_eq!(doc.to_tabular().unwrap().columns[0].values, data.columns[0].values);
+    }
+
+    #[test]
+    fn test_all_detectors_disabled_yields_raw() {
+        let data = create_test_data_with_patterns();
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_ctx_fallback_threshold(1.0)
+                .with_disabled_detectors(PatternType::COMPRESSED),
+        );
+
+        for doc in [
+            compressor.compress(&data).unwrap(),
+            compressor.compress_with_stats(&data).unwrap().0,
+        ] {
+            for stream in &doc.streams {
+                assert!(
+                    stream.operators.iter().all(|op| op.is_raw() || op.is_dict_ref()),
+                    "{:?}",
+                    stream
+                );
+            }
+        }
+    }
+
+    /// Pattern-rich data where the combined detector and dictionary both pay off
+    fn create_effort_test_data() -> TabularData<'static> {
+        let mut data = TabularData::new();
+        let cycle: Vec<Value> = (0..60).map(|i| Value::Integer(i % 5 + 1)).collect();
+        let status: Vec<Value> = (0..60)
+            .map(|i| Value::string(["accepted", "rejected", "pending"][(i * 7 % 11) % 3]))
+            .collect();
+        let region: Vec<Value> = (0..60)
+            .map(|i| Value::string(["us-east-1", "eu-west-1"][(i * 5 % 13) % 2]))
+            .collect();
+        data.add_column(Column::new(Cow::Borrowed("cycle"), cycle));
+        data.add_column(Column::new(Cow::Borrowed("status"), status));
+        data.add_column(Column::new(Cow::Borrowed("region"), region));
+        data
+    }
+
+    #[test]
+    fn test_effort_zero_skips_dictionary_and_combined_detectors() {
+        let data = create_effort_test_data();
+
+        let doc = AlsCompressor::new().compress(&data).unwrap();
+        assert!(doc.default_dictionary().is_some());
+        assert!(doc.streams[0].operators[0].is_multiply());
+
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_effort(0));
+        assert!(compressor.build_dictionary(&data).is_empty());
+        let doc = compressor.compress(&data).unwrap();
+        assert!(doc.default_dictionary().is_none());
+        assert!(!doc.streams[0].operators[0].is_multiply());
+        assert_eq!(doc.to_tabular().unwrap().columns[1].values, data.columns[1].values);
+    }
+
+    #[test]
+    fn test_effort_ratio_increases_with_level() {
+        let data = create_effort_test_data();
+        let ratio = |effort| {
+            let compressor = AlsCompressor::with_config(
+                CompressorConfig::new()
+                    .with_ctx_fallback_threshold(1.0)
+                    .with_effort(effort),
+            );
+            let doc = compressor.compress(&data).unwrap();
+            compressor.calculate_compression_ratio(&data, &doc)
+        };
+
+        let fastest = ratio(0);
+        assert!(ratio(6) >= fastest);
+        assert!(ratio(9) >= fastest);
+        assert!(ratio(9) >= ratio(6));
+    }
+
+    #[test]
+    fn test_default_effort_matches_unset() {
+        let data = create_effort_test_data();
+        let default = AlsCompressor::new().compress(&data).unwrap();
+        let explicit = AlsCompressor::with_config(CompressorConfig::new().with_effort(6))
+            .compress(&data)
+            .unwrap();
+        assert_eq!(
+            AlsSerializer::new().serialize(&default),
+            AlsSerializer::new().serialize(&explicit)
+        );
+    }
+
+    #[test]
+    fn test_constant_column_compresses_to_single_multiply() {
+        let rows = 100_000;
+        let mut data = TabularData::new();
+        data.add_column(Column::new("status", vec![Value::string("active"); rows]));
+        data.add_column(Column::new("deleted_at", vec![Value::Null; rows]));
+
+        let compressor = AlsCompressor::new();
+        let start = std::time::Instant::now();
+        let doc = compressor.compress(&data).unwrap();
+        let elapsed = start.elapsed();
+
+        for stream in &doc.streams {
+            assert_eq!(stream.operators.len(), 1);
+            assert!(stream.operators[0].is_multiply());
+            assert_eq!(stream.operators[0].expanded_count(), rows);
+        }
+        // Generous bound so the check holds in debug builds on slow machines
+        assert!(elapsed < std::time::Duration::from_secs(5), "took {:?}", elapsed);
+        assert_eq!(doc.to_tabular().unwrap().columns[1].values, data.columns[1].values);
+    }
+
+    #[test]
+    fn test_constant_column_respects_disabled_repeat() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("status", vec![Value::string("active"); 10]));
+
+        let compressor = AlsCompressor::with_config(
+            CompressorConfig::new()
+                .with_ctx_fallback_threshold(1.0)
+                .with_disabled_detectors([PatternType::Repeat]),
+        );
+        let doc = compressor.compress(&data).unwrap();
+        assert!(!doc.streams[0].operators[0].is_multiply());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_roundtrip(data in crate::testutil::arb_tabular_data()) {
+            crate::testutil::assert_roundtrip(&data);
+        }
+
+        #[test]
+        fn prop_roundtrip_every_effort(data in crate::testutil::arb_tabular_data(), effort in 0..=9u8) {
+            let compressor = AlsCompressor::with_config(CompressorConfig::new().with_effort(effort));
+            crate::testutil::assert_roundtrip_with(&compressor, &data);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_special_strings() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "text",
+            vec![
+                Value::string("a|b"),
+                Value::string("1>5"),
+                Value::string("back\\slash"),
+                Value::string("line\nbreak"),
+                Value::string(""),
+                Value::Null,
+            ],
+        ));
+        data.add_column(Column::new("flag", vec![Value::Boolean(true); 6]));
+        crate::testutil::assert_roundtrip(&data);
+    }
+
+    #[test]
+    fn test_roundtrip_default_sentinel_strings() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "text",
+            vec![
+                Value::string("\\0"),
+                Value::string("\\e"),
+                Value::string("\\\\0"),
+                Value::Null,
+                Value::string(""),
+            ],
+        ));
+        crate::testutil::assert_roundtrip(&data);
+
+        let doc = AlsCompressor::new().compress(&data).unwrap();
+        let als = AlsSerializer::new().serialize(&doc);
+        let restored = AlsParser::new().parse(&als).unwrap().to_tabular().unwrap();
+        assert_eq!(restored.columns[0].values, data.columns[0].values);
+    }
+
+    #[test]
+    fn test_custom_sentinel_tokens_roundtrip() {
+        let tokens = SentinelTokens::new("NULL", "EMPTY");
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_sentinel_tokens(tokens.clone()));
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "text",
+            vec![
+                Value::string("\\0"),
+                Value::Null,
+                Value::string("NULL"),
+                Value::string(""),
+                Value::string("EMPTY"),
+                Value::string("\\NULL"),
+                Value::string("NULL"),
+                Value::string("NULL"),
+            ],
+        ));
+        data.add_column(Column::new("id", (1..=8).map(Value::Integer).collect()));
+        crate::testutil::assert_roundtrip_with(&compressor, &data);
+
+        let doc = compressor.compress(&data).unwrap();
+        assert_eq!(doc.sentinel_tokens, tokens);
+        let als = AlsSerializer::new().serialize(&doc);
+        assert!(als.contains("\n!null:NULL\n!empty:EMPTY\n"), "{}", als);
+
+        // The directives tell a default parser which tokens to read
+        let restored = AlsParser::new().parse(&als).unwrap().to_tabular().unwrap();
+        assert_eq!(restored.columns[0].values, data.columns[0].values);
+        assert_eq!(restored.columns[1].values, data.columns[1].values);
+    }
+
+    /// Compress CSV, apply `codec` and parse the bytes back.
+    fn roundtrip_post_codec(codec: crate::als::Codec) -> (Vec<u8>, String) {
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_post_codec(codec));
+        let csv = "id,status\n1,active\n2,inactive\n3,active\n4,inactive";
+        let als = compressor.compress_csv(csv).unwrap();
+        let bytes = compressor.apply_post_codec(&als).unwrap();
+        let parser = AlsParser::new();
+        let doc = parser.parse_bytes(&bytes).unwrap();
+        assert_eq!(doc, parser.parse(&als).unwrap());
+        (bytes, als)
+    }
+
+    #[test]
+    fn test_post_codec_none_keeps_text() {
+        let (bytes, als) = roundtrip_post_codec(crate::als::Codec::None);
+        assert_eq!(bytes, als.as_bytes());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_post_codec_gzip_roundtrip() {
+        let (bytes, _) = roundtrip_post_codec(crate::als::Codec::Gzip);
+        assert_eq!(bytes[0], crate::als::codec::GZIP_MAGIC);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_post_codec_zstd_roundtrip() {
+        let (bytes, _) = roundtrip_post_codec(crate::als::Codec::Zstd);
+        assert_eq!(bytes[0], crate::als::codec::ZSTD_MAGIC);
+    }
+
+    #[test]
+    fn test_roundtrip_empty_columns() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("nulls", vec![Value::Null; 4]));
+        data.add_column(Column::new("empty", vec![Value::string(""); 4]));
+        crate::testutil::assert_roundtrip(&data);
+
+        let mut no_rows = TabularData::new();
+        no_rows.add_column(Column::new("id", Vec::new()));
+        crate::testutil::assert_roundtrip(&no_rows);
+    }
+
+    #[test]
+    fn test_compress_collapses_duplicate_rows() {
+        use crate::als::AlsParser;
+
+        let mut csv = String::from("sensor,reading,status
+");
+        for i in 1..=10 {
+            csv.push_str(&format!("s{},{},ok\n", i, 20 + i));
+            if i == 4 {
+                // The sensor repeats its reading four more times
+                csv.push_str(&"s4,24,ok\n".repeat(4));
+            }
+        }
+
+        let compressor = AlsCompressor::with_config(CompressorConfig::new().with_collapse_duplicate_rows(true));
+        let als = compressor.compress_csv(&csv).unwrap();
+        assert!(als.contains("!rows:3*5\n"), "{}", als);
+
+        let parser = AlsParser::new();
+        let doc = parser.parse(&als).unwrap();
+        assert_eq!(doc.row_repeats, vec![(3, 5)]);
+        assert_eq!(doc.streams[0].expanded_count(), 10);
+        assert_eq!(doc.row_count(), 14);
+        assert_eq!(parser.to_csv(&als).unwrap(), csv);
+        assert_eq!(parser.parse_rows_lazy(&als).unwrap().count(), 14);
+
+        // Off by default
+        let plain = AlsCompressor::new().compress_csv(&csv).unwrap();
+        assert!(!plain.contains("!rows:"), "{}", plain);
+        assert_eq!(parser.to_csv(&plain).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_duplicate_row_runs() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("a", ["x", "x", "x", "y", "y", "x"].map(Value::string).to_vec()));
+        data.add_column(Column::new("b", ["1", "1", "2", "3", "3", "3"].map(Value::string).to_vec()));
+
+        let (kept_rows, repeats) = duplicate_row_runs(&data);
+        assert_eq!(kept_rows, vec![0, 2, 3, 5]);
+        assert_eq!(repeats, vec![(0, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_compress_with_buffers_matches_compress() {
+        let datasets = [
+            create_test_data_with_patterns(),
+            create_test_data_no_patterns(),
+            create_test_data_shared_values(),
+            create_effort_test_data(),
+            create_empty_data(),
+        ];
+        let sequential = AlsCompressor::with_config(CompressorConfig::new().with_parallelism(1));
+        let parallel = AlsCompressor::with_config(CompressorConfig::new().with_parallel_threshold(0));
+
+        // One set of buffers across every dataset, wide and narrow
+        let mut buffers = CompressionBuffers::new();
+        for compressor in [&sequential, &parallel] {
+            for data in &datasets {
+                let expected = compressor.compress(data).unwrap();
+                assert_eq!(compressor.compress_with_buffers(data, &mut buffers).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_compress_csv_ip_range_round_trip() {
+        use crate::als::AlsParser;
+
+        let mut csv = String::from("host,ip\n");
+        for i in 0..300 {
+            csv.push_str(&format!("web,10.0.{}.{}\n", i / 256, i % 256));
+        }
+        let als = AlsCompressor::new().compress_csv(&csv).unwrap();
+        assert!(als.contains("@ip4(167772160>167772459)"), "{}", als);
+        assert_eq!(AlsParser::new().to_csv(&als).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_compress_csv_numeric_oscillation_round_trip() {
+        use crate::als::AlsParser;
+
+        let mut csv = String::from("swing,level,delta\n");
+        for i in 0..41 {
+            let (swing, level, delta) = if i % 2 == 0 { ("10", "5", "-1.5") } else { ("-10", "7", "1.5") };
+            csv.push_str(&format!("{},{},{}\n", swing, level, delta));
+        }
+        let als = AlsCompressor::new().compress_csv(&csv).unwrap();
+        assert!(als.contains("10~-10*41|5~7*41|-1.5~1.5*41"), "{}", als);
+        assert_eq!(AlsParser::new().to_csv(&als).unwrap(), csv);
+    }
+
+    /// CSV of 120 eight-letter country codes next to a five-value status.
+    fn country_status_csv() -> String {
+        let mut state: u64 = 0x853c_49e6_748f_ea9b;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as usize
+        };
+        let countries: Vec<String> = (0..120)
+            .map(|_| (0..8).map(|_| (b'a' + (next() % 26) as u8) as char).collect())
+            .collect();
+        let statuses = ["ok", "warn", "fail", "skip", "late"];
+
+        let mut csv = String::from("country,status\n");
+        for _ in 0..2000 {
+            csv.push_str(&format!("{},{}\n", countries[next() % 120], statuses[next() % 5]));
+        }
+        csv
+    }
+
+    #[test]
+    fn test_column_stats_include_dictionary_share() {
+        let csv = country_status_csv();
+        let data = crate::convert::csv::parse_csv(&csv).unwrap();
+
+        // Each column owns its dictionary, so it is charged the whole line
+        let compressor = AlsCompressor::new();
+        let (doc, report) = compressor.compress_with_stats(&data).unwrap();
+        let serialized = AlsSerializer::new().serialize(&doc);
+        for (idx, column) in report.columns.iter().enumerate() {
+            let prefix = format!("${}:", doc.dictionary_name(idx));
+            let line = serialized.lines().find(|line| line.starts_with(&prefix)).unwrap();
+            assert_eq!(column.dictionary_bytes, line.len() + 1, "{}", column.name);
+            assert_eq!(
+                column.output_bytes,
+                compressor.estimate_stream_size(&doc.streams[idx]) + column.dictionary_bytes
+            );
+        }
+
+        // A shared dictionary is split by the entries each column uses
+        let compressor =
+            AlsCompressor::with_config(CompressorConfig::new().with_column_dictionaries(false));
+        let (doc, report) = compressor.compress_with_stats(&data).unwrap();
+        let serialized = AlsSerializer::new().serialize(&doc);
+        let line = serialized.lines().find(|line| line.starts_with("$default:")).unwrap();
+        let (country, status) = (&report.columns[0], &report.columns[1]);
+        assert!(country.dictionary_bytes > 10 * status.dictionary_bytes);
+        assert!(status.dictionary_bytes >= "|fail|warn|skip|late".len());
+        let total = country.dictionary_bytes + status.dictionary_bytes;
+        assert!(total.abs_diff(line.len() + 1) <= 1);
+
+        // Savings are net of the share
+        let stream_size = compressor.estimate_stream_size(&doc.streams[0]);
+        assert_eq!(
+            country.bytes_saved(),
+            country.input_bytes as i64 - (stream_size + country.dictionary_bytes) as i64
+        );
+    }
+
+    #[test]
+    fn test_compress_splits_disjoint_column_dictionaries() {
+        use crate::als::AlsParser;
+
+        let csv = country_status_csv();
+        let data = crate::convert::csv::parse_csv(&csv).unwrap();
+        let split = AlsCompressor::new().compress(&data).unwrap();
+        let single = AlsCompressor::with_config(CompressorConfig::new().with_column_dictionaries(false))
+            .compress(&data)
+            .unwrap();
+
+        assert_eq!(single.dictionaries.len(), 1);
+        assert_eq!(split.dictionaries.len(), 2);
+        assert_eq!(split.dictionary_name(0), "default");
+        assert_eq!(split.dictionary_name(1), "status");
+        // Only status values, so every status reference is a single digit
+        let statuses = split.column_dictionary(1).unwrap();
+        assert!(statuses.len() <= 5 && statuses.iter().all(|value| value.len() <= 4), "{:?}", statuses);
+
+        let serializer = AlsSerializer::new();
+        let (split_als, single_als) = (serializer.serialize(&split), serializer.serialize(&single));
+        assert!(split_als.contains("!dicts:1=status\n"), "{}", split_als);
+        assert!(split_als.len() < single_als.len(), "{} >= {}", split_als.len(), single_als.len());
+        assert_eq!(AlsParser::new().to_csv(&split_als).unwrap(), csv);
+        assert_eq!(AlsParser::new().to_csv(&single_als).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_compress_keeps_shared_dictionary_for_overlapping_columns() {
+        // Both columns draw from the same values, so they share one group
+        let mut csv = String::from("from,to\n");
+        for i in 0..200 {
+            let cities = ["amsterdam", "barcelona", "copenhagen", "dublin"];
+            csv.push_str(&format!("{},{}\n", cities[i * 7 % 4], cities[i * 3 % 4]));
+        }
+        let data = crate::convert::csv::parse_csv(&csv).unwrap();
+        let doc = AlsCompressor::new().compress(&data).unwrap();
+        assert!(doc.column_dictionaries.is_empty());
+        assert!(doc.dictionaries.len() <= 1);
+    }
+
+    #[test]
+    fn test_register_detector() {
+        use crate::als::{AlsOperator, AlsParser};
+
+        /// Encodes a column of hex colors as a toggle of itself.
+        struct ColorDetector;
+
+        impl PatternDetector for ColorDetector {
+            fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+                if !values.iter().all(|value| value.starts_with('#')) {
+                    return None;
+                }
+                let operator = AlsOperator::toggle_multi(values.to_vec(), values.len());
+                Some(DetectionResult::new(operator, 100.0, PatternType::Toggle))
+            }
+        }
+
+        let mut csv = String::from("color,id,status\n");
+        for i in 1..=40 {
+            csv.push_str(&format!("#{:06x},{},active\n", i * 40503, i));
+        }
+        let mut compressor = AlsCompressor::new();
+        compressor.register_detector(ColorDetector);
+        let als = compressor.compress_csv(&csv).unwrap();
+
+        let parser = AlsParser::new();
+        let doc = parser.parse(&als).unwrap();
+        assert!(matches!(doc.streams[0].operators[..], [AlsOperator::Toggle { .. }]), "{}", als);
+        assert!(matches!(doc.streams[1].operators[..], [AlsOperator::Range { .. }]), "{}", als);
+        assert_eq!(parser.to_csv(&als).unwrap(), csv);
+    }
 }
@@ -2,10 +2,12 @@
 //!
 //! This module provides the `DictionaryBuilder` which tracks string frequencies
 //! and builds optimal dictionaries for ALS compression. It also includes the
-//! `EnumDetector` for detecting columns with limited distinct values.
+//! `EnumDetector` for detecting columns with limited distinct values and the
+//! `IntDictionaryEncoder` for packing low-cardinality integer columns.
 
 use std::collections::HashMap;
 
+use crate::als::AlsOperator;
 use crate::config::CompressorConfig;
 
 /// Entry in the dictionary with frequency and compression benefit information.
@@ -136,6 +138,20 @@ impl DictionaryBuilder {
         self.add_all(values);
     }
 
+    /// Merge the frequencies tracked by another builder into this one.
+    ///
+    /// Used to combine per-column builders that were filled in parallel.
+    pub fn merge(&mut self, other: DictionaryBuilder) {
+        // Fold the smaller map into the larger one
+        let mut other = other.frequencies;
+        if other.len() > self.frequencies.len() {
+            std::mem::swap(&mut self.frequencies, &mut other);
+        }
+        for (value, frequency) in other {
+            *self.frequencies.entry(value).or_insert(0) += frequency;
+        }
+    }
+
     /// Get the frequency of a value.
     pub fn frequency(&self, value: &str) -> usize {
         self.frequencies.get(value).copied().unwrap_or(0)
@@ -176,8 +192,9 @@ impl DictionaryBuilder {
             .filter(|(_, &freq)| freq > 1)
             .collect();
 
-        // Sort by frequency (descending) to assign lower indices to more frequent values
-        candidates.sort_by(|a, b| b.1.cmp(a.1));
+        // Sort by frequency (descending) to assign lower indices to more frequent values,
+        // breaking ties by value so the order does not depend on hash map iteration
+        candidates.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
 
         // Calculate bytes saved for each candidate with their assigned index
         let mut entries: Vec<DictionaryEntry> = candidates
@@ -362,6 +379,79 @@ impl Default for EnumDetector {
     }
 }
 
+/// Encoder for integer enum columns such as status codes.
+///
+/// String dictionaries only hold string values, so numeric enums would
+/// otherwise be stored raw. This encoder keeps the distinct integers once
+/// and references them by index, packing each index into just enough bits
+/// to address the column's cardinality.
+#[derive(Debug, Clone)]
+pub struct IntDictionaryEncoder {
+    /// Maximum distinct values to encode.
+    max_distinct_values: usize,
+}
+
+impl IntDictionaryEncoder {
+    /// Default maximum distinct values for integer dictionary encoding.
+    pub const DEFAULT_MAX_DISTINCT: usize = 4096;
+
+    /// Create a new integer dictionary encoder with default settings.
+    pub fn new() -> Self {
+        Self {
+            max_distinct_values: Self::DEFAULT_MAX_DISTINCT,
+        }
+    }
+
+    /// Create a new encoder with a custom max distinct values limit.
+    pub fn with_max_distinct(max_distinct_values: usize) -> Self {
+        Self {
+            max_distinct_values,
+        }
+    }
+
+    /// Encode a column as an `IntDict` operator.
+    ///
+    /// Returns `None` unless every value is a canonical integer (so that it
+    /// expands back to the same text) and the column has between 2 and
+    /// `max_distinct_values` distinct values. Distinct values are stored in
+    /// ascending order.
+    pub fn encode(&self, values: &[&str]) -> Option<AlsOperator> {
+        let mut parsed = Vec::with_capacity(values.len());
+        let mut distinct: HashMap<i64, usize> = HashMap::new();
+        for &value in values {
+            let n = value.parse::<i64>().ok().filter(|n| n.to_string() == value)?;
+            distinct.entry(n).or_insert(0);
+            if distinct.len() > self.max_distinct_values {
+                return None;
+            }
+            parsed.push(n);
+        }
+        if distinct.len() < 2 {
+            return None;
+        }
+
+        let mut sorted: Vec<i64> = distinct.keys().copied().collect();
+        sorted.sort_unstable();
+        for (index, value) in sorted.iter().enumerate() {
+            distinct.insert(*value, index);
+        }
+
+        let indices = parsed.iter().map(|n| distinct[n]).collect();
+        Some(AlsOperator::int_dict(sorted, indices))
+    }
+
+    /// Get the maximum distinct values setting.
+    pub fn max_distinct_values(&self) -> usize {
+        self.max_distinct_values
+    }
+}
+
+impl Default for IntDictionaryEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -546,6 +636,37 @@ mod tests {
         assert!(builder.total_bytes_saved() > 0);
     }
 
+    #[test]
+    fn test_dictionary_builder_merge() {
+        let mut left = DictionaryBuilder::new();
+        left.add_all(["apple", "apple", "banana"]);
+        let mut right = DictionaryBuilder::new();
+        right.add_all(["banana", "cherry", "cherry", "cherry"]);
+
+        left.merge(right);
+
+        assert_eq!(left.frequency("apple"), 2);
+        assert_eq!(left.frequency("banana"), 2);
+        assert_eq!(left.frequency("cherry"), 3);
+        assert_eq!(left.distinct_count(), 3);
+    }
+
+    #[test]
+    fn test_dictionary_builder_ties_ordered_by_value() {
+        let values = ["value_d", "value_a", "value_c", "value_b"];
+        let mut builder = DictionaryBuilder::new();
+        for value in values {
+            for _ in 0..10 {
+                builder.add(value);
+            }
+        }
+
+        assert_eq!(
+            builder.build(),
+            vec!["value_a", "value_b", "value_c", "value_d"]
+        );
+    }
+
     #[test]
     fn test_dictionary_builder_max_entries() {
         let mut builder = DictionaryBuilder::with_max_entries(2);
@@ -771,11 +892,46 @@ mod tests {
         assert_eq!(dict.len(), 3);
     }
 
+    // IntDictionaryEncoder tests
+
+    #[test]
+    fn test_int_dictionary_encoder_encode() {
+        let encoder = IntDictionaryEncoder::new();
+        let values = vec!["404", "200", "200", "100", "404"];
+
+        let op = encoder.encode(&values).unwrap();
+        assert_eq!(
+            op,
+            AlsOperator::int_dict(vec![100, 200, 404], vec![2, 1, 1, 0, 2])
+        );
+        assert_eq!(op.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_int_dictionary_encoder_rejects_non_integers() {
+        let encoder = IntDictionaryEncoder::new();
+        assert!(encoder.encode(&["200", "OK", "404"]).is_none());
+        assert!(encoder.encode(&["1.5", "2.5"]).is_none());
+        // Non-canonical integers would not expand back to the same text
+        assert!(encoder.encode(&["007", "8"]).is_none());
+        assert!(encoder.encode(&["+1", "2"]).is_none());
+    }
+
+    #[test]
+    fn test_int_dictionary_encoder_cardinality_limits() {
+        let encoder = IntDictionaryEncoder::with_max_distinct(3);
+        assert_eq!(encoder.max_distinct_values(), 3);
+        assert!(encoder.encode(&["1", "1", "1"]).is_none());
+        assert!(encoder.encode(&["1", "2", "3"]).is_some());
+        assert!(encoder.encode(&["1", "2", "3", "4"]).is_none());
+    }
+
     #[test]
     fn test_types_are_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<DictionaryBuilder>();
         assert_send_sync::<DictionaryEntry>();
         assert_send_sync::<EnumDetector>();
+        assert_send_sync::<IntDictionaryEncoder>();
     }
 }
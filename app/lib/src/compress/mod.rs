@@ -3,10 +3,13 @@
 //! This module contains the dictionary builder, compressor, statistics tracking,
 //! and other compression utilities used to optimize ALS output.
 
+mod buffers;
 mod compressor;
 mod dictionary;
+mod reorder;
 mod stats;
 
+pub use buffers::CompressionBuffers;
 pub use compressor::AlsCompressor;
-pub use dictionary::{DictionaryBuilder, DictionaryEntry, EnumDetector};
-pub use stats::{ColumnStats, CompressionReport, CompressionStats, StatsSnapshot};
+pub use dictionary::{DictionaryBuilder, DictionaryEntry, EnumDetector, IntDictionaryEncoder};
+pub use stats::{ColumnStats, CompressionReport, CompressionStats, StatsSnapshot, ValueStats};
@@ -0,0 +1,99 @@
+//! Similarity-based column ordering.
+//!
+//! Columns that share many values compress better next to each other:
+//! their dictionary references cluster, and post-compression codecs find
+//! the repeats within a short window. [`similarity_order`] places each
+//! column after the one whose values overlap most with it, measured by the
+//! Jaccard similarity of the columns' value sets.
+
+use std::collections::HashSet;
+
+use crate::convert::{Column, TabularData};
+
+/// Leading rows of each column sampled into its value set.
+const SAMPLE_ROWS: usize = 4096;
+
+/// Compute a column order that keeps columns with similar values adjacent.
+///
+/// Starts from the first column and repeatedly appends the remaining column
+/// most similar to the last one placed. Ties go to the column that comes
+/// first in the input, so columns without any overlap keep their relative
+/// order. Returns `None` when the result is the input order.
+pub(crate) fn similarity_order(data: &TabularData) -> Option<Vec<usize>> {
+    let sets: Vec<HashSet<String>> = data.columns.iter().map(value_set).collect();
+    let mut remaining: Vec<usize> = (1..sets.len()).collect();
+    let mut order = Vec::with_capacity(sets.len());
+    order.push(0);
+
+    while !remaining.is_empty() {
+        let last = &sets[order[order.len() - 1]];
+        let mut best = 0;
+        let mut best_similarity = jaccard(last, &sets[remaining[0]]);
+        for (position, &index) in remaining.iter().enumerate().skip(1) {
+            let similarity = jaccard(last, &sets[index]);
+            if similarity > best_similarity {
+                best = position;
+                best_similarity = similarity;
+            }
+        }
+        order.push(remaining.remove(best));
+    }
+
+    let reordered = order.iter().enumerate().any(|(position, &index)| position != index);
+    reordered.then_some(order)
+}
+
+/// Collect the distinct non-null values of a column's leading rows.
+fn value_set(column: &Column) -> HashSet<String> {
+    column
+        .values
+        .iter()
+        .take(SAMPLE_ROWS)
+        .filter(|value| !value.is_null())
+        .map(|value| value.to_string_repr().into_owned())
+        .collect()
+}
+
+/// Jaccard similarity of two value sets, 0 when both are empty.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let (small, large) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let shared = small.iter().filter(|value| large.contains(*value)).count();
+    let union = a.len() + b.len() - shared;
+    if union == 0 {
+        0.0
+    } else {
+        shared as f64 / union as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::Value;
+
+    fn table(columns: &[(&str, &[&'static str])]) -> TabularData<'static> {
+        let mut data = TabularData::new();
+        for (name, values) in columns {
+            data.add_column(Column::new(name.to_string(), values.iter().map(|v| Value::string(v)).collect()));
+        }
+        data
+    }
+
+    #[test]
+    fn test_similar_columns_become_adjacent() {
+        let data = table(&[
+            ("id", &["1", "2", "3"]),
+            ("src", &["web", "db", "cache"]),
+            ("status", &["ok", "fail", "ok"]),
+            ("dst", &["db", "cache", "web"]),
+        ]);
+        assert_eq!(similarity_order(&data), Some(vec![0, 1, 3, 2]));
+    }
+
+    #[test]
+    fn test_unrelated_columns_keep_input_order() {
+        let data = table(&[("a", &["1", "2"]), ("b", &["x", "y"]), ("c", &["p", "q"])]);
+        assert_eq!(similarity_order(&data), None);
+        assert_eq!(similarity_order(&table(&[("a", &["1"])])), None);
+    }
+}
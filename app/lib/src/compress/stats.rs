@@ -63,6 +63,8 @@
 
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
+use crate::compress::dictionary::EnumDetector;
+use crate::convert::Value;
 use crate::pattern::PatternType;
 
 /// Thread-safe compression statistics.
@@ -176,19 +178,32 @@ impl CompressionStats {
         self.patterns_detected.fetch_add(1, Ordering::Relaxed);
         
         match pattern_type {
-            PatternType::Sequential | PatternType::Arithmetic => {
+            PatternType::Sequential
+            | PatternType::Arithmetic
+            | PatternType::SparseRange
+            | PatternType::PatchedRange
+            | PatternType::Delta
+            | PatternType::Geometric
+            | PatternType::Timestamp
+            | PatternType::IpRange => {
                 self.ranges_used.fetch_add(1, Ordering::Relaxed);
             }
-            PatternType::Repeat => {
+            PatternType::Repeat | PatternType::RunList => {
                 self.multipliers_used.fetch_add(1, Ordering::Relaxed);
             }
             PatternType::Toggle | PatternType::RepeatedToggle => {
                 self.toggles_used.fetch_add(1, Ordering::Relaxed);
             }
-            PatternType::RepeatedRange => {
+            PatternType::RepeatedRange | PatternType::Interleave => {
                 self.ranges_used.fetch_add(1, Ordering::Relaxed);
                 self.multipliers_used.fetch_add(1, Ordering::Relaxed);
             }
+            PatternType::IntDictionary => {
+                // Each packed index is counted by `record_dict_refs`
+            }
+            PatternType::Affix => {
+                // Counted only as a detected pattern
+            }
             PatternType::Raw => {
                 self.raw_values.fetch_add(1, Ordering::Relaxed);
             }
@@ -325,6 +340,7 @@ impl CompressionStats {
 /// in time. However, if other threads are actively updating the stats, individual
 /// counter values may be from slightly different moments.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StatsSnapshot {
     /// Total input bytes processed.
     pub input_bytes: u64,
@@ -375,6 +391,7 @@ impl StatsSnapshot {
 /// This struct is `Send + Sync` and can be safely shared across threads.
 /// It is an immutable value type with no interior mutability.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ColumnStats {
     /// Column name.
     pub name: String,
@@ -382,14 +399,25 @@ pub struct ColumnStats {
     pub index: usize,
     /// Input size in bytes for this column.
     pub input_bytes: usize,
-    /// Output size in bytes for this column.
+    /// Output size in bytes for this column, including its share of the
+    /// dictionary header (see `dictionary_bytes`).
     pub output_bytes: usize,
+    /// Bytes of the dictionary header apportioned to this column.
+    ///
+    /// Each entry's bytes are split across the columns that reference it,
+    /// in proportion to their number of references; the `$name:` prefix and
+    /// line break are split in proportion to those entry shares. Shares are
+    /// rounded to whole bytes.
+    pub dictionary_bytes: usize,
     /// Pattern type used for this column.
     pub pattern_type: PatternType,
     /// Number of values in the column.
     pub value_count: usize,
     /// Whether the column benefited from compression.
     pub was_compressed: bool,
+    /// Distinct values and value lengths of the column.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub values: ValueStats,
 }
 
 impl ColumnStats {
@@ -407,12 +435,36 @@ impl ColumnStats {
             index,
             input_bytes,
             output_bytes,
+            dictionary_bytes: 0,
             pattern_type,
             value_count,
             was_compressed: output_bytes < input_bytes,
+            values: ValueStats::default(),
         }
     }
 
+    /// Charge the column its share of the dictionary header, on top of the
+    /// bytes of its own stream.
+    pub fn with_dictionary_bytes(mut self, bytes: usize) -> Self {
+        self.output_bytes = self.output_bytes - self.dictionary_bytes + bytes;
+        self.dictionary_bytes = bytes;
+        self.was_compressed = self.output_bytes < self.input_bytes;
+        self
+    }
+
+    /// Attach the distinct value count and value lengths of the column.
+    pub fn with_values(mut self, values: ValueStats) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Length of the value at the given percentile (0 to 100).
+    ///
+    /// See [`ValueStats::percentile_value_length`].
+    pub fn percentile_value_length(&self, percentile: f64) -> usize {
+        self.values.percentile_value_length(percentile)
+    }
+
     /// Calculate the compression ratio for this column.
     pub fn compression_ratio(&self) -> f64 {
         if self.output_bytes > 0 {
@@ -428,6 +480,96 @@ impl ColumnStats {
     }
 }
 
+/// Distinct value count and value length distribution of a column.
+///
+/// Lengths are byte lengths of the values' text, so an empty string has
+/// length 0. Null values count as one distinct value but have no length.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ValueStats {
+    /// Number of distinct values.
+    pub distinct_count: usize,
+    /// Length of the shortest non-null value.
+    pub min_value_length: usize,
+    /// Length of the longest non-null value.
+    pub max_value_length: usize,
+    /// Mean length of the non-null values.
+    pub mean_value_length: f64,
+    /// Median length of the non-null values.
+    pub median_value_length: usize,
+    /// Number of non-null values of each length, ordered by length.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    length_counts: Vec<(usize, usize)>,
+}
+
+impl ValueStats {
+    /// Compute the statistics of a column's values.
+    pub fn from_values(values: &[Value]) -> Self {
+        let text: Vec<_> = values.iter().map(Value::to_string_repr).collect();
+        let refs: Vec<&str> = text.iter().map(|value| value.as_ref()).collect();
+        let distinct_count = EnumDetector::new().count_distinct(&refs);
+
+        let mut lengths: Vec<usize> = values
+            .iter()
+            .zip(&text)
+            .filter_map(|(value, text)| match value {
+                Value::Null => None,
+                Value::String(s) => Some(s.len()),
+                _ => Some(text.len()),
+            })
+            .collect();
+        lengths.sort_unstable();
+
+        let mut length_counts: Vec<(usize, usize)> = Vec::new();
+        for &length in &lengths {
+            match length_counts.last_mut() {
+                Some((last, count)) if *last == length => *count += 1,
+                _ => length_counts.push((length, 1)),
+            }
+        }
+
+        let mut stats = Self {
+            distinct_count,
+            min_value_length: lengths.first().copied().unwrap_or(0),
+            max_value_length: lengths.last().copied().unwrap_or(0),
+            mean_value_length: if lengths.is_empty() {
+                0.0
+            } else {
+                lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+            },
+            median_value_length: 0,
+            length_counts,
+        };
+        stats.median_value_length = stats.percentile_value_length(50.0);
+        stats
+    }
+
+    /// Length of the non-null value at the given percentile (0 to 100),
+    /// using the nearest-rank method.
+    ///
+    /// Returns 0 if the column has no non-null values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if percentile is not between 0 and 100.
+    pub fn percentile_value_length(&self, percentile: f64) -> usize {
+        assert!(
+            (0.0..=100.0).contains(&percentile),
+            "percentile must be between 0 and 100"
+        );
+        let total: usize = self.length_counts.iter().map(|(_, count)| count).sum();
+        let rank = ((percentile / 100.0 * total as f64).ceil() as usize).max(1);
+        let mut seen = 0;
+        for &(length, count) in &self.length_counts {
+            seen += count;
+            if seen >= rank {
+                return length;
+            }
+        }
+        0
+    }
+}
+
 /// Detailed compression report with per-column statistics.
 ///
 /// # Thread Safety
@@ -435,6 +577,7 @@ impl ColumnStats {
 /// This struct is `Send + Sync` and can be safely shared across threads.
 /// It is an immutable value type with no interior mutability.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CompressionReport {
     /// Overall statistics snapshot.
     pub overall: StatsSnapshot,
@@ -494,6 +637,15 @@ impl CompressionReport {
     pub fn compressed_column_count(&self) -> usize {
         self.columns.iter().filter(|c| c.was_compressed).count()
     }
+
+    /// Serialize the report to a JSON string for telemetry export.
+    ///
+    /// Pattern types are written as their stable snake_case names (see
+    /// [`PatternType::as_str`]).
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("compression reports always serialize to JSON")
+    }
 }
 
 #[cfg(test)]
@@ -723,6 +875,20 @@ mod tests {
         assert_eq!(stats.bytes_saved(), 75);
     }
 
+    #[test]
+    fn test_column_stats_dictionary_bytes() {
+        let stats = ColumnStats::new("test".to_string(), 0, 100, 60, PatternType::Raw, 10)
+            .with_dictionary_bytes(30);
+        assert_eq!(stats.output_bytes, 90);
+        assert_eq!(stats.bytes_saved(), 10);
+        assert!(stats.was_compressed);
+
+        // Replacing the share does not charge it twice
+        let stats = stats.with_dictionary_bytes(45);
+        assert_eq!(stats.output_bytes, 105);
+        assert!(!stats.was_compressed);
+    }
+
     #[test]
     fn test_column_stats_not_compressed() {
         let stats = ColumnStats::new(
@@ -738,6 +904,60 @@ mod tests {
         assert_eq!(stats.bytes_saved(), -50);
     }
 
+    #[test]
+    fn test_value_stats_uniform_lengths() {
+        let values: Vec<Value> = ["alpha", "bravo", "delta", "alpha"].iter().map(|s| Value::string(*s)).collect();
+        let stats = ValueStats::from_values(&values);
+
+        assert_eq!(stats.distinct_count, 3);
+        assert_eq!(stats.min_value_length, 5);
+        assert_eq!(stats.max_value_length, 5);
+        assert_eq!(stats.mean_value_length, 5.0);
+        assert_eq!(stats.median_value_length, 5);
+        assert_eq!(stats.percentile_value_length(99.0), 5);
+    }
+
+    #[test]
+    fn test_value_stats_mixed_lengths() {
+        let values = vec![
+            Value::Integer(7),
+            Value::Integer(1234),
+            Value::string(""),
+            Value::Null,
+            Value::string("abc"),
+            Value::Null,
+            Value::string("abcdefgh"),
+        ];
+        let stats = ValueStats::from_values(&values);
+
+        // Nulls are one distinct value and have no length
+        assert_eq!(stats.distinct_count, 6);
+        assert_eq!(stats.min_value_length, 0);
+        assert_eq!(stats.max_value_length, 8);
+        assert_eq!(stats.mean_value_length, 16.0 / 5.0);
+        assert_eq!(stats.median_value_length, 3);
+        assert_eq!(stats.percentile_value_length(0.0), 0);
+        assert_eq!(stats.percentile_value_length(80.0), 4);
+        assert_eq!(stats.percentile_value_length(100.0), 8);
+    }
+
+    #[test]
+    fn test_value_stats_without_values() {
+        let stats = ValueStats::from_values(&[Value::Null, Value::Null]);
+        assert_eq!(stats.distinct_count, 1);
+        assert_eq!(stats.max_value_length, 0);
+        assert_eq!(stats.mean_value_length, 0.0);
+        assert_eq!(stats.percentile_value_length(50.0), 0);
+
+        assert_eq!(ValueStats::from_values(&[]), ValueStats::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "percentile must be between 0 and 100")]
+    fn test_value_stats_rejects_invalid_percentile() {
+        ValueStats::default().percentile_value_length(101.0);
+    }
+
     #[test]
     fn test_compression_report() {
         let overall = StatsSnapshot {
@@ -819,6 +1039,39 @@ mod tests {
         assert_eq!(least_effective.name, "col2");
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_compression_report_to_json() {
+        let overall = StatsSnapshot {
+            input_bytes: 200,
+            output_bytes: 100,
+            patterns_detected: 2,
+            ranges_used: 1,
+            multipliers_used: 0,
+            toggles_used: 0,
+            dict_refs_used: 0,
+            raw_values: 0,
+            columns_processed: 2,
+            columns_compressed: 2,
+        };
+        let columns = vec![
+            ColumnStats::new("id".to_string(), 0, 100, 25, PatternType::Sequential, 10),
+            ColumnStats::new("status".to_string(), 1, 100, 75, PatternType::IntDictionary, 10),
+        ];
+        let report = CompressionReport::new(overall, columns, false, 0.5);
+
+        let json = report.to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["overall"]["input_bytes"], 200);
+        assert_eq!(value["overall"]["columns_compressed"], 2);
+        assert_eq!(value["used_ctx_fallback"], false);
+        assert_eq!(value["dictionary_utilization"], 0.5);
+        assert_eq!(value["columns"][0]["name"], "id");
+        assert_eq!(value["columns"][0]["pattern_type"], "sequential");
+        assert_eq!(value["columns"][1]["pattern_type"], "int_dictionary");
+        assert_eq!(value["columns"][1]["was_compressed"], true);
+    }
+
     #[test]
     fn test_stats_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
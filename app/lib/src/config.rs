@@ -1,7 +1,15 @@
 //! Configuration types for the ALS compression library.
 //!
 //! This module provides configuration structs for controlling compression behavior,
-//! SIMD optimization, parallelism, and security limits.
+//! SIMD optimization, parallelism, CSV dialects, and security limits.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::als::codec::Codec;
+use crate::als::{ColumnEncoding, SentinelTokens};
+use crate::convert::InputLimits;
+use crate::pattern::PatternType;
+use crate::simd::SimdLevel;
 
 /// Configuration for the ALS compressor.
 ///
@@ -17,6 +25,25 @@ pub struct CompressorConfig {
     /// Default: 1.2 (20% compression required)
     pub ctx_fallback_threshold: f64,
 
+    /// Fall back to CTX format when the ALS ratio is below
+    /// `ctx_fallback_threshold`.
+    ///
+    /// When disabled, compression always produces ALS output, even if it is
+    /// larger than the input.
+    ///
+    /// Default: true
+    pub ctx_fallback: bool,
+
+    /// Split the shared dictionary into one dictionary per group of columns
+    /// with disjoint values when that makes the output smaller.
+    ///
+    /// Each group indexes only its own entries, so a column with few
+    /// distinct values keeps short references even when another column
+    /// fills the dictionary.
+    ///
+    /// Default: true
+    pub column_dictionaries: bool,
+
     /// Size threshold for switching from HashMap to DashMap.
     ///
     /// When the expected number of dictionary entries exceeds this threshold,
@@ -35,6 +62,28 @@ pub struct CompressorConfig {
     /// Default: 3 values
     pub min_pattern_length: usize,
 
+    /// Maximum number of values in a toggle cycle.
+    ///
+    /// Longer cycles serialize nearly as large as the values themselves, so
+    /// they are left to the other detectors.
+    ///
+    /// Default: 8 values
+    pub max_toggle_cycle_length: usize,
+
+    /// Pattern types the compressor must not produce.
+    ///
+    /// Detectors whose pattern types are all disabled are skipped, and
+    /// results of a disabled type are discarded. Columns then fall back to
+    /// the best remaining pattern, dictionary references, or raw values.
+    /// Encodings forced by honored encoding hints are not affected.
+    ///
+    /// Repeated patterns are built from simpler ones, so disabling `Toggle`
+    /// also disables `RepeatedToggle`, and disabling `Sequential` or
+    /// `Arithmetic` also disables `RepeatedRange`.
+    ///
+    /// Default: empty (all detectors enabled)
+    pub disabled_detectors: HashSet<PatternType>,
+
     /// SIMD instruction set configuration.
     ///
     /// Controls which SIMD instruction sets are enabled for acceleration.
@@ -49,6 +98,17 @@ pub struct CompressorConfig {
     /// Default: 0 (auto)
     pub parallelism: usize,
 
+    /// Minimum number of values (columns × rows) before columns are
+    /// compressed in parallel.
+    ///
+    /// Smaller inputs are compressed sequentially, where splitting the work
+    /// across threads costs more than it saves. Lower it for data with a few
+    /// very expensive columns. Has no effect when `parallelism` is 1 or the
+    /// `parallel` feature is disabled.
+    ///
+    /// Default: 1,000 values
+    pub parallel_threshold: usize,
+
     /// Maximum number of values to expand from a single range operator.
     ///
     /// This security limit prevents memory exhaustion from malicious or
@@ -72,19 +132,194 @@ pub struct CompressorConfig {
     ///
     /// Default: 1,073,741,824 bytes (1 GB)
     pub max_input_size: usize,
+
+    /// Maximum number of input bytes accepted by `compress_csv`,
+    /// `compress_tsv`, `compress_json` and `compress_ndjson`.
+    ///
+    /// Input over the limit is rejected with
+    /// `AlsError::InputLimitExceeded` before it is parsed.
+    ///
+    /// Default: None (unlimited)
+    pub max_input_bytes: Option<usize>,
+
+    /// Maximum number of cells (rows times columns) those methods parse.
+    ///
+    /// Checked while parsing, so oversized input is rejected with
+    /// `AlsError::InputLimitExceeded` before all of its values are held
+    /// in memory.
+    ///
+    /// Default: None (unlimited)
+    pub max_cells: Option<usize>,
+
+    /// CSV dialect used when parsing CSV input.
+    ///
+    /// Default: comma-delimited, double-quoted fields
+    pub csv_config: CsvConfig,
+
+    /// Merge JSON keys that differ only in case (`Name` and `name`) into
+    /// one column when compressing JSON or NDJSON.
+    ///
+    /// The merged column is named with the first-seen spelling, which is
+    /// what decompression restores.
+    ///
+    /// Default: false
+    pub case_insensitive_keys: bool,
+
+    /// Order of the columns built from JSON or NDJSON keys.
+    ///
+    /// By default columns follow the key order of the first object, with
+    /// keys introduced by later objects appended, so the same input always
+    /// compresses to the same ALS text.
+    ///
+    /// Default: `JsonColumnOrder::FirstSeen`
+    pub json_column_order: JsonColumnOrder,
+
+    /// Apply `encoding_hints` instead of running pattern detection.
+    ///
+    /// Columns whose hinted encoding cannot represent their values fall
+    /// back to normal detection and log a warning.
+    ///
+    /// Default: false
+    pub honor_encoding_hints: bool,
+
+    /// Encodings to force for specific columns, keyed by column name.
+    ///
+    /// Only used when `honor_encoding_hints` is enabled. Honored hints are
+    /// recorded in the document schema (e.g. `#id:range`).
+    ///
+    /// Default: empty
+    pub encoding_hints: HashMap<String, ColumnEncoding>,
+
+    /// Record whether each numeric column held integers or floats as a
+    /// schema annotation (e.g. `#price:float`).
+    ///
+    /// Parsers with `ParserConfig::preserve_number_types` enabled use the
+    /// annotation to restore whole-number floats such as `1.0` as floats.
+    ///
+    /// Default: false
+    pub annotate_number_types: bool,
+
+    /// Write a `!crc<hex>` integrity checksum after the version header.
+    ///
+    /// Parsers verify the checksum when present and report corruption as
+    /// `AlsError::ChecksumMismatch`.
+    ///
+    /// Default: false
+    pub checksum: bool,
+
+    /// Write columns with overlapping value sets next to each other.
+    ///
+    /// The compressor orders columns by the similarity of their values and
+    /// records the permutation in an `!order:` directive, so parsers restore
+    /// the original column order. Adjacent similar streams help dictionary
+    /// locality and any post-compression codec. Streaming compression keeps
+    /// the input order.
+    ///
+    /// Default: false
+    pub column_reordering: bool,
+
+    /// Store runs of identical consecutive rows once.
+    ///
+    /// When every column repeats the previous row, the compressor keeps one
+    /// copy of the row and records its count in a `!rows:` directive, which
+    /// the parser expands across all columns. The collapsed document is
+    /// used only when it is smaller than compressing every row, as
+    /// per-column repeats already cover runs in a few columns.
+    /// [`AlsCompressor::compress_with_stats`](crate::AlsCompressor::compress_with_stats)
+    /// and streaming compression keep every row.
+    ///
+    /// Default: false
+    pub collapse_duplicate_rows: bool,
+
+    /// Write a `;; Generated by als-compression <version> at <time>` comment
+    /// line after the version header.
+    ///
+    /// The parser skips comments, so this only helps people reading the
+    /// document. It makes the output differ between runs.
+    ///
+    /// Default: false
+    pub generation_comment: bool,
+
+    /// Compression effort from 0 (fastest) to 9 (smallest output).
+    ///
+    /// Like gzip levels, effort is a single dial over several internal
+    /// parameters:
+    ///
+    /// - 0-1: no shared dictionary, and the combined (repeated range,
+    ///   repeated toggle, interleave) and delta detectors are skipped
+    /// - 2-3: the dictionary is built, but those detectors are still skipped
+    /// - 4-7: every enabled detector runs (the default behavior)
+    /// - 8-9: exhaustive detection; the affix detector also runs on columns
+    ///   that already matched a pattern, and a pattern is only kept when it
+    ///   serializes smaller than dictionary references or raw values
+    ///
+    /// No level runs [`AlsCompressor::canonicalize`]: compressing already
+    /// picks the encoding it would re-derive, so it is only useful on
+    /// documents from other writers and has to be called directly.
+    ///
+    /// [`AlsCompressor::canonicalize`]: crate::compress::AlsCompressor::canonicalize
+    ///
+    /// Default: 6
+    pub effort: u8,
+
+    /// Tokens written for null values and empty strings.
+    ///
+    /// Non-default tokens are declared in the document header so parsers
+    /// read them back, and data values that spell out a token are escaped.
+    ///
+    /// Default: [`SentinelTokens::DEFAULT`] (`\0` and `\e`)
+    pub sentinel_tokens: SentinelTokens,
+
+    /// Codec wrapping the serialized document in
+    /// `AlsCompressor::apply_post_codec`.
+    ///
+    /// Encoded output starts with a magic byte, so parsers detect the codec
+    /// on read.
+    ///
+    /// Default: [`Codec::None`] (plain ALS text)
+    pub post_codec: Codec,
 }
 
+/// Highest accepted compression effort.
+const MAX_EFFORT: u8 = 9;
+
+/// Default compression effort, which runs every enabled detector.
+const DEFAULT_EFFORT: u8 = 6;
+
+/// Default [`CompressorConfig::parallel_threshold`].
+const DEFAULT_PARALLEL_THRESHOLD: usize = 1000;
+
 impl Default for CompressorConfig {
     fn default() -> Self {
         Self {
             ctx_fallback_threshold: 1.2,
+            ctx_fallback: true,
+            column_dictionaries: true,
             hashmap_threshold: 10_000,
             min_pattern_length: 3,
+            max_toggle_cycle_length: 8,
+            disabled_detectors: HashSet::new(),
             simd_config: SimdConfig::default(),
             parallelism: 0, // auto-detect
+            parallel_threshold: DEFAULT_PARALLEL_THRESHOLD,
             max_range_expansion: 10_000_000,
             max_dictionary_entries: 65_536,
             max_input_size: 1_073_741_824, // 1 GB
+            max_input_bytes: None,
+            max_cells: None,
+            csv_config: CsvConfig::default(),
+            case_insensitive_keys: false,
+            json_column_order: JsonColumnOrder::FirstSeen,
+            honor_encoding_hints: false,
+            encoding_hints: HashMap::new(),
+            annotate_number_types: false,
+            checksum: false,
+            column_reordering: false,
+            collapse_duplicate_rows: false,
+            generation_comment: false,
+            effort: DEFAULT_EFFORT,
+            sentinel_tokens: SentinelTokens::DEFAULT,
+            post_codec: Codec::None,
         }
     }
 }
@@ -110,6 +345,18 @@ impl CompressorConfig {
         self
     }
 
+    /// Enable or disable the CTX fallback.
+    pub fn with_ctx_fallback(mut self, enabled: bool) -> Self {
+        self.ctx_fallback = enabled;
+        self
+    }
+
+    /// Enable or disable per-column dictionaries.
+    pub fn with_column_dictionaries(mut self, enabled: bool) -> Self {
+        self.column_dictionaries = enabled;
+        self
+    }
+
     /// Set the HashMap/DashMap size threshold.
     pub fn with_hashmap_threshold(mut self, threshold: usize) -> Self {
         self.hashmap_threshold = threshold;
@@ -122,6 +369,52 @@ impl CompressorConfig {
         self
     }
 
+    /// Set the maximum toggle cycle length.
+    pub fn with_max_toggle_cycle_length(mut self, length: usize) -> Self {
+        self.max_toggle_cycle_length = length;
+        self
+    }
+
+    /// Disable detection of the given pattern types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{AlsCompressor, CompressorConfig, PatternType};
+    ///
+    /// let config = CompressorConfig::new().with_disabled_detectors([PatternType::Toggle]);
+    /// let als = AlsCompressor::with_config(config).compress_csv("v\nx\ny\nx\ny\nx\ny").unwrap();
+    /// assert!(!als.contains('~'));
+    /// ```
+    pub fn with_disabled_detectors<I: IntoIterator<Item = PatternType>>(mut self, pattern_types: I) -> Self {
+        self.disabled_detectors.extend(pattern_types);
+        self
+    }
+
+    /// Check whether the compressor may produce the given pattern type.
+    ///
+    /// Besides `disabled_detectors`, efforts below 4 skip the combined and
+    /// delta detectors.
+    pub fn detector_enabled(&self, pattern_type: PatternType) -> bool {
+        let enabled = |pattern_type| !self.disabled_detectors.contains(&pattern_type);
+        let low_effort_skip = matches!(
+            pattern_type,
+            PatternType::RepeatedRange
+                | PatternType::RepeatedToggle
+                | PatternType::Interleave
+                | PatternType::Delta
+        );
+        enabled(pattern_type)
+            && (self.effort >= 4 || !low_effort_skip)
+            && match pattern_type {
+                PatternType::RepeatedToggle => enabled(PatternType::Toggle),
+                PatternType::RepeatedRange => {
+                    enabled(PatternType::Sequential) && enabled(PatternType::Arithmetic)
+                }
+                _ => true,
+            }
+    }
+
     /// Set the SIMD configuration.
     pub fn with_simd_config(mut self, config: SimdConfig) -> Self {
         self.simd_config = config;
@@ -134,6 +427,14 @@ impl CompressorConfig {
         self
     }
 
+    /// Set the minimum number of values compressed in parallel.
+    ///
+    /// See [`CompressorConfig::parallel_threshold`].
+    pub fn with_parallel_threshold(mut self, threshold: usize) -> Self {
+        self.parallel_threshold = threshold;
+        self
+    }
+
     /// Set the maximum range expansion limit.
     pub fn with_max_range_expansion(mut self, max: usize) -> Self {
         self.max_range_expansion = max;
@@ -151,6 +452,148 @@ impl CompressorConfig {
         self.max_input_size = max;
         self
     }
+
+    /// Set the maximum number of input bytes the `compress_*` methods accept.
+    pub fn with_max_input_bytes(mut self, max: usize) -> Self {
+        self.max_input_bytes = Some(max);
+        self
+    }
+
+    /// Set the maximum number of cells the `compress_*` methods parse.
+    pub fn with_max_cells(mut self, max: usize) -> Self {
+        self.max_cells = Some(max);
+        self
+    }
+
+    /// Set the CSV dialect used when parsing CSV input.
+    pub fn with_csv_config(mut self, config: CsvConfig) -> Self {
+        self.csv_config = config;
+        self
+    }
+
+    /// Set how CSV rows with a different number of fields than the header
+    /// are handled, keeping the rest of the CSV dialect.
+    pub fn with_ragged_row_policy(mut self, policy: RaggedRowPolicy) -> Self {
+        self.csv_config.ragged_rows = policy;
+        self
+    }
+
+    /// Enable or disable case-insensitive merging of JSON keys.
+    pub fn with_case_insensitive_keys(mut self, enabled: bool) -> Self {
+        self.case_insensitive_keys = enabled;
+        self
+    }
+
+    /// Set the order of columns built from JSON keys.
+    pub fn with_json_column_order(mut self, order: JsonColumnOrder) -> Self {
+        self.json_column_order = order;
+        self
+    }
+
+    /// Enable or disable honoring per-column encoding hints.
+    pub fn with_honor_encoding_hints(mut self, honor: bool) -> Self {
+        self.honor_encoding_hints = honor;
+        self
+    }
+
+    /// Add an encoding hint for the named column.
+    pub fn with_encoding_hint<S: Into<String>>(mut self, column: S, encoding: ColumnEncoding) -> Self {
+        self.encoding_hints.insert(column.into(), encoding);
+        self
+    }
+
+    /// Enable or disable number type annotations in the schema.
+    pub fn with_annotate_number_types(mut self, annotate: bool) -> Self {
+        self.annotate_number_types = annotate;
+        self
+    }
+
+    /// Enable or disable the integrity checksum in serialized output.
+    pub fn with_checksum(mut self, checksum: bool) -> Self {
+        self.checksum = checksum;
+        self
+    }
+
+    /// Enable or disable similarity-based column reordering.
+    ///
+    /// See [`CompressorConfig::column_reordering`].
+    pub fn with_column_reordering(mut self, reorder: bool) -> Self {
+        self.column_reordering = reorder;
+        self
+    }
+
+    /// Enable or disable collapsing runs of identical rows.
+    ///
+    /// See [`CompressorConfig::collapse_duplicate_rows`].
+    pub fn with_collapse_duplicate_rows(mut self, collapse: bool) -> Self {
+        self.collapse_duplicate_rows = collapse;
+        self
+    }
+
+    /// Enable or disable the generation comment.
+    ///
+    /// See [`CompressorConfig::generation_comment`].
+    pub fn with_generation_comment(mut self, comment: bool) -> Self {
+        self.generation_comment = comment;
+        self
+    }
+
+    /// Set the compression effort.
+    ///
+    /// See [`CompressorConfig::effort`] for what each level changes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if effort is greater than 9.
+    pub fn with_effort(mut self, effort: u8) -> Self {
+        assert!(effort <= MAX_EFFORT, "effort must be between 0 and 9");
+        self.effort = effort;
+        self
+    }
+
+    /// Set the tokens written for null values and empty strings.
+    pub fn with_sentinel_tokens(mut self, tokens: SentinelTokens) -> Self {
+        self.sentinel_tokens = tokens;
+        self
+    }
+
+    /// Set the codec wrapping serialized output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if support for the codec was not compiled in.
+    pub fn with_post_codec(mut self, codec: Codec) -> Self {
+        assert!(codec.is_available(), "codec requires its crate feature to be enabled");
+        self.post_codec = codec;
+        self
+    }
+
+    /// Whether the effort level builds a shared dictionary.
+    pub(crate) fn builds_dictionary(&self) -> bool {
+        self.effort >= 2
+    }
+
+    /// Whether the effort level compares every detector exhaustively.
+    pub(crate) fn exhaustive_detection(&self) -> bool {
+        self.effort >= 8
+    }
+
+    /// Get the input limits enforced while parsing text input.
+    pub(crate) fn input_limits(&self) -> InputLimits {
+        InputLimits {
+            max_input_bytes: self.max_input_bytes,
+            max_cells: self.max_cells,
+        }
+    }
+
+    /// Get the encoding hint for a column, if hints are honored.
+    pub fn encoding_hint(&self, column: &str) -> Option<ColumnEncoding> {
+        if self.honor_encoding_hints {
+            self.encoding_hints.get(column).copied()
+        } else {
+            None
+        }
+    }
 }
 
 /// Configuration for the ALS parser.
@@ -196,6 +639,81 @@ pub struct ParserConfig {
     ///
     /// Default: 1,073,741,824 bytes (1 GB)
     pub max_input_size: usize,
+
+    /// CSV dialect used when writing CSV output.
+    ///
+    /// Default: comma-delimited, double-quoted fields
+    pub csv_config: CsvConfig,
+
+    /// Maximum nesting depth of parenthesized groups in a column stream.
+    ///
+    /// This security limit prevents stack overflows from malicious ALS
+    /// documents such as `((((...))))`.
+    ///
+    /// Default: 64 levels
+    pub max_nesting_depth: usize,
+
+    /// Maximum number of values in a single toggle operator (`a~b~c...`).
+    ///
+    /// This security limit prevents memory exhaustion from malicious or
+    /// malformed ALS documents with very long toggles.
+    ///
+    /// Default: 4,096 values
+    pub max_toggle_values: usize,
+
+    /// Recover from syntax errors in column streams instead of failing.
+    ///
+    /// When enabled, malformed operators are replaced with raw placeholders
+    /// holding their source text. See `AlsParser::parse_lenient`.
+    ///
+    /// Default: false
+    pub lenient: bool,
+
+    /// Convert values using the number type annotations in the schema
+    /// (e.g. `#price:float`) when expanding to typed data.
+    ///
+    /// With this enabled, a float column keeps `1.0` as a float in JSON
+    /// output instead of turning it into the integer `1`. Columns without
+    /// an annotation are typed from their text as usual.
+    ///
+    /// Default: false
+    pub preserve_number_types: bool,
+
+    /// Write timestamp values as ISO-8601 UTC text (`2023-01-01T00:00:00Z`)
+    /// instead of raw epoch seconds in CSV and JSON output.
+    ///
+    /// Only affects columns typed as timestamps, which are named
+    /// `timestamp`, `ts` or `time` and hold plausible epochs, or annotated
+    /// `:epoch` with `preserve_number_types` enabled.
+    ///
+    /// Default: false
+    pub iso8601_timestamps: bool,
+
+    /// Tokens read as null values and empty strings in documents that do
+    /// not declare their own with `!null:` and `!empty:` directives.
+    ///
+    /// Default: [`SentinelTokens::DEFAULT`] (`\0` and `\e`)
+    pub sentinel_tokens: SentinelTokens,
+
+    /// Keep the text of `;;` comment lines in `AlsDocument::comments`.
+    ///
+    /// Comments never affect the decoded data; this only records them so
+    /// they can be inspected or written back out.
+    ///
+    /// Default: false
+    pub preserve_comments: bool,
+
+    /// Maximum number of values expanding a document may produce, summed
+    /// over all of its columns.
+    ///
+    /// This security limit stops decompression bombs: a few bytes such as
+    /// `1>1000000000*1000` can expand to billions of values. Each operator's
+    /// value count is checked against the limit before the operator is
+    /// expanded, so expansion fails with `AlsError::OutputTooLarge` before
+    /// the memory is allocated. Recommended for untrusted input.
+    ///
+    /// Default: None (no limit)
+    pub max_output_values: Option<usize>,
 }
 
 impl Default for ParserConfig {
@@ -206,11 +724,26 @@ impl Default for ParserConfig {
             max_range_expansion: 10_000_000,
             max_dictionary_entries: 65_536,
             max_input_size: 1_073_741_824, // 1 GB
+            csv_config: CsvConfig::default(),
+            max_nesting_depth: Self::DEFAULT_MAX_NESTING_DEPTH,
+            max_toggle_values: Self::DEFAULT_MAX_TOGGLE_VALUES,
+            lenient: false,
+            preserve_number_types: false,
+            iso8601_timestamps: false,
+            sentinel_tokens: SentinelTokens::DEFAULT,
+            preserve_comments: false,
+            max_output_values: None,
         }
     }
 }
 
 impl ParserConfig {
+    /// Default maximum nesting depth of groups in a column stream.
+    pub const DEFAULT_MAX_NESTING_DEPTH: usize = 64;
+
+    /// Default maximum number of values in a single toggle operator.
+    pub const DEFAULT_MAX_TOGGLE_VALUES: usize = 4096;
+
     /// Create a new configuration with default values.
     pub fn new() -> Self {
         Self::default()
@@ -245,6 +778,158 @@ impl ParserConfig {
         self.max_input_size = max;
         self
     }
+
+    /// Set the CSV dialect used when writing CSV output.
+    pub fn with_csv_config(mut self, config: CsvConfig) -> Self {
+        self.csv_config = config;
+        self
+    }
+
+    /// Set the maximum nesting depth of groups.
+    pub fn with_max_nesting_depth(mut self, depth: usize) -> Self {
+        self.max_nesting_depth = depth;
+        self
+    }
+
+    /// Set the maximum number of values in a single toggle.
+    pub fn with_max_toggle_values(mut self, max: usize) -> Self {
+        self.max_toggle_values = max;
+        self
+    }
+
+    /// Enable or disable recovery from column stream syntax errors.
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Enable or disable typing values by their schema number type.
+    pub fn with_preserve_number_types(mut self, preserve: bool) -> Self {
+        self.preserve_number_types = preserve;
+        self
+    }
+
+    /// Enable or disable writing timestamps as ISO-8601 text.
+    pub fn with_iso8601_timestamps(mut self, iso8601: bool) -> Self {
+        self.iso8601_timestamps = iso8601;
+        self
+    }
+
+    /// Set the tokens read for null values and empty strings in documents
+    /// without sentinel directives.
+    pub fn with_sentinel_tokens(mut self, tokens: SentinelTokens) -> Self {
+        self.sentinel_tokens = tokens;
+        self
+    }
+
+    /// Enable or disable keeping the text of comment lines.
+    pub fn with_preserve_comments(mut self, preserve: bool) -> Self {
+        self.preserve_comments = preserve;
+        self
+    }
+
+    /// Set the maximum number of values expanding a document may produce.
+    pub fn with_max_output_values(mut self, max: usize) -> Self {
+        self.max_output_values = Some(max);
+        self
+    }
+}
+
+/// How CSV parsing handles rows whose field count differs from the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RaggedRowPolicy {
+    /// Fail with `AlsError::CsvParseError` reporting the row's line.
+    #[default]
+    Error,
+    /// Fill missing trailing fields with nulls and drop extra fields.
+    PadNull,
+    /// Drop extra fields and skip rows that are missing fields.
+    Truncate,
+}
+
+/// Order of the columns built from the keys of JSON objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonColumnOrder {
+    /// Keys in the order they first appear, starting with the first object.
+    #[default]
+    FirstSeen,
+    /// Keys sorted alphabetically.
+    Sorted,
+}
+
+/// CSV dialect configuration.
+///
+/// Controls the field delimiter and quote character used when reading and
+/// writing CSV. Fields containing the delimiter, the quote character, or
+/// line breaks are quoted, and quote characters inside quoted fields are
+/// escaped by doubling them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvConfig {
+    /// Field delimiter.
+    ///
+    /// Default: `b','`
+    pub delimiter: u8,
+
+    /// Quote character for fields containing special characters.
+    ///
+    /// Default: `b'"'`
+    pub quote: u8,
+
+    /// Handling of rows with more or fewer fields than the header.
+    ///
+    /// Default: `RaggedRowPolicy::Error`
+    pub ragged_rows: RaggedRowPolicy,
+}
+
+impl Default for CsvConfig {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            ragged_rows: RaggedRowPolicy::Error,
+        }
+    }
+}
+
+impl CsvConfig {
+    /// Create a new CSV configuration with comma delimiters and double quotes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the field delimiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the delimiter is not ASCII or is a line break.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        assert!(
+            delimiter.is_ascii() && delimiter != b'\n' && delimiter != b'\r',
+            "CSV delimiter must be an ASCII character other than a line break"
+        );
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the quote character is not ASCII or is a line break.
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        assert!(
+            quote.is_ascii() && quote != b'\n' && quote != b'\r',
+            "CSV quote must be an ASCII character other than a line break"
+        );
+        self.quote = quote;
+        self
+    }
+
+    /// Set how rows with a different number of fields than the header are handled.
+    pub fn with_ragged_rows(mut self, policy: RaggedRowPolicy) -> Self {
+        self.ragged_rows = policy;
+        self
+    }
 }
 
 /// SIMD instruction set configuration.
@@ -277,6 +962,15 @@ pub struct SimdConfig {
     ///
     /// Default: true
     pub enable_neon: bool,
+
+    /// Force a specific SIMD level instead of picking the best available one.
+    ///
+    /// The forced level overrides the `enable_*` flags, but is only used when
+    /// the CPU supports it; otherwise the normal selection applies. Mainly
+    /// useful for benchmarking and for comparing against the scalar path.
+    ///
+    /// Default: None (automatic selection)
+    pub forced_level: Option<SimdLevel>,
 }
 
 impl Default for SimdConfig {
@@ -285,6 +979,7 @@ impl Default for SimdConfig {
             enable_avx512: true,
             enable_avx2: true,
             enable_neon: true,
+            forced_level: None,
         }
     }
 }
@@ -304,6 +999,7 @@ impl SimdConfig {
             enable_avx512: false,
             enable_avx2: false,
             enable_neon: false,
+            forced_level: None,
         }
     }
 
@@ -325,6 +1021,16 @@ impl SimdConfig {
         self
     }
 
+    /// Force the given SIMD level when the CPU supports it.
+    ///
+    /// `SimdDispatcher::with_config` logs a warning and falls back to
+    /// automatic selection if the level is unsupported, while
+    /// `SimdDispatcher::try_with_config` returns an error instead.
+    pub fn with_forced_level(mut self, level: SimdLevel) -> Self {
+        self.forced_level = Some(level);
+        self
+    }
+
     /// Check if any SIMD instruction set is enabled.
     pub fn is_any_enabled(&self) -> bool {
         self.enable_avx512 || self.enable_avx2 || self.enable_neon
@@ -341,10 +1047,14 @@ mod tests {
         assert_eq!(config.ctx_fallback_threshold, 1.2);
         assert_eq!(config.hashmap_threshold, 10_000);
         assert_eq!(config.min_pattern_length, 3);
+        assert_eq!(config.max_toggle_cycle_length, 8);
         assert_eq!(config.parallelism, 0);
+        assert_eq!(config.parallel_threshold, 1000);
         assert_eq!(config.max_range_expansion, 10_000_000);
         assert_eq!(config.max_dictionary_entries, 65_536);
         assert_eq!(config.max_input_size, 1_073_741_824);
+        assert_eq!(config.max_input_bytes, None);
+        assert_eq!(config.max_cells, None);
     }
 
     #[test]
@@ -353,7 +1063,9 @@ mod tests {
             .with_ctx_fallback_threshold(1.5)
             .with_hashmap_threshold(5_000)
             .with_min_pattern_length(5)
+            .with_max_toggle_cycle_length(16)
             .with_parallelism(4)
+            .with_parallel_threshold(50)
             .with_max_range_expansion(1_000_000)
             .with_max_dictionary_entries(10_000)
             .with_max_input_size(500_000_000);
@@ -361,12 +1073,127 @@ mod tests {
         assert_eq!(config.ctx_fallback_threshold, 1.5);
         assert_eq!(config.hashmap_threshold, 5_000);
         assert_eq!(config.min_pattern_length, 5);
+        assert_eq!(config.max_toggle_cycle_length, 16);
         assert_eq!(config.parallelism, 4);
+        assert_eq!(config.parallel_threshold, 50);
         assert_eq!(config.max_range_expansion, 1_000_000);
         assert_eq!(config.max_dictionary_entries, 10_000);
         assert_eq!(config.max_input_size, 500_000_000);
     }
 
+    #[test]
+    fn test_compressor_config_input_limits() {
+        assert_eq!(CompressorConfig::new().input_limits(), InputLimits::NONE);
+
+        let config = CompressorConfig::new()
+            .with_max_input_bytes(4096)
+            .with_max_cells(1000);
+        assert_eq!(config.max_input_bytes, Some(4096));
+        assert_eq!(config.max_cells, Some(1000));
+        assert_eq!(
+            config.input_limits(),
+            InputLimits {
+                max_input_bytes: Some(4096),
+                max_cells: Some(1000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_compressor_config_encoding_hints() {
+        let config = CompressorConfig::new().with_encoding_hint("id", ColumnEncoding::Range);
+        assert!(!config.honor_encoding_hints);
+        assert_eq!(config.encoding_hint("id"), None);
+
+        let config = config.with_honor_encoding_hints(true);
+        assert_eq!(config.encoding_hint("id"), Some(ColumnEncoding::Range));
+        assert_eq!(config.encoding_hint("name"), None);
+    }
+
+    #[test]
+    fn test_compressor_config_case_insensitive_keys() {
+        assert!(!CompressorConfig::new().case_insensitive_keys);
+        assert!(CompressorConfig::new().with_case_insensitive_keys(true).case_insensitive_keys);
+    }
+
+    #[test]
+    fn test_compressor_config_json_column_order() {
+        assert_eq!(CompressorConfig::new().json_column_order, JsonColumnOrder::FirstSeen);
+        let config = CompressorConfig::new().with_json_column_order(JsonColumnOrder::Sorted);
+        assert_eq!(config.json_column_order, JsonColumnOrder::Sorted);
+    }
+
+    #[test]
+    fn test_compressor_config_effort() {
+        let config = CompressorConfig::new();
+        assert_eq!(config.effort, 6);
+        assert!(config.builds_dictionary());
+        assert!(!config.exhaustive_detection());
+        assert!(config.detector_enabled(PatternType::Delta));
+
+        let config = CompressorConfig::new().with_effort(0);
+        assert!(!config.builds_dictionary());
+        for pattern_type in [
+            PatternType::RepeatedRange,
+            PatternType::RepeatedToggle,
+            PatternType::Interleave,
+            PatternType::Delta,
+        ] {
+            assert!(!config.detector_enabled(pattern_type));
+        }
+        assert!(config.detector_enabled(PatternType::Sequential));
+
+        let config = CompressorConfig::new().with_effort(3);
+        assert!(config.builds_dictionary());
+        assert!(!config.detector_enabled(PatternType::Interleave));
+
+        let config = CompressorConfig::new().with_effort(9);
+        assert!(config.exhaustive_detection());
+        assert!(config.detector_enabled(PatternType::RepeatedRange));
+    }
+
+    #[test]
+    #[should_panic(expected = "effort must be between 0 and 9")]
+    fn test_compressor_config_invalid_effort() {
+        CompressorConfig::new().with_effort(10);
+    }
+
+    #[test]
+    fn test_compressor_config_ctx_fallback() {
+        assert!(CompressorConfig::new().ctx_fallback);
+        assert!(!CompressorConfig::new().with_ctx_fallback(false).ctx_fallback);
+    }
+
+    #[test]
+    fn test_compressor_config_column_dictionaries() {
+        assert!(CompressorConfig::new().column_dictionaries);
+        assert!(!CompressorConfig::new().with_column_dictionaries(false).column_dictionaries);
+    }
+
+    #[test]
+    fn test_compressor_config_checksum() {
+        assert!(!CompressorConfig::new().checksum);
+        assert!(CompressorConfig::new().with_checksum(true).checksum);
+    }
+
+    #[test]
+    fn test_compressor_config_column_reordering() {
+        assert!(!CompressorConfig::new().column_reordering);
+        assert!(CompressorConfig::new().with_column_reordering(true).column_reordering);
+    }
+
+    #[test]
+    fn test_compressor_config_collapse_duplicate_rows() {
+        assert!(!CompressorConfig::new().collapse_duplicate_rows);
+        assert!(CompressorConfig::new().with_collapse_duplicate_rows(true).collapse_duplicate_rows);
+    }
+
+    #[test]
+    fn test_compressor_config_generation_comment() {
+        assert!(!CompressorConfig::new().generation_comment);
+        assert!(CompressorConfig::new().with_generation_comment(true).generation_comment);
+    }
+
     #[test]
     #[should_panic(expected = "CTX fallback threshold must be >= 1.0")]
     fn test_compressor_config_invalid_threshold() {
@@ -380,6 +1207,11 @@ mod tests {
         assert_eq!(config.max_range_expansion, 10_000_000);
         assert_eq!(config.max_dictionary_entries, 65_536);
         assert_eq!(config.max_input_size, 1_073_741_824);
+        assert_eq!(config.max_nesting_depth, 64);
+        assert_eq!(config.max_toggle_values, 4096);
+        assert!(!config.lenient);
+        assert!(!config.preserve_comments);
+        assert_eq!(config.max_output_values, None);
     }
 
     #[test]
@@ -388,12 +1220,66 @@ mod tests {
             .with_parallelism(8)
             .with_max_range_expansion(5_000_000)
             .with_max_dictionary_entries(32_768)
-            .with_max_input_size(2_000_000_000);
+            .with_max_input_size(2_000_000_000)
+            .with_max_nesting_depth(8)
+            .with_max_toggle_values(16)
+            .with_max_output_values(1_000)
+            .with_lenient(true);
 
         assert_eq!(config.parallelism, 8);
         assert_eq!(config.max_range_expansion, 5_000_000);
         assert_eq!(config.max_dictionary_entries, 32_768);
         assert_eq!(config.max_input_size, 2_000_000_000);
+        assert_eq!(config.max_nesting_depth, 8);
+        assert_eq!(config.max_toggle_values, 16);
+        assert_eq!(config.max_output_values, Some(1_000));
+        assert!(config.lenient);
+    }
+
+    #[test]
+    fn test_csv_config_default() {
+        let config = CsvConfig::default();
+        assert_eq!(config.delimiter, b',');
+        assert_eq!(config.quote, b'"');
+        assert_eq!(config.ragged_rows, RaggedRowPolicy::Error);
+        assert_eq!(CompressorConfig::default().csv_config, config);
+        assert_eq!(ParserConfig::default().csv_config, config);
+    }
+
+    #[test]
+    fn test_csv_config_builder() {
+        let csv = CsvConfig::new().with_delimiter(b';').with_quote(b'\'');
+        assert_eq!(csv.delimiter, b';');
+        assert_eq!(csv.quote, b'\'');
+
+        assert_eq!(CompressorConfig::new().with_csv_config(csv).csv_config, csv);
+        assert_eq!(ParserConfig::new().with_csv_config(csv).csv_config, csv);
+
+        let config = CompressorConfig::new()
+            .with_csv_config(csv)
+            .with_ragged_row_policy(RaggedRowPolicy::PadNull);
+        assert_eq!(config.csv_config.ragged_rows, RaggedRowPolicy::PadNull);
+        assert_eq!(config.csv_config.delimiter, b';');
+        assert_eq!(CsvConfig::new().with_ragged_rows(RaggedRowPolicy::Truncate).ragged_rows, RaggedRowPolicy::Truncate);
+    }
+
+    #[test]
+    #[should_panic(expected = "CSV delimiter must be an ASCII character")]
+    fn test_csv_config_invalid_delimiter() {
+        CsvConfig::new().with_delimiter(b'\n');
+    }
+
+    #[test]
+    fn test_compressor_config_disabled_detectors() {
+        let config = CompressorConfig::new();
+        assert!(PatternType::COMPRESSED.iter().all(|&t| config.detector_enabled(t)));
+
+        let config = CompressorConfig::new().with_disabled_detectors([PatternType::Toggle, PatternType::Arithmetic]);
+        assert!(!config.detector_enabled(PatternType::Toggle));
+        assert!(!config.detector_enabled(PatternType::RepeatedToggle));
+        assert!(!config.detector_enabled(PatternType::RepeatedRange));
+        assert!(config.detector_enabled(PatternType::Sequential));
+        assert!(config.detector_enabled(PatternType::Repeat));
     }
 
     #[test]
@@ -438,12 +1324,22 @@ mod tests {
         assert!(config.is_any_enabled());
     }
 
+    #[test]
+    fn test_simd_config_forced_level() {
+        assert_eq!(SimdConfig::default().forced_level, None);
+
+        let config = SimdConfig::new().with_forced_level(SimdLevel::Scalar);
+        assert_eq!(config.forced_level, Some(SimdLevel::Scalar));
+        assert_ne!(config, SimdConfig::new());
+    }
+
     #[test]
     fn test_config_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<CompressorConfig>();
         assert_send_sync::<ParserConfig>();
         assert_send_sync::<SimdConfig>();
+        assert_send_sync::<CsvConfig>();
     }
 
     #[test]
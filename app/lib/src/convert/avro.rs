@@ -0,0 +1,466 @@
+//! Avro writing.
+//!
+//! This module converts `TabularData` structures to Avro object container
+//! files. The record schema is inferred from the column types, every row is
+//! written as one record, and records are grouped into uncompressed blocks.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+
+use serde_json::{json, Value as Json};
+
+use crate::convert::{
+    boolean_like_value, plain_text, unscaled_decimals, Column, ColumnType, TabularData, Value,
+};
+use crate::error::Result;
+
+/// Magic bytes opening an Avro object container file.
+const MAGIC: &[u8] = b"Obj\x01";
+
+/// Most decimal digits a decimal field can hold, the digits of an `i128`.
+const MAX_DECIMAL_PRECISION: usize = 38;
+
+/// Number of records written per block.
+const BLOCK_ROWS: usize = 4096;
+
+/// Convert `TabularData` to an Avro object container file and write it to
+/// `writer`.
+///
+/// The schema is a record named `Row` with one field per column, typed from
+/// [`Column::infer_type`]:
+///
+/// | Column type       | Avro type                                   |
+/// |-------------------|---------------------------------------------|
+/// | `Integer`         | `long`                                      |
+/// | `Float`           | `double`                                    |
+/// | `Decimal`         | `bytes` with logical type `decimal`         |
+/// | `Timestamp`       | `long` with logical type `timestamp-millis` |
+/// | `Boolean`         | `boolean`                                   |
+/// | `String`, `Mixed` | `string`                                    |
+///
+/// Columns holding a null become a union of `null` and their type, and a
+/// column with only nulls is a nullable `string`. Decimal columns need every
+/// value written with the same number of fraction digits to become Avro
+/// decimals. Those that do not, and any column with a value that does not
+/// fit its type, are written as strings. Column names that are not valid
+/// Avro names have their other characters replaced with `_`.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::avro::to_avro;
+/// use als_compression::convert::csv::parse_csv;
+///
+/// let data = parse_csv("id,name\n1,Alice\n2,Bob").unwrap();
+/// let mut avro = Vec::new();
+/// to_avro(&data, &mut avro).unwrap();
+/// assert!(avro.starts_with(b"Obj\x01"));
+/// ```
+pub fn to_avro<W: Write>(data: &TabularData, mut writer: W) -> Result<()> {
+    let mut names = HashSet::new();
+    let fields: Vec<Field> = data
+        .columns
+        .iter()
+        .map(|column| Field::from_column(column, &mut names))
+        .collect();
+    let schema = json!({
+        "type": "record",
+        "name": "Row",
+        "fields": fields.iter().map(Field::schema).collect::<Vec<_>>(),
+    });
+    let sync = sync_marker();
+
+    let mut header = Vec::from(MAGIC);
+    write_long(&mut header, 2);
+    write_bytes(&mut header, b"avro.schema");
+    write_bytes(&mut header, schema.to_string().as_bytes());
+    write_bytes(&mut header, b"avro.codec");
+    write_bytes(&mut header, b"null");
+    write_long(&mut header, 0);
+    header.extend_from_slice(&sync);
+    writer.write_all(&header)?;
+
+    let mut positions = vec![0; fields.len()];
+    let mut block = Vec::new();
+    let mut start = 0;
+    while start < data.row_count {
+        let end = (start + BLOCK_ROWS).min(data.row_count);
+        block.clear();
+        for row in start..end {
+            for ((field, column), position) in fields.iter().zip(&data.columns).zip(&mut positions) {
+                field.write_value(&mut block, column.values[row].is_null(), position);
+            }
+        }
+
+        let mut prefix = Vec::new();
+        write_long(&mut prefix, (end - start) as i64);
+        write_long(&mut prefix, block.len() as i64);
+        writer.write_all(&prefix)?;
+        writer.write_all(&block)?;
+        writer.write_all(&sync)?;
+        start = end;
+    }
+    Ok(())
+}
+
+/// A column converted to Avro values, ready to be written.
+struct Field {
+    /// Avro field name.
+    name: String,
+    /// Whether the field is a union with `null`.
+    nullable: bool,
+    /// Non-null values in row order.
+    values: FieldValues,
+}
+
+/// Non-null values of a column in their Avro type.
+enum FieldValues {
+    Long(Vec<i64>),
+    Double(Vec<f64>),
+    Decimal { scale: usize, unscaled: Vec<i128> },
+    TimestampMillis(Vec<i64>),
+    Boolean(Vec<bool>),
+    String(Vec<String>),
+}
+
+impl Field {
+    /// Convert a column using the Avro type for its inferred type, falling
+    /// back to strings when a value does not fit.
+    fn from_column(column: &Column, names: &mut HashSet<String>) -> Self {
+        let non_null = || column.values.iter().filter(|value| !value.is_null());
+
+        let typed = match column.infer_type() {
+            ColumnType::Integer => non_null()
+                .map(Value::as_integer)
+                .collect::<Option<Vec<_>>>()
+                .map(FieldValues::Long),
+            ColumnType::Float => non_null()
+                .map(Value::as_float)
+                .collect::<Option<Vec<_>>>()
+                .map(FieldValues::Double),
+            ColumnType::Decimal => unscaled_decimals(non_null(), MAX_DECIMAL_PRECISION)
+                .map(|(scale, unscaled)| FieldValues::Decimal { scale, unscaled }),
+            ColumnType::Timestamp => non_null()
+                .map(|value| value.as_timestamp()?.checked_mul(1000))
+                .collect::<Option<Vec<_>>>()
+                .map(FieldValues::TimestampMillis),
+            ColumnType::Boolean => non_null()
+                .map(boolean_like_value)
+                .collect::<Option<Vec<_>>>()
+                .map(FieldValues::Boolean),
+            ColumnType::String | ColumnType::Mixed => None,
+        };
+
+        Self {
+            name: field_name(&column.name, names),
+            nullable: column.values.iter().any(Value::is_null),
+            values: typed
+                .unwrap_or_else(|| FieldValues::String(non_null().map(plain_text).collect())),
+        }
+    }
+
+    /// The field's entry in the record schema.
+    fn schema(&self) -> Json {
+        let value_type = match &self.values {
+            FieldValues::Long(_) => json!("long"),
+            FieldValues::Double(_) => json!("double"),
+            FieldValues::Decimal { scale, .. } => json!({
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": MAX_DECIMAL_PRECISION,
+                "scale": scale,
+            }),
+            FieldValues::TimestampMillis(_) => json!({
+                "type": "long",
+                "logicalType": "timestamp-millis",
+            }),
+            FieldValues::Boolean(_) => json!("boolean"),
+            FieldValues::String(_) => json!("string"),
+        };
+
+        if self.nullable {
+            json!({ "name": self.name, "type": ["null", value_type], "default": null })
+        } else {
+            json!({ "name": self.name, "type": value_type })
+        }
+    }
+
+    /// Write the field of one record, taking the next non-null value at
+    /// `position` unless the row is null.
+    fn write_value(&self, output: &mut Vec<u8>, is_null: bool, position: &mut usize) {
+        if self.nullable {
+            // Union branch: 0 for null, 1 for the value type
+            write_long(output, i64::from(!is_null));
+        }
+        if is_null {
+            return;
+        }
+
+        let index = *position;
+        *position += 1;
+        match &self.values {
+            FieldValues::Long(values) | FieldValues::TimestampMillis(values) => {
+                write_long(output, values[index])
+            }
+            FieldValues::Double(values) => output.extend_from_slice(&values[index].to_le_bytes()),
+            FieldValues::Decimal { unscaled, .. } => {
+                write_bytes(output, &twos_complement(unscaled[index]))
+            }
+            FieldValues::Boolean(values) => output.push(u8::from(values[index])),
+            FieldValues::String(values) => write_bytes(output, values[index].as_bytes()),
+        }
+    }
+}
+
+/// A valid, unique Avro name for a column.
+///
+/// Avro names start with a letter or `_` and contain only letters, digits
+/// and `_`; other characters become `_`. Repeated names get a numeric
+/// suffix.
+fn field_name(column: &str, names: &mut HashSet<String>) -> String {
+    let mut name: String = column
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+
+    let mut unique = name.clone();
+    let mut suffix = 2;
+    while !names.insert(unique.clone()) {
+        unique = format!("{}_{}", name, suffix);
+        suffix += 1;
+    }
+    unique
+}
+
+/// Write a `long` as a zig-zag variable-length integer.
+fn write_long(output: &mut Vec<u8>, value: i64) {
+    let mut zigzag = ((value << 1) ^ (value >> 63)) as u64;
+    while zigzag >= 0x80 {
+        output.push((zigzag as u8) | 0x80);
+        zigzag >>= 7;
+    }
+    output.push(zigzag as u8);
+}
+
+/// Write `bytes` or a `string` as its length followed by its bytes.
+fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(output, bytes.len() as i64);
+    output.extend_from_slice(bytes);
+}
+
+/// Shortest big-endian two's-complement encoding of a decimal's unscaled
+/// value.
+fn twos_complement(value: i128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let sign = if value < 0 { 0xFF } else { 0x00 };
+    // Drop leading sign bytes while the next byte still carries the sign bit
+    let start = (0..bytes.len() - 1)
+        .find(|&i| bytes[i] != sign || (bytes[i + 1] & 0x80) != (sign & 0x80))
+        .unwrap_or(bytes.len() - 1);
+    bytes[start..].to_vec()
+}
+
+/// A random 16-byte marker separating the blocks of a file.
+fn sync_marker() -> [u8; 16] {
+    let state = RandomState::new();
+    let mut marker = [0; 16];
+    for (seed, half) in marker.chunks_mut(8).enumerate() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(seed);
+        half.copy_from_slice(&hasher.finish().to_le_bytes());
+    }
+    marker
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal reader for the files `to_avro` writes: returns the schema and
+    /// the records, with each field decoded to JSON.
+    fn read_back(avro: &[u8]) -> (Json, Vec<Vec<Json>>) {
+        let mut input = avro.strip_prefix(MAGIC).unwrap();
+        let mut schema = None;
+        let entries = read_long(&mut input);
+        for _ in 0..entries {
+            let key = read_bytes(&mut input);
+            let value = read_bytes(&mut input);
+            if key == b"avro.schema" {
+                schema = Some(serde_json::from_slice::<Json>(&value).unwrap());
+            }
+        }
+        assert_eq!(read_long(&mut input), 0);
+        let schema = schema.unwrap();
+        let (sync, mut input) = input.split_at(16);
+
+        let fields = schema["fields"].as_array().unwrap();
+        let mut records = Vec::new();
+        while !input.is_empty() {
+            let count = read_long(&mut input);
+            read_long(&mut input); // block size
+            for _ in 0..count {
+                records.push(fields.iter().map(|field| read_value(&mut input, &field["type"])).collect());
+            }
+            let (marker, rest) = input.split_at(16);
+            assert_eq!(marker, sync);
+            input = rest;
+        }
+        (schema, records)
+    }
+
+    fn read_value(input: &mut &[u8], schema: &Json) -> Json {
+        if let Some(branches) = schema.as_array() {
+            let branch = read_long(input) as usize;
+            return read_value(input, &branches[branch]);
+        }
+        let (type_name, logical) = match schema {
+            Json::String(name) => (name.as_str(), None),
+            _ => (schema["type"].as_str().unwrap(), schema["logicalType"].as_str()),
+        };
+        match (type_name, logical) {
+            ("null", _) => Json::Null,
+            ("long", Some("timestamp-millis")) => json!({ "millis": read_long(input) }),
+            ("long", _) => json!(read_long(input)),
+            ("double", _) => {
+                let (bytes, rest) = input.split_at(8);
+                *input = rest;
+                json!(f64::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            ("boolean", _) => {
+                let value = input[0] != 0;
+                *input = &input[1..];
+                json!(value)
+            }
+            ("bytes", Some("decimal")) => {
+                let bytes = read_bytes(input);
+                let mut unscaled: i128 = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+                for byte in bytes {
+                    unscaled = (unscaled << 8) | i128::from(byte);
+                }
+                json!({ "unscaled": unscaled.to_string(), "scale": schema["scale"] })
+            }
+            ("string", _) => json!(String::from_utf8(read_bytes(input)).unwrap()),
+            other => panic!("unexpected type {:?}", other),
+        }
+    }
+
+    fn read_long(input: &mut &[u8]) -> i64 {
+        let (mut zigzag, mut shift) = (0u64, 0);
+        loop {
+            let byte = input[0];
+            *input = &input[1..];
+            zigzag |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64)
+    }
+
+    fn read_bytes(input: &mut &[u8]) -> Vec<u8> {
+        let length = read_long(input) as usize;
+        let (bytes, rest) = input.split_at(length);
+        *input = rest;
+        bytes.to_vec()
+    }
+
+    #[test]
+    fn test_to_avro_types_and_nulls() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("id", vec![Value::Integer(1), Value::Integer(-300), Value::Integer(3)]));
+        data.add_column(Column::new("score", vec![Value::Float(1.5), Value::Null, Value::Float(-2.0)]));
+        data.add_column(Column::new(
+            "price",
+            vec![
+                Value::Decimal("19.90".to_string()),
+                Value::Decimal("-0.05".to_string()),
+                Value::Null,
+            ],
+        ));
+        data.add_column(Column::new(
+            "ts",
+            vec![Value::Timestamp(1_700_000_000), Value::Timestamp(1_700_000_060), Value::Null],
+        ));
+        data.add_column(Column::new("ok", vec![Value::string("yes"), Value::string("no"), Value::Null]));
+        data.add_column(Column::new("first name", vec![Value::string("Ann"), Value::Null, Value::string("")]));
+        data.add_column(Column::new("missing", vec![Value::Null, Value::Null, Value::Null]));
+
+        let mut avro = Vec::new();
+        to_avro(&data, &mut avro).unwrap();
+        let (schema, records) = read_back(&avro);
+
+        let fields = schema["fields"].as_array().unwrap();
+        let names: Vec<&str> = fields.iter().map(|field| field["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["id", "score", "price", "ts", "ok", "first_name", "missing"]);
+        assert_eq!(fields[0]["type"], json!("long"));
+        assert_eq!(fields[1]["type"], json!(["null", "double"]));
+        assert_eq!(fields[2]["type"][1]["logicalType"], json!("decimal"));
+        assert_eq!(fields[2]["type"][1]["scale"], json!(2));
+        assert_eq!(fields[3]["type"][1]["logicalType"], json!("timestamp-millis"));
+        assert_eq!(fields[4]["type"], json!(["null", "boolean"]));
+        assert_eq!(fields[6]["type"], json!(["null", "string"]));
+
+        assert_eq!(
+            records,
+            vec![
+                vec![
+                    json!(1),
+                    json!(1.5),
+                    json!({ "unscaled": "1990", "scale": 2 }),
+                    json!({ "millis": 1_700_000_000_000i64 }),
+                    json!(true),
+                    json!("Ann"),
+                    Json::Null,
+                ],
+                vec![
+                    json!(-300),
+                    Json::Null,
+                    json!({ "unscaled": "-5", "scale": 2 }),
+                    json!({ "millis": 1_700_000_060_000i64 }),
+                    json!(false),
+                    Json::Null,
+                    Json::Null,
+                ],
+                vec![json!(3), json!(-2.0), Json::Null, Json::Null, Json::Null, json!(""), Json::Null],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_avro_falls_back_to_strings() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "amount",
+            vec![Value::Decimal("1.5".to_string()), Value::Decimal("2.25".to_string())],
+        ));
+        data.add_column(Column::new("mixed", vec![Value::Integer(1), Value::Boolean(true)]));
+        data.add_column(Column::new("1st", vec![Value::string("a"), Value::string("b")]));
+        data.add_column(Column::new("1st", vec![Value::string("c"), Value::string("d")]));
+
+        let mut avro = Vec::new();
+        to_avro(&data, &mut avro).unwrap();
+        let (schema, records) = read_back(&avro);
+
+        let fields = schema["fields"].as_array().unwrap();
+        assert!(fields.iter().all(|field| field["type"] == json!("string")));
+        let names: Vec<&str> = fields.iter().map(|field| field["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["amount", "mixed", "_1st", "_1st_2"]);
+        assert_eq!(records[1], vec![json!("2.25"), json!("true"), json!("b"), json!("d")]);
+    }
+
+    #[test]
+    fn test_twos_complement() {
+        assert_eq!(twos_complement(0), vec![0x00]);
+        assert_eq!(twos_complement(127), vec![0x7F]);
+        assert_eq!(twos_complement(128), vec![0x00, 0x80]);
+        assert_eq!(twos_complement(-1), vec![0xFF]);
+        assert_eq!(twos_complement(-128), vec![0x80]);
+        assert_eq!(twos_complement(-129), vec![0xFF, 0x7F]);
+    }
+}
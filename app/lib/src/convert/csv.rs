@@ -1,11 +1,28 @@
 //! CSV parsing and writing.
 //!
 //! This module provides functions for converting between CSV format and
-//! `TabularData` structures.
-
-use crate::convert::{Column, TabularData, Value};
+//! `TabularData` structures. The delimiter and quote character default to
+//! `,` and `"` and can be changed with a [`CsvConfig`].
+//!
+//! Input without any quote characters is split with a SIMD scan for
+//! delimiters and line breaks; anything quoted goes through the `csv` crate,
+//! which handles quoted delimiters, line breaks, and doubled quotes.
+//!
+//! Rows with more or fewer fields than the header are rejected unless the
+//! configured [`RaggedRowPolicy`] pads or truncates them.
+
+use crate::config::{CsvConfig, RaggedRowPolicy};
+use crate::convert::{
+    is_epoch_column, is_fixed_decimal_column, is_verbatim_number, parse_epoch, Column, InputLimits,
+    TabularData, Value,
+};
 use crate::error::{AlsError, Result};
+use crate::simd::SimdDispatcher;
 use std::borrow::Cow;
+use std::io::Read;
+
+/// Header names and per-column field values read from delimited text.
+type Records = (Vec<String>, Vec<Vec<String>>);
 
 /// Parse CSV text into `TabularData`.
 ///
@@ -31,16 +48,322 @@ use std::borrow::Cow;
 /// assert_eq!(data.row_count, 2);
 /// ```
 pub fn parse_csv(input: &str) -> Result<TabularData<'static>> {
+    parse_delimited(input, &CsvConfig::default())
+}
+
+/// Parse CSV text into `TabularData` using a custom delimiter and quote character.
+///
+/// Fields wrapped in the quote character may contain the delimiter and line
+/// breaks; a doubled quote character inside a quoted field is read as a
+/// single literal quote.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::CsvConfig;
+/// use als_compression::convert::csv::parse_csv_with_config;
+///
+/// let config = CsvConfig::new().with_delimiter(b';');
+/// let data = parse_csv_with_config("id;name\n1;\"Smith, Jane\"", &config).unwrap();
+/// assert_eq!(data.column_count(), 2);
+/// assert_eq!(data.get_row(0).unwrap()[1].as_str(), Some("Smith, Jane"));
+/// ```
+pub fn parse_csv_with_config(input: &str, config: &CsvConfig) -> Result<TabularData<'static>> {
+    parse_delimited(input, config)
+}
+
+/// Parse CSV text into `TabularData` without inferring column types.
+///
+/// Reads records exactly like [`parse_csv_with_config`], but keeps every
+/// non-empty field as a `Value::String` holding its text as written, so
+/// `007` and `f` are not read as numbers or booleans. Empty fields are
+/// nulls. Useful for comparing two CSV files cell by cell.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::CsvConfig;
+/// use als_compression::convert::csv::parse_csv_text_with_config;
+///
+/// let data = parse_csv_text_with_config("id,flag\n007,f\n", &CsvConfig::default()).unwrap();
+/// let row = data.get_row(0).unwrap();
+/// assert_eq!(row[0].as_str(), Some("007"));
+/// assert_eq!(row[1].as_str(), Some("f"));
+/// ```
+pub fn parse_csv_text_with_config(input: &str, config: &CsvConfig) -> Result<TabularData<'static>> {
+    let (column_names, columns) =
+        read_delimited(input, config, &InputLimits::NONE, &SimdDispatcher::detect())?;
+    Ok(build_tabular_data(column_names, columns, text_values))
+}
+
+/// Parse the header and the first `max_rows` records of CSV text.
+///
+/// Only the records needed are scanned, and the sample is typed exactly as
+/// [`parse_csv_with_config`] would type that prefix on its own. Also
+/// returns the number of input bytes the sample spans, so callers can
+/// compare it against the full input size.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::CsvConfig;
+/// use als_compression::convert::csv::parse_csv_sample;
+///
+/// let csv = "id,name\n1,Alice\n2,Bob\n3,Carol\n";
+/// let (data, bytes) = parse_csv_sample(csv, &CsvConfig::default(), 2).unwrap();
+/// assert_eq!(data.row_count, 2);
+/// assert_eq!(&csv[..bytes], "id,name\n1,Alice\n2,Bob\n");
+/// ```
+pub fn parse_csv_sample(
+    input: &str,
+    config: &CsvConfig,
+    max_rows: usize,
+) -> Result<(TabularData<'static>, usize)> {
+    let end = delimited_prefix_len(input, config, max_rows)?;
+    Ok((parse_delimited(&input[..end], config)?, end))
+}
+
+/// Byte length of the header and the first `max_rows` records of
+/// delimiter-separated text, including the line break after the last one.
+fn delimited_prefix_len(input: &str, config: &CsvConfig, max_rows: usize) -> Result<usize> {
+    check_dialect(config)?;
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(config.delimiter)
+        .quote(config.quote)
+        .double_quote(true)
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+
+    reader.byte_headers().map_err(|e| AlsError::CsvParseError {
+        line: 0,
+        column: 0,
+        message: format!("Failed to read headers: {}", e),
+    })?;
+    let mut record = csv::ByteRecord::new();
+    for line_num in 0..max_rows {
+        let more = reader.read_byte_record(&mut record).map_err(|e| AlsError::CsvParseError {
+            line: line_num + 2,
+            column: 0,
+            message: format!("Failed to parse record: {}", e),
+        })?;
+        if !more {
+            break;
+        }
+    }
+
+    // Records end at a line break, which may be a two-byte CRLF
+    let end = (reader.position().byte() as usize).min(input.len());
+    Ok(if input[..end].ends_with('\r') && input[end..].starts_with('\n') {
+        end + 1
+    } else {
+        end
+    })
+}
+
+/// Parse delimiter-separated text into `TabularData`.
+///
+/// Shared by the CSV and TSV parsers; quoting follows RFC 4180 with the
+/// configured delimiter and quote character.
+pub(crate) fn parse_delimited(input: &str, config: &CsvConfig) -> Result<TabularData<'static>> {
+    parse_delimited_with_limits(input, config, &InputLimits::NONE, &SimdDispatcher::detect())
+}
+
+/// Parse delimiter-separated text, rejecting input over `limits`.
+///
+/// The cell limit is checked after each record, so oversized input fails
+/// before the remaining rows are read. `dispatcher` selects the SIMD
+/// implementation used to scan quote-free input.
+pub(crate) fn parse_delimited_with_limits(
+    input: &str,
+    config: &CsvConfig,
+    limits: &InputLimits,
+    dispatcher: &SimdDispatcher,
+) -> Result<TabularData<'static>> {
+    let (column_names, columns) = read_delimited(input, config, limits, dispatcher)?;
+    Ok(build_tabular_data(column_names, columns, infer_and_convert_values))
+}
+
+/// Read the header and field values of delimiter-separated text.
+///
+/// Empty input reads as no columns.
+fn read_delimited(
+    input: &str,
+    config: &CsvConfig,
+    limits: &InputLimits,
+    dispatcher: &SimdDispatcher,
+) -> Result<Records> {
+    check_dialect(config)?;
+    limits.check_input(input)?;
+
     // Handle empty input
     if input.trim().is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    if is_unquoted(input, config, dispatcher) {
+        read_unquoted_records(input, config, limits, dispatcher)
+    } else {
+        read_records(input.as_bytes(), config, limits)
+    }
+}
+
+/// Parse delimiter-separated text from `reader`, one record at a time.
+///
+/// Produces the same `TabularData` as [`parse_delimited_with_limits`] on
+/// the whole text, without holding the text itself in memory. The byte
+/// limit is enforced while reading.
+pub(crate) fn parse_delimited_reader_with_limits<R: Read>(
+    reader: R,
+    config: &CsvConfig,
+    limits: &InputLimits,
+) -> Result<TabularData<'static>> {
+    check_dialect(config)?;
+
+    let mut reader = limits.limit_reader(reader);
+    let records = read_records(&mut reader, config, limits);
+    let (column_names, columns) = reader.check(records)?;
+
+    // Whitespace-only input reads as one blank header field
+    let blank = matches!(column_names.as_slice(), [name] if name.trim().is_empty());
+    if blank && columns[0].is_empty() {
         return Ok(TabularData::new());
     }
 
-    // Use csv crate to parse
+    Ok(build_tabular_data(column_names, columns, infer_and_convert_values))
+}
+
+/// Convert the field values read for each column with `convert` and
+/// collect them into `TabularData`.
+fn build_tabular_data(
+    column_names: Vec<String>,
+    columns: Vec<Vec<String>>,
+    convert: fn(&str, &[String]) -> Vec<Value<'static>>,
+) -> TabularData<'static> {
+    // Handle single column edge case
+    if column_names.is_empty() {
+        return TabularData::new();
+    }
+
+    // Handle single row edge case - still valid
+    // Convert to TabularData with type inference
+    let mut data = TabularData::with_capacity(column_names.len());
+
+    for (column_name, col_values) in column_names.into_iter().zip(columns) {
+        let typed_values = convert(&column_name, &col_values);
+        data.add_column(Column::new(Cow::Owned(column_name), typed_values));
+    }
+
+    data
+}
+
+/// Check whether `input` can take the SIMD fast path: no quote characters,
+/// no byte order mark, and an ASCII delimiter.
+fn is_unquoted(input: &str, config: &CsvConfig, dispatcher: &SimdDispatcher) -> bool {
+    config.delimiter.is_ascii()
+        && !input.starts_with('\u{feff}')
+        && dispatcher.find_any_byte(input.as_bytes(), [config.quote; 4]).is_none()
+}
+
+/// Read quote-free delimited text, finding field boundaries with a SIMD
+/// scan for the delimiter and line breaks.
+///
+/// Matches the `csv` crate on such input: `\n`, `\r\n`, and `\r` end a
+/// record, and blank lines are skipped.
+fn read_unquoted_records(
+    input: &str,
+    config: &CsvConfig,
+    limits: &InputLimits,
+    dispatcher: &SimdDispatcher,
+) -> Result<Records> {
+    let mut records = UnquotedRecords {
+        input,
+        position: 0,
+        needles: [config.delimiter, config.delimiter, b'\n', b'\r'],
+        dispatcher,
+    };
+
+    let mut fields = Vec::new();
+    if !records.next_record(&mut fields) {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let column_names: Vec<String> = fields.iter().map(|field| field.to_string()).collect();
+    let column_count = column_names.len();
+
+    let mut columns: Vec<Vec<String>> = vec![Vec::new(); column_count];
+    let mut cells = 0usize;
+    let mut line_num = 0;
+    while records.next_record(&mut fields) {
+        line_num += 1;
+        if !keep_row(config.ragged_rows, line_num + 1, column_count, fields.len())? {
+            continue;
+        }
+
+        cells += column_count;
+        limits.check_cells(cells)?;
+
+        push_row(&mut columns, &fields);
+    }
+
+    Ok((column_names, columns))
+}
+
+/// Cursor over the records of quote-free delimited text.
+struct UnquotedRecords<'a, 'd> {
+    /// Text being split
+    input: &'a str,
+    /// Byte offset of the next unread byte
+    position: usize,
+    /// Bytes that end a field: the delimiter (twice) and both line breaks
+    needles: [u8; 4],
+    /// SIMD implementation used to find the next field boundary
+    dispatcher: &'d SimdDispatcher,
+}
+
+impl<'a> UnquotedRecords<'a, '_> {
+    /// Read the next non-blank record into `fields`.
+    ///
+    /// Returns `false` once the input is exhausted.
+    fn next_record(&mut self, fields: &mut Vec<&'a str>) -> bool {
+        let bytes = self.input.as_bytes();
+
+        // Skip line breaks left by the previous record and blank lines
+        while matches!(bytes.get(self.position), Some(b'\n' | b'\r')) {
+            self.position += 1;
+        }
+        if self.position >= bytes.len() {
+            return false;
+        }
+
+        fields.clear();
+        let mut field_start = self.position;
+        loop {
+            let end = self
+                .dispatcher
+                .find_any_byte(&bytes[self.position..], self.needles)
+                .map_or(bytes.len(), |offset| self.position + offset);
+
+            // Boundaries are ASCII bytes, so slicing stays on char boundaries
+            fields.push(&self.input[field_start..end]);
+            self.position = end;
+            if bytes.get(end) != Some(&self.needles[0]) {
+                return true;
+            }
+            self.position += 1;
+            field_start = self.position;
+        }
+    }
+}
+
+/// Read delimited text with the `csv` crate, which handles quoted fields.
+fn read_records<R: Read>(input: R, config: &CsvConfig, limits: &InputLimits) -> Result<Records> {
     let mut reader = csv::ReaderBuilder::new()
+        .delimiter(config.delimiter)
+        .quote(config.quote)
+        .double_quote(true)
         .has_headers(true)
-        .flexible(false) // Require consistent column count
-        .from_reader(input.as_bytes());
+        .flexible(true) // Column counts are checked against the ragged row policy
+        .from_reader(input);
 
     // Get headers
     let headers = reader.headers().map_err(|e| AlsError::CsvParseError {
@@ -50,17 +373,13 @@ pub fn parse_csv(input: &str) -> Result<TabularData<'static>> {
     })?;
 
     let column_count = headers.len();
-    
-    // Handle single column edge case
-    if column_count == 0 {
-        return Ok(TabularData::new());
-    }
 
     // Initialize columns with headers
     let mut columns: Vec<Vec<String>> = vec![Vec::new(); column_count];
     let column_names: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
 
     // Read all records
+    let mut cells = 0usize;
     for (line_num, result) in reader.records().enumerate() {
         let record = result.map_err(|e| AlsError::CsvParseError {
             line: line_num + 2, // +2 because line 1 is headers, and enumerate starts at 0
@@ -69,49 +388,83 @@ pub fn parse_csv(input: &str) -> Result<TabularData<'static>> {
         })?;
 
         // Validate column count
-        if record.len() != column_count {
-            return Err(AlsError::CsvParseError {
-                line: line_num + 2,
-                column: record.len(),
-                message: format!(
-                    "Column count mismatch: expected {}, found {}",
-                    column_count,
-                    record.len()
-                ),
-            });
+        if !keep_row(config.ragged_rows, line_num + 2, column_count, record.len())? {
+            continue;
         }
 
+        cells += column_count;
+        limits.check_cells(cells)?;
+
         // Add values to columns
-        for (col_idx, field) in record.iter().enumerate() {
-            columns[col_idx].push(field.to_string());
-        }
+        let fields: Vec<&str> = record.iter().collect();
+        push_row(&mut columns, &fields);
     }
 
-    // Handle single row edge case - still valid
-    // Convert to TabularData with type inference
-    let mut data = TabularData::with_capacity(column_count);
+    Ok((column_names, columns))
+}
 
-    for (col_idx, col_values) in columns.into_iter().enumerate() {
-        let column_name = &column_names[col_idx];
-        let typed_values = infer_and_convert_values(&col_values);
-        data.add_column(Column::new(
-            Cow::Owned(column_name.clone()),
-            typed_values,
-        ));
+/// Decide whether a row with `found` fields is kept under `policy`.
+///
+/// Returns `Ok(false)` for rows skipped by `RaggedRowPolicy::Truncate`, and
+/// an error naming `line` for ragged rows under `RaggedRowPolicy::Error`.
+fn keep_row(policy: RaggedRowPolicy, line: usize, expected: usize, found: usize) -> Result<bool> {
+    match policy {
+        _ if found == expected => Ok(true),
+        RaggedRowPolicy::Error => Err(AlsError::CsvParseError {
+            line,
+            column: found,
+            message: format!("Column count mismatch: expected {}, found {}", expected, found),
+        }),
+        RaggedRowPolicy::PadNull => Ok(true),
+        RaggedRowPolicy::Truncate => Ok(found > expected),
+    }
+}
+
+/// Append one row to `columns`, dropping extra fields and filling missing
+/// ones with empty fields, which are read as nulls.
+fn push_row(columns: &mut [Vec<String>], fields: &[&str]) {
+    for (index, column) in columns.iter_mut().enumerate() {
+        column.push(fields.get(index).map_or_else(String::new, |field| field.to_string()));
     }
+}
 
-    Ok(data)
+/// Keep field values as text, with empty fields as nulls.
+fn text_values(_name: &str, values: &[String]) -> Vec<Value<'static>> {
+    values
+        .iter()
+        .map(|s| if s.is_empty() { Value::Null } else { Value::string_owned(s.clone()) })
+        .collect()
 }
 
 /// Infer types and convert string values to typed `Value` enum.
 ///
-/// This function attempts to parse each value as:
+/// Columns that look like fixed-point decimals (see
+/// `is_fixed_decimal_column`) keep their exact text as `Decimal` values, and
+/// columns named like timestamps holding plausible epochs (see
+/// `is_epoch_column`) become `Timestamp` values.
+/// Otherwise this function attempts to parse each value as:
 /// 1. Null (empty string)
-/// 2. Integer (i64)
-/// 3. Float (f64)
-/// 4. Boolean (true/false, yes/no, 1/0) - but only non-numeric booleans
-/// 5. String (fallback)
-fn infer_and_convert_values(values: &[String]) -> Vec<Value<'static>> {
+/// 2. Decimal, for numbers with an exponent or a leading `+` (see
+///    `is_verbatim_number`)
+/// 3. Integer (i64)
+/// 4. Float (f64)
+/// 5. Boolean (true/false, yes/no, 1/0) - but only non-numeric booleans
+/// 6. String (fallback)
+fn infer_and_convert_values(name: &str, values: &[String]) -> Vec<Value<'static>> {
+    let non_null = || values.iter().filter(|s| !s.is_empty()).map(String::as_str);
+    if is_epoch_column(name, non_null()) {
+        return values
+            .iter()
+            .map(|s| parse_epoch(s).map_or(Value::Null, Value::Timestamp))
+            .collect();
+    }
+    if is_fixed_decimal_column(non_null()) {
+        return values
+            .iter()
+            .map(|s| if s.is_empty() { Value::Null } else { Value::Decimal(s.clone()) })
+            .collect();
+    }
+
     values
         .iter()
         .map(|s| {
@@ -122,6 +475,11 @@ fn infer_and_convert_values(values: &[String]) -> Vec<Value<'static>> {
 
             let trimmed = s.trim();
 
+            // Keep the text of numbers that formatting would not reproduce
+            if is_verbatim_number(trimmed) {
+                return Value::Decimal(trimmed.to_string());
+            }
+
             // Try to parse as integer first (before boolean, since "1" and "0" are valid integers)
             if let Ok(i) = trimmed.parse::<i64>() {
                 return Value::Integer(i);
@@ -188,12 +546,45 @@ fn parse_boolean(s: &str) -> Option<bool> {
 /// assert!(csv.contains("1,Alice"));
 /// ```
 pub fn to_csv(data: &TabularData) -> Result<String> {
+    to_delimited(data, &CsvConfig::default())
+}
+
+/// Convert `TabularData` to CSV using a custom delimiter and quote character.
+///
+/// Fields containing the delimiter, the quote character, or line breaks are
+/// quoted, and quote characters inside them are doubled.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::CsvConfig;
+/// use als_compression::convert::csv::{parse_csv_with_config, to_csv_with_config};
+///
+/// let config = CsvConfig::new().with_delimiter(b';');
+/// let data = parse_csv_with_config("id;name\n1;Alice", &config).unwrap();
+/// let csv = to_csv_with_config(&data, &config).unwrap();
+/// assert!(csv.starts_with("id;name"));
+/// ```
+pub fn to_csv_with_config(data: &TabularData, config: &CsvConfig) -> Result<String> {
+    to_delimited(data, config)
+}
+
+/// Convert `TabularData` to delimiter-separated text.
+///
+/// Fields containing the delimiter, quotes, or line breaks are quoted.
+pub(crate) fn to_delimited(data: &TabularData, config: &CsvConfig) -> Result<String> {
+    check_dialect(config)?;
+
     // Handle empty data
     if data.is_empty() || data.column_count() == 0 {
         return Ok(String::new());
     }
 
-    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(config.delimiter)
+        .quote(config.quote)
+        .double_quote(true)
+        .from_writer(Vec::new());
 
     // Write headers
     let headers: Vec<&str> = data.column_names();
@@ -242,12 +633,29 @@ pub fn to_csv(data: &TabularData) -> Result<String> {
     })
 }
 
+/// Reject dialects whose delimiter and quote character are the same.
+fn check_dialect(config: &CsvConfig) -> Result<()> {
+    if config.delimiter == config.quote {
+        return Err(AlsError::CsvParseError {
+            line: 0,
+            column: 0,
+            message: format!(
+                "Delimiter and quote character must differ (both are '{}')",
+                config.delimiter as char
+            ),
+        });
+    }
+    Ok(())
+}
+
 /// Convert a `Value` to its CSV string representation.
 fn value_to_csv_string(value: &Value) -> String {
     match value {
         Value::Null => String::new(),
         Value::Integer(i) => i.to_string(),
         Value::Float(f) => f.to_string(),
+        Value::Decimal(d) => d.clone(),
+        Value::Timestamp(t) => t.to_string(),
         Value::String(s) => s.to_string(),
         Value::Boolean(b) => b.to_string(),
     }
@@ -258,6 +666,24 @@ mod tests {
     use super::*;
     use crate::convert::ColumnType;
 
+    #[test]
+    fn test_parse_csv_sample_stops_after_rows() {
+        let csv = "id,note\r\n1,\"two\r\nlines\"\r\n2,plain\r\n3,x\r\n";
+        let (data, bytes) = parse_csv_sample(csv, &CsvConfig::default(), 2).unwrap();
+        assert_eq!(data.row_count, 2);
+        assert_eq!(data.columns[1].values[0], Value::string("two\r\nlines"));
+        assert_eq!(&csv[bytes..], "3,x\r\n");
+
+        // Asking for more rows than exist samples everything
+        let (data, bytes) = parse_csv_sample(csv, &CsvConfig::default(), 10).unwrap();
+        assert_eq!(data.row_count, 3);
+        assert_eq!(bytes, csv.len());
+
+        let (data, bytes) = parse_csv_sample(csv, &CsvConfig::default(), 0).unwrap();
+        assert_eq!((data.column_count(), data.row_count), (2, 0));
+        assert_eq!(&csv[..bytes], "id,note\r\n");
+    }
+
     #[test]
     fn test_parse_csv_basic() {
         let csv = "id,name\n1,Alice\n2,Bob";
@@ -523,6 +949,60 @@ mod tests {
         assert_eq!(data.column_names(), data2.column_names());
     }
 
+    #[test]
+    fn test_parse_csv_semicolon_with_quoted_delimiters() {
+        let config = CsvConfig::new().with_delimiter(b';');
+        let csv = "id;name;note\n1;\"Smith, Jane\";\"a;b\"\n2;Bob;\"say \"\"hi\"\"\"";
+        let data = parse_csv_with_config(csv, &config).unwrap();
+
+        assert_eq!(data.column_names(), vec!["id", "name", "note"]);
+        let row0 = data.get_row(0).unwrap();
+        assert_eq!(row0[1].as_str(), Some("Smith, Jane"));
+        assert_eq!(row0[2].as_str(), Some("a;b"));
+        let row1 = data.get_row(1).unwrap();
+        assert_eq!(row1[2].as_str(), Some("say \"hi\""));
+    }
+
+    #[test]
+    fn test_parse_csv_custom_quote() {
+        let config = CsvConfig::new().with_quote(b'\'');
+        let csv = "name,note\n'Smith, Jane','it''s'";
+        let data = parse_csv_with_config(csv, &config).unwrap();
+
+        let row0 = data.get_row(0).unwrap();
+        assert_eq!(row0[0].as_str(), Some("Smith, Jane"));
+        assert_eq!(row0[1].as_str(), Some("it's"));
+    }
+
+    #[test]
+    fn test_csv_round_trip_preserves_delimiter() {
+        let config = CsvConfig::new().with_delimiter(b';');
+        let original = "id;name\n1;\"Smith, Jane\"\n2;\"a;b\"\n3;\"x \"\"y\"\"\"\n";
+        let data = parse_csv_with_config(original, &config).unwrap();
+        let output = to_csv_with_config(&data, &config).unwrap();
+
+        assert!(output.starts_with("id;name\n"));
+        // Commas are ordinary characters once the delimiter is a semicolon
+        assert!(output.contains("1;Smith, Jane\n"));
+        assert!(output.contains("2;\"a;b\""));
+        assert!(output.contains("3;\"x \"\"y\"\"\""));
+
+        let data2 = parse_csv_with_config(&output, &config).unwrap();
+        assert_eq!(data2.columns[1].values, data.columns[1].values);
+    }
+
+    #[test]
+    fn test_csv_rejects_matching_delimiter_and_quote() {
+        let config = CsvConfig::new().with_delimiter(b'"');
+        assert!(matches!(
+            parse_csv_with_config("a\n1", &config),
+            Err(AlsError::CsvParseError { .. })
+        ));
+
+        let data = parse_csv("a\n1").unwrap();
+        assert!(to_csv_with_config(&data, &config).is_err());
+    }
+
     #[test]
     fn test_parse_boolean_function() {
         assert_eq!(parse_boolean("true"), Some(true));
@@ -570,4 +1050,158 @@ mod tests {
         // Strings preserve original spacing
         assert_eq!(data.columns[0].values[1].as_str(), Some("  hello  "));
     }
+
+    #[test]
+    fn test_unquoted_records_match_csv_crate() {
+        let long = "x".repeat(70);
+        let inputs = [
+            "id,name\n1,Alice\n2,Bob".to_string(),
+            "id,name\r\n1,Alice\r\n2,Bob\r\n".to_string(),
+            "a,b\n\n1,2\n\r\n3,4\r5,6\n\n".to_string(),
+            "a,b,c\n,,\n1,,3".to_string(),
+            format!("long,city\n{long},Zürich\n{long}{long},東京"),
+            "single\nvalue".to_string(),
+        ];
+        let dispatchers = [SimdDispatcher::detect(), SimdDispatcher::scalar_only()];
+
+        for input in &inputs {
+            let expected = read_records(input.as_bytes(), &CsvConfig::default(), &InputLimits::NONE).unwrap();
+            for dispatcher in &dispatchers {
+                assert!(is_unquoted(input, &CsvConfig::default(), dispatcher));
+                let actual =
+                    read_unquoted_records(input, &CsvConfig::default(), &InputLimits::NONE, dispatcher).unwrap();
+                assert_eq!(actual, expected, "input: {input:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_simd_matches_scalar() {
+        let inputs = [
+            "id,name,score\n1,Alice,9.5\n2,Bob,\n3,Carol,7",
+            "id,note\n1,\"a,b\"\n2,\"line\nbreak\"\n3,\"say \"\"hi\"\"\"",
+        ];
+        for input in inputs {
+            let config = CsvConfig::default();
+            let simd = parse_delimited_with_limits(
+                input,
+                &config,
+                &InputLimits::NONE,
+                &SimdDispatcher::detect(),
+            )
+            .unwrap();
+            let scalar = parse_delimited_with_limits(
+                input,
+                &config,
+                &InputLimits::NONE,
+                &SimdDispatcher::scalar_only(),
+            )
+            .unwrap();
+            assert_eq!(simd.column_names(), scalar.column_names());
+            for (simd_column, scalar_column) in simd.columns.iter().zip(&scalar.columns) {
+                assert_eq!(simd_column.values, scalar_column.values);
+            }
+        }
+
+        let quoted = parse_csv("id,note\n1,\"a,b\"\n2,\"line\nbreak\"").unwrap();
+        assert_eq!(quoted.columns[1].values[0].as_str(), Some("a,b"));
+        assert_eq!(quoted.columns[1].values[1].as_str(), Some("line\nbreak"));
+    }
+
+    #[test]
+    fn test_parse_csv_unquoted_column_mismatch() {
+        let result = parse_csv("a,b\n1,2\n3");
+        assert!(matches!(
+            result,
+            Err(AlsError::CsvParseError { line: 3, .. })
+        ));
+    }
+
+    /// Ragged CSV with a short row on line 3 and a long row on line 4.
+    const RAGGED_CSV: &str = "id,name,score\n1,alice,10\n2,bob\n3,carol,30,extra\n4,dave,40";
+
+    fn parse_ragged(input: &str, policy: RaggedRowPolicy) -> Result<TabularData<'static>> {
+        parse_csv_with_config(input, &CsvConfig::new().with_ragged_rows(policy))
+    }
+
+    #[test]
+    fn test_parse_csv_ragged_rows_error() {
+        for input in [RAGGED_CSV.to_string(), RAGGED_CSV.replace("alice", "\"alice\"")] {
+            let result = parse_ragged(&input, RaggedRowPolicy::Error);
+            assert!(
+                matches!(result, Err(AlsError::CsvParseError { line: 3, column: 2, .. })),
+                "{:?}",
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_ragged_rows_pad_null() {
+        // The quoted variant goes through the csv crate instead of the SIMD scan
+        for input in [RAGGED_CSV.to_string(), RAGGED_CSV.replace("alice", "\"alice\"")] {
+            let data = parse_ragged(&input, RaggedRowPolicy::PadNull).unwrap();
+            assert_eq!(data.column_names(), vec!["id", "name", "score"]);
+            assert_eq!(data.row_count, 4);
+            assert_eq!(
+                data.columns[2].values,
+                vec![Value::Integer(10), Value::Null, Value::Integer(30), Value::Integer(40)]
+            );
+            assert_eq!(data.columns[1].values[2].as_str(), Some("carol"));
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_ragged_rows_truncate() {
+        for input in [RAGGED_CSV.to_string(), RAGGED_CSV.replace("alice", "\"alice\"")] {
+            let data = parse_ragged(&input, RaggedRowPolicy::Truncate).unwrap();
+            assert_eq!(data.column_count(), 3);
+            assert_eq!(data.row_count, 3);
+            assert_eq!(
+                data.columns[0].values,
+                vec![Value::Integer(1), Value::Integer(3), Value::Integer(4)]
+            );
+            assert_eq!(data.columns[2].values[1], Value::Integer(30));
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_decimal_column_round_trip() {
+        let csv = "price,ratio\n19.90,0.5\n5.00,1.25\n,2.0\n";
+        let data = parse_csv(csv).unwrap();
+
+        assert_eq!(data.columns[0].inferred_type, ColumnType::Decimal);
+        assert_eq!(data.columns[0].values[0], Value::Decimal("19.90".to_string()));
+        assert_eq!(data.columns[0].values[2], Value::Null);
+        assert_eq!(data.columns[1].inferred_type, ColumnType::Float);
+        assert_eq!(
+            to_csv(&data).unwrap(),
+            "price,ratio\n19.90,0.5\n5.00,1.25\n,2\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_epoch_column() {
+        let csv = "ts,count\n1672531200,1672531200\n1672531260,5\n,7\n";
+        let data = parse_csv(csv).unwrap();
+
+        assert_eq!(data.columns[0].inferred_type, ColumnType::Timestamp);
+        assert_eq!(data.columns[0].values[1], Value::Timestamp(1_672_531_260));
+        assert_eq!(data.columns[0].values[2], Value::Null);
+        // Only columns named like timestamps are detected
+        assert_eq!(data.columns[1].inferred_type, ColumnType::Integer);
+        assert_eq!(to_csv(&data).unwrap(), csv);
+    }
+
+    #[test]
+    fn test_parse_csv_unquoted_respects_cell_limit() {
+        let limits = InputLimits { max_cells: Some(3), ..InputLimits::NONE };
+        let result = parse_delimited_with_limits(
+            "a,b\n1,2\n3,4",
+            &CsvConfig::default(),
+            &limits,
+            &SimdDispatcher::detect(),
+        );
+        assert!(result.is_err());
+    }
 }
@@ -1,21 +1,72 @@
 //! JSON parsing and writing.
 //!
 //! This module provides functions for converting between JSON format and
-//! `TabularData` structures. It handles JSON arrays of objects, nested
-//! object flattening with dot-notation, and null value preservation.
-
-use crate::convert::{Column, TabularData, Value};
+//! `TabularData` structures. It handles JSON arrays of objects,
+//! newline-delimited JSON (NDJSON), nested object and array flattening with
+//! dot-notation, and null value preservation.
+
+use crate::config::JsonColumnOrder;
+use crate::convert::{
+    is_epoch_column, is_fixed_decimal_column, is_verbatim_number, parse_epoch, Column, InputLimits,
+    TabularData, Value,
+};
 use crate::error::{AlsError, Result};
+use indexmap::IndexMap;
+use serde::Serialize;
 use serde_json;
+use serde_json::value::RawValue;
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::io;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read};
+
+/// A flattened JSON object: dot-notation keys in the order they appear.
+pub(crate) type FlatObject = IndexMap<String, JsonScalar>;
+
+/// Deepest nesting of objects and arrays that flattening accepts, the same
+/// limit serde_json applies when parsing a `serde_json::Value`.
+const MAX_NESTING: usize = 128;
+
+/// A value of a flattened JSON object.
+///
+/// Numbers keep the text they are written with, so `19.90`, `1e-9` and
+/// `2.5E+3` convert without going through `f64` formatting.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum JsonScalar {
+    Null,
+    Bool(bool),
+    /// A number, as written in the input
+    Number(String),
+    String(String),
+    /// An empty array, which has no elements to flatten
+    EmptyArray,
+}
+
+impl JsonScalar {
+    /// Check whether this is JSON `null`.
+    fn is_null(&self) -> bool {
+        matches!(self, JsonScalar::Null)
+    }
+
+    /// Get the text of a number, or `None` for any other value.
+    fn number_text(&self) -> Option<&str> {
+        match self {
+            JsonScalar::Number(text) => Some(text),
+            _ => None,
+        }
+    }
+}
 
 /// Parse JSON array of objects into `TabularData`.
 ///
 /// This function parses a JSON array where each element is an object with
 /// consistent keys. Nested objects are flattened using dot-notation
-/// (e.g., `{"user": {"name": "Alice"}}` becomes column `user.name`).
+/// (e.g., `{"user": {"name": "Alice"}}` becomes column `user.name`), and
+/// nested arrays use their indices as path segments (e.g.,
+/// `{"items": [{"name": "pen"}]}` becomes column `items.0.name`).
+///
+/// Columns are ordered as their keys first appear: the keys of the first
+/// object in document order, followed by keys introduced by later objects.
+/// The same input therefore always yields the same schema.
 ///
 /// # Arguments
 ///
@@ -32,27 +83,88 @@ use std::io;
 ///
 /// let json = r#"[{"id": 1, "name": "Alice"}, {"id": 2, "name": "Bob"}]"#;
 /// let data = parse_json(json).unwrap();
-/// assert_eq!(data.column_count(), 2);
+/// assert_eq!(data.column_names(), vec!["id", "name"]);
 /// assert_eq!(data.row_count, 2);
 /// ```
 pub fn parse_json(input: &str) -> Result<TabularData<'static>> {
+    parse_json_with_options(input, &InputLimits::NONE, false, JsonColumnOrder::FirstSeen)
+}
+
+/// Parse a JSON array of objects, rejecting input over `limits`.
+///
+/// With `case_insensitive_keys`, keys that differ only in case are merged
+/// into one column named with their first-seen spelling. `column_order`
+/// selects between first-seen and sorted column order.
+pub(crate) fn parse_json_with_options(
+    input: &str,
+    limits: &InputLimits,
+    case_insensitive_keys: bool,
+    column_order: JsonColumnOrder,
+) -> Result<TabularData<'static>> {
+    limits.check_input(input)?;
+
     // Handle empty input
     if input.trim().is_empty() {
         return Ok(TabularData::new());
     }
 
-    // Parse JSON
-    let json_value: serde_json::Value = serde_json::from_str(input)?;
+    // Parse JSON, keeping the text of every value
+    let json_value: &RawValue = serde_json::from_str(input)?;
+    tabular_from_json_array(json_value, limits, case_insensitive_keys, column_order)
+}
+
+/// Parse a JSON array of objects from `reader`, rejecting input over
+/// `limits`.
+///
+/// Produces the same `TabularData` as [`parse_json_with_options`] on the
+/// whole text, without holding the text itself in memory. The byte limit is
+/// enforced while reading.
+pub(crate) fn parse_json_reader_with_options<R: Read>(
+    reader: R,
+    limits: &InputLimits,
+    case_insensitive_keys: bool,
+    column_order: JsonColumnOrder,
+) -> Result<TabularData<'static>> {
+    let mut reader = limits.limit_reader(reader);
+    let json_value = read_single_json_value(io::BufReader::new(&mut reader));
+    let json_value = reader.check(json_value)?;
 
-    // Ensure it's an array
-    let array = match json_value {
-        serde_json::Value::Array(arr) => arr,
-        _ => {
-            return Err(AlsError::JsonParseError(serde_json::Error::io(
-                io::Error::new(io::ErrorKind::InvalidData, "Expected JSON array of objects"),
-            )))
-        }
+    // Handle empty input
+    let Some(json_value) = json_value else {
+        return Ok(TabularData::new());
+    };
+    tabular_from_json_array(&json_value, limits, case_insensitive_keys, column_order)
+}
+
+/// Read the only JSON value in `reader`, or `None` for whitespace-only input.
+fn read_single_json_value<R: Read>(reader: R) -> Result<Option<Box<RawValue>>> {
+    let mut values = serde_json::Deserializer::from_reader(reader).into_iter::<Box<RawValue>>();
+    let Some(json_value) = values.next().transpose()? else {
+        return Ok(None);
     };
+    if values.next().is_some() {
+        return Err(AlsError::JsonParseError(serde_json::Error::io(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Trailing characters after JSON array",
+        ))));
+    }
+    Ok(Some(json_value))
+}
+
+/// Flatten a parsed JSON array of objects into `TabularData`.
+fn tabular_from_json_array(
+    json_value: &RawValue,
+    limits: &InputLimits,
+    case_insensitive_keys: bool,
+    column_order: JsonColumnOrder,
+) -> Result<TabularData<'static>> {
+    // Ensure it's an array
+    if !json_value.get().starts_with('[') {
+        return Err(AlsError::JsonParseError(serde_json::Error::io(
+            io::Error::new(io::ErrorKind::InvalidData, "Expected JSON array of objects"),
+        )));
+    }
+    let array: Vec<&RawValue> = serde_json::from_str(json_value.get())?;
 
     // Handle empty array
     if array.is_empty() {
@@ -60,19 +172,23 @@ pub fn parse_json(input: &str) -> Result<TabularData<'static>> {
     }
 
     // Flatten all objects and collect all column names
-    let mut flattened_rows: Vec<HashMap<String, serde_json::Value>> = Vec::new();
-    let mut all_columns: std::collections::HashSet<String> = std::collections::HashSet::new();
+    let mut flattened_rows: Vec<FlatObject> = Vec::new();
+    let mut all_columns = ColumnCollector::default();
+    let mut cells = 0usize;
+    let mut key_folder = case_insensitive_keys.then(KeyCaseFolder::default);
 
     for item in array {
-        match item {
-            serde_json::Value::Object(obj) => {
-                let flattened = flatten_object(&obj, "");
-                for key in flattened.keys() {
-                    all_columns.insert(key.clone());
+        match flatten_json_object(item)? {
+            Some(mut flattened) => {
+                if let Some(folder) = key_folder.as_mut() {
+                    flattened = folder.fold(flattened);
                 }
+                cells += flattened.len();
+                limits.check_cells(cells)?;
+                all_columns.add_keys(&flattened);
                 flattened_rows.push(flattened);
             }
-            _ => {
+            None => {
                 return Err(AlsError::JsonParseError(serde_json::Error::io(
                     io::Error::new(io::ErrorKind::InvalidData, "Array must contain only objects"),
                 )))
@@ -80,35 +196,489 @@ pub fn parse_json(input: &str) -> Result<TabularData<'static>> {
         }
     }
 
-    // Sort column names for consistent ordering
-    let mut column_names: Vec<String> = all_columns.into_iter().collect();
-    column_names.sort();
+    check_table_cells(&flattened_rows, &all_columns, limits)?;
+    Ok(build_tabular_data(flattened_rows, all_columns, column_order))
+}
 
-    // Build columns
-    let mut columns_data: HashMap<String, Vec<Value<'static>>> = HashMap::new();
-    for col_name in &column_names {
-        columns_data.insert(col_name.clone(), Vec::new());
+/// Parse the first `max_rows` objects of a JSON array.
+///
+/// Objects are read one at a time, so the rest of the array is neither
+/// parsed nor validated. Also returns the number of input bytes the sampled
+/// objects span.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::json::parse_json_sample;
+///
+/// let json = r#"[{"id": 1}, {"id": 2}, {"id": 3}]"#;
+/// let (data, bytes) = parse_json_sample(json, 2).unwrap();
+/// assert_eq!(data.row_count, 2);
+/// assert_eq!(&json[..bytes], r#"[{"id": 1}, {"id": 2}"#);
+/// ```
+pub fn parse_json_sample(input: &str, max_rows: usize) -> Result<(TabularData<'static>, usize)> {
+    let mut reader = input.as_bytes();
+    let mut array = JsonArrayReader::new(false);
+    let mut flattened_rows = Vec::new();
+    let mut all_columns = ColumnCollector::default();
+    while flattened_rows.len() < max_rows {
+        let Some(row) = array.next_object(&mut reader)? else {
+            break;
+        };
+        all_columns.add_keys(&row);
+        flattened_rows.push(row);
     }
 
-    // Populate columns from flattened rows
-    for row in &flattened_rows {
-        for col_name in &column_names {
-            let value = row
-                .get(col_name)
-                .map(|v| json_value_to_value(v))
-                .unwrap_or(Value::Null);
-            columns_data.get_mut(col_name).unwrap().push(value);
+    let consumed = input.len() - reader.len();
+    Ok((build_tabular_data(flattened_rows, all_columns, JsonColumnOrder::FirstSeen), consumed))
+}
+
+/// Parse newline-delimited JSON (NDJSON / JSON Lines) into `TabularData`.
+///
+/// Each non-blank line must contain a single JSON object. Objects may have
+/// different keys: the schema is the union of all (flattened) keys, and
+/// fields missing from a line become `Value::Null`. Nested objects are
+/// flattened with dot-notation, and columns ordered by first appearance,
+/// exactly as in [`parse_json`].
+///
+/// # Arguments
+///
+/// * `input` - NDJSON text to parse
+///
+/// # Errors
+///
+/// Returns `AlsError::JsonParseError` naming the 1-based line number if a
+/// line is not valid JSON or is not an object.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::json::parse_ndjson;
+///
+/// let ndjson = "{\"id\": 1, \"level\": \"info\"}\n{\"id\": 2, \"error\": \"timeout\"}\n";
+/// let data = parse_ndjson(ndjson).unwrap();
+/// assert_eq!(data.column_names(), vec!["id", "level", "error"]);
+/// assert_eq!(data.row_count, 2);
+/// ```
+pub fn parse_ndjson(input: &str) -> Result<TabularData<'static>> {
+    parse_ndjson_with_options(input, &InputLimits::NONE, false, JsonColumnOrder::FirstSeen)
+}
+
+/// Parse the first `max_rows` objects of newline-delimited JSON.
+///
+/// Blank lines are skipped and do not count as rows. Also returns the
+/// number of input bytes the sampled lines span, including the line break
+/// after the last one.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::json::parse_ndjson_sample;
+///
+/// let ndjson = "{\"id\": 1}\n\n{\"id\": 2}\n{\"id\": 3}\n";
+/// let (data, bytes) = parse_ndjson_sample(ndjson, 2).unwrap();
+/// assert_eq!(data.row_count, 2);
+/// assert_eq!(&ndjson[..bytes], "{\"id\": 1}\n\n{\"id\": 2}\n");
+/// ```
+pub fn parse_ndjson_sample(input: &str, max_rows: usize) -> Result<(TabularData<'static>, usize)> {
+    let mut end = 0;
+    let mut rows = 0;
+    for line in input.split_inclusive('\n') {
+        if rows == max_rows {
+            break;
+        }
+        end += line.len();
+        if !line.trim().is_empty() {
+            rows += 1;
+        }
+    }
+    Ok((parse_ndjson(&input[..end])?, end))
+}
+
+/// Parse newline-delimited JSON, rejecting input over `limits`.
+///
+/// `case_insensitive_keys` and `column_order` apply as in
+/// [`parse_json_with_options`].
+pub(crate) fn parse_ndjson_with_options(
+    input: &str,
+    limits: &InputLimits,
+    case_insensitive_keys: bool,
+    column_order: JsonColumnOrder,
+) -> Result<TabularData<'static>> {
+    limits.check_input(input)?;
+
+    let mut flattened_rows: Vec<FlatObject> = Vec::new();
+    let mut all_columns = ColumnCollector::default();
+    let mut cells = 0usize;
+    let mut key_folder = case_insensitive_keys.then(KeyCaseFolder::default);
+
+    for (line_idx, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let line_number = line_idx + 1;
+        let value: &RawValue = serde_json::from_str(line).map_err(|e| {
+            AlsError::JsonParseError(serde_json::Error::io(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", line_number, e),
+            )))
+        })?;
+
+        match flatten_json_object(value)? {
+            Some(mut flattened) => {
+                if let Some(folder) = key_folder.as_mut() {
+                    flattened = folder.fold(flattened);
+                }
+                cells += flattened.len();
+                limits.check_cells(cells)?;
+                all_columns.add_keys(&flattened);
+                flattened_rows.push(flattened);
+            }
+            None => {
+                return Err(AlsError::JsonParseError(serde_json::Error::io(
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("line {}: expected a JSON object", line_number),
+                    ),
+                )))
+            }
         }
     }
 
-    // Create TabularData
+    check_table_cells(&flattened_rows, &all_columns, limits)?;
+    Ok(build_tabular_data(flattened_rows, all_columns, column_order))
+}
+
+/// Merges flattened keys that differ only in case.
+///
+/// Keys are compared by their lowercase form; each is renamed to the
+/// spelling first seen for that form, so the merged column keeps the casing
+/// of the first object that used it.
+#[derive(Debug, Default)]
+struct KeyCaseFolder {
+    /// First-seen spelling of each lowercased key
+    names: HashMap<String, String>,
+}
+
+impl KeyCaseFolder {
+    /// Rename the keys of one flattened object to their first-seen spelling.
+    ///
+    /// If an object spells the same key in several ways, the first non-null
+    /// value in object order wins.
+    fn fold(&mut self, row: FlatObject) -> FlatObject {
+        let mut folded = FlatObject::with_capacity(row.len());
+        for (key, value) in row {
+            let name = self
+                .names
+                .entry(key.to_lowercase())
+                .or_insert(key)
+                .clone();
+            let slot = folded.entry(name).or_insert(JsonScalar::Null);
+            if slot.is_null() {
+                *slot = value;
+            }
+        }
+        folded
+    }
+}
+
+/// Collects the keys of flattened objects in the order they first appear.
+#[derive(Debug, Default)]
+struct ColumnCollector {
+    names: Vec<String>,
+    seen: HashSet<String>,
+}
+
+impl ColumnCollector {
+    /// Append the keys of `row` that no earlier object had.
+    fn add_keys(&mut self, row: &FlatObject) {
+        for key in row.keys() {
+            if !self.seen.contains(key) {
+                self.seen.insert(key.clone());
+                self.names.push(key.clone());
+            }
+        }
+    }
+
+    /// Number of distinct keys seen.
+    fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Return the collected names in the requested order.
+    fn into_names(self, order: JsonColumnOrder) -> Vec<String> {
+        let mut names = self.names;
+        if order == JsonColumnOrder::Sorted {
+            names.sort();
+        }
+        names
+    }
+}
+
+/// Check the cell count of the table built from `flattened_rows`.
+///
+/// Objects with different keys are padded with nulls to the union of all
+/// keys, so the table can hold more cells than the objects themselves.
+fn check_table_cells(
+    flattened_rows: &[FlatObject],
+    all_columns: &ColumnCollector,
+    limits: &InputLimits,
+) -> Result<()> {
+    limits.check_cells(flattened_rows.len().saturating_mul(all_columns.len()))
+}
+
+/// Build `TabularData` from flattened rows, filling missing keys with nulls.
+fn build_tabular_data(
+    flattened_rows: Vec<FlatObject>,
+    all_columns: ColumnCollector,
+    order: JsonColumnOrder,
+) -> TabularData<'static> {
+    let column_names = all_columns.into_names(order);
+    build_tabular_data_with_columns(&flattened_rows, &column_names)
+}
+
+/// Build `TabularData` with the given columns from flattened rows.
+///
+/// Keys missing from a row are filled with nulls, and keys that are not in
+/// `column_names` are ignored.
+pub(crate) fn build_tabular_data_with_columns(
+    flattened_rows: &[FlatObject],
+    column_names: &[String],
+) -> TabularData<'static> {
     let mut data = TabularData::with_capacity(column_names.len());
     for col_name in column_names {
-        let values = columns_data.remove(&col_name).unwrap();
-        data.add_column(Column::new(Cow::Owned(col_name), values));
+        let cells: Vec<Option<&JsonScalar>> = flattened_rows
+            .iter()
+            .map(|row| row.get(col_name).filter(|v| !v.is_null()))
+            .collect();
+
+        let values = if is_epoch_json_column(col_name, &cells) {
+            cells
+                .iter()
+                .map(|cell| {
+                    cell.and_then(JsonScalar::number_text)
+                        .and_then(parse_epoch)
+                        .map_or(Value::Null, Value::Timestamp)
+                })
+                .collect()
+        } else if is_decimal_json_column(&cells) {
+            cells
+                .iter()
+                .map(|cell| {
+                    cell.and_then(JsonScalar::number_text)
+                        .map_or(Value::Null, |text| Value::Decimal(text.to_string()))
+                })
+                .collect()
+        } else {
+            cells
+                .iter()
+                .map(|cell| cell.map_or(Value::Null, json_value_to_value))
+                .collect()
+        };
+        data.add_column(Column::new(Cow::Owned(col_name.clone()), values));
+    }
+
+    data
+}
+
+/// Check whether the non-null cells of a column are fixed-point decimals.
+///
+/// Numbers keep their original text, so `19.90` is seen as written.
+fn is_decimal_json_column(cells: &[Option<&JsonScalar>]) -> bool {
+    let mut texts = Vec::new();
+    for cell in cells.iter().flatten() {
+        match cell {
+            JsonScalar::Number(text) if !is_integer_text(text) => texts.push(text.as_str()),
+            _ => return false,
+        }
+    }
+    is_fixed_decimal_column(texts)
+}
+
+/// Check whether the non-null cells of a column are epoch timestamps.
+///
+/// Only integer numbers count; epochs written as strings stay strings.
+fn is_epoch_json_column(name: &str, cells: &[Option<&JsonScalar>]) -> bool {
+    let mut texts = Vec::new();
+    for cell in cells.iter().flatten() {
+        match cell {
+            JsonScalar::Number(text) if text.parse::<i64>().is_ok() => texts.push(text.as_str()),
+            _ => return false,
+        }
+    }
+    is_epoch_column(name, texts)
+}
+
+/// Check whether a JSON number is an integer that fits `i64` or `u64`.
+fn is_integer_text(text: &str) -> bool {
+    text.parse::<i64>().is_ok() || text.parse::<u64>().is_ok()
+}
+
+/// Pull parser for the objects of a JSON array read from a stream.
+///
+/// Reads one array element at a time and buffers only that element, so
+/// arrays larger than memory can be processed object by object. Objects
+/// are flattened exactly as in [`parse_json`].
+pub(crate) struct JsonArrayReader {
+    state: ArrayState,
+    /// Bytes of the element being read
+    element: Vec<u8>,
+    key_folder: Option<KeyCaseFolder>,
+}
+
+/// Position of a [`JsonArrayReader`] within the array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrayState {
+    /// Before the opening `[`
+    Start,
+    /// After the opening `[`, before any element
+    First,
+    /// After an element, expecting `,` or `]`
+    Next,
+    /// After the closing `]`, or at the end of empty input
+    Done,
+}
+
+impl JsonArrayReader {
+    /// Create a reader positioned before the opening `[`.
+    ///
+    /// With `case_insensitive_keys`, keys are merged as in
+    /// [`parse_json_with_options`], across all objects read.
+    pub(crate) fn new(case_insensitive_keys: bool) -> Self {
+        Self {
+            state: ArrayState::Start,
+            element: Vec::new(),
+            key_folder: case_insensitive_keys.then(KeyCaseFolder::default),
+        }
+    }
+
+    /// Read and flatten the next object of the array.
+    ///
+    /// Returns `None` after the closing `]`, or if the input is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::JsonParseError` if the input is not an array of
+    /// objects, or if anything but whitespace follows the array.
+    pub(crate) fn next_object(
+        &mut self,
+        reader: &mut impl io::BufRead,
+    ) -> Result<Option<FlatObject>> {
+        loop {
+            let next = peek_non_whitespace(reader)?;
+            match (self.state, next) {
+                (ArrayState::Done, _) => return Ok(None),
+                (ArrayState::Start, None) => {
+                    self.state = ArrayState::Done;
+                    return Ok(None);
+                }
+                (ArrayState::Start, Some(b'[')) => {
+                    reader.consume(1);
+                    self.state = ArrayState::First;
+                }
+                (ArrayState::Start, Some(_)) => return Err(invalid_json("Expected JSON array of objects")),
+                (ArrayState::First | ArrayState::Next, Some(b']')) => {
+                    reader.consume(1);
+                    self.state = ArrayState::Done;
+                    if peek_non_whitespace(reader)?.is_some() {
+                        return Err(invalid_json("Unexpected characters after JSON array"));
+                    }
+                    return Ok(None);
+                }
+                (ArrayState::Next, Some(b',')) => {
+                    reader.consume(1);
+                    return self.read_object(reader).map(Some);
+                }
+                (ArrayState::First, Some(_)) => return self.read_object(reader).map(Some),
+                (ArrayState::Next, Some(_)) => {
+                    return Err(invalid_json("Expected ',' or ']' after array element"))
+                }
+                (ArrayState::First | ArrayState::Next, None) => {
+                    return Err(invalid_json("Unterminated JSON array"))
+                }
+            }
+        }
     }
 
-    Ok(data)
+    /// Read one array element, which must be an object.
+    fn read_object(&mut self, reader: &mut impl io::BufRead) -> Result<FlatObject> {
+        self.state = ArrayState::Next;
+        if peek_non_whitespace(reader)? != Some(b'{') {
+            return Err(invalid_json("Array must contain only objects"));
+        }
+
+        // Scan to the matching `}`, skipping brackets inside strings
+        self.element.clear();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        loop {
+            let Some(&byte) = reader.fill_buf()?.first() else {
+                return Err(invalid_json("Unterminated JSON object"));
+            };
+            reader.consume(1);
+            self.element.push(byte);
+            if in_string {
+                match byte {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match byte {
+                b'"' => in_string = true,
+                b'{' | b'[' => depth += 1,
+                b'}' | b']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let obj: &RawValue = serde_json::from_slice(&self.element)?;
+        let flattened = flatten_object(obj, "", 0)?;
+        Ok(match self.key_folder.as_mut() {
+            Some(folder) => folder.fold(flattened),
+            None => flattened,
+        })
+    }
+}
+
+/// Skip whitespace and peek at the next byte without consuming it.
+fn peek_non_whitespace(reader: &mut impl io::BufRead) -> io::Result<Option<u8>> {
+    loop {
+        let buffer = reader.fill_buf()?;
+        let Some(&byte) = buffer.first() else {
+            return Ok(None);
+        };
+        if !byte.is_ascii_whitespace() {
+            return Ok(Some(byte));
+        }
+        reader.consume(1);
+    }
+}
+
+/// Create a JSON parse error for structurally invalid input.
+fn invalid_json(message: &str) -> AlsError {
+    AlsError::JsonParseError(serde_json::Error::io(io::Error::new(
+        io::ErrorKind::InvalidData,
+        message.to_string(),
+    )))
+}
+
+/// Flatten `raw` if it is a JSON object, or return `None` for any other value.
+fn flatten_json_object(raw: &RawValue) -> Result<Option<FlatObject>> {
+    if !raw.get().starts_with('{') {
+        return Ok(None);
+    }
+    flatten_object(raw, "", 0).map(Some)
 }
 
 /// Flatten a JSON object using dot-notation for nested keys.
@@ -116,66 +686,101 @@ pub fn parse_json(input: &str) -> Result<TabularData<'static>> {
 /// For example: `{"user": {"name": "Alice", "age": 30}}` becomes:
 /// - `user.name` -> "Alice"
 /// - `user.age` -> 30
-fn flatten_object(
-    obj: &serde_json::Map<String, serde_json::Value>,
-    prefix: &str,
-) -> HashMap<String, serde_json::Value> {
-    let mut result = HashMap::new();
+///
+/// Array elements are keyed by their index, so `{"tags": ["a", "b"]}`
+/// becomes `tags.0` -> "a" and `tags.1` -> "b". `depth` counts the objects
+/// and arrays that enclose `obj`.
+fn flatten_object(obj: &RawValue, prefix: &str, depth: usize) -> Result<FlatObject> {
+    if depth >= MAX_NESTING {
+        return Err(invalid_json("JSON nesting is too deep"));
+    }
 
-    for (key, value) in obj {
+    let entries: IndexMap<String, &RawValue> = serde_json::from_str(obj.get())?;
+    let mut result = FlatObject::new();
+
+    for (key, value) in entries {
         let full_key = if prefix.is_empty() {
-            key.clone()
+            key
         } else {
             format!("{}.{}", prefix, key)
         };
 
-        match value {
-            serde_json::Value::Object(nested_obj) => {
-                // Recursively flatten nested objects
-                let nested = flatten_object(nested_obj, &full_key);
-                result.extend(nested);
+        flatten_value(value, full_key, &mut result, depth + 1)?;
+    }
+
+    Ok(result)
+}
+
+/// Flatten a single JSON value stored under `key` into `result`.
+fn flatten_value(
+    value: &RawValue,
+    key: String,
+    result: &mut FlatObject,
+    depth: usize,
+) -> Result<()> {
+    let text = value.get();
+    let scalar = match text.as_bytes().first() {
+        Some(b'{') => {
+            // Recursively flatten nested objects
+            result.extend(flatten_object(value, &key, depth)?);
+            return Ok(());
+        }
+        Some(b'[') => {
+            if depth >= MAX_NESTING {
+                return Err(invalid_json("JSON nesting is too deep"));
             }
-            _ => {
-                // Non-object values are added directly
-                result.insert(full_key, value.clone());
+            let items: Vec<&RawValue> = serde_json::from_str(text)?;
+            if items.is_empty() {
+                JsonScalar::EmptyArray
+            } else {
+                // Recursively flatten array elements under their indices
+                for (index, item) in items.into_iter().enumerate() {
+                    flatten_value(item, format!("{}.{}", key, index), result, depth + 1)?;
+                }
+                return Ok(());
             }
         }
-    }
-
-    result
+        Some(b'"') => JsonScalar::String(serde_json::from_str(text)?),
+        Some(b't') => JsonScalar::Bool(true),
+        Some(b'f') => JsonScalar::Bool(false),
+        Some(b'n') => JsonScalar::Null,
+        _ => JsonScalar::Number(text.to_string()),
+    };
+    result.insert(key, scalar);
+    Ok(())
 }
 
-/// Convert a `serde_json::Value` to our `Value` type.
-fn json_value_to_value(json_val: &serde_json::Value) -> Value<'static> {
+/// Convert a flattened JSON value to our `Value` type.
+fn json_value_to_value(json_val: &JsonScalar) -> Value<'static> {
     match json_val {
-        serde_json::Value::Null => Value::Null,
-        serde_json::Value::Bool(b) => Value::Boolean(*b),
-        serde_json::Value::Number(n) => {
-            if let Some(i) = n.as_i64() {
+        JsonScalar::Null => Value::Null,
+        JsonScalar::Bool(b) => Value::Boolean(*b),
+        JsonScalar::Number(text) => {
+            // An exponent or a leading `+` that formatting would drop is
+            // kept verbatim
+            if is_verbatim_number(text) {
+                Value::Decimal(text.clone())
+            } else if let Ok(i) = text.parse::<i64>() {
                 Value::Integer(i)
-            } else if let Some(f) = n.as_f64() {
+            } else if let Some(f) = text.parse::<f64>().ok().filter(|f| f.is_finite()) {
                 Value::Float(f)
             } else {
                 // Fallback for numbers that don't fit i64 or f64
-                Value::String(Cow::Owned(n.to_string()))
+                Value::String(Cow::Owned(text.clone()))
             }
         }
-        serde_json::Value::String(s) => Value::String(Cow::Owned(s.clone())),
-        serde_json::Value::Array(_) => {
-            // Arrays are serialized as JSON strings
-            Value::String(Cow::Owned(json_val.to_string()))
-        }
-        serde_json::Value::Object(_) => {
-            // This shouldn't happen after flattening, but handle it
-            Value::String(Cow::Owned(json_val.to_string()))
-        }
+        JsonScalar::String(s) => Value::String(Cow::Owned(s.clone())),
+        // Only empty arrays survive flattening; keep them as JSON text
+        JsonScalar::EmptyArray => Value::String(Cow::Borrowed("[]")),
     }
 }
 
 /// Convert `TabularData` to JSON array format.
 ///
 /// This function serializes tabular data to a JSON array of objects.
-/// Dot-notation column names are reconstructed into nested objects.
+/// Dot-notation column names are reconstructed into nested objects, and
+/// numeric path segments into arrays (e.g., `items.0.name` and
+/// `items.1.name` become `"items": [{"name": ...}, {"name": ...}]`).
 ///
 /// # Arguments
 ///
@@ -212,25 +817,140 @@ pub fn to_json(data: &TabularData) -> Result<String> {
         return Ok("[]".to_string());
     }
 
-    let mut array = Vec::new();
-
     // Build each row as a JSON object
+    let array: Vec<JsonOutput> = (0..data.row_count)
+        .map(|row_idx| row_to_json_object(data, row_idx))
+        .collect();
+
+    // Serialize to JSON string
+    serde_json::to_string(&array).map_err(|e| e.into())
+}
+
+/// Convert `TabularData` to newline-delimited JSON (NDJSON).
+///
+/// Each row is written as one JSON object followed by a newline. Dot-notation
+/// column names are reconstructed into nested objects as in [`to_json`].
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::{TabularData, Column, Value};
+/// use als_compression::convert::json::to_ndjson;
+///
+/// let mut data = TabularData::new();
+/// data.add_column(Column::new("id", vec![Value::Integer(1), Value::Integer(2)]));
+///
+/// assert_eq!(to_ndjson(&data).unwrap(), "{\"id\":1}\n{\"id\":2}\n");
+/// ```
+pub fn to_ndjson(data: &TabularData) -> Result<String> {
+    let mut output = String::new();
+    if data.column_count() == 0 {
+        return Ok(output);
+    }
+
     for row_idx in 0..data.row_count {
-        let mut row_obj = serde_json::Map::new();
+        let row_obj = row_to_json_object(data, row_idx);
+        output.push_str(&serde_json::to_string(&row_obj)?);
+        output.push('\n');
+    }
+
+    Ok(output)
+}
+
+/// A JSON value being written.
+///
+/// Mirrors `serde_json::Value`, except that numbers hold their text, so a
+/// `Value::Decimal` such as `19.90` or `2.5E+3` is written exactly.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum JsonOutput {
+    Null,
+    Bool(bool),
+    Number(Box<RawValue>),
+    String(String),
+    Array(Vec<JsonOutput>),
+    Object(IndexMap<String, JsonOutput>),
+}
+
+impl JsonOutput {
+    /// Make a number from its text, or `None` if `text` is not a JSON number.
+    fn number(text: String) -> Option<Self> {
+        if !text.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            return None;
+        }
+        RawValue::from_string(text).ok().map(JsonOutput::Number)
+    }
+}
+
+/// Build the JSON object for a single row.
+fn row_to_json_object(data: &TabularData, row_idx: usize) -> JsonOutput {
+    let mut row_obj = IndexMap::new();
+
+    for col in &data.columns {
+        let value = &col.values[row_idx];
+        let json_value = value_to_json_value(value);
+
+        // Handle dot-notation to reconstruct nested objects
+        insert_nested(&mut row_obj, col.name.as_ref(), json_value);
+    }
+
+    // Turn index-keyed objects back into arrays; the row itself stays an object
+    let row_obj = row_obj
+        .into_iter()
+        .map(|(key, value)| (key, rebuild_arrays(value)))
+        .collect();
 
-        for col in &data.columns {
-            let value = &col.values[row_idx];
-            let json_value = value_to_json_value(value);
+    JsonOutput::Object(row_obj)
+}
+
+/// Convert nested objects keyed by array indices back into arrays.
+///
+/// An object becomes an array when every key is an index, one of them is
+/// `0`, and no more than half of the resulting elements would be missing.
+/// Missing indices become null elements. Objects that mix index and name
+/// keys, or that have sparse numeric keys such as years, keep their shape.
+fn rebuild_arrays(value: JsonOutput) -> JsonOutput {
+    let obj = match value {
+        JsonOutput::Object(obj) => obj,
+        other => return other,
+    };
 
-            // Handle dot-notation to reconstruct nested objects
-            insert_nested(&mut row_obj, col.name.as_ref(), json_value);
+    let mut entries = Vec::with_capacity(obj.len());
+    let mut max_index = None;
+    let mut all_indices = !obj.is_empty();
+    for (key, value) in obj {
+        let value = rebuild_arrays(value);
+        match parse_array_index(&key) {
+            Some(index) => max_index = max_index.max(Some(index)),
+            None => all_indices = false,
         }
+        entries.push((key, value));
+    }
 
-        array.push(serde_json::Value::Object(row_obj));
+    let has_zero = entries.iter().any(|(key, _)| key == "0");
+    match max_index {
+        Some(max_index) if all_indices && has_zero && max_index < entries.len() * 2 => {
+            let mut items = vec![JsonOutput::Null; max_index + 1];
+            for (key, value) in entries {
+                if let Some(index) = parse_array_index(&key) {
+                    items[index] = value;
+                }
+            }
+            JsonOutput::Array(items)
+        }
+        _ => JsonOutput::Object(entries.into_iter().collect()),
     }
+}
 
-    // Serialize to JSON string
-    serde_json::to_string(&array).map_err(|e| e.into())
+/// Parse a path segment written by array flattening (no sign, no leading zeros).
+fn parse_array_index(segment: &str) -> Option<usize> {
+    let canonical = segment == "0"
+        || (!segment.starts_with('0') && !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()));
+    if canonical {
+        segment.parse().ok()
+    } else {
+        None
+    }
 }
 
 /// Insert a value into a JSON object, creating nested structure for dot-notation keys.
@@ -238,9 +958,9 @@ pub fn to_json(data: &TabularData) -> Result<String> {
 /// For example, inserting key "user.name" with value "Alice" creates:
 /// `{"user": {"name": "Alice"}}`
 fn insert_nested(
-    obj: &mut serde_json::Map<String, serde_json::Value>,
+    obj: &mut IndexMap<String, JsonOutput>,
     key: &str,
-    value: serde_json::Value,
+    value: JsonOutput,
 ) {
     let parts: Vec<&str> = key.split('.').collect();
 
@@ -258,28 +978,31 @@ fn insert_nested(
                 break;
             } else {
                 // Intermediate part, ensure object exists
-                current = current
+                let entry = current
                     .entry(part.to_string())
-                    .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()))
-                    .as_object_mut()
-                    .expect("Expected object for nested key");
+                    .or_insert_with(|| JsonOutput::Object(IndexMap::new()));
+                current = match entry {
+                    JsonOutput::Object(nested) => nested,
+                    _ => panic!("Expected object for nested key"),
+                };
             }
         }
     }
 }
 
-/// Convert our `Value` type to `serde_json::Value`.
-fn value_to_json_value(value: &Value) -> serde_json::Value {
+/// Convert our `Value` type to a JSON value for writing.
+fn value_to_json_value(value: &Value) -> JsonOutput {
     match value {
-        Value::Null => serde_json::Value::Null,
-        Value::Integer(i) => serde_json::Value::Number((*i).into()),
-        Value::Float(f) => {
-            serde_json::Number::from_f64(*f)
-                .map(serde_json::Value::Number)
-                .unwrap_or(serde_json::Value::Null)
-        }
-        Value::String(s) => serde_json::Value::String(s.to_string()),
-        Value::Boolean(b) => serde_json::Value::Bool(*b),
+        Value::Null => JsonOutput::Null,
+        Value::Integer(i) => JsonOutput::number(i.to_string()).unwrap_or(JsonOutput::Null),
+        Value::Float(f) => serde_json::Number::from_f64(*f)
+            .and_then(|n| JsonOutput::number(n.to_string()))
+            .unwrap_or(JsonOutput::Null),
+        Value::Decimal(d) => JsonOutput::number(d.strip_prefix('+').unwrap_or(d).to_string())
+            .unwrap_or_else(|| JsonOutput::String(d.clone())),
+        Value::Timestamp(t) => JsonOutput::number(t.to_string()).unwrap_or(JsonOutput::Null),
+        Value::String(s) => JsonOutput::String(s.to_string()),
+        Value::Boolean(b) => JsonOutput::Bool(*b),
     }
 }
 
@@ -288,6 +1011,32 @@ mod tests {
     use super::*;
     use crate::convert::ColumnType;
 
+    #[test]
+    fn test_parse_json_sample_ignores_rest_of_array() {
+        // The tail is never read, so it may even be malformed
+        let json = r#"[{"id": 1, "tags": ["a"]}, {"id": 2, "name": "x"}, {"id": oops"#;
+        let (data, bytes) = parse_json_sample(json, 2).unwrap();
+        assert_eq!(data.column_names(), vec!["id", "tags.0", "name"]);
+        assert_eq!(data.row_count, 2);
+        assert_eq!(&json[bytes..], r#", {"id": oops"#);
+
+        let (data, bytes) = parse_json_sample("[]", 5).unwrap();
+        assert!(data.is_empty());
+        assert_eq!(bytes, 2);
+    }
+
+    #[test]
+    fn test_parse_ndjson_sample_skips_blank_lines() {
+        let ndjson = "\n{\"id\": 1}\n\n{\"id\": 2}\n{\"id\": 3}";
+        let (data, bytes) = parse_ndjson_sample(ndjson, 2).unwrap();
+        assert_eq!(data.row_count, 2);
+        assert_eq!(&ndjson[bytes..], "{\"id\": 3}");
+
+        let (data, bytes) = parse_ndjson_sample(ndjson, 3).unwrap();
+        assert_eq!(data.row_count, 3);
+        assert_eq!(bytes, ndjson.len());
+    }
+
     #[test]
     fn test_parse_json_basic() {
         let json = r#"[{"id": 1, "name": "Alice"}, {"id": 2, "name": "Bob"}]"#;
@@ -296,7 +1045,7 @@ mod tests {
         assert_eq!(data.column_count(), 2);
         assert_eq!(data.row_count, 2);
 
-        // Columns should be sorted alphabetically
+        // Columns keep the key order of the objects
         let col_names = data.column_names();
         assert_eq!(col_names, vec!["id", "name"]);
 
@@ -387,6 +1136,80 @@ mod tests {
         assert!(name_col.values[1].is_null());
     }
 
+    #[test]
+    fn test_parse_json_mixed_case_keys() {
+        let json = r#"[
+            {"Name": "Alice", "ID": 1},
+            {"name": "Bob", "id": 2},
+            {"NAME": "Carol", "Id": 3}
+        ]"#;
+
+        // Case-sensitive by default: every spelling is its own column
+        let data = parse_json(json).unwrap();
+        assert_eq!(data.column_count(), 6);
+
+        let data = parse_json_with_options(json, &InputLimits::NONE, true, JsonColumnOrder::FirstSeen).unwrap();
+        assert_eq!(data.column_names(), vec!["Name", "ID"]);
+        let names: Vec<_> = data.get_column_by_name("Name").unwrap().values.iter()
+            .map(|v| v.as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["Alice", "Bob", "Carol"]);
+        let ids: Vec<_> = data.get_column_by_name("ID").unwrap().values.iter()
+            .map(|v| v.as_integer().unwrap())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_parse_ndjson_mixed_case_nested_keys() {
+        let ndjson = "{\"User\": {\"Name\": \"Alice\"}}\n{\"user\": {\"name\": \"Bob\"}}\n";
+
+        let data = parse_ndjson(ndjson).unwrap();
+        assert_eq!(data.column_names(), vec!["User.Name", "user.name"]);
+
+        let data = parse_ndjson_with_options(ndjson, &InputLimits::NONE, true, JsonColumnOrder::FirstSeen).unwrap();
+        assert_eq!(data.column_names(), vec!["User.Name"]);
+        assert_eq!(data.row_count, 2);
+    }
+
+    #[test]
+    fn test_key_case_folder_prefers_non_null_duplicate() {
+        let mut folder = KeyCaseFolder::default();
+        let row: FlatObject = [
+            ("Email".to_string(), JsonScalar::Null),
+            ("email".to_string(), JsonScalar::String("a@example.com".to_string())),
+        ]
+        .into_iter()
+        .collect();
+
+        let folded = folder.fold(row);
+        assert_eq!(folded.len(), 1);
+        assert_eq!(folded["Email"], JsonScalar::String("a@example.com".to_string()));
+    }
+
+    #[test]
+    fn test_parse_json_first_seen_column_order() {
+        // Keys later objects introduce are appended, whatever their position
+        let json = r#"[
+            {"zeta": 1, "user": {"name": "Alice", "age": 30}, "alpha": true},
+            {"alpha": false, "beta": "x", "zeta": 2},
+            {"user": {"email": "c@example.com"}, "zeta": 3, "aardvark": null}
+        ]"#;
+
+        let data = parse_json(json).unwrap();
+        assert_eq!(
+            data.column_names(),
+            vec!["zeta", "user.name", "user.age", "alpha", "beta", "user.email", "aardvark"]
+        );
+        assert_eq!(parse_json(json).unwrap().column_names(), data.column_names());
+
+        let sorted = parse_json_with_options(json, &InputLimits::NONE, false, JsonColumnOrder::Sorted).unwrap();
+        assert_eq!(
+            sorted.column_names(),
+            vec!["aardvark", "alpha", "beta", "user.age", "user.email", "user.name", "zeta"]
+        );
+    }
+
     #[test]
     fn test_parse_json_missing_keys() {
         // Objects with inconsistent keys should fill missing values with null
@@ -460,6 +1283,97 @@ mod tests {
         assert!(matches!(result, Err(AlsError::JsonParseError(_))));
     }
 
+    #[test]
+    fn test_parse_ndjson_heterogeneous_objects() {
+        let ndjson = r#"{"id": 1, "level": "info", "msg": "started"}
+{"id": 2, "level": "error", "code": 500}
+{"id": 3, "msg": "done"}
+"#;
+        let data = parse_ndjson(ndjson).unwrap();
+
+        assert_eq!(data.row_count, 3);
+        assert_eq!(data.column_names(), vec!["id", "level", "msg", "code"]);
+
+        let sorted = parse_ndjson_with_options(ndjson, &InputLimits::NONE, false, JsonColumnOrder::Sorted).unwrap();
+        assert_eq!(sorted.column_names(), vec!["code", "id", "level", "msg"]);
+
+        let code_col = data.get_column_by_name("code").unwrap();
+        assert_eq!(code_col.values, vec![Value::Null, Value::Integer(500), Value::Null]);
+
+        let level_col = data.get_column_by_name("level").unwrap();
+        assert_eq!(level_col.values[1].as_str(), Some("error"));
+        assert!(level_col.values[2].is_null());
+    }
+
+    #[test]
+    fn test_parse_ndjson_nested_matches_array_path() {
+        let ndjson = r#"{"id": 1, "user": {"name": "Alice", "age": 30}}
+{"id": 2, "user": {"name": "Bob", "age": 25}}"#;
+        let json = r#"[
+            {"id": 1, "user": {"name": "Alice", "age": 30}},
+            {"id": 2, "user": {"name": "Bob", "age": 25}}
+        ]"#;
+
+        let from_lines = parse_ndjson(ndjson).unwrap();
+        let from_array = parse_json(json).unwrap();
+
+        assert_eq!(from_lines.column_names(), from_array.column_names());
+        for (a, b) in from_lines.columns.iter().zip(&from_array.columns) {
+            assert_eq!(a.values, b.values);
+        }
+    }
+
+    #[test]
+    fn test_parse_ndjson_blank_lines_and_whitespace() {
+        let ndjson = "\n  {\"id\": 1}  \r\n\n\t\n{\"id\": 2}\t\n   \n";
+        let data = parse_ndjson(ndjson).unwrap();
+
+        assert_eq!(data.row_count, 2);
+        assert_eq!(
+            data.columns[0].values,
+            vec![Value::Integer(1), Value::Integer(2)]
+        );
+    }
+
+    #[test]
+    fn test_parse_ndjson_empty() {
+        let data = parse_ndjson("").unwrap();
+        assert_eq!(data.column_count(), 0);
+
+        let data = parse_ndjson("\n  \n").unwrap();
+        assert_eq!(data.column_count(), 0);
+    }
+
+    #[test]
+    fn test_parse_ndjson_error_reports_line() {
+        let ndjson = "{\"id\": 1}\n\n{\"id\": 2,\n{\"id\": 3}";
+        let err = parse_ndjson(ndjson).unwrap_err();
+        assert!(matches!(err, AlsError::JsonParseError(_)));
+        assert!(err.to_string().contains("line 3"), "{}", err);
+
+        let err = parse_ndjson("{\"id\": 1}\n[1, 2]").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_ndjson_round_trip() {
+        let ndjson = r#"{"id": 1, "user": {"name": "Alice"}, "ok": true}
+{"id": 2, "user": {"name": "Bob"}, "ok": false}
+"#;
+        let data = parse_ndjson(ndjson).unwrap();
+        let output = to_ndjson(&data).unwrap();
+
+        assert_eq!(output.lines().count(), 2);
+        let first: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(first["user"]["name"], "Alice");
+
+        let data2 = parse_ndjson(&output).unwrap();
+        assert_eq!(data.column_names(), data2.column_names());
+        for (a, b) in data.columns.iter().zip(&data2.columns) {
+            assert_eq!(a.values, b.values);
+        }
+    }
+
     #[test]
     fn test_to_json_basic() {
         let mut data = TabularData::new();
@@ -632,74 +1546,213 @@ mod tests {
 
     #[test]
     fn test_flatten_object() {
-        let mut obj = serde_json::Map::new();
-        obj.insert("id".to_string(), serde_json::json!(1));
+        let obj: &RawValue =
+            serde_json::from_str(r#"{"id": 1, "user": {"name": "Alice", "age": 30}}"#).unwrap();
 
-        let mut user = serde_json::Map::new();
-        user.insert("name".to_string(), serde_json::json!("Alice"));
-        user.insert("age".to_string(), serde_json::json!(30));
-        obj.insert("user".to_string(), serde_json::Value::Object(user));
-
-        let flattened = flatten_object(&obj, "");
+        let flattened = flatten_object(obj, "", 0).unwrap();
 
         assert_eq!(flattened.len(), 3);
-        assert_eq!(flattened.get("id").unwrap(), &serde_json::json!(1));
-        assert_eq!(flattened.get("user.name").unwrap(), &serde_json::json!("Alice"));
-        assert_eq!(flattened.get("user.age").unwrap(), &serde_json::json!(30));
+        assert_eq!(flattened["id"], JsonScalar::Number("1".to_string()));
+        assert_eq!(flattened["user.name"], JsonScalar::String("Alice".to_string()));
+        assert_eq!(flattened["user.age"], JsonScalar::Number("30".to_string()));
     }
 
     #[test]
-    fn test_insert_nested() {
-        let mut obj = serde_json::Map::new();
+    fn test_flatten_object_arrays() {
+        let obj: &RawValue = serde_json::from_str(
+            r#"{
+                "tags": ["a", "b"],
+                "items": [{"name": "pen"}, {"name": "ink", "sizes": [1, 2]}],
+                "empty": []
+            }"#,
+        )
+        .unwrap();
+
+        let flattened = flatten_object(obj, "", 0).unwrap();
+
+        let string = |s: &str| JsonScalar::String(s.to_string());
+        assert_eq!(flattened.len(), 7);
+        assert_eq!(flattened["tags.0"], string("a"));
+        assert_eq!(flattened["tags.1"], string("b"));
+        assert_eq!(flattened["items.0.name"], string("pen"));
+        assert_eq!(flattened["items.1.name"], string("ink"));
+        assert_eq!(flattened["items.1.sizes.1"], JsonScalar::Number("2".to_string()));
+        assert_eq!(flattened["empty"], JsonScalar::EmptyArray);
+    }
+
+    #[test]
+    fn test_parse_json_rejects_deep_nesting() {
+        let depth = MAX_NESTING + 1;
+        let json = format!("[{}1{}]", r#"{"a":"#.repeat(depth), "}".repeat(depth));
+        assert!(parse_json(&json).is_err());
+
+        let json = format!("[{{\"a\":{}1{}}}]", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_json_decimal_column_round_trip() {
+        let json = r#"[{"price":19.90,"ratio":0.5},{"price":5.00,"ratio":1.25},{"price":null,"ratio":2.5}]"#;
+        let data = parse_json(json).unwrap();
+
+        assert_eq!(data.columns[0].inferred_type, ColumnType::Decimal);
+        assert_eq!(data.columns[0].values[1], Value::Decimal("5.00".to_string()));
+        assert_eq!(data.columns[1].inferred_type, ColumnType::Float);
+        assert_eq!(to_json(&data).unwrap(), json);
+    }
+
+    #[test]
+    fn test_json_epoch_column_round_trip() {
+        let json = r#"[{"id":1,"time":1672531200},{"id":2,"time":null},{"id":3,"time":1672531260}]"#;
+        let data = parse_json(json).unwrap();
+
+        assert_eq!(data.columns[1].inferred_type, ColumnType::Timestamp);
+        assert_eq!(data.columns[1].values[0], Value::Timestamp(1_672_531_200));
+        assert_eq!(to_json(&data).unwrap(), json);
+
+        // Epochs written as strings stay strings
+        let data = parse_json(r#"[{"time":"1672531200"}]"#).unwrap();
+        assert_eq!(data.columns[0].values[0], Value::string("1672531200"));
+    }
+
+    #[test]
+    fn test_to_json_rebuilds_arrays() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("id", vec![Value::Integer(1)]));
+        data.add_column(Column::new("items.0.name", vec![Value::string("pen")]));
+        data.add_column(Column::new("items.1.name", vec![Value::string("ink")]));
+        data.add_column(Column::new("tags.0", vec![Value::string("new")]));
 
-        insert_nested(&mut obj, "id", serde_json::json!(1));
-        insert_nested(&mut obj, "user.name", serde_json::json!("Alice"));
-        insert_nested(&mut obj, "user.age", serde_json::json!(30));
+        let output: serde_json::Value = serde_json::from_str(&to_json(&data).unwrap()).unwrap();
 
-        assert_eq!(obj.get("id").unwrap(), &serde_json::json!(1));
         assert_eq!(
-            obj.get("user").unwrap().get("name").unwrap(),
-            &serde_json::json!("Alice")
+            output,
+            serde_json::json!([{
+                "id": 1,
+                "items": [{"name": "pen"}, {"name": "ink"}],
+                "tags": ["new"]
+            }])
         );
+    }
+
+    #[test]
+    fn test_to_json_sparse_array() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("values.0", vec![Value::Integer(1)]));
+        data.add_column(Column::new("values.2", vec![Value::Integer(3)]));
+
+        let output: serde_json::Value = serde_json::from_str(&to_json(&data).unwrap()).unwrap();
+
+        assert_eq!(output, serde_json::json!([{"values": [1, null, 3]}]));
+    }
+
+    #[test]
+    fn test_to_json_mixed_object_array_siblings() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("list.0", vec![Value::string("a")]));
+        data.add_column(Column::new("list.1", vec![Value::string("b")]));
+        data.add_column(Column::new("mixed.0", vec![Value::Integer(1)]));
+        data.add_column(Column::new("mixed.label", vec![Value::string("x")]));
+        data.add_column(Column::new("years.2023", vec![Value::Integer(7)]));
+
+        let output: serde_json::Value = serde_json::from_str(&to_json(&data).unwrap()).unwrap();
+
+        // Only purely index-keyed, mostly dense objects become arrays
+        assert_eq!(
+            output,
+            serde_json::json!([{
+                "list": ["a", "b"],
+                "mixed": {"0": 1, "label": "x"},
+                "years": {"2023": 7}
+            }])
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip_array_of_objects() {
+        let original_json = r#"[
+            {"id": 1, "order": {"items": [{"sku": "A1", "qty": 2}, {"sku": "B2", "qty": 1}], "paid": true}},
+            {"id": 2, "order": {"items": [{"sku": "C3", "qty": 4}, {"sku": "A1", "qty": 1}], "paid": false}}
+        ]"#;
+
+        let data = parse_json(original_json).unwrap();
+        assert!(data.get_column_by_name("order.items.1.sku").is_some());
+
+        let output: serde_json::Value = serde_json::from_str(&to_json(&data).unwrap()).unwrap();
+        let original: serde_json::Value = serde_json::from_str(original_json).unwrap();
+        assert_eq!(output, original);
+    }
+
+    #[test]
+    fn test_parse_array_index() {
+        assert_eq!(parse_array_index("0"), Some(0));
+        assert_eq!(parse_array_index("12"), Some(12));
+        assert_eq!(parse_array_index("01"), None);
+        assert_eq!(parse_array_index("-1"), None);
+        assert_eq!(parse_array_index(""), None);
+        assert_eq!(parse_array_index("name"), None);
+    }
+
+    #[test]
+    fn test_insert_nested() {
+        let mut obj = IndexMap::new();
+        let number = |text: &str| JsonOutput::number(text.to_string()).unwrap();
+
+        insert_nested(&mut obj, "id", number("1"));
+        insert_nested(&mut obj, "user.name", JsonOutput::String("Alice".to_string()));
+        insert_nested(&mut obj, "user.age", number("30"));
+
         assert_eq!(
-            obj.get("user").unwrap().get("age").unwrap(),
-            &serde_json::json!(30)
+            serde_json::to_string(&obj).unwrap(),
+            r#"{"id":1,"user":{"name":"Alice","age":30}}"#
         );
     }
 
     #[test]
     fn test_json_value_to_value() {
-        assert!(json_value_to_value(&serde_json::Value::Null).is_null());
+        let number = |text: &str| JsonScalar::Number(text.to_string());
+        assert!(json_value_to_value(&JsonScalar::Null).is_null());
         assert_eq!(
-            json_value_to_value(&serde_json::json!(42)).as_integer(),
+            json_value_to_value(&number("42")).as_integer(),
             Some(42)
         );
         assert_eq!(
-            json_value_to_value(&serde_json::json!(3.14)).as_float(),
+            json_value_to_value(&number("3.14")).as_float(),
             Some(3.14)
         );
         assert_eq!(
-            json_value_to_value(&serde_json::json!("hello")).as_str(),
+            json_value_to_value(&JsonScalar::String("hello".to_string())).as_str(),
             Some("hello")
         );
         assert_eq!(
-            json_value_to_value(&serde_json::json!(true)).as_boolean(),
+            json_value_to_value(&JsonScalar::Bool(true)).as_boolean(),
             Some(true)
         );
+
+        // Exponent literals keep their exact text
+        for text in ["1.5E10", "2.5E+3", "1e9", "-3e-2"] {
+            assert_eq!(json_value_to_value(&number(text)), Value::Decimal(text.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_json_exponent_literals_round_trip() {
+        let json = r#"[{"a":1.5E10,"b":1},{"a":2.5E+3,"b":2},{"a":1e9,"b":3}]"#;
+        let data = parse_json(json).unwrap();
+
+        assert_eq!(data.columns[0].values[0], Value::Decimal("1.5E10".to_string()));
+        assert_eq!(to_json(&data).unwrap(), json);
     }
 
     #[test]
     fn test_value_to_json_value() {
-        assert!(value_to_json_value(&Value::Null).is_null());
-        assert_eq!(value_to_json_value(&Value::Integer(42)), serde_json::json!(42));
-        assert_eq!(value_to_json_value(&Value::Float(3.14)), serde_json::json!(3.14));
-        assert_eq!(
-            value_to_json_value(&Value::string("hello")),
-            serde_json::json!("hello")
-        );
-        assert_eq!(
-            value_to_json_value(&Value::Boolean(true)),
-            serde_json::json!(true)
-        );
+        let json = |value: Value| serde_json::to_string(&value_to_json_value(&value)).unwrap();
+        assert_eq!(json(Value::Null), "null");
+        assert_eq!(json(Value::Integer(42)), "42");
+        assert_eq!(json(Value::Float(3.14)), "3.14");
+        assert_eq!(json(Value::string("hello")), r#""hello""#);
+        assert_eq!(json(Value::Boolean(true)), "true");
+        assert_eq!(json(Value::Decimal("19.90".to_string())), "19.90");
+        assert_eq!(json(Value::Decimal("+7".to_string())), "7");
     }
 }
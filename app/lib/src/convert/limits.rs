@@ -0,0 +1,155 @@
+//! Allocation limits for parsing untrusted input.
+//!
+//! The text parsers hold every value of their input in memory, so a huge
+//! CSV or JSON file can exhaust memory before compression starts.
+//! [`InputLimits`] lets callers reject such input early: the byte limit is
+//! checked before parsing (or while reading, for input from a reader), the
+//! cell limit while rows are being read.
+
+use crate::error::{AlsError, Result};
+use std::io::{self, Read};
+
+/// Limits enforced while parsing text input into `TabularData`.
+///
+/// Built from `CompressorConfig::max_input_bytes` and
+/// `CompressorConfig::max_cells`; `None` disables a limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct InputLimits {
+    /// Maximum input length in bytes
+    pub max_input_bytes: Option<usize>,
+    /// Maximum number of cells (rows times columns)
+    pub max_cells: Option<usize>,
+}
+
+impl InputLimits {
+    /// No limits; the behaviour of the plain `parse_*` functions.
+    pub const NONE: Self = Self {
+        max_input_bytes: None,
+        max_cells: None,
+    };
+
+    /// Reject `input` if it is longer than `max_input_bytes`.
+    pub fn check_input(&self, input: &str) -> Result<()> {
+        match self.max_input_bytes {
+            Some(limit) if input.len() > limit => Err(AlsError::InputLimitExceeded {
+                limit,
+                unit: "bytes",
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Wrap `reader` so that reading more than `max_input_bytes` fails.
+    pub fn limit_reader<R: Read>(&self, reader: R) -> LimitedReader<R> {
+        LimitedReader {
+            inner: reader,
+            limit: self.max_input_bytes,
+            read: 0,
+        }
+    }
+
+    /// Reject a parse that has produced more than `max_cells` cells so far.
+    pub fn check_cells(&self, cells: usize) -> Result<()> {
+        match self.max_cells {
+            Some(limit) if cells > limit => Err(AlsError::InputLimitExceeded {
+                limit,
+                unit: "cells",
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Reader that fails once more than a byte limit has been read.
+///
+/// The parsers report the read failure as their own parse error, so
+/// [`LimitedReader::check`] turns it back into `InputLimitExceeded`.
+pub(crate) struct LimitedReader<R> {
+    /// Reader being limited
+    inner: R,
+    /// Maximum number of bytes to read, if any
+    limit: Option<usize>,
+    /// Number of bytes read so far
+    read: usize,
+}
+
+impl<R> LimitedReader<R> {
+    /// Replace the result of a parse through this reader with
+    /// `InputLimitExceeded` if the parse read past the limit.
+    pub fn check<T>(&self, result: Result<T>) -> Result<T> {
+        match self.limit {
+            Some(limit) if self.read > limit => Err(AlsError::InputLimitExceeded {
+                limit,
+                unit: "bytes",
+            }),
+            _ => result,
+        }
+    }
+}
+
+impl<R: Read> Read for LimitedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        self.read += count;
+        match self.limit {
+            Some(limit) if self.read > limit => {
+                Err(io::Error::new(io::ErrorKind::InvalidData, "input limit exceeded"))
+            }
+            _ => Ok(count),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_limits_accept_everything() {
+        let limits = InputLimits::NONE;
+        assert!(limits.check_input(&"x".repeat(10_000)).is_ok());
+        assert!(limits.check_cells(usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_limits_are_inclusive() {
+        let limits = InputLimits {
+            max_input_bytes: Some(4),
+            max_cells: Some(10),
+        };
+        assert!(limits.check_input("abcd").is_ok());
+        assert!(limits.check_cells(10).is_ok());
+
+        match limits.check_input("abcde") {
+            Err(AlsError::InputLimitExceeded { limit, unit }) => {
+                assert_eq!(limit, 4);
+                assert_eq!(unit, "bytes");
+            }
+            other => panic!("Expected InputLimitExceeded, got {:?}", other),
+        }
+        assert!(matches!(
+            limits.check_cells(11),
+            Err(AlsError::InputLimitExceeded { limit: 10, unit: "cells" })
+        ));
+    }
+
+    #[test]
+    fn test_limited_reader() {
+        let limits = InputLimits {
+            max_input_bytes: Some(4),
+            max_cells: None,
+        };
+        let mut text = String::new();
+        let mut reader = limits.limit_reader("abcd".as_bytes());
+        reader.read_to_string(&mut text).unwrap();
+        assert_eq!(text, "abcd");
+        assert!(reader.check(Ok(())).is_ok());
+
+        let mut reader = limits.limit_reader("abcde".as_bytes());
+        let result = reader.read_to_string(&mut String::new()).map_err(AlsError::from);
+        assert!(matches!(
+            reader.check(result),
+            Err(AlsError::InputLimitExceeded { limit: 4, unit: "bytes" })
+        ));
+    }
+}
@@ -1,15 +1,33 @@
 //! Data conversion types and utilities.
 //!
 //! This module contains types for representing tabular data in a format-agnostic
-//! way, enabling conversion between CSV, JSON, ALS, and log formats.
+//! way, enabling conversion between CSV, TSV, JSON, ALS, and log formats.
 
+#[cfg(feature = "avro")]
+pub mod avro;
 pub mod csv;
 pub mod json;
 pub mod log_compress;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod syslog;
 pub mod syslog_optimized;
+pub mod tsv;
+#[cfg(feature = "xml")]
+pub mod xml;
+mod limits;
 mod tabular;
 
+pub(crate) use limits::InputLimits;
+pub(crate) use tabular::{
+    decimal_scale, format_epoch_iso8601, is_epoch_column, is_fixed_decimal_column,
+    is_verbatim_number, parse_epoch,
+};
+#[cfg(any(feature = "parquet", feature = "avro"))]
+pub(crate) use tabular::{boolean_like_value, unscaled_decimals};
+#[cfg(any(feature = "parquet", feature = "avro", feature = "xml"))]
+pub(crate) use tabular::plain_text;
+
 pub use tabular::{Column, ColumnType, TabularData, Value};
 pub use syslog::{parse_syslog, to_syslog, MessageType, SyslogEntry};
 pub use syslog_optimized::parse_syslog_optimized;
@@ -0,0 +1,274 @@
+//! Parquet writing.
+//!
+//! This module converts `TabularData` structures to Parquet files. ALS data
+//! is already columnar, so each column is written directly as one column
+//! chunk of a single row group. Every column is optional, and nulls are
+//! recorded in its definition levels.
+
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use parquet::basic::{LogicalType, Repetition, TimeUnit, Type as PhysicalType};
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, DoubleType, Int64Type};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::format::MilliSeconds;
+use parquet::schema::types::Type;
+
+use crate::convert::{
+    boolean_like_value, plain_text, unscaled_decimals, Column, ColumnType, TabularData, Value,
+};
+use crate::error::{AlsError, Result};
+
+/// Most decimal digits an INT64 decimal column can hold.
+const MAX_INT64_DECIMAL_PRECISION: usize = 18;
+
+/// Convert `TabularData` to Parquet and write it to `writer`.
+///
+/// Column types map to Parquet types as follows:
+///
+/// | Column type       | Physical type | Logical type                    |
+/// |-------------------|---------------|---------------------------------|
+/// | `Integer`         | `INT64`       | `INTEGER(64, signed)`           |
+/// | `Float`           | `DOUBLE`      | none                            |
+/// | `Decimal`         | `INT64`       | `DECIMAL(18, scale)`            |
+/// | `Timestamp`       | `INT64`       | `TIMESTAMP(MILLIS, UTC)`        |
+/// | `Boolean`         | `BOOLEAN`     | none                            |
+/// | `String`, `Mixed` | `BYTE_ARRAY`  | `STRING`                        |
+///
+/// Decimal columns need every value written with the same number of
+/// fraction digits to become Parquet decimals. Those that do not, and any
+/// column with a value that does not fit its type, are written as strings.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::csv::parse_csv;
+/// use als_compression::convert::parquet::to_parquet;
+///
+/// let data = parse_csv("id,name\n1,Alice\n2,Bob").unwrap();
+/// let mut parquet = Vec::new();
+/// to_parquet(&data, &mut parquet).unwrap();
+/// assert!(parquet.starts_with(b"PAR1"));
+/// ```
+pub fn to_parquet<W: Write + Send>(data: &TabularData, writer: W) -> Result<()> {
+    let chunks: Vec<ColumnChunk> = data.columns.iter().map(ColumnChunk::from_column).collect();
+    let fields = chunks
+        .iter()
+        .map(|chunk| chunk.field.clone())
+        .collect();
+    let schema = Type::group_type_builder("schema")
+        .with_fields(fields)
+        .build()
+        .map_err(parquet_error)?;
+
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut file =
+        SerializedFileWriter::new(writer, Arc::new(schema), properties).map_err(parquet_error)?;
+    let mut row_group = file.next_row_group().map_err(parquet_error)?;
+    for chunk in &chunks {
+        let mut column = row_group
+            .next_column()
+            .map_err(parquet_error)?
+            .expect("row group has a column writer per schema field");
+        let levels = Some(chunk.definition_levels.as_slice());
+        match &chunk.values {
+            ChunkValues::Int64(values) => column.typed::<Int64Type>().write_batch(values, levels, None),
+            ChunkValues::Double(values) => column.typed::<DoubleType>().write_batch(values, levels, None),
+            ChunkValues::Boolean(values) => column.typed::<BoolType>().write_batch(values, levels, None),
+            ChunkValues::Bytes(values) => column.typed::<ByteArrayType>().write_batch(values, levels, None),
+        }
+        .map_err(parquet_error)?;
+        column.close().map_err(parquet_error)?;
+    }
+    row_group.close().map_err(parquet_error)?;
+    file.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// A column converted to Parquet values, ready to be written.
+struct ColumnChunk {
+    /// Schema field describing the column.
+    field: Arc<Type>,
+    /// Non-null values in row order.
+    values: ChunkValues,
+    /// 1 for each row holding a value, 0 for each null.
+    definition_levels: Vec<i16>,
+}
+
+/// Non-null values of a column in their Parquet physical type.
+enum ChunkValues {
+    Int64(Vec<i64>),
+    Double(Vec<f64>),
+    Boolean(Vec<bool>),
+    Bytes(Vec<ByteArray>),
+}
+
+impl ColumnChunk {
+    /// Convert a column using the Parquet type for its inferred type,
+    /// falling back to strings when a value does not fit.
+    fn from_column(column: &Column) -> Self {
+        let definition_levels = column
+            .values
+            .iter()
+            .map(|value| i16::from(!value.is_null()))
+            .collect();
+        let non_null = || column.values.iter().filter(|value| !value.is_null());
+
+        let typed = match column.inferred_type {
+            ColumnType::Integer => non_null()
+                .map(Value::as_integer)
+                .collect::<Option<Vec<_>>>()
+                .map(|values| {
+                    let logical = LogicalType::Integer { bit_width: 64, is_signed: true };
+                    (PhysicalType::INT64, Some(logical), None, ChunkValues::Int64(values))
+                }),
+            ColumnType::Float => non_null()
+                .map(Value::as_float)
+                .collect::<Option<Vec<_>>>()
+                .map(|values| (PhysicalType::DOUBLE, None, None, ChunkValues::Double(values))),
+            ColumnType::Decimal => unscaled_decimals(non_null(), MAX_INT64_DECIMAL_PRECISION).map(
+                |(scale, values)| {
+                    let logical = LogicalType::Decimal {
+                        scale: scale as i32,
+                        precision: MAX_INT64_DECIMAL_PRECISION as i32,
+                    };
+                    (PhysicalType::INT64, Some(logical), Some(scale), ChunkValues::Int64(values))
+                },
+            ),
+            ColumnType::Timestamp => non_null()
+                .map(|value| value.as_timestamp()?.checked_mul(1000))
+                .collect::<Option<Vec<_>>>()
+                .map(|values| {
+                    let logical = LogicalType::Timestamp {
+                        is_adjusted_to_u_t_c: true,
+                        unit: TimeUnit::MILLIS(MilliSeconds {}),
+                    };
+                    (PhysicalType::INT64, Some(logical), None, ChunkValues::Int64(values))
+                }),
+            ColumnType::Boolean => non_null()
+                .map(boolean_like_value)
+                .collect::<Option<Vec<_>>>()
+                .map(|values| (PhysicalType::BOOLEAN, None, None, ChunkValues::Boolean(values))),
+            ColumnType::String | ColumnType::Mixed => None,
+        };
+
+        let (physical, logical, scale, values) = typed.unwrap_or_else(|| {
+            let values = non_null().map(|value| ByteArray::from(plain_text(value).as_str())).collect();
+            (PhysicalType::BYTE_ARRAY, Some(LogicalType::String), None, ChunkValues::Bytes(values))
+        });
+
+        let mut field = Type::primitive_type_builder(&column.name, physical)
+            .with_repetition(Repetition::OPTIONAL)
+            .with_logical_type(logical);
+        if let Some(scale) = scale {
+            field = field
+                .with_precision(MAX_INT64_DECIMAL_PRECISION as i32)
+                .with_scale(scale as i32);
+        }
+        let field = field
+            .build()
+            .expect("primitive field types are valid for their logical types");
+
+        Self {
+            field: Arc::new(field),
+            values,
+            definition_levels,
+        }
+    }
+}
+
+/// Report a Parquet writer failure as an I/O error.
+fn parquet_error(error: ParquetError) -> AlsError {
+    AlsError::IoError(io::Error::other(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    fn read_back(data: &TabularData) -> SerializedFileReader<std::fs::File> {
+        let mut file = tempfile::tempfile().unwrap();
+        to_parquet(data, &mut file).unwrap();
+        SerializedFileReader::new(file).unwrap()
+    }
+
+    #[test]
+    fn test_to_parquet_types_and_nulls() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("id", vec![Value::Integer(1), Value::Null, Value::Integer(3)]));
+        data.add_column(Column::new("score", vec![Value::Float(1.5), Value::Float(2.0), Value::Null]));
+        data.add_column(Column::new(
+            "price",
+            vec![
+                Value::Decimal("19.90".to_string()),
+                Value::Null,
+                Value::Decimal("-0.05".to_string()),
+            ],
+        ));
+        data.add_column(Column::new(
+            "ts",
+            vec![Value::Timestamp(1_700_000_000), Value::Null, Value::Timestamp(0)],
+        ));
+        data.add_column(Column::new(
+            "active",
+            vec![Value::Boolean(true), Value::string("no"), Value::Null],
+        ));
+        data.add_column(Column::new("name", vec![Value::string("Alice"), Value::Null, Value::string("")]));
+
+        let reader = read_back(&data);
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 3);
+        let schema = metadata.file_metadata().schema_descr();
+        assert_eq!(schema.column(0).physical_type(), PhysicalType::INT64);
+        assert_eq!(schema.column(1).physical_type(), PhysicalType::DOUBLE);
+        assert_eq!(
+            schema.column(2).logical_type(),
+            Some(LogicalType::Decimal { scale: 2, precision: 18 })
+        );
+        assert!(matches!(schema.column(3).logical_type(), Some(LogicalType::Timestamp { .. })));
+        assert_eq!(schema.column(4).physical_type(), PhysicalType::BOOLEAN);
+        assert_eq!(schema.column(5).logical_type(), Some(LogicalType::String));
+
+        let rows: Vec<Vec<Field>> = reader
+            .get_row_iter(None)
+            .unwrap()
+            .map(|row| row.unwrap().get_column_iter().map(|(_, field)| field.clone()).collect())
+            .collect();
+        assert_eq!(rows[0][0], Field::Long(1));
+        assert_eq!(rows[1][0], Field::Null);
+        assert_eq!(rows[1][1], Field::Double(2.0));
+        assert_eq!(rows[2][1], Field::Null);
+        assert_eq!(rows[0][2].to_string(), "19.90");
+        assert_eq!(rows[2][2].to_string(), "-0.05");
+        assert_eq!(rows[1][2], Field::Null);
+        assert_eq!(rows[0][3], Field::TimestampMillis(1_700_000_000_000));
+        assert_eq!(rows[1][4], Field::Bool(false));
+        assert_eq!(rows[2][4], Field::Null);
+        assert_eq!(rows[0][5], Field::Str("Alice".to_string()));
+        assert_eq!(rows[1][5], Field::Null);
+        assert_eq!(rows[2][5], Field::Str(String::new()));
+    }
+
+    #[test]
+    fn test_to_parquet_falls_back_to_strings() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "mixed_scale",
+            vec![Value::Decimal("1.50".to_string()), Value::Decimal("1e-9".to_string())],
+        ));
+        data.add_column(Column::new("mixed", vec![Value::Integer(1), Value::Boolean(true)]));
+
+        let reader = read_back(&data);
+        let schema = reader.metadata().file_metadata().schema_descr();
+        assert_eq!(schema.column(0).logical_type(), Some(LogicalType::String));
+        assert_eq!(schema.column(1).logical_type(), Some(LogicalType::String));
+
+        let rows: Vec<_> = reader.get_row_iter(None).unwrap().map(|row| row.unwrap()).collect();
+        let second: Vec<_> = rows[1].get_column_iter().map(|(_, field)| field.clone()).collect();
+        assert_eq!(second, vec![Field::Str("1e-9".to_string()), Field::Str("true".to_string())]);
+    }
+}
@@ -5,7 +5,14 @@
 //!
 //! ## Log Format
 //!
-//! Standard syslog format: `<Month> <Day> <Time> <Hostname> <Service>[<PID>]: <Message>`
+//! Two formats are recognized line by line, so mixed files parse:
+//! - BSD syslog (RFC 3164): `<Month> <Day> <Time> <Hostname> <Service>[<PID>]: <Message>`
+//! - RFC 5424: `<PRI>1 <Timestamp> <Hostname> <App> <ProcID> <MsgID> <StructuredData> <Message>`
+//!
+//! RFC 5424 lines leave `month` and `day` empty and keep the full timestamp
+//! in the `time` column; their priority and message ID get their own
+//! columns. Structured-data parameters such as `[meta user="root"]` fill
+//! the same parameter columns as `key=value` pairs in the message.
 //!
 //! ## Compression Optimization
 //!
@@ -14,6 +21,13 @@
 //! - Services and hostnames are extracted for dictionary encoding
 //! - PIDs are extracted as integers for range compression
 //! - Message templates are separated from variable parameters
+//! - `key=value` parameters (`rhost`, `user`, `uid`, `euid`, `tty`) and
+//!   source IPs get their own columns; quoted values such as
+//!   `user="john doe"` are extracted without their quotes
+//!
+//! The full message is always kept in the `message` column, so lines
+//! round-trip exactly. Lines that do not parse as syslog are stored whole in
+//! that column with a `parse_error` message type.
 
 use crate::convert::{Column, TabularData, Value};
 use crate::error::{AlsError, Result};
@@ -22,11 +36,11 @@ use std::borrow::Cow;
 /// Parsed syslog entry with all extracted fields.
 #[derive(Debug, Clone)]
 pub struct SyslogEntry<'a> {
-    /// Month (e.g., "Jun")
-    pub month: &'a str,
-    /// Day of month (1-31)
-    pub day: u8,
-    /// Time string (HH:MM:SS)
+    /// Month (e.g., "Jun"), absent for RFC 5424 lines
+    pub month: Option<&'a str>,
+    /// Day of month (1-31), absent for RFC 5424 lines
+    pub day: Option<u8>,
+    /// Time string (HH:MM:SS), or the full timestamp for RFC 5424 lines
     pub time: &'a str,
     /// Hostname
     pub hostname: &'a str,
@@ -34,7 +48,11 @@ pub struct SyslogEntry<'a> {
     pub service: &'a str,
     /// Process ID (if present)
     pub pid: Option<u32>,
-    /// Log message
+    /// RFC 5424 priority (facility * 8 + severity)
+    pub priority: Option<u8>,
+    /// RFC 5424 message ID (if present)
+    pub msgid: Option<&'a str>,
+    /// Log message, including the structured data of RFC 5424 lines
     pub message: &'a str,
     /// Message type (extracted template)
     pub message_type: MessageType,
@@ -160,6 +178,12 @@ pub fn parse_syslog(input: &str) -> Result<TabularData<'static>> {
     let mut msg_types: Vec<Value<'static>> = Vec::with_capacity(line_count);
     let mut rhosts: Vec<Value<'static>> = Vec::with_capacity(line_count);
     let mut users: Vec<Value<'static>> = Vec::with_capacity(line_count);
+    let mut uids: Vec<Value<'static>> = Vec::with_capacity(line_count);
+    let mut euids: Vec<Value<'static>> = Vec::with_capacity(line_count);
+    let mut ttys: Vec<Value<'static>> = Vec::with_capacity(line_count);
+    let mut ips: Vec<Value<'static>> = Vec::with_capacity(line_count);
+    let mut priorities: Vec<Value<'static>> = Vec::with_capacity(line_count);
+    let mut msgids: Vec<Value<'static>> = Vec::with_capacity(line_count);
     let mut raw_msgs: Vec<Value<'static>> = Vec::with_capacity(line_count);
 
     for (_line_num, line) in lines.iter().enumerate() {
@@ -170,8 +194,10 @@ pub fn parse_syslog(input: &str) -> Result<TabularData<'static>> {
 
         match parse_syslog_line(trimmed) {
             Ok(entry) => {
-                months.push(Value::String(Cow::Owned(entry.month.to_string())));
-                days.push(Value::Integer(entry.day as i64));
+                months.push(entry.month
+                    .map(|m| Value::String(Cow::Owned(m.to_string())))
+                    .unwrap_or(Value::Null));
+                days.push(entry.day.map(|d| Value::Integer(d as i64)).unwrap_or(Value::Null));
                 times.push(Value::String(Cow::Owned(entry.time.to_string())));
                 hostnames.push(Value::String(Cow::Owned(entry.hostname.to_string())));
                 services.push(Value::String(Cow::Owned(entry.service.to_string())));
@@ -183,6 +209,18 @@ pub fn parse_syslog(input: &str) -> Result<TabularData<'static>> {
                 users.push(entry.params.user
                     .map(|u| Value::String(Cow::Owned(u.to_string())))
                     .unwrap_or(Value::Null));
+                uids.push(entry.params.uid.map(|u| Value::Integer(u as i64)).unwrap_or(Value::Null));
+                euids.push(entry.params.euid.map(|u| Value::Integer(u as i64)).unwrap_or(Value::Null));
+                ttys.push(entry.params.tty
+                    .map(|t| Value::String(Cow::Owned(t.to_string())))
+                    .unwrap_or(Value::Null));
+                ips.push(entry.params.ip
+                    .map(|ip| Value::String(Cow::Owned(ip.to_string())))
+                    .unwrap_or(Value::Null));
+                priorities.push(entry.priority.map(|p| Value::Integer(p as i64)).unwrap_or(Value::Null));
+                msgids.push(entry.msgid
+                    .map(|m| Value::String(Cow::Owned(m.to_string())))
+                    .unwrap_or(Value::Null));
                 raw_msgs.push(Value::String(Cow::Owned(entry.message.to_string())));
             }
             Err(_) => {
@@ -196,6 +234,12 @@ pub fn parse_syslog(input: &str) -> Result<TabularData<'static>> {
                 msg_types.push(Value::String(Cow::Owned("parse_error".to_string())));
                 rhosts.push(Value::Null);
                 users.push(Value::Null);
+                uids.push(Value::Null);
+                euids.push(Value::Null);
+                ttys.push(Value::Null);
+                ips.push(Value::Null);
+                priorities.push(Value::Null);
+                msgids.push(Value::Null);
                 raw_msgs.push(Value::String(Cow::Owned(trimmed.to_string())));
             }
         }
@@ -206,7 +250,7 @@ pub fn parse_syslog(input: &str) -> Result<TabularData<'static>> {
         return Ok(TabularData::new());
     }
 
-    let mut data = TabularData::with_capacity(10);
+    let mut data = TabularData::with_capacity(16);
     data.add_column(Column::new(Cow::Borrowed("month"), months));
     data.add_column(Column::new(Cow::Borrowed("day"), days));
     data.add_column(Column::new(Cow::Borrowed("time"), times));
@@ -216,13 +260,28 @@ pub fn parse_syslog(input: &str) -> Result<TabularData<'static>> {
     data.add_column(Column::new(Cow::Borrowed("msg_type"), msg_types));
     data.add_column(Column::new(Cow::Borrowed("rhost"), rhosts));
     data.add_column(Column::new(Cow::Borrowed("user"), users));
+    data.add_column(Column::new(Cow::Borrowed("uid"), uids));
+    data.add_column(Column::new(Cow::Borrowed("euid"), euids));
+    data.add_column(Column::new(Cow::Borrowed("tty"), ttys));
+    data.add_column(Column::new(Cow::Borrowed("ip"), ips));
+    data.add_column(Column::new(Cow::Borrowed("priority"), priorities));
+    data.add_column(Column::new(Cow::Borrowed("msgid"), msgids));
     data.add_column(Column::new(Cow::Borrowed("message"), raw_msgs));
 
     Ok(data)
 }
 
-/// Parse a single syslog line.
+/// Parse a single syslog line, routing RFC 5424 lines by their `<PRI>` prefix.
 fn parse_syslog_line(line: &str) -> Result<SyslogEntry<'_>> {
+    if line.starts_with('<') {
+        parse_rfc5424_line(line)
+    } else {
+        parse_bsd_line(line)
+    }
+}
+
+/// Parse a single BSD syslog line.
+fn parse_bsd_line(line: &str) -> Result<SyslogEntry<'_>> {
     // Format: "Jun 14 15:16:01 combo sshd(pam_unix)[19939]: message"
     // Note: Single-digit days may have extra space: "Jul  1 ..."
     
@@ -257,18 +316,131 @@ fn parse_syslog_line(line: &str) -> Result<SyslogEntry<'_>> {
     let (message_type, params) = classify_message(message);
 
     Ok(SyslogEntry {
-        month,
-        day,
+        month: Some(month),
+        day: Some(day),
+        time,
+        hostname,
+        service,
+        pid,
+        priority: None,
+        msgid: None,
+        message,
+        message_type,
+        params,
+    })
+}
+
+/// Parse a single RFC 5424 line.
+///
+/// Only version 1 is recognized. The process ID must be numeric or nil
+/// (`-`); anything else is rejected so the line is kept whole instead of
+/// losing its process ID.
+fn parse_rfc5424_line(line: &str) -> Result<SyslogEntry<'_>> {
+    // Format: "<34>1 2003-10-11T22:14:15.003Z host su 77 ID47 [meta user="root"] message"
+    let invalid = |message: &str| AlsError::CsvParseError {
+        line: 0,
+        column: 0,
+        message: format!("Invalid RFC 5424 syslog format: {}", message),
+    };
+
+    let pri_end = line.find('>').ok_or_else(|| invalid("unterminated priority"))?;
+    let priority: u8 = line[1..pri_end]
+        .parse()
+        .ok()
+        .filter(|&p| p <= 191)
+        .ok_or_else(|| invalid("priority out of range"))?;
+
+    let mut fields = line[pri_end + 1..].splitn(7, ' ');
+    let mut next_field = || {
+        fields
+            .next()
+            .filter(|f| !f.is_empty())
+            .ok_or_else(|| invalid("not enough fields"))
+    };
+    if next_field()? != "1" {
+        return Err(invalid("unsupported version"));
+    }
+    let time = next_field()?;
+    let hostname = next_field()?;
+    let service = next_field()?;
+    let pid = match next_field()? {
+        "-" => None,
+        procid => Some(procid.parse().map_err(|_| invalid("non-numeric process ID"))?),
+    };
+    let msgid = Some(next_field()?).filter(|&m| m != "-");
+    let message = next_field()?;
+
+    let (sd_params, text) =
+        parse_structured_data(message).ok_or_else(|| invalid("malformed structured data"))?;
+    let (message_type, mut params) = classify_message(text);
+    apply_structured_params(&mut params, &sd_params);
+
+    Ok(SyslogEntry {
+        month: None,
+        day: None,
         time,
         hostname,
         service,
         pid,
+        priority: Some(priority),
+        msgid,
         message,
         message_type,
         params,
     })
 }
 
+/// Split RFC 5424 structured data from the free-form message.
+///
+/// Returns the `key="value"` parameters of every element and the text
+/// after them. Values are returned without their quotes; escapes (`\"`,
+/// `\\`, `\]`) are left in the slice, as in [`extract_param`].
+fn parse_structured_data(input: &str) -> Option<(Vec<(&str, &str)>, &str)> {
+    let mut params = Vec::new();
+    let mut rest = input.strip_prefix('-').unwrap_or(input);
+    while let Some(element) = rest.strip_prefix('[') {
+        // The SD-ID runs up to the first space or the end of the element
+        let id_end = element.find([' ', ']'])?;
+        let mut body = &element[id_end..];
+        loop {
+            body = body.trim_start_matches(' ');
+            if let Some(after) = body.strip_prefix(']') {
+                rest = after;
+                break;
+            }
+            let (name, after_name) = body.split_once("=\"")?;
+            let value = quoted_value(&body[name.len() + 1..])?;
+            params.push((name, value));
+            body = &after_name[value.len() + 1..];
+        }
+    }
+
+    // Nil or at least one element is required, followed by a space or the end
+    if rest.len() == input.len() || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    Some((params, rest.trim_start()))
+}
+
+/// Fill parameters missing from the message with structured-data values.
+fn apply_structured_params<'a>(params: &mut MessageParams<'a>, sd_params: &[(&'a str, &'a str)]) {
+    for &(name, value) in sd_params {
+        if value.is_empty() {
+            continue;
+        }
+        match name {
+            "rhost" => params.rhost = params.rhost.or(Some(value)),
+            "user" => params.user = params.user.or(Some(value)),
+            "uid" => params.uid = params.uid.or(value.parse().ok()),
+            "euid" => params.euid = params.euid.or(value.parse().ok()),
+            "tty" => params.tty = params.tty.or(Some(value)),
+            "ruser" => params.ruser = params.ruser.or(Some(value)),
+            "ip" => params.ip = params.ip.or(Some(value)),
+            _ => {}
+        }
+    }
+}
+
 /// Find the end position of the nth whitespace-separated field.
 fn find_nth_field_end(s: &str, n: usize) -> usize {
     let mut field_count = 0;
@@ -396,30 +568,51 @@ fn classify_message(message: &str) -> (MessageType, MessageParams<'_>) {
 }
 
 /// Extract a parameter value from a message.
-/// Handles key=value pairs separated by spaces.
+///
+/// Handles `key=value` pairs separated by whitespace, where the key must
+/// start a word (so `uid=` does not match inside `euid=`). Values wrapped in
+/// double or single quotes may contain spaces and are returned without the
+/// quotes; a backslash inside quotes escapes the next character and is left
+/// in the returned slice. An unterminated quote is treated as part of an
+/// unquoted value. Empty values (`key=` or `key=""`) yield `None`.
 fn extract_param<'a>(message: &'a str, param: &str) -> Option<&'a str> {
-    // Split by whitespace and find the param
-    for part in message.split_whitespace() {
-        if part.starts_with(param) {
-            let value = &part[param.len()..];
-            if value.is_empty() {
-                return None;
-            }
-            return Some(value);
+    for (start, _) in message.match_indices(param) {
+        let at_word_start = message[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || c == ';');
+        if !at_word_start {
+            continue;
         }
+
+        let rest = &message[start + param.len()..];
+        let value = quoted_value(rest).unwrap_or_else(|| {
+            let end = rest.find(|c: char| c.is_whitespace()).unwrap_or(rest.len());
+            &rest[..end]
+        });
+        return if value.is_empty() { None } else { Some(value) };
     }
-    
-    // Fallback: try direct find for params that might have spaces before them
-    if let Some(start) = message.find(param) {
-        let value_start = start + param.len();
-        let rest = &message[value_start..];
-        let end = rest.find(|c: char| c.is_whitespace()).unwrap_or(rest.len());
-        let value = &rest[..end];
-        if !value.is_empty() {
-            return Some(value);
+
+    None
+}
+
+/// Return the contents of a quoted value at the start of `s`, if it is
+/// terminated by a matching unescaped quote.
+fn quoted_value(s: &str) -> Option<&str> {
+    let quote = s.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let body = &s[1..];
+
+    let mut escaped = false;
+    for (i, c) in body.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return Some(&body[..i]);
         }
     }
-    
+
     None
 }
 
@@ -503,6 +696,9 @@ pub fn to_syslog(data: &TabularData) -> Result<String> {
     let hostname_idx = data.columns.iter().position(|c| c.name == "hostname");
     let service_idx = data.columns.iter().position(|c| c.name == "service");
     let pid_idx = data.columns.iter().position(|c| c.name == "pid");
+    let msg_type_idx = data.columns.iter().position(|c| c.name == "msg_type");
+    let priority_idx = data.columns.iter().position(|c| c.name == "priority");
+    let msgid_idx = data.columns.iter().position(|c| c.name == "msgid");
     let message_idx = data.columns.iter().position(|c| c.name == "message");
 
     for row_idx in 0..data.row_count {
@@ -514,6 +710,24 @@ pub fn to_syslog(data: &TabularData) -> Result<String> {
         let pid = pid_idx.and_then(|i| data.columns[i].values[row_idx].as_integer());
         let message = message_idx.and_then(|i| data.columns[i].values[row_idx].as_str()).unwrap_or("");
 
+        // Unparseable lines were stored whole in the message column
+        let msg_type = msg_type_idx.and_then(|i| data.columns[i].values[row_idx].as_str());
+        if msg_type == Some("parse_error") {
+            output.push_str(message);
+            output.push('\n');
+            continue;
+        }
+
+        // RFC 5424 lines are the ones with a priority
+        let priority = priority_idx.and_then(|i| data.columns[i].values[row_idx].as_integer());
+        if let Some(priority) = priority {
+            let msgid = msgid_idx.and_then(|i| data.columns[i].values[row_idx].as_str()).unwrap_or("-");
+            let pid = pid.map_or_else(|| "-".to_string(), |p| p.to_string());
+            output.push_str(&format!("<{}>1 {} {} {} {} {} {}\n",
+                priority, time, hostname, service, pid, msgid, message));
+            continue;
+        }
+
         // Reconstruct line
         if let Some(p) = pid {
             output.push_str(&format!("{} {:2} {} {} {}[{}]: {}\n", 
@@ -545,7 +759,7 @@ mod tests {
         let data = parse_syslog(log).unwrap();
         
         assert_eq!(data.row_count, 1);
-        assert_eq!(data.column_count(), 10);
+        assert_eq!(data.column_count(), 16);
         
         // Check parsed values
         assert_eq!(data.columns[0].values[0].as_str(), Some("Jun"));
@@ -651,6 +865,89 @@ Jun 15 04:06:18 combo su(pam_unix)[21416]: session opened for user cyrus by (uid
         assert_eq!(extract_param(msg, "nonexistent="), None);
     }
 
+    #[test]
+    fn test_extract_param_word_boundary() {
+        let msg = "authentication failure; logname= euid=7 uid=3";
+        assert_eq!(extract_param(msg, "uid="), Some("3"));
+        assert_eq!(extract_param(msg, "euid="), Some("7"));
+        assert_eq!(extract_param(msg, "logname="), None);
+    }
+
+    #[test]
+    fn test_extract_param_quoted() {
+        let msg = r#"user="john doe" rhost='gw 2.example.com' tty="" note="say \"hi\"" bad="open"#;
+        assert_eq!(extract_param(msg, "user="), Some("john doe"));
+        assert_eq!(extract_param(msg, "rhost="), Some("gw 2.example.com"));
+        assert_eq!(extract_param(msg, "tty="), None);
+        assert_eq!(extract_param(msg, "note="), Some(r#"say \"hi\""#));
+        // Unterminated quotes fall back to an unquoted value
+        assert_eq!(extract_param(msg, "bad="), Some("\"open"));
+    }
+
+    #[test]
+    fn test_parse_syslog_sshd_quoted_fields() {
+        let log = r#"Jun 21 08:56:36 combo sshd(pam_unix)[31246]: authentication failure; logname= uid=500 euid=0 tty="ssh session" ruser= rhost="vpn gateway.example.com" user="john doe""#;
+        let data = parse_syslog(log).unwrap();
+
+        assert_eq!(data.column_count(), 16);
+        let column = |name: &str| &data.get_column_by_name(name).unwrap().values[0];
+        assert_eq!(column("msg_type").as_str(), Some("auth_fail"));
+        assert_eq!(column("rhost").as_str(), Some("vpn gateway.example.com"));
+        assert_eq!(column("user").as_str(), Some("john doe"));
+        assert_eq!(column("uid").as_integer(), Some(500));
+        assert_eq!(column("euid").as_integer(), Some(0));
+        assert_eq!(column("tty").as_str(), Some("ssh session"));
+        assert!(column("ip").is_null());
+
+        assert_eq!(to_syslog(&data).unwrap(), format!("{}\n", log));
+    }
+
+    #[test]
+    fn test_parse_syslog_ftpd_quoted_fields() {
+        let log = r#"Jun 22 03:16:43 combo ftpd[6187]: connection from 211.107.232.1 () at Wed Jun 22 03:16:43 2005
+Jun 22 03:16:45 combo ftpd(pam_unix)[6187]: authentication failure; logname= uid=0 euid=0 tty=ftp ruser= rhost="211.107.232.1" user="anonymous guest""#;
+        let data = parse_syslog(log).unwrap();
+
+        let column = |name: &str| &data.get_column_by_name(name).unwrap().values;
+        assert_eq!(column("msg_type")[0].as_str(), Some("ftp_conn"));
+        assert_eq!(column("ip")[0].as_str(), Some("211.107.232.1"));
+        assert!(column("uid")[0].is_null());
+        assert_eq!(column("rhost")[1].as_str(), Some("211.107.232.1"));
+        assert_eq!(column("user")[1].as_str(), Some("anonymous guest"));
+        assert_eq!(column("tty")[1].as_str(), Some("ftp"));
+
+        assert_eq!(to_syslog(&data).unwrap(), format!("{}\n", log));
+    }
+
+    #[test]
+    fn test_unparseable_line_round_trips() {
+        let log = "Jun 14 15:16:01 combo sshd(pam_unix)[19939]: check pass; user unknown
+-- MARK: not a syslog line --";
+        let data = parse_syslog(log).unwrap();
+
+        assert_eq!(data.row_count, 2);
+        assert_eq!(data.columns[6].values[1].as_str(), Some("parse_error"));
+        assert_eq!(to_syslog(&data).unwrap(), format!("{}\n", log));
+    }
+
+    #[test]
+    fn test_quoted_fields_survive_compression() {
+        use crate::{AlsCompressor, AlsParser, AlsSerializer};
+
+        let log = r#"Jun 21 08:56:36 combo sshd(pam_unix)[31246]: authentication failure; logname= uid=500 euid=0 tty="ssh session" ruser= rhost="vpn gateway.example.com" user="john doe"
+Jun 21 08:56:40 combo sshd(pam_unix)[31247]: authentication failure; logname= uid=500 euid=0 tty="ssh session" ruser= rhost="vpn gateway.example.com" user="jane roe""#;
+        let data = parse_syslog(log).unwrap();
+
+        let doc = AlsCompressor::new().compress(&data).unwrap();
+        let als = AlsSerializer::new().serialize(&doc);
+        let parser = AlsParser::new();
+        let restored = parser.expand_typed(&parser.parse(&als).unwrap()).unwrap();
+
+        let users = &restored.get_column_by_name("user").unwrap().values;
+        assert_eq!(users[1].as_str(), Some("jane roe"));
+        assert_eq!(to_syslog(&restored).unwrap(), format!("{}\n", log));
+    }
+
     #[test]
     fn test_extract_ftp_connection() {
         let msg = "connection from 24.54.76.216 (24-54-76-216.bflony.adelphia.net) at Fri Jun 17 07:07:00 2005";
@@ -686,4 +983,116 @@ Jun 15 04:06:18 combo su(pam_unix)[21416]: session opened for user cyrus by (uid
         assert_eq!(data.columns[1].values[0].as_integer(), Some(1));
         assert_eq!(data.columns[8].values[0].as_str(), Some("root"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_rfc5424_line() {
+        let log = r#"<38>1 2024-03-05T10:15:30.123Z combo sshd 4321 AUTH [meta@32473 rhost="10.0.0.7" user="john doe"] authentication failure; logname= uid=0 euid=0 tty=ssh ruser="#;
+        let data = parse_syslog(log).unwrap();
+
+        assert_eq!(data.column_count(), 16);
+        let column = |name: &str| &data.get_column_by_name(name).unwrap().values[0];
+        assert!(column("month").is_null());
+        assert!(column("day").is_null());
+        assert_eq!(column("time").as_str(), Some("2024-03-05T10:15:30.123Z"));
+        assert_eq!(column("hostname").as_str(), Some("combo"));
+        assert_eq!(column("service").as_str(), Some("sshd"));
+        assert_eq!(column("pid").as_integer(), Some(4321));
+        assert_eq!(column("priority").as_integer(), Some(38));
+        assert_eq!(column("msgid").as_str(), Some("AUTH"));
+        assert_eq!(column("msg_type").as_str(), Some("auth_fail"));
+        assert_eq!(column("rhost").as_str(), Some("10.0.0.7"));
+        assert_eq!(column("user").as_str(), Some("john doe"));
+        assert_eq!(column("uid").as_integer(), Some(0));
+        assert_eq!(column("tty").as_str(), Some("ssh"));
+
+        assert_eq!(to_syslog(&data).unwrap(), format!("{}\n", log));
+    }
+
+    #[test]
+    fn test_parse_syslog_interleaved_formats() {
+        let log = r#"Jun 14 15:16:01 combo sshd(pam_unix)[19939]: check pass; user unknown
+<86>1 2024-03-05T10:15:31Z combo su - - - session opened for user cyrus by (uid=0)
+Jun 15 04:06:18 combo su(pam_unix)[21416]: session opened for user cyrus by (uid=0)
+<30>1 2024-03-05T10:15:32+01:00 - ftpd 29504 - [origin ip="24.54.76.216"][x@1 note="a \"quoted\" \] value"] connection from 24.54.76.216 () at Fri Jun 17 07:07:00 2005"#;
+        let data = parse_syslog(log).unwrap();
+
+        assert_eq!(data.row_count, 4);
+        let column = |name: &str| &data.get_column_by_name(name).unwrap().values;
+        assert_eq!(column("month")[0].as_str(), Some("Jun"));
+        assert!(column("month")[1].is_null());
+        assert_eq!(column("month")[2].as_str(), Some("Jun"));
+        assert!(column("priority")[0].is_null());
+        assert_eq!(column("priority")[1].as_integer(), Some(86));
+        assert_eq!(column("priority")[3].as_integer(), Some(30));
+
+        assert_eq!(column("msg_type")[1].as_str(), Some("sess_open"));
+        assert_eq!(column("user")[1].as_str(), Some("cyrus"));
+        assert!(column("pid")[1].is_null());
+        assert!(column("msgid")[1].is_null());
+
+        assert_eq!(column("msg_type")[3].as_str(), Some("ftp_conn"));
+        assert_eq!(column("hostname")[3].as_str(), Some("-"));
+        assert_eq!(column("ip")[3].as_str(), Some("24.54.76.216"));
+
+        assert_eq!(to_syslog(&data).unwrap(), format!("{}\n", log));
+    }
+
+    #[test]
+    fn test_rfc5424_structured_data_params() {
+        let (params, text) =
+            parse_structured_data(r#"[a@1 user="root" uid="0"][b tty="pts/1"] hello"#).unwrap();
+        assert_eq!(params, vec![("user", "root"), ("uid", "0"), ("tty", "pts/1")]);
+        assert_eq!(text, "hello");
+
+        let (params, text) = parse_structured_data("- hello").unwrap();
+        assert!(params.is_empty());
+        assert_eq!(text, "hello");
+
+        assert_eq!(parse_structured_data("[a]").unwrap().1, "");
+        assert!(parse_structured_data("[a user=\"root\"").is_none());
+        assert!(parse_structured_data("[a user=root] x").is_none());
+        assert!(parse_structured_data("hello").is_none());
+    }
+
+    #[test]
+    fn test_rfc5424_message_params_take_precedence() {
+        let log = r#"<38>1 2024-03-05T10:15:30Z combo sshd 1 - [meta user="sd_user" rhost="sd_host"] authentication failure; user=msg_user"#;
+        let data = parse_syslog(log).unwrap();
+
+        let column = |name: &str| &data.get_column_by_name(name).unwrap().values[0];
+        assert_eq!(column("user").as_str(), Some("msg_user"));
+        assert_eq!(column("rhost").as_str(), Some("sd_host"));
+    }
+
+    #[test]
+    fn test_malformed_rfc5424_lines_round_trip() {
+        let log = r#"<38>2 2024-03-05T10:15:30Z combo sshd 1 - - unsupported version
+<999>1 2024-03-05T10:15:30Z combo sshd 1 - - priority out of range
+<38>1 2024-03-05T10:15:30Z combo sshd worker - - named process
+<38>1 2024-03-05T10:15:30Z combo sshd 1 - [meta user="root" missing structured data end
+<38>1 2024-03-05T10:15:30Z combo"#;
+        let data = parse_syslog(log).unwrap();
+
+        assert_eq!(data.row_count, 5);
+        let msg_types = &data.get_column_by_name("msg_type").unwrap().values;
+        assert!(msg_types.iter().all(|v| v.as_str() == Some("parse_error")));
+        assert_eq!(to_syslog(&data).unwrap(), format!("{}\n", log));
+    }
+
+    #[test]
+    fn test_rfc5424_survives_compression() {
+        use crate::{AlsCompressor, AlsParser, AlsSerializer};
+
+        let log = r#"<38>1 2024-03-05T10:15:30Z combo sshd 4321 AUTH [meta user="root"] authentication failure; rhost=10.0.0.7
+Jun 14 15:16:01 combo sshd(pam_unix)[19939]: check pass; user unknown
+<38>1 2024-03-05T10:15:31Z combo sshd 4322 AUTH - authentication failure; rhost=10.0.0.8"#;
+        let data = parse_syslog(log).unwrap();
+
+        let doc = AlsCompressor::new().compress(&data).unwrap();
+        let als = AlsSerializer::new().serialize(&doc);
+        let parser = AlsParser::new();
+        let restored = parser.expand_typed(&parser.parse(&als).unwrap()).unwrap();
+
+        assert_eq!(to_syslog(&restored).unwrap(), format!("{}\n", log));
+    }
+}
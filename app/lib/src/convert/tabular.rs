@@ -5,6 +5,9 @@
 
 use std::borrow::Cow;
 
+use crate::als::SentinelTokens;
+use crate::error::{AlsError, Result};
+
 /// Zero-copy tabular data representation.
 ///
 /// `TabularData` represents structured data as a collection of columns,
@@ -58,6 +61,52 @@ impl<'a> TabularData<'a> {
         }
     }
 
+    /// Build tabular data from row-major values.
+    ///
+    /// Each row must have one value per header; values keep the types they
+    /// were given. A ragged row returns [`AlsError::ColumnMismatch`] with
+    /// the header count as `schema` and the row width as `data`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::convert::{TabularData, Value};
+    ///
+    /// let data = TabularData::from_rows(
+    ///     vec!["id".to_string(), "name".to_string()],
+    ///     vec![
+    ///         vec![Value::Integer(1), Value::string("Alice")],
+    ///         vec![Value::Integer(2), Value::string("Bob")],
+    ///     ],
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(data.row_count, 2);
+    /// assert_eq!(data.columns[1].values[0], Value::string("Alice"));
+    /// ```
+    pub fn from_rows(headers: Vec<String>, rows: Vec<Vec<Value<'a>>>) -> Result<Self> {
+        if let Some(row) = rows.iter().find(|row| row.len() != headers.len()) {
+            return Err(AlsError::ColumnMismatch {
+                schema: headers.len(),
+                data: row.len(),
+            });
+        }
+
+        let mut columns: Vec<Vec<Value<'a>>> =
+            headers.iter().map(|_| Vec::with_capacity(rows.len())).collect();
+        for row in rows {
+            for (column, value) in columns.iter_mut().zip(row) {
+                column.push(value);
+            }
+        }
+
+        let mut data = Self::with_capacity(headers.len());
+        for (name, values) in headers.into_iter().zip(columns) {
+            data.add_column(Column::new(name, values));
+        }
+        Ok(data)
+    }
+
     /// Add a column to the data.
     ///
     /// # Panics
@@ -130,6 +179,68 @@ impl<'a> TabularData<'a> {
         })
     }
 
+    /// Keep only the rows for which `predicate` returns true.
+    ///
+    /// The predicate sees each row as a slice with one value per column, in
+    /// column order, so `row.get(index)` is `None` for an index past the
+    /// last column. Every column drops the same rows, keeping the columns
+    /// aligned; names and `inferred_type` are unchanged. Values are moved,
+    /// not cloned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::convert::{TabularData, Value};
+    ///
+    /// let mut data = TabularData::from_rows(
+    ///     vec!["id".to_string(), "status".to_string()],
+    ///     vec![
+    ///         vec![Value::Integer(1), Value::string("ok")],
+    ///         vec![Value::Integer(2), Value::string("error")],
+    ///         vec![Value::Integer(3), Value::string("ok")],
+    ///     ],
+    /// )
+    /// .unwrap();
+    ///
+    /// data.filter_rows(|row| row[1].as_str() != Some("error"));
+    /// assert_eq!(data.row_count, 2);
+    /// assert_eq!(data.columns[0].values, vec![Value::Integer(1), Value::Integer(3)]);
+    /// ```
+    pub fn filter_rows(&mut self, predicate: impl Fn(&[Value<'a>]) -> bool) {
+        let mut values: Vec<_> = self
+            .columns
+            .iter_mut()
+            .map(|column| std::mem::take(&mut column.values).into_iter())
+            .collect();
+        let mut row = Vec::with_capacity(self.columns.len());
+        let mut kept = 0;
+
+        for _ in 0..self.row_count {
+            row.extend(values.iter_mut().filter_map(Iterator::next));
+            if predicate(&row) {
+                for (column, value) in self.columns.iter_mut().zip(row.drain(..)) {
+                    column.values.push(value);
+                }
+                kept += 1;
+            } else {
+                row.clear();
+            }
+        }
+
+        self.row_count = kept;
+    }
+
+    /// Infer the type of every column from its current values.    /// Infer the type of every column from its current values.
+    ///
+    /// Returns `(name, type)` pairs in column order. See
+    /// [`Column::infer_type`] for the precedence rules.
+    pub fn schema_types(&self) -> Vec<(String, ColumnType)> {
+        self.columns
+            .iter()
+            .map(|c| (c.name.to_string(), c.infer_type()))
+            .collect()
+    }
+
     /// Convert to owned data (removes lifetime dependency).
     pub fn into_owned(self) -> TabularData<'static> {
         TabularData {
@@ -163,7 +274,7 @@ impl<'a> Column<'a> {
     ///
     /// The column type is automatically inferred from the values.
     pub fn new<S: Into<Cow<'a, str>>>(name: S, values: Vec<Value<'a>>) -> Self {
-        let inferred_type = Self::infer_type(&values);
+        let inferred_type = Self::infer_type_of(&values);
         Self {
             name: name.into(),
             values,
@@ -199,52 +310,86 @@ impl<'a> Column<'a> {
         self.values.get(index)
     }
 
-    /// Infer the column type from values.
-    fn infer_type(values: &[Value<'a>]) -> ColumnType {
-        if values.is_empty() {
-            return ColumnType::String; // Default for empty columns
+    /// Check if every value in the column is identical.
+    ///
+    /// Values of different variants never compare equal, even if they share
+    /// a string representation. An empty column is not constant.
+    pub fn is_constant(&self) -> bool {
+        match self.values.split_first() {
+            Some((first, rest)) => rest.iter().all(|value| value == first),
+            None => false,
         }
+    }
+
+    /// Infer the dominant type of the column from its current values.
+    ///
+    /// Unlike `inferred_type`, which is computed once on construction, this
+    /// rescans the values. Nulls are compatible with every type. Precedence:
+    ///
+    /// 1. No non-null values: `String`
+    /// 2. Only integers: `Integer`
+    /// 3. Only timestamps: `Timestamp`
+    /// 4. Decimals, optionally with integers: `Decimal`
+    /// 5. Integers and decimals with floats: `Float`
+    /// 6. Booleans, optionally with boolean-like strings (`true`/`false`,
+    ///    `yes`/`no`, `y`/`n`, `t`/`f`, any case): `Boolean`
+    /// 7. Any other string: `String`
+    /// 8. Booleans mixed with numbers: `Mixed`
+    ///
+    /// Timestamps mixed with other numbers count as integers.
+    pub fn infer_type(&self) -> ColumnType {
+        Self::infer_type_of(&self.values)
+    }
 
+    /// Infer the column type from values.
+    fn infer_type_of(values: &[Value<'a>]) -> ColumnType {
         let mut has_integer = false;
         let mut has_float = false;
+        let mut has_decimal = false;
+        let mut has_timestamp = false;
         let mut has_string = false;
         let mut has_boolean = false;
+        let mut all_strings_boolean_like = true;
 
         for value in values {
             match value {
                 Value::Null => {} // Null is compatible with any type
                 Value::Integer(_) => has_integer = true,
                 Value::Float(_) => has_float = true,
-                Value::String(_) => has_string = true,
+                Value::Decimal(_) => has_decimal = true,
+                Value::Timestamp(_) => has_timestamp = true,
+                Value::String(s) => {
+                    has_string = true;
+                    all_strings_boolean_like &= is_boolean_like(s);
+                }
                 Value::Boolean(_) => has_boolean = true,
             }
         }
 
-        // Determine the most specific type
-        let type_count = [has_integer, has_float, has_string, has_boolean]
-            .iter()
-            .filter(|&&b| b)
-            .count();
+        let has_number = has_integer || has_float || has_decimal || has_timestamp;
 
-        if type_count == 0 {
-            // All nulls
-            ColumnType::String
-        } else if type_count > 1 {
-            // Mixed types
-            if has_string {
-                ColumnType::String
-            } else if has_float && has_integer {
-                ColumnType::Float // Integers can be represented as floats
+        if has_string {
+            if all_strings_boolean_like && !has_number {
+                ColumnType::Boolean
             } else {
+                ColumnType::String
+            }
+        } else if has_boolean {
+            if has_number {
                 ColumnType::Mixed
+            } else {
+                ColumnType::Boolean
             }
+        } else if has_float {
+            ColumnType::Float // Integers can be represented as floats
+        } else if has_decimal {
+            ColumnType::Decimal
         } else if has_integer {
             ColumnType::Integer
-        } else if has_float {
-            ColumnType::Float
-        } else if has_boolean {
-            ColumnType::Boolean
+        } else if has_timestamp {
+            ColumnType::Timestamp
         } else {
+            // Empty or all nulls
             ColumnType::String
         }
     }
@@ -261,7 +406,8 @@ impl<'a> Column<'a> {
 
 /// A single value in the tabular data.
 ///
-/// Values can be null, integers, floats, strings, or booleans.
+/// Values can be null, integers, floats, decimals, timestamps, strings, or
+/// booleans.
 /// String values use `Cow` for zero-copy support.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum Value<'a> {
@@ -272,6 +418,18 @@ pub enum Value<'a> {
     Integer(i64),
     /// Floating point value (f64).
     Float(f64),
+    /// Fixed-point decimal kept in its exact textual form, e.g. `19.90`.
+    ///
+    /// Used for columns whose values would not survive a round trip through
+    /// `f64`, such as prices with trailing zeros, and for numbers written
+    /// with an exponent or a leading `+`, such as `1e-9`.
+    Decimal(String),
+    /// Unix epoch timestamp in seconds.
+    ///
+    /// Compressed like an integer, so delta and range encodings apply, and
+    /// optionally written back as ISO-8601 text (see
+    /// `ParserConfig::iso8601_timestamps`).
+    Timestamp(i64),
     /// String value with zero-copy support.
     String(Cow<'a, str>),
     /// Boolean value.
@@ -304,6 +462,16 @@ impl<'a> Value<'a> {
         matches!(self, Value::Float(_))
     }
 
+    /// Check if the value is a decimal.
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, Value::Decimal(_))
+    }
+
+    /// Check if the value is a timestamp.
+    pub fn is_timestamp(&self) -> bool {
+        matches!(self, Value::Timestamp(_))
+    }
+
     /// Check if the value is a string.
     pub fn is_string(&self) -> bool {
         matches!(self, Value::String(_))
@@ -327,6 +495,23 @@ impl<'a> Value<'a> {
         match self {
             Value::Float(f) => Some(*f),
             Value::Integer(i) => Some(*i as f64),
+            Value::Decimal(d) => d.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Get the exact text of the value, if it is a decimal.
+    pub fn as_decimal(&self) -> Option<&str> {
+        match self {
+            Value::Decimal(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Get the epoch seconds of the value, if it is a timestamp.
+    pub fn as_timestamp(&self) -> Option<i64> {
+        match self {
+            Value::Timestamp(t) => Some(*t),
             _ => None,
         }
     }
@@ -352,18 +537,51 @@ impl<'a> Value<'a> {
     /// For ALS format, null values are represented as `NULL_TOKEN` and
     /// empty strings as `EMPTY_TOKEN`.
     pub fn to_string_repr(&self) -> Cow<'_, str> {
-        match self {
-            Value::Null => Cow::Borrowed(crate::als::NULL_TOKEN),
-            Value::Integer(i) => Cow::Owned(i.to_string()),
-            Value::Float(f) => Cow::Owned(f.to_string()),
-            Value::String(s) => {
-                if s.is_empty() {
-                    Cow::Borrowed(crate::als::EMPTY_TOKEN)
-                } else {
-                    Cow::Borrowed(s.as_ref())
-                }
+        self.to_string_repr_with(&SentinelTokens::DEFAULT)
+    }
+
+    /// Convert the value to a string representation using `tokens`.
+    ///
+    /// Null values become the null token and empty strings the empty token.
+    /// Values whose text would read back as a token are escaped as
+    /// described on [`SentinelTokens`].
+    pub fn to_string_repr_with<'b>(&'b self, tokens: &'b SentinelTokens) -> Cow<'b, str> {
+        let repr = match self {
+            Value::Null => return Cow::Borrowed(tokens.null()),
+            Value::Decimal(d) => return tokens.encode_str(d),
+            Value::String(s) => return tokens.encode_str(s),
+            Value::Boolean(b) => return tokens.encode_str(if *b { "true" } else { "false" }),
+            Value::Integer(i) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Timestamp(t) => t.to_string(),
+        };
+        if tokens.collides(&repr) {
+            Cow::Owned(format!("\\{}", repr))
+        } else {
+            Cow::Owned(repr)
+        }
+    }
+
+    /// Append the string representation using `tokens` to `out`.
+    ///
+    /// Writes the same text as [`Value::to_string_repr_with`], but formats
+    /// numbers straight into `out` instead of allocating a new string.
+    pub(crate) fn write_string_repr_with(&self, tokens: &SentinelTokens, out: &mut String) {
+        use std::fmt::Write;
+
+        let start = out.len();
+        let written = match self {
+            Value::Integer(i) => write!(out, "{}", i),
+            Value::Float(f) => write!(out, "{}", f),
+            Value::Timestamp(t) => write!(out, "{}", t),
+            _ => {
+                out.push_str(&self.to_string_repr_with(tokens));
+                return;
             }
-            Value::Boolean(b) => Cow::Borrowed(if *b { "true" } else { "false" }),
+        };
+        written.expect("writing to a String cannot fail");
+        if tokens.collides(&out[start..]) {
+            out.insert(start, '\\');
         }
     }
 
@@ -373,6 +591,8 @@ impl<'a> Value<'a> {
             Value::Null => Value::Null,
             Value::Integer(i) => Value::Integer(i),
             Value::Float(f) => Value::Float(f),
+            Value::Decimal(d) => Value::Decimal(d),
+            Value::Timestamp(t) => Value::Timestamp(t),
             Value::String(s) => Value::String(Cow::Owned(s.into_owned())),
             Value::Boolean(b) => Value::Boolean(b),
         }
@@ -420,6 +640,10 @@ pub enum ColumnType {
     Integer,
     /// Floating point values (f64).
     Float,
+    /// Fixed-point decimal values kept as exact text.
+    Decimal,
+    /// Unix epoch timestamps in seconds.
+    Timestamp,
     /// String values.
     #[default]
     String,
@@ -429,7 +653,176 @@ pub enum ColumnType {
     Mixed,
 }
 
+/// Check whether a number is written in a form that formatting its value
+/// never reproduces: with a leading `+` or an exponent, as in `+7`, `1e-9`
+/// or `2.5E10`.
+///
+/// Such numbers keep their exact text as `Value::Decimal`, so that `1e-9`
+/// does not come back as `0.000000001`.
+pub(crate) fn is_verbatim_number(s: &str) -> bool {
+    let marked = s.starts_with('+') || s.bytes().any(|b| b == b'e' || b == b'E');
+    marked && s.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// Get the number of fraction digits of a plain decimal literal.
+///
+/// Accepts an optional leading `-`, at least one integer digit, a `.` and at
+/// least one fraction digit, e.g. `-19.90`. Returns `None` for anything else,
+/// including exponents and surrounding whitespace.
+pub(crate) fn decimal_scale(s: &str) -> Option<usize> {
+    let unsigned = s.strip_prefix('-').unwrap_or(s);
+    let (integer, fraction) = unsigned.split_once('.')?;
+    let all_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    (all_digits(integer) && all_digits(fraction)).then_some(fraction.len())
+}
+
+/// Check whether a column of non-null values looks like fixed-point decimals.
+///
+/// This is the case when every value is a plain decimal literal with the
+/// same number of fraction digits, at least two as with currency amounts,
+/// and at least one of them would not be reproduced exactly by formatting
+/// its `f64` value (`19.90` becomes `19.9`). Columns that survive the float
+/// round trip stay floats.
+pub(crate) fn is_fixed_decimal_column<'s>(values: impl IntoIterator<Item = &'s str>) -> bool {
+    let mut scale = None;
+    let mut lossy = false;
+    for value in values {
+        let Some(value_scale) = decimal_scale(value).filter(|&scale| scale >= 2) else {
+            return false;
+        };
+        if *scale.get_or_insert(value_scale) != value_scale {
+            return false;
+        }
+        lossy |= value.parse::<f64>().map_or(true, |f| f.to_string() != value);
+    }
+    lossy
+}
+
+/// Column names recognized as holding Unix epoch timestamps (any case).
+const TIMESTAMP_COLUMN_NAMES: [&str; 3] = ["timestamp", "ts", "time"];
+
+/// Earliest epoch treated as a plausible timestamp (2000-01-01T00:00:00Z).
+const MIN_PLAUSIBLE_EPOCH: i64 = 946_684_800;
+
+/// Latest epoch treated as a plausible timestamp (2100-01-01T00:00:00Z).
+const MAX_PLAUSIBLE_EPOCH: i64 = 4_102_444_800;
+
+/// Check whether a column holds Unix epoch timestamps in seconds.
+///
+/// The column name must be one of `timestamp`, `ts` or `time` (any case),
+/// and every non-null value an integer in canonical form between the years
+/// 2000 and 2100. Columns without values are not timestamps.
+pub(crate) fn is_epoch_column<'s>(name: &str, values: impl IntoIterator<Item = &'s str>) -> bool {
+    if !TIMESTAMP_COLUMN_NAMES.iter().any(|n| name.eq_ignore_ascii_case(n)) {
+        return false;
+    }
+    let mut any = false;
+    for value in values {
+        if parse_epoch(value).is_none() {
+            return false;
+        }
+        any = true;
+    }
+    any
+}
+
+/// Parse a plausible epoch written as a canonical integer.
+pub(crate) fn parse_epoch(value: &str) -> Option<i64> {
+    value
+        .parse::<i64>()
+        .ok()
+        .filter(|epoch| (MIN_PLAUSIBLE_EPOCH..MAX_PLAUSIBLE_EPOCH).contains(epoch))
+        .filter(|epoch| epoch.to_string() == value)
+}
+
+/// Format epoch seconds as an ISO-8601 UTC timestamp, e.g. `2023-01-01T00:00:00Z`.
+pub(crate) fn format_epoch_iso8601(epoch: i64) -> Option<String> {
+    crate::als::TimestampFormat {
+        separator: 'T',
+        fraction_digits: 0,
+        offset: "Z".to_string(),
+    }
+    .format(epoch)
+}
+
+/// Convert decimal values to unscaled integers sharing one scale, for
+/// writers with a fixed-point decimal type.
+///
+/// Returns `None` unless every value is a plain decimal literal with the
+/// same number of fraction digits and at most `max_precision` digits in all.
+#[cfg(any(feature = "parquet", feature = "avro"))]
+pub(crate) fn unscaled_decimals<'v, 'a: 'v, T: std::str::FromStr>(
+    values: impl Iterator<Item = &'v Value<'a>>,
+    max_precision: usize,
+) -> Option<(usize, Vec<T>)> {
+    let mut scale = None;
+    let mut unscaled = Vec::new();
+    for value in values {
+        let text = value.as_decimal()?;
+        let value_scale = decimal_scale(text)?;
+        if *scale.get_or_insert(value_scale) != value_scale {
+            return None;
+        }
+        let digits = text.replace('.', "");
+        if digits.trim_start_matches('-').len() > max_precision {
+            return None;
+        }
+        unscaled.push(digits.parse().ok()?);
+    }
+    Some((scale.unwrap_or(0), unscaled))
+}
+
+/// Read a boolean value, accepting the boolean-like strings that make a
+/// column infer as boolean.
+#[cfg(any(feature = "parquet", feature = "avro"))]
+pub(crate) fn boolean_like_value(value: &Value) -> Option<bool> {
+    match value {
+        Value::Boolean(b) => Some(*b),
+        Value::String(s) => match s.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "y" | "t" => Some(true),
+            "false" | "no" | "n" | "f" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Format a value as plain text, without sentinel tokens or escaping.
+#[cfg(any(feature = "parquet", feature = "avro", feature = "xml"))]
+pub(crate) fn plain_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(i) => i.to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Decimal(d) => d.clone(),
+        Value::Timestamp(t) => t.to_string(),
+        Value::String(s) => s.to_string(),
+        Value::Boolean(b) => b.to_string(),
+    }
+}
+
+/// Check if a string spells a boolean (case-insensitive, non-numeric forms only).
+fn is_boolean_like(s: &str) -> bool {
+    matches!(
+        s.to_ascii_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "y" | "n" | "t" | "f"
+    )
+}
+
 impl ColumnType {
+    /// Get the lowercase name of this type.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColumnType::Integer => "integer",
+            ColumnType::Float => "float",
+            ColumnType::Decimal => "decimal",
+            ColumnType::Timestamp => "timestamp",
+            ColumnType::String => "string",
+            ColumnType::Boolean => "boolean",
+            ColumnType::Mixed => "mixed",
+        }
+    }
+
     /// Check if this type can represent the given value.
     pub fn can_represent(&self, value: &Value) -> bool {
         match (self, value) {
@@ -437,6 +830,11 @@ impl ColumnType {
             (ColumnType::Integer, Value::Integer(_)) => true,
             (ColumnType::Float, Value::Float(_)) => true,
             (ColumnType::Float, Value::Integer(_)) => true, // Integers can be floats
+            (ColumnType::Float, Value::Decimal(_)) => true,
+            (ColumnType::Decimal, Value::Decimal(_)) => true,
+            (ColumnType::Decimal, Value::Integer(_)) => true,
+            (ColumnType::Timestamp, Value::Timestamp(_)) => true,
+            (ColumnType::Integer, Value::Timestamp(_)) => true, // Timestamps are integers
             (ColumnType::String, Value::String(_)) => true,
             (ColumnType::Boolean, Value::Boolean(_)) => true,
             (ColumnType::Mixed, _) => true, // Mixed accepts anything
@@ -472,6 +870,46 @@ mod tests {
         assert_eq!(data.row_count, 3);
     }
 
+    #[test]
+    fn test_tabular_data_from_rows() {
+        let headers = vec!["id".to_string(), "name".to_string(), "score".to_string()];
+        let data = TabularData::from_rows(
+            headers,
+            vec![
+                vec![Value::Integer(1), Value::string("Alice"), Value::Float(9.5)],
+                vec![Value::Integer(2), Value::Null, Value::Float(7.0)],
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(data.column_names(), vec!["id", "name", "score"]);
+        assert_eq!(data.row_count, 2);
+        assert_eq!(data.columns[0].values, vec![Value::Integer(1), Value::Integer(2)]);
+        assert_eq!(data.columns[1].values, vec![Value::string("Alice"), Value::Null]);
+        // Values are kept as given, not re-inferred
+        assert_eq!(data.columns[2].values[1], Value::Float(7.0));
+    }
+
+    #[test]
+    fn test_tabular_data_from_rows_ragged() {
+        let headers = vec!["a".to_string(), "b".to_string()];
+        let result = TabularData::from_rows(
+            headers,
+            vec![vec![Value::Integer(1), Value::Integer(2)], vec![Value::Integer(3)]],
+        );
+        assert!(matches!(result, Err(AlsError::ColumnMismatch { schema: 2, data: 1 })));
+    }
+
+    #[test]
+    fn test_tabular_data_from_rows_empty() {
+        let data = TabularData::from_rows(vec!["a".to_string(), "b".to_string()], Vec::new()).unwrap();
+        assert_eq!(data.column_count(), 2);
+        assert!(data.is_empty());
+
+        let data = TabularData::from_rows(Vec::new(), Vec::new()).unwrap();
+        assert_eq!(data.column_count(), 0);
+    }
+
     #[test]
     #[should_panic(expected = "has 2 values, expected 3")]
     fn test_tabular_data_add_column_mismatch() {
@@ -532,6 +970,67 @@ mod tests {
         assert_eq!(rows.len(), 2);
     }
 
+    fn status_data() -> TabularData<'static> {
+        TabularData::from_rows(
+            vec!["id".to_string(), "status".to_string(), "latency".to_string()],
+            vec![
+                vec![Value::Integer(1), Value::string("ok"), Value::Float(1.5)],
+                vec![Value::Integer(2), Value::string("error"), Value::Null],
+                vec![Value::Integer(3), Value::string("ok"), Value::Float(2.5)],
+                vec![Value::Integer(4), Value::string("error"), Value::Float(9.0)],
+                vec![Value::Integer(5), Value::string("ok"), Value::Float(0.5)],
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_filter_rows_drops_error_rows() {
+        let mut data = status_data();
+        data.filter_rows(|row| row[1].as_str() != Some("error"));
+
+        assert_eq!(data.row_count, 3);
+        assert!(data.columns.iter().all(|column| column.len() == 3));
+        assert_eq!(
+            data.columns[0].values,
+            vec![Value::Integer(1), Value::Integer(3), Value::Integer(5)]
+        );
+        assert!(data.columns[1].values.iter().all(|value| value.as_str() == Some("ok")));
+        assert_eq!(
+            data.columns[2].values,
+            vec![Value::Float(1.5), Value::Float(2.5), Value::Float(0.5)]
+        );
+        assert_eq!(data.column_names(), vec!["id", "status", "latency"]);
+    }
+
+    #[test]
+    fn test_filter_rows_empty_result() {
+        let mut data = status_data();
+        data.filter_rows(|_| false);
+
+        assert!(data.is_empty());
+        assert_eq!(data.column_count(), 3);
+        assert!(data.columns.iter().all(Column::is_empty));
+        assert!(data.get_row(0).is_none());
+    }
+
+    #[test]
+    fn test_filter_rows_missing_column_index() {
+        let mut data = status_data();
+        // Index 7 is past the last column, so `get` finds nothing
+        data.filter_rows(|row| row.get(7).is_some_and(|value| !value.is_null()));
+        assert!(data.is_empty());
+
+        let mut data = status_data();
+        data.filter_rows(|row| row.get(7).is_none() && !row[2].is_null());
+        assert_eq!(data.row_count, 4);
+        assert_eq!(data.get_row(1).unwrap()[0].as_integer(), Some(3));
+
+        let mut empty = TabularData::new();
+        empty.filter_rows(|_| true);
+        assert!(empty.is_empty());
+    }
+
     #[test]
     fn test_tabular_data_column_names() {
         let mut data = TabularData::new();
@@ -588,6 +1087,80 @@ mod tests {
         assert_eq!(col.inferred_type, ColumnType::String);
     }
 
+    #[test]
+    fn test_infer_type_homogeneous() {
+        let col = Column::new("int", vec![Value::Integer(1), Value::Null, Value::Integer(3)]);
+        assert_eq!(col.infer_type(), ColumnType::Integer);
+
+        let col = Column::new("float", vec![Value::Null, Value::Float(1.5)]);
+        assert_eq!(col.infer_type(), ColumnType::Float);
+
+        let col = Column::new("bool", vec![Value::Boolean(true), Value::Null]);
+        assert_eq!(col.infer_type(), ColumnType::Boolean);
+
+        let col = Column::new("str", vec![Value::string("a"), Value::Null]);
+        assert_eq!(col.infer_type(), ColumnType::String);
+    }
+
+    #[test]
+    fn test_infer_type_mixed() {
+        // Integers widen to floats
+        let col = Column::new("num", vec![Value::Integer(1), Value::Float(2.5), Value::Null]);
+        assert_eq!(col.infer_type(), ColumnType::Float);
+
+        // Boolean-like strings keep a boolean column boolean
+        let col = Column::new(
+            "flag",
+            vec![Value::Boolean(true), Value::string("No"), Value::string("f")],
+        );
+        assert_eq!(col.infer_type(), ColumnType::Boolean);
+        let col = Column::new("flag", vec![Value::string("yes"), Value::string("no")]);
+        assert_eq!(col.infer_type(), ColumnType::Boolean);
+
+        // Any other string wins
+        let col = Column::new("str", vec![Value::Boolean(true), Value::string("maybe")]);
+        assert_eq!(col.infer_type(), ColumnType::String);
+        let col = Column::new("str", vec![Value::Integer(1), Value::string("yes")]);
+        assert_eq!(col.infer_type(), ColumnType::String);
+
+        // Booleans and numbers are incompatible
+        let col = Column::new("mixed", vec![Value::Boolean(true), Value::Integer(1)]);
+        assert_eq!(col.infer_type(), ColumnType::Mixed);
+    }
+
+    #[test]
+    fn test_infer_type_rescans_values() {
+        let mut col = Column::new("id", vec![Value::Integer(1)]);
+        col.values.push(Value::Float(2.5));
+
+        assert_eq!(col.inferred_type, ColumnType::Integer);
+        assert_eq!(col.infer_type(), ColumnType::Float);
+    }
+
+    #[test]
+    fn test_schema_types() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("id", vec![Value::Integer(1), Value::Integer(2)]));
+        data.add_column(Column::new("name", vec![Value::string("a"), Value::Null]));
+        data.add_column(Column::new("score", vec![Value::Integer(1), Value::Float(0.5)]));
+
+        assert_eq!(
+            data.schema_types(),
+            vec![
+                ("id".to_string(), ColumnType::Integer),
+                ("name".to_string(), ColumnType::String),
+                ("score".to_string(), ColumnType::Float),
+            ]
+        );
+        assert!(TabularData::new().schema_types().is_empty());
+    }
+
+    #[test]
+    fn test_column_type_name() {
+        assert_eq!(ColumnType::Integer.name(), "integer");
+        assert_eq!(ColumnType::Mixed.name(), "mixed");
+    }
+
     #[test]
     fn test_value_constructors() {
         assert!(Value::Null.is_null());
@@ -666,6 +1239,64 @@ mod tests {
         assert_eq!(data.columns[0].name, "test");
     }
 
+    #[test]
+    fn test_is_fixed_decimal_column() {
+        assert!(is_fixed_decimal_column(["19.99", "5.50", "-3.00"]));
+        // Values a float reproduces exactly stay floats
+        assert!(!is_fixed_decimal_column(["19.99", "5.49"]));
+        // Scales must match and have at least two digits
+        assert!(!is_fixed_decimal_column(["19.90", "5.5"]));
+        assert!(!is_fixed_decimal_column(["1.0", "2.0"]));
+        assert!(!is_fixed_decimal_column(["1.50e2", "2.50"]));
+        assert!(!is_fixed_decimal_column(["1.50", "abc"]));
+        assert!(!is_fixed_decimal_column([]));
+    }
+
+    #[test]
+    fn test_infer_type_decimal() {
+        let column = Column::new("price", vec![
+            Value::Decimal("19.90".to_string()),
+            Value::Null,
+            Value::Integer(5),
+        ]);
+        assert_eq!(column.infer_type(), ColumnType::Decimal);
+        assert_eq!(column.values[0].as_float(), Some(19.9));
+        assert_eq!(column.values[0].to_string_repr(), "19.90");
+    }
+
+    #[test]
+    fn test_column_is_constant() {
+        assert!(Column::new("a", vec![Value::Integer(1); 3]).is_constant());
+        assert!(!Column::new("a", vec![Value::Integer(1), Value::Integer(2)]).is_constant());
+        assert!(!Column::new("a", vec![Value::Integer(1), Value::string("1")]).is_constant());
+        assert!(!Column::new("a", Vec::new()).is_constant());
+    }
+
+    #[test]
+    fn test_is_epoch_column() {
+        assert!(is_epoch_column("ts", ["1672531200", "1672531260"]));
+        assert!(is_epoch_column("Timestamp", ["1672531200"]));
+        // The name must match, and every value be a plausible epoch
+        assert!(!is_epoch_column("created", ["1672531200"]));
+        assert!(!is_epoch_column("time", ["1672531200", "42"]));
+        assert!(!is_epoch_column("time", ["01672531200"]));
+        assert!(!is_epoch_column("time", ["1672531200.5"]));
+        assert!(!is_epoch_column("time", []));
+    }
+
+    #[test]
+    fn test_timestamp_value() {
+        let column = Column::new("ts", vec![Value::Timestamp(1_672_531_200), Value::Null]);
+        assert_eq!(column.infer_type(), ColumnType::Timestamp);
+        assert_eq!(column.values[0].as_timestamp(), Some(1_672_531_200));
+        assert_eq!(column.values[0].to_string_repr(), "1672531200");
+        assert_eq!(format_epoch_iso8601(1_672_531_200).unwrap(), "2023-01-01T00:00:00Z");
+
+        // Timestamps mixed with plain integers are integers
+        let column = Column::new("ts", vec![Value::Timestamp(1_672_531_200), Value::Integer(1)]);
+        assert_eq!(column.infer_type(), ColumnType::Integer);
+    }
+
     #[test]
     fn test_value_default() {
         assert_eq!(Value::default(), Value::Null);
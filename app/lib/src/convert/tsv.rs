@@ -0,0 +1,200 @@
+//! TSV parsing and writing.
+//!
+//! This module provides functions for converting between TSV (tab-separated
+//! values) format and `TabularData` structures. Parsing, type inference, and
+//! quoting follow the same rules as the CSV functions: fields containing
+//! tabs, quotes, or line breaks are wrapped in double quotes so that
+//! round-trips are lossless.
+
+use crate::config::{CsvConfig, RaggedRowPolicy};
+use crate::convert::csv::{parse_csv_sample, parse_delimited, parse_delimited_with_limits, to_delimited};
+use crate::convert::{InputLimits, TabularData};
+use crate::error::Result;
+use crate::simd::SimdDispatcher;
+
+/// Dialect for TSV: tab-delimited with double-quoted fields.
+const TSV: CsvConfig = CsvConfig {
+    delimiter: b'\t',
+    quote: b'"',
+    ragged_rows: RaggedRowPolicy::Error,
+};
+
+/// Parse TSV text into `TabularData`.
+///
+/// This function parses TSV input and infers column types from the data,
+/// exactly like [`parse_csv`](crate::convert::csv::parse_csv).
+///
+/// # Arguments
+///
+/// * `input` - TSV text to parse
+///
+/// # Returns
+///
+/// A `TabularData` structure containing the parsed data.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::tsv::parse_tsv;
+///
+/// let tsv = "id\tname\n1\tAlice\n2\tBob";
+/// let data = parse_tsv(tsv).unwrap();
+/// assert_eq!(data.column_count(), 2);
+/// assert_eq!(data.row_count, 2);
+/// ```
+pub fn parse_tsv(input: &str) -> Result<TabularData<'static>> {
+    parse_delimited(input, &TSV)
+}
+
+/// Parse the header and the first `max_rows` records of TSV text.
+///
+/// Returns the sample and the number of input bytes it spans, as
+/// [`parse_csv_sample`] does for CSV.
+pub fn parse_tsv_sample(input: &str, max_rows: usize) -> Result<(TabularData<'static>, usize)> {
+    parse_csv_sample(input, &TSV, max_rows)
+}
+
+/// Parse TSV text, rejecting input over `limits`.
+pub(crate) fn parse_tsv_with_limits(
+    input: &str,
+    limits: &InputLimits,
+    dispatcher: &SimdDispatcher,
+) -> Result<TabularData<'static>> {
+    parse_delimited_with_limits(input, &TSV, limits, dispatcher)
+}
+
+/// Convert `TabularData` to TSV format.
+///
+/// # Arguments
+///
+/// * `data` - The tabular data to convert
+///
+/// # Returns
+///
+/// A TSV string representation of the data.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::{TabularData, Column, Value};
+/// use als_compression::convert::tsv::to_tsv;
+/// use std::borrow::Cow;
+///
+/// let mut data = TabularData::new();
+/// data.add_column(Column::new(
+///     Cow::Borrowed("id"),
+///     vec![Value::Integer(1), Value::Integer(2)],
+/// ));
+/// data.add_column(Column::new(
+///     Cow::Borrowed("name"),
+///     vec![Value::string("Alice"), Value::string("Bob")],
+/// ));
+///
+/// let tsv = to_tsv(&data).unwrap();
+/// assert!(tsv.contains("id\tname"));
+/// assert!(tsv.contains("1\tAlice"));
+/// ```
+pub fn to_tsv(data: &TabularData) -> Result<String> {
+    to_delimited(data, &TSV)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::{Column, ColumnType, Value};
+    use crate::error::AlsError;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_parse_tsv_basic() {
+        let tsv = "id\tname\tscore\n1\tAlice\t9.5\n2\tBob\t8.0";
+        let data = parse_tsv(tsv).unwrap();
+
+        assert_eq!(data.column_names(), vec!["id", "name", "score"]);
+        assert_eq!(data.row_count, 2);
+        assert_eq!(data.columns[0].inferred_type, ColumnType::Integer);
+        assert_eq!(data.columns[1].values[1], Value::String(Cow::Borrowed("Bob")));
+        assert_eq!(data.columns[2].inferred_type, ColumnType::Float);
+    }
+
+    #[test]
+    fn test_parse_tsv_commas_are_not_delimiters() {
+        let tsv = "name\tcity\nSmith, John\tParis, France";
+        let data = parse_tsv(tsv).unwrap();
+
+        assert_eq!(data.column_count(), 2);
+        assert_eq!(
+            data.columns[0].values[0],
+            Value::String(Cow::Borrowed("Smith, John"))
+        );
+    }
+
+    #[test]
+    fn test_parse_tsv_empty() {
+        let data = parse_tsv("").unwrap();
+        assert_eq!(data.column_count(), 0);
+        assert_eq!(data.row_count, 0);
+    }
+
+    #[test]
+    fn test_parse_tsv_empty_fields() {
+        let tsv = "a\tb\tc\n1\t\t3\n\t\t";
+        let data = parse_tsv(tsv).unwrap();
+
+        assert_eq!(data.row_count, 2);
+        assert_eq!(data.columns[0].values, vec![Value::Integer(1), Value::Null]);
+        assert_eq!(data.columns[1].values, vec![Value::Null, Value::Null]);
+        assert_eq!(data.columns[2].values, vec![Value::Integer(3), Value::Null]);
+    }
+
+    #[test]
+    fn test_parse_tsv_headers_with_spaces() {
+        let tsv = "first name\tlast name\nAda\tLovelace";
+        let data = parse_tsv(tsv).unwrap();
+        assert_eq!(data.column_names(), vec!["first name", "last name"]);
+    }
+
+    #[test]
+    fn test_parse_tsv_error_column_mismatch() {
+        let result = parse_tsv("a\tb\n1\t2\t3");
+        assert!(matches!(result, Err(AlsError::CsvParseError { .. })));
+    }
+
+    #[test]
+    fn test_to_tsv_quotes_embedded_tabs_and_newlines() {
+        let mut data = TabularData::new();
+        data.add_column(Column::new(
+            "text",
+            vec![Value::string("a\tb"), Value::string("line1\nline2")],
+        ));
+        data.add_column(Column::new("n", vec![Value::Integer(1), Value::Integer(2)]));
+
+        let tsv = to_tsv(&data).unwrap();
+        assert!(tsv.contains("\"a\tb\"\t1"));
+        assert!(tsv.contains("\"line1\nline2\"\t2"));
+    }
+
+    #[test]
+    fn test_to_tsv_empty() {
+        let data = TabularData::new();
+        assert_eq!(to_tsv(&data).unwrap(), "");
+    }
+
+    #[test]
+    fn test_tsv_round_trip() {
+        let original = "first name\tnote\tcount\nAda\t\"tab\there\"\t1\nBob\t\"say \"\"hi\"\"\"\t\nCy\t\"multi\nline\"\t3\n";
+        let data = parse_tsv(original).unwrap();
+        let tsv = to_tsv(&data).unwrap();
+        let reparsed = parse_tsv(&tsv).unwrap();
+
+        assert_eq!(reparsed.column_names(), data.column_names());
+        for (a, b) in data.columns.iter().zip(&reparsed.columns) {
+            assert_eq!(a.values, b.values);
+        }
+        assert_eq!(
+            reparsed.columns[1].values[0],
+            Value::String(Cow::Borrowed("tab\there"))
+        );
+        assert_eq!(reparsed.columns[2].values[1], Value::Null);
+    }
+}
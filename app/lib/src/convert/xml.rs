@@ -0,0 +1,221 @@
+//! XML writing.
+//!
+//! This module converts `TabularData` structures to XML documents: a
+//! `<rows>` root holding one `<row>` element per row, with one child element
+//! per column named after the column.
+
+use crate::convert::{plain_text, TabularData, Value};
+use crate::error::Result;
+
+/// Options for writing XML with [`to_xml_with_config`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct XmlConfig {
+    /// Leave out the element of a null value instead of writing it empty.
+    pub omit_nulls: bool,
+}
+
+impl XmlConfig {
+    /// Create the default configuration, which writes nulls as empty
+    /// elements.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether null values are left out rather than written as empty
+    /// elements.
+    pub fn with_omit_nulls(mut self, omit_nulls: bool) -> Self {
+        self.omit_nulls = omit_nulls;
+        self
+    }
+}
+
+/// Convert `TabularData` to an XML document.
+///
+/// Nulls are written as empty elements; see [`to_xml_with_config`] to
+/// leave them out instead.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::csv::parse_csv;
+/// use als_compression::convert::xml::to_xml;
+///
+/// let data = parse_csv("id,name\n1,Alice & Bob").unwrap();
+/// let xml = to_xml(&data).unwrap();
+/// assert!(xml.contains("<row><id>1</id><name>Alice &amp; Bob</name></row>"));
+/// ```
+pub fn to_xml(data: &TabularData) -> Result<String> {
+    to_xml_with_config(data, &XmlConfig::default())
+}
+
+/// Convert `TabularData` to an XML document using the given options.
+///
+/// Each row is written on its own line as a `<row>` element, with the
+/// values of its columns as child elements in column order. Values are
+/// written as plain text with `&`, `<`, `>`, `"`, and `'` escaped, and
+/// carriage returns written as character references so parsers keep them.
+/// Control characters that XML 1.0 cannot represent become U+FFFD.
+///
+/// Column names that are not valid XML element names have their other
+/// characters replaced with `_`, and get a leading `_` if they do not start
+/// with a letter or `_` or if they start with the reserved `xml`.
+///
+/// # Examples
+///
+/// ```
+/// use als_compression::convert::{Column, TabularData, Value};
+/// use als_compression::convert::xml::{to_xml_with_config, XmlConfig};
+///
+/// let mut data = TabularData::new();
+/// data.add_column(Column::new("id", vec![Value::Integer(1)]));
+/// data.add_column(Column::new("note", vec![Value::Null]));
+///
+/// let xml = to_xml_with_config(&data, &XmlConfig::new().with_omit_nulls(true)).unwrap();
+/// assert!(xml.contains("<row><id>1</id></row>"));
+/// ```
+pub fn to_xml_with_config(data: &TabularData, config: &XmlConfig) -> Result<String> {
+    let names: Vec<String> = data.columns.iter().map(|column| element_name(&column.name)).collect();
+
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rows>\n");
+    for row in 0..data.row_count {
+        output.push_str("  <row>");
+        for (column, name) in data.columns.iter().zip(&names) {
+            match column.values.get(row).unwrap_or(&Value::Null) {
+                Value::Null if config.omit_nulls => {}
+                Value::Null => {
+                    output.push('<');
+                    output.push_str(name);
+                    output.push_str("/>");
+                }
+                value => {
+                    output.push('<');
+                    output.push_str(name);
+                    output.push('>');
+                    push_escaped(&mut output, &plain_text(value));
+                    output.push_str("</");
+                    output.push_str(name);
+                    output.push('>');
+                }
+            }
+        }
+        output.push_str("</row>\n");
+    }
+    output.push_str("</rows>\n");
+    Ok(output)
+}
+
+/// A valid XML element name for a column.
+///
+/// Keeps the characters XML 1.0 allows in names; other characters,
+/// including spaces and the namespace separator `:`, become `_`.
+fn element_name(column: &str) -> String {
+    let mut name: String = column
+        .chars()
+        .map(|c| if is_name_char(c) { c } else { '_' })
+        .collect();
+    let reserved = name.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("xml"));
+    if reserved || !name.starts_with(is_name_start_char) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+/// Whether `c` may start an XML 1.0 name (the `NameStartChar` production,
+/// without `:`).
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | '_' | 'a'..='z'
+        | '\u{c0}'..='\u{d6}' | '\u{d8}'..='\u{f6}' | '\u{f8}'..='\u{2ff}'
+        | '\u{370}'..='\u{37d}' | '\u{37f}'..='\u{1fff}' | '\u{200c}'..='\u{200d}'
+        | '\u{2070}'..='\u{218f}' | '\u{2c00}'..='\u{2fef}' | '\u{3001}'..='\u{d7ff}'
+        | '\u{f900}'..='\u{fdcf}' | '\u{fdf0}'..='\u{fffd}' | '\u{10000}'..='\u{effff}')
+}
+
+/// Whether `c` may appear in an XML 1.0 name (the `NameChar` production,
+/// without `:`).
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{b7}' | '\u{300}'..='\u{36f}' | '\u{203f}'..='\u{2040}')
+}
+
+/// Append `text` to `output` as XML character data.
+fn push_escaped(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            '\r' => output.push_str("&#13;"),
+            '\t' | '\n' => output.push(c),
+            c if c < ' ' || c == '\u{fffe}' || c == '\u{ffff}' => output.push('\u{fffd}'),
+            c => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::Column;
+
+    fn special_data() -> TabularData<'static> {
+        let mut data = TabularData::new();
+        data.add_column(Column::new("id", vec![Value::Integer(1), Value::Integer(2)]));
+        data.add_column(Column::new(
+            "first name",
+            vec![Value::string("<Tom & \"Jerry\">"), Value::Null],
+        ));
+        data.add_column(Column::new(
+            "note",
+            vec![Value::string("it's\r\nfine\u{1}"), Value::Boolean(true)],
+        ));
+        data
+    }
+
+    #[test]
+    fn test_to_xml_escapes_special_characters() {
+        let xml = to_xml(&special_data()).unwrap();
+        assert_eq!(
+            xml,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <rows>\n  \
+             <row><id>1</id><first_name>&lt;Tom &amp; &quot;Jerry&quot;&gt;</first_name>\
+             <note>it&apos;s&#13;\nfine\u{fffd}</note></row>\n  \
+             <row><id>2</id><first_name/><note>true</note></row>\n\
+             </rows>\n"
+        );
+    }
+
+    #[test]
+    fn test_to_xml_omits_nulls() {
+        let config = XmlConfig::new().with_omit_nulls(true);
+        let xml = to_xml_with_config(&special_data(), &config).unwrap();
+        assert!(xml.contains("<row><id>2</id><note>true</note></row>"));
+        assert!(!xml.contains("<first_name/>"));
+    }
+
+    #[test]
+    fn test_to_xml_empty() {
+        let xml = to_xml(&TabularData::new()).unwrap();
+        assert_eq!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rows>\n</rows>\n");
+    }
+
+    #[test]
+    fn test_element_name_sanitizes() {
+        assert_eq!(element_name("user.name"), "user.name");
+        assert_eq!(element_name("first name"), "first_name");
+        assert_eq!(element_name("a:b/c"), "a_b_c");
+        assert_eq!(element_name("2nd"), "_2nd");
+        assert_eq!(element_name("-x"), "_-x");
+        assert_eq!(element_name("XmlData"), "_XmlData");
+        assert_eq!(element_name("prénom"), "prénom");
+        assert_eq!(element_name("x²"), "x_");
+        assert_eq!(element_name("½"), "_");
+        assert_eq!(element_name("ªb"), "_b");
+        assert_eq!(element_name("·a"), "_·a");
+        assert_eq!(element_name(""), "_");
+    }
+}
@@ -3,6 +3,8 @@
 //! This module defines all error types that can occur during compression,
 //! decompression, parsing, and serialization operations.
 
+use crate::als::SentinelTokens;
+use crate::simd::SimdLevel;
 use thiserror::Error;
 
 /// Main error type for the ALS compression library.
@@ -78,6 +80,116 @@ pub enum AlsError {
         step: i64,
     },
 
+    /// Invalid float range.
+    ///
+    /// Occurs when a float range operator has a zero step, non-finite
+    /// bounds, values that cannot be reconstructed exactly, or would
+    /// produce too many values.
+    #[error("Invalid float range {start} to {end} with step {step}: {message}")]
+    InvalidFloatRange {
+        /// Start value of the range
+        start: f64,
+        /// End value of the range
+        end: f64,
+        /// Step value of the range
+        step: f64,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Delta reconstruction would overflow.
+    ///
+    /// Occurs when applying the deltas of a delta operator produces a
+    /// value outside the `i64` range.
+    #[error("Delta overflow: value {index} of delta sequence starting at {base} exceeds the i64 range")]
+    DeltaOverflow {
+        /// Base value of the delta sequence
+        base: i64,
+        /// Index of the value that overflowed
+        index: usize,
+    },
+
+    /// Invalid geometric sequence.
+    ///
+    /// Occurs when a geometric operator has a ratio of 0 or 1, produces too
+    /// many values, or reconstructs a value outside the `i64` range.
+    #[error("Invalid geometric sequence {start}^*{ratio}: {message}")]
+    InvalidGeometric {
+        /// First value of the sequence
+        start: i64,
+        /// Ratio between consecutive values
+        ratio: i64,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Invalid sparse range.
+    ///
+    /// Occurs when a sparse range operator excludes a value that is not part
+    /// of its base range, lists exclusions out of order, or would produce too
+    /// many values.
+    #[error("Invalid sparse range {start}>{end}: {message}")]
+    InvalidSparseRange {
+        /// Start value of the base range
+        start: i64,
+        /// End value of the base range
+        end: i64,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Invalid patched range operator.
+    ///
+    /// Occurs when a patched range's step points away from its end, or it
+    /// overrides a position past the end of its base range or lists
+    /// positions out of order.
+    #[error("Invalid patched range {start}>{end}: {message}")]
+    InvalidPatchedRange {
+        /// Start value of the base range
+        start: i64,
+        /// End value of the base range
+        end: i64,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Invalid interleave operator.
+    ///
+    /// Occurs when an interleave operator has fewer than two lanes, or when
+    /// its lanes differ in length by more than the one value a column of odd
+    /// length needs.
+    #[error("Invalid interleave of {lanes} lanes: {message}")]
+    InvalidInterleave {
+        /// Number of lanes
+        lanes: usize,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Invalid timestamp operator value.
+    ///
+    /// Occurs when a timestamp operator's inner value is not an integer or
+    /// falls outside the years representable in its format.
+    #[error("Invalid timestamp value '{value}': {message}")]
+    InvalidTimestamp {
+        /// The inner value that could not be formatted
+        value: String,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Invalid IPv4 operator value.
+    ///
+    /// Occurs when an IPv4 operator's inner value is not an integer in the
+    /// unsigned 32-bit range.
+    #[error("Invalid IPv4 address value '{value}': {message}")]
+    InvalidIpv4 {
+        /// The inner value that could not be formatted
+        value: String,
+        /// Description of the problem
+        message: String,
+    },
+
     /// Version mismatch between parser and ALS document.
     ///
     /// Occurs when attempting to parse an ALS document with a version
@@ -90,6 +202,54 @@ pub enum AlsError {
         found: u8,
     },
 
+    /// Checksum mismatch.
+    ///
+    /// Occurs when an ALS document carries a `!crc` checksum directive
+    /// that does not match the document bytes, indicating corruption.
+    #[error("Checksum mismatch: expected {expected:08x}, found {found:08x}")]
+    ChecksumMismatch {
+        /// Checksum recorded in the document
+        expected: u32,
+        /// Checksum computed over the document bytes
+        found: u32,
+    },
+
+    /// Invalid escape sequence.
+    ///
+    /// Occurs when a dictionary value contains a backslash that is not
+    /// followed by one of the escapes the serializer writes (`\|`, `\\`,
+    /// `\n`, `\r`), which would otherwise corrupt the value silently.
+    #[error("Invalid escape sequence {sequence} at position {position}")]
+    InvalidEscape {
+        /// Byte position of the backslash in the input
+        position: usize,
+        /// The escape sequence as written, e.g. `\x`
+        sequence: String,
+    },
+
+    /// Input limit exceeded.
+    ///
+    /// Occurs when text input is larger than the `max_input_bytes` or
+    /// `max_cells` limit configured on the compressor.
+    #[error("Input exceeds the configured limit of {limit} {unit}")]
+    InputLimitExceeded {
+        /// The configured limit
+        limit: usize,
+        /// What the limit counts: `bytes` or `cells`
+        unit: &'static str,
+    },
+
+    /// Expanded output too large.
+    ///
+    /// Occurs when expanding a document would produce more values than the
+    /// `max_output_values` limit configured on the parser, as with a small
+    /// document like `1>1000000000*1000` built to exhaust memory.
+    #[error("Expanded output exceeds the configured limit of {limit} values")]
+    OutputTooLarge {
+        /// The configured limit
+        limit: usize,
+    },
+
     /// Column count mismatch.
     ///
     /// Occurs when the number of columns in the schema doesn't match
@@ -102,6 +262,53 @@ pub enum AlsError {
         data: usize,
     },
 
+    /// Schema mismatch.
+    ///
+    /// Occurs when merging ALS documents whose schemas do not name the same
+    /// columns in the same order.
+    #[error("Schema mismatch: expected {expected:?}, found {found:?}")]
+    SchemaMismatch {
+        /// Column names of the document being merged into
+        expected: Vec<String>,
+        /// Column names of the document being merged
+        found: Vec<String>,
+    },
+
+    /// Sentinel token mismatch.
+    ///
+    /// Occurs when merging ALS documents that use different tokens for null
+    /// values or empty strings.
+    #[error(
+        "Sentinel token mismatch: expected null {:?} and empty {:?}, found null {:?} and empty {:?}",
+        expected.null(), expected.empty(), found.null(), found.empty()
+    )]
+    SentinelMismatch {
+        /// Tokens of the document being merged into
+        expected: SentinelTokens,
+        /// Tokens of the document being merged
+        found: SentinelTokens,
+    },
+
+    /// Column not found.
+    ///
+    /// Occurs when a column selected by name or index does not exist in
+    /// the document schema.
+    #[error("Column not found: {column}")]
+    ColumnNotFound {
+        /// The column name or index that was requested
+        column: String,
+    },
+
+    /// Unsupported SIMD level.
+    ///
+    /// Occurs when a SIMD level is forced through `SimdConfig::with_forced_level`
+    /// but the CPU does not support the required instruction set.
+    #[error("Unsupported SIMD level: {level} is not available on this CPU")]
+    UnsupportedSimdLevel {
+        /// The level that was requested
+        level: SimdLevel,
+    },
+
     /// I/O error.
     ///
     /// Wraps errors from standard I/O operations.
@@ -109,9 +316,127 @@ pub enum AlsError {
     IoError(#[from] std::io::Error),
 }
 
+impl AlsError {
+    /// Render the error against the ALS text it was parsed from.
+    ///
+    /// Syntax errors and invalid escapes carry a byte position into `input`.
+    /// For those, the rendering names the line and column, shows the
+    /// offending line with a caret under the column and, when the preceding
+    /// character makes the mistake clear, adds a hint. Other errors render
+    /// as their `Display` output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::AlsParser;
+    ///
+    /// let input = "#id\n1>x";
+    /// let error = AlsParser::new().parse(input).unwrap_err();
+    /// let rendered = error.with_source(input);
+    /// assert!(rendered.contains("line 2, column 3"));
+    /// assert!(rendered.contains("1>x\n  |   ^"));
+    /// ```
+    pub fn with_source(&self, input: &str) -> String {
+        let (position, message) = match self {
+            AlsError::AlsSyntaxError { position, message } => (*position, message.clone()),
+            AlsError::InvalidEscape { position, sequence } => {
+                (*position, format!("Invalid escape sequence {}", sequence))
+            }
+            other => return other.to_string(),
+        };
+
+        let span = SourceSpan::locate(input, position);
+        let mut rendered = format!(
+            "ALS syntax error at line {}, column {}: {}",
+            span.line, span.column, message
+        );
+
+        let gutter = " ".repeat(span.line.to_string().len());
+        rendered.push_str(&format!("\n{} |", gutter));
+        rendered.push_str(&format!("\n{} | {}", span.line, span.text));
+        rendered.push_str(&format!("\n{} | {}^", gutter, span.padding));
+        let hint = match self {
+            AlsError::InvalidEscape { .. } => Some("write a literal backslash as `\\\\`"),
+            _ => span.hint(),
+        };
+        if let Some(hint) = hint {
+            rendered.push_str(&format!("\n{} = hint: {}", gutter, hint));
+        }
+        rendered
+    }
+}
+
+/// The line and column a byte position falls on.
+struct SourceSpan<'a> {
+    /// Line number (1-indexed)
+    line: usize,
+    /// Column number in characters (1-indexed)
+    column: usize,
+    /// The full source line, without its line break
+    text: &'a str,
+    /// Source text of the line before the column
+    before: &'a str,
+    /// Whitespace that aligns a caret under the column
+    padding: String,
+}
+
+impl<'a> SourceSpan<'a> {
+    /// Locate `position` in `input`, clamping it to the input.
+    fn locate(input: &'a str, position: usize) -> Self {
+        let mut position = position.min(input.len());
+        while !input.is_char_boundary(position) {
+            position -= 1;
+        }
+
+        let line_start = input[..position].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line_end = input[position..]
+            .find('\n')
+            .map(|i| position + i)
+            .unwrap_or(input.len());
+        let before = &input[line_start..position];
+
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: before.chars().count() + 1,
+            text: input[line_start..line_end].trim_end_matches('\r'),
+            before,
+            // Keep tabs so the caret lines up however the terminal expands them
+            padding: before
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect(),
+        }
+    }
+
+    /// Suggest a fix based on the operator just before the column.
+    fn hint(&self) -> Option<&'static str> {
+        match self.before.trim_end().chars().last()? {
+            '>' => Some("expected integer after `>`"),
+            ':' => Some("expected a step after `:`"),
+            '*' => Some("expected a repeat count after `*`"),
+            '^' => Some("expected a number after `^`"),
+            '_' => Some("expected a dictionary index after `_`"),
+            _ => None,
+        }
+    }
+}
+
 /// Type alias for Results using `AlsError`.
 pub type Result<T> = std::result::Result<T, AlsError>;
 
+/// A syntax error recovered from while parsing leniently.
+///
+/// Returned by `AlsParser::parse_lenient` for each malformed operator that
+/// was replaced with a raw placeholder instead of aborting the parse.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("ALS syntax error at position {position}: {message}")]
+pub struct AlsSyntaxError {
+    /// Byte position in the input where the error occurred
+    pub position: usize,
+    /// Description of the syntax error
+    pub message: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +454,81 @@ mod tests {
         assert!(display.contains("unexpected character"));
     }
 
+    #[test]
+    fn test_with_source_malformed_range() {
+        let input = "#id #name\n1>x|alice";
+        let error = crate::AlsParser::new().parse(input).unwrap_err();
+        assert_eq!(
+            error.with_source(input),
+            "ALS syntax error at line 2, column 3: Expected integer but found RawValue(\"x\")\n  \
+             |\n\
+             2 | 1>x|alice\n  \
+             |   ^\n  \
+             = hint: expected integer after `>`"
+        );
+    }
+
+    #[test]
+    fn test_with_source_missing_step() {
+        let input = "#id\n10>20:";
+        let error = crate::AlsParser::new().parse(input).unwrap_err();
+        let rendered = error.with_source(input);
+        assert!(rendered.contains("line 2, column 7"), "{}", rendered);
+        assert!(rendered.contains("10>20:\n  |       ^"), "{}", rendered);
+        assert!(rendered.contains("hint: expected a step after `:`"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_with_source_aligns_caret_after_tabs_and_multibyte() {
+        let error = AlsError::AlsSyntaxError {
+            position: "ab\n\té".len(),
+            message: "unexpected value".to_string(),
+        };
+        let rendered = error.with_source("ab\n\téx");
+        assert!(rendered.contains("line 2, column 3"), "{}", rendered);
+        assert!(rendered.ends_with("2 | \téx\n  | \t ^"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_with_source_clamps_position() {
+        let error = AlsError::AlsSyntaxError {
+            position: 100,
+            message: "unexpected end of input".to_string(),
+        };
+        let rendered = error.with_source("#a\n1*");
+        assert!(rendered.contains("line 2, column 3"), "{}", rendered);
+        assert!(rendered.contains("hint: expected a repeat count after `*`"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_with_source_invalid_escape() {
+        let error = AlsError::InvalidEscape {
+            position: 6,
+            sequence: "\\q".to_string(),
+        };
+        let rendered = error.with_source("$d:a|b\\q\n");
+        assert!(rendered.starts_with("ALS syntax error at line 1, column 7: Invalid escape sequence \\q"));
+        assert!(rendered.contains("hint: write a literal backslash as `\\\\`"), "{}", rendered);
+    }
+
+    #[test]
+    fn test_with_source_other_errors_use_display() {
+        let error = AlsError::InvalidDictRef { index: 5, size: 3 };
+        assert_eq!(error.with_source("#a\n_5"), error.to_string());
+    }
+
+    #[test]
+    fn test_recovered_syntax_error_display() {
+        let error = AlsSyntaxError {
+            position: 7,
+            message: "dangling multiplier".to_string(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "ALS syntax error at position 7: dangling multiplier"
+        );
+    }
+
     #[test]
     fn test_als_syntax_error_display() {
         let error = AlsError::AlsSyntaxError {
@@ -163,6 +563,100 @@ mod tests {
         assert!(display.contains("step 1"));
     }
 
+    #[test]
+    fn test_invalid_float_range_display() {
+        let error = AlsError::InvalidFloatRange {
+            start: 0.5,
+            end: 2.5,
+            step: 0.0,
+            message: "step cannot be zero".to_string(),
+        };
+        let display = format!("{}", error);
+        assert!(display.contains("0.5 to 2.5 with step 0"));
+        assert!(display.contains("step cannot be zero"));
+    }
+
+    #[test]
+    fn test_delta_overflow_display() {
+        let error = AlsError::DeltaOverflow { base: 42, index: 3 };
+        let display = format!("{}", error);
+        assert!(display.contains("value 3"));
+        assert!(display.contains("starting at 42"));
+    }
+
+    #[test]
+    fn test_invalid_geometric_display() {
+        let error = AlsError::InvalidGeometric {
+            start: 3,
+            ratio: 1,
+            message: "ratio must not be 0 or 1".to_string(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Invalid geometric sequence 3^*1: ratio must not be 0 or 1"
+        );
+    }
+
+    #[test]
+    fn test_invalid_interleave_display() {
+        let error = AlsError::InvalidInterleave {
+            lanes: 2,
+            message: "lane 2 has 1 values but lane 1 has 3".to_string(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Invalid interleave of 2 lanes: lane 2 has 1 values but lane 1 has 3"
+        );
+    }
+
+    #[test]
+    fn test_invalid_sparse_range_display() {
+        let error = AlsError::InvalidSparseRange {
+            start: 1,
+            end: 7,
+            message: "excluded value 9 is not in the range".to_string(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Invalid sparse range 1>7: excluded value 9 is not in the range"
+        );
+    }
+
+    #[test]
+    fn test_invalid_patched_range_display() {
+        let error = AlsError::InvalidPatchedRange {
+            start: 1,
+            end: 7,
+            message: "override position 9 is past the end of the range".to_string(),
+        };
+        assert_eq!(
+            format!("{}", error),
+            "Invalid patched range 1>7: override position 9 is past the end of the range"
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestamp_display() {
+        let error = AlsError::InvalidTimestamp {
+            value: "abc".to_string(),
+            message: "not an integer".to_string(),
+        };
+        let display = format!("{}", error);
+        assert!(display.contains("'abc'"));
+        assert!(display.contains("not an integer"));
+    }
+
+    #[test]
+    fn test_invalid_ipv4_display() {
+        let error = AlsError::InvalidIpv4 {
+            value: "-1".to_string(),
+            message: "not an integer between 0 and 4294967295".to_string(),
+        };
+        let display = format!("{}", error);
+        assert!(display.contains("'-1'"));
+        assert!(display.contains("IPv4"));
+    }
+
     #[test]
     fn test_version_mismatch_display() {
         let error = AlsError::VersionMismatch {
@@ -174,6 +668,41 @@ mod tests {
         assert!(display.contains("found 2"));
     }
 
+    #[test]
+    fn test_invalid_escape_display() {
+        let error = AlsError::InvalidEscape {
+            position: 12,
+            sequence: "\\x".to_string(),
+        };
+        assert_eq!(format!("{}", error), "Invalid escape sequence \\x at position 12");
+    }
+
+    #[test]
+    fn test_input_limit_exceeded_display() {
+        let error = AlsError::InputLimitExceeded {
+            limit: 1000,
+            unit: "cells",
+        };
+        assert_eq!(format!("{}", error), "Input exceeds the configured limit of 1000 cells");
+    }
+
+    #[test]
+    fn test_output_too_large_display() {
+        let error = AlsError::OutputTooLarge { limit: 5000 };
+        assert_eq!(format!("{}", error), "Expanded output exceeds the configured limit of 5000 values");
+    }
+
+    #[test]
+    fn test_checksum_mismatch_display() {
+        let error = AlsError::ChecksumMismatch {
+            expected: 0xdeadbeef,
+            found: 0x1,
+        };
+        let display = format!("{}", error);
+        assert!(display.contains("expected deadbeef"));
+        assert!(display.contains("found 00000001"));
+    }
+
     #[test]
     fn test_column_mismatch_display() {
         let error = AlsError::ColumnMismatch {
@@ -185,6 +714,27 @@ mod tests {
         assert!(display.contains("data has 5"));
     }
 
+    #[test]
+    fn test_schema_mismatch_display() {
+        let error = AlsError::SchemaMismatch {
+            expected: vec!["id".to_string(), "name".to_string()],
+            found: vec!["id".to_string(), "email".to_string()],
+        };
+        let display = format!("{}", error);
+        assert!(display.contains(r#"expected ["id", "name"]"#));
+        assert!(display.contains(r#"found ["id", "email"]"#));
+    }
+
+    #[test]
+    fn test_unsupported_simd_level_display() {
+        let error = AlsError::UnsupportedSimdLevel {
+            level: SimdLevel::Avx512,
+        };
+        let display = format!("{}", error);
+        assert!(display.contains("AVX-512"));
+        assert!(display.contains("not available"));
+    }
+
     #[test]
     fn test_json_parse_error_from() {
         let json_error = serde_json::from_str::<serde_json::Value>("invalid json")
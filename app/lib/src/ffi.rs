@@ -6,12 +6,20 @@
 //! # Memory Management
 //!
 //! All strings returned by this API are allocated on the heap and must be freed
-//! by calling `als_string_free()`. Failure to free strings will result in memory leaks.
+//! by calling `als_string_free()` or `als_free()`. Failure to free strings will result
+//! in memory leaks.
 //!
 //! # Error Handling
 //!
-//! Functions return null pointers on error. Use `als_get_last_error()` to retrieve
-//! the error message.
+//! Functions returning pointers return null on error; the streaming functions return
+//! an `ALS_ERROR_*` code instead. Use `als_get_last_error()` to retrieve the error message.
+//!
+//! # Streaming
+//!
+//! Large inputs can be compressed piece by piece: `als_stream_compress_begin()` opens a
+//! session, `als_stream_compress_feed()` supplies input and `als_stream_compress_end()`
+//! finishes and releases it. Compressed chunks are passed to a callback, which owns
+//! each chunk and frees it with `als_free()`.
 //!
 //! # Thread Safety
 //!
@@ -54,14 +62,40 @@
 //! ```
 
 use std::ffi::CString;
-use std::os::raw::{c_char, c_int};
+use std::io::{self, Read};
+use std::os::raw::{c_char, c_int, c_void};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::ptr;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
 use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
 
 use crate::compress::AlsCompressor;
 use crate::als::AlsParser;
 use crate::config::CompressorConfig;
+use crate::error::Result;
+use crate::streaming::StreamingCompressor;
+
+/// Success status code.
+pub const ALS_OK: c_int = 0;
+
+/// A required pointer argument was null.
+pub const ALS_ERROR_NULL_POINTER: c_int = -1;
+
+/// The input could not be compressed.
+pub const ALS_ERROR_COMPRESSION: c_int = -2;
+
+/// A chunk callback returned a nonzero value.
+pub const ALS_ERROR_CALLBACK: c_int = -3;
+
+/// A panic was caught at the FFI boundary.
+pub const ALS_ERROR_PANIC: c_int = -4;
+
+/// CSV input format for streaming compression.
+pub const ALS_FORMAT_CSV: c_int = 0;
+
+/// JSON array input format for streaming compression.
+pub const ALS_FORMAT_JSON: c_int = 1;
 
 /// Thread-local storage for the last error message.
 ///
@@ -516,6 +550,359 @@ pub unsafe extern "C" fn als_to_json(
     }
 }
 
+/// Opaque handle to a streaming compression session.
+///
+/// Created by `als_stream_compress_begin()` and released by
+/// `als_stream_compress_end()`.
+#[repr(C)]
+pub struct AlsStreamCompressorHandle {
+    _private: [u8; 0],
+}
+
+/// Callback receiving one compressed chunk of a stream.
+///
+/// `chunk` is a null-terminated ALS fragment of `len` bytes (excluding the
+/// null terminator). Ownership passes to the callback, which must release
+/// the chunk with `als_free()`. Return 0 to continue, or any other value to
+/// abort the stream with `ALS_ERROR_CALLBACK`.
+pub type AlsChunkCallback =
+    extern "C" fn(chunk: *mut c_char, len: usize, user_data: *mut c_void) -> c_int;
+
+/// Number of fed buffers queued for the compression worker before
+/// `als_stream_compress_feed()` blocks.
+const STREAM_INPUT_QUEUE: usize = 16;
+
+/// State behind an `AlsStreamCompressorHandle`.
+///
+/// Compression runs on a worker thread reading the fed bytes from a
+/// channel; finished chunks are sent back and handed to the callback on the
+/// caller's thread during `feed` and `end`.
+struct StreamSession {
+    input: Option<SyncSender<Vec<u8>>>,
+    chunks: Receiver<Result<String>>,
+    worker: JoinHandle<()>,
+    callback: AlsChunkCallback,
+    user_data: *mut c_void,
+    /// Error code of the first failure, after which the stream is dead.
+    error: Option<c_int>,
+}
+
+impl StreamSession {
+    /// Hand one chunk to the callback.
+    fn deliver(&mut self, chunk: Result<String>) -> c_int {
+        let als = match chunk {
+            Ok(als) => als,
+            Err(e) => return self.fail(ALS_ERROR_COMPRESSION, format!("Compression failed: {}", e)),
+        };
+        let c_str = match CString::new(als) {
+            Ok(c_str) => c_str,
+            Err(e) => {
+                return self.fail(ALS_ERROR_COMPRESSION, format!("Failed to create C string: {}", e))
+            }
+        };
+
+        let len = c_str.as_bytes().len();
+        match (self.callback)(c_str.into_raw(), len, self.user_data) {
+            0 => ALS_OK,
+            code => self.fail(ALS_ERROR_CALLBACK, format!("Chunk callback returned {}", code)),
+        }
+    }
+
+    /// Deliver the chunks that are ready without waiting for more.
+    fn deliver_ready(&mut self) -> c_int {
+        while let Ok(chunk) = self.chunks.try_recv() {
+            let code = self.deliver(chunk);
+            if code != ALS_OK {
+                return code;
+            }
+        }
+        ALS_OK
+    }
+
+    /// Deliver every remaining chunk, waiting for the worker to produce them.
+    fn deliver_all(&mut self) -> c_int {
+        while let Ok(chunk) = self.chunks.recv() {
+            let code = self.deliver(chunk);
+            if code != ALS_OK {
+                return code;
+            }
+        }
+        ALS_OK
+    }
+
+    /// Record a failure and stop feeding the worker.
+    fn fail(&mut self, code: c_int, message: String) -> c_int {
+        set_last_error(message);
+        self.error = Some(code);
+        self.input = None;
+        code
+    }
+
+    fn feed(&mut self, bytes: &[u8]) -> c_int {
+        if let Some(code) = self.error {
+            return code;
+        }
+
+        let sent = match &self.input {
+            Some(input) => input.send(bytes.to_vec()).is_ok(),
+            None => false,
+        };
+        if sent {
+            self.deliver_ready()
+        } else {
+            // The worker stopped reading: it either failed, in which case
+            // the error is waiting in the channel, or it reached the end of
+            // a JSON array and ignores anything after it.
+            self.input = None;
+            self.deliver_all()
+        }
+    }
+
+    fn finish(mut self) -> c_int {
+        self.input = None;
+        let code = match self.error {
+            Some(code) => code,
+            None => self.deliver_all(),
+        };
+
+        let StreamSession { chunks, worker, .. } = self;
+        drop(chunks);
+        match worker.join() {
+            Ok(()) => code,
+            Err(e) => {
+                set_last_error(format!("Panic during compression: {:?}", e));
+                ALS_ERROR_PANIC
+            }
+        }
+    }
+}
+
+/// `Read` adapter over the buffers fed to a stream.
+///
+/// Reports end of input once the sending side has been dropped.
+struct ChannelReader {
+    receiver: Receiver<Vec<u8>>,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.buffer.len() {
+            match self.receiver.recv() {
+                Ok(bytes) => {
+                    self.buffer = bytes;
+                    self.position = 0;
+                }
+                Err(_) => return Ok(0),
+            }
+        }
+
+        let n = buf.len().min(self.buffer.len() - self.position);
+        buf[..n].copy_from_slice(&self.buffer[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Run a streaming compressor over fed input, sending every chunk back.
+fn run_stream_worker(
+    config: CompressorConfig,
+    json: bool,
+    chunk_rows: usize,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<Result<String>>,
+) {
+    let reader = ChannelReader { receiver, buffer: Vec::new(), position: 0 };
+    let mut stream = StreamingCompressor::with_config(reader, config);
+    if chunk_rows > 0 {
+        stream = stream.with_csv_chunk_size(chunk_rows).with_json_chunk_size(chunk_rows);
+    }
+
+    let chunks: Box<dyn Iterator<Item = Result<String>> + '_> = if json {
+        Box::new(stream.compress_json_chunks())
+    } else {
+        Box::new(stream.compress_csv_chunks())
+    };
+    for chunk in chunks {
+        let failed = chunk.is_err();
+        if sender.send(chunk).is_err() || failed {
+            break;
+        }
+    }
+}
+
+/// Begin a streaming compression session.
+///
+/// Input is supplied in arbitrary pieces with `als_stream_compress_feed()`
+/// and the session is finished with `als_stream_compress_end()`. Compressed
+/// chunks are handed to `callback` as they become ready, always on the
+/// thread calling `feed` or `end`; concatenated in order, they form one ALS
+/// stream readable by the streaming parser.
+///
+/// # Arguments
+///
+/// * `compressor` - Pointer to an ALS compressor whose configuration is used
+/// * `format` - Input format, `ALS_FORMAT_CSV` or `ALS_FORMAT_JSON` (a JSON array)
+/// * `chunk_rows` - Rows (or JSON objects) per chunk (0 = library default)
+/// * `callback` - Function receiving each compressed chunk
+/// * `user_data` - Opaque pointer passed through to `callback`
+///
+/// Returns a pointer to the session, or null on failure.
+///
+/// # Safety
+///
+/// * `compressor` must be a valid pointer returned by `als_compressor_new()`;
+///   it may be freed once this function returns
+/// * `user_data` must stay valid until `als_stream_compress_end()` returns
+/// * The returned pointer must be released exactly once using
+///   `als_stream_compress_end()`
+#[no_mangle]
+pub unsafe extern "C" fn als_stream_compress_begin(
+    compressor: *const AlsCompressorHandle,
+    format: c_int,
+    chunk_rows: usize,
+    callback: Option<AlsChunkCallback>,
+    user_data: *mut c_void,
+) -> *mut AlsStreamCompressorHandle {
+    clear_last_error();
+
+    if compressor.is_null() {
+        set_last_error("Compressor pointer is null".to_string());
+        return ptr::null_mut();
+    }
+
+    let Some(callback) = callback else {
+        set_last_error("Callback pointer is null".to_string());
+        return ptr::null_mut();
+    };
+
+    let json = match format {
+        ALS_FORMAT_CSV => false,
+        ALS_FORMAT_JSON => true,
+        _ => {
+            set_last_error(format!("Unknown stream format: {}", format));
+            return ptr::null_mut();
+        }
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let config = (*(compressor as *const AlsCompressor)).config().clone();
+        let (input, receiver) = mpsc::sync_channel(STREAM_INPUT_QUEUE);
+        let (sender, chunks) = mpsc::channel();
+
+        let worker = thread::Builder::new()
+            .name("als-stream".to_string())
+            .spawn(move || run_stream_worker(config, json, chunk_rows, receiver, sender));
+        match worker {
+            Ok(worker) => {
+                let session = Box::new(StreamSession {
+                    input: Some(input),
+                    chunks,
+                    worker,
+                    callback,
+                    user_data,
+                    error: None,
+                });
+                Box::into_raw(session) as *mut AlsStreamCompressorHandle
+            }
+            Err(e) => {
+                set_last_error(format!("Failed to start compression worker: {}", e));
+                ptr::null_mut()
+            }
+        }
+    }));
+
+    match result {
+        Ok(ptr) => ptr,
+        Err(e) => {
+            set_last_error(format!("Panic starting stream: {:?}", e));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Feed the next piece of input to a streaming compression session.
+///
+/// Pieces may split rows or UTF-8 sequences anywhere. Chunks that are ready
+/// are delivered to the callback before this function returns. Blocks while
+/// the worker is too far behind the fed input.
+///
+/// Returns `ALS_OK`, or an error code once the stream has failed; after a
+/// failure every further call returns the same code.
+///
+/// # Safety
+///
+/// * `stream` must be a valid pointer returned by `als_stream_compress_begin()`
+/// * `bytes` must be a valid pointer to a buffer of at least `len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn als_stream_compress_feed(
+    stream: *mut AlsStreamCompressorHandle,
+    bytes: *const c_char,
+    len: usize,
+) -> c_int {
+    clear_last_error();
+
+    if stream.is_null() {
+        set_last_error("Stream pointer is null".to_string());
+        return ALS_ERROR_NULL_POINTER;
+    }
+
+    if bytes.is_null() {
+        set_last_error("Input pointer is null".to_string());
+        return ALS_ERROR_NULL_POINTER;
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let session = &mut *(stream as *mut StreamSession);
+        session.feed(std::slice::from_raw_parts(bytes as *const u8, len))
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            set_last_error(format!("Panic during compression: {:?}", e));
+            ALS_ERROR_PANIC
+        }
+    }
+}
+
+/// Finish a streaming compression session and release it.
+///
+/// Marks the end of input, delivers the remaining chunks to the callback
+/// and waits for the worker to exit. The handle is freed whatever the
+/// outcome.
+///
+/// Returns `ALS_OK` if the whole stream compressed successfully, or the
+/// error code of the first failure.
+///
+/// # Safety
+///
+/// * `stream` must be a valid pointer returned by `als_stream_compress_begin()`
+/// * `stream` must not have been previously ended
+/// * After calling this function, `stream` must not be used again
+#[no_mangle]
+pub unsafe extern "C" fn als_stream_compress_end(stream: *mut AlsStreamCompressorHandle) -> c_int {
+    clear_last_error();
+
+    if stream.is_null() {
+        set_last_error("Stream pointer is null".to_string());
+        return ALS_ERROR_NULL_POINTER;
+    }
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Box::from_raw(stream as *mut StreamSession).finish()
+    }));
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            set_last_error(format!("Panic during compression: {:?}", e));
+            ALS_ERROR_PANIC
+        }
+    }
+}
+
 /// Free a string returned by the ALS library.
 ///
 /// # Safety
@@ -533,6 +920,21 @@ pub unsafe extern "C" fn als_string_free(s: *mut c_char) {
     }
 }
 
+/// Free memory allocated by the ALS library.
+///
+/// Releases stream chunks passed to an `AlsChunkCallback` as well as any
+/// string returned by the other functions; equivalent to `als_string_free()`.
+///
+/// # Safety
+///
+/// * `ptr` must be a pointer handed out by the ALS library, or null
+/// * `ptr` must not have been previously freed
+/// * After calling this function, `ptr` must not be used again
+#[no_mangle]
+pub unsafe extern "C" fn als_free(ptr: *mut c_char) {
+    als_string_free(ptr);
+}
+
 /// Get the last error message.
 ///
 /// # Arguments
@@ -732,4 +1134,121 @@ mod tests {
             als_compressor_free(compressor);
         }
     }
+    
+    /// Chunk callback appending every chunk to the `Vec<String>` behind `user_data`.
+    extern "C" fn collect_chunk(chunk: *mut c_char, len: usize, user_data: *mut c_void) -> c_int {
+        unsafe {
+            let chunks = &mut *(user_data as *mut Vec<String>);
+            let text = CStr::from_ptr(chunk).to_str().unwrap().to_string();
+            assert_eq!(text.len(), len);
+            chunks.push(text);
+            als_free(chunk);
+        }
+        0
+    }
+    
+    /// Chunk callback rejecting every chunk.
+    extern "C" fn reject_chunk(chunk: *mut c_char, _len: usize, _user_data: *mut c_void) -> c_int {
+        unsafe { als_free(chunk) };
+        1
+    }
+    
+    /// Feed `input` to a new stream in pieces of `piece` bytes, returning the
+    /// collected chunks and the status of `als_stream_compress_end()`.
+    unsafe fn stream_compress(input: &str, format: c_int, chunk_rows: usize, piece: usize) -> (Vec<String>, c_int) {
+        let compressor = als_compressor_new();
+        let mut chunks: Vec<String> = Vec::new();
+        let stream = als_stream_compress_begin(
+            compressor,
+            format,
+            chunk_rows,
+            Some(collect_chunk),
+            &mut chunks as *mut Vec<String> as *mut c_void,
+        );
+        assert!(!stream.is_null());
+        als_compressor_free(compressor);
+        
+        for bytes in input.as_bytes().chunks(piece) {
+            assert_eq!(als_stream_compress_feed(stream, bytes.as_ptr() as *const c_char, bytes.len()), ALS_OK);
+        }
+        let status = als_stream_compress_end(stream);
+        (chunks, status)
+    }
+    
+    #[test]
+    fn test_stream_compress_csv() {
+        let mut csv = String::from("id,level,host\n");
+        for i in 0..200 {
+            csv.push_str(&format!("{},{},node-{}\n", i, ["info", "warn", "error"][i % 3], i % 4));
+        }
+        
+        let (chunks, status) = unsafe { stream_compress(&csv, ALS_FORMAT_CSV, 32, 7) };
+        assert_eq!(status, ALS_OK);
+        
+        let expected: Vec<String> = StreamingCompressor::new(csv.as_bytes())
+            .with_csv_chunk_size(32)
+            .compress_csv_chunks()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(chunks, expected);
+        assert!(chunks.len() > 1);
+    }
+    
+    #[test]
+    fn test_stream_compress_json() {
+        let json = r#"[{"id": 1, "name": "Alice"}, {"id": 2, "name": "Bob"}, {"id": 3, "name": "Carol"}]"#;
+        
+        let (chunks, status) = unsafe { stream_compress(json, ALS_FORMAT_JSON, 2, 5) };
+        assert_eq!(status, ALS_OK);
+        assert_eq!(chunks.len(), 2);
+        
+        let parser = AlsParser::new();
+        assert!(parser.to_json(&chunks[0]).unwrap().contains("Alice"));
+        assert!(parser.to_json(&chunks[1]).unwrap().contains("Carol"));
+    }
+    
+    #[test]
+    fn test_stream_compress_error_code() {
+        let (chunks, status) = unsafe { stream_compress("[{\"id\": 1}, oops]", ALS_FORMAT_JSON, 0, 4) };
+        assert_eq!(status, ALS_ERROR_COMPRESSION);
+        assert!(chunks.is_empty());
+    }
+    
+    #[test]
+    fn test_stream_callback_abort() {
+        unsafe {
+            let compressor = als_compressor_new();
+            let stream = als_stream_compress_begin(compressor, ALS_FORMAT_CSV, 1, Some(reject_chunk), ptr::null_mut());
+            als_compressor_free(compressor);
+            
+            let csv = "id\n1\n2\n3\n";
+            let mut status = ALS_OK;
+            for bytes in csv.as_bytes().chunks(2) {
+                status = als_stream_compress_feed(stream, bytes.as_ptr() as *const c_char, bytes.len());
+                if status != ALS_OK {
+                    break;
+                }
+            }
+            
+            let end = als_stream_compress_end(stream);
+            assert_eq!(end, ALS_ERROR_CALLBACK);
+            assert!(status == ALS_OK || status == ALS_ERROR_CALLBACK);
+        }
+    }
+    
+    #[test]
+    fn test_stream_null_pointer_handling() {
+        unsafe {
+            let stream = als_stream_compress_begin(ptr::null(), ALS_FORMAT_CSV, 0, Some(collect_chunk), ptr::null_mut());
+            assert!(stream.is_null());
+            
+            let compressor = als_compressor_new();
+            assert!(als_stream_compress_begin(compressor, ALS_FORMAT_CSV, 0, None, ptr::null_mut()).is_null());
+            assert!(als_stream_compress_begin(compressor, 7, 0, Some(collect_chunk), ptr::null_mut()).is_null());
+            als_compressor_free(compressor);
+            
+            assert_eq!(als_stream_compress_feed(ptr::null_mut(), ptr::null(), 0), ALS_ERROR_NULL_POINTER);
+            assert_eq!(als_stream_compress_end(ptr::null_mut()), ALS_ERROR_NULL_POINTER);
+        }
+    }
 }
@@ -18,10 +18,14 @@ pub const DEFAULT_THRESHOLD: usize = 10_000;
 
 /// An adaptive map that selects between HashMap and DashMap based on capacity.
 ///
-/// This enum provides a unified interface over two hashmap implementations:
+/// This type provides a unified interface over two hashmap implementations:
 /// - `Small`: Uses standard `HashMap` for lower overhead with small datasets
 /// - `Large`: Uses `DashMap` for concurrent access performance with large datasets
 ///
+/// A `Small` map is promoted to `Large` once it grows to the promotion
+/// threshold. Promotion migrates every entry into the `DashMap` and happens
+/// at most once; a promoted map never switches back.
+///
 /// # Thread Safety
 ///
 /// `AdaptiveMap` implements `Send + Sync` when its key and value types do,
@@ -48,7 +52,12 @@ pub const DEFAULT_THRESHOLD: usize = 10_000;
 ///   control synchronization externally
 /// - Use `Large` (above threshold) for concurrent access from multiple threads
 ///
-/// The threshold can be configured via [`with_capacity_threshold`](Self::with_capacity_threshold).
+/// The threshold can be configured via [`with_capacity_threshold`](Self::with_capacity_threshold)
+/// or [`set_promotion_threshold`](Self::set_promotion_threshold).
+///
+/// Promotion only happens through methods taking `&mut self`, so the borrow
+/// checker guarantees a single thread performs the migration. Share a map
+/// that is still growing behind a `Mutex` or `RwLock`.
 ///
 /// # Example
 ///
@@ -88,7 +97,17 @@ pub const DEFAULT_THRESHOLD: usize = 10_000;
 /// }).collect();
 /// ```
 #[derive(Debug)]
-pub enum AdaptiveMap<K, V>
+pub struct AdaptiveMap<K, V>
+where
+    K: Eq + Hash,
+{
+    storage: Storage<K, V>,
+    promotion_threshold: usize,
+}
+
+/// Backing storage of an [`AdaptiveMap`].
+#[derive(Debug)]
+enum Storage<K, V>
 where
     K: Eq + Hash,
 {
@@ -106,7 +125,10 @@ where
     ///
     /// This creates a `Small` variant since the initial capacity is 0.
     pub fn new() -> Self {
-        Self::Small(HashMap::new())
+        Self {
+            storage: Storage::Small(HashMap::new()),
+            promotion_threshold: DEFAULT_THRESHOLD,
+        }
     }
 
     /// Create a new AdaptiveMap with the given capacity, using the default threshold.
@@ -126,45 +148,80 @@ where
     ///
     /// If `capacity < threshold`, creates a `Small` variant (HashMap).
     /// Otherwise, creates a `Large` variant (DashMap).
+    ///
+    /// The threshold is kept as the promotion threshold, so a `Small` map
+    /// migrates to DashMap once it grows to `threshold` entries.
     pub fn with_capacity_threshold(capacity: usize, threshold: usize) -> Self {
-        if capacity < threshold {
-            Self::Small(HashMap::with_capacity(capacity))
+        let storage = if capacity < threshold {
+            Storage::Small(HashMap::with_capacity(capacity))
         } else {
-            Self::Large(DashMap::with_capacity(capacity))
+            Storage::Large(DashMap::with_capacity(capacity))
+        };
+        Self {
+            storage,
+            promotion_threshold: threshold,
         }
     }
 
+    /// Returns the number of entries at which a `Small` map is promoted.
+    pub fn promotion_threshold(&self) -> usize {
+        self.promotion_threshold
+    }
+
+    /// Set the number of entries at which a `Small` map is promoted to DashMap.
+    ///
+    /// If the map already holds at least `threshold` entries, it is promoted
+    /// immediately. Lowering or raising the threshold never demotes a map
+    /// that is already using DashMap.
+    pub fn set_promotion_threshold(&mut self, threshold: usize) {
+        self.promotion_threshold = threshold;
+        self.promote_if_needed();
+    }
+
     /// Check if this map is using the Small (HashMap) variant.
     pub fn is_small(&self) -> bool {
-        matches!(self, Self::Small(_))
+        matches!(self.storage, Storage::Small(_))
     }
 
     /// Check if this map is using the Large (DashMap) variant.
     pub fn is_large(&self) -> bool {
-        matches!(self, Self::Large(_))
+        matches!(self.storage, Storage::Large(_))
+    }
+
+    /// Migrate a `Small` map to DashMap once it reaches the promotion threshold.
+    fn promote_if_needed(&mut self) {
+        if let Storage::Small(map) = &mut self.storage {
+            if map.len() >= self.promotion_threshold {
+                let large = DashMap::with_capacity(map.len());
+                for (key, value) in map.drain() {
+                    large.insert(key, value);
+                }
+                self.storage = Storage::Large(large);
+            }
+        }
     }
 
     /// Returns the number of entries in the map.
     pub fn len(&self) -> usize {
-        match self {
-            Self::Small(map) => map.len(),
-            Self::Large(map) => map.len(),
+        match &self.storage {
+            Storage::Small(map) => map.len(),
+            Storage::Large(map) => map.len(),
         }
     }
 
     /// Returns true if the map contains no entries.
     pub fn is_empty(&self) -> bool {
-        match self {
-            Self::Small(map) => map.is_empty(),
-            Self::Large(map) => map.is_empty(),
+        match &self.storage {
+            Storage::Small(map) => map.is_empty(),
+            Storage::Large(map) => map.is_empty(),
         }
     }
 
     /// Clears the map, removing all entries.
     pub fn clear(&mut self) {
-        match self {
-            Self::Small(map) => map.clear(),
-            Self::Large(map) => map.clear(),
+        match &mut self.storage {
+            Storage::Small(map) => map.clear(),
+            Storage::Large(map) => map.clear(),
         }
     }
 }
@@ -178,35 +235,37 @@ where
     ///
     /// If the map already contained this key, the old value is returned.
     pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        match self {
-            Self::Small(map) => map.insert(key, value),
-            Self::Large(map) => map.insert(key, value),
-        }
+        let previous = match &mut self.storage {
+            Storage::Small(map) => map.insert(key, value),
+            Storage::Large(map) => map.insert(key, value),
+        };
+        self.promote_if_needed();
+        previous
     }
 
     /// Get a clone of the value associated with the key.
     ///
     /// Returns `None` if the key is not present.
     pub fn get(&self, key: &K) -> Option<V> {
-        match self {
-            Self::Small(map) => map.get(key).cloned(),
-            Self::Large(map) => map.get(key).map(|v| v.value().clone()),
+        match &self.storage {
+            Storage::Small(map) => map.get(key).cloned(),
+            Storage::Large(map) => map.get(key).map(|v| v.value().clone()),
         }
     }
 
     /// Check if the map contains the given key.
     pub fn contains_key(&self, key: &K) -> bool {
-        match self {
-            Self::Small(map) => map.contains_key(key),
-            Self::Large(map) => map.contains_key(key),
+        match &self.storage {
+            Storage::Small(map) => map.contains_key(key),
+            Storage::Large(map) => map.contains_key(key),
         }
     }
 
     /// Remove a key from the map, returning the value if it was present.
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        match self {
-            Self::Small(map) => map.remove(key),
-            Self::Large(map) => map.remove(key).map(|(_, v)| v),
+        match &mut self.storage {
+            Storage::Small(map) => map.remove(key),
+            Storage::Large(map) => map.remove(key).map(|(_, v)| v),
         }
     }
 
@@ -214,9 +273,9 @@ where
     ///
     /// Note: For the Large variant, this requires iterating and cloning all keys.
     pub fn keys(&self) -> Vec<K> {
-        match self {
-            Self::Small(map) => map.keys().cloned().collect(),
-            Self::Large(map) => map.iter().map(|entry| entry.key().clone()).collect(),
+        match &self.storage {
+            Storage::Small(map) => map.keys().cloned().collect(),
+            Storage::Large(map) => map.iter().map(|entry| entry.key().clone()).collect(),
         }
     }
 
@@ -224,9 +283,9 @@ where
     ///
     /// Note: This requires cloning all values.
     pub fn values(&self) -> Vec<V> {
-        match self {
-            Self::Small(map) => map.values().cloned().collect(),
-            Self::Large(map) => map.iter().map(|entry| entry.value().clone()).collect(),
+        match &self.storage {
+            Storage::Small(map) => map.values().cloned().collect(),
+            Storage::Large(map) => map.iter().map(|entry| entry.value().clone()).collect(),
         }
     }
 
@@ -234,9 +293,9 @@ where
     ///
     /// Note: This requires cloning all entries.
     pub fn entries(&self) -> Vec<(K, V)> {
-        match self {
-            Self::Small(map) => map.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
-            Self::Large(map) => map
+        match &self.storage {
+            Storage::Small(map) => map.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            Storage::Large(map) => map
                 .iter()
                 .map(|entry| (entry.key().clone(), entry.value().clone()))
                 .collect(),
@@ -250,9 +309,9 @@ where
     where
         F: FnOnce(&mut V) -> R,
     {
-        match self {
-            Self::Small(map) => map.get_mut(key).map(f),
-            Self::Large(map) => map.get_mut(key).map(|mut entry| f(entry.value_mut())),
+        match &mut self.storage {
+            Storage::Small(map) => map.get_mut(key).map(f),
+            Storage::Large(map) => map.get_mut(key).map(|mut entry| f(entry.value_mut())),
         }
     }
 
@@ -260,10 +319,12 @@ where
     ///
     /// Returns a clone of the value (either existing or newly inserted).
     pub fn entry_or_insert(&mut self, key: K, default: V) -> V {
-        match self {
-            Self::Small(map) => map.entry(key).or_insert(default).clone(),
-            Self::Large(map) => map.entry(key).or_insert(default).clone(),
-        }
+        let value = match &mut self.storage {
+            Storage::Small(map) => map.entry(key).or_insert(default).clone(),
+            Storage::Large(map) => map.entry(key).or_insert(default).clone(),
+        };
+        self.promote_if_needed();
+        value
     }
 
     /// Insert a key-value pair if the key is not already present, using a function to create the default.
@@ -273,10 +334,12 @@ where
     where
         F: FnOnce() -> V,
     {
-        match self {
-            Self::Small(map) => map.entry(key).or_insert_with(default).clone(),
-            Self::Large(map) => map.entry(key).or_insert_with(default).clone(),
-        }
+        let value = match &mut self.storage {
+            Storage::Small(map) => map.entry(key).or_insert_with(default).clone(),
+            Storage::Large(map) => map.entry(key).or_insert_with(default).clone(),
+        };
+        self.promote_if_needed();
+        value
     }
 }
 
@@ -295,15 +358,19 @@ where
     V: Clone,
 {
     fn clone(&self) -> Self {
-        match self {
-            Self::Small(map) => Self::Small(map.clone()),
-            Self::Large(map) => {
+        let storage = match &self.storage {
+            Storage::Small(map) => Storage::Small(map.clone()),
+            Storage::Large(map) => {
                 let new_map = DashMap::with_capacity(map.len());
                 for entry in map.iter() {
                     new_map.insert(entry.key().clone(), entry.value().clone());
                 }
-                Self::Large(new_map)
+                Storage::Large(new_map)
             }
+        };
+        Self {
+            storage,
+            promotion_threshold: self.promotion_threshold,
         }
     }
 }
@@ -627,4 +694,130 @@ mod tests {
         map.clear();
         assert!(map.is_empty());
     }
+
+    #[test]
+    fn test_insert_below_threshold_stays_small() {
+        let mut map: AdaptiveMap<i32, i32> = AdaptiveMap::with_capacity_threshold(0, 10);
+        for i in 0..9 {
+            map.insert(i, i * 10);
+        }
+
+        assert!(map.is_small());
+        assert_eq!(map.len(), 9);
+    }
+
+    #[test]
+    fn test_insert_reaching_threshold_promotes() {
+        let mut map: AdaptiveMap<i32, i32> = AdaptiveMap::with_capacity_threshold(0, 10);
+        for i in 0..25 {
+            map.insert(i, i * 10);
+            assert_eq!(map.is_large(), i >= 9, "after inserting key {}", i);
+        }
+
+        assert_eq!(map.len(), 25);
+        for i in 0..25 {
+            assert_eq!(map.get(&i), Some(i * 10));
+        }
+    }
+
+    #[test]
+    fn test_overwrite_does_not_promote() {
+        let mut map: AdaptiveMap<i32, i32> = AdaptiveMap::with_capacity_threshold(0, 3);
+        map.insert(1, 1);
+        map.insert(2, 2);
+        map.insert(2, 20);
+
+        assert!(map.is_small());
+        assert_eq!(map.get(&2), Some(20));
+    }
+
+    #[test]
+    fn test_entry_or_insert_promotes() {
+        let mut map: AdaptiveMap<i32, i32> = AdaptiveMap::with_capacity_threshold(0, 2);
+        map.entry_or_insert(1, 1);
+        assert!(map.is_small());
+        map.entry_or_insert_with(2, || 2);
+
+        assert!(map.is_large());
+        assert_eq!(map.entries().len(), 2);
+    }
+
+    #[test]
+    fn test_set_promotion_threshold_promotes_existing_entries() {
+        let mut map: AdaptiveMap<String, i32> = AdaptiveMap::new();
+        assert_eq!(map.promotion_threshold(), DEFAULT_THRESHOLD);
+        for i in 0..5 {
+            map.insert(format!("key{}", i), i);
+        }
+        assert!(map.is_small());
+
+        map.set_promotion_threshold(5);
+
+        assert!(map.is_large());
+        assert_eq!(map.promotion_threshold(), 5);
+        for i in 0..5 {
+            assert_eq!(map.get(&format!("key{}", i)), Some(i));
+        }
+    }
+
+    #[test]
+    fn test_promoted_map_is_never_demoted() {
+        let mut map: AdaptiveMap<i32, i32> = AdaptiveMap::with_capacity_threshold(0, 2);
+        map.insert(1, 1);
+        map.insert(2, 2);
+        assert!(map.is_large());
+
+        map.set_promotion_threshold(100);
+        map.remove(&1);
+        map.clear();
+        map.insert(3, 3);
+
+        assert!(map.is_large());
+        assert_eq!(map.get(&3), Some(3));
+    }
+
+    #[test]
+    fn test_clone_preserves_promotion_threshold() {
+        let mut map: AdaptiveMap<i32, i32> = AdaptiveMap::with_capacity_threshold(0, 3);
+        map.insert(1, 1);
+
+        let mut cloned = map.clone();
+        assert_eq!(cloned.promotion_threshold(), 3);
+        cloned.insert(2, 2);
+        cloned.insert(3, 3);
+
+        assert!(cloned.is_large());
+        assert!(map.is_small());
+    }
+
+    #[test]
+    fn test_concurrent_inserts_promote_once() {
+        use std::sync::{Arc, Mutex};
+        use std::thread;
+
+        let map: Arc<Mutex<AdaptiveMap<i32, i32>>> =
+            Arc::new(Mutex::new(AdaptiveMap::with_capacity_threshold(0, 50)));
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let map = Arc::clone(&map);
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        let key = t * 100 + i;
+                        map.lock().unwrap().insert(key, key * 2);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let map = map.lock().unwrap();
+        assert!(map.is_large());
+        assert_eq!(map.len(), 400);
+        for key in 0..400 {
+            assert_eq!(map.get(&key), Some(key * 2));
+        }
+    }
 }
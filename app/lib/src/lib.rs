@@ -10,7 +10,7 @@
 //!
 //! - **Pattern-based compression**: Detects and encodes sequential ranges, repetitions,
 //!   and alternating patterns
-//! - **Multiple formats**: Supports CSV and JSON input/output
+//! - **Multiple formats**: Supports CSV, TSV, and JSON input/output
 //! - **Zero-copy parsing**: Minimizes memory allocations using borrowed references
 //! - **SIMD acceleration**: Uses AVX2, AVX-512, or NEON instructions when available
 //! - **Parallel processing**: Leverages multiple CPU cores for large datasets
@@ -75,7 +75,7 @@
 //!
 //! - **Compression types**: [`AlsCompressor`], [`CompressionStats`], [`StatsSnapshot`]
 //! - **Document types**: [`AlsDocument`], [`AlsOperator`], [`ColumnStream`]
-//! - **Configuration types**: [`CompressorConfig`], [`ParserConfig`], [`SimdConfig`]
+//! - **Configuration types**: [`CompressorConfig`], [`ParserConfig`], [`SimdConfig`], [`CsvConfig`]
 //! - **Data types**: [`TabularData`], [`Column`], [`Value`]
 //!
 //! ### Concurrent Compression
@@ -174,7 +174,7 @@
 //! let reader = BufReader::new(file);
 //! let mut compressor = StreamingCompressor::new(reader);
 //!
-//! for chunk in compressor.compress_chunks() {
+//! for chunk in compressor.compress_csv_chunks() {
 //!     let als_chunk = chunk?;
 //!     // Process chunk...
 //! }
@@ -271,6 +271,10 @@ pub mod pattern;
 pub mod simd;
 pub mod streaming;
 
+// Round-trip test harness (tests only)
+#[cfg(test)]
+pub(crate) mod testutil;
+
 // Python bindings (optional)
 #[cfg(feature = "python")]
 pub mod python;
@@ -282,24 +286,28 @@ pub mod ffi;
 // Re-exports for convenience
 pub use als::{
     decode_als_value, encode_als_value, escape_als_string, is_empty_token, is_null_token,
-    needs_escaping, unescape_als_string, AlsDocument, AlsOperator, AlsParser, AlsPrettyPrinter,
-    AlsSerializer, ColumnStream, FormatIndicator, Token, Tokenizer, VersionType, EMPTY_TOKEN,
-    NULL_TOKEN,
+    needs_escaping, unescape_als_string, AlsDocument, AlsDocumentBuilder, AlsOperator, AlsParser,
+    AlsPrettyPrinter, AlsSerializer, Codec, ColumnEncoding, ColumnStream, FormatIndicator, NumberType,
+    SentinelTokens, SpannedToken, TimestampFormat, Token, Tokenizer, Tokens, VersionType,
+    COMMENT_MARKER, EMPTY_TOKEN, NULL_TOKEN,
+};
+pub use config::{
+    CompressorConfig, CsvConfig, JsonColumnOrder, ParserConfig, RaggedRowPolicy, SimdConfig,
 };
-pub use config::{CompressorConfig, ParserConfig, SimdConfig};
 pub use convert::{Column, ColumnType, TabularData, Value, parse_syslog, to_syslog, MessageType, SyslogEntry, parse_syslog_optimized};
-pub use error::{AlsError, Result};
+pub use error::{AlsError, AlsSyntaxError, Result};
 pub use pattern::{
-    CombinedDetector, DetectionResult, PatternDetector, PatternEngine, PatternType,
-    RangeDetector, RepeatDetector, RunDetector, ToggleDetector,
+    AffixDetector, CombinedDetector, DeltaDetector, DetectionResult, GeometricDetector, IpRangeDetector,
+    PatternDetector, PatternEngine, PatternType, RangeDetector, RepeatDetector, RunDetector,
+    TimestampDetector, ToggleDetector,
 };
 pub use compress::{
-    AlsCompressor, ColumnStats, CompressionReport, CompressionStats, DictionaryBuilder,
-    DictionaryEntry, EnumDetector, StatsSnapshot,
+    AlsCompressor, ColumnStats, CompressionBuffers, CompressionReport, CompressionStats, DictionaryBuilder,
+    DictionaryEntry, EnumDetector, IntDictionaryEncoder, StatsSnapshot, ValueStats,
 };
 pub use hashmap::AdaptiveMap;
 pub use simd::{CpuFeatures, SimdDispatcher, SimdLevel};
-pub use streaming::{StreamingCompressor, StreamingParser};
+pub use streaming::{ParserCheckpoint, StreamingCompressor, StreamingParser};
 
 /// Thread safety verification module.
 ///
@@ -332,8 +340,8 @@ pub use streaming::{StreamingCompressor, StreamingParser};
 ///
 /// ## Configuration Types
 ///
-/// - [`CompressorConfig`], [`ParserConfig`], [`SimdConfig`]: Immutable configuration
-///   types that can be safely shared.
+/// - [`CompressorConfig`], [`ParserConfig`], [`SimdConfig`], [`CsvConfig`]: Immutable
+///   configuration types that can be safely shared.
 ///
 /// ## Concurrent Data Structures
 ///
@@ -381,6 +389,7 @@ mod thread_safety {
         assert_send_sync::<DictionaryBuilder>();
         assert_send_sync::<DictionaryEntry>();
         assert_send_sync::<EnumDetector>();
+        assert_send_sync::<IntDictionaryEncoder>();
     }
 
     /// Verify all public ALS document types are thread-safe.
@@ -389,6 +398,8 @@ mod thread_safety {
         assert_send_sync::<AlsDocument>();
         assert_send_sync::<AlsOperator>();
         assert_send_sync::<ColumnStream>();
+        assert_send_sync::<ColumnEncoding>();
+        assert_send_sync::<NumberType>();
         assert_send_sync::<FormatIndicator>();
         assert_send_sync::<AlsParser>();
         assert_send_sync::<AlsSerializer>();
@@ -404,6 +415,7 @@ mod thread_safety {
         assert_send_sync::<CompressorConfig>();
         assert_send_sync::<ParserConfig>();
         assert_send_sync::<SimdConfig>();
+        assert_send_sync::<CsvConfig>();
     }
 
     /// Verify all public data types are thread-safe.
@@ -426,6 +438,10 @@ mod thread_safety {
         assert_send_sync::<ToggleDetector>();
         assert_send_sync::<CombinedDetector>();
         assert_send_sync::<RunDetector>();
+        assert_send_sync::<DeltaDetector>();
+        assert_send_sync::<GeometricDetector>();
+        assert_send_sync::<TimestampDetector>();
+        assert_send_sync::<IpRangeDetector>();
     }
 
     /// Verify all public SIMD types are thread-safe.
@@ -448,6 +464,7 @@ mod thread_safety {
         use std::io::Cursor;
         assert_send_sync::<StreamingCompressor<Cursor<Vec<u8>>>>();
         assert_send_sync::<StreamingParser<Cursor<Vec<u8>>>>();
+        assert_send_sync::<ParserCheckpoint>();
     }
 
     /// Verify error types are thread-safe.
@@ -0,0 +1,315 @@
+//! Common prefix and suffix detection.
+//!
+//! This module detects string columns whose values all start or end with the
+//! same text, such as URLs or file names, and stores that text once around
+//! the varying middles (e.g., `{https\://example.com/}(a,b,c)`).
+
+use super::detector::{DetectionResult, PatternDetector};
+use super::range::RangeDetector;
+use crate::als::{escape_als_string, AlsOperator, Token, Tokenizer};
+
+/// Detector for columns sharing a prefix and/or suffix.
+///
+/// Detects values with common affixes:
+/// - `https://example.com/a`, `https://example.com/b` → `{https\://example.com/}(a,b)`
+/// - `report.csv`, `summary.csv` → `{:.csv}(report,summary)`
+/// - `img1.png`, `img2.png`, `img3.png` → `{img:.png}(1>3)`
+///
+/// Every value keeps a non-empty middle, so a value equal to the whole
+/// affix is never produced. The result is only returned when the affixes
+/// are long enough for the encoding to be smaller than the raw values.
+#[derive(Debug, Clone)]
+pub struct AffixDetector {
+    min_pattern_length: usize,
+    range_detector: RangeDetector,
+}
+
+impl AffixDetector {
+    /// Create a new affix detector with the given minimum pattern length.
+    pub fn new(min_pattern_length: usize) -> Self {
+        Self {
+            min_pattern_length,
+            range_detector: RangeDetector::new(min_pattern_length),
+        }
+    }
+
+    /// Length in bytes of the prefix shared by all values, cut back to a
+    /// character boundary and to at most `limit` bytes.
+    fn common_prefix_len(values: &[&str], limit: usize) -> usize {
+        let first = values[0].as_bytes();
+        let mut len = limit.min(first.len());
+        for value in &values[1..] {
+            len = first[..len]
+                .iter()
+                .zip(value.as_bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len == 0 {
+                return 0;
+            }
+        }
+        while !values[0].is_char_boundary(len) {
+            len -= 1;
+        }
+        len
+    }
+
+    /// Length in bytes of the suffix shared by all values, cut back to a
+    /// character boundary and to at most `limit` bytes.
+    fn common_suffix_len(values: &[&str], limit: usize) -> usize {
+        let first = values[0].as_bytes();
+        let mut len = limit.min(first.len());
+        for value in &values[1..] {
+            len = first[first.len() - len..]
+                .iter()
+                .rev()
+                .zip(value.as_bytes().iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            if len == 0 {
+                return 0;
+            }
+        }
+        while !values[0].is_char_boundary(values[0].len() - len) {
+            len -= 1;
+        }
+        len
+    }
+
+    /// Encode the middles as a range if they form one, or as raw values.
+    ///
+    /// The range must expand to the middles exactly, so middles with leading
+    /// zeros (e.g. `03`, `05`) are not a range. Returns `None` if a raw
+    /// middle would not read back as written (see
+    /// [`Self::middle_round_trips`]).
+    fn encode_middles(&self, middles: &[&str]) -> Option<Vec<AlsOperator>> {
+        let range = self.range_detector.detect(middles).filter(|range| {
+            range.operator.expand(None).is_ok_and(|expanded| expanded == middles)
+        });
+        match range {
+            Some(range) => Some(vec![range.operator]),
+            None => middles
+                .iter()
+                .map(|&middle| Self::middle_round_trips(middle).then(|| AlsOperator::raw(middle)))
+                .collect(),
+        }
+    }
+
+    /// Check that a raw middle reads back from ALS text as the same text.
+    ///
+    /// Middles are written as plain elements, so one that looks like a
+    /// number not in its shortest form (e.g. `0001`, `-0`) would read back
+    /// as that number and lose its spelling.
+    fn middle_round_trips(middle: &str) -> bool {
+        let escaped = escape_als_string(middle);
+        let mut tokenizer = Tokenizer::new(&escaped);
+        let text = match tokenizer.next_token() {
+            Ok(Token::Integer(n)) => n.to_string(),
+            Ok(Token::Float(f)) => f.to_string(),
+            Ok(Token::Decimal(_, text) | Token::RawValue(text)) => text,
+            _ => return false,
+        };
+        text == middle && matches!(tokenizer.next_token(), Ok(Token::Eof))
+    }
+
+    /// Calculate the original string length of the values.
+    fn calculate_original_length(values: &[&str]) -> usize {
+        // Sum of all value lengths plus separators (spaces)
+        let value_len: usize = values.iter().map(|v| v.len()).sum();
+        let separator_len = values.len().saturating_sub(1);
+        value_len + separator_len
+    }
+}
+
+impl PatternDetector for AffixDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+
+        // Leave at least one byte of every value for its middle
+        let shortest = values.iter().map(|v| v.len()).min()?;
+        let prefix_len = Self::common_prefix_len(values, shortest.saturating_sub(1));
+        let rests: Vec<&str> = values.iter().map(|v| &v[prefix_len..]).collect();
+        let suffix_len = Self::common_suffix_len(&rests, shortest.saturating_sub(prefix_len + 1));
+        if prefix_len + suffix_len == 0 {
+            return None;
+        }
+
+        let middles: Vec<&str> = rests.iter().map(|rest| &rest[..rest.len() - suffix_len]).collect();
+        let prefix = &values[0][..prefix_len];
+        let suffix = &rests[0][rests[0].len() - suffix_len..];
+
+        let original_len = Self::calculate_original_length(values);
+        let result = DetectionResult::affix(prefix, suffix, self.encode_middles(&middles)?, original_len);
+
+        // Only return if the shared affixes outweigh the encoding overhead
+        if result.compression_ratio > 1.0 {
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::PatternType;
+
+    fn expand(result: &DetectionResult) -> Vec<String> {
+        result.operator.expand(None).unwrap()
+    }
+
+    #[test]
+    fn test_common_prefix_urls() {
+        let detector = AffixDetector::new(3);
+        let values: Vec<&str> = vec![
+            "https://example.com/products/shoes",
+            "https://example.com/products/hats",
+            "https://example.com/products/socks",
+            "https://example.com/products/belt",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Affix);
+        assert_eq!(
+            result.operator,
+            AlsOperator::affix(
+                "https://example.com/products/",
+                "",
+                vec![
+                    AlsOperator::raw("shoes"),
+                    AlsOperator::raw("hats"),
+                    AlsOperator::raw("socks"),
+                    AlsOperator::raw("belt"),
+                ],
+            )
+        );
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_common_suffix_filenames() {
+        let detector = AffixDetector::new(3);
+        let values: Vec<&str> = vec![
+            "quarterly_report.backup.tar.gz",
+            "annual_summary.backup.tar.gz",
+            "ledger.backup.tar.gz",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Affix);
+        match &result.operator {
+            AlsOperator::Affix { prefix, suffix, .. } => {
+                assert_eq!(prefix, "");
+                assert_eq!(suffix, ".backup.tar.gz");
+            }
+            other => panic!("expected affix operator, got {:?}", other),
+        }
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_prefix_and_suffix_around_range() {
+        let detector = AffixDetector::new(3);
+        let values: Vec<String> = (1..=20).map(|i| format!("/images/photo_{}.jpeg", i)).collect();
+        let values: Vec<&str> = values.iter().map(String::as_str).collect();
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(
+            result.operator,
+            AlsOperator::affix("/images/photo_", ".jpeg", vec![AlsOperator::range(1, 20)])
+        );
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_no_shared_affix() {
+        let detector = AffixDetector::new(3);
+        let values: Vec<&str> = vec!["apple", "banana", "cherry", "date"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_short_affix_not_worth_it() {
+        let detector = AffixDetector::new(3);
+        // A one-byte prefix does not pay for the braces
+        let values: Vec<&str> = vec!["xa", "xb", "xc"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_middles_never_empty() {
+        let detector = AffixDetector::new(3);
+        let values: Vec<&str> = vec![
+            "https://example.com/",
+            "https://example.com/a",
+            "https://example.com/ab",
+            "https://example.com/abc",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        match &result.operator {
+            AlsOperator::Affix { prefix, .. } => assert_eq!(prefix, "https://example.com"),
+            other => panic!("expected affix operator, got {:?}", other),
+        }
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_zero_padded_middles_stay_raw() {
+        let detector = AffixDetector::new(3);
+        let values: Vec<String> = (3..=43).step_by(2).map(|i| format!("16365389{:02}", i)).collect();
+        let values: Vec<&str> = values.iter().map(String::as_str).collect();
+        if let Some(result) = detector.detect(&values) {
+            assert_eq!(round_trip(&result), values);
+        }
+    }
+
+    /// Serialize the operator, parse it back, and expand it.
+    fn round_trip(result: &DetectionResult) -> Vec<String> {
+        use crate::als::{AlsDocument, AlsParser, AlsSerializer, ColumnStream};
+
+        let mut doc = AlsDocument::with_schema(vec!["col"]);
+        doc.add_stream(ColumnStream::from_operators(vec![result.operator.clone()]));
+        let text = AlsSerializer::new().serialize(&doc);
+        let parsed = AlsParser::new().parse(&text).unwrap();
+        parsed.streams[0].expand(None).unwrap()
+    }
+
+    #[test]
+    fn test_zero_padded_sku_round_trips() {
+        let detector = AffixDetector::new(3);
+        let skus = [
+            "SKU-0001", "SKU-0502", "SKU-0093", "SKU-0065", "SKU-0830", "SKU-0007", "SKU-0412", "SKU-0099",
+        ];
+        if let Some(result) = detector.detect(&skus) {
+            assert_eq!(round_trip(&result), skus);
+        }
+        for values in [
+            vec!["21805", "21806", "21809", "21804"],
+            vec!["877.06", "877.09", "877.01", "877.05"],
+            vec!["id-0", "id--0", "id-00", "id-7"],
+        ] {
+            if let Some(result) = detector.detect(&values) {
+                assert_eq!(round_trip(&result), values, "{values:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_multibyte_boundary() {
+        let detector = AffixDetector::new(3);
+        // The values share the first byte of 'é' and 'è' but not the character
+        let values: Vec<&str> = vec!["commentaire_é", "commentaire_è", "commentaire_ê"];
+        let result = detector.detect(&values).unwrap();
+
+        match &result.operator {
+            AlsOperator::Affix { prefix, .. } => assert_eq!(prefix, "commentaire_"),
+            other => panic!("expected affix operator, got {:?}", other),
+        }
+        assert_eq!(expand(&result), values);
+    }
+}
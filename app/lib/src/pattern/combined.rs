@@ -1,17 +1,20 @@
 //! Combined pattern detection.
 //!
-//! This module detects repeated patterns such as repeated ranges (e.g., `(1>3)*2`)
-//! and repeated alternating patterns.
+//! This module detects repeated patterns such as repeated ranges (e.g., `(1>3)*2`),
+//! repeated alternating patterns, and ranges interleaved with repeats
+//! (e.g., `(1>3&A*3)`).
 
 use super::detector::{DetectionResult, PatternDetector, PatternType};
 use super::range::RangeDetector;
 use super::toggle::ToggleDetector;
+use crate::als::AlsOperator;
 
 /// Detector for combined/repeated patterns.
 ///
 /// Detects patterns like:
 /// - Repeated ranges: 1, 2, 3, 1, 2, 3 → `(1>3)*2`
 /// - Repeated alternating patterns: A, B, A, B, A, B, A, B → `(A~B)*4` or `A~B*8`
+/// - Interleaved ranges and repeats: 1, A, 2, A, 3, A → `(1>3&A*3)`
 #[derive(Debug, Clone)]
 pub struct CombinedDetector {
     min_pattern_length: usize,
@@ -20,6 +23,12 @@ pub struct CombinedDetector {
 }
 
 impl CombinedDetector {
+    /// Largest stride tried when looking for interleaved lanes.
+    const MAX_INTERLEAVE_LANES: usize = 4;
+
+    /// Minimum number of values each interleaved lane must hold.
+    const MIN_INTERLEAVE_LANE_LEN: usize = 3;
+
     /// Create a new combined detector with the given minimum pattern length.
     pub fn new(min_pattern_length: usize) -> Self {
         Self {
@@ -196,7 +205,7 @@ impl CombinedDetector {
         // Try to parse first two values as integers to detect arithmetic sequences
         let first: i64 = values[0].trim().parse().ok()?;
         let second: i64 = values[1].trim().parse().ok()?;
-        let step = second - first;
+        let step = second.checked_sub(first)?;
 
         if step == 0 {
             return None; // All same values, not a range pattern
@@ -205,7 +214,8 @@ impl CombinedDetector {
         // Find where the sequence breaks (value doesn't follow the expected pattern)
         for i in 2..values.len() {
             let current: i64 = values[i].trim().parse().ok()?;
-            let expected = first + (i as i64) * step;
+            // A sequence that would overflow cannot be a range
+            let expected = (i as i64).checked_mul(step).and_then(|offset| first.checked_add(offset))?;
 
             if current != expected {
                 // Found a break - this is the pattern length
@@ -264,6 +274,12 @@ impl CombinedDetector {
             // Check if the pattern itself is a toggle
             if let Some(toggle_result) = self.toggle_detector.detect(pattern) {
                 if let crate::als::AlsOperator::Toggle { values: toggle_values, count: _ } = toggle_result.operator {
+                    // A unit made of whole cycles means the column is a plain
+                    // toggle, which the toggle detector encodes directly
+                    if pattern_len % toggle_values.len() == 0 {
+                        continue;
+                    }
+
                     // Create a repeated toggle result
                     let inner = crate::als::AlsOperator::Toggle {
                         values: toggle_values,
@@ -291,6 +307,58 @@ impl CombinedDetector {
         None
     }
 
+    /// Try to detect a range interleaved with repeats.
+    ///
+    /// Looks for patterns like 1, A, 2, A, 3, A where every `n`-th value
+    /// (for a small stride `n`) forms its own lane, each lane being an integer
+    /// range or a single repeated value. The structure must hold across the
+    /// whole column, and at least one lane must be a range.
+    fn detect_interleaved(&self, values: &[&str]) -> Option<DetectionResult> {
+        for lane_count in 2..=Self::MAX_INTERLEAVE_LANES {
+            // Require a few values per lane so short columns don't misfire
+            if values.len() < lane_count * Self::MIN_INTERLEAVE_LANE_LEN {
+                break;
+            }
+
+            let lanes: Option<Vec<AlsOperator>> = (0..lane_count)
+                .map(|lane| {
+                    let lane_values: Vec<&str> =
+                        values.iter().skip(lane).step_by(lane_count).copied().collect();
+                    Self::interleave_lane(&lane_values)
+                })
+                .collect();
+
+            if let Some(lanes) = lanes {
+                if lanes.iter().any(AlsOperator::is_range) {
+                    let original_len = Self::calculate_original_length(values);
+                    return Some(DetectionResult::interleave(lanes, original_len));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Encode one interleave lane as an integer range or a repeated value.
+    fn interleave_lane(values: &[&str]) -> Option<AlsOperator> {
+        let first = values[0];
+        if values.iter().all(|&v| v == first) {
+            return Some(AlsOperator::multiply(AlsOperator::raw(first), values.len()));
+        }
+
+        // Integers must round-trip exactly so values like "007" stay raw
+        let integers: Vec<i64> = values
+            .iter()
+            .map(|v| v.parse::<i64>().ok().filter(|n| n.to_string() == *v))
+            .collect::<Option<_>>()?;
+        let step = integers[1].checked_sub(integers[0])?;
+        if step == 0 || integers.windows(2).any(|w| w[1].checked_sub(w[0]) != Some(step)) {
+            return None;
+        }
+
+        Some(AlsOperator::range_with_step(integers[0], integers[integers.len() - 1], step))
+    }
+
     /// Calculate the original string length of the values.
     fn calculate_original_length(values: &[&str]) -> usize {
         let value_len: usize = values.iter().map(|v| v.len()).sum();
@@ -325,6 +393,15 @@ impl PatternDetector for CombinedDetector {
             }
         }
 
+        // Try interleaved range/repeat detection
+        if let Some(result) = self.detect_interleaved(values) {
+            if result.compression_ratio > 1.0
+                && best_result.as_ref().is_none_or(|r| result.compression_ratio > r.compression_ratio)
+            {
+                best_result = Some(result);
+            }
+        }
+
         best_result
     }
 }
@@ -333,6 +410,13 @@ impl PatternDetector for CombinedDetector {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_overflowing_sequence_not_detected() {
+        let detector = CombinedDetector::new(3);
+        let values = vec!["-9223372036854775808", "9223372036854775807", "0", "1"];
+        assert!(detector.detect(&values).is_none());
+    }
+
     #[test]
     fn test_repeated_range() {
         let detector = CombinedDetector::new(3);
@@ -475,4 +559,52 @@ mod tests {
             panic!("Expected Multiply operator");
         }
     }
+
+    #[test]
+    fn test_interleaved_range_and_constant() {
+        let detector = CombinedDetector::new(3);
+        let values: Vec<String> = (1..=20).flat_map(|i| [i.to_string(), "A".to_string()]).collect();
+        let values: Vec<&str> = values.iter().map(String::as_str).collect();
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Interleave);
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(
+            result.operator,
+            AlsOperator::interleave(vec![
+                AlsOperator::range(1, 20),
+                AlsOperator::multiply(AlsOperator::raw("A"), 20),
+            ])
+        );
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_interleaved_odd_length() {
+        let detector = CombinedDetector::new(3);
+        let values: Vec<&str> = vec!["10", "x", "20", "x", "30", "x", "40", "x", "50", "x", "60"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Interleave);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_interleaved_ignores_random_data() {
+        let detector = CombinedDetector::new(3);
+        // Deterministic pseudo-random values from a linear congruential generator
+        let mut state: u64 = 42;
+        let values: Vec<String> = (0..40)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                ((state >> 33) % 1000).to_string()
+            })
+            .collect();
+        let values: Vec<&str> = values.iter().map(String::as_str).collect();
+
+        assert!(detector.detect_interleaved(&values).is_none());
+        if let Some(result) = detector.detect(&values) {
+            assert_ne!(result.pattern_type, PatternType::Interleave);
+        }
+    }
 }
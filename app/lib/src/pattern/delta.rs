@@ -0,0 +1,171 @@
+//! Delta pattern detection.
+//!
+//! This module detects nearly-arithmetic integer columns that can be encoded
+//! as a base value followed by consecutive differences (e.g., `100^d1,1,5,1`).
+
+use super::detector::{parse_canonical_integers, DetectionResult, PatternDetector};
+
+/// Detector for delta-encodable integer sequences.
+///
+/// Detects integer columns whose consecutive differences serialize shorter
+/// than the raw values, such as counters or timestamps that mostly advance
+/// by a fixed amount but occasionally jump:
+/// - `1000, 1001, 1002, 1010, 1011` → `1000^d1,1,8,1`
+/// - `500, 500, 500, 505, 505` → `500^d0,0,5,0`
+///
+/// Values are only accepted if they are in canonical integer form (no
+/// leading zeros, `+` signs or whitespace), so that expansion reproduces
+/// the original strings exactly.
+#[derive(Debug, Clone)]
+pub struct DeltaDetector {
+    min_pattern_length: usize,
+}
+
+impl DeltaDetector {
+    /// Create a new delta detector with the given minimum pattern length.
+    pub fn new(min_pattern_length: usize) -> Self {
+        Self { min_pattern_length }
+    }
+
+    /// Compute the consecutive differences between values.
+    ///
+    /// Returns `None` if any difference overflows `i64`.
+    fn compute_deltas(values: &[i64]) -> Option<Vec<i64>> {
+        values
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect()
+    }
+
+    /// Calculate the original string length of the values.
+    fn calculate_original_length(values: &[&str]) -> usize {
+        // Sum of all value lengths plus separators (spaces)
+        let value_len: usize = values.iter().map(|v| v.len()).sum();
+        let separator_len = values.len().saturating_sub(1);
+        value_len + separator_len
+    }
+
+    /// Detect the pattern in values already parsed by `parse_canonical_integers`.
+    ///
+    /// Lets the pattern engine share one parse between integer detectors.
+    pub(crate) fn detect_integers(&self, values: &[&str], integers: &[i64]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+
+        let deltas = Self::compute_deltas(integers)?;
+
+        // Calculate compression benefit
+        let original_len = Self::calculate_original_length(values);
+        let result = DetectionResult::delta(integers[0], deltas, original_len);
+
+        // Only return if the delta stream is smaller than raw
+        if result.compression_ratio > 1.0 {
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+impl PatternDetector for DeltaDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+        self.detect_integers(values, &parse_canonical_integers(values)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::als::AlsOperator;
+    use crate::pattern::PatternType;
+
+    fn expand(result: &DetectionResult) -> Vec<String> {
+        result.operator.expand(None).unwrap()
+    }
+
+    #[test]
+    fn test_nearly_sequential_timestamps() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec![
+            "1700000000", "1700000001", "1700000002", "1700000010", "1700000011",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Delta);
+        assert_eq!(
+            result.operator,
+            AlsOperator::delta(1700000000, vec![1, 1, 8, 1])
+        );
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_single_jump_in_flat_column() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec!["50000", "50000", "50000", "50005", "50005", "50005"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(
+            result.operator,
+            AlsOperator::delta(50000, vec![0, 0, 5, 0, 0])
+        );
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_negative_deltas() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec!["90000", "89999", "89990", "89991", "89985"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(
+            result.operator,
+            AlsOperator::delta(90000, vec![-1, -9, 1, -6])
+        );
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_no_pattern_when_deltas_are_larger() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec!["1", "900", "2", "800"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_non_integers() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec!["1000", "1001", "abc", "1003"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_non_canonical_integers() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec!["01000", "01001", "01002", "01010"];
+        assert!(detector.detect(&values).is_none());
+
+        let values: Vec<&str> = vec!["+1000", "1001", "1002", "1010"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_too_short() {
+        let detector = DeltaDetector::new(3);
+        let values: Vec<&str> = vec!["1000", "1001"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_delta_overflow_rejected() {
+        let detector = DeltaDetector::new(3);
+        let max = i64::MAX.to_string();
+        let min = i64::MIN.to_string();
+        let values: Vec<&str> = vec![&min, &max, &min];
+        assert!(detector.detect(&values).is_none());
+    }
+}
@@ -3,7 +3,7 @@
 //! This module defines the `PatternDetector` trait and associated types
 //! used by all pattern detection implementations.
 
-use crate::als::AlsOperator;
+use crate::als::{AlsOperator, AlsSerializer, TimestampFormat};
 
 /// Trait for pattern detection algorithms.
 ///
@@ -17,6 +17,21 @@ pub trait PatternDetector: Send + Sync {
     fn detect(&self, values: &[&str]) -> Option<DetectionResult>;
 }
 
+/// Parse every value as an integer in canonical form.
+///
+/// Rejects leading zeros, `+` signs and whitespace, so that expanding an
+/// integer pattern reproduces the original strings exactly. Returns `None`
+/// if any value is not a canonical integer.
+pub(crate) fn parse_canonical_integers(values: &[&str]) -> Option<Vec<i64>> {
+    values
+        .iter()
+        .map(|s| {
+            let n = s.parse::<i64>().ok()?;
+            (n.to_string() == *s).then_some(n)
+        })
+        .collect()
+}
+
 /// Result of pattern detection.
 ///
 /// Contains the detected operator, compression ratio, and pattern type.
@@ -99,6 +114,89 @@ impl DetectionResult {
         }
     }
 
+    /// Create a sparse range detection result.
+    pub fn sparse_range(start: i64, end: i64, step: i64, excluded: Vec<i64>, original_len: usize) -> Self {
+        // Estimate compressed size: start>end!e1,e2,...
+        let excluded_len: usize = excluded.iter().map(|&value| Self::digit_count_i64(value)).sum();
+        let separators = excluded.len().saturating_sub(1); // , between exclusions
+        let compressed_len =
+            Self::estimate_range_length(start, end, step) + (1 + excluded_len + separators) as f64;
+
+        let operator = AlsOperator::SparseRange { start, end, step, excluded };
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::SparseRange,
+        }
+    }
+
+    /// Create a patched range detection result.
+    pub fn patched_range(
+        start: i64,
+        end: i64,
+        step: i64,
+        overrides: Vec<(usize, i64)>,
+        original_len: usize,
+    ) -> Self {
+        // Estimate compressed size: start>end!=p1:v1,p2:v2,...
+        let overrides_len: usize = overrides
+            .iter()
+            .map(|&(position, value)| Self::digit_count(position) + 1 + Self::digit_count_i64(value))
+            .sum();
+        let separators = overrides.len().saturating_sub(1); // , between overrides
+        let compressed_len =
+            Self::estimate_range_length(start, end, step) + (2 + overrides_len + separators) as f64;
+
+        let operator = AlsOperator::PatchedRange { start, end, step, overrides };
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::PatchedRange,
+        }
+    }
+
+    /// Create a float range detection result.
+    pub fn float_range(start: f64, end: f64, step: f64, original_len: usize) -> Self {
+        let operator = AlsOperator::float_range(start, end, step);
+        let precision = match operator {
+            AlsOperator::FloatRange { precision, .. } => precision as usize,
+            _ => 0,
+        };
+
+        // Estimate compressed size: start>end:step at fixed precision
+        let compressed_len = (format!("{:.*}", precision, start).len()
+            + 1
+            + format!("{:.*}", precision, end).len()
+            + 1
+            + format!("{:.*}", precision, step).len()) as f64;
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::Arithmetic,
+        }
+    }
+
     /// Create a repeat detection result.
     pub fn repeat(value: &str, count: usize, _original_len: usize) -> Self {
         let operator = AlsOperator::Multiply {
@@ -149,6 +247,31 @@ impl DetectionResult {
         }
     }
 
+    /// Create a run list detection result.
+    pub fn run_list(runs: Vec<(String, usize)>, original_len: usize) -> Self {
+        // Estimate compressed size: (val1*n1,val2*n2,...)
+        let runs_len: usize = runs
+            .iter()
+            .map(|(value, count)| value.len() + 1 + Self::digit_count(*count))
+            .sum();
+        let separators = runs.len().saturating_sub(1); // , between runs
+        let compressed_len = (runs_len + separators + 2) as f64;
+
+        let operator = AlsOperator::RunList { runs };
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::RunList,
+        }
+    }
+
     /// Create a repeated range detection result.
     pub fn repeated_range(start: i64, end: i64, step: i64, repeat_count: usize, original_len: usize) -> Self {
         let inner = AlsOperator::Range { start, end, step };
@@ -174,6 +297,144 @@ impl DetectionResult {
         }
     }
 
+    /// Create an interleave detection result from its lanes.
+    pub fn interleave(lanes: Vec<AlsOperator>, original_len: usize) -> Self {
+        let operator = AlsOperator::interleave(lanes);
+
+        // Measure the exact serialized form: (lane&lane...)
+        let mut serialized = String::new();
+        AlsSerializer::new().serialize_operator(&mut serialized, &operator);
+        let compressed_len = serialized.len() as f64;
+
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::Interleave,
+        }
+    }
+
+    /// Create a delta detection result.
+    pub fn delta(base: i64, deltas: Vec<i64>, original_len: usize) -> Self {
+        // Estimate compressed size: base^dd1,d2,...
+        let deltas_len: usize = deltas.iter().map(|&d| Self::digit_count_i64(d)).sum();
+        let separators = deltas.len().saturating_sub(1); // , between deltas
+        let compressed_len = (Self::digit_count_i64(base) + 2 + deltas_len + separators) as f64;
+
+        let operator = AlsOperator::Delta { base, deltas };
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::Delta,
+        }
+    }
+
+    /// Create a geometric detection result.
+    pub fn geometric(start: i64, ratio: i64, count: usize, original_len: usize) -> Self {
+        // Estimate compressed size: start^*ratio#count
+        let compressed_len = (Self::digit_count_i64(start)
+            + 2
+            + Self::digit_count_i64(ratio)
+            + 1
+            + Self::digit_count(count)) as f64;
+
+        let operator = AlsOperator::Geometric { start, ratio, count };
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::Geometric,
+        }
+    }
+
+    /// Create a timestamp detection result wrapping an epoch operator.
+    pub fn timestamp(format: TimestampFormat, value: AlsOperator, original_len: usize) -> Self {
+        let operator = AlsOperator::timestamp(format, value);
+
+        // Measure the exact serialized form: @tag(inner)
+        let mut serialized = String::new();
+        AlsSerializer::new().serialize_operator(&mut serialized, &operator);
+        let compressed_len = serialized.len() as f64;
+
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::Timestamp,
+        }
+    }
+
+    /// Create an IPv4 range detection result wrapping an address range.
+    pub fn ipv4_range(value: AlsOperator, original_len: usize) -> Self {
+        let operator = AlsOperator::ipv4(value);
+
+        // Measure the exact serialized form: @ip4(inner)
+        let mut serialized = String::new();
+        AlsSerializer::new().serialize_operator(&mut serialized, &operator);
+        let compressed_len = serialized.len() as f64;
+
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::IpRange,
+        }
+    }
+
+    /// Create an affix detection result around the encoded middles.
+    pub fn affix(prefix: &str, suffix: &str, values: Vec<AlsOperator>, original_len: usize) -> Self {
+        let operator = AlsOperator::affix(prefix, suffix, values);
+
+        // Measure the exact serialized form: {prefix:suffix}(middles)
+        let mut serialized = String::new();
+        AlsSerializer::new().serialize_operator(&mut serialized, &operator);
+        let compressed_len = serialized.len() as f64;
+
+        let original_size = original_len as f64;
+        let compression_ratio = if compressed_len > 0.0 {
+            original_size / compressed_len
+        } else {
+            1.0
+        };
+
+        Self {
+            operator,
+            compression_ratio,
+            pattern_type: PatternType::Affix,
+        }
+    }
+
     /// Estimate the string length of a range operator.
     fn estimate_range_length(start: i64, end: i64, step: i64) -> f64 {
         let start_len = Self::digit_count_i64(start);
@@ -217,29 +478,119 @@ pub enum PatternType {
     Sequential,
     /// Arithmetic sequence with custom step (e.g., 10>50:10).
     Arithmetic,
+    /// Range with a few values left out (e.g., 1>7!4).
+    SparseRange,
+    /// Range with a few values replaced (e.g., 1>6!=2:90).
+    PatchedRange,
     /// Repeated identical values (e.g., val*n).
     Repeat,
     /// Alternating values (e.g., T~F*n).
     Toggle,
+    /// Consecutive runs of repeated values (e.g., (A*3,B*2)).
+    RunList,
     /// Repeated range pattern (e.g., (1>3)*2).
     RepeatedRange,
     /// Repeated toggle pattern (e.g., (A~B)*2).
     RepeatedToggle,
+    /// Ranges and repeats merged round-robin (e.g., (1>3&A*3)).
+    Interleave,
+    /// Integers encoded as consecutive differences (e.g., 100^d1,1,5).
+    Delta,
+    /// Integers multiplied by a constant ratio (e.g., 1^*2#5).
+    Geometric,
+    /// ISO-8601 timestamps encoded as epoch integers (e.g., @T0Z(0>59)).
+    Timestamp,
+    /// IPv4 addresses encoded as an integer range (e.g., @ip4(167772161>167772414)).
+    IpRange,
+    /// Values sharing a prefix and/or suffix (e.g., {img:.png}(1>3)).
+    Affix,
+    /// Low-cardinality integers as packed dictionary indices (e.g., %200,404#4:G).
+    IntDictionary,
     /// Raw values (no pattern detected).
     Raw,
 }
 
 impl PatternType {
+    /// Every pattern type that produces a compressed encoding.
+    ///
+    /// Passing this to `CompressorConfig::with_disabled_detectors` limits
+    /// output to raw values and dictionary references.
+    pub const COMPRESSED: [PatternType; 16] = [
+        PatternType::Sequential,
+        PatternType::Arithmetic,
+        PatternType::SparseRange,
+        PatternType::PatchedRange,
+        PatternType::Repeat,
+        PatternType::Toggle,
+        PatternType::RunList,
+        PatternType::RepeatedRange,
+        PatternType::RepeatedToggle,
+        PatternType::Interleave,
+        PatternType::Delta,
+        PatternType::Geometric,
+        PatternType::Timestamp,
+        PatternType::IpRange,
+        PatternType::Affix,
+        PatternType::IntDictionary,
+    ];
+
     /// Check if this pattern type provides compression benefit.
     pub fn is_compressed(&self) -> bool {
         !matches!(self, PatternType::Raw)
     }
+
+    /// Get the stable snake_case name of this pattern type.
+    ///
+    /// These names are used when serializing statistics and must not change
+    /// once released, since downstream dashboards key on them.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PatternType::Sequential => "sequential",
+            PatternType::Arithmetic => "arithmetic",
+            PatternType::SparseRange => "sparse_range",
+            PatternType::PatchedRange => "patched_range",
+            PatternType::Repeat => "repeat",
+            PatternType::Toggle => "toggle",
+            PatternType::RunList => "run_list",
+            PatternType::RepeatedRange => "repeated_range",
+            PatternType::RepeatedToggle => "repeated_toggle",
+            PatternType::Interleave => "interleave",
+            PatternType::Delta => "delta",
+            PatternType::Geometric => "geometric",
+            PatternType::Timestamp => "timestamp",
+            PatternType::IpRange => "ip_range",
+            PatternType::Affix => "affix",
+            PatternType::IntDictionary => "int_dictionary",
+            PatternType::Raw => "raw",
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PatternType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_pattern_type_names() {
+        assert_eq!(PatternType::Sequential.as_str(), "sequential");
+        assert_eq!(PatternType::RunList.as_str(), "run_list");
+        assert_eq!(PatternType::SparseRange.as_str(), "sparse_range");
+        assert_eq!(PatternType::PatchedRange.as_str(), "patched_range");
+        assert_eq!(PatternType::RepeatedToggle.as_str(), "repeated_toggle");
+        assert_eq!(PatternType::Interleave.as_str(), "interleave");
+        assert_eq!(PatternType::IpRange.as_str(), "ip_range");
+        assert_eq!(PatternType::Affix.as_str(), "affix");
+        assert_eq!(PatternType::IntDictionary.as_str(), "int_dictionary");
+        assert_eq!(PatternType::Raw.as_str(), "raw");
+    }
+
     #[test]
     fn test_detection_result_raw_empty() {
         let result = DetectionResult::raw_empty();
@@ -278,6 +629,15 @@ mod tests {
         assert_eq!(result.pattern_type, PatternType::Arithmetic);
     }
 
+    #[test]
+    fn test_detection_result_float_range() {
+        // 0 0.5 1 1.5 2 2.5 3 -> 0.0>3.0:0.5
+        let result = DetectionResult::float_range(0.0, 3.0, 0.5, 19);
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(result.pattern_type, PatternType::Arithmetic);
+        assert!(result.operator.is_float_range());
+    }
+
     #[test]
     fn test_detection_result_repeat() {
         let result = DetectionResult::repeat("hello", 5, 5);
@@ -301,13 +661,54 @@ mod tests {
         assert_eq!(result.pattern_type, PatternType::RepeatedRange);
     }
 
+    #[test]
+    fn test_detection_result_delta() {
+        // 1000 1001 1002 1010 1011 -> 1000^d1,1,8,1
+        let result = DetectionResult::delta(1000, vec![1, 1, 8, 1], 24);
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(result.pattern_type, PatternType::Delta);
+    }
+
+    #[test]
+    fn test_detection_result_geometric() {
+        // 1 2 4 8 16 32 64 128 -> 1^*2#8
+        let result = DetectionResult::geometric(1, 2, 8, 22);
+        assert_eq!(result.compression_ratio, 22.0 / 6.0);
+        assert_eq!(result.pattern_type, PatternType::Geometric);
+    }
+
+    #[test]
+    fn test_detection_result_run_list() {
+        // A A A B B B -> (A*3,B*3)
+        let runs = vec![("A".to_string(), 3), ("B".to_string(), 3)];
+        let result = DetectionResult::run_list(runs, 11);
+        assert_eq!(result.pattern_type, PatternType::RunList);
+        assert!((result.compression_ratio - 11.0 / 9.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_detection_result_timestamp() {
+        // 20 x "2023-01-01T00:00:00Z" style values -> @T0Z(1672531200>1672531219)
+        let format = TimestampFormat::from_tag("T0Z").unwrap();
+        let result = DetectionResult::timestamp(
+            format,
+            AlsOperator::range(1_672_531_200, 1_672_531_219),
+            20 * 21 - 1,
+        );
+        assert_eq!(result.pattern_type, PatternType::Timestamp);
+        assert!((result.compression_ratio - 419.0 / 27.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_pattern_type_is_compressed() {
         assert!(PatternType::Sequential.is_compressed());
         assert!(PatternType::Arithmetic.is_compressed());
         assert!(PatternType::Repeat.is_compressed());
         assert!(PatternType::Toggle.is_compressed());
+        assert!(PatternType::RunList.is_compressed());
         assert!(PatternType::RepeatedRange.is_compressed());
+        assert!(PatternType::Delta.is_compressed());
+        assert!(PatternType::Timestamp.is_compressed());
         assert!(!PatternType::Raw.is_compressed());
     }
 
@@ -0,0 +1,184 @@
+//! Geometric pattern detection.
+//!
+//! This module detects integer columns where each value is the previous one
+//! multiplied by a constant integer ratio (e.g., `1, 2, 4, 8, 16` → `1^*2#5`).
+
+use super::detector::{parse_canonical_integers, DetectionResult, PatternDetector};
+
+/// Detector for geometric integer sequences.
+///
+/// Detects sequences with a constant integer ratio between consecutive values:
+/// - `1, 2, 4, 8, 16` → `1^*2#5`
+/// - `3, 30, 300, 3000` → `3^*10#4`
+///
+/// Ratios of 0 and 1 are never emitted, since those sequences are better
+/// encoded as raw values or a multiplier. Values are only accepted in
+/// canonical integer form so that expansion reproduces the original strings
+/// exactly.
+#[derive(Debug, Clone)]
+pub struct GeometricDetector {
+    min_pattern_length: usize,
+}
+
+impl GeometricDetector {
+    /// Create a new geometric detector with the given minimum pattern length.
+    pub fn new(min_pattern_length: usize) -> Self {
+        Self { min_pattern_length }
+    }
+
+    /// Find the constant ratio between consecutive values.
+    ///
+    /// Returns `None` if the first value is zero, the ratio is not an
+    /// integer, the ratio is 0 or 1, or any value breaks the ratio.
+    fn find_ratio(values: &[i64]) -> Option<i64> {
+        let (first, second) = (values[0], values[1]);
+        if first == 0 || second % first != 0 {
+            return None;
+        }
+
+        let ratio = second / first;
+        if ratio == 0 || ratio == 1 {
+            return None;
+        }
+
+        let consistent = values
+            .windows(2)
+            .all(|pair| pair[0].checked_mul(ratio) == Some(pair[1]));
+        if consistent {
+            Some(ratio)
+        } else {
+            None
+        }
+    }
+
+    /// Calculate the original string length of the values.
+    fn calculate_original_length(values: &[&str]) -> usize {
+        // Sum of all value lengths plus separators (spaces)
+        let value_len: usize = values.iter().map(|v| v.len()).sum();
+        let separator_len = values.len().saturating_sub(1);
+        value_len + separator_len
+    }
+
+    /// Detect the pattern in values already parsed by `parse_canonical_integers`.
+    ///
+    /// Lets the pattern engine share one parse between integer detectors.
+    pub(crate) fn detect_integers(&self, values: &[&str], integers: &[i64]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+
+        let ratio = Self::find_ratio(integers)?;
+
+        // Calculate compression benefit
+        let original_len = Self::calculate_original_length(values);
+        let result = DetectionResult::geometric(integers[0], ratio, values.len(), original_len);
+
+        // Only return if the encoding is smaller than raw
+        if result.compression_ratio > 1.0 {
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+impl PatternDetector for GeometricDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+        self.detect_integers(values, &parse_canonical_integers(values)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::als::AlsOperator;
+    use crate::pattern::PatternType;
+
+    fn expand(result: &DetectionResult) -> Vec<String> {
+        result.operator.expand(None).unwrap()
+    }
+
+    #[test]
+    fn test_powers_of_two() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["1", "2", "4", "8", "16", "32", "64", "128"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Geometric);
+        assert_eq!(result.operator, AlsOperator::geometric(1, 2, 8));
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_powers_of_ten() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["10", "100", "1000", "10000", "100000"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.operator, AlsOperator::geometric(10, 10, 5));
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_negative_ratio() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["3", "-6", "12", "-24", "48", "-96"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.operator, AlsOperator::geometric(3, -2, 6));
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_no_pattern_ratio_breaks_partway() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["1", "2", "4", "8", "16", "33", "64"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_ratio_zero_or_one() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["7", "7", "7", "7"];
+        assert!(detector.detect(&values).is_none());
+
+        let values: Vec<&str> = vec!["7", "0", "0", "0"];
+        assert!(detector.detect(&values).is_none());
+
+        let values: Vec<&str> = vec!["0", "0", "0", "0"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_non_integer_ratio() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["4", "6", "9", "13"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_non_canonical_integers() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["01", "02", "04", "08"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_overflow() {
+        let detector = GeometricDetector::new(3);
+        let big = (i64::MAX / 2 + 1).to_string();
+        let values: Vec<&str> = vec!["1", "2", &big];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_too_short() {
+        let detector = GeometricDetector::new(3);
+        let values: Vec<&str> = vec!["1", "2"];
+        assert!(detector.detect(&values).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! IPv4 address range pattern detection.
+//!
+//! This module detects columns of dotted-quad IPv4 addresses that advance by
+//! a constant step and encodes them as an integer range wrapped in an IPv4
+//! operator that restores the dotted-quad text on expansion (e.g.,
+//! `@ip4(167772161>167772414)` for `10.0.0.1` … `10.0.0.254`).
+
+use super::detector::{DetectionResult, PatternDetector};
+use crate::als::AlsOperator;
+use std::net::Ipv4Addr;
+
+/// Detector for IPv4 address ranges.
+///
+/// Detects:
+/// - Contiguous addresses (e.g., 10.0.0.1, 10.0.0.2, 10.0.0.3 → `@ip4(167772161>167772163)`)
+/// - Stepped addresses (e.g., 10.0.0.0, 10.0.0.4, 10.0.0.8 → `@ip4(167772160>167772168:4)`)
+/// - Ranges crossing octet boundaries (e.g., 10.0.0.255, 10.0.1.0)
+///
+/// Every value must be an address in canonical dotted-quad form, without
+/// leading zeros, and the whole column must follow one step; other columns
+/// are left to the other detectors.
+#[derive(Debug, Clone)]
+pub struct IpRangeDetector {
+    min_pattern_length: usize,
+}
+
+impl IpRangeDetector {
+    /// Create a new IPv4 range detector with the given minimum pattern length.
+    pub fn new(min_pattern_length: usize) -> Self {
+        Self { min_pattern_length }
+    }
+
+    /// Parse a canonical dotted-quad address as an integer.
+    ///
+    /// Returns `None` for anything that would not format back to the same
+    /// text, such as octets with leading zeros.
+    fn parse_address(s: &str) -> Option<i64> {
+        let address: Ipv4Addr = s.parse().ok()?;
+        (address.to_string() == s).then_some(i64::from(u32::from(address)))
+    }
+
+    /// Calculate the original string length of the values.
+    fn calculate_original_length(values: &[&str]) -> usize {
+        // Sum of all value lengths plus separators (spaces)
+        let value_len: usize = values.iter().map(|v| v.len()).sum();
+        let separator_len = values.len().saturating_sub(1);
+        value_len + separator_len
+    }
+}
+
+impl PatternDetector for IpRangeDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+
+        let start = Self::parse_address(values[0])?;
+        let step = Self::parse_address(values[1])? - start;
+        if step == 0 {
+            return None;
+        }
+
+        // Check each value against the progression, stopping at the first miss
+        let mut end = start;
+        for value in values {
+            if Self::parse_address(value)? != end {
+                return None;
+            }
+            end += step;
+        }
+        end -= step;
+
+        let range = AlsOperator::range_with_step(start, end, step);
+        let result = DetectionResult::ipv4_range(range, Self::calculate_original_length(values));
+
+        // Only return if the encoding is smaller than raw
+        if result.compression_ratio > 1.0 {
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pattern::PatternType;
+
+    fn expand(result: &DetectionResult) -> Vec<String> {
+        result.operator.expand(None).unwrap()
+    }
+
+    #[test]
+    fn test_same_subnet_range() {
+        let detector = IpRangeDetector::new(3);
+        let column: Vec<String> = (1..=254).map(|i| format!("10.0.0.{}", i)).collect();
+        let values: Vec<&str> = column.iter().map(String::as_str).collect();
+
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(result.pattern_type, PatternType::IpRange);
+        assert_eq!(
+            result.operator,
+            AlsOperator::ipv4(AlsOperator::range(167_772_161, 167_772_414))
+        );
+        assert!(result.compression_ratio > 100.0);
+        assert_eq!(expand(&result), column);
+    }
+
+    #[test]
+    fn test_range_crossing_octet_boundary() {
+        let detector = IpRangeDetector::new(3);
+        let values = vec!["10.0.0.254", "10.0.0.255", "10.0.1.0", "10.0.1.1"];
+
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(expand(&result), values);
+
+        // Carries propagate through every octet
+        let values = vec!["10.255.255.254", "10.255.255.255", "11.0.0.0", "11.0.0.1"];
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_stepped_and_descending_ranges() {
+        let detector = IpRangeDetector::new(3);
+
+        let values = vec!["192.168.0.0", "192.168.0.64", "192.168.0.128", "192.168.0.192", "192.168.1.0"];
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(
+            result.operator,
+            AlsOperator::ipv4(AlsOperator::range_with_step(3_232_235_520, 3_232_235_776, 64))
+        );
+        assert_eq!(expand(&result), values);
+
+        let values = vec!["10.0.1.1", "10.0.1.0", "10.0.0.255", "10.0.0.254"];
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_non_ip_data_falls_back() {
+        let detector = IpRangeDetector::new(3);
+
+        // Mixed with non-addresses
+        assert!(detector.detect(&["10.0.0.1", "10.0.0.2", "localhost", "10.0.0.4"]).is_none());
+        assert!(detector.detect(&["1", "2", "3", "4"]).is_none());
+        // Not a progression
+        assert!(detector.detect(&["10.0.0.1", "10.0.0.2", "10.0.0.7", "10.0.0.8"]).is_none());
+        // Repeated address
+        assert!(detector.detect(&["10.0.0.1", "10.0.0.1", "10.0.0.1"]).is_none());
+        // Leading zeros would not expand to the same text
+        assert!(detector.detect(&["10.0.0.1", "10.0.0.2", "10.0.0.03"]).is_none());
+        // Too short
+        assert!(detector.detect(&["10.0.0.1", "10.0.0.2"]).is_none());
+    }
+}
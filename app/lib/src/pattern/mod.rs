@@ -2,33 +2,58 @@
 //!
 //! This module provides pattern detection algorithms that analyze column data
 //! and identify compressible patterns such as sequential ranges, repetitions,
-//! alternations, and combined patterns.
+//! runs, alternations, delta and geometric sequences, timestamps, IPv4
+//! address ranges, shared prefixes and suffixes, and combined patterns.
 
 mod detector;
 mod range;
 mod repeat;
 mod toggle;
 mod combined;
+mod delta;
+mod geometric;
+mod timestamp;
+mod ip_range;
+mod affix;
 
 pub use detector::{DetectionResult, PatternDetector, PatternType};
 pub use range::RangeDetector;
 pub use repeat::{RepeatDetector, RunDetector};
 pub use toggle::ToggleDetector;
 pub use combined::CombinedDetector;
+pub use delta::DeltaDetector;
+pub use geometric::GeometricDetector;
+pub use timestamp::TimestampDetector;
+pub use ip_range::IpRangeDetector;
+pub use affix::AffixDetector;
 
+use crate::als::ColumnEncoding;
 use crate::config::CompressorConfig;
+use std::cell::OnceCell;
+use std::fmt;
+use std::sync::Arc;
 
 /// Main pattern detection engine that combines all detectors.
 ///
 /// The `PatternEngine` analyzes column values and selects the optimal
 /// compression encoding by comparing results from multiple detectors.
-#[derive(Debug, Clone)]
+/// Detectors registered with [`PatternEngine::register`] compete with the
+/// built-in ones.
+#[derive(Clone)]
 pub struct PatternEngine {
     config: CompressorConfig,
     range_detector: RangeDetector,
     repeat_detector: RepeatDetector,
+    run_detector: RunDetector,
     toggle_detector: ToggleDetector,
     combined_detector: CombinedDetector,
+    delta_detector: DeltaDetector,
+    geometric_detector: GeometricDetector,
+    timestamp_detector: TimestampDetector,
+    ip_range_detector: IpRangeDetector,
+    affix_detector: AffixDetector,
+    /// Detectors registered on top of the built-in ones, in registration order.
+    detectors: Vec<Arc<dyn PatternDetector>>,
 }
 
 impl PatternEngine {
@@ -42,16 +67,33 @@ impl PatternEngine {
         Self {
             range_detector: RangeDetector::new(config.min_pattern_length),
             repeat_detector: RepeatDetector::new(config.min_pattern_length),
-            toggle_detector: ToggleDetector::new(config.min_pattern_length),
+            run_detector: RunDetector::new(config.min_pattern_length),
+            toggle_detector: ToggleDetector::new(config.min_pattern_length)
+                .with_max_cycle_length(config.max_toggle_cycle_length),
             combined_detector: CombinedDetector::new(config.min_pattern_length),
+            delta_detector: DeltaDetector::new(config.min_pattern_length),
+            geometric_detector: GeometricDetector::new(config.min_pattern_length),
+            timestamp_detector: TimestampDetector::new(config.min_pattern_length),
+            ip_range_detector: IpRangeDetector::new(config.min_pattern_length),
+            affix_detector: AffixDetector::new(config.min_pattern_length),
+            detectors: Vec::new(),
             config,
         }
     }
 
+    /// Register a custom detector, such as one for a domain-specific format.
+    ///
+    /// Registered detectors run after the built-in ones and win when they
+    /// find a strictly better compression ratio. Their results are discarded
+    /// when the result's pattern type is disabled in the configuration.
+    pub fn register(&mut self, detector: impl PatternDetector + 'static) {
+        self.detectors.push(Arc::new(detector));
+    }
+
     /// Detect the best pattern for the given values.
     ///
-    /// Analyzes the values using all available detectors and returns
-    /// the result with the best compression ratio.
+    /// Analyzes the values using all detectors enabled in the configuration
+    /// and returns the result with the best compression ratio.
     pub fn detect(&self, values: &[&str]) -> DetectionResult {
         if values.is_empty() {
             return DetectionResult::raw_empty();
@@ -61,38 +103,152 @@ impl PatternEngine {
             return DetectionResult::raw_from_values(values);
         }
 
-        // Collect results from all detectors
+        // Keep the first candidate with the best compression ratio
         let mut best_result = DetectionResult::raw_from_values(values);
-
-        // Try range detection (for integer sequences)
-        if let Some(result) = self.range_detector.detect(values) {
+        for result in self.pattern_candidates(values) {
             if result.compression_ratio > best_result.compression_ratio {
                 best_result = result;
             }
         }
 
-        // Try repeat detection
-        if let Some(result) = self.repeat_detector.detect(values) {
-            if result.compression_ratio > best_result.compression_ratio {
-                best_result = result;
+        // Try affix detection (for URLs, paths and file names) only as a
+        // fallback, so columns with a more specific pattern keep it, unless
+        // the effort level asks for every detector to be compared
+        if best_result.pattern_type == PatternType::Raw || self.config.exhaustive_detection() {
+            if let Some(result) = self.affix_candidate(values) {
+                if result.compression_ratio > best_result.compression_ratio {
+                    best_result = result;
+                }
             }
         }
 
-        // Try toggle detection
-        if let Some(result) = self.toggle_detector.detect(values) {
-            if result.compression_ratio > best_result.compression_ratio {
-                best_result = result;
-            }
+        best_result
+    }
+
+    /// Return every candidate the detectors considered for the given values.
+    ///
+    /// The raw encoding comes first, followed by the result of each enabled
+    /// detector that matched, in the order [`PatternEngine::detect`] tries
+    /// them. Unlike `detect`, the affix detector always runs. Useful for
+    /// tooling and tests that want to see why an encoding won.
+    pub fn detect_all(&self, values: &[&str]) -> Vec<DetectionResult> {
+        if values.is_empty() {
+            return vec![DetectionResult::raw_empty()];
         }
 
+        let mut results = vec![DetectionResult::raw_from_values(values)];
+        if values.len() >= self.config.min_pattern_length {
+            results.extend(self.pattern_candidates(values));
+            results.extend(self.affix_candidate(values));
+        }
+        results
+    }
+
+    /// Run every enabled detector except affix, in priority order.
+    fn pattern_candidates(&self, values: &[&str]) -> Vec<DetectionResult> {
+        let mut results = Vec::new();
+
+        // Parse the integers once, for every detector that needs them
+        let integers = OnceCell::new();
+        let integers = || integers.get_or_init(|| detector::parse_canonical_integers(values));
+
+        // Try range detection (for integer sequences)
+        results.extend(self.try_detector(
+            &[
+                PatternType::Sequential,
+                PatternType::Arithmetic,
+                PatternType::SparseRange,
+                PatternType::PatchedRange,
+            ],
+            || self.range_detector.detect_parsed(values, integers().as_deref()),
+        ));
+
+        // Try repeat detection
+        results.extend(self.try_detector(&[PatternType::Repeat], || {
+            self.repeat_detector.detect(values)
+        }));
+
+        // Try run list detection
+        results.extend(self.try_detector(&[PatternType::RunList], || {
+            self.run_detector.detect(values)
+        }));
+
+        // Try toggle detection
+        results.extend(self.try_detector(&[PatternType::Toggle], || {
+            self.toggle_detector.detect(values)
+        }));
+
         // Try combined pattern detection
-        if let Some(result) = self.combined_detector.detect(values) {
-            if result.compression_ratio > best_result.compression_ratio {
-                best_result = result;
-            }
+        results.extend(self.try_detector(
+            &[PatternType::RepeatedRange, PatternType::RepeatedToggle, PatternType::Interleave],
+            || self.combined_detector.detect(values),
+        ));
+
+        // Try delta detection (for nearly-arithmetic integers)
+        results.extend(self.try_detector(&[PatternType::Delta], || {
+            self.delta_detector.detect_integers(values, integers().as_deref()?)
+        }));
+
+        // Try geometric detection (for integers with a constant ratio)
+        results.extend(self.try_detector(&[PatternType::Geometric], || {
+            self.geometric_detector.detect_integers(values, integers().as_deref()?)
+        }));
+
+        // Try timestamp detection (for ISO-8601 timestamp columns)
+        results.extend(self.try_detector(&[PatternType::Timestamp], || {
+            self.timestamp_detector.detect(values)
+        }));
+
+        // Try IPv4 range detection (for dotted-quad address columns)
+        results.extend(self.try_detector(&[PatternType::IpRange], || {
+            self.ip_range_detector.detect(values)
+        }));
+
+        // Try the registered detectors
+        for detector in &self.detectors {
+            results.extend(
+                detector
+                    .detect(values)
+                    .filter(|result| self.config.detector_enabled(result.pattern_type)),
+            );
         }
 
-        best_result
+        results
+    }
+
+    /// Run the affix detector if it is enabled.
+    fn affix_candidate(&self, values: &[&str]) -> Option<DetectionResult> {
+        self.try_detector(&[PatternType::Affix], || self.affix_detector.detect(values))
+    }
+
+    /// Run a detector producing `pattern_types`.
+    ///
+    /// The detector is skipped when all of its pattern types are disabled,
+    /// and a result of a disabled type is discarded.
+    fn try_detector(
+        &self,
+        pattern_types: &[PatternType],
+        detect: impl FnOnce() -> Option<DetectionResult>,
+    ) -> Option<DetectionResult> {
+        if !pattern_types.iter().any(|&pattern_type| self.config.detector_enabled(pattern_type)) {
+            return None;
+        }
+        detect().filter(|result| self.config.detector_enabled(result.pattern_type))
+    }
+
+    /// Detect the given encoding only, skipping every other detector.
+    ///
+    /// Used for columns whose encoding is forced by a schema hint. Returns
+    /// `None` if the values do not fit the encoding, or if the encoding is
+    /// not a pattern (`dict` and `raw`).
+    pub fn detect_encoding(&self, values: &[&str], encoding: ColumnEncoding) -> Option<DetectionResult> {
+        match encoding {
+            ColumnEncoding::Range => self.range_detector.detect(values),
+            ColumnEncoding::Repeat => self.repeat_detector.detect(values),
+            ColumnEncoding::Toggle => self.toggle_detector.detect(values),
+            ColumnEncoding::Delta => self.delta_detector.detect(values),
+            ColumnEncoding::Dict | ColumnEncoding::Raw => None,
+        }
     }
 
     /// Get the minimum pattern length configuration.
@@ -101,6 +257,15 @@ impl PatternEngine {
     }
 }
 
+impl fmt::Debug for PatternEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PatternEngine")
+            .field("config", &self.config)
+            .field("registered_detectors", &self.detectors.len())
+            .finish_non_exhaustive()
+    }
+}
+
 impl Default for PatternEngine {
     fn default() -> Self {
         Self::new()
@@ -125,6 +290,18 @@ mod tests {
         assert_eq!(engine.min_pattern_length(), 5);
     }
 
+    #[test]
+    fn test_pattern_engine_disabled_detectors() {
+        let values = vec!["1", "2", "3", "4", "5", "6"];
+        let config = CompressorConfig::new().with_disabled_detectors([PatternType::Sequential]);
+        let result = PatternEngine::with_config(config).detect(&values);
+        assert_ne!(result.pattern_type, PatternType::Sequential);
+
+        let config = CompressorConfig::new().with_disabled_detectors(PatternType::COMPRESSED);
+        let result = PatternEngine::with_config(config).detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Raw);
+    }
+
     #[test]
     fn test_pattern_engine_empty_input() {
         let engine = PatternEngine::new();
@@ -169,6 +346,36 @@ mod tests {
         assert_eq!(result.pattern_type, PatternType::Toggle);
     }
 
+    #[test]
+    fn test_pattern_engine_selects_plain_toggle_for_cycle() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = ["night", "day", "evening"].iter().copied().cycle().take(12).collect();
+        let result = engine.detect(&values);
+
+        // A repeated toggle would also fit, but the plain toggle is minimal
+        assert_eq!(result.pattern_type, PatternType::Toggle);
+        assert_eq!(
+            result.operator,
+            crate::als::AlsOperator::toggle_multi(vec!["night", "day", "evening"], 12)
+        );
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_detect_encoding() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec!["1", "2", "3", "4", "5", "1", "2", "3", "4", "5"];
+
+        // Forcing a range ignores the better repeated-range encoding
+        assert!(engine.detect_encoding(&values, ColumnEncoding::Range).is_none());
+        let result = engine.detect_encoding(&values[..5], ColumnEncoding::Range).unwrap();
+        assert_eq!(result.pattern_type, PatternType::Sequential);
+
+        assert!(engine.detect_encoding(&values, ColumnEncoding::Repeat).is_none());
+        assert!(engine.detect_encoding(&values, ColumnEncoding::Dict).is_none());
+        assert!(engine.detect_encoding(&values, ColumnEncoding::Raw).is_none());
+    }
+
     #[test]
     fn test_pattern_engine_selects_repeated_range() {
         let engine = PatternEngine::new();
@@ -211,9 +418,224 @@ mod tests {
         assert_eq!(result.pattern_type, PatternType::Arithmetic);
     }
 
+    #[test]
+    fn test_pattern_engine_selects_delta() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec![
+            "1700000000", "1700000001", "1700000002", "1700000003",
+            "1700000060", "1700000061", "1700000062", "1700000063",
+        ];
+        let result = engine.detect(&values);
+        // Mostly +1 with a jump - range fails, delta should win
+        assert_eq!(result.pattern_type, PatternType::Delta);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_prefers_range_over_delta() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec!["100", "101", "102", "103", "104", "105"];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Sequential);
+    }
+
+    #[test]
+    fn test_pattern_engine_selects_geometric() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec!["1", "2", "4", "8", "16", "32", "64", "128", "256", "512"];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Geometric);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_prefers_range_over_geometric() {
+        let engine = PatternEngine::new();
+        // 2, 4 fits both a range and a ratio of 2, but only the range holds
+        let values: Vec<&str> = vec!["2", "4", "6", "8", "10"];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Arithmetic);
+    }
+
+    #[test]
+    fn test_pattern_engine_selects_sparse_range() {
+        let engine = PatternEngine::new();
+        // IDs with a few rows deleted
+        let owned: Vec<String> = (1..=50)
+            .filter(|i| ![7, 23, 24].contains(i))
+            .map(|i| i.to_string())
+            .collect();
+        let values: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::SparseRange);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_selects_run_list() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec![
+            "pending", "pending", "pending", "shipped", "shipped", "shipped", "shipped",
+        ];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::RunList);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_selects_affix() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec![
+            "https://example.com/docs/intro",
+            "https://example.com/docs/setup",
+            "https://example.com/docs/usage",
+            "https://example.com/docs/faq",
+        ];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Affix);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+
+        let config = CompressorConfig::new().with_disabled_detectors([PatternType::Affix]);
+        let result = PatternEngine::with_config(config).detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Raw);
+    }
+
+    #[test]
+    fn test_pattern_engine_selects_timestamp() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec![
+            "2023-01-01T00:00:00Z",
+            "2023-01-01T00:01:00Z",
+            "2023-01-01T00:02:00Z",
+            "2023-01-01T00:03:00Z",
+        ];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Timestamp);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_selects_ip_range() {
+        let engine = PatternEngine::new();
+        let column: Vec<String> = (250..=260).map(|i| format!("10.0.{}.{}", i / 256, i % 256)).collect();
+        let values: Vec<&str> = column.iter().map(String::as_str).collect();
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::IpRange);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+
+        // Addresses that do not form a range are left to the other detectors
+        let values = vec!["10.0.0.1", "10.0.0.9", "10.0.0.4", "10.0.0.1"];
+        assert_ne!(engine.detect(&values).pattern_type, PatternType::IpRange);
+    }
+
+    #[test]
+    fn test_pattern_engine_prefers_repeat_for_identical_timestamps() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = vec!["2023-01-01T00:00:00Z"; 5];
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Repeat);
+    }
+
+    #[test]
+    fn test_pattern_engine_toggle_cycle_cap() {
+        let cycle: Vec<String> = (0..40).map(|i| format!("state_{}", i)).collect();
+        let values: Vec<&str> = cycle.iter().cycle().take(200).map(|s| s.as_str()).collect();
+
+        let engine = PatternEngine::new();
+        let result = engine.detect(&values);
+        assert_ne!(result.pattern_type, PatternType::Toggle);
+
+        let engine = PatternEngine::with_config(CompressorConfig::new().with_max_toggle_cycle_length(40));
+        let result = engine.detect(&values);
+        assert_eq!(result.pattern_type, PatternType::Toggle);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_pattern_engine_long_cycle_uses_other_encodings() {
+        // A repeating 1..40 sequence is rejected as a toggle but is still a range
+        let numbers: Vec<String> = (0..200).map(|i| (i % 40 + 1).to_string()).collect();
+        let values: Vec<&str> = numbers.iter().map(|s| s.as_str()).collect();
+
+        let result = PatternEngine::new().detect(&values);
+        assert_ne!(result.pattern_type, PatternType::Toggle);
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
     #[test]
     fn test_pattern_engine_is_send_sync() {
         fn assert_send_sync<T: Send + Sync>() {}
         assert_send_sync::<PatternEngine>();
     }
+
+    #[test]
+    fn test_pattern_engine_detect_all_lists_candidates() {
+        let engine = PatternEngine::new();
+        let values: Vec<&str> = "1 2 3 4 5 1 2 3 4 5 1 2 3 4 5 1 2 3 4 5".split(' ').collect();
+
+        let results = engine.detect_all(&values);
+        assert_eq!(results[0].pattern_type, PatternType::Raw);
+        let ratio = |pattern_type| {
+            results
+                .iter()
+                .find(|result| result.pattern_type == pattern_type)
+                .map(|result| result.compression_ratio)
+                .unwrap()
+        };
+        assert!(ratio(PatternType::RepeatedRange) > ratio(PatternType::Toggle));
+        assert!(ratio(PatternType::Toggle) > ratio(PatternType::Raw));
+
+        // The winner is the best candidate
+        let best = engine.detect(&values);
+        assert_eq!(best.pattern_type, PatternType::RepeatedRange);
+        assert!(results.iter().all(|result| result.compression_ratio <= best.compression_ratio));
+    }
+
+    #[test]
+    fn test_pattern_engine_detect_all_short_input() {
+        let engine = PatternEngine::new();
+        assert_eq!(engine.detect_all(&[]).len(), 1);
+        let results = engine.detect_all(&["1", "2"]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].pattern_type, PatternType::Raw);
+    }
+
+    /// Encodes a column of hex colors as a toggle of itself with a high ratio.
+    struct ColorDetector;
+
+    impl PatternDetector for ColorDetector {
+        fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+            if !values.iter().all(|value| value.len() == 7 && value.starts_with('#')) {
+                return None;
+            }
+            let operator = crate::als::AlsOperator::toggle_multi(values.to_vec(), values.len());
+            Some(DetectionResult::new(operator, 100.0, PatternType::Toggle))
+        }
+    }
+
+    #[test]
+    fn test_pattern_engine_registered_detector() {
+        let colors = vec!["#ff0000", "#00ff00", "#0000ff", "#ffffff"];
+        assert_eq!(PatternEngine::new().detect(&colors).pattern_type, PatternType::Raw);
+
+        let mut engine = PatternEngine::new();
+        engine.register(ColorDetector);
+        let result = engine.detect(&colors);
+        assert_eq!(result.pattern_type, PatternType::Toggle);
+        assert_eq!(result.compression_ratio, 100.0);
+        assert_eq!(result.operator.expand(None).unwrap(), colors);
+
+        // Other columns still use the built-in detectors, also after cloning
+        let engine = engine.clone();
+        let values = vec!["1", "2", "3", "4", "5"];
+        assert_eq!(engine.detect(&values).pattern_type, PatternType::Sequential);
+        assert_eq!(engine.detect_all(&colors).len(), 2);
+
+        // A result of a disabled pattern type is discarded
+        let config = CompressorConfig::new().with_disabled_detectors([PatternType::Toggle]);
+        let mut engine = PatternEngine::with_config(config);
+        engine.register(ColorDetector);
+        assert_eq!(engine.detect(&colors).pattern_type, PatternType::Raw);
+    }
 }
@@ -1,9 +1,14 @@
 //! Sequential and arithmetic range pattern detection.
 //!
-//! This module detects integer sequences that can be encoded using
-//! range syntax (e.g., `1>5` or `10>50:10`).
+//! This module detects integer and decimal sequences that can be encoded
+//! using range syntax (e.g., `1>5`, `10>50:10`, or `0.0>2.0:0.5`), including
+//! sequential integers with a few values missing (e.g., `1>7!4`) and
+//! ranges with a few values replaced (e.g., `1>6!=2:90`).
+
+use std::borrow::Cow;
 
 use super::detector::{DetectionResult, PatternDetector};
+use crate::als::AlsOperator;
 
 /// Detector for sequential and arithmetic range patterns.
 ///
@@ -11,6 +16,9 @@ use super::detector::{DetectionResult, PatternDetector};
 /// - Sequential integers with step 1 (e.g., 1, 2, 3, 4, 5 → `1>5`)
 /// - Arithmetic sequences with custom step (e.g., 10, 20, 30 → `10>30:10`)
 /// - Descending sequences (e.g., 5, 4, 3, 2, 1 → `5>1`)
+/// - Decimal sequences (e.g., 0, 0.5, 1, 1.5 → `0.0>1.5:0.5`)
+/// - Sequential integers with gaps (e.g., 1, 2, 3, 5, 6, 7 → `1>7!4`)
+/// - Ranges with a few out-of-sequence values (e.g., 1, 2, 90, 4, 5, 6 → `1>6!=2:90`)
 #[derive(Debug, Clone)]
 pub struct RangeDetector {
     min_pattern_length: usize,
@@ -23,8 +31,14 @@ impl RangeDetector {
     }
 
     /// Try to parse a string as an integer.
+    ///
+    /// A leading `+` is rejected, since ranges expand without it.
     fn parse_integer(s: &str) -> Option<i64> {
-        s.trim().parse::<i64>().ok()
+        let trimmed = s.trim();
+        if trimmed.starts_with('+') {
+            return None;
+        }
+        trimmed.parse::<i64>().ok()
     }
 
     /// Detect a range pattern in the values.
@@ -36,7 +50,7 @@ impl RangeDetector {
         }
 
         let start = values[0];
-        let step = values[1] - values[0];
+        let step = values[1].checked_sub(values[0])?;
 
         // Step of 0 means all values are the same - not a range pattern
         if step == 0 {
@@ -55,6 +69,144 @@ impl RangeDetector {
         Some((start, end, step))
     }
 
+    /// Detect a sequential range with missing values.
+    ///
+    /// Values must strictly increase or decrease from first to last. Every
+    /// value skipped between neighbours is listed as an exclusion. Gives up
+    /// once the exclusions could no longer be shorter than the raw values.
+    fn detect_sparse_range(&self, values: &[i64], original_len: usize) -> Option<DetectionResult> {
+        let start = *values.first()?;
+        let end = *values.last()?;
+        let step: i64 = if end > start { 1 } else if end < start { -1 } else { return None };
+
+        // Each exclusion takes at least two bytes: a digit and a separator
+        let max_excluded = original_len / 2;
+        let mut excluded = Vec::new();
+        for pair in values.windows(2) {
+            let gap = (pair[1] as i128 - pair[0] as i128) * step as i128;
+            if gap <= 0 {
+                return None;
+            }
+            if excluded.len() as i128 + gap - 1 > max_excluded as i128 {
+                return None;
+            }
+            let mut missing = pair[0];
+            for _ in 1..gap {
+                missing += step;
+                excluded.push(missing);
+            }
+        }
+
+        if excluded.is_empty() {
+            return None;
+        }
+        Some(DetectionResult::sparse_range(start, end, step, excluded, original_len))
+    }
+
+    /// Detect a range with a few values replaced.
+    ///
+    /// The step is the delta shared by most neighbours and the start is the
+    /// offset shared by most values, so overrides anywhere in the column,
+    /// including the first value, are found. Every value off the resulting
+    /// range becomes an override. Gives up once more than a tenth of the
+    /// values would need one.
+    fn detect_patched_range(&self, values: &[i64], original_len: usize) -> Option<DetectionResult> {
+        let max_overrides = values.len() / 10;
+        if max_overrides == 0 {
+            return None;
+        }
+
+        // Each override breaks at most the two deltas around it
+        let deltas = values.windows(2).map(|pair| pair[1] as i128 - pair[0] as i128);
+        let step = Self::majority(deltas.clone())?;
+        let matching = deltas.filter(|&delta| delta == step).count();
+        if step == 0 || matching + 2 * max_overrides < values.len() - 1 {
+            return None;
+        }
+
+        let offsets = values.iter().enumerate().map(|(i, &value)| value as i128 - i as i128 * step);
+        let start = Self::majority(offsets)?;
+        let end = start + (values.len() - 1) as i128 * step;
+
+        let mut overrides = Vec::new();
+        for (i, &value) in values.iter().enumerate() {
+            if value as i128 != start + i as i128 * step {
+                if overrides.len() == max_overrides {
+                    return None;
+                }
+                overrides.push((i, value));
+            }
+        }
+
+        if overrides.is_empty() {
+            return None;
+        }
+        let (start, end, step) = (i64::try_from(start).ok()?, i64::try_from(end).ok()?, i64::try_from(step).ok()?);
+        Some(DetectionResult::patched_range(start, end, step, overrides, original_len))
+    }
+
+    /// Find the item most likely to hold a majority, by Boyer-Moore vote.
+    ///
+    /// The caller must count the candidate's occurrences to confirm it.
+    fn majority(items: impl Iterator<Item = i128>) -> Option<i128> {
+        let mut candidate = None;
+        let mut votes = 0usize;
+        for item in items {
+            if votes == 0 {
+                candidate = Some(item);
+                votes = 1;
+            } else if candidate == Some(item) {
+                votes += 1;
+            } else {
+                votes -= 1;
+            }
+        }
+        candidate
+    }
+
+    /// Detect a decimal range pattern in the values.
+    ///
+    /// Values must all be written with the same number of fraction digits,
+    /// as in `0.0 0.5 1.0`, since a range expands at a fixed precision.
+    /// Returns the start, end, and step if a valid range is detected.
+    fn detect_float_range(&self, values: &[&str]) -> Option<(f64, f64, f64)> {
+        if values.len() < 2 {
+            return None;
+        }
+
+        let precision = values
+            .iter()
+            .map(|s| s.split_once('.').map(|(_, fraction)| fraction.len()).unwrap_or(0))
+            .max()?;
+        let floats: Option<Vec<f64>> = values
+            .iter()
+            .map(|s| {
+                let f = s.parse::<f64>().ok()?;
+                (f.is_finite() && format!("{:.*}", precision, f) == *s).then_some(f)
+            })
+            .collect();
+        let floats = floats?;
+
+        // Snap the step to the decimal precision of the values
+        let factor = 10f64.powi(i32::try_from(precision).ok()?);
+        let step = ((floats[1] - floats[0]) * factor).round() / factor;
+        if step == 0.0 {
+            return None;
+        }
+
+        let start = floats[0];
+        let end = *floats.last()?;
+
+        // Validate and reconstruct at fixed precision, then compare exactly
+        let op = AlsOperator::float_range_safe_with_limit(start, end, step, values.len()).ok()?;
+        let expanded = op.expand(None).ok()?;
+        if expanded.len() != values.len() || expanded.iter().zip(values).any(|(a, b)| a != b) {
+            return None;
+        }
+
+        Some((start, end, step))
+    }
+
     /// Calculate the original string length of the values.
     fn calculate_original_length(values: &[&str]) -> usize {
         // Sum of all value lengths plus separators (spaces)
@@ -62,24 +214,91 @@ impl RangeDetector {
         let separator_len = values.len().saturating_sub(1);
         value_len + separator_len
     }
-}
 
-impl PatternDetector for RangeDetector {
-    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+    /// Check whether the first two deltas already rule out every range.
+    ///
+    /// A zero delta or a change of direction fits no range, and two
+    /// different deltas whose gaps exceed the exclusion budget fit no
+    /// sparse range either. Lets non-range columns bail out before the
+    /// whole column is parsed.
+    fn rules_out_range(values: &[&str], original_len: usize) -> bool {
+        let [first, second, third, ..] = values else {
+            return false;
+        };
+        let (Some(first), Some(second), Some(third)) = (
+            Self::parse_integer(first),
+            Self::parse_integer(second),
+            Self::parse_integer(third),
+        ) else {
+            return false;
+        };
+
+        let first_delta = second as i128 - first as i128;
+        let second_delta = third as i128 - second as i128;
+        if first_delta == 0 || second_delta == 0 || (first_delta > 0) != (second_delta > 0) {
+            return true;
+        }
+        let skipped = first_delta.abs() + second_delta.abs() - 2;
+        first_delta != second_delta && skipped > (original_len / 2) as i128
+    }
+
+    /// Detect a range, reusing integers already parsed by the caller.
+    ///
+    /// `canonical` holds the values parsed by `parse_canonical_integers`, so
+    /// the pattern engine can share one parse between integer detectors.
+    /// When it is `None` the values are parsed here, leniently.
+    pub(crate) fn detect_parsed(
+        &self,
+        values: &[&str],
+        canonical: Option<&[i64]>,
+    ) -> Option<DetectionResult> {
         if values.len() < self.min_pattern_length {
             return None;
         }
 
-        // Try to parse all values as integers
-        let integers: Option<Vec<i64>> = values.iter().map(|s| Self::parse_integer(s)).collect();
-        let integers = integers?;
+        let original_len = Self::calculate_original_length(values);
+        if Self::rules_out_range(values, original_len) {
+            // Out-of-sequence values may still be overrides on a range
+            let integers = Self::integers(values, canonical)?;
+            return self
+                .detect_patched_range(&integers, original_len)
+                .filter(|result| result.compression_ratio > 1.0);
+        }
+        self.detect_full(values, canonical, original_len)
+    }
 
-        // Detect range pattern
-        let (start, end, step) = self.detect_range(&integers)?;
+    /// Use the integers parsed by the caller, or parse them here leniently.
+    fn integers<'v>(values: &[&str], canonical: Option<&'v [i64]>) -> Option<Cow<'v, [i64]>> {
+        // Canonical integers also parse leniently, to the same values
+        match canonical {
+            Some(integers) => Some(Cow::Borrowed(integers)),
+            None => values.iter().map(|s| Self::parse_integer(s)).collect::<Option<Vec<_>>>().map(Cow::Owned),
+        }
+    }
 
-        // Calculate compression benefit
-        let original_len = Self::calculate_original_length(values);
-        let result = DetectionResult::range(start, end, step, original_len);
+    /// Detect a range by scanning every value, without the early exit.
+    fn detect_full(
+        &self,
+        values: &[&str],
+        canonical: Option<&[i64]>,
+        original_len: usize,
+    ) -> Option<DetectionResult> {
+        let result = match Self::integers(values, canonical) {
+            Some(integers) => match self.detect_range(&integers) {
+                Some((start, end, step)) => DetectionResult::range(start, end, step, original_len),
+                // Sequential with gaps, e.g. IDs after deletions
+                None => match self.detect_sparse_range(&integers, original_len) {
+                    Some(result) => result,
+                    // Mostly a range, e.g. a counter with a few manual edits
+                    None => self.detect_patched_range(&integers, original_len)?,
+                },
+            },
+            None => {
+                // Fall back to decimal range pattern
+                let (start, end, step) = self.detect_float_range(values)?;
+                DetectionResult::float_range(start, end, step, original_len)
+            }
+        };
 
         // Only return if there's compression benefit
         if result.compression_ratio > 1.0 {
@@ -90,11 +309,24 @@ impl PatternDetector for RangeDetector {
     }
 }
 
+impl PatternDetector for RangeDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        self.detect_parsed(values, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::pattern::PatternType;
 
+    #[test]
+    fn test_overflowing_step_not_detected() {
+        let detector = RangeDetector::new(3);
+        let values = vec!["-9223372036854775808", "9223372036854775807", "0"];
+        assert!(detector.detect(&values).is_none());
+    }
+
     #[test]
     fn test_sequential_ascending() {
         let detector = RangeDetector::new(3);
@@ -184,7 +416,8 @@ mod tests {
     #[test]
     fn test_no_pattern_irregular() {
         let detector = RangeDetector::new(3);
-        let values: Vec<&str> = vec!["1", "2", "4", "5"];
+        // Too many gaps for a sparse range to pay off
+        let values: Vec<&str> = vec!["1", "2", "4", "7"];
         assert!(detector.detect(&values).is_none());
     }
 
@@ -254,6 +487,143 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_float_range_ascending() {
+        let detector = RangeDetector::new(3);
+        let values: Vec<&str> = vec!["0.0", "0.5", "1.0", "1.5", "2.0", "2.5"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Arithmetic);
+        assert!(result.operator.is_float_range());
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_float_range_keeps_fixed_precision_text() {
+        let detector = RangeDetector::new(3);
+        let values: Vec<&str> = vec!["0.0", "0.5", "1.0", "1.5"];
+        let result = detector.detect(&values).unwrap();
+
+        assert!(result.operator.is_float_range());
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+
+        // Mixed precision cannot be expanded back, so it is not a float range
+        let values: Vec<&str> = vec!["0", "0.5", "1", "1.5"];
+        assert!(detector.detect(&values).is_none_or(|result| !result.operator.is_float_range()));
+    }
+
+    #[test]
+    fn test_float_range_descending() {
+        let detector = RangeDetector::new(3);
+        let values: Vec<&str> = vec!["10.75", "10.50", "10.25", "10.00", "9.75", "9.50"];
+        let result = detector.detect(&values).unwrap();
+
+        if let crate::als::AlsOperator::FloatRange { start, end, step, precision } = result.operator {
+            assert_eq!(start, 10.75);
+            assert_eq!(end, 9.5);
+            assert_eq!(step, -0.25);
+            assert_eq!(precision, 2);
+        } else {
+            panic!("Expected FloatRange operator");
+        }
+    }
+
+    #[test]
+    fn test_float_range_precision_sensitive() {
+        let detector = RangeDetector::new(3);
+        // 0.1 + 0.2 != 0.3 in f64, so accumulation must not be used
+        let values: Vec<&str> = vec!["0.1", "0.2", "0.3", "0.4", "0.5", "0.6", "0.7"];
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+
+        // 0.3 - 0.2 is 0.09999999999999998 in f64
+        let values: Vec<&str> = vec!["0.2", "0.3", "0.4", "0.5", "0.6", "0.7"];
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_float_range_rejects_non_canonical() {
+        let detector = RangeDetector::new(3);
+        // Trailing zeros would not be reproduced exactly
+        let values: Vec<&str> = vec!["0.50", "1.00", "1.50", "2.00"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_float_range_irregular() {
+        let detector = RangeDetector::new(3);
+        let values: Vec<&str> = vec!["0.5", "1", "1.25", "2"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_sparse_range_single_gap() {
+        let detector = RangeDetector::new(3);
+        let values: Vec<&str> = vec!["1", "2", "3", "5", "6", "7"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::SparseRange);
+        assert_eq!(result.operator, AlsOperator::sparse_range(1, 7, 1, vec![4]));
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_sparse_range_multiple_gaps() {
+        let detector = RangeDetector::new(3);
+        let owned: Vec<String> = (1..=30)
+            .rev()
+            .filter(|i| ![4, 9, 10, 22].contains(i))
+            .map(|i| i.to_string())
+            .collect();
+        let values: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::SparseRange);
+        assert_eq!(result.operator, AlsOperator::sparse_range(30, 1, -1, vec![22, 10, 9, 4]));
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_sparse_range_dense_gaps_fall_back() {
+        let detector = RangeDetector::new(3);
+        let values: Vec<&str> = vec!["1", "4", "7", "9", "12"];
+        assert!(detector.detect(&values).is_none());
+
+        // Unsorted values are never a sparse range
+        let values: Vec<&str> = vec!["1", "2", "4", "3", "5"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_patched_range_two_overrides() {
+        let detector = RangeDetector::new(3);
+        let mut owned: Vec<String> = (1..=20).map(|i| i.to_string()).collect();
+        owned[0] = "900".to_string();
+        owned[12] = "-5".to_string();
+        let values: Vec<&str> = owned.iter().map(String::as_str).collect();
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::PatchedRange);
+        assert_eq!(result.operator, AlsOperator::patched_range(1, 20, 1, vec![(0, 900), (12, -5)]));
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_patched_range_too_noisy_falls_back() {
+        let detector = RangeDetector::new(3);
+        let mut owned: Vec<String> = (0..20).map(|i| (i * 5).to_string()).collect();
+        for i in [2, 7, 11] {
+            owned[i] = "3".to_string();
+        }
+        let values: Vec<&str> = owned.iter().map(String::as_str).collect();
+        assert!(detector.detect(&values).is_none());
+
+        // Short columns allow no overrides at all
+        let values: Vec<&str> = vec!["1", "2", "9", "4", "5"];
+        assert!(detector.detect(&values).is_none());
+    }
+
     #[test]
     fn test_compression_ratio() {
         let detector = RangeDetector::new(3);
@@ -262,4 +632,57 @@ mod tests {
         let result = detector.detect(&values).unwrap();
         assert!(result.compression_ratio > 1.0);
     }
+
+    #[test]
+    fn test_early_exit_matches_full_scan() {
+        let detector = RangeDetector::new(3);
+        let detect_full = |values: &[&str]| {
+            let original_len = RangeDetector::calculate_original_length(values);
+            detector.detect_full(values, None, original_len)
+        };
+        let summary = |result: Option<DetectionResult>| {
+            result.map(|result| (result.operator, result.pattern_type))
+        };
+
+        // Every short column over a handful of values, plus a few longer ones
+        let digits = ["-3", "-1", "0", "1", "2", "4", "7", "+1", " 2", "1.5"];
+        let mut columns: Vec<Vec<&str>> = Vec::new();
+        for &a in &digits {
+            for &b in &digits {
+                for &c in &digits {
+                    columns.push(vec![a, b, c]);
+                    columns.push(vec![a, b, c, "8"]);
+                    columns.push(vec![a, b, c, "-4"]);
+                }
+            }
+        }
+        columns.push(vec!["1", "2", "4", "5", "6", "7", "8", "9", "10", "11"]);
+        columns.push(vec!["10", "8", "7", "6", "5", "4", "3", "2", "1", "0"]);
+        columns.push(vec!["0", "0.5", "1", "1.5", "2"]);
+        columns.push(vec!["83", "1042", "7", "555", "12"]);
+        columns.push(vec!["1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "99", "13"]);
+        columns.push(vec!["40", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10"]);
+
+        for values in &columns {
+            let expected = summary(detect_full(values));
+            assert_eq!(summary(detector.detect(values)), expected, "{values:?}");
+
+            let canonical = crate::pattern::detector::parse_canonical_integers(values);
+            let cached = detector.detect_parsed(values, canonical.as_deref());
+            assert_eq!(summary(cached), expected, "{values:?}");
+        }
+    }
+
+    #[test]
+    fn test_early_exit_rejects_non_range_columns() {
+        let len = |values: &[&str]| RangeDetector::calculate_original_length(values);
+
+        for values in [vec!["5", "5", "6"], vec!["1", "3", "2"], vec!["83", "1042", "7", "555"]] {
+            assert!(RangeDetector::rules_out_range(&values, len(&values)), "{values:?}");
+        }
+        // Equal deltas, small gaps, floats and short columns need a full scan
+        for values in [vec!["1", "3", "5"], vec!["1", "2", "4", "5"], vec!["0.5", "1", "3"], vec!["1", "5"]] {
+            assert!(!RangeDetector::rules_out_range(&values, len(&values)), "{values:?}");
+        }
+    }
 }
@@ -56,7 +56,10 @@ impl PatternDetector for RepeatDetector {
 /// Detector that finds runs of repeated values within a larger sequence.
 ///
 /// Unlike `RepeatDetector` which requires all values to be identical,
-/// this detector finds the longest run of consecutive identical values.
+/// this detector finds runs of consecutive identical values. As a
+/// `PatternDetector` it encodes a column made of several runs as a run
+/// list (e.g., "A", "A", "A", "B", "B", "B" → `(A*3,B*3)`). A column that is
+/// a single run is left to `RepeatDetector`, whose `val*n` form is shorter.
 #[derive(Debug, Clone)]
 pub struct RunDetector {
     min_run_length: usize,
@@ -108,6 +111,47 @@ impl RunDetector {
     pub fn find_longest_run<'a>(&self, values: &[&'a str]) -> Option<(usize, &'a str, usize)> {
         self.find_runs(values).into_iter().max_by_key(|&(_, _, count)| count)
     }
+
+    /// Split values into consecutive runs of any length.
+    fn split_runs(values: &[&str]) -> Vec<(String, usize)> {
+        let mut runs: Vec<(String, usize)> = Vec::new();
+        for &value in values {
+            match runs.last_mut() {
+                Some((run_value, count)) if run_value == value => *count += 1,
+                _ => runs.push((value.to_string(), 1)),
+            }
+        }
+        runs
+    }
+
+    /// Calculate the original string length of the values.
+    fn calculate_original_length(values: &[&str]) -> usize {
+        let value_len: usize = values.iter().map(|v| v.len()).sum();
+        let separator_len = values.len().saturating_sub(1);
+        value_len + separator_len
+    }
+}
+
+impl PatternDetector for RunDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        let runs = Self::split_runs(values);
+
+        // A single run is better expressed as a plain multiplier, and
+        // without a run of the minimum length there is nothing to gain
+        if runs.len() < 2 || !runs.iter().any(|(_, count)| *count >= self.min_run_length) {
+            return None;
+        }
+
+        let original_len = Self::calculate_original_length(values);
+        let result = DetectionResult::run_list(runs, original_len);
+
+        // Only return if there's compression benefit
+        if result.compression_ratio > 1.0 {
+            Some(result)
+        } else {
+            None
+        }
+    }
 }
 
 #[cfg(test)]
@@ -233,6 +277,63 @@ mod tests {
         assert_eq!(longest, (2, "b", 4));
     }
 
+    #[test]
+    fn test_run_list_two_runs() {
+        let detector = RunDetector::new(3);
+        let values: Vec<&str> = vec!["active", "active", "active", "inactive", "inactive", "inactive"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, crate::pattern::PatternType::RunList);
+        assert_eq!(
+            result.operator,
+            crate::als::AlsOperator::run_list(vec![("active", 3), ("inactive", 3)])
+        );
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_run_list_many_short_runs() {
+        let detector = RunDetector::new(2);
+        let values: Vec<&str> = vec![
+            "north", "north", "south", "south", "east", "west", "west", "north", "north",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(
+            result.operator,
+            crate::als::AlsOperator::run_list(vec![
+                ("north", 2),
+                ("south", 2),
+                ("east", 1),
+                ("west", 2),
+                ("north", 2),
+            ])
+        );
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+
+        // Runs of single characters cost more than they save
+        let values: Vec<&str> = vec!["a", "a", "b", "b", "c", "c"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_run_list_single_run_prefers_multiply() {
+        let detector = RunDetector::new(2);
+        let values: Vec<&str> = vec!["same", "same", "same", "same"];
+        assert!(detector.detect(&values).is_none());
+
+        let result = RepeatDetector::new(2).detect(&values).unwrap();
+        assert!(result.operator.is_multiply());
+    }
+
+    #[test]
+    fn test_run_list_requires_min_run_length() {
+        let detector = RunDetector::new(3);
+        let values: Vec<&str> = vec!["alpha", "alpha", "beta", "beta", "gamma", "gamma"];
+        assert!(detector.detect(&values).is_none());
+    }
+
     #[test]
     fn test_find_longest_run_none() {
         let detector = RunDetector::new(5);
@@ -0,0 +1,222 @@
+//! ISO-8601 timestamp pattern detection.
+//!
+//! This module detects columns of timestamps sharing one textual format and
+//! encodes them as epoch integers using the range or delta operators, wrapped
+//! in a timestamp operator that restores the original text on expansion
+//! (e.g., `@T0Z(1672531200>1672531260:60)`).
+
+use super::delta::DeltaDetector;
+use super::detector::{DetectionResult, PatternDetector};
+use super::range::RangeDetector;
+use crate::als::TimestampFormat;
+
+/// Detector for ISO-8601 timestamp columns.
+///
+/// Detects timestamps that advance regularly or nearly regularly:
+/// - Evenly spaced timestamps → `@T0Z(1672531200>1672531500:60)`
+/// - Irregularly spaced timestamps → `@T0Z(1672531200^d5,60,1)`
+///
+/// Every value must use the same format (date/time separator, number of
+/// fractional digits, and timezone suffix); columns mixing formats are left
+/// to the other detectors so that expansion reproduces each value exactly.
+#[derive(Debug, Clone)]
+pub struct TimestampDetector {
+    range_detector: RangeDetector,
+    delta_detector: DeltaDetector,
+    min_pattern_length: usize,
+}
+
+impl TimestampDetector {
+    /// Create a new timestamp detector with the given minimum pattern length.
+    pub fn new(min_pattern_length: usize) -> Self {
+        Self {
+            range_detector: RangeDetector::new(min_pattern_length),
+            delta_detector: DeltaDetector::new(min_pattern_length),
+            min_pattern_length,
+        }
+    }
+
+    /// Parse all values with a single shared format.
+    ///
+    /// Returns `None` if any value is not a timestamp or uses a different
+    /// format from the first value.
+    fn parse_consistent(values: &[&str]) -> Option<(TimestampFormat, Vec<i64>)> {
+        let (format, first) = TimestampFormat::parse(values.first()?)?;
+        let mut epochs = Vec::with_capacity(values.len());
+        epochs.push(first);
+
+        for value in &values[1..] {
+            let (value_format, epoch) = TimestampFormat::parse(value)?;
+            if value_format != format {
+                return None;
+            }
+            epochs.push(epoch);
+        }
+
+        Some((format, epochs))
+    }
+
+    /// Calculate the original string length of the values.
+    fn calculate_original_length(values: &[&str]) -> usize {
+        // Sum of all value lengths plus separators (spaces)
+        let value_len: usize = values.iter().map(|v| v.len()).sum();
+        let separator_len = values.len().saturating_sub(1);
+        value_len + separator_len
+    }
+}
+
+impl PatternDetector for TimestampDetector {
+    fn detect(&self, values: &[&str]) -> Option<DetectionResult> {
+        if values.len() < self.min_pattern_length || values.len() < 2 {
+            return None;
+        }
+
+        let (format, epochs) = Self::parse_consistent(values)?;
+        let epoch_strings: Vec<String> = epochs.iter().map(i64::to_string).collect();
+        let epoch_refs: Vec<&str> = epoch_strings.iter().map(String::as_str).collect();
+
+        // Prefer an exact range, falling back to deltas for irregular spacing
+        let inner = self
+            .range_detector
+            .detect(&epoch_refs)
+            .or_else(|| self.delta_detector.detect(&epoch_refs))?;
+
+        let original_len = Self::calculate_original_length(values);
+        let result = DetectionResult::timestamp(format, inner.operator, original_len);
+
+        // Only return if the encoding is smaller than raw
+        if result.compression_ratio > 1.0 {
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::als::AlsOperator;
+    use crate::pattern::PatternType;
+
+    fn expand(result: &DetectionResult) -> Vec<String> {
+        result.operator.expand(None).unwrap()
+    }
+
+    fn inner(result: &DetectionResult) -> &AlsOperator {
+        match &result.operator {
+            AlsOperator::Timestamp { value, .. } => value,
+            other => panic!("expected timestamp operator, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_second_spaced_timestamps() {
+        let detector = TimestampDetector::new(3);
+        let values: Vec<&str> = vec![
+            "2023-01-01T00:00:00Z",
+            "2023-01-01T00:00:01Z",
+            "2023-01-01T00:00:02Z",
+            "2023-01-01T00:00:03Z",
+            "2023-01-01T00:00:04Z",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.pattern_type, PatternType::Timestamp);
+        assert_eq!(inner(&result), &AlsOperator::range(1_672_531_200, 1_672_531_204));
+        assert!(result.compression_ratio > 1.0);
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_minute_spaced_timestamps_crossing_midnight() {
+        let detector = TimestampDetector::new(3);
+        let values: Vec<&str> = vec![
+            "2023-12-31 23:58:00",
+            "2023-12-31 23:59:00",
+            "2024-01-01 00:00:00",
+            "2024-01-01 00:01:00",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert!(matches!(inner(&result), AlsOperator::Range { step: 60, .. }));
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_irregular_timestamps_use_deltas() {
+        let detector = TimestampDetector::new(3);
+        let values: Vec<&str> = vec![
+            "2023-01-01T00:00:00Z",
+            "2023-01-01T00:00:05Z",
+            "2023-01-01T00:01:05Z",
+            "2023-01-01T00:01:06Z",
+            "2023-01-01T00:01:06Z",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(
+            inner(&result),
+            &AlsOperator::delta(1_672_531_200, vec![5, 60, 1, 0])
+        );
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_fractional_seconds_with_offset() {
+        let detector = TimestampDetector::new(3);
+        let values: Vec<&str> = vec![
+            "2023-06-01T12:00:00.000+05:30",
+            "2023-06-01T12:00:00.250+05:30",
+            "2023-06-01T12:00:00.500+05:30",
+            "2023-06-01T12:00:00.750+05:30",
+        ];
+        let result = detector.detect(&values).unwrap();
+
+        assert!(matches!(inner(&result), AlsOperator::Range { step: 250, .. }));
+        assert_eq!(expand(&result), values);
+    }
+
+    #[test]
+    fn test_no_pattern_inconsistent_formats() {
+        let detector = TimestampDetector::new(3);
+
+        // Mixed timezone suffix
+        let values: Vec<&str> = vec![
+            "2023-01-01T00:00:00Z",
+            "2023-01-01T00:00:01Z",
+            "2023-01-01T00:00:02+00:00",
+        ];
+        assert!(detector.detect(&values).is_none());
+
+        // Mixed fractional precision
+        let values: Vec<&str> = vec![
+            "2023-01-01T00:00:00.1",
+            "2023-01-01T00:00:00.20",
+            "2023-01-01T00:00:00.3",
+        ];
+        assert!(detector.detect(&values).is_none());
+
+        // Mixed separator
+        let values: Vec<&str> = vec![
+            "2023-01-01T00:00:00",
+            "2023-01-01 00:00:01",
+            "2023-01-01T00:00:02",
+        ];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_non_timestamps() {
+        let detector = TimestampDetector::new(3);
+        let values: Vec<&str> = vec!["2023-01-01T00:00:00Z", "yesterday", "2023-01-01T00:00:02Z"];
+        assert!(detector.detect(&values).is_none());
+    }
+
+    #[test]
+    fn test_no_pattern_too_short() {
+        let detector = TimestampDetector::new(3);
+        let values: Vec<&str> = vec!["2023-01-01T00:00:00Z", "2023-01-01T00:00:01Z"];
+        assert!(detector.detect(&values).is_none());
+    }
+}
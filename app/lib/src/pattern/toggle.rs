@@ -9,28 +9,51 @@ use super::detector::{DetectionResult, PatternDetector};
 ///
 /// Detects alternating sequences of two or more values that can be
 /// compressed using the toggle operator (e.g., "T", "F", "T", "F" → `T~F*4`).
+/// Any fixed repeating order is captured, not just first-seen or sorted
+/// order: `A, C, B, A, C, B` becomes `A~C~B*6`. The shortest repeating unit
+/// is used, and cycles longer than the maximum cycle length are rejected.
 #[derive(Debug, Clone)]
 pub struct ToggleDetector {
     min_pattern_length: usize,
+    max_cycle_length: usize,
 }
 
 impl ToggleDetector {
+    /// Default maximum number of values in a cycle.
+    pub const DEFAULT_MAX_CYCLE_LENGTH: usize = 8;
+
     /// Create a new toggle detector with the given minimum pattern length.
     pub fn new(min_pattern_length: usize) -> Self {
-        Self { min_pattern_length }
+        Self {
+            min_pattern_length,
+            max_cycle_length: Self::DEFAULT_MAX_CYCLE_LENGTH,
+        }
+    }
+
+    /// Set the maximum number of values in a cycle.
+    pub fn with_max_cycle_length(mut self, max_cycle_length: usize) -> Self {
+        self.max_cycle_length = max_cycle_length;
+        self
+    }
+
+    /// Get the maximum number of values in a cycle.
+    pub fn max_cycle_length(&self) -> usize {
+        self.max_cycle_length
     }
 
     /// Detect the alternating pattern in values.
     ///
-    /// Returns the distinct values in order if an alternating pattern is found.
+    /// Returns the shortest repeating unit, in the order it appears, if the
+    /// values follow a cycle that repeats at least partially.
     fn detect_alternation<'a>(&self, values: &[&'a str]) -> Option<Vec<&'a str>> {
         if values.len() < 2 {
             return None;
         }
 
         // Find the cycle length by looking for when the pattern repeats
-        // Start with assuming 2 values (most common case)
-        for cycle_len in 2..=values.len().min(8) {
+        // Start with assuming 2 values (most common case). A unit spanning
+        // every value never repeats, so it is not a cycle.
+        for cycle_len in 2..=(values.len() - 1).min(self.max_cycle_length) {
             if self.is_valid_cycle(values, cycle_len) {
                 let cycle: Vec<&str> = values[..cycle_len].to_vec();
                 // Ensure we have at least 2 distinct values
@@ -177,6 +200,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sign_alternating_toggle() {
+        let detector = ToggleDetector::new(3);
+        let values: Vec<&str> = vec!["10", "-10", "10", "-10", "10", "-10"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.operator, crate::als::AlsOperator::toggle("10", "-10", 6));
+        // Negative values keep their exact numeric text
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_two_value_numeric_oscillation() {
+        let detector = ToggleDetector::new(3);
+        let values: Vec<&str> = vec!["5", "7", "5", "7", "5", "7", "5"];
+        let result = detector.detect(&values).unwrap();
+
+        assert_eq!(result.operator, crate::als::AlsOperator::toggle("5", "7", 7));
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
     #[test]
     fn test_odd_count_toggle() {
         let detector = ToggleDetector::new(3);
@@ -227,4 +271,69 @@ mod tests {
         // Should still detect the pattern
         assert!(result.is_some());
     }
+
+    #[test]
+    fn test_three_value_cycle_within_cap() {
+        let detector = ToggleDetector::new(3).with_max_cycle_length(3);
+        assert_eq!(detector.max_cycle_length(), 3);
+
+        let values: Vec<&str> = vec!["red", "green", "blue", "red", "green", "blue", "red"];
+        let result = detector.detect(&values).unwrap();
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_non_alphabetical_cycle() {
+        let detector = ToggleDetector::new(3);
+        let values: Vec<&str> = vec!["A", "C", "B", "A", "C", "B", "A", "C", "B"];
+        let result = detector.detect(&values).unwrap();
+
+        if let crate::als::AlsOperator::Toggle { values: toggle_values, count } = &result.operator {
+            assert_eq!(*count, 9);
+            assert_eq!(toggle_values, &vec!["A", "C", "B"]);
+        } else {
+            panic!("Expected Toggle operator");
+        }
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_cycle_with_repeated_value_uses_minimal_unit() {
+        let detector = ToggleDetector::new(3);
+        // The unit repeats a value, and its doubled form is also a valid cycle
+        let values: Vec<&str> = vec!["x", "y", "x", "z", "x", "y", "x", "z", "x", "y", "x", "z"];
+        let result = detector.detect(&values).unwrap();
+
+        if let crate::als::AlsOperator::Toggle { values: toggle_values, .. } = &result.operator {
+            assert_eq!(toggle_values, &vec!["x", "y", "x", "z"]);
+        } else {
+            panic!("Expected Toggle operator");
+        }
+        assert_eq!(result.operator.expand(None).unwrap(), values);
+    }
+
+    #[test]
+    fn test_near_cycle_breaking_at_end_rejected() {
+        let detector = ToggleDetector::new(3).with_max_cycle_length(16);
+        let values: Vec<&str> = vec!["A", "C", "B", "A", "C", "B", "A", "C", "X"];
+        assert!(detector.detect(&values).is_none());
+
+        // A unit that covers every value does not repeat
+        let values: Vec<&str> = vec!["A", "C", "B"];
+        assert!(detector.detect_alternation(&values).is_none());
+    }
+
+    #[test]
+    fn test_long_pseudo_cycle_rejected() {
+        let cycle: Vec<String> = (0..40).map(|i| format!("state_{}", i)).collect();
+        let values: Vec<&str> = cycle.iter().chain(&cycle).chain(&cycle).map(|s| s.as_str()).collect();
+
+        let detector = ToggleDetector::new(3);
+        assert_eq!(detector.max_cycle_length(), ToggleDetector::DEFAULT_MAX_CYCLE_LENGTH);
+        assert!(detector.detect(&values).is_none());
+
+        // Raising the cap is what lets the cycle through
+        let detector = ToggleDetector::new(3).with_max_cycle_length(40);
+        assert!(detector.detect(&values).is_some());
+    }
 }
@@ -8,6 +8,8 @@
 //!
 //! - **PyAlsCompressor**: Compress CSV and JSON data to ALS format
 //! - **PyAlsParser**: Parse ALS format back to CSV or JSON
+//! - **PyStreamingCompressor** / **PyStreamingParser**: Stream large files
+//!   chunk by chunk and row by row, releasing the GIL while Rust works
 //! - **Error handling**: Python exceptions with descriptive messages
 //! - **Type conversion**: Automatic conversion between Rust and Python types
 //!
@@ -28,9 +30,13 @@
 //! ```
 
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyRuntimeError, PyValueError};
-use pyo3::types::PyAny;
+use pyo3::exceptions::{PyKeyError, PyRuntimeError, PyValueError};
+use pyo3::types::{PyAny, PyBytes, PyList, PyString};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
 use crate::{AlsCompressor as RustAlsCompressor, AlsParser as RustAlsParser, AlsError, CompressorConfig, ParserConfig};
+use crate::{StreamingCompressor, StreamingParser, Value};
 
 /// Python wrapper for AlsCompressor.
 ///
@@ -532,6 +538,226 @@ impl PyAlsParser {
     }
 }
 
+/// Input for the streaming classes: a file opened by path, or a Python
+/// file-like object read through its `read` method.
+enum PySource {
+    File(File),
+    Object {
+        object: Py<PyAny>,
+        /// Bytes returned by the last `read` call that were not consumed yet
+        pending: Vec<u8>,
+        offset: usize,
+    },
+}
+
+impl PySource {
+    /// Open a path (`str` or `os.PathLike`), or wrap an object with a `read` method.
+    fn open(source: &Bound<'_, PyAny>) -> PyResult<Self> {
+        if source.hasattr("read")? {
+            return Ok(PySource::Object {
+                object: source.clone().unbind(),
+                pending: Vec::new(),
+                offset: 0,
+            });
+        }
+        let path: PathBuf = source.extract()?;
+        Ok(PySource::File(File::open(path)?))
+    }
+}
+
+impl Read for PySource {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            PySource::File(file) => file.read(buf),
+            PySource::Object { object, pending, offset } => {
+                if *offset == pending.len() {
+                    // Text-mode files return `str`, binary files `bytes`
+                    let data = Python::attach(|py| -> PyResult<Vec<u8>> {
+                        let data = object.bind(py).call_method1("read", (buf.len(),))?;
+                        if let Ok(text) = data.cast::<PyString>() {
+                            Ok(text.to_str()?.as_bytes().to_vec())
+                        } else {
+                            Ok(data.cast::<PyBytes>()?.as_bytes().to_vec())
+                        }
+                    })
+                    .map_err(|e| io::Error::other(e.to_string()))?;
+                    *pending = data;
+                    *offset = 0;
+                }
+
+                let count = buf.len().min(pending.len() - *offset);
+                buf[..count].copy_from_slice(&pending[*offset..*offset + count]);
+                *offset += count;
+                Ok(count)
+            }
+        }
+    }
+}
+
+/// Streaming CSV compressor for files larger than memory.
+///
+/// Iterating yields ALS fragments: the first holds the dictionary and schema
+/// header, every later one the column streams of the next batch of rows.
+/// Concatenated, the fragments form one ALS stream that `StreamingParser`
+/// reads back. The GIL is released while a batch is being compressed.
+///
+/// # Examples (Python)
+///
+/// ```python
+/// from als_compression import StreamingCompressor
+///
+/// with open("data.als", "w") as out:
+///     for chunk in StreamingCompressor("data.csv", csv_chunk_size=10000):
+///         out.write(chunk)
+/// ```
+#[pyclass(name = "StreamingCompressor")]
+pub struct PyStreamingCompressor {
+    chunks: Box<dyn Iterator<Item = crate::Result<String>> + Send + Sync>,
+}
+
+#[pymethods]
+impl PyStreamingCompressor {
+    /// Create a streaming compressor over CSV input.
+    ///
+    /// Args:
+    ///     source: Path to a CSV file, or a file-like object opened in text
+    ///         or binary mode
+    ///     csv_chunk_size (int, optional): Rows per yielded chunk. Default: 1000
+    ///     dictionary_sample_size (int, optional): Rows sampled to build the
+    ///         shared dictionary. Default: 1000
+    ///
+    /// Raises:
+    ///     OSError: If the file cannot be opened
+    ///
+    /// Example:
+    ///     >>> chunks = list(StreamingCompressor("data.csv", csv_chunk_size=500))
+    #[new]
+    #[pyo3(signature = (source, csv_chunk_size=None, dictionary_sample_size=None))]
+    fn new(
+        source: &Bound<'_, PyAny>,
+        csv_chunk_size: Option<usize>,
+        dictionary_sample_size: Option<usize>,
+    ) -> PyResult<Self> {
+        let mut compressor = StreamingCompressor::new(PySource::open(source)?);
+        if let Some(size) = csv_chunk_size {
+            compressor = compressor.with_csv_chunk_size(size);
+        }
+        if let Some(size) = dictionary_sample_size {
+            compressor = compressor.with_dictionary_sample_size(size);
+        }
+
+        Ok(PyStreamingCompressor {
+            chunks: Box::new(compressor.into_csv_chunks()),
+        })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Compress and return the next ALS fragment.
+    ///
+    /// Raises:
+    ///     ValueError: If the CSV data is malformed
+    ///     RuntimeError: If reading the input fails
+    fn __next__(mut slf: PyRefMut<'_, Self>, py: Python<'_>) -> PyResult<Option<String>> {
+        let chunks = &mut slf.chunks;
+        py.detach(|| chunks.next())
+            .transpose()
+            .map_err(convert_als_error)
+    }
+
+    /// Get a string representation of the compressor.
+    fn __repr__(&self) -> String {
+        "StreamingCompressor()".to_string()
+    }
+}
+
+/// Streaming ALS parser for files larger than memory.
+///
+/// Iterating yields one row at a time as a list of values (`None`, `int`,
+/// `float` or `str`). Streams written by `StreamingCompressor` are read batch
+/// by batch; the GIL is released while a batch is being expanded.
+///
+/// # Examples (Python)
+///
+/// ```python
+/// from als_compression import StreamingParser
+///
+/// for row in StreamingParser("data.als"):
+///     print(row)
+/// ```
+#[pyclass(name = "StreamingParser")]
+pub struct PyStreamingParser {
+    rows: Box<dyn Iterator<Item = crate::Result<Vec<Value<'static>>>> + Send + Sync>,
+}
+
+#[pymethods]
+impl PyStreamingParser {
+    /// Create a streaming parser over ALS input.
+    ///
+    /// Args:
+    ///     source: Path to an ALS file, or a file-like object opened in text
+    ///         or binary mode
+    ///
+    /// Raises:
+    ///     OSError: If the file cannot be opened
+    ///
+    /// Example:
+    ///     >>> rows = list(StreamingParser("data.als"))
+    #[new]
+    fn new(source: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let parser = StreamingParser::new(PySource::open(source)?);
+        Ok(PyStreamingParser {
+            rows: Box::new(parser.into_rows()),
+        })
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Parse and return the next row.
+    ///
+    /// Raises:
+    ///     ValueError: If the ALS data is malformed
+    ///     RuntimeError: If reading the input fails
+    fn __next__<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        py: Python<'py>,
+    ) -> PyResult<Option<Bound<'py, PyList>>> {
+        let rows = &mut slf.rows;
+        let Some(row) = py.detach(|| rows.next()) else {
+            return Ok(None);
+        };
+        let row = row.map_err(convert_als_error)?;
+
+        let values = row
+            .iter()
+            .map(|value| value_to_py(py, value))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(Some(PyList::new(py, values)?))
+    }
+
+    /// Get a string representation of the parser.
+    fn __repr__(&self) -> String {
+        "StreamingParser()".to_string()
+    }
+}
+
+/// Convert a parsed value to the matching Python object.
+fn value_to_py<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Integer(i) => i.into_pyobject(py)?.into_any(),
+        Value::Float(f) => f.into_pyobject(py)?.into_any(),
+        Value::Decimal(d) => py.import("decimal")?.getattr("Decimal")?.call1((d.as_str(),))?,
+        Value::Timestamp(t) => t.into_pyobject(py)?.into_any(),
+        Value::Boolean(b) => b.into_pyobject(py)?.to_owned().into_any(),
+        Value::String(s) => PyString::new(py, s).into_any(),
+    })
+}
+
 /// Convert AlsError to Python exception.
 fn convert_als_error(error: AlsError) -> PyErr {
     match error {
@@ -541,6 +767,12 @@ fn convert_als_error(error: AlsError) -> PyErr {
                 line, column, message
             ))
         }
+        AlsError::LogParseError { line, message } => {
+            PyValueError::new_err(format!(
+                "Log parsing error at line {}: {}",
+                line, message
+            ))
+        }
         AlsError::JsonParseError(e) => {
             PyValueError::new_err(format!("JSON parsing error: {}", e))
         }
@@ -562,18 +794,106 @@ fn convert_als_error(error: AlsError) -> PyErr {
                 start, end, step
             ))
         }
+        AlsError::InvalidFloatRange { start, end, step, message } => {
+            PyValueError::new_err(format!(
+                "Invalid float range {} to {} with step {}: {}",
+                start, end, step, message
+            ))
+        }
+        AlsError::DeltaOverflow { base, index } => {
+            PyValueError::new_err(format!(
+                "Delta overflow: value {} of delta sequence starting at {} exceeds the i64 range",
+                index, base
+            ))
+        }
+        AlsError::InvalidGeometric { start, ratio, message } => {
+            PyValueError::new_err(format!(
+                "Invalid geometric sequence {}^*{}: {}",
+                start, ratio, message
+            ))
+        }
+        AlsError::InvalidSparseRange { start, end, message } => {
+            PyValueError::new_err(format!(
+                "Invalid sparse range {}>{}: {}",
+                start, end, message
+            ))
+        }
+        AlsError::InvalidPatchedRange { start, end, message } => {
+            PyValueError::new_err(format!(
+                "Invalid patched range {}>{}: {}",
+                start, end, message
+            ))
+        }
+        AlsError::InvalidInterleave { lanes, message } => {
+            PyValueError::new_err(format!(
+                "Invalid interleave of {} lanes: {}",
+                lanes, message
+            ))
+        }
+        AlsError::InvalidTimestamp { value, message } => {
+            PyValueError::new_err(format!(
+                "Invalid timestamp value '{}': {}",
+                value, message
+            ))
+        }
+        AlsError::InvalidIpv4 { value, message } => {
+            PyValueError::new_err(format!(
+                "Invalid IPv4 address value '{}': {}",
+                value, message
+            ))
+        }
         AlsError::VersionMismatch { expected, found } => {
             PyValueError::new_err(format!(
                 "Version mismatch: expected <= {}, found {}",
                 expected, found
             ))
         }
+        AlsError::InvalidEscape { position, sequence } => {
+            PyValueError::new_err(format!(
+                "Invalid escape sequence {} at position {}",
+                sequence, position
+            ))
+        }
+        AlsError::ChecksumMismatch { expected, found } => {
+            PyValueError::new_err(format!(
+                "Checksum mismatch: expected {:08x}, found {:08x}",
+                expected, found
+            ))
+        }
+        AlsError::InputLimitExceeded { limit, unit } => {
+            PyValueError::new_err(format!(
+                "Input exceeds the configured limit of {} {}",
+                limit, unit
+            ))
+        }
+        AlsError::OutputTooLarge { limit } => {
+            PyValueError::new_err(format!(
+                "Expanded output exceeds the configured limit of {} values",
+                limit
+            ))
+        }
         AlsError::ColumnMismatch { schema, data } => {
             PyValueError::new_err(format!(
                 "Column count mismatch: schema has {} columns, data has {} columns",
                 schema, data
             ))
         }
+        AlsError::SchemaMismatch { expected, found } => {
+            PyValueError::new_err(format!(
+                "Schema mismatch: expected {:?}, found {:?}",
+                expected, found
+            ))
+        }
+        AlsError::SentinelMismatch { .. } => PyValueError::new_err(error.to_string()),
+        AlsError::ColumnNotFound { column } => {
+            PyKeyError::new_err(format!("Column not found: {}", column))
+        }
+        AlsError::UnsupportedSimdLevel { level } => {
+            PyRuntimeError::new_err(format!(
+                "Unsupported SIMD level: {} is not available on this CPU",
+                level
+            ))
+        }
         AlsError::IoError(e) => {
             PyRuntimeError::new_err(format!("IO error: {}", e))
         }
@@ -587,6 +907,8 @@ fn convert_als_error(error: AlsError) -> PyErr {
 fn als_compression(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlsCompressor>()?;
     m.add_class::<PyAlsParser>()?;
+    m.add_class::<PyStreamingCompressor>()?;
+    m.add_class::<PyStreamingParser>()?;
     
     // Add module-level documentation
     m.add("__doc__", "ALS (Adaptive Logic Stream) compression library for structured data.\n\n\
@@ -594,7 +916,9 @@ fn als_compression(m: &Bound<'_, PyModule>) -> PyResult<()> {
         algorithmic pattern description rather than raw enumeration.\n\n\
         Classes:\n\
         - AlsCompressor: Compress CSV and JSON data to ALS format\n\
-        - AlsParser: Parse ALS format back to CSV or JSON\n\n\
+        - AlsParser: Parse ALS format back to CSV or JSON\n\
+        - StreamingCompressor: Compress large CSV files chunk by chunk\n\
+        - StreamingParser: Read large ALS files row by row\n\n\
         Example:\n\
             >>> from als_compression import AlsCompressor, AlsParser\n\
             >>> compressor = AlsCompressor()\n\
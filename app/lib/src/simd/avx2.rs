@@ -255,6 +255,44 @@ pub unsafe fn find_arithmetic_sequences_avx2(values: &[i64]) -> Vec<(usize, usiz
     sequences
 }
 
+/// Find the first byte equal to any of `needles` using AVX2.
+///
+/// Compares 32 bytes at a time against each needle and falls back to the
+/// scalar implementation for the tail.
+///
+/// # Safety
+///
+/// The caller must ensure that AVX2 is available on the current CPU.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to search
+/// * `needles` - The byte values to look for
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+pub unsafe fn find_any_byte_avx2(bytes: &[u8], needles: [u8; 4]) -> Option<usize> {
+    let splats = needles.map(|needle| _mm256_set1_epi8(needle as i8));
+    let ptr = bytes.as_ptr();
+
+    let mut offset = 0;
+    while offset + 32 <= bytes.len() {
+        let chunk = _mm256_loadu_si256(ptr.add(offset) as *const __m256i);
+        let matches = _mm256_or_si256(
+            _mm256_or_si256(_mm256_cmpeq_epi8(chunk, splats[0]), _mm256_cmpeq_epi8(chunk, splats[1])),
+            _mm256_or_si256(_mm256_cmpeq_epi8(chunk, splats[2]), _mm256_cmpeq_epi8(chunk, splats[3])),
+        );
+
+        // One bit per byte; the lowest set bit is the first match
+        let mask = _mm256_movemask_epi8(matches) as u32;
+        if mask != 0 {
+            return Some(offset + mask.trailing_zeros() as usize);
+        }
+        offset += 32;
+    }
+
+    super::scalar::find_any_byte_scalar(&bytes[offset..], needles).map(|i| offset + i)
+}
+
 #[cfg(all(test, target_arch = "x86_64"))]
 mod tests {
     use super::*;
@@ -360,4 +398,29 @@ mod tests {
             assert_eq!(scalar_runs, avx2_runs);
         }
     }
+
+    #[test]
+    fn test_find_any_byte_avx2_matches_scalar() {
+        if !has_avx2() {
+            println!("AVX2 not available, skipping test");
+            return;
+        }
+
+        let needles = [b',', b'"', b'\n', b'\r'];
+        let mut bytes = vec![b'x'; 100];
+        unsafe {
+            assert_eq!(find_any_byte_avx2(&bytes, needles), None);
+            // Matches in the first chunk, a later chunk, and the scalar tail
+            for position in [0, 5, 31, 32, 63, 64, 97, 99] {
+                bytes[position] = needles[position % 4];
+                assert_eq!(
+                    find_any_byte_avx2(&bytes, needles),
+                    super::super::scalar::find_any_byte_scalar(&bytes, needles),
+                    "Mismatch for position {}",
+                    position
+                );
+                bytes[position] = b'x';
+            }
+        }
+    }
 }
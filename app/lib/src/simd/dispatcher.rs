@@ -4,6 +4,7 @@
 //! automatically selecting the best available implementation based on CPU features.
 
 use crate::config::SimdConfig;
+use crate::error::{AlsError, Result};
 
 /// Detected CPU features for SIMD acceleration.
 ///
@@ -70,6 +71,18 @@ impl CpuFeatures {
     pub fn has_any(&self) -> bool {
         self.avx512 || self.avx2 || self.neon
     }
+
+    /// Check if the given SIMD level can run on this CPU.
+    ///
+    /// The scalar level is always supported.
+    pub fn supports(&self, level: SimdLevel) -> bool {
+        match level {
+            SimdLevel::Avx512 => self.avx512,
+            SimdLevel::Avx2 => self.avx2,
+            SimdLevel::Neon => self.neon,
+            SimdLevel::Scalar => true,
+        }
+    }
 }
 
 impl Default for CpuFeatures {
@@ -91,6 +104,16 @@ pub enum SimdLevel {
     Scalar,
 }
 
+impl SimdLevel {
+    /// All SIMD levels, from widest vectors to the scalar fallback.
+    pub const ALL: [SimdLevel; 4] = [
+        SimdLevel::Avx512,
+        SimdLevel::Avx2,
+        SimdLevel::Neon,
+        SimdLevel::Scalar,
+    ];
+}
+
 impl std::fmt::Display for SimdLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -141,9 +164,18 @@ impl SimdDispatcher {
     /// Create a new dispatcher with the given configuration.
     ///
     /// The configuration allows disabling specific SIMD instruction sets,
-    /// which can be useful for testing or compatibility.
+    /// which can be useful for testing or compatibility. A forced level the
+    /// CPU does not support is logged as a warning and ignored.
     pub fn with_config(config: SimdConfig) -> Self {
         let features = CpuFeatures::detect();
+        if let Some(level) = config.forced_level {
+            if !features.supports(level) {
+                log::warn!(
+                    "Forced SIMD level {} is not supported by this CPU; using automatic selection",
+                    level
+                );
+            }
+        }
         let level = Self::select_level(&features, &config);
         Self {
             features,
@@ -152,6 +184,28 @@ impl SimdDispatcher {
         }
     }
 
+    /// Create a new dispatcher with the given configuration, rejecting a
+    /// forced level the CPU does not support.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::UnsupportedSimdLevel` if `config.forced_level` is
+    /// set to a level that is not available on this CPU.
+    pub fn try_with_config(config: SimdConfig) -> Result<Self> {
+        Self::check_forced_level(&CpuFeatures::detect(), &config)?;
+        Ok(Self::with_config(config))
+    }
+
+    /// Check that a forced level in `config` is supported by `features`.
+    fn check_forced_level(features: &CpuFeatures, config: &SimdConfig) -> Result<()> {
+        match config.forced_level {
+            Some(level) if !features.supports(level) => {
+                Err(AlsError::UnsupportedSimdLevel { level })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Create a dispatcher that only uses scalar operations.
     ///
     /// This is useful for testing or when SIMD causes issues.
@@ -159,8 +213,23 @@ impl SimdDispatcher {
         Self::with_config(SimdConfig::disabled())
     }
 
+    /// Create a dispatcher that uses the given SIMD level.
+    ///
+    /// Returns `None` if the CPU does not support the level.
+    pub fn with_level(level: SimdLevel) -> Option<Self> {
+        let dispatcher = Self::with_config(SimdConfig::default().with_forced_level(level));
+        (dispatcher.level == level).then_some(dispatcher)
+    }
+
     /// Select the best SIMD level based on features and configuration.
     fn select_level(features: &CpuFeatures, config: &SimdConfig) -> SimdLevel {
+        // A supported forced level overrides the automatic selection
+        if let Some(level) = config.forced_level {
+            if features.supports(level) {
+                return level;
+            }
+        }
+
         // Priority: AVX-512 > AVX2 > NEON > Scalar
         if features.avx512 && config.enable_avx512 {
             SimdLevel::Avx512
@@ -343,6 +412,48 @@ impl SimdDispatcher {
             _ => super::scalar::find_arithmetic_sequences_scalar(values),
         }
     }
+
+    /// Find the first byte equal to any of `needles`.
+    ///
+    /// The CSV parser uses this to jump between delimiters, quotes, and line
+    /// breaks instead of inspecting every byte. AVX-512 CPUs use the AVX2
+    /// implementation, since byte comparisons gain nothing from wider vectors
+    /// at typical field lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes to search
+    /// * `needles` - The byte values to look for
+    ///
+    /// # Returns
+    ///
+    /// The index of the first matching byte, or `None` if no byte matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use als_compression::simd::SimdDispatcher;
+    ///
+    /// let dispatcher = SimdDispatcher::detect();
+    /// let needles = [b',', b'"', b'\n', b'\r'];
+    /// assert_eq!(dispatcher.find_any_byte(b"name,value", needles), Some(4));
+    /// assert_eq!(dispatcher.find_any_byte(b"name", needles), None);
+    /// ```
+    pub fn find_any_byte(&self, bytes: &[u8], needles: [u8; 4]) -> Option<usize> {
+        match self.level {
+            #[cfg(target_arch = "x86_64")]
+            SimdLevel::Avx512 | SimdLevel::Avx2 if self.features.avx2 => {
+                // Safety: We've verified AVX2 is available
+                unsafe { super::avx2::find_any_byte_avx2(bytes, needles) }
+            }
+            #[cfg(target_arch = "aarch64")]
+            SimdLevel::Neon => {
+                // Safety: NEON is always available on ARM64
+                unsafe { super::neon::find_any_byte_neon(bytes, needles) }
+            }
+            _ => super::scalar::find_any_byte_scalar(bytes, needles),
+        }
+    }
 }
 
 impl Default for SimdDispatcher {
@@ -384,6 +495,89 @@ mod tests {
         assert!(!dispatcher.is_accelerated());
     }
 
+    #[test]
+    fn test_cpu_features_supports() {
+        let features = CpuFeatures::none();
+        assert!(features.supports(SimdLevel::Scalar));
+        assert!(!features.supports(SimdLevel::Avx2));
+
+        let features = CpuFeatures { avx512: false, avx2: true, neon: false };
+        assert!(features.supports(SimdLevel::Avx2));
+        assert!(!features.supports(SimdLevel::Avx512));
+    }
+
+    #[test]
+    fn test_select_level_forced() {
+        let features = CpuFeatures { avx512: true, avx2: true, neon: false };
+        let config = SimdConfig::default().with_forced_level(SimdLevel::Avx2);
+        assert_eq!(SimdDispatcher::select_level(&features, &config), SimdLevel::Avx2);
+
+        // Forcing overrides the enable flags
+        let config = SimdConfig::disabled().with_forced_level(SimdLevel::Avx2);
+        assert_eq!(SimdDispatcher::select_level(&features, &config), SimdLevel::Avx2);
+
+        // Unsupported levels fall back to automatic selection
+        let config = SimdConfig::default().with_forced_level(SimdLevel::Neon);
+        assert_eq!(SimdDispatcher::select_level(&features, &config), SimdLevel::Avx512);
+    }
+
+    #[test]
+    fn test_with_level() {
+        let features = CpuFeatures::detect();
+        for level in SimdLevel::ALL {
+            let dispatcher = SimdDispatcher::with_level(level);
+            assert_eq!(dispatcher.is_some(), features.supports(level), "{}", level);
+            if let Some(dispatcher) = dispatcher {
+                assert_eq!(dispatcher.level(), level);
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_scalar_matches_detected() {
+        let detected = SimdDispatcher::detect();
+        let scalar = SimdDispatcher::with_level(SimdLevel::Scalar).unwrap();
+        assert_eq!(scalar.level(), SimdLevel::Scalar);
+
+        for (start, end, step) in [(1, 1000, 1), (500, -500, -3), (0, 10_000, 7), (42, 42, 1)] {
+            assert_eq!(scalar.expand_range(start, end, step), detected.expand_range(start, end, step));
+        }
+
+        let values: Vec<i64> = (0..1000).map(|i| i / 7).collect();
+        assert_eq!(scalar.find_runs(&values), detected.find_runs(&values));
+    }
+
+    #[test]
+    fn test_check_forced_level() {
+        let features = CpuFeatures { avx512: false, avx2: true, neon: false };
+        let config = SimdConfig::default().with_forced_level(SimdLevel::Avx2);
+        assert!(SimdDispatcher::check_forced_level(&features, &config).is_ok());
+        assert!(SimdDispatcher::check_forced_level(&features, &SimdConfig::default()).is_ok());
+
+        let config = SimdConfig::default().with_forced_level(SimdLevel::Avx512);
+        let err = SimdDispatcher::check_forced_level(&features, &config).unwrap_err();
+        assert!(matches!(err, AlsError::UnsupportedSimdLevel { level: SimdLevel::Avx512 }));
+    }
+
+    #[test]
+    fn test_try_with_config_reports_unsupported_level() {
+        let features = CpuFeatures::detect();
+        for level in SimdLevel::ALL {
+            let config = SimdConfig::default().with_forced_level(level);
+            match SimdDispatcher::try_with_config(config) {
+                Ok(dispatcher) => {
+                    assert!(features.supports(level), "{}", level);
+                    assert_eq!(dispatcher.level(), level);
+                }
+                Err(AlsError::UnsupportedSimdLevel { level: reported }) => {
+                    assert!(!features.supports(level), "{}", level);
+                    assert_eq!(reported, level);
+                }
+                Err(other) => panic!("unexpected error: {}", other),
+            }
+        }
+    }
+
     #[test]
     fn test_expand_range_ascending() {
         let dispatcher = SimdDispatcher::scalar_only();
@@ -453,6 +647,29 @@ mod tests {
         assert!(!seqs.is_empty());
     }
 
+    #[test]
+    fn test_find_any_byte_matches_scalar() {
+        let needles = [b';', b'\'', b'\n', b'\r'];
+        let input: Vec<u8> = (0..200u8)
+            .map(|i| match i % 37 {
+                0 => b';',
+                19 => b'\n',
+                _ => b'a' + i % 26,
+            })
+            .collect();
+
+        let scalar = SimdDispatcher::scalar_only();
+        let detected = SimdDispatcher::detect();
+        for start in 0..input.len() {
+            assert_eq!(
+                detected.find_any_byte(&input[start..], needles),
+                scalar.find_any_byte(&input[start..], needles),
+                "Mismatch from offset {}",
+                start
+            );
+        }
+    }
+
     #[test]
     fn test_simd_level_display() {
         assert_eq!(format!("{}", SimdLevel::Avx512), "AVX-512");
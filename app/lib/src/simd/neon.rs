@@ -256,6 +256,43 @@ pub unsafe fn find_arithmetic_sequences_neon(values: &[i64]) -> Vec<(usize, usiz
     sequences
 }
 
+/// Find the first byte equal to any of `needles` using NEON.
+///
+/// Compares 16 bytes at a time against each needle. A chunk containing a
+/// match is rescanned with the scalar implementation to locate it.
+///
+/// # Safety
+///
+/// The caller must ensure this is called on an ARM64 platform.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to search
+/// * `needles` - The byte values to look for
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+pub unsafe fn find_any_byte_neon(bytes: &[u8], needles: [u8; 4]) -> Option<usize> {
+    let splats = needles.map(|needle| vdupq_n_u8(needle));
+    let ptr = bytes.as_ptr();
+
+    let mut offset = 0;
+    while offset + 16 <= bytes.len() {
+        let chunk = vld1q_u8(ptr.add(offset));
+        let matches = vorrq_u8(
+            vorrq_u8(vceqq_u8(chunk, splats[0]), vceqq_u8(chunk, splats[1])),
+            vorrq_u8(vceqq_u8(chunk, splats[2]), vceqq_u8(chunk, splats[3])),
+        );
+
+        if vmaxvq_u8(matches) != 0 {
+            return super::scalar::find_any_byte_scalar(&bytes[offset..offset + 16], needles)
+                .map(|i| offset + i);
+        }
+        offset += 16;
+    }
+
+    super::scalar::find_any_byte_scalar(&bytes[offset..], needles).map(|i| offset + i)
+}
+
 #[cfg(all(test, target_arch = "aarch64"))]
 mod tests {
     use super::*;
@@ -215,10 +215,27 @@ pub fn max_scalar(values: &[i64]) -> Option<i64> {
     values.iter().copied().max()
 }
 
+/// Find the first byte equal to any of `needles` (scalar implementation).
+///
+/// Returns the index of the first match, or None if no byte matches.
+pub fn find_any_byte_scalar(bytes: &[u8], needles: [u8; 4]) -> Option<usize> {
+    bytes.iter().position(|byte| needles.contains(byte))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_find_any_byte() {
+        let needles = [b',', b'"', b'\n', b'\r'];
+        assert_eq!(find_any_byte_scalar(b"abc,def", needles), Some(3));
+        assert_eq!(find_any_byte_scalar(b"abc\ndef", needles), Some(3));
+        assert_eq!(find_any_byte_scalar(b"\"", needles), Some(0));
+        assert_eq!(find_any_byte_scalar(b"abcdef", needles), None);
+        assert_eq!(find_any_byte_scalar(b"", needles), None);
+    }
+
     #[test]
     fn test_expand_range_ascending() {
         assert_eq!(expand_range_scalar(1, 5, 1), vec![1, 2, 3, 4, 5]);
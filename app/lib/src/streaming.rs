@@ -8,9 +8,17 @@
 //!
 //! The streaming implementation follows these principles:
 //!
-//! - **Independent chunks**: Each compressed chunk is a complete, independently
-//!   parseable ALS document. This allows chunks to be processed in parallel or
-//!   out of order if needed.
+//! - **Shared dictionary header**: CSV compression samples the first rows to
+//!   build a single dictionary, emits the dictionary and schema header once,
+//!   then emits one line of column streams per batch. Values that were not in
+//!   the sampled window stay raw. The concatenated chunks form one stream that
+//!   [`StreamingParser`] consumes batch by batch. Rows later appended to
+//!   the CSV source can be compressed with [`StreamingCompressor::append`]
+//!   and written after the existing batches.
+//!
+//! - **Pull-based JSON**: JSON arrays are read one object at a time. The
+//!   schema is discovered from the first objects, and keys seen later add
+//!   null-backfilled columns to the fragments that follow.
 //!
 //! - **Configurable chunk sizes**: Users can control the trade-off between memory
 //!   usage and compression efficiency by adjusting chunk sizes.
@@ -18,6 +26,10 @@
 //! - **Lazy evaluation**: Data is only read and processed as needed, allowing
 //!   processing of files larger than available RAM.
 //!
+//! - **Resumable parsing**: [`StreamingParser::checkpoint`] captures the byte
+//!   offset and header state, so a failed read can continue with
+//!   [`StreamingParser::resume_from`] instead of starting over.
+//!
 //! # Examples
 //!
 //! ## Streaming CSV Compression
@@ -51,13 +63,16 @@
 //! }
 //! ```
 
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::io::{BufRead, BufReader, Read};
+use std::ops::DerefMut;
 
-use crate::als::{AlsParser, AlsSerializer};
-use crate::compress::AlsCompressor;
-use crate::config::{CompressorConfig, ParserConfig};
-use crate::convert::{TabularData, Value};
-use crate::error::Result;
+use crate::als::{AlsDocument, AlsParser, AlsSerializer, SentinelTokens};
+use crate::compress::{AlsCompressor, CompressionBuffers};
+use crate::config::{CompressorConfig, JsonColumnOrder, ParserConfig};
+use crate::convert::json::{build_tabular_data_with_columns, FlatObject, JsonArrayReader};
+use crate::convert::{decimal_scale, is_verbatim_number, TabularData, Value};
+use crate::error::{AlsError, Result};
 
 /// Default buffer size for streaming operations (64 KB).
 const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
@@ -65,6 +80,9 @@ const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
 /// Default chunk size for CSV processing (number of rows per chunk).
 const DEFAULT_CSV_CHUNK_SIZE: usize = 1000;
 
+/// Default number of CSV rows or JSON objects sampled up front.
+const DEFAULT_DICTIONARY_SAMPLE_SIZE: usize = 1000;
+
 /// Default chunk size for JSON processing (number of objects per chunk).
 const DEFAULT_JSON_CHUNK_SIZE: usize = 1000;
 
@@ -94,6 +112,17 @@ pub struct StreamingCompressor<R: Read> {
     buffer_size: usize,
     csv_chunk_size: usize,
     json_chunk_size: usize,
+    dictionary_sample_size: usize,
+    /// Header and dictionary of the CSV stream emitted so far, if any.
+    stream: Option<CsvStreamState>,
+}
+
+/// The parts of an emitted CSV stream that later batches are encoded against.
+struct CsvStreamState {
+    /// The raw CSV header line, prepended to every batch before parsing.
+    header: String,
+    schema: Vec<String>,
+    dictionary: Vec<String>,
 }
 
 impl<R: Read> StreamingCompressor<R> {
@@ -109,6 +138,8 @@ impl<R: Read> StreamingCompressor<R> {
             buffer_size: DEFAULT_BUFFER_SIZE,
             csv_chunk_size: DEFAULT_CSV_CHUNK_SIZE,
             json_chunk_size: DEFAULT_JSON_CHUNK_SIZE,
+            dictionary_sample_size: DEFAULT_DICTIONARY_SAMPLE_SIZE,
+            stream: None,
         }
     }
 
@@ -125,6 +156,8 @@ impl<R: Read> StreamingCompressor<R> {
             buffer_size: DEFAULT_BUFFER_SIZE,
             csv_chunk_size: DEFAULT_CSV_CHUNK_SIZE,
             json_chunk_size: DEFAULT_JSON_CHUNK_SIZE,
+            dictionary_sample_size: DEFAULT_DICTIONARY_SAMPLE_SIZE,
+            stream: None,
         }
     }
 
@@ -146,55 +179,161 @@ impl<R: Read> StreamingCompressor<R> {
         self
     }
 
+    /// Set the number of leading CSV rows or JSON objects sampled up front.
+    ///
+    /// CSV streams build their shared dictionary from this window, and JSON
+    /// streams discover their schema from it.
+    pub fn with_dictionary_sample_size(mut self, size: usize) -> Self {
+        self.dictionary_sample_size = size;
+        self
+    }
+
     /// Compress CSV input in chunks, yielding ALS fragments.
     ///
-    /// This method reads CSV data in chunks, compresses each chunk to ALS format,
-    /// and yields the compressed fragments as strings. The first fragment includes
-    /// the schema header.
+    /// The first `dictionary_sample_size` rows are sampled to build one
+    /// dictionary for the whole stream. The first fragment holds the version,
+    /// dictionary and schema header followed by the first batch; every later
+    /// fragment is a single line of column streams for the next
+    /// `csv_chunk_size` rows. Each fragment ends with a newline, so the
+    /// fragments can be concatenated and read back with [`StreamingParser`].
     ///
     /// # Returns
     ///
     /// An iterator that yields `Result<String>` for each compressed chunk.
     pub fn compress_csv_chunks(&mut self) -> impl Iterator<Item = Result<String>> + '_ {
-        StreamingCsvCompressor {
-            compressor: self,
-            first_chunk: true,
-            schema: None,
-            buffer: String::new(),
-            finished: false,
+        StreamingCsvCompressor::new(self)
+    }
+
+    /// Compress CSV input in chunks, taking ownership of the compressor.
+    ///
+    /// Yields the same fragments as
+    /// [`compress_csv_chunks`](Self::compress_csv_chunks), for callers that
+    /// need an iterator that does not borrow the compressor, such as
+    /// language bindings.
+    pub fn into_csv_chunks(self) -> impl Iterator<Item = Result<String>> {
+        StreamingCsvCompressor::new(Box::new(self))
+    }
+
+    /// Compress rows appended to a CSV source since the stream was written.
+    ///
+    /// `new_rows` holds CSV records without a header line, in the dialect
+    /// of the original input. They are encoded against the dictionary
+    /// emitted by the last [`compress_csv_chunks`](Self::compress_csv_chunks)
+    /// call, so the result is only the column stream lines for the new rows,
+    /// one line per `csv_chunk_size` rows, ready to be appended to the
+    /// existing ALS stream. Values missing from that dictionary stay raw.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AlsError::CsvParseError` if no CSV stream has been
+    /// compressed yet or if `new_rows` is not valid CSV for the stream's
+    /// schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::StreamingCompressor;
+    ///
+    /// let mut compressor = StreamingCompressor::new("id,level\n1,info\n2,warn\n".as_bytes());
+    /// let mut als: String = compressor
+    ///     .compress_csv_chunks()
+    ///     .collect::<Result<Vec<_>, _>>()
+    ///     .unwrap()
+    ///     .concat();
+    ///
+    /// als.push_str(&compressor.append("3,info\n4,error\n").unwrap());
+    /// ```
+    pub fn append(&mut self, new_rows: &str) -> Result<String> {
+        let Some(stream) = self.stream.as_ref() else {
+            return Err(AlsError::CsvParseError {
+                line: 0,
+                column: 0,
+                message: "No CSV stream to append to; call compress_csv_chunks first".to_string(),
+            });
+        };
+
+        let als_compressor = AlsCompressor::with_config(self.config.clone());
+        let mut reader = new_rows.as_bytes();
+        let mut buffer = String::new();
+        let mut buffers = CompressionBuffers::new();
+        let mut output = String::new();
+        while let Some(data) = read_csv_batch(
+            &mut reader,
+            &mut VecDeque::new(),
+            &stream.header,
+            self.csv_chunk_size,
+            &self.config,
+            &mut buffer,
+        )? {
+            let doc = compress_csv_batch(&als_compressor, stream, &data, &mut buffers)?;
+            AlsSerializer::new().serialize_streams(&mut output, &doc);
+            if !output.ends_with('\n') {
+                output.push('\n');
+            }
         }
+
+        Ok(output)
     }
 
-    /// Compress JSON input in chunks, yielding ALS fragments.
+    /// Compress a JSON array in chunks, yielding ALS fragments.
+    ///
+    /// Objects are read from the array one at a time, so the array is never
+    /// held in memory as a whole. Every fragment is a complete ALS document
+    /// for the next `json_chunk_size` objects.
     ///
-    /// This method reads JSON array data in chunks, compresses each chunk to ALS
-    /// format, and yields the compressed fragments as strings. The first fragment
-    /// includes the schema header.
+    /// The schema is discovered from the first `dictionary_sample_size`
+    /// objects, with columns in the order set by
+    /// [`CompressorConfig::json_column_order`](crate::CompressorConfig::json_column_order).
+    /// Keys first seen after that
+    /// window are appended as new columns from the fragment they appear in
+    /// onwards, and every object missing a column holds null there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use als_compression::{AlsParser, StreamingCompressor};
+    ///
+    /// let json = r#"[{"id": 1}, {"id": 2}, {"id": 3, "tag": "new"}]"#;
+    /// let mut compressor = StreamingCompressor::new(json.as_bytes())
+    ///     .with_json_chunk_size(2)
+    ///     .with_dictionary_sample_size(1);
+    /// let chunks: Vec<String> = compressor
+    ///     .compress_json_chunks()
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(chunks.len(), 2);
+    /// let last = AlsParser::new().parse(&chunks[1]).unwrap();
+    /// assert_eq!(last.schema, vec!["id", "tag"]);
+    /// ```
     ///
     /// # Returns
     ///
     /// An iterator that yields `Result<String>` for each compressed chunk.
     pub fn compress_json_chunks(&mut self) -> impl Iterator<Item = Result<String>> + '_ {
-        StreamingJsonCompressor {
-            compressor: self,
-            first_chunk: true,
-            schema: None,
-            buffer: String::new(),
-            finished: false,
-        }
+        StreamingJsonCompressor::new(self)
     }
 }
 
 /// Iterator for streaming CSV compression.
-struct StreamingCsvCompressor<'a, R: Read> {
-    compressor: &'a mut StreamingCompressor<R>,
-    first_chunk: bool,
-    schema: Option<Vec<String>>,
+///
+/// Generic over how the compressor is held, so that it can either borrow
+/// or own it.
+struct StreamingCsvCompressor<C> {
+    compressor: C,
+    als_compressor: AlsCompressor,
+    /// Rows read while sampling that have not been compressed yet.
+    pending: VecDeque<String>,
+    header_emitted: bool,
     buffer: String,
+    /// Scratch space reused by every batch.
+    buffers: CompressionBuffers,
     finished: bool,
 }
 
-impl<'a, R: Read> Iterator for StreamingCsvCompressor<'a, R> {
+impl<R: Read, C: DerefMut<Target = StreamingCompressor<R>>> Iterator
+    for StreamingCsvCompressor<C>
+{
     type Item = Result<String>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -202,26 +341,8 @@ impl<'a, R: Read> Iterator for StreamingCsvCompressor<'a, R> {
             return None;
         }
 
-        // Read a chunk of CSV rows
-        match self.read_csv_chunk() {
-            Ok(Some(chunk_data)) => {
-                // Compress the chunk
-                let als_compressor = AlsCompressor::with_config(self.compressor.config.clone());
-                match als_compressor.compress(&chunk_data) {
-                    Ok(doc) => {
-                        // Capture schema from first chunk
-                        if self.first_chunk {
-                            self.schema = Some(doc.schema.clone());
-                            self.first_chunk = false;
-                        }
-                        
-                        // Each chunk is a complete, independently parseable ALS document
-                        let serializer = AlsSerializer::new();
-                        Some(Ok(serializer.serialize(&doc)))
-                    }
-                    Err(e) => Some(Err(e)),
-                }
-            }
+        match self.next_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk)),
             Ok(None) => {
                 self.finished = true;
                 None
@@ -234,65 +355,186 @@ impl<'a, R: Read> Iterator for StreamingCsvCompressor<'a, R> {
     }
 }
 
-impl<'a, R: Read> StreamingCsvCompressor<'a, R> {
-    /// Read a chunk of CSV rows from the input.
-    fn read_csv_chunk(&mut self) -> Result<Option<TabularData<'static>>> {
-        self.buffer.clear();
-        let mut lines_read = 0;
-        let mut header_line = String::new();
+impl<R: Read, C: DerefMut<Target = StreamingCompressor<R>>> StreamingCsvCompressor<C> {
+    /// Start a new CSV stream, discarding the state of any previous one.
+    fn new(mut compressor: C) -> Self {
+        compressor.stream = None;
+        Self {
+            als_compressor: AlsCompressor::with_config(compressor.config.clone()),
+            compressor,
+            pending: VecDeque::new(),
+            header_emitted: false,
+            buffer: String::new(),
+            buffers: CompressionBuffers::new(),
+            finished: false,
+        }
+    }
 
-        // Read header if this is the first chunk
-        if self.schema.is_none() {
-            if self.compressor.reader.read_line(&mut header_line)? == 0 {
-                return Ok(None); // Empty file
-            }
-            self.buffer.push_str(&header_line);
-            lines_read += 1;
+    /// Compress the next batch, prefixing the stream header on the first one.
+    fn next_chunk(&mut self) -> Result<Option<String>> {
+        if self.compressor.stream.is_none() && !self.start()? {
+            return Ok(None); // Empty file
+        }
+
+        let compressor = &mut *self.compressor;
+        let batch = read_csv_batch(
+            &mut compressor.reader,
+            &mut self.pending,
+            compressor.stream.as_ref().map_or("", |stream| &stream.header),
+            compressor.csv_chunk_size,
+            &compressor.config,
+            &mut self.buffer,
+        )?;
+        if batch.is_none() && self.header_emitted {
+            return Ok(None);
+        }
+
+        let Some(stream) = compressor.stream.as_ref() else {
+            return Ok(None);
+        };
+        let mut doc = match batch {
+            Some(data) => compress_csv_batch(&self.als_compressor, stream, &data, &mut self.buffers)?,
+            None => stream_document(&self.als_compressor, stream),
+        };
+
+        let serializer = AlsSerializer::new();
+        let mut chunk = if self.header_emitted {
+            let mut output = String::new();
+            serializer.serialize_streams(&mut output, &doc);
+            output
         } else {
-            // For subsequent chunks, reconstruct header from schema
-            if let Some(ref schema) = self.schema {
-                header_line = schema.join(",");
-                header_line.push('\n');
-                self.buffer.push_str(&header_line);
+            if !stream.dictionary.is_empty() {
+                doc.add_dictionary("default", stream.dictionary.clone());
             }
+            self.header_emitted = true;
+            serializer.serialize(&doc)
+        };
+        if !chunk.ends_with('\n') {
+            chunk.push('\n');
         }
 
-        // Read data rows
-        let mut line = String::new();
-        while lines_read < self.compressor.csv_chunk_size {
-            line.clear();
-            let bytes_read = self.compressor.reader.read_line(&mut line)?;
-            if bytes_read == 0 {
+        Ok(Some(chunk))
+    }
+
+    /// Read the CSV header and the sampling window, and build the dictionary.
+    ///
+    /// Returns `false` if the input is empty.
+    fn start(&mut self) -> Result<bool> {
+        let mut header_line = String::new();
+        if self.compressor.reader.read_line(&mut header_line)? == 0 {
+            return Ok(false);
+        }
+        if !header_line.ends_with('\n') {
+            header_line.push('\n');
+        }
+
+        self.buffer.clear();
+        self.buffer.push_str(&header_line);
+        while self.pending.len() < self.compressor.dictionary_sample_size {
+            let mut line = String::new();
+            if self.compressor.reader.read_line(&mut line)? == 0 {
                 break; // End of file
             }
             self.buffer.push_str(&line);
+            if !line.ends_with('\n') {
+                self.buffer.push('\n');
+            }
+            self.pending.push_back(line);
+        }
+
+        use crate::convert::csv::parse_csv_with_config;
+        let sample = parse_csv_with_config(&self.buffer, &self.compressor.config.csv_config)?;
+        self.compressor.stream = Some(CsvStreamState {
+            header: header_line,
+            schema: sample.column_names().into_iter().map(String::from).collect(),
+            dictionary: self.als_compressor.build_dictionary(&sample),
+        });
+
+        Ok(true)
+    }
+}
+
+/// Read the next batch of CSV rows, drawing on `pending` rows first.
+///
+/// `header` is prepended to the batch so that it parses with the stream's
+/// column names.
+fn read_csv_batch(
+    reader: &mut impl BufRead,
+    pending: &mut VecDeque<String>,
+    header: &str,
+    batch_size: usize,
+    config: &CompressorConfig,
+    buffer: &mut String,
+) -> Result<Option<TabularData<'static>>> {
+    use crate::convert::csv::parse_csv_with_config;
+
+    let batch_size = batch_size.max(1);
+    loop {
+        buffer.clear();
+        buffer.push_str(header);
+
+        let mut lines_read = 0;
+        let mut line = String::new();
+        while lines_read < batch_size {
+            if let Some(row) = pending.pop_front() {
+                line = row;
+            } else {
+                line.clear();
+                if reader.read_line(&mut line)? == 0 {
+                    break; // End of file
+                }
+            }
+            buffer.push_str(&line);
+            if !line.ends_with('\n') {
+                buffer.push('\n');
+            }
             lines_read += 1;
         }
 
-        // If we only read the header and no data, we're done
-        if lines_read <= 1 && self.schema.is_some() {
+        if lines_read == 0 {
             return Ok(None);
         }
 
-        // Parse the CSV chunk
-        use crate::convert::csv::parse_csv;
-        let data = parse_csv(&self.buffer)?;
-        
-        // Capture schema from first chunk
-        if self.schema.is_none() {
-            self.schema = Some(data.column_names().into_iter().map(String::from).collect());
+        let data = parse_csv_with_config(buffer, &config.csv_config)?;
+        if data.row_count > 0 {
+            return Ok(Some(data));
         }
+    }
+}
 
-        Ok(Some(data))
+/// Create an empty document with the stream's schema and the compressor's
+/// sentinel tokens.
+fn stream_document(compressor: &AlsCompressor, stream: &CsvStreamState) -> AlsDocument {
+    let mut doc = AlsDocument::with_schema(stream.schema.clone());
+    doc.sentinel_tokens = compressor.config().sentinel_tokens.clone();
+    doc
+}
+
+/// Compress one batch of CSV rows against the stream's dictionary.
+fn compress_csv_batch(
+    compressor: &AlsCompressor,
+    stream: &CsvStreamState,
+    data: &TabularData,
+    buffers: &mut CompressionBuffers,
+) -> Result<AlsDocument> {
+    let mut doc = stream_document(compressor, stream);
+    for column in compressor.compress_batch(data, &stream.dictionary, buffers)? {
+        doc.add_stream(column);
     }
+    Ok(doc)
 }
 
 /// Iterator for streaming JSON compression.
 struct StreamingJsonCompressor<'a, R: Read> {
     compressor: &'a mut StreamingCompressor<R>,
-    first_chunk: bool,
-    schema: Option<Vec<String>>,
-    buffer: String,
+    als_compressor: AlsCompressor,
+    array: JsonArrayReader,
+    /// Objects read while sampling that have not been compressed yet.
+    pending: VecDeque<FlatObject>,
+    /// Columns discovered so far, in the order they are emitted.
+    columns: Vec<String>,
+    known_columns: HashSet<String>,
+    started: bool,
     finished: bool,
 }
 
@@ -304,26 +546,8 @@ impl<'a, R: Read> Iterator for StreamingJsonCompressor<'a, R> {
             return None;
         }
 
-        // Read a chunk of JSON objects
-        match self.read_json_chunk() {
-            Ok(Some(chunk_data)) => {
-                // Compress the chunk
-                let als_compressor = AlsCompressor::with_config(self.compressor.config.clone());
-                match als_compressor.compress(&chunk_data) {
-                    Ok(doc) => {
-                        // Capture schema from first chunk
-                        if self.first_chunk {
-                            self.schema = Some(doc.schema.clone());
-                            self.first_chunk = false;
-                        }
-                        
-                        // Each chunk is a complete, independently parseable ALS document
-                        let serializer = AlsSerializer::new();
-                        Some(Ok(serializer.serialize(&doc)))
-                    }
-                    Err(e) => Some(Err(e)),
-                }
-            }
+        match self.next_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk)),
             Ok(None) => {
                 self.finished = true;
                 None
@@ -337,27 +561,83 @@ impl<'a, R: Read> Iterator for StreamingJsonCompressor<'a, R> {
 }
 
 impl<'a, R: Read> StreamingJsonCompressor<'a, R> {
-    /// Read a chunk of JSON objects from the input.
-    fn read_json_chunk(&mut self) -> Result<Option<TabularData<'static>>> {
-        // Read the entire JSON array into memory
-        // Note: True streaming JSON parsing would require a more sophisticated approach
-        // For now, we read chunks of objects from a JSON array
-        
-        self.buffer.clear();
-        self.compressor.reader.read_to_string(&mut self.buffer)?;
-        
-        if self.buffer.trim().is_empty() {
+    fn new(compressor: &'a mut StreamingCompressor<R>) -> Self {
+        Self {
+            als_compressor: AlsCompressor::with_config(compressor.config.clone()),
+            array: JsonArrayReader::new(compressor.config.case_insensitive_keys),
+            compressor,
+            pending: VecDeque::new(),
+            columns: Vec::new(),
+            known_columns: HashSet::new(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Compress the next batch of objects into a complete ALS document.
+    fn next_chunk(&mut self) -> Result<Option<String>> {
+        if !self.started {
+            self.start()?;
+        }
+
+        let batch_size = self.compressor.json_chunk_size.max(1);
+        let mut rows = Vec::new();
+        while rows.len() < batch_size {
+            let row = match self.pending.pop_front() {
+                Some(row) => row,
+                None => match self.array.next_object(&mut self.compressor.reader)? {
+                    Some(row) => row,
+                    None => break,
+                },
+            };
+            self.add_columns(&row);
+            rows.push(row);
+        }
+        if rows.is_empty() {
             return Ok(None);
         }
 
-        // Parse the JSON
-        use crate::convert::json::parse_json;
-        let data = parse_json(&self.buffer)?;
-        
-        // Mark as finished since we read everything
-        self.finished = true;
-        
-        Ok(Some(data))
+        let data = build_tabular_data_with_columns(&rows, &self.columns);
+        let doc = self.als_compressor.compress(&data)?;
+        Ok(Some(AlsSerializer::new().serialize(&doc)))
+    }
+
+    /// Read the sampling window and discover the schema from it.
+    fn start(&mut self) -> Result<()> {
+        self.started = true;
+        while self.pending.len() < self.compressor.dictionary_sample_size {
+            match self.array.next_object(&mut self.compressor.reader)? {
+                Some(row) => self.pending.push_back(row),
+                None => break,
+            }
+        }
+
+        let mut columns = Vec::new();
+        for key in self.pending.iter().flat_map(|row| row.keys()) {
+            if self.known_columns.insert(key.clone()) {
+                columns.push(key.clone());
+            }
+        }
+        if self.compressor.config.json_column_order == JsonColumnOrder::Sorted {
+            columns.sort();
+        }
+        self.columns = columns;
+        Ok(())
+    }
+
+    /// Append keys of `row` that no earlier object had as new columns.
+    fn add_columns(&mut self, row: &FlatObject) {
+        let mut new_columns: Vec<&String> = row
+            .keys()
+            .filter(|key| !self.known_columns.contains(*key))
+            .collect();
+        if self.compressor.config.json_column_order == JsonColumnOrder::Sorted {
+            new_columns.sort();
+        }
+        for column in new_columns {
+            self.known_columns.insert(column.clone());
+            self.columns.push(column.clone());
+        }
     }
 }
 
@@ -385,6 +665,56 @@ pub struct StreamingParser<R: Read> {
     reader: BufReader<R>,
     config: ParserConfig,
     buffer_size: usize,
+    /// Decode state, kept on the parser so that parsing can be checkpointed.
+    cursor: ParserCursor,
+}
+
+/// Position and decode state of a [`StreamingParser`].
+#[derive(Debug, Default)]
+struct ParserCursor {
+    /// Bytes consumed from the reader, including any checkpointed offset.
+    offset: u64,
+    /// Offset at which the batch in `rows` starts.
+    batch_start: u64,
+    /// Version and schema lines prepended to every batch before parsing.
+    prefix: Option<String>,
+    dictionaries: HashMap<String, Vec<String>>,
+    column_count: usize,
+    /// Null and empty tokens of the document, taken from each parsed batch.
+    sentinel_tokens: SentinelTokens,
+    /// First stream line, read while looking for the end of the header.
+    lookahead: Option<String>,
+    rows: Vec<Vec<String>>,
+    row_index: usize,
+    /// Rows of the first batch already yielded before a checkpoint.
+    skip_rows: usize,
+    finished: bool,
+}
+
+/// A resumable position in an ALS stream read by [`StreamingParser`].
+///
+/// Holds the byte offset of the batch being decoded, the number of its rows
+/// already yielded, and the header state (schema and dictionaries) needed to
+/// decode the remaining batches. With the `serde` feature enabled the
+/// checkpoint can be serialized and stored alongside a partial download.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ParserCheckpoint {
+    offset: u64,
+    rows_to_skip: usize,
+    prefix: Option<String>,
+    dictionaries: HashMap<String, Vec<String>>,
+    column_count: usize,
+}
+
+impl ParserCheckpoint {
+    /// Byte offset in the ALS input at which parsing resumes.
+    ///
+    /// The reader passed to [`StreamingParser::resume_from`] must yield the
+    /// input starting at this offset.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
 }
 
 impl<R: Read> StreamingParser<R> {
@@ -394,11 +724,7 @@ impl<R: Read> StreamingParser<R> {
     ///
     /// * `reader` - The input reader to stream from
     pub fn new(reader: R) -> Self {
-        Self {
-            reader: BufReader::with_capacity(DEFAULT_BUFFER_SIZE, reader),
-            config: ParserConfig::default(),
-            buffer_size: DEFAULT_BUFFER_SIZE,
-        }
+        Self::with_config(reader, ParserConfig::default())
     }
 
     /// Create a new streaming parser with custom configuration.
@@ -412,72 +738,269 @@ impl<R: Read> StreamingParser<R> {
             reader: BufReader::with_capacity(DEFAULT_BUFFER_SIZE, reader),
             config,
             buffer_size: DEFAULT_BUFFER_SIZE,
+            cursor: ParserCursor::default(),
         }
     }
 
+    /// Resume parsing from a checkpoint taken with [`checkpoint`](Self::checkpoint).
+    ///
+    /// The resumed parser uses the default configuration; see
+    /// [`resume_from_with_config`](Self::resume_from_with_config).
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The input reader, positioned at [`ParserCheckpoint::offset`]
+    /// * `checkpoint` - The checkpoint to resume from
+    pub fn resume_from(reader: R, checkpoint: ParserCheckpoint) -> Self {
+        Self::resume_from_with_config(reader, checkpoint, ParserConfig::default())
+    }
+
+    /// Resume parsing from a checkpoint with custom configuration.
+    ///
+    /// The checkpoint does not record the configuration, so pass the one the
+    /// checkpointed parser was created with to parse the rest alike.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - The input reader, positioned at [`ParserCheckpoint::offset`]
+    /// * `checkpoint` - The checkpoint to resume from
+    /// * `config` - Parser configuration
+    pub fn resume_from_with_config(
+        reader: R,
+        checkpoint: ParserCheckpoint,
+        config: ParserConfig,
+    ) -> Self {
+        let mut parser = Self::with_config(reader, config);
+        parser.cursor = ParserCursor {
+            offset: checkpoint.offset,
+            batch_start: checkpoint.offset,
+            prefix: checkpoint.prefix,
+            dictionaries: checkpoint.dictionaries,
+            column_count: checkpoint.column_count,
+            skip_rows: checkpoint.rows_to_skip,
+            ..ParserCursor::default()
+        };
+        parser
+    }
+
     /// Set the buffer size for reading.
     pub fn with_buffer_size(mut self, size: usize) -> Self {
         self.buffer_size = size;
         self
     }
 
+    /// Capture the current parse position.
+    ///
+    /// Rows yielded so far by [`parse_rows`](Self::parse_rows) are not
+    /// yielded again after [`resume_from`](Self::resume_from). A checkpoint
+    /// taken inside a batch points at the start of that batch and records
+    /// how many of its rows to skip.
+    pub fn checkpoint(&self) -> ParserCheckpoint {
+        let cursor = &self.cursor;
+        let (offset, rows_to_skip) = if cursor.row_index < cursor.rows.len() {
+            (cursor.batch_start, cursor.row_index)
+        } else {
+            let lookahead = cursor.lookahead.as_ref().map_or(0, |line| line.len() as u64);
+            (cursor.offset - lookahead, cursor.skip_rows)
+        };
+        ParserCheckpoint {
+            offset,
+            rows_to_skip,
+            prefix: cursor.prefix.clone(),
+            dictionaries: cursor.dictionaries.clone(),
+            column_count: cursor.column_count,
+        }
+    }
+
     /// Parse ALS input in streaming fashion, yielding rows incrementally.
     ///
-    /// This method reads ALS format data, parses it, and yields rows one at a time.
-    /// The schema is parsed first, then data rows are yielded as they are expanded.
+    /// The version, dictionary and schema header is read first. Column streams
+    /// are then read one batch at a time, as written by
+    /// [`StreamingCompressor::compress_csv_chunks`], and each batch is expanded
+    /// against the header dictionary before its rows are yielded. A plain ALS
+    /// document is a stream with a single batch.
+    ///
+    /// Dropping the iterator keeps the parse position, so a later call
+    /// continues with the next row.
     ///
     /// # Returns
     ///
     /// An iterator that yields `Result<Vec<Value>>` for each row.
     pub fn parse_rows(&mut self) -> impl Iterator<Item = Result<Vec<Value<'static>>>> + '_ {
-        StreamingRowParser {
-            parser: self,
-            schema: None,
-            rows: Vec::new(),
-            row_index: 0,
-            finished: false,
+        StreamingRowParser { parser: self }
+    }
+
+    /// Parse ALS input row by row, taking ownership of the parser.
+    ///
+    /// Yields the same rows as [`parse_rows`](Self::parse_rows), for callers
+    /// that need an iterator that does not borrow the parser, such as
+    /// language bindings.
+    pub fn into_rows(self) -> impl Iterator<Item = Result<Vec<Value<'static>>>> {
+        StreamingRowParser { parser: Box::new(self) }
+    }
+
+    /// Read a line, advancing the cursor offset.
+    fn read_line(&mut self, line: &mut String) -> Result<usize> {
+        let read = self.reader.read_line(line)?;
+        self.cursor.offset += read as u64;
+        Ok(read)
+    }
+
+    /// Read and parse the next batch of ALS data.
+    fn read_and_parse(&mut self) -> Result<bool> {
+        if self.cursor.prefix.is_none() && !self.read_header()? {
+            return Ok(false); // No more data
+        }
+
+        let als_parser = AlsParser::with_config(self.config.clone());
+        loop {
+            let batch_start = self.cursor.offset
+                - self.cursor.lookahead.as_ref().map_or(0, |line| line.len() as u64);
+            let Some(batch) = self.read_batch()? else {
+                break;
+            };
+            let mut input = self.cursor.prefix.clone().unwrap_or_default();
+            input.push_str(&batch);
+            let mut doc = als_parser.parse(&input)?;
+
+            // Expand against the header dictionaries without cloning them
+            doc.dictionaries = std::mem::take(&mut self.cursor.dictionaries);
+            let rows = als_parser.expand(&doc);
+            self.cursor.dictionaries = std::mem::take(&mut doc.dictionaries);
+
+            self.cursor.rows = rows?;
+            self.cursor.sentinel_tokens = doc.sentinel_tokens;
+            self.cursor.batch_start = batch_start;
+            self.cursor.row_index = std::mem::take(&mut self.cursor.skip_rows);
+            if self.cursor.row_index < self.cursor.rows.len() {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Read the version, dictionary and schema header.
+    ///
+    /// Returns `false` if the input is empty.
+    fn read_header(&mut self) -> Result<bool> {
+        let mut header = String::new();
+        let mut line = String::new();
+        let mut directives = true;
+        loop {
+            line.clear();
+            if self.read_line(&mut line)? == 0 {
+                break;
+            }
+            let trimmed = line.trim_start();
+            if trimmed.is_empty() {
+                continue;
+            }
+            // Version and directive lines precede dictionaries and schema
+            let is_directive = trimmed.starts_with('!') && directives;
+            directives &= is_directive;
+            let is_header = trimmed.starts_with('$') || trimmed.starts_with('#') || is_directive;
+            if !is_header {
+                self.cursor.lookahead = Some(line.clone());
+                break;
+            }
+            header.push_str(&line);
+            if !line.ends_with('\n') {
+                header.push('\n');
+            }
+        }
+
+        if header.is_empty() && self.cursor.lookahead.is_none() {
+            return Ok(false);
         }
+
+        let als_parser = AlsParser::with_config(self.config.clone());
+        let (mut doc, _) = als_parser.parse_lenient(&header)?;
+        self.cursor.column_count = doc.schema.len();
+        self.cursor.dictionaries = std::mem::take(&mut doc.dictionaries);
+        doc.streams.clear();
+        self.cursor.prefix = Some(AlsSerializer::new().serialize(&doc));
+
+        Ok(true)
+    }
+
+    /// Read the column streams of the next batch.
+    ///
+    /// A batch ends once it holds one unescaped column separator fewer than
+    /// the schema has columns, or at the end of the input.
+    fn read_batch(&mut self) -> Result<Option<String>> {
+        let mut batch = String::new();
+        let mut separators = 0;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if let Some(lookahead) = self.cursor.lookahead.take() {
+                line = lookahead;
+            } else if self.read_line(&mut line)? == 0 {
+                break; // End of file
+            }
+            if batch.is_empty() && line.trim().is_empty() {
+                continue;
+            }
+
+            separators += count_separators(&line);
+            batch.push_str(&line);
+            if separators + 1 >= self.cursor.column_count {
+                break;
+            }
+        }
+
+        Ok((!batch.is_empty()).then_some(batch))
     }
 }
 
 /// Iterator for streaming row parsing.
-struct StreamingRowParser<'a, R: Read> {
-    parser: &'a mut StreamingParser<R>,
-    schema: Option<Vec<String>>,
-    rows: Vec<Vec<String>>,
-    row_index: usize,
-    finished: bool,
+///
+/// Generic over how the parser is held, so that it can either borrow or
+/// own it. All decode state lives on the parser itself.
+struct StreamingRowParser<P> {
+    parser: P,
 }
 
-impl<'a, R: Read> Iterator for StreamingRowParser<'a, R> {
+impl<R: Read, P: DerefMut<Target = StreamingParser<R>>> Iterator for StreamingRowParser<P> {
     type Item = Result<Vec<Value<'static>>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.finished {
+        let cursor = &mut self.parser.cursor;
+        if cursor.finished {
             return None;
         }
 
         // If we have rows buffered, return the next one
-        if self.row_index < self.rows.len() {
-            let row = &self.rows[self.row_index];
-            self.row_index += 1;
+        if cursor.row_index < cursor.rows.len() {
+            let row = &cursor.rows[cursor.row_index];
+            cursor.row_index += 1;
             
             // Convert string row to Value row
+            let tokens = &cursor.sentinel_tokens;
             let value_row: Vec<Value<'static>> = row
                 .iter()
                 .map(|s| {
                     use std::borrow::Cow;
                     // Try to parse as different types
-                    if s == crate::als::NULL_TOKEN {
-                        Value::Null
-                    } else if s == crate::als::EMPTY_TOKEN {
-                        Value::String(Cow::Owned(String::new()))
-                    } else if let Ok(i) = s.parse::<i64>() {
-                        Value::Integer(i)
-                    } else if let Ok(f) = s.parse::<f64>() {
-                        Value::Float(f)
-                    } else {
-                        Value::String(Cow::Owned(s.clone()))
+                    match tokens.decode_str(s.clone()) {
+                        None => Value::Null,
+                        Some(s) if s.is_empty() => Value::String(Cow::Owned(s)),
+                        Some(s) => {
+                            // Keep the text of numbers like `1e-9` or `78.60`
+                            // that formatting their value would change
+                            let fixed_decimal = decimal_scale(&s) >= Some(2)
+                                && s.parse::<f64>().is_ok_and(|f| f.to_string() != s);
+                            if is_verbatim_number(&s) || fixed_decimal {
+                                Value::Decimal(s)
+                            } else if let Ok(i) = s.parse::<i64>() {
+                                Value::Integer(i)
+                            } else if let Ok(f) = s.parse::<f64>() {
+                                Value::Float(f)
+                            } else {
+                                Value::String(Cow::Owned(s))
+                            }
+                        }
                     }
                 })
                 .collect();
@@ -486,50 +1009,38 @@ impl<'a, R: Read> Iterator for StreamingRowParser<'a, R> {
         }
 
         // Need to read more data
-        match self.read_and_parse() {
+        match self.parser.read_and_parse() {
             Ok(true) => {
                 // Successfully read more rows, try again
                 self.next()
             }
             Ok(false) => {
                 // No more data
-                self.finished = true;
+                self.parser.cursor.finished = true;
                 None
             }
             Err(e) => {
-                self.finished = true;
+                self.parser.cursor.finished = true;
                 Some(Err(e))
             }
         }
     }
 }
 
-impl<'a, R: Read> StreamingRowParser<'a, R> {
-    /// Read and parse the next chunk of ALS data.
-    fn read_and_parse(&mut self) -> Result<bool> {
-        // Read all remaining input
-        // Note: True streaming would parse incrementally, but ALS format
-        // requires knowing the full structure (schema + all streams)
-        let mut buffer = String::new();
-        let bytes_read = self.parser.reader.read_to_string(&mut buffer)?;
-        
-        if bytes_read == 0 {
-            return Ok(false); // No more data
+/// Count the unescaped column separators in a line of ALS streams.
+fn count_separators(line: &str) -> usize {
+    let mut count = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '|' => count += 1,
+            _ => {}
         }
-
-        // Parse the ALS document
-        let als_parser = AlsParser::with_config(self.parser.config.clone());
-        let doc = als_parser.parse(&buffer)?;
-        
-        // Capture schema
-        self.schema = Some(doc.schema.clone());
-        
-        // Expand to rows
-        self.rows = als_parser.expand(&doc)?;
-        self.row_index = 0;
-        
-        Ok(!self.rows.is_empty())
     }
+    count
 }
 
 #[cfg(test)]
@@ -585,6 +1096,19 @@ mod tests {
         assert_eq!(rows[0].len(), 2); // Two columns
     }
 
+    #[test]
+    fn test_streaming_parser_sentinel_directives() {
+        let als_data = "!v1\n!null:NULL\n!empty:EMPTY\n#id #name\n1>4|NULL EMPTY \\\\0 \\\\NULL";
+        let mut parser = StreamingParser::new(Cursor::new(als_data.as_bytes()));
+
+        let rows: Vec<Vec<Value>> = parser.parse_rows().collect::<Result<_>>().unwrap();
+        let names: Vec<&Value> = rows.iter().map(|row| &row[1]).collect();
+        assert_eq!(
+            names,
+            vec![&Value::Null, &Value::string(""), &Value::string("\\0"), &Value::string("NULL")]
+        );
+    }
+
     #[test]
     fn test_streaming_parser_empty() {
         let als_data = "";
@@ -655,4 +1179,398 @@ mod tests {
         
         assert_eq!(rows.len(), 3);
     }
+
+    #[test]
+    fn test_streaming_compressor_emits_header_once() {
+        let csv_data = "id,status\n1,ok\n2,ok\n3,ok\n4,ok\n5,ok\n";
+        let cursor = Cursor::new(csv_data.as_bytes());
+
+        let mut compressor = StreamingCompressor::new(cursor)
+            .with_csv_chunk_size(2);
+
+        let chunks: Vec<_> = compressor.compress_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].starts_with("!v1\n"));
+        assert!(chunks[0].contains("#id #status"));
+        for chunk in &chunks[1..] {
+            assert!(!chunk.contains('#'));
+            assert!(!chunk.contains('$'));
+            assert_eq!(chunk.lines().count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_streaming_compressor_header_only_csv() {
+        let csv_data = "id,name\n";
+        let cursor = Cursor::new(csv_data.as_bytes());
+
+        let mut compressor = StreamingCompressor::new(cursor);
+
+        let chunks: Vec<_> = compressor.compress_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].contains("#id #name"));
+
+        let mut parser = StreamingParser::new(Cursor::new(chunks[0].as_bytes()));
+        assert_eq!(parser.parse_rows().count(), 0);
+    }
+
+    #[test]
+    fn test_streaming_parser_multiple_batches() {
+        let als_data = "!v1\n$default:red|blue\n#id #color\n1>2|_0 _1\n3>4|_1 green\n";
+        let cursor = Cursor::new(als_data.as_bytes());
+
+        let mut parser = StreamingParser::new(cursor);
+
+        let rows: Vec<_> = parser.parse_rows().collect::<Result<Vec<_>>>().unwrap();
+
+        assert_eq!(rows.len(), 4);
+        let colors: Vec<String> = rows.iter().map(|r| r[1].to_string_repr().into_owned()).collect();
+        assert_eq!(colors, vec!["red", "blue", "blue", "green"]);
+    }
+
+    fn parse_all_rows(als: &str) -> Vec<Vec<String>> {
+        let mut parser = StreamingParser::new(Cursor::new(als.as_bytes()));
+        parser
+            .parse_rows()
+            .map(|row| {
+                row.unwrap()
+                    .iter()
+                    .map(|value| value.to_string_repr().into_owned())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_streaming_compressor_append_batches() {
+        let csv_data = "id,level\n1,info\n2,warn\n3,info\n4,info\n";
+        let mut compressor = StreamingCompressor::new(Cursor::new(csv_data.as_bytes()))
+            .with_csv_chunk_size(2);
+        let mut als: String = compressor
+            .compress_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .concat();
+
+        let first = compressor.append("5,warn\n6,info\n7,info\n").unwrap();
+        let second = compressor.append("8,debug\n9,info\n").unwrap();
+
+        // Appends hold only stream lines, one per batch of two rows
+        assert!(!first.contains('#') && !first.contains('$'));
+        assert_eq!(first.lines().count(), 2);
+        assert_eq!(second.lines().count(), 1);
+
+        als.push_str(&first);
+        als.push_str(&second);
+
+        let rows = parse_all_rows(&als);
+        let expected: Vec<Vec<String>> = [
+            ("1", "info"),
+            ("2", "warn"),
+            ("3", "info"),
+            ("4", "info"),
+            ("5", "warn"),
+            ("6", "info"),
+            ("7", "info"),
+            ("8", "debug"),
+            ("9", "info"),
+        ]
+        .iter()
+        .map(|(id, level)| vec![id.to_string(), level.to_string()])
+        .collect();
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn test_streaming_compressor_append_reuses_dictionary() {
+        let csv_data = "host,status\nweb-01,ok\nweb-01,ok\nweb-02,ok\n";
+        let mut compressor = StreamingCompressor::new(Cursor::new(csv_data.as_bytes()));
+        let header = compressor
+            .compress_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .concat();
+        assert!(header.contains("$default:"));
+
+        // Known values become references, the new host stays raw
+        let appended = compressor.append("web-01,ok\nweb-03,ok\n").unwrap();
+        assert!(appended.contains('_'), "{}", appended);
+        assert!(appended.contains("web-03"), "{}", appended);
+        assert!(!appended.contains("web-01"), "{}", appended);
+
+        let rows = parse_all_rows(&(header + &appended));
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[3], vec!["web-01", "ok"]);
+        assert_eq!(rows[4], vec!["web-03", "ok"]);
+    }
+
+    #[test]
+    fn test_streaming_compressor_append_requires_stream() {
+        let mut compressor = StreamingCompressor::new(Cursor::new(b"".as_slice()));
+        assert!(matches!(
+            compressor.append("1,a\n"),
+            Err(AlsError::CsvParseError { .. })
+        ));
+
+        // An empty source never starts a stream either
+        assert_eq!(compressor.compress_csv_chunks().count(), 0);
+        assert!(compressor.append("1,a\n").is_err());
+    }
+
+    #[test]
+    fn test_streaming_compressor_append_nothing() {
+        let mut compressor = StreamingCompressor::new(Cursor::new(b"id\n1\n".as_slice()));
+        compressor.compress_csv_chunks().for_each(drop);
+        assert_eq!(compressor.append("").unwrap(), "");
+    }
+
+    #[test]
+    fn test_owned_iterators_match_borrowed() {
+        let csv_data = "id,status\n1,ok\n2,ok\n3,fail\n4,ok\n5,ok\n";
+
+        let mut compressor = StreamingCompressor::new(Cursor::new(csv_data.as_bytes()))
+            .with_csv_chunk_size(2);
+        let borrowed: Vec<String> = compressor
+            .compress_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let owned: Vec<String> = StreamingCompressor::new(Cursor::new(csv_data.as_bytes()))
+            .with_csv_chunk_size(2)
+            .into_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(owned, borrowed);
+
+        let als = owned.concat();
+        let rows: Vec<_> = StreamingParser::new(Cursor::new(als.into_bytes()))
+            .into_rows()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[2][1].to_string_repr(), "fail");
+    }
+
+    #[test]
+    fn test_streaming_parser_checkpoint_resume() {
+        let csv_data = "id,status\n1,ok\n2,ok\n3,fail\n4,ok\n5,ok\n6,retry\n7,ok\n";
+        let als = StreamingCompressor::new(Cursor::new(csv_data.as_bytes()))
+            .with_csv_chunk_size(3)
+            .into_csv_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+            .concat();
+        let expected = parse_all_rows(&als);
+        assert_eq!(expected.len(), 7);
+
+        // Checkpoint at every row, including mid-batch and before the header
+        for split in 0..=expected.len() {
+            let mut parser = StreamingParser::new(Cursor::new(als.as_bytes()));
+            let mut rows: Vec<Vec<String>> = parser
+                .parse_rows()
+                .take(split)
+                .map(|row| row.unwrap().iter().map(|v| v.to_string_repr().into_owned()).collect())
+                .collect();
+            let checkpoint = parser.checkpoint();
+
+            let rest = &als.as_bytes()[checkpoint.offset() as usize..];
+            let mut resumed = StreamingParser::resume_from(Cursor::new(rest), checkpoint);
+            rows.extend(
+                resumed
+                    .parse_rows()
+                    .map(|row| row.unwrap().iter().map(|v| v.to_string_repr().into_owned()).collect()),
+            );
+            assert_eq!(rows, expected, "split at {}", split);
+        }
+    }
+
+    #[test]
+    fn test_streaming_parser_checkpoint_after_end() {
+        let als = "!v1\n$default:red|blue\n#id #color\n1>2|_0 _1\n";
+        let mut parser = StreamingParser::new(Cursor::new(als.as_bytes()));
+        assert_eq!(parser.parse_rows().count(), 2);
+
+        let checkpoint = parser.checkpoint();
+        assert_eq!(checkpoint.offset(), als.len() as u64);
+        let mut resumed = StreamingParser::resume_from(Cursor::new(&b""[..]), checkpoint);
+        assert_eq!(resumed.parse_rows().count(), 0);
+    }
+
+    #[test]
+    fn test_streaming_parser_resume_keeps_config() {
+        let als = "#id\n1>3\n4>10\n";
+        let config = ParserConfig::new().with_max_range_expansion(5);
+        let mut parser = StreamingParser::with_config(Cursor::new(als.as_bytes()), config.clone());
+        assert_eq!(parser.parse_rows().take(3).count(), 3);
+        let checkpoint = parser.checkpoint();
+
+        let rest = &als.as_bytes()[checkpoint.offset() as usize..];
+        let mut resumed =
+            StreamingParser::resume_from_with_config(Cursor::new(rest), checkpoint.clone(), config);
+        assert!(resumed.parse_rows().any(|row| row.is_err()));
+        let mut unlimited = StreamingParser::resume_from(Cursor::new(rest), checkpoint);
+        assert_eq!(unlimited.parse_rows().count(), 7);
+    }
+
+    #[test]
+    fn test_streaming_parser_keeps_decimal_text() {
+        let als = "#price\n78.60 19.99 1e-9 0.5 2.10\n";
+        let rows: Vec<_> = StreamingParser::new(Cursor::new(als.as_bytes()))
+            .into_rows()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let prices: Vec<_> = rows.into_iter().map(|row| row[0].clone()).collect();
+        assert_eq!(
+            prices,
+            vec![
+                Value::Decimal("78.60".to_string()),
+                Value::Float(19.99),
+                Value::Decimal("1e-9".to_string()),
+                Value::Float(0.5),
+                Value::Decimal("2.10".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streaming_parser_keeps_position_between_iterators() {
+        let als = "#id\n1>3\n4>6\n";
+        let mut parser = StreamingParser::new(Cursor::new(als.as_bytes()));
+        assert_eq!(parser.parse_rows().take(2).count(), 2);
+        let rest: Vec<_> = parser.parse_rows().collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(rest.len(), 4);
+        assert_eq!(rest[0][0], Value::Integer(3));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_parser_checkpoint_serde_roundtrip() {
+        let als = "!v1\n$default:red|blue\n#id #color\n1>2|_0 _1\n3>4|_1 green\n";
+        let mut parser = StreamingParser::new(Cursor::new(als.as_bytes()));
+        assert_eq!(parser.parse_rows().take(3).count(), 3);
+        let checkpoint = parser.checkpoint();
+
+        let json = serde_json::to_string(&checkpoint).unwrap();
+        let restored: ParserCheckpoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, checkpoint);
+
+        let rest = &als.as_bytes()[restored.offset() as usize..];
+        let rows: Vec<_> = StreamingParser::resume_from(Cursor::new(rest), restored)
+            .into_rows()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0][1].to_string_repr(), "green");
+    }
+
+    fn compress_json_stream(json: &str, chunk_size: usize, sample_size: usize) -> Vec<String> {
+        StreamingCompressor::new(Cursor::new(json.as_bytes()))
+            .with_json_chunk_size(chunk_size)
+            .with_dictionary_sample_size(sample_size)
+            .compress_json_chunks()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+    }
+
+    /// Expand every chunk and map each column name to its values across chunks.
+    fn expand_json_chunks(chunks: &[String], rows: usize) -> HashMap<String, Vec<String>> {
+        let parser = AlsParser::new();
+        let mut columns: HashMap<String, Vec<String>> = HashMap::new();
+        let mut seen = 0;
+        for chunk in chunks {
+            let doc = parser.parse(chunk).unwrap();
+            let data = parser.expand_typed(&doc).unwrap();
+            for column in &data.columns {
+                let values = columns
+                    .entry(column.name.to_string())
+                    .or_insert_with(|| vec![crate::als::NULL_TOKEN.to_string(); seen]);
+                values.extend(column.values.iter().map(|v| v.to_string_repr().into_owned()));
+            }
+            seen += data.row_count;
+            for values in columns.values_mut() {
+                values.resize(seen, crate::als::NULL_TOKEN.to_string());
+            }
+        }
+        assert_eq!(seen, rows);
+        columns
+    }
+
+    #[test]
+    fn test_streaming_json_matches_non_streaming() {
+        let objects: Vec<String> = (0..5000)
+            .map(|i| {
+                format!(
+                    r#"{{"id": {}, "level": "{}", "user": {{"name": "user{}", "tags": ["a", "b"]}}, "msg": "say \"hi\" [{}]"}}"#,
+                    i,
+                    ["info", "warn", "error"][i % 3],
+                    i % 17,
+                    i % 5
+                )
+            })
+            .collect();
+        let json = format!("[\n  {}\n]\n", objects.join(",\n  "));
+
+        let chunks = compress_json_stream(&json, 700, 100);
+        assert_eq!(chunks.len(), 8);
+        let streamed = expand_json_chunks(&chunks, 5000);
+
+        let expected = crate::convert::json::parse_json(&json).unwrap();
+        assert_eq!(streamed.len(), expected.column_count());
+        for column in &expected.columns {
+            let values: Vec<String> = column.values.iter().map(|v| v.to_string_repr().into_owned()).collect();
+            assert_eq!(streamed[column.name.as_ref()], values, "column {}", column.name);
+        }
+    }
+
+    #[test]
+    fn test_streaming_json_new_keys_add_null_backfilled_columns() {
+        let json = r#"[{"id": 1}, {"id": 2, "b": true}, {"id": 3, "a": "x"}, {"id": 4}]"#;
+        let chunks = compress_json_stream(json, 2, 1);
+        assert_eq!(chunks.len(), 2);
+
+        let parser = AlsParser::new();
+        let first = parser.parse(&chunks[0]).unwrap();
+        assert_eq!(first.schema, vec!["id", "b"]);
+        let second = parser.parse(&chunks[1]).unwrap();
+        assert_eq!(second.schema, vec!["id", "b", "a"]);
+        assert_eq!(
+            parser.expand(&second).unwrap(),
+            vec![vec!["3", "\\0", "x"], vec!["4", "\\0", "\\0"]]
+        );
+    }
+
+    #[test]
+    fn test_streaming_json_empty_inputs() {
+        assert!(compress_json_stream("", 10, 10).is_empty());
+        assert!(compress_json_stream("  [ ]  ", 10, 10).is_empty());
+    }
+
+    #[test]
+    fn test_streaming_json_errors() {
+        for json in [r#"{"id": 1}"#, r#"[{"id": 1}, 2]"#, r#"[{"id": 1} {"id": 2}]"#, r#"[{"id": 1}"#, "[] x"] {
+            let results: Vec<Result<String>> = StreamingCompressor::new(json.as_bytes())
+                .with_dictionary_sample_size(1)
+                .with_json_chunk_size(1)
+                .compress_json_chunks()
+                .collect();
+            assert!(
+                matches!(results.last(), Some(Err(AlsError::JsonParseError(_)))),
+                "{} gave {:?}",
+                json,
+                results
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_separators_skips_escapes() {
+        assert_eq!(count_separators("1>3|a\\|b|c"), 2);
+        assert_eq!(count_separators("a\\\\|b"), 1);
+        assert_eq!(count_separators("1>3"), 0);
+    }
 }
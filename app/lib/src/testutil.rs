@@ -0,0 +1,127 @@
+//! Round-trip test harness shared by the crate's unit tests.
+//!
+//! [`arb_tabular_data`] generates random tables mixing the value kinds the
+//! compressor has to preserve, and [`assert_roundtrip`] pushes a table
+//! through compression, serialization, parsing and expansion and checks that
+//! every cell survives. Tables are generated with patterned columns as well
+//! as noisy ones, so both pattern operators and raw values get exercised.
+
+use proptest::prelude::*;
+
+use crate::als::{AlsParser, AlsSerializer, FormatIndicator};
+use crate::compress::AlsCompressor;
+use crate::config::CompressorConfig;
+use crate::convert::{Column, TabularData, Value};
+
+/// Strings that stress escaping: ALS operators, separators, whitespace,
+/// control characters, multi-byte text and the default sentinel tokens.
+const SPECIAL_STRINGS: &[&str] = &[
+    "a|b", "1>5", "x*3", "a~b", "_0", "#tag", "$dict", "(group)", "a,b", "c:d",
+    "e^f", "g@h", "i!j", "k%l", "m&n", "{brace}", "back\\slash", "two words",
+    " padded ", "line\nbreak", "tab\tbed", "car\rriage", "naïve", "日本語", "-",
+    "\\0", "\\e", "\\\\0",
+];
+
+/// Generate a single string cell.
+fn arb_string() -> impl Strategy<Value = Value<'static>> {
+    prop_oneof![
+        proptest::sample::select(SPECIAL_STRINGS).prop_map(Value::string),
+        "[a-z]{1,8}".prop_map(Value::string_owned),
+        // Zero-padded digits, which read back as numbers unless kept raw
+        "0[0-9]{1,4}".prop_map(Value::string_owned),
+        Just(Value::string("")),
+    ]
+}
+
+/// Generate the values of one column with `rows` rows.
+fn arb_column_values(rows: usize) -> impl Strategy<Value = Vec<Value<'static>>> {
+    let cell = |strategy: BoxedStrategy<Value<'static>>| {
+        proptest::collection::vec(strategy, rows).boxed()
+    };
+    let nullable = |strategy: BoxedStrategy<Value<'static>>| {
+        prop_oneof![4 => strategy, 1 => Just(Value::Null)].boxed()
+    };
+
+    prop_oneof![
+        // Noisy columns of every kind, with nulls mixed in
+        cell(nullable(any::<i64>().prop_map(Value::Integer).boxed())),
+        cell(nullable((-1e6..1e6f64).prop_map(Value::Float).boxed())),
+        cell(nullable(any::<bool>().prop_map(Value::Boolean).boxed())),
+        cell(nullable(arb_string().boxed())),
+        // Patterned columns for ranges, repeats and toggles
+        (any::<i32>(), 1..5i64).prop_map(move |(start, step)| {
+            (0..rows as i64)
+                .map(|i| Value::Integer(start as i64 + i * step))
+                .collect()
+        }),
+        // Zero-padded identifiers with a shared prefix, for the affix detector
+        (0..1000u32, 1..4u32).prop_map(move |(start, step)| {
+            (0..rows as u32)
+                .map(|i| Value::string_owned(format!("SKU-{:04}", start + i * step)))
+                .collect()
+        }),
+        arb_string().prop_map(move |value| vec![value; rows]),
+        (arb_string(), arb_string()).prop_map(move |(a, b)| {
+            (0..rows).map(|i| if i % 2 == 0 { a.clone() } else { b.clone() }).collect()
+        }),
+        // Columns with nothing in them
+        Just(vec![Value::Null; rows]),
+        Just(vec![Value::string(""); rows]),
+    ]
+}
+
+/// Generate random tabular data with 1 to 6 columns and up to 40 rows.
+pub(crate) fn arb_tabular_data() -> impl Strategy<Value = TabularData<'static>> {
+    (1..=6usize, 0..=40usize)
+        .prop_flat_map(|(columns, rows)| {
+            proptest::collection::vec(arb_column_values(rows), columns)
+        })
+        .prop_map(|columns| {
+            let mut data = TabularData::new();
+            for (index, values) in columns.into_iter().enumerate() {
+                data.add_column(Column::new(format!("col{}", index), values));
+            }
+            data
+        })
+}
+
+/// Assert that `data` round-trips through both the ALS and the CTX format.
+///
+/// The ALS pass uses the default compressor, which may still fall back to
+/// CTX for data without patterns; the CTX pass forces the fallback.
+pub(crate) fn assert_roundtrip(data: &TabularData) {
+    assert_roundtrip_with(&AlsCompressor::new(), data);
+
+    let ctx = AlsCompressor::with_config(CompressorConfig::new().with_ctx_fallback_threshold(f64::MAX));
+    let format = assert_roundtrip_with(&ctx, data);
+    if !data.is_empty() {
+        assert_eq!(format, FormatIndicator::Ctx);
+    }
+}
+
+/// Assert that `data` round-trips through `compressor`.
+///
+/// Compresses, serializes, parses and expands the data, then compares
+/// column names and the string form of every cell. Cells are compared
+/// before type inference, which may legitimately read `f` back as `false`.
+/// Returns the format the compressor chose.
+pub(crate) fn assert_roundtrip_with(compressor: &AlsCompressor, data: &TabularData) -> FormatIndicator {
+    let doc = compressor.compress(data).expect("compression failed");
+    let als = AlsSerializer::new().serialize(&doc);
+    let parser = AlsParser::new();
+    let parsed = parser
+        .parse(&als)
+        .unwrap_or_else(|e| panic!("failed to parse {:?}: {}", als, e));
+    let indices: Vec<usize> = (0..parsed.column_count()).collect();
+    let expanded = parser
+        .expand_columns(&parsed, &indices)
+        .unwrap_or_else(|e| panic!("failed to expand {:?}: {}", als, e));
+
+    assert_eq!(parsed.schema, data.column_names(), "column names of {:?}", als);
+    for (column, restored) in data.columns.iter().zip(&expanded) {
+        let tokens = &compressor.config().sentinel_tokens;
+        let original: Vec<_> = column.values.iter().map(|v| v.to_string_repr_with(tokens)).collect();
+        assert_eq!(restored, &original, "column {} of {:?}", column.name, als);
+    }
+    doc.format_indicator
+}
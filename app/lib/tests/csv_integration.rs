@@ -1,6 +1,8 @@
 //! Integration tests for CSV compression pipeline.
 
-use als_compression::{AlsCompressor, AlsParser};
+use als_compression::{
+    AlsCompressor, AlsError, AlsParser, CompressorConfig, ParserConfig, RaggedRowPolicy, NULL_TOKEN,
+};
 
 #[test]
 fn test_csv_compression_basic() {
@@ -225,3 +227,332 @@ fn test_csv_error_handling_malformed() {
     // Should return an error
     assert!(result.is_err());
 }
+
+#[test]
+fn test_csv_round_trip_with_delta_encoding() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    // Nearly-sequential timestamps with occasional jumps
+    let original_csv = "ts\n1700000000\n1700000001\n1700000002\n1700000003\n1700000060\n1700000061\n1700000062\n1700000030";
+
+    let als = compressor.compress_csv(original_csv).unwrap();
+
+    // Should use delta encoding for the timestamp column
+    assert!(als.contains("1700000000^d1,1,1,57,1,1,-32"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_with_timestamp_encoding() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let original_csv = "id,created_at,seen_at\n\
+        1,2023-01-01T00:00:00Z,2023-01-01 08:00:00.125+05:30\n\
+        2,2023-01-01T00:01:00Z,2023-01-01 08:00:07.000+05:30\n\
+        3,2023-01-01T00:02:00Z,2023-01-01 08:01:00.500+05:30\n\
+        4,2023-01-01T00:03:00Z,2023-01-01 08:01:01.500+05:30\n\
+        5,2023-01-01T00:04:00Z,2023-01-01 08:02:30.000+05:30";
+
+    let als = compressor.compress_csv(original_csv).unwrap();
+
+    // Minute-spaced column becomes an epoch range, irregular one a delta
+    assert!(als.contains("@T0Z(1672531200>1672531440:60)"), "{}", als);
+    assert!(als.contains("@S3+05:30(1672560000125^d"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_with_affix_encoding() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let original_csv = "url,file\n\
+        https://example.com/blog/welcome,invoice_march.pdf\n\
+        https://example.com/blog/release-notes,receipt.pdf\n\
+        https://example.com/blog/roadmap,contract_signed.pdf\n\
+        https://example.com/blog/hiring,summary.pdf";
+
+    let als = compressor.compress_csv(original_csv).unwrap();
+
+    // The shared prefix and suffix are stored once
+    assert!(als.contains("{https\\://example.com/blog/}("), "{}", als);
+    assert!(als.contains("{:.pdf}("), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_compress_csv_ragged_row_policy() {
+    let ragged_csv = "id,name\n1,alice\n2\n3,carol,extra";
+
+    let result = AlsCompressor::new().compress_csv(ragged_csv);
+    assert!(matches!(result, Err(AlsError::CsvParseError { line: 3, .. })));
+
+    let config = CompressorConfig::new().with_ragged_row_policy(RaggedRowPolicy::PadNull);
+    let als = AlsCompressor::with_config(config).compress_csv(ragged_csv).unwrap();
+    let rows = AlsParser::new().expand(&AlsParser::new().parse(&als).unwrap()).unwrap();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1][1], NULL_TOKEN);
+    assert_eq!(rows[2], vec!["3", "carol"]);
+}
+
+#[test]
+fn test_csv_round_trip_with_mixed_timestamp_formats() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let original_csv = "ts\n2023-01-01T00:00:00Z\n2023-01-01T00:00:01+00:00\n2023-01-01 00:00:02\n@2023-01-01T00:00:03Z";
+
+    let als = compressor.compress_csv(original_csv).unwrap();
+    assert!(!als.contains("@T"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_with_run_list_encoding() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let mut original_csv = String::from("region");
+    for (city, count) in [("Paris, FR", 10), ("Lyon, FR", 12), ("Nice, FR", 8)] {
+        for _ in 0..count {
+            original_csv.push_str(&format!("\n\"{}\"", city));
+        }
+    }
+
+    let als = compressor.compress_csv(&original_csv).unwrap();
+
+    // Sorted categorical column becomes a single run list
+    assert!(als.contains("(Paris\\,\\ FR*10,Lyon\\,\\ FR*12,Nice\\,\\ FR*8)"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_with_geometric_encoding() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let mut original_csv = String::from("id,size");
+    for i in 0..20 {
+        original_csv.push_str(&format!("\n{},{}", i + 1, 1i64 << (i + 10)));
+    }
+
+    let als = compressor.compress_csv(&original_csv).unwrap();
+    assert!(als.contains("1024^*2#20"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_with_int_dictionary() {
+    use als_compression::Value;
+
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    // Pseudo-random order so no periodic pattern applies
+    let codes = [200, 404, 500];
+    let mut original_csv = String::from("status");
+    let mut seed: u32 = 7;
+    for _ in 0..120 {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+        original_csv.push_str(&format!("\n{}", codes[(seed >> 16) as usize % codes.len()]));
+    }
+
+    let als = compressor.compress_csv(&original_csv).unwrap();
+    assert!(als.contains("%200,404,500#120:"), "{}", als);
+
+    let doc = parser.parse(&als).unwrap();
+    let data = parser.expand_typed(&doc).unwrap();
+    let status = data.get_column(0).unwrap();
+    assert!(status.values.iter().all(|v| matches!(v, Value::Integer(_))));
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_with_checksum() {
+    use als_compression::CompressorConfig;
+
+    let compressor = AlsCompressor::with_config(CompressorConfig::new().with_checksum(true));
+    let parser = AlsParser::new();
+
+    let original_csv = "id,city\n1,Berlin\n2,Paris\n3,Berlin\n4,Rome";
+    let als = compressor.compress_csv(original_csv).unwrap();
+    assert!(als.lines().nth(1).unwrap().starts_with("!crc"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_checksum_detects_flipped_byte() {
+    use als_compression::{AlsError, CompressorConfig};
+
+    let compressor = AlsCompressor::with_config(CompressorConfig::new().with_checksum(true));
+    let parser = AlsParser::new();
+
+    let als = compressor
+        .compress_csv("id,city\n1,Berlin\n2,Paris\n3,Berlin\n4,Rome")
+        .unwrap();
+
+    // Flip one bit in the last byte of the document
+    let mut bytes = als.into_bytes();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0x01;
+    let corrupted = String::from_utf8(bytes).unwrap();
+
+    let err = parser.parse(&corrupted).unwrap_err();
+    assert!(matches!(err, AlsError::ChecksumMismatch { .. }), "{:?}", err);
+}
+
+#[test]
+fn test_csv_without_checksum_still_parses() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let original_csv = "id,city\n1,Berlin\n2,Paris";
+    let als = compressor.compress_csv(original_csv).unwrap();
+    assert!(!als.contains("!crc"));
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_pipe_in_cell() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let original_csv = "path\na|b\n|\nx|\n|y\n1|2\na||b";
+    let als = compressor.compress_csv(original_csv).unwrap();
+    assert!(als.contains("a\\|b"), "{}", als);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv.trim_end(), original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_pipe_in_cells_multi_column() {
+    // A low fallback threshold keeps the ALS operators (dictionary, toggle,
+    // runs) instead of falling back to CTX
+    let compressor =
+        AlsCompressor::with_config(CompressorConfig::new().with_ctx_fallback_threshold(1.0));
+    let parser = AlsParser::new();
+
+    let words = ["alpha|beta", "gamma|", "|delta", "12|34", "-5|6"];
+    let mut original_csv = String::from("id,word,flag,group\n");
+    for i in 0..30 {
+        original_csv.push_str(&format!(
+            "{},{},{},{}\n",
+            i,
+            words[(i * 7) % words.len()],
+            if i % 2 == 0 { "on|1" } else { "off|0" },
+            if i < 15 { "g|1" } else { "g|2" },
+        ));
+    }
+
+    let als = compressor.compress_csv(&original_csv).unwrap();
+    let doc = parser.parse(&als).unwrap();
+    assert_eq!(doc.streams.len(), 4);
+
+    let result_csv = parser.to_csv(&als).unwrap();
+    assert_eq!(result_csv, original_csv);
+}
+
+#[test]
+fn test_csv_round_trip_keeps_decimal_prices() {
+    let original_csv = "sku,price\nA1,19.90\nA2,5.00\nA3,0.10\nA4,1234.50\nA5,19.99\n";
+    // A float column would reformat these prices
+    assert_eq!("19.90".parse::<f64>().unwrap().to_string(), "19.9");
+
+    for compressor in [
+        AlsCompressor::new(),
+        AlsCompressor::with_config(CompressorConfig::new().with_annotate_number_types(true)),
+    ] {
+        let als = compressor.compress_csv(original_csv).unwrap();
+        let result_csv = AlsParser::new().to_csv(&als).unwrap();
+        assert_eq!(result_csv, original_csv, "{}", als);
+    }
+}
+
+#[test]
+fn test_csv_round_trip_keeps_exponents_and_signs() {
+    let original_csv = "tiny,big,signed\n1e-9,2.5E10,+1.5\n2e-9,-3e+2,+7\n1.5e-12,1E5,-2\n";
+    // A float column would expand these numbers
+    assert_eq!(1e-9f64.to_string(), "0.000000001");
+
+    for compressor in [
+        AlsCompressor::new(),
+        AlsCompressor::with_config(CompressorConfig::new().with_annotate_number_types(true)),
+    ] {
+        let als = compressor.compress_csv(original_csv).unwrap();
+        let result_csv = AlsParser::new().to_csv(&als).unwrap();
+        assert_eq!(result_csv, original_csv, "{}", als);
+    }
+}
+
+#[test]
+fn test_csv_plus_sign_integers_are_not_ranges() {
+    let original_csv = "n\n+1\n+2\n+3\n+4\n+5\n";
+    let als = AlsCompressor::new().compress_csv(original_csv).unwrap();
+    assert_eq!(AlsParser::new().to_csv(&als).unwrap(), original_csv, "{}", als);
+}
+
+#[test]
+fn test_csv_epoch_column_uses_delta_and_renders_iso8601() {
+    let mut original_csv = String::from("ts,level\n");
+    let mut ts = 1_672_531_200;
+    for i in 0..40 {
+        // Mostly one-minute steps with an occasional jitter
+        ts += if i % 9 == 0 { 63 } else { 60 };
+        original_csv.push_str(&format!("{},{}\n", ts, ["info", "warn"][i % 2]));
+    }
+
+    let compressor = AlsCompressor::with_config(CompressorConfig::new().with_annotate_number_types(true));
+    let als = compressor.compress_csv(&original_csv).unwrap();
+    assert!(als.contains("#ts:epoch"), "{}", als);
+    assert!(als.contains("1672531263^d"), "{}", als);
+
+    assert_eq!(AlsParser::new().to_csv(&als).unwrap(), original_csv);
+
+    let parser = AlsParser::with_config(ParserConfig::new().with_iso8601_timestamps(true));
+    let iso_csv = parser.to_csv(&als).unwrap();
+    let mut lines = iso_csv.lines().skip(1);
+    assert_eq!(lines.next(), Some("2023-01-01T00:01:03Z,info"));
+    assert_eq!(lines.next(), Some("2023-01-01T00:02:03Z,warn"));
+}
+
+#[test]
+fn test_csv_column_reordering_restores_original_order() {
+    let mut original_csv = String::from("src,id,level,dst\n");
+    let hosts = ["web", "db", "cache", "queue"];
+    for i in 0..40 {
+        original_csv.push_str(&format!("{},{},{},{}\n", hosts[i % 4], i, ["info", "warn"][i % 2], hosts[(i + 1) % 4]));
+    }
+
+    let default_als = AlsCompressor::new().compress_csv(&original_csv).unwrap();
+    assert!(!default_als.contains("!order:"), "{}", default_als);
+
+    let compressor = AlsCompressor::with_config(CompressorConfig::new().with_column_reordering(true));
+    let als = compressor.compress_csv(&original_csv).unwrap();
+    assert!(als.contains("!order:0,3,1,2\n"), "{}", als);
+    assert!(als.contains("#src #dst #id #level"), "{}", als);
+
+    let parser = AlsParser::new();
+    assert_eq!(parser.to_csv(&als).unwrap(), original_csv);
+    assert_eq!(parser.to_json(&als).unwrap(), parser.to_json(&default_als).unwrap());
+}
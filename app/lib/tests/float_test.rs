@@ -504,3 +504,34 @@ fn test_float_pattern_detection() {
         assert_eq!(rows[i][0].parse::<f64>().unwrap(), 2.5);
     }
 }
+
+/// Test that decimal arithmetic sequences use the float range operator.
+///
+/// **Validates: Requirement 18.2, 18.3**
+#[test]
+fn test_float_range_round_trip() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    // `Value::Float(1.0)` is written `1`, which a range expanding at one
+    // fraction digit cannot produce, so the sequence stops at 0.9
+    let values: Vec<f64> = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9];
+    let mut data = TabularData::new();
+    data.add_column(Column::new(
+        "values",
+        values.iter().map(|&v| Value::Float(v)).collect(),
+    ));
+
+    // Compress to ALS
+    let doc = compressor.compress(&data).unwrap();
+    let als_text = als_compression::AlsSerializer::new().serialize(&doc);
+    assert!(als_text.contains("0.1>0.9:0.1"), "{}", als_text);
+
+    // Parse back and verify exact string representations
+    let parsed_doc = parser.parse(&als_text).unwrap();
+    let rows = parser.expand(&parsed_doc).unwrap();
+
+    let expected: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    let actual: Vec<String> = rows.into_iter().map(|row| row[0].clone()).collect();
+    assert_eq!(actual, expected);
+}
@@ -2,7 +2,7 @@
 //!
 //! These tests verify the complete JSON → ALS → JSON round-trip functionality.
 
-use als_compression::{AlsCompressor, AlsParser};
+use als_compression::{AlsCompressor, AlsParser, CompressorConfig, ParserConfig};
 
 #[test]
 fn test_json_to_als_to_json_round_trip() {
@@ -79,6 +79,28 @@ fn test_json_nested_objects_round_trip() {
     assert_eq!(array[0]["user"]["email"], "alice@example.com");
 }
 
+#[test]
+fn test_json_nested_array_of_objects_round_trip() {
+    let original_json = r#"[
+        {"id": 1, "order": {"items": [{"name": "pen", "qty": 2}, {"name": "ink", "qty": 1}]}},
+        {"id": 2, "order": {"items": [{"name": "pad", "qty": 5}, {"name": "pen", "qty": 3}]}}
+    ]"#;
+
+    let compressor = AlsCompressor::new();
+    let als = compressor.compress_json(original_json).unwrap();
+
+    // Array elements are flattened into indexed columns
+    assert!(als.contains("order.items.0.name"));
+    assert!(als.contains("order.items.1.qty"));
+
+    let parser = AlsParser::new();
+    let output_json = parser.to_json(&als).unwrap();
+
+    let output: serde_json::Value = serde_json::from_str(&output_json).unwrap();
+    let original: serde_json::Value = serde_json::from_str(original_json).unwrap();
+    assert_eq!(output, original);
+}
+
 #[test]
 fn test_json_with_nulls_round_trip() {
     let original_json = r#"[
@@ -183,3 +205,98 @@ fn test_json_mixed_types() {
     assert_eq!(array[0]["str"], "hello");
     assert!(array[0]["null"].is_null());
 }
+
+#[test]
+fn test_ndjson_round_trip_with_missing_fields() {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    let original = r#"{"ts": 1700000000, "level": "info", "req": {"path": "/a"}}
+{"ts": 1700000001, "level": "info"}
+
+{"ts": 1700000002, "level": "warn", "req": {"path": "/b"}, "retry": 2}
+"#;
+
+    let als = compressor.compress_ndjson(original).unwrap();
+    let result = parser.to_ndjson(&als).unwrap();
+
+    let original_data = als_compression::convert::json::parse_ndjson(original).unwrap();
+    let result_data = als_compression::convert::json::parse_ndjson(&result).unwrap();
+
+    assert_eq!(original_data.column_names(), result_data.column_names());
+    assert_eq!(result_data.row_count, 3);
+    for (a, b) in original_data.columns.iter().zip(&result_data.columns) {
+        assert_eq!(a.values, b.values, "column {}", a.name);
+    }
+}
+
+#[test]
+fn test_json_float_column_keeps_decimal_point() {
+    let compressor =
+        AlsCompressor::with_config(CompressorConfig::new().with_annotate_number_types(true));
+    let parser = AlsParser::with_config(ParserConfig::new().with_preserve_number_types(true));
+
+    let original = r#"[{"id": 1, "price": 1.0}, {"id": 2, "price": 2.0}, {"id": 3, "price": 3.0}]"#;
+    let als = compressor.compress_json(original).unwrap();
+    assert!(als.contains("#id:int"), "{}", als);
+    assert!(als.contains("#price:float"), "{}", als);
+
+    let json = parser.to_json(&als).unwrap();
+    let output: serde_json::Value = serde_json::from_str(&json).unwrap();
+    for (i, row) in output.as_array().unwrap().iter().enumerate() {
+        assert!(row["id"].is_i64(), "{}", json);
+        assert!(row["price"].is_f64(), "{}", json);
+        assert_eq!(row["price"].as_f64(), Some((i + 1) as f64));
+    }
+    assert!(json.contains("1.0"), "{}", json);
+
+    // Without the parser option whole-number floats come back as integers
+    let json = AlsParser::new().to_json(&als).unwrap();
+    let output: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert!(output[0]["price"].is_i64(), "{}", json);
+}
+
+#[test]
+fn test_json_number_types_not_annotated_by_default() {
+    let als = AlsCompressor::new()
+        .compress_json(r#"[{"price": 1.0}, {"price": 2.0}, {"price": 3.0}]"#)
+        .unwrap();
+    assert!(!als.contains(":float"), "{}", als);
+}
+
+#[test]
+fn test_json_round_trip_keeps_decimal_prices() {
+    let original = r#"[{"price":19.90,"sku":"A1"},{"price":5.00,"sku":"A2"},{"price":0.10,"sku":"A3"}]"#;
+
+    let als = AlsCompressor::with_config(CompressorConfig::new().with_annotate_number_types(true))
+        .compress_json(original)
+        .unwrap();
+    assert!(als.contains("#price:decimal"), "{}", als);
+
+    let parser = AlsParser::with_config(ParserConfig::new().with_preserve_number_types(true));
+    assert_eq!(parser.to_json(&als).unwrap(), original);
+    assert_eq!(AlsParser::new().to_json(&als).unwrap(), original);
+}
+
+#[test]
+fn test_json_round_trip_keeps_exponent_numbers() {
+    let original = r#"[{"a":1e-9},{"a":2.5E+3},{"a":0.5}]"#;
+    let als = AlsCompressor::new().compress_json(original).unwrap();
+    assert!(als.contains("1e-9"), "{}", als);
+
+    assert_eq!(AlsParser::new().to_json(&als).unwrap(), original);
+}
+
+#[test]
+fn test_json_epoch_column_renders_raw_or_iso8601() {
+    let original = r#"[{"id":1,"ts":1700000000},{"id":2,"ts":1700000060},{"id":3,"ts":1700000125},{"id":4,"ts":1700000185}]"#;
+
+    let als = AlsCompressor::new().compress_json(original).unwrap();
+    assert!(als.contains("1700000000^d60,65,60"), "{}", als);
+    assert_eq!(AlsParser::new().to_json(&als).unwrap(), original);
+
+    let parser = AlsParser::with_config(ParserConfig::new().with_iso8601_timestamps(true));
+    let output: serde_json::Value = serde_json::from_str(&parser.to_json(&als).unwrap()).unwrap();
+    assert_eq!(output[0]["ts"], "2023-11-14T22:13:20Z");
+    assert_eq!(output[3]["ts"], "2023-11-14T22:16:25Z");
+}
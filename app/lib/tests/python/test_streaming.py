@@ -0,0 +1,79 @@
+"""Tests for the streaming Python bindings.
+
+Build the module first, then run from app/lib:
+
+    maturin develop --features python
+    python -m unittest discover tests/python
+"""
+
+import io
+import os
+import tempfile
+import unittest
+
+from als_compression import AlsParser, StreamingCompressor, StreamingParser
+
+
+def sample_csv(rows=250):
+    lines = ["id,status,latency"]
+    for i in range(1, rows + 1):
+        status = ["ok", "ok", "error"][i % 3]
+        lines.append(f"{i},{status},{(i * 7) % 50}")
+    return "\n".join(lines) + "\n"
+
+
+class StreamingTest(unittest.TestCase):
+    def test_stream_csv_file_and_reconstruct(self):
+        csv = sample_csv()
+        with tempfile.TemporaryDirectory() as tmp:
+            csv_path = os.path.join(tmp, "data.csv")
+            als_path = os.path.join(tmp, "data.als")
+            with open(csv_path, "w") as f:
+                f.write(csv)
+
+            chunks = list(StreamingCompressor(csv_path, csv_chunk_size=100))
+            self.assertEqual(len(chunks), 3)
+            self.assertTrue(chunks[0].startswith("!v1"))
+            with open(als_path, "w") as f:
+                f.writelines(chunks)
+
+            rows = list(StreamingParser(als_path))
+
+        self.assertEqual(len(rows), 250)
+        self.assertEqual(rows[0], [1, "ok", 7])
+        self.assertEqual(rows[2], [3, "ok", 21])
+        self.assertEqual(rows[-1], [250, "ok", 0])
+
+        rebuilt = "id,status,latency\n" + "".join(
+            ",".join(str(v) for v in row) + "\n" for row in rows
+        )
+        self.assertEqual(rebuilt, csv)
+
+    def test_stream_file_like_objects(self):
+        csv = sample_csv(20)
+        als = "".join(StreamingCompressor(io.StringIO(csv), csv_chunk_size=8))
+
+        rows = list(StreamingParser(io.BytesIO(als.encode())))
+        self.assertEqual(len(rows), 20)
+        self.assertEqual(rows[4], [5, "error", 35])
+
+        # A single-batch stream is also a plain ALS document
+        als = "".join(StreamingCompressor(io.BytesIO(csv.encode()), csv_chunk_size=100))
+        self.assertEqual(AlsParser().to_csv(als), csv)
+
+    def test_stream_non_ascii_text(self):
+        csv = "name\n" + "\n".join(["Zoë", "Łukasz", "東京"] * 5) + "\n"
+        als = "".join(StreamingCompressor(io.StringIO(csv)))
+        rows = list(StreamingParser(io.StringIO(als)))
+        self.assertEqual([row[0] for row in rows[:3]], ["Zoë", "Łukasz", "東京"])
+
+    def test_errors_map_to_python_exceptions(self):
+        with self.assertRaises(OSError):
+            StreamingCompressor("/nonexistent/data.csv")
+
+        with self.assertRaises(ValueError):
+            list(StreamingCompressor(io.StringIO("a,b\n1,2\n3\n")))
+
+
+if __name__ == "__main__":
+    unittest.main()
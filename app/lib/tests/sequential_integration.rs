@@ -0,0 +1,34 @@
+//! Integration test for sequential compression.
+//!
+//! Kept in its own test binary so that no other test starts threads while
+//! the thread count is being observed.
+
+use als_compression::{AlsCompressor, CompressorConfig};
+
+/// Number of threads in this process.
+#[cfg(target_os = "linux")]
+fn thread_count() -> usize {
+    std::fs::read_dir("/proc/self/task").unwrap().count()
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_compress_sequential_never_spawns_threads() {
+    let mut csv = String::from("id,status,score\n");
+    for i in 0..500 {
+        let status = if i % 3 == 0 { "open" } else { "closed" };
+        csv.push_str(&format!("{},{},{}\n", i, status, i * i % 17));
+    }
+    let data = als_compression::convert::csv::parse_csv(&csv).unwrap();
+
+    // Settings that would compress in parallel on a 4-thread pool
+    let compressor = AlsCompressor::with_config(
+        CompressorConfig::new().with_parallelism(4).with_parallel_threshold(0),
+    );
+    assert_eq!(compressor.would_use_parallel(&data), cfg!(feature = "parallel"));
+
+    let before = thread_count();
+    let doc = compressor.compress_sequential(&data).unwrap();
+    assert_eq!(thread_count(), before);
+    assert_eq!(doc.row_count(), 500);
+}
@@ -28,24 +28,92 @@ id,name,value
     // Should have multiple chunks due to small chunk size
     assert!(!compressed_chunks.is_empty());
 
-    // Each chunk is independently parseable
-    // In real usage, chunks would be processed as they arrive
+    // The chunks form one stream: the header once, then one line per batch
+    let stream = compressed_chunks.concat();
+    let cursor = Cursor::new(stream.as_bytes());
+    let mut parser = StreamingParser::new(cursor);
+
     let mut total_row_count = 0;
-    for chunk in &compressed_chunks {
-        let cursor = Cursor::new(chunk.as_bytes());
-        let mut parser = StreamingParser::new(cursor);
-
-        for row_result in parser.parse_rows() {
-            let row = row_result.unwrap();
-            assert_eq!(row.len(), 3); // Three columns
-            total_row_count += 1;
-        }
+    for row_result in parser.parse_rows() {
+        let row = row_result.unwrap();
+        assert_eq!(row.len(), 3); // Three columns
+        total_row_count += 1;
     }
 
     // Should have parsed all rows (5 rows total)
     assert_eq!(total_row_count, 5);
 }
 
+#[test]
+fn test_streaming_multi_batch_round_trip_matches_original_rows() {
+    let cities = ["Berlin", "Paris", "Madrid", "Rome"];
+    let mut csv_data = String::from("id,city,status\n");
+    let mut expected = Vec::new();
+    for i in 1..=95 {
+        let city = cities[(i / 7) % cities.len()];
+        let status = if i % 3 == 0 { "inactive" } else { "active" };
+        csv_data.push_str(&format!("{},{},{}\n", i, city, status));
+        expected.push(vec![i.to_string(), city.to_string(), status.to_string()]);
+    }
+
+    let cursor = Cursor::new(csv_data.as_bytes());
+    let mut compressor = StreamingCompressor::new(cursor)
+        .with_csv_chunk_size(10)
+        .with_dictionary_sample_size(20);
+    let chunks: Vec<String> = compressor
+        .compress_csv_chunks()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(chunks.len(), 10);
+
+    let stream = chunks.concat();
+    let cursor = Cursor::new(stream.as_bytes());
+    let mut parser = StreamingParser::new(cursor);
+    let rows: Vec<Vec<String>> = parser
+        .parse_rows()
+        .map(|row| row.unwrap().iter().map(|v| v.to_string_repr().into_owned()).collect())
+        .collect();
+
+    assert_eq!(rows, expected);
+}
+
+#[test]
+fn test_streaming_value_outside_sampled_dictionary() {
+    let mut csv_data = String::from("id,region\n");
+    for i in 1..=8 {
+        csv_data.push_str(&format!("{},north-america\n", i));
+    }
+    for i in 9..=12 {
+        csv_data.push_str(&format!("{},south-america\n", i));
+    }
+
+    let cursor = Cursor::new(csv_data.as_bytes());
+    let mut compressor = StreamingCompressor::new(cursor)
+        .with_csv_chunk_size(4)
+        .with_dictionary_sample_size(4);
+    let chunks: Vec<String> = compressor
+        .compress_csv_chunks()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // The sampled value is shared, the late value stays raw
+    assert!(chunks[0].contains("$default:north-america"));
+    assert!(!chunks[0].contains("south-america"));
+    assert!(chunks[2].contains("south-america"));
+
+    let stream = chunks.concat();
+    let cursor = Cursor::new(stream.as_bytes());
+    let mut parser = StreamingParser::new(cursor);
+    let regions: Vec<String> = parser
+        .parse_rows()
+        .map(|row| row.unwrap()[1].to_string_repr().into_owned())
+        .collect();
+
+    assert_eq!(regions.len(), 12);
+    assert!(regions[..8].iter().all(|r| r == "north-america"));
+    assert!(regions[8..].iter().all(|r| r == "south-america"));
+}
+
 #[test]
 fn test_streaming_large_csv() {
     // Generate a larger CSV
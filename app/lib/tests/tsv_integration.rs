@@ -0,0 +1,59 @@
+//! Integration tests for TSV compression pipeline.
+
+use als_compression::convert::tsv::parse_tsv;
+use als_compression::{AlsCompressor, AlsParser};
+
+fn assert_tsv_round_trip(original_tsv: &str) {
+    let compressor = AlsCompressor::new();
+    let parser = AlsParser::new();
+
+    // Compress to ALS and decompress back to TSV
+    let als = compressor.compress_tsv(original_tsv).unwrap();
+    let result_tsv = parser.to_tsv(&als).unwrap();
+
+    // Parse both TSVs to compare
+    let original_data = parse_tsv(original_tsv).unwrap();
+    let result_data = parse_tsv(&result_tsv).unwrap();
+
+    assert_eq!(original_data.column_names(), result_data.column_names());
+    assert_eq!(original_data.row_count, result_data.row_count);
+    for (original, result) in original_data.columns.iter().zip(&result_data.columns) {
+        assert_eq!(original.values, result.values, "column {}", original.name);
+    }
+}
+
+#[test]
+fn test_tsv_compression_basic() {
+    let compressor = AlsCompressor::new();
+    let als = compressor.compress_tsv("id\tname\n1\tAlice\n2\tBob\n3\tCharlie").unwrap();
+
+    assert!(als.contains("#id"));
+    assert!(als.contains("#name"));
+}
+
+#[test]
+fn test_tsv_round_trip_with_patterns() {
+    assert_tsv_round_trip("id\tstatus\n1\tok\n2\tok\n3\tok\n4\tok\n5\tok\n");
+}
+
+#[test]
+fn test_tsv_round_trip_empty_fields() {
+    assert_tsv_round_trip("a\tb\tc\n1\t\tx\n2\t5\t\n\t6\tz\n");
+}
+
+#[test]
+fn test_tsv_round_trip_embedded_tabs_and_newlines() {
+    assert_tsv_round_trip("id\tnote\n1\t\"a\tb\"\n2\t\"line1\nline2\"\n3\tplain\n");
+}
+
+#[test]
+fn test_tsv_round_trip_headers_with_spaces() {
+    assert_tsv_round_trip("first name\tlast name\nAda\tLovelace\nAlan\tTuring\n");
+}
+
+#[test]
+fn test_tsv_output_uses_tabs() {
+    let parser = AlsParser::new();
+    let tsv = parser.to_tsv("#id #name\n1>2|Alice Bob").unwrap();
+    assert_eq!(tsv, "id\tname\n1\tAlice\n2\tBob\n");
+}